@@ -1,11 +1,12 @@
 use gpui::{
-    px, App, AppContext, Context, Entity, FocusHandle, Focusable, IntoElement, ParentElement,
-    Render, Styled, Window,
+    App, AppContext, Context, Entity, FocusHandle, Focusable, IntoElement, ParentElement, Render,
+    Styled, Window, px,
 };
 use gpui_component::{
+    ActiveTheme, IconName, Sizable as _, StyledExt,
     avatar::{Avatar, AvatarGroup},
     dock::PanelControl,
-    v_flex, ActiveTheme, IconName, Sizable as _, StyledExt,
+    v_flex,
 };
 
 use crate::section;
@@ -1,14 +1,25 @@
+use std::{ops::Range, time::Duration};
+
+use chrono::{NaiveDate, NaiveDateTime};
 use gpui::{
     App, AppContext, Context, Entity, FocusHandle, Focusable, Hsla, IntoElement, ParentElement,
-    Render, SharedString, Styled, Window, div, linear_color_stop, linear_gradient,
-    prelude::FluentBuilder, px,
+    Render, SharedString, Styled, Subscription, Timer, Window, div, linear_color_stop,
+    linear_gradient, prelude::FluentBuilder, px,
 };
 use gpui_component::{
-    ActiveTheme, StyledExt,
-    chart::{AreaChart, BarChart, LineChart, PieChart},
+    ActiveTheme, Sizable, StyledExt,
+    button::Button,
+    chart::{
+        AreaChart, AxisScale, BarChart, Binning, BoxPlotChart, ChartEvent, ChartLegend,
+        ChartPalette, ChartState, ChartStream, HistogramChart, LineChart, PieChart,
+        PieLabelPosition, SeriesStyle,
+    },
     divider::Divider,
     dock::PanelControl,
-    h_flex, v_flex,
+    format::format_currency,
+    h_flex,
+    plot::Marker,
+    v_flex,
 };
 use serde::Deserialize;
 
@@ -36,6 +47,15 @@ pub struct ChartStory {
     focus_handle: FocusHandle,
     daily_devices: Vec<DailyDevice>,
     monthly_devices: Vec<MonthlyDevice>,
+    /// The growing requests in every 10 minutes of a day.
+    requests: Vec<(NaiveDateTime, f64)>,
+    stream: ChartStream<f64>,
+    chart_state: Entity<ChartState>,
+    pie_state: Entity<ChartState>,
+    donut_state: Entity<ChartState>,
+    clicked_slice: Option<usize>,
+    brushed: Option<Range<usize>>,
+    _subscriptions: Vec<Subscription>,
 }
 
 impl ChartStory {
@@ -48,9 +68,63 @@ impl ChartStory {
         ))
         .unwrap();
 
+        // Push a random walk sample every 50ms, like a monitoring metric.
+        cx.spawn(async move |this, cx| {
+            let mut value = 50.;
+            loop {
+                Timer::after(Duration::from_millis(50)).await;
+                value = (value + rand::random::<f64>() * 10. - 5.).clamp(0., 100.);
+                if this
+                    .update(cx, |this, cx| {
+                        this.stream.push(value);
+                        cx.notify();
+                    })
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        })
+        .detach();
+
+        let chart_state = cx.new(|_| ChartState::new().brush_zoom(true));
+        let donut_state = cx.new(|_| ChartState::new());
+        let _subscriptions = vec![
+            cx.subscribe(&donut_state, |this, _, event: &ChartEvent, cx| {
+                if let ChartEvent::Click(ix) = event {
+                    this.clicked_slice = Some(*ix);
+                    cx.notify();
+                }
+            }),
+            cx.subscribe(&chart_state, |this, _, event: &ChartEvent, cx| {
+                if let ChartEvent::Brush(range) = event {
+                    this.brushed = Some(range.clone());
+                    cx.notify();
+                }
+            }),
+        ];
+
+        let start = NaiveDate::from_ymd_opt(2024, 6, 1)
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .unwrap_or_default();
+        let requests = (0..144)
+            .map(|i| {
+                let value = 1.05_f64.powi(i) * (1. + (i as f64 / 5.).sin() * 0.3);
+                (start + chrono::Duration::minutes(10 * i as i64), value)
+            })
+            .collect();
+
         Self {
             daily_devices,
             monthly_devices,
+            requests,
+            chart_state,
+            pie_state: cx.new(|_| ChartState::new()),
+            donut_state,
+            clicked_slice: None,
+            brushed: None,
+            _subscriptions,
+            stream: ChartStream::new(2_000).duration(Duration::from_secs(30)),
             focus_handle: cx.focus_handle(),
         }
     }
@@ -130,10 +204,55 @@ fn chart_container(
 impl Render for ChartStory {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let color = cx.theme().chart_3;
+        let device_groups: Vec<(SharedString, Vec<f64>)> = vec![
+            (
+                "Desktop".into(),
+                self.daily_devices.iter().map(|d| d.desktop).collect(),
+            ),
+            (
+                "Mobile".into(),
+                self.daily_devices.iter().map(|d| d.mobile).collect(),
+            ),
+        ];
         v_flex()
             .size_full()
             .gap_y_4()
             .bg(cx.theme().background)
+            .child(
+                v_flex()
+                    .h(px(300.))
+                    .gap_2()
+                    .child(
+                        h_flex().justify_end().child(
+                            Button::new("toggle-stream")
+                                .small()
+                                .label(if self.stream.is_paused() {
+                                    "Resume"
+                                } else {
+                                    "Pause"
+                                })
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    if this.stream.is_paused() {
+                                        this.stream.resume();
+                                    } else {
+                                        this.stream.pause();
+                                    }
+                                    cx.notify();
+                                })),
+                        ),
+                    )
+                    .child(
+                        div().flex_1().child(
+                            AreaChart::stream(&self.stream)
+                                .y(|d| *d)
+                                .stroke(cx.theme().chart_1)
+                                .fill(cx.theme().chart_1.opacity(0.2))
+                                .linear()
+                                .y_axis(),
+                        ),
+                    ),
+            )
+            .child(Divider::horizontal())
             .child(
                 div().h(px(400.)).child(chart_container(
                     "Area Chart - Stacked",
@@ -153,31 +272,129 @@ impl Render for ChartStory {
                             linear_color_stop(cx.theme().chart_2.opacity(0.4), 1.),
                             linear_color_stop(cx.theme().background.opacity(0.3), 0.),
                         ))
-                        .tick_margin(8),
+                        .tick_margin(8)
+                        .state(&self.chart_state),
                     false,
                     cx,
                 )),
             )
+            .child(
+                ChartLegend::new("area-legend", &self.chart_state)
+                    .item("Desktop", cx.theme().chart_1)
+                    .item("Mobile", cx.theme().chart_2),
+            )
+            .child(
+                h_flex()
+                    .gap_x_4()
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(
+                        "Hover to show the synced crosshair, scroll to zoom, drag to select a range.",
+                    )
+                    .when_some(self.brushed.clone(), |this, range| {
+                        this.child(format!(
+                            "Selected: {} - {}",
+                            self.daily_devices[range.start].date,
+                            self.daily_devices[range.end - 1].date
+                        ))
+                    })
+                    .child(
+                        Button::new("reset-zoom")
+                            .small()
+                            .label("Reset Zoom")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.brushed = None;
+                                this.chart_state
+                                    .update(cx, |state, cx| state.reset_zoom(cx));
+                            })),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .gap_x_4()
+                    .h(px(300.))
+                    .child(chart_container(
+                        "Line Chart - Synced",
+                        LineChart::new(self.daily_devices.clone())
+                            .x(|d| d.date.clone())
+                            .y(|d| d.desktop)
+                            .tick_margin(16)
+                            .state(&self.chart_state),
+                        false,
+                        cx,
+                    ))
+                    .child(chart_container(
+                        "Bar Chart - Synced",
+                        BarChart::new(self.daily_devices.clone())
+                            .x(|d| d.date.clone())
+                            .y(|d| d.mobile)
+                            .tick_margin(16)
+                            .state(&self.chart_state),
+                        false,
+                        cx,
+                    )),
+            )
             .child(
                 h_flex()
                     .gap_x_8()
                     .h(px(450.))
                     .child(chart_container(
                         "Pie Chart",
-                        PieChart::new(self.monthly_devices.clone())
-                            .value(|d| d.desktop as f32)
-                            .outer_radius(100.)
-                            .color(move |d| d.color(color)),
+                        v_flex()
+                            .size_full()
+                            .gap_2()
+                            .child(
+                                div().flex_1().child(
+                                    PieChart::new(self.monthly_devices.clone())
+                                        .value(|d| d.desktop as f32)
+                                        .outer_radius(100.)
+                                        .color(move |d| d.color(color))
+                                        .state(&self.pie_state),
+                                ),
+                            )
+                            .child(
+                                ChartLegend::new("pie-legend", &self.pie_state)
+                                    .items(
+                                        self.monthly_devices
+                                            .iter()
+                                            .map(|d| (d.month.clone(), d.color(color))),
+                                    )
+                                    .max_items(4),
+                            ),
                         true,
                         cx,
                     ))
                     .child(chart_container(
                         "Pie Chart - Donut",
-                        PieChart::new(self.monthly_devices.clone())
-                            .value(|d| d.desktop as f32)
-                            .inner_radius(60.)
-                            .outer_radius_fn(|d| 100. - d.index as f32 * 4.)
-                            .color(move |d| d.color(color)),
+                        v_flex()
+                            .size_full()
+                            .gap_2()
+                            .child(
+                                div().flex_1().child(
+                                    PieChart::new(self.monthly_devices.clone())
+                                        .value(|d| d.desktop as f32)
+                                        .inner_radius(60.)
+                                        .outer_radius_fn(|d| 100. - d.index as f32 * 4.)
+                                        .color(move |d| d.color(color))
+                                        .label(|d| d.month.clone())
+                                        .label_position(PieLabelPosition::Outside)
+                                        .center_label(|total| format!("{}", total as i64))
+                                        .center_title("Desktop")
+                                        .state(&self.donut_state),
+                                ),
+                            )
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(match self.clicked_slice {
+                                        Some(ix) => format!(
+                                            "Clicked: {}",
+                                            self.monthly_devices[ix].month
+                                        ),
+                                        None => "Click a slice to drill down.".to_string(),
+                                    }),
+                            ),
                         true,
                         cx,
                     ))
@@ -194,6 +411,41 @@ impl Render for ChartStory {
                     )),
             )
             .child(Divider::horizontal())
+            .child(
+                h_flex()
+                    .gap_x_8()
+                    .h(px(400.))
+                    .child(chart_container(
+                        "Histogram",
+                        HistogramChart::new(self.daily_devices.clone())
+                            .value(|d| d.desktop)
+                            .binning(Binning::FreedmanDiaconis)
+                            .y_axis(),
+                        false,
+                        cx,
+                    ))
+                    .child(chart_container(
+                        "Box Plot",
+                        BoxPlotChart::new(device_groups.clone())
+                            .x(|(name, _)| name.clone())
+                            .values(|(_, values)| values.clone())
+                            .palette(ChartPalette::categorical())
+                            .y_axis(),
+                        false,
+                        cx,
+                    ))
+                    .child(chart_container(
+                        "Violin Plot",
+                        BoxPlotChart::new(device_groups)
+                            .x(|(name, _)| name.clone())
+                            .values(|(_, values)| values.clone())
+                            .palette(ChartPalette::categorical())
+                            .violin(true),
+                        false,
+                        cx,
+                    )),
+            )
+            .child(Divider::horizontal())
             .child(
                 h_flex()
                     .gap_x_8()
@@ -266,6 +518,31 @@ impl Render for ChartStory {
                         cx,
                     )),
             )
+            .child(
+                h_flex()
+                    .gap_x_4()
+                    .h(px(300.))
+                    .child(chart_container(
+                        "Line Chart - Time Axis",
+                        LineChart::new(self.requests.clone())
+                            .x_time(|d| d.0)
+                            .y(|d| d.1)
+                            .y_axis_format(|value| format_currency(value, "USD")),
+                        false,
+                        cx,
+                    ))
+                    .child(chart_container(
+                        "Line Chart - Log Scale",
+                        LineChart::new(self.requests.clone())
+                            .x_time(|d| d.0)
+                            .x_axis_format(|time| time.format("%H:%M").to_string())
+                            .y(|d| d.1)
+                            .y_scale(AxisScale::Log)
+                            .y_axis(),
+                        false,
+                        cx,
+                    )),
+            )
             .child(Divider::horizontal())
             .child(
                 h_flex()
@@ -311,5 +588,45 @@ impl Render for ChartStory {
                         cx,
                     )),
             )
+            .child(Divider::horizontal())
+            .child(
+                h_flex()
+                    .gap_x_4()
+                    .h(px(400.))
+                    .child(chart_container(
+                        "Pie Chart - Colorblind Safe",
+                        PieChart::new(self.monthly_devices.clone())
+                            .value(|d| d.desktop as f32)
+                            .outer_radius(100.)
+                            .palette(ChartPalette::categorical().colorblind_safe())
+                            .label(|d| d.month.clone()),
+                        true,
+                        cx,
+                    ))
+                    .child(chart_container(
+                        "Bar Chart - Sequential",
+                        BarChart::new(self.monthly_devices.clone())
+                            .x(|d| d.month.clone())
+                            .y(|d| d.desktop)
+                            .palette(ChartPalette::sequential().steps(6)),
+                        false,
+                        cx,
+                    ))
+                    .child(chart_container(
+                        "Line Chart - Series Style",
+                        LineChart::new(self.monthly_devices.clone())
+                            .x(|d| d.month.clone())
+                            .y(|d| d.desktop)
+                            .series_style(
+                                SeriesStyle::new()
+                                    .color(cx.theme().magenta)
+                                    .width(px(1.5))
+                                    .dash(&[px(6.), px(3.)])
+                                    .marker(Marker::Diamond),
+                            ),
+                        false,
+                        cx,
+                    )),
+            )
     }
 }
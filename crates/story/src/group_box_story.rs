@@ -1,9 +1,10 @@
 use gpui::{
-    relative, App, AppContext, Context, Entity, Focusable, IntoElement, ParentElement, Render,
-    StyleRefinement, Styled, Window,
+    App, AppContext, Context, Entity, Focusable, IntoElement, ParentElement, Render,
+    StyleRefinement, Styled, Window, relative,
 };
 
 use gpui_component::{
+    ActiveTheme as _, StyledExt,
     button::{Button, ButtonVariants},
     checkbox::Checkbox,
     group_box::GroupBox,
@@ -11,7 +12,7 @@ use gpui_component::{
     radio::{Radio, RadioGroup},
     switch::Switch,
     text::TextView,
-    v_flex, ActiveTheme as _, StyledExt,
+    v_flex,
 };
 
 use crate::section;
@@ -1,8 +1,8 @@
 use gpui::{
-    px, App, AppContext, Context, Entity, Focusable, IntoElement, ParentElement, Render, Styled,
-    Window,
+    App, AppContext, Context, Entity, Focusable, IntoElement, ParentElement, Render, Styled,
+    Window, px,
 };
-use gpui_component::{indicator::Indicator, v_flex, ActiveTheme as _, IconName, Sizable};
+use gpui_component::{ActiveTheme as _, IconName, Sizable, indicator::Indicator, v_flex};
 
 use crate::section;
 
@@ -112,7 +112,7 @@ pub use webview_story::WebViewStory;
 pub use welcome_story::WelcomeStory;
 
 use gpui_component::{
-    ActiveTheme, ContextModal, IconName, Root, TitleBar,
+    ActiveTheme, ContextModal, IconName, Root,
     button::Button,
     dock::{Panel, PanelControl, PanelEvent, PanelInfo, PanelState, TitleStyle, register_panel},
     group_box::GroupBox,
@@ -121,6 +121,7 @@ use gpui_component::{
     notification::Notification,
     scroll::ScrollbarShow,
     v_flex,
+    window_chrome::WindowChrome,
 };
 use tracing_subscriber::{layer::SubscriberExt as _, util::SubscriberInitExt as _};
 
@@ -202,21 +203,17 @@ pub fn create_new_window_with_size<F, E>(
     }
     let window_bounds = Bounds::centered(None, window_size, cx);
     let title = SharedString::from(title.to_string());
+    let chrome_options = WindowChrome::window_options(cx);
 
     cx.spawn(async move |cx| {
         let options = WindowOptions {
             window_bounds: Some(WindowBounds::Windowed(window_bounds)),
-            titlebar: Some(TitleBar::title_bar_options()),
             window_min_size: Some(gpui::Size {
                 width: px(480.),
                 height: px(320.),
             }),
             kind: WindowKind::Normal,
-            #[cfg(target_os = "linux")]
-            window_background: gpui::WindowBackgroundAppearance::Transparent,
-            #[cfg(target_os = "linux")]
-            window_decorations: Some(gpui::WindowDecorations::Client),
-            ..Default::default()
+            ..chrome_options
         };
 
         let window = cx
@@ -1,14 +1,15 @@
 use gpui::{
-    div, App, AppContext as _, Context, Entity, Focusable, InteractiveElement, IntoElement,
-    ParentElement as _, Render, Styled, Subscription, Window,
+    App, AppContext as _, Context, Entity, Focusable, InteractiveElement, IntoElement,
+    ParentElement as _, Render, Styled, Subscription, Window, div,
 };
 use regex::Regex;
 
 use crate::section;
 use gpui_component::{
+    ActiveTheme, Disableable, IconName, Sizable,
     button::{Button, ButtonVariants},
     input::{InputEvent, InputState, MaskPattern, NumberInput, NumberInputEvent, StepAction},
-    v_flex, ActiveTheme, Disableable, IconName, Sizable,
+    v_flex,
 };
 
 pub fn init(_: &mut App) {}
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use gpui::{
     Action, App, AppContext, ClickEvent, Context, Entity, Focusable, IntoElement, ParentElement,
-    Render, SharedString, Styled, Window, div, prelude::FluentBuilder, relative,
+    Render, SharedString, Styled, Subscription, Window, div, prelude::FluentBuilder, px, relative,
 };
 
 use gpui_component::{
@@ -12,6 +12,7 @@ use gpui_component::{
     divider::Divider,
     h_flex,
     menu::DropdownMenu,
+    router::{Route, Router},
     sidebar::{
         Sidebar, SidebarFooter, SidebarGroup, SidebarHeader, SidebarMenu, SidebarMenuItem,
         SidebarToggleButton,
@@ -27,12 +28,12 @@ pub struct SelectCompany(SharedString);
 
 pub struct SidebarStory {
     active_items: HashMap<Item, bool>,
-    last_active_item: Item,
-    active_subitem: Option<SubItem>,
+    router: Entity<Router>,
     collapsed: bool,
     side: Side,
     focus_handle: gpui::FocusHandle,
     checked: bool,
+    _subscriptions: Vec<Subscription>,
 }
 
 impl SidebarStory {
@@ -44,17 +45,34 @@ impl SidebarStory {
         let mut active_items = HashMap::new();
         active_items.insert(Item::Playground, true);
 
+        let router = cx.new(|_| Router::new(Item::Playground.route()));
+        let _subscriptions = vec![cx.observe(&router, |_, _, cx| cx.notify())];
+
         Self {
             active_items,
-            last_active_item: Item::Playground,
-            active_subitem: None,
+            router,
             collapsed: false,
             side: Side::Left,
             focus_handle: cx.focus_handle(),
             checked: false,
+            _subscriptions,
         }
     }
 
+    /// Returns the item and the sub item of the current route.
+    fn current_item(&self, cx: &App) -> (Item, Option<SubItem>) {
+        let router = self.router.read(cx);
+        let item = Item::all()
+            .into_iter()
+            .find(|item| router.is_active(&item.route()))
+            .unwrap_or(Item::Playground);
+        let subitem = item
+            .items()
+            .into_iter()
+            .find(|subitem| router.is_active(&subitem.route(&item)));
+        (item, subitem)
+    }
+
     fn render_content(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         v_flex().child(
             h_flex().gap_2().child(
@@ -100,6 +118,22 @@ enum SubItem {
 }
 
 impl Item {
+    pub fn all() -> [Item; 7] {
+        [
+            Self::Playground,
+            Self::Models,
+            Self::Documentation,
+            Self::Settings,
+            Self::DesignEngineering,
+            Self::SalesAndMarketing,
+            Self::Travel,
+        ]
+    }
+
+    pub fn route(&self) -> Route {
+        Route::new(self.label().to_lowercase().replace(' ', "-"))
+    }
+
     pub fn label(&self) -> &'static str {
         match self {
             Self::Playground => "Playground",
@@ -135,8 +169,6 @@ impl Item {
             } else {
                 this.active_items.insert(item, true);
             }
-
-            this.last_active_item = item;
             cx.notify();
         }
     }
@@ -182,24 +214,12 @@ impl SubItem {
         }
     }
 
-    pub fn handler(
-        &self,
-        item: &Item,
-    ) -> impl Fn(&mut SidebarStory, &ClickEvent, &mut Window, &mut Context<SidebarStory>) + 'static
-    {
-        let item = *item;
-        let subitem = *self;
-        move |this, _, _, cx| {
-            println!(
-                "Clicked on item: {}, child: {}",
-                item.label(),
-                subitem.label()
-            );
-            this.active_items.insert(item, true);
-            this.last_active_item = item;
-            this.active_subitem = Some(subitem);
-            cx.notify();
-        }
+    pub fn route(&self, item: &Item) -> Route {
+        Route::new(format!(
+            "{}/{}",
+            item.route().as_str(),
+            self.label().to_lowercase().replace(' ', "-")
+        ))
     }
 }
 
@@ -242,6 +262,7 @@ impl Render for SidebarStory {
                 Item::Travel,
             ],
         ];
+        let (current_item, current_subitem) = self.current_item(cx);
 
         h_flex()
             .rounded(cx.theme().radius)
@@ -252,6 +273,11 @@ impl Render for SidebarStory {
             .child(
                 Sidebar::new(self.side)
                     .collapsed(self.collapsed)
+                    .overlay_below(px(800.))
+                    .on_toggle(cx.listener(|this, collapsed: &bool, _, cx| {
+                        this.collapsed = *collapsed;
+                        cx.notify();
+                    }))
                     .header(
                         SidebarHeader::new()
                             .w_full()
@@ -317,7 +343,7 @@ impl Render for SidebarStory {
                                     .children(item.items().into_iter().enumerate().map(
                                         |(ix, sub_item)| {
                                             SidebarMenuItem::new(sub_item.label())
-                                                .active(self.active_subitem == Some(sub_item))
+                                                .route(&self.router, sub_item.route(item))
                                                 .when(ix == 0, |this| {
                                                     this.suffix(
                                                         Switch::new("switch")
@@ -330,7 +356,6 @@ impl Render for SidebarStory {
                                                             )),
                                                     )
                                                 })
-                                                .on_click(cx.listener(sub_item.handler(&item)))
                                         },
                                     ))
                                     .on_click(cx.listener(item.handler()))
@@ -342,7 +367,7 @@ impl Render for SidebarStory {
                             groups[1].iter().enumerate().map(|(ix, item)| {
                                 SidebarMenuItem::new(item.label())
                                     .icon(item.icon())
-                                    .active(self.last_active_item == *item)
+                                    .route(&self.router, item.route())
                                     .when(ix == 0, |this| {
                                         this.suffix(
                                             Badge::new().dot().count(1).child(
@@ -383,30 +408,21 @@ impl Render for SidebarStory {
                             .child(
                                 SidebarToggleButton::left()
                                     .side(self.side)
-                                    .collapsed(self.collapsed)
-                                    .on_click(cx.listener(|this, _, _, cx| {
-                                        this.collapsed = !this.collapsed;
-                                        cx.notify();
-                                    })),
+                                    .collapsed(self.collapsed),
                             )
                             .child(Divider::vertical().h_4())
                             .child(
                                 Breadcrumb::new()
                                     .child("Breadcrumb")
-                                    .child(BreadcrumbItem::new("Home").on_click(cx.listener(
-                                        |this, _, _, cx| {
-                                            this.last_active_item = Item::Playground;
-                                            cx.notify();
-                                        },
-                                    )))
                                     .child(
-                                        BreadcrumbItem::new(self.last_active_item.label())
-                                            .on_click(cx.listener(|this, _, _, cx| {
-                                                this.active_subitem = None;
-                                                cx.notify();
-                                            })),
+                                        BreadcrumbItem::new("Home")
+                                            .route(&self.router, Item::Playground.route()),
+                                    )
+                                    .child(
+                                        BreadcrumbItem::new(current_item.label())
+                                            .route(&self.router, current_item.route()),
                                     )
-                                    .when_some(self.active_subitem, |this, subitem| {
+                                    .when_some(current_subitem, |this, subitem| {
                                         this.child(BreadcrumbItem::new(subitem.label()))
                                     }),
                             ),
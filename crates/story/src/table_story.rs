@@ -1,4 +1,5 @@
 use std::{
+    collections::{HashMap, HashSet},
     ops::Range,
     sync::LazyLock,
     time::{self, Duration},
@@ -6,13 +7,14 @@ use std::{
 
 use fake::Fake;
 use gpui::{
-    Action, AnyElement, App, AppContext, ClickEvent, Context, Div, Entity, Focusable,
-    InteractiveElement, IntoElement, ParentElement, Render, SharedString, Stateful,
+    Action, AnyElement, App, AppContext, ClickEvent, ClipboardItem, Context, Div, Entity,
+    Focusable, InteractiveElement, IntoElement, ParentElement, Render, SharedString, Stateful,
     StatefulInteractiveElement, Styled, Subscription, Task, TextAlign, Timer, Window, div,
     prelude::FluentBuilder as _,
 };
 use gpui_component::{
-    ActiveTheme as _, Selectable, Sizable as _, Size, StyleSized as _, StyledExt,
+    ActiveTheme as _, ContextModal as _, Disableable as _, Selectable, Sizable as _, Size,
+    StyleSized as _, StyledExt,
     button::Button,
     checkbox::Checkbox,
     h_flex,
@@ -20,7 +22,10 @@ use gpui_component::{
     input::{Input, InputEvent, InputState},
     label::Label,
     menu::{DropdownMenu, PopupMenu},
-    table::{Column, ColumnFixed, ColumnSort, Table, TableDelegate, TableEvent, TableState},
+    table::{
+        CellValue, Column, ColumnFilter, ColumnFixed, ExportFormat, ExportScope, FilterValue,
+        Table, TableColState, TableDelegate, TableEvent, TableState,
+    },
     v_flex,
 };
 use serde::{Deserialize, Serialize};
@@ -175,6 +180,9 @@ fn random_stocks(size: usize) -> Vec<Stock> {
 
 struct StockTableDelegate {
     stocks: Vec<Stock>,
+    /// The stocks hidden by the filters.
+    filtered_stocks: Vec<Stock>,
+    filters: HashMap<usize, FilterValue>,
     columns: Vec<Column>,
     size: Size,
     loading: bool,
@@ -182,6 +190,10 @@ struct StockTableDelegate {
     eof: bool,
     visible_rows: Range<usize>,
     visible_cols: Range<usize>,
+    /// The editor of the name cell in editing.
+    name_editor: Option<Entity<InputState>>,
+    /// The keys of the columns hidden by the header menu.
+    hidden_columns: HashSet<SharedString>,
 
     _load_task: Task<()>,
 }
@@ -191,32 +203,42 @@ impl StockTableDelegate {
         Self {
             size: Size::default(),
             stocks: random_stocks(size),
+            filtered_stocks: vec![],
+            filters: HashMap::new(),
             columns: vec![
                 Column::new("id", "ID")
                     .width(60.)
                     .fixed(ColumnFixed::Left)
                     .resizable(false),
                 Column::new("market", "Market")
-                    .width(60.)
+                    .width(80.)
+                    .filter(ColumnFilter::Set(vec!["US".into(), "HK".into()]))
                     .fixed(ColumnFixed::Left)
                     .resizable(false),
                 Column::new("name", "Name")
                     .width(180.)
+                    .min_width(120.)
+                    .max_width(300.)
                     .fixed(ColumnFixed::Left),
                 Column::new("symbol", "Symbol")
                     .width(100.)
                     .fixed(ColumnFixed::Left)
-                    .sortable(),
-                Column::new("price", "Price").sortable().text_right().p_0(),
+                    .sortable()
+                    .filter(ColumnFilter::Text),
+                Column::new("price", "Price")
+                    .sortable()
+                    .filter(ColumnFilter::Range)
+                    .text_right()
+                    .p_0(),
                 Column::new("change", "Chg").sortable().text_right().p_0(),
                 Column::new("change_percent", "Chg%")
                     .sortable()
                     .text_right()
                     .p_0(),
                 Column::new("volume", "Volume").p_0(),
-                Column::new("turnover", "Turnover").p_0(),
-                Column::new("market_cap", "Market Cap").p_0(),
-                Column::new("ttm", "TTM").p_0(),
+                Column::new("turnover", "Turnover").p_0().priority(2),
+                Column::new("market_cap", "Market Cap").p_0().priority(3),
+                Column::new("ttm", "TTM").p_0().priority(1),
                 Column::new("five_mins_ranking", "5m Ranking")
                     .text_right()
                     .p_0(),
@@ -256,24 +278,43 @@ impl StockTableDelegate {
                 Column::new("day_10_ranking", "10d Ranking"),
                 Column::new("day_30_ranking", "30d Ranking"),
                 Column::new("day_120_ranking", "120d Ranking"),
-                Column::new("day_250_ranking", "250d Ranking"),
+                Column::new("day_250_ranking", "250d Ranking").fixed_right(),
             ],
             loading: false,
             full_loading: false,
             eof: false,
             visible_cols: Range::default(),
             visible_rows: Range::default(),
+            name_editor: None,
+            hidden_columns: HashSet::new(),
             _load_task: Task::ready(()),
         }
     }
 
     fn update_stocks(&mut self, size: usize) {
         self.stocks = random_stocks(size);
+        self.filtered_stocks.clear();
+        self.filter_stocks();
         self.eof = size <= 50;
         self.loading = false;
         self.full_loading = false;
     }
 
+    /// Move the stocks not matched by the filters to `filtered_stocks`, and back if matched.
+    fn filter_stocks(&mut self) {
+        let (stocks, filtered_stocks) = self
+            .stocks
+            .drain(..)
+            .chain(self.filtered_stocks.drain(..))
+            .partition(|stock| {
+                self.filters.iter().all(|(col_ix, filter)| {
+                    filter.matches(&stock_value(stock, &self.columns[*col_ix].key))
+                })
+            });
+        self.stocks = stocks;
+        self.filtered_stocks = filtered_stocks;
+    }
+
     fn render_percent(&self, col: &Column, val: f64, cx: &mut App) -> AnyElement {
         let right_num = ((val - val.floor()) * 1000.).floor() as i32;
 
@@ -324,6 +365,18 @@ impl StockTableDelegate {
     }
 }
 
+fn stock_value(stock: &Stock, key: &str) -> CellValue {
+    match key {
+        "id" => stock.id.into(),
+        "market" => stock.counter.market.clone().into(),
+        "symbol" => stock.counter.symbol_code().into(),
+        "price" => stock.price.into(),
+        "change" => stock.change.into(),
+        "change_percent" => stock.change_percent.into(),
+        _ => CellValue::Null,
+    }
+}
+
 impl TableDelegate for StockTableDelegate {
     fn columns_count(&self, _: &App) -> usize {
         self.columns.len()
@@ -337,6 +390,14 @@ impl TableDelegate for StockTableDelegate {
         &self.columns[col_ix]
     }
 
+    fn col_group(&self, col_ix: usize, _: &App) -> Option<SharedString> {
+        match self.columns[col_ix].key.as_ref() {
+            "bid" | "bid_volume" | "ask" | "ask_volume" => Some("Quote".into()),
+            "open" | "prev_close" | "high" | "low" => Some("Daily".into()),
+            _ => None,
+        }
+    }
+
     fn render_th(&self, col_ix: usize, _: &mut Window, _: &mut App) -> impl IntoElement {
         let col = self.columns.get(col_ix).unwrap();
 
@@ -483,6 +544,110 @@ impl TableDelegate for StockTableDelegate {
         }
     }
 
+    fn can_edit_cell(&self, _: usize, col_ix: usize, _: &App) -> bool {
+        self.columns[col_ix].key == "name"
+    }
+
+    fn can_expand_row(&self, _: usize, _: &App) -> bool {
+        true
+    }
+
+    fn render_row_detail(&self, row_ix: usize, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let stock = &self.stocks[row_ix];
+        let items = [
+            ("Open", stock.open),
+            ("Prev Close", stock.prev_close),
+            ("High", stock.high),
+            ("Low", stock.low),
+            ("Bid", stock.bid),
+            ("Ask", stock.ask),
+            ("Turnover Rate", stock.turnover_rate),
+            ("Amplitude", stock.amplitude),
+        ];
+
+        // The number of the items is different to show the detail panels in different heights.
+        v_flex()
+            .py_2()
+            .px_4()
+            .gap_1()
+            .text_sm()
+            .child(Label::new(stock.counter.name.clone()).font_semibold())
+            .children(
+                items
+                    .into_iter()
+                    .take(2 + stock.id % 7)
+                    .map(|(label, value)| {
+                        h_flex()
+                            .gap_4()
+                            .child(
+                                div()
+                                    .w_32()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(label),
+                            )
+                            .child(format!("{:.3}", value))
+                    }),
+            )
+    }
+
+    fn begin_edit_cell(
+        &mut self,
+        row_ix: usize,
+        _: usize,
+        window: &mut Window,
+        cx: &mut Context<TableState<Self>>,
+    ) {
+        let name = self.stocks[row_ix].counter.name.clone();
+        let editor = cx.new(|cx| InputState::new(window, cx).default_value(name));
+        editor.focus_handle(cx).focus(window);
+        self.name_editor = Some(editor);
+    }
+
+    fn render_editing_td(
+        &self,
+        row_ix: usize,
+        col_ix: usize,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> impl IntoElement {
+        match &self.name_editor {
+            Some(editor) => Input::new(editor).xsmall().into_any_element(),
+            None => self
+                .render_td(row_ix, col_ix, window, cx)
+                .into_any_element(),
+        }
+    }
+
+    fn commit_edit_cell(
+        &mut self,
+        row_ix: usize,
+        _: usize,
+        _: &mut Window,
+        cx: &mut Context<TableState<Self>>,
+    ) -> bool {
+        let Some(editor) = self.name_editor.take() else {
+            return true;
+        };
+        let name = editor.read(cx).value();
+        if name.trim().is_empty() {
+            self.name_editor = Some(editor);
+            return false;
+        }
+
+        self.stocks[row_ix].counter.name = name;
+        true
+    }
+
+    fn cancel_edit_cell(
+        &mut self,
+        _: usize,
+        _: usize,
+        _: &mut Window,
+        _: &mut Context<TableState<Self>>,
+    ) {
+        self.name_editor = None;
+    }
+
     fn move_column(
         &mut self,
         col_ix: usize,
@@ -494,41 +659,88 @@ impl TableDelegate for StockTableDelegate {
         self.columns.insert(to_ix, col);
     }
 
-    fn perform_sort(
+    fn can_move_row(&self, _: usize, _: &App) -> bool {
+        true
+    }
+
+    fn move_row(
+        &mut self,
+        row_ix: usize,
+        to_ix: usize,
+        _: &mut Window,
+        _: &mut Context<TableState<Self>>,
+    ) {
+        let stock = self.stocks.remove(row_ix);
+        self.stocks.insert(to_ix, stock);
+    }
+
+    fn col_visible(&self, col_ix: usize, _: &App) -> bool {
+        !self.hidden_columns.contains(&self.columns[col_ix].key)
+    }
+
+    fn set_col_visible(
         &mut self,
         col_ix: usize,
-        sort: ColumnSort,
+        visible: bool,
         _: &mut Window,
         _: &mut Context<TableState<Self>>,
     ) {
-        if let Some(col) = self.columns.get_mut(col_ix) {
-            match col.key.as_ref() {
-                "id" => self.stocks.sort_by(|a, b| match sort {
-                    ColumnSort::Descending => b.id.cmp(&a.id),
-                    _ => a.id.cmp(&b.id),
-                }),
-                "symbol" => self.stocks.sort_by(|a, b| match sort {
-                    ColumnSort::Descending => b.counter.symbol.cmp(&a.counter.symbol),
-                    _ => a.id.cmp(&b.id),
-                }),
-                "change" | "change_percent" => self.stocks.sort_by(|a, b| match sort {
-                    ColumnSort::Descending => b
-                        .change
-                        .partial_cmp(&a.change)
-                        .unwrap_or(std::cmp::Ordering::Equal),
-                    _ => a.id.cmp(&b.id),
-                }),
-                _ => {}
-            }
+        let key = self.columns[col_ix].key.clone();
+        if visible {
+            self.hidden_columns.remove(&key);
+        } else {
+            self.hidden_columns.insert(key);
+        }
+    }
+
+    fn row_id(&self, row_ix: usize, _: &App) -> Option<RowId> {
+        self.stocks.get(row_ix).map(|stock| stock.id.into())
+    }
+
+    fn cell_value(&self, row_ix: usize, col_ix: usize, _: &App) -> CellValue {
+        let (Some(stock), Some(col)) = (self.stocks.get(row_ix), self.columns.get(col_ix)) else {
+            return CellValue::Null;
+        };
+
+        stock_value(stock, &col.key)
+    }
+
+    fn apply_filter(
+        &mut self,
+        col_ix: usize,
+        filter: Option<FilterValue>,
+        _: &mut Window,
+        _: &mut Context<TableState<Self>>,
+    ) {
+        match filter {
+            Some(filter) => self.filters.insert(col_ix, filter),
+            None => self.filters.remove(&col_ix),
+        };
+        self.filter_stocks();
+    }
+
+    fn render_tfoot_td(&self, col_ix: usize, _: &mut Window, _: &mut App) -> impl IntoElement {
+        match self.columns[col_ix].key.as_ref() {
+            "id" => "Total".to_string(),
+            "name" => format!("{} stocks", self.stocks.len()),
+            _ => String::new(),
         }
     }
 
+    fn reorder_rows(&mut self, order: &[usize], _: &mut Window, _: &mut Context<TableState<Self>>) {
+        self.stocks = order.iter().map(|ix| self.stocks[*ix].clone()).collect();
+    }
+
     fn loading(&self, _: &App) -> bool {
         self.full_loading
     }
 
-    fn is_eof(&self, _: &App) -> bool {
-        return !self.loading && !self.eof;
+    fn has_no_more_rows(&self, _: &App) -> bool {
+        self.eof
+    }
+
+    fn loading_more(&self, _: &App) -> bool {
+        self.loading
     }
 
     fn load_more_threshold(&self) -> usize {
@@ -575,8 +787,11 @@ pub struct TableStory {
     table: Entity<TableState<StockTableDelegate>>,
     num_stocks_input: Entity<InputState>,
     stripe: bool,
+    footer: bool,
     refresh_data: bool,
     size: Size,
+    /// The saved layout of the columns.
+    col_state: Option<TableColState>,
 
     _subscriptions: Vec<Subscription>,
     _load_task: Task<()>,
@@ -622,7 +837,11 @@ impl TableStory {
         });
 
         let delegate = StockTableDelegate::new(5000);
-        let table = cx.new(|cx| TableState::new(delegate, window, cx));
+        let table = cx.new(|cx| {
+            TableState::new(delegate, window, cx)
+                .preserve_scroll_position(true)
+                .row_drag_handle(true)
+        });
 
         let _subscriptions = vec![
             cx.subscribe_in(&table, window, Self::on_table_event),
@@ -640,7 +859,12 @@ impl TableStory {
                     }
 
                     this.table.update(cx, |table, _| {
-                        table.delegate_mut().stocks.iter_mut().enumerate().for_each(
+                        // Only update the visible stocks, to keep scrolling smooth with 100k rows.
+                        let delegate = table.delegate_mut();
+                        let visible_rows = delegate.visible_rows.clone();
+                        let end = visible_rows.end.min(delegate.stocks.len());
+                        let start = visible_rows.start.min(end);
+                        delegate.stocks[start..end].iter_mut().enumerate().for_each(
                             |(i, stock)| {
                                 let n = (3..10).fake::<usize>();
                                 // update 30% of the stocks
@@ -660,8 +884,10 @@ impl TableStory {
             table,
             num_stocks_input,
             stripe: false,
+            footer: false,
             refresh_data: false,
             size: Size::default(),
+            col_state: None,
             _subscriptions,
             _load_task,
         }
@@ -715,6 +941,13 @@ impl TableStory {
         });
     }
 
+    fn toggle_row_order(&mut self, checked: &bool, _: &mut Window, cx: &mut Context<Self>) {
+        self.table.update(cx, |table, cx| {
+            table.row_movable = *checked;
+            cx.notify();
+        });
+    }
+
     fn toggle_col_sort(&mut self, checked: &bool, _: &mut Window, cx: &mut Context<Self>) {
         self.table.update(cx, |table, cx| {
             table.sortable = *checked;
@@ -736,11 +969,23 @@ impl TableStory {
         });
     }
 
+    fn toggle_cell_selection(&mut self, checked: &bool, _: &mut Window, cx: &mut Context<Self>) {
+        self.table.update(cx, |table, cx| {
+            table.cell_selectable = *checked;
+            table.clear_selection(cx);
+        });
+    }
+
     fn toggle_stripe(&mut self, checked: &bool, _: &mut Window, cx: &mut Context<Self>) {
         self.stripe = *checked;
         cx.notify();
     }
 
+    fn toggle_footer(&mut self, checked: &bool, _: &mut Window, cx: &mut Context<Self>) {
+        self.footer = *checked;
+        cx.notify();
+    }
+
     fn on_change_size(&mut self, a: &ChangeSize, _: &mut Window, cx: &mut Context<Self>) {
         self.size = a.0;
         cx.notify();
@@ -768,6 +1013,28 @@ impl TableStory {
             TableEvent::MoveColumn(origin_idx, target_idx) => {
                 println!("Move col index: {} -> {}", origin_idx, target_idx);
             }
+            TableEvent::SortChanged(ix, sort) => println!("Sort col: {} {:?}", ix, sort),
+            TableEvent::FilterChanged(ix, filter) => println!("Filter col: {} {:?}", ix, filter),
+            TableEvent::CellEdited { row, col } => println!("Edited cell: {} {}", row, col),
+            TableEvent::RowExpanded { row, expanded } => {
+                println!("Row expanded: {} {}", row, expanded)
+            }
+            TableEvent::TreeExpanded { row, expanded } => {
+                println!("Tree expanded: {} {}", row, expanded)
+            }
+            TableEvent::PageChanged(page) => println!("Page changed: {}", page),
+            TableEvent::LoadMore => println!("Load more"),
+            TableEvent::VisibleRangeChanged(_) => {}
+            TableEvent::ActiveCellChanged { row, col } => {
+                println!("Active cell: {} {}", row, col)
+            }
+            TableEvent::ActivateRow(ix) => println!("Activate row: {}", ix),
+            TableEvent::MoveRow(origin_idx, target_idx) => {
+                println!("Move row index: {} -> {}", origin_idx, target_idx);
+            }
+            TableEvent::ColumnVisibilityChanged { col, visible } => {
+                println!("Column visibility: {} {}", col, visible)
+            }
         }
     }
 }
@@ -807,6 +1074,12 @@ impl Render for TableStory {
                             .selected(table.col_movable)
                             .on_click(cx.listener(Self::toggle_col_order)),
                     )
+                    .child(
+                        Checkbox::new("row-order")
+                            .label("Row Order")
+                            .selected(table.row_movable)
+                            .on_click(cx.listener(Self::toggle_row_order)),
+                    )
                     .child(
                         Checkbox::new("col-sort")
                             .label("Sortable")
@@ -819,6 +1092,12 @@ impl Render for TableStory {
                             .selected(table.col_selectable)
                             .on_click(cx.listener(Self::toggle_col_selection)),
                     )
+                    .child(
+                        Checkbox::new("cell-selection")
+                            .label("Cell Selectable")
+                            .selected(table.cell_selectable)
+                            .on_click(cx.listener(Self::toggle_cell_selection)),
+                    )
                     .child(
                         Checkbox::new("fixed")
                             .label("Column Fixed")
@@ -831,6 +1110,12 @@ impl Render for TableStory {
                             .selected(self.stripe)
                             .on_click(cx.listener(Self::toggle_stripe)),
                     )
+                    .child(
+                        Checkbox::new("footer")
+                            .label("Footer")
+                            .selected(self.footer)
+                            .on_click(cx.listener(Self::toggle_footer)),
+                    )
                     .child(
                         Checkbox::new("loading")
                             .label("Loading")
@@ -901,6 +1186,54 @@ impl Render for TableStory {
                                     table.scroll_to_row(table.delegate().rows_count(cx) - 1, cx);
                                 })
                             })),
+                    )
+                    .child(
+                        Button::new("export-csv")
+                            .outline()
+                            .small()
+                            .child("Copy as CSV")
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                let csv = this.table.read(cx).export(ExportFormat::Csv, cx);
+                                cx.write_to_clipboard(ClipboardItem::new_string(csv));
+                                window.push_notification("Copied the table as CSV.", cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("export-svg")
+                            .outline()
+                            .small()
+                            .child("Copy as SVG")
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                let svg = this.table.update(cx, |table, cx| {
+                                    table.export_svg(ExportScope::Viewport, cx)
+                                });
+                                cx.write_to_clipboard(ClipboardItem::new_string(svg));
+                                window.push_notification("Copied the table as SVG.", cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("save-layout")
+                            .outline()
+                            .small()
+                            .child("Save Layout")
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.col_state = Some(this.table.read(cx).col_state());
+                                window.push_notification("Saved the layout of the columns.", cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("restore-layout")
+                            .outline()
+                            .small()
+                            .child("Restore Layout")
+                            .disabled(self.col_state.is_none())
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                if let Some(col_state) = this.col_state.clone() {
+                                    this.table.update(cx, |table, cx| {
+                                        table.set_col_state(&col_state, window, cx)
+                                    });
+                                }
+                            })),
                     ), // .child(
                        //     Button::new("scroll-first-col")
                        //         .child("Scroll to First Column")
@@ -962,7 +1295,8 @@ impl Render for TableStory {
             .child(
                 Table::new(&self.table)
                     .with_size(self.size)
-                    .stripe(self.stripe),
+                    .stripe(self.stripe)
+                    .footer(self.footer),
             )
     }
 }
@@ -7,6 +7,7 @@ use gpui::{
 };
 
 use crate::{
+    dismissal::{dismissed, Dismissals},
     h_flex,
     text::{Text, TextViewStyle},
     ActiveTheme as _, Icon, IconName, Sizable, Size, StyledExt,
@@ -68,6 +69,7 @@ pub struct Alert {
     banner: bool,
     on_close: Option<Rc<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>>,
     visible: bool,
+    dismiss_id: Option<SharedString>,
 }
 
 impl Alert {
@@ -84,6 +86,7 @@ impl Alert {
             banner: false,
             visible: true,
             on_close: None,
+            dismiss_id: None,
         }
     }
 
@@ -156,6 +159,14 @@ impl Alert {
         self.on_close = Some(Rc::new(on_close));
         self
     }
+
+    /// Set the id to remember the dismissal across restarts, see [`Dismissals`].
+    ///
+    /// The alert shows the Close icon, and is hidden once closed.
+    pub fn dismiss_id(mut self, id: impl Into<SharedString>) -> Self {
+        self.dismiss_id = Some(id.into());
+        self
+    }
 }
 
 impl Sizable for Alert {
@@ -173,10 +184,14 @@ impl Styled for Alert {
 
 impl RenderOnce for Alert {
     fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
-        if !self.visible {
+        if !self.visible || self.dismiss_id.as_ref().is_some_and(|id| dismissed(id, cx)) {
             return Empty.into_any_element();
         }
 
+        let dismiss_id = self.dismiss_id;
+        let on_close = self.on_close;
+        let closable = on_close.is_some() || dismiss_id.is_some();
+
         let (radius, padding_x, padding_y, gap) = match self.size {
             Size::XSmall => (cx.theme().radius, px(12.), px(6.), px(6.)),
             Size::Small => (cx.theme().radius, px(12.), px(8.), px(6.)),
@@ -232,7 +247,7 @@ impl RenderOnce for Alert {
                             ),
                     ),
             )
-            .when_some(self.on_close, |this, on_close| {
+            .when(closable, |this| {
                 this.child(
                     div()
                         .id("close")
@@ -241,7 +256,13 @@ impl RenderOnce for Alert {
                         .hover(|this| this.bg(color.opacity(0.1)))
                         .active(|this| this.bg(color.opacity(0.2)))
                         .on_click(move |ev, window, cx| {
-                            on_close(ev, window, cx);
+                            if let Some(id) = dismiss_id.clone() {
+                                _ = Dismissals::dismiss(id, cx);
+                                window.refresh();
+                            }
+                            if let Some(on_close) = &on_close {
+                                on_close(ev, window, cx);
+                            }
                         })
                         .child(
                             Icon::new(IconName::Close)
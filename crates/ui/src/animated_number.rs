@@ -0,0 +1,242 @@
+use std::{
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use gpui::{
+    div, relative, App, ElementId, IntoElement, ParentElement, RenderOnce, SharedString,
+    StyleRefinement, Styled, Window,
+};
+
+use crate::{
+    animation::{cubic_bezier, MotionSettings},
+    h_flex, StyledExt as _,
+};
+
+/// The animation of the [`AnimatedNumber`] when the value changes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AnimatedNumberMode {
+    /// Count from the previous value to the new value.
+    #[default]
+    Tween,
+    /// Roll the changed digits like an odometer.
+    Odometer,
+}
+
+struct AnimatedNumberState {
+    from: f64,
+    to: f64,
+    started_at: Option<Instant>,
+}
+
+impl AnimatedNumberState {
+    /// Returns the eased progress of the animation.
+    fn progress(&self, duration: Duration, now: Instant) -> f32 {
+        let Some(started_at) = self.started_at else {
+            return 1.;
+        };
+        if duration.is_zero() {
+            return 1.;
+        }
+
+        let t = (now.duration_since(started_at).as_secs_f32() / duration.as_secs_f32()).min(1.);
+        cubic_bezier(0.25, 0.1, 0.25, 1.)(t)
+    }
+
+    fn value_at(&self, progress: f32) -> f64 {
+        self.from + (self.to - self.from) * progress as f64
+    }
+}
+
+/// Returns the position of the rolling digit, in range `0.0..10.0`.
+///
+/// The digit rolls forward from `from` to `to` if increasing, otherwise backward.
+fn roll_position(from: u32, to: u32, increasing: bool, progress: f32) -> f32 {
+    let distance = if increasing {
+        (to + 10 - from) % 10
+    } else {
+        (from + 10 - to) % 10
+    } as f32;
+    let offset = distance * progress;
+    let position = if increasing {
+        from as f32 + offset
+    } else {
+        from as f32 - offset
+    };
+    position.rem_euclid(10.)
+}
+
+/// Align the previous text to the right of the new text, by the characters.
+fn align_right(from: &str, to: &str) -> Vec<Option<char>> {
+    let from = from.chars().collect::<Vec<_>>();
+    let len = to.chars().count();
+    (0..len)
+        .map(|ix| {
+            (ix + from.len())
+                .checked_sub(len)
+                .and_then(|ix| from.get(ix).copied())
+        })
+        .collect()
+}
+
+/// A number that animates between the values, for dashboards and counters.
+///
+/// ```ignore
+/// AnimatedNumber::new("revenue", revenue)
+///     .odometer()
+///     .format(|value| format!("${:.2}", value).into())
+///     .text_2xl()
+/// ```
+#[derive(IntoElement)]
+pub struct AnimatedNumber {
+    id: ElementId,
+    value: f64,
+    duration: Duration,
+    mode: AnimatedNumberMode,
+    formatter: Rc<dyn Fn(f64) -> SharedString>,
+    style: StyleRefinement,
+}
+
+impl AnimatedNumber {
+    /// Create a new animated number, the `id` keeps the previous value to animate from.
+    pub fn new(id: impl Into<ElementId>, value: impl Into<f64>) -> Self {
+        Self {
+            id: id.into(),
+            value: value.into(),
+            duration: Duration::from_millis(600),
+            mode: AnimatedNumberMode::default(),
+            formatter: Rc::new(|value| format!("{:.0}", value).into()),
+            style: StyleRefinement::default(),
+        }
+    }
+
+    /// Set the duration of the animation, default: 600ms
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Set the animation mode, default: [`AnimatedNumberMode::Tween`]
+    pub fn mode(mut self, mode: AnimatedNumberMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Roll the changed digits like an odometer.
+    pub fn odometer(self) -> Self {
+        self.mode(AnimatedNumberMode::Odometer)
+    }
+
+    /// Set the number of decimal places, default: 0
+    pub fn decimals(self, decimals: usize) -> Self {
+        self.format(move |value| format!("{:.*}", decimals, value).into())
+    }
+
+    /// Set the function to format the value.
+    pub fn format(mut self, formatter: impl Fn(f64) -> SharedString + 'static) -> Self {
+        self.formatter = Rc::new(formatter);
+        self
+    }
+}
+
+impl Styled for AnimatedNumber {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for AnimatedNumber {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let value = self.value;
+        let state = window.use_keyed_state(self.id.clone(), cx, |_, _| AnimatedNumberState {
+            from: value,
+            to: value,
+            started_at: None,
+        });
+
+        let now = Instant::now();
+        let duration = MotionSettings::global(cx).motion_duration(self.duration);
+        state.update(cx, |state, _| {
+            if state.to != value {
+                // Start from the current value, if it was still animating.
+                state.from = state.value_at(state.progress(duration, now));
+                state.to = value;
+                state.started_at = Some(now);
+            }
+        });
+
+        let state = state.read(cx);
+        let progress = state.progress(duration, now);
+        if progress < 1. {
+            window.request_animation_frame();
+        }
+
+        let base = h_flex().refine_style(&self.style);
+        match self.mode {
+            AnimatedNumberMode::Tween => base.child((self.formatter)(state.value_at(progress))),
+            AnimatedNumberMode::Odometer => {
+                let from = (self.formatter)(state.from);
+                let to = (self.formatter)(state.to);
+                let increasing = state.to >= state.from;
+
+                base.children(align_right(&from, &to).into_iter().zip(to.chars()).map(
+                    |(from, to)| {
+                        let (Some(from), Some(to_digit)) =
+                            (from.and_then(|c| c.to_digit(10)), to.to_digit(10))
+                        else {
+                            return div().child(to.to_string());
+                        };
+                        if from == to_digit || progress >= 1. {
+                            return div().child(to.to_string());
+                        }
+
+                        let position = roll_position(from, to_digit, increasing, progress);
+                        div()
+                            .relative()
+                            .overflow_hidden()
+                            // The placeholder for the size of the digit.
+                            .child(div().invisible().child(to.to_string()))
+                            .children((0..=10).map(|digit| {
+                                div()
+                                    .absolute()
+                                    .left_0()
+                                    .right_0()
+                                    .h_full()
+                                    .flex()
+                                    .justify_center()
+                                    .top(relative(digit as f32 - position))
+                                    .child((digit % 10).to_string())
+                            }))
+                    },
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{align_right, roll_position};
+
+    #[test]
+    fn test_roll_position() {
+        assert_eq!(roll_position(2, 5, true, 0.), 2.);
+        assert_eq!(roll_position(2, 5, true, 0.5), 3.5);
+        assert_eq!(roll_position(2, 5, true, 1.), 5.);
+        // Roll forward over 9.
+        assert_eq!(roll_position(8, 1, true, 0.5), 9.5);
+        assert_eq!(roll_position(8, 1, true, 1.), 1.);
+        // Roll backward over 0.
+        assert_eq!(roll_position(1, 8, false, 0.5), 9.5);
+        assert_eq!(roll_position(5, 2, false, 1.), 2.);
+    }
+
+    #[test]
+    fn test_align_right() {
+        assert_eq!(
+            align_right("99", "1,00"),
+            vec![None, None, Some('9'), Some('9')]
+        );
+        assert_eq!(align_right("123", "45"), vec![Some('2'), Some('3')]);
+    }
+}
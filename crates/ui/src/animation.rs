@@ -1,3 +1,7 @@
+use std::time::Duration;
+
+use gpui::{Animation, App, Global};
+
 /// A cubic bezier function like CSS `cubic-bezier`.
 ///
 /// Builder:
@@ -17,3 +21,200 @@ pub fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32) -> impl Fn(f32) -> f32 {
         y
     }
 }
+
+/// The global preference of the animations of the components, e.g. the notifications, modals,
+/// drawers, switches, charts and animated numbers.
+///
+/// It's detected from the accessibility settings of the OS on [`init`](crate::init) by the
+/// [`MotionSettings::detect`] if not set yet, use [`MotionSettings::set_global`] to change it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum MotionSettings {
+    /// No animations, the components are shown in the final state immediately.
+    Off,
+    /// No moving, scaling or looping animations, only the fades are kept.
+    Reduced,
+    /// All the animations.
+    #[default]
+    Full,
+}
+
+impl Global for MotionSettings {}
+
+impl MotionSettings {
+    /// Returns the global motion settings.
+    pub fn global(cx: &App) -> Self {
+        cx.try_global::<Self>().copied().unwrap_or_default()
+    }
+
+    /// Set the global motion settings, the windows are refreshed.
+    pub fn set_global(self, cx: &mut App) {
+        cx.set_global(self);
+        cx.refresh_windows();
+    }
+
+    /// Returns true if all the animations are enabled.
+    pub fn is_full(&self) -> bool {
+        *self == Self::Full
+    }
+
+    /// Returns true if the animations are disabled.
+    pub fn is_off(&self) -> bool {
+        *self == Self::Off
+    }
+
+    /// Returns the `duration` of a fade, zero if the animations are off.
+    pub fn duration(&self, duration: Duration) -> Duration {
+        if self.is_off() {
+            Duration::ZERO
+        } else {
+            duration
+        }
+    }
+
+    /// Returns the `duration` of a moving animation, zero if the motion is reduced or off.
+    pub fn motion_duration(&self, duration: Duration) -> Duration {
+        if self.is_full() {
+            duration
+        } else {
+            Duration::ZERO
+        }
+    }
+
+    /// Returns the `animation`, or an animation that ends immediately if the animations are off.
+    pub fn animation(&self, animation: Animation) -> Animation {
+        if self.is_off() {
+            Animation::new(Duration::from_millis(1)).with_easing(|_| 1.)
+        } else {
+            animation
+        }
+    }
+
+    /// Returns the `delta` of a moving animation, the end (`1.0`) if the motion is reduced or off.
+    pub fn motion_delta(&self, delta: f32) -> f32 {
+        if self.is_full() {
+            delta
+        } else {
+            1.
+        }
+    }
+
+    /// Detect the motion settings from the accessibility settings of the OS, `None` if unknown.
+    ///
+    /// It's called on [`init`](crate::init), and reads the settings without a subprocess:
+    ///
+    /// - macOS: the "Reduce motion" of the Accessibility Display settings.
+    /// - Linux: the `gtk-enable-animations` of the GTK `settings.ini`, the `enable-animations`
+    ///   of GNOME is stored in the gsettings instead, so it's `None` on most GNOME desktops.
+    /// - Windows: the "Animation effects" of the Accessibility Visual effects settings.
+    pub fn detect() -> Option<Self> {
+        #[cfg(target_os = "macos")]
+        {
+            use objc2_app_kit::NSWorkspace;
+
+            let reduce_motion =
+                unsafe { NSWorkspace::sharedWorkspace().accessibilityDisplayShouldReduceMotion() };
+            Some(if reduce_motion {
+                Self::Reduced
+            } else {
+                Self::Full
+            })
+        }
+        #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+        {
+            let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+                .map(std::path::PathBuf::from)
+                .or_else(|| {
+                    std::env::var_os("HOME")
+                        .map(|home| std::path::PathBuf::from(home).join(".config"))
+                })?;
+            ["gtk-4.0", "gtk-3.0"].iter().find_map(|dir| {
+                let settings =
+                    std::fs::read_to_string(config_dir.join(dir).join("settings.ini")).ok()?;
+                settings.lines().find_map(|line| {
+                    let (key, value) = line.split_once('=')?;
+                    (key.trim() == "gtk-enable-animations").then(|| match value.trim() {
+                        "false" | "0" => Self::Reduced,
+                        _ => Self::Full,
+                    })
+                })
+            })
+        }
+        #[cfg(target_os = "windows")]
+        {
+            use windows_sys::Win32::UI::WindowsAndMessaging::{
+                SystemParametersInfoW, SPI_GETCLIENTAREAANIMATION,
+            };
+
+            let mut enabled = 0;
+            let ok = unsafe {
+                SystemParametersInfoW(
+                    SPI_GETCLIENTAREAANIMATION,
+                    0,
+                    &mut enabled as *mut _ as *mut _,
+                    0,
+                )
+            };
+            (ok != 0).then(|| {
+                if enabled == 0 {
+                    Self::Reduced
+                } else {
+                    Self::Full
+                }
+            })
+        }
+        #[cfg(not(any(
+            target_os = "macos",
+            target_os = "linux",
+            target_os = "freebsd",
+            target_os = "windows"
+        )))]
+        None
+    }
+}
+
+/// Detect the [`MotionSettings`] from the OS, unless it's set by the app before.
+pub(crate) fn init(cx: &mut App) {
+    if cx.has_global::<MotionSettings>() {
+        return;
+    }
+
+    if let Some(motion) = MotionSettings::detect() {
+        cx.set_global(motion);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::MotionSettings;
+
+    #[test]
+    fn test_motion_settings() {
+        let duration = Duration::from_millis(200);
+        assert_eq!(MotionSettings::Full.duration(duration), duration);
+        assert_eq!(MotionSettings::Reduced.duration(duration), duration);
+        assert_eq!(MotionSettings::Off.duration(duration), Duration::ZERO);
+
+        assert_eq!(MotionSettings::Full.motion_duration(duration), duration);
+        assert_eq!(
+            MotionSettings::Reduced.motion_duration(duration),
+            Duration::ZERO
+        );
+
+        assert_eq!(MotionSettings::Full.motion_delta(0.3), 0.3);
+        assert_eq!(MotionSettings::Reduced.motion_delta(0.3), 1.);
+        assert_eq!(MotionSettings::Off.motion_delta(0.3), 1.);
+    }
+
+    #[gpui::test]
+    fn test_motion_settings_global(cx: &mut gpui::TestAppContext) {
+        cx.update(|cx| MotionSettings::Off.set_global(cx));
+        // The setting is not overridden by the detection.
+        cx.update(crate::init);
+        assert_eq!(
+            cx.update(|cx| MotionSettings::global(cx)),
+            MotionSettings::Off
+        );
+    }
+}
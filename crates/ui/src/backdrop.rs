@@ -0,0 +1,66 @@
+use gpui::{App, Hsla, WindowBackgroundAppearance};
+
+use crate::ActiveTheme;
+
+/// Returns true if the platform supports the blurred window background.
+///
+/// The X11 and some Wayland compositors don't support it, the Wayland is treated as not
+/// supported to keep the fallback readable.
+pub fn is_backdrop_blur_supported() -> bool {
+    cfg!(any(target_os = "macos", target_os = "windows"))
+}
+
+/// The translucent surfaces enabled by the [`Theme::backdrop_blur`](crate::Theme::backdrop_blur).
+///
+/// GPUI can't blur the elements behind an element, so the blur is from the window background,
+/// the surfaces directly on the window (the [`Material::Window`] and [`Material::Sidebar`])
+/// show the blurred desktop behind the window, like the macOS sidebar.
+///
+/// The surfaces above the content, and all of them on the platforms without the blurred
+/// window background, fall back to the translucent fills.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Material {
+    /// The background of the window, painted by the [`Root`](crate::Root).
+    Window,
+    /// The sidebar on the window background.
+    Sidebar,
+    /// The Popover and menus.
+    Popover,
+    /// The scrim below the Modal and Drawer.
+    Scrim,
+}
+
+impl Material {
+    /// Returns the background color of the material in the current theme.
+    pub fn color(self, cx: &App) -> Hsla {
+        let theme = cx.theme();
+        if !theme.backdrop_blur {
+            return match self {
+                Self::Window => theme.background,
+                Self::Sidebar => theme.sidebar,
+                Self::Popover => theme.popover,
+                Self::Scrim => theme.overlay,
+            };
+        }
+
+        let blurred = is_backdrop_blur_supported();
+        match self {
+            Self::Window if blurred => theme.background.opacity(0.),
+            Self::Window => theme.background,
+            Self::Sidebar if blurred => theme.sidebar.opacity(0.6),
+            Self::Sidebar => theme.sidebar.opacity(0.9),
+            Self::Popover => theme.popover.opacity(0.9),
+            // A milky scrim looks more frosted than the dimmed one without the blur.
+            Self::Scrim => theme.background.opacity(0.6),
+        }
+    }
+}
+
+/// Returns the window background appearance for the [`Theme::backdrop_blur`](crate::Theme::backdrop_blur).
+pub(crate) fn window_background_appearance(cx: &App) -> WindowBackgroundAppearance {
+    if cx.theme().backdrop_blur && is_backdrop_blur_supported() {
+        WindowBackgroundAppearance::Blurred
+    } else {
+        WindowBackgroundAppearance::Opaque
+    }
+}
@@ -1,12 +1,17 @@
 use std::rc::Rc;
 
 use gpui::{
-    div, prelude::FluentBuilder as _, App, ClickEvent, ElementId, InteractiveElement as _,
+    div, prelude::FluentBuilder as _, App, ClickEvent, ElementId, Entity, InteractiveElement as _,
     IntoElement, ParentElement, RenderOnce, SharedString, StatefulInteractiveElement,
     StyleRefinement, Styled, Window,
 };
+use itertools::Itertools;
 
-use crate::{h_flex, ActiveTheme, Icon, IconName, StyledExt};
+use crate::{
+    h_flex,
+    router::{Route, Router},
+    ActiveTheme, Icon, IconName, StyledExt,
+};
 
 /// A breadcrumb navigation element.
 #[derive(IntoElement)]
@@ -24,6 +29,7 @@ pub struct BreadcrumbItem {
     on_click: Option<Rc<dyn Fn(&ClickEvent, &mut Window, &mut App)>>,
     disabled: bool,
     is_last: bool,
+    route: Option<(Entity<Router>, Route)>,
 }
 
 impl BreadcrumbItem {
@@ -36,6 +42,7 @@ impl BreadcrumbItem {
             on_click: None,
             disabled: false,
             is_last: false,
+            route: None,
         }
     }
 
@@ -52,6 +59,12 @@ impl BreadcrumbItem {
         self
     }
 
+    /// Bind the item to a route of the [`Router`], navigate to the route when clicked.
+    pub fn route(mut self, router: &Entity<Router>, route: impl Into<Route>) -> Self {
+        self.route = Some((router.clone(), route.into()));
+        self
+    }
+
     fn id(mut self, id: impl Into<ElementId>) -> Self {
         self.id = id.into();
         self
@@ -100,7 +113,12 @@ impl RenderOnce for BreadcrumbItem {
             })
             .refine_style(&self.style)
             .when(!self.disabled, |this| {
-                this.when_some(self.on_click, |this, on_click| {
+                this.when_some(self.route, |this, (router, route)| {
+                    this.cursor_pointer().on_click(move |_, _, cx| {
+                        router.update(cx, |router, cx| router.navigate(route.clone(), cx));
+                    })
+                })
+                .when_some(self.on_click, |this, on_click| {
                     this.cursor_pointer().on_click(move |event, window, cx| {
                         on_click(event, window, cx);
                     })
@@ -138,7 +156,6 @@ impl RenderOnce for BreadcrumbSeparator {
         Icon::new(IconName::ChevronRight)
             .text_color(cx.theme().muted_foreground)
             .size_3p5()
-            .into_any_element()
     }
 }
 
@@ -152,22 +169,18 @@ impl RenderOnce for Breadcrumb {
     fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
         let items_count = self.items.len();
 
-        let mut children = vec![];
-        for (ix, item) in self.items.into_iter().enumerate() {
-            let is_last = ix == items_count - 1;
-
-            let item = item.id(ix);
-            children.push(item.is_last(is_last).into_any_element());
-            if !is_last {
-                children.push(BreadcrumbSeparator.into_any_element());
-            }
-        }
-
         h_flex()
             .gap_1p5()
             .text_sm()
             .text_color(cx.theme().muted_foreground)
             .refine_style(&self.style)
-            .children(children)
+            .children(Itertools::intersperse_with(
+                self.items.into_iter().enumerate().map(|(ix, item)| {
+                    item.id(ix)
+                        .is_last(ix + 1 == items_count)
+                        .into_any_element()
+                }),
+                || BreadcrumbSeparator.into_any_element(),
+            ))
     }
 }
@@ -0,0 +1,166 @@
+use gpui::{px, App, IntoElement, Pixels, Size, Window};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::ActiveTheme;
+
+/// The responsive sizes, from the narrowest.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Breakpoint {
+    /// Narrower than the `sm`.
+    #[default]
+    Xs,
+    Sm,
+    Md,
+    Lg,
+    Xl,
+}
+
+impl Breakpoint {
+    /// Returns the breakpoint of the width by the thresholds in the theme.
+    pub fn of(width: &impl ResponsiveWidth, cx: &App) -> Self {
+        cx.theme().breakpoints.breakpoint(width.responsive_width())
+    }
+}
+
+/// The minimum widths of the [`Breakpoint`]s, configurable in the theme.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Breakpoints {
+    pub sm: Pixels,
+    pub md: Pixels,
+    pub lg: Pixels,
+    pub xl: Pixels,
+}
+
+impl Default for Breakpoints {
+    fn default() -> Self {
+        Self {
+            sm: px(640.),
+            md: px(768.),
+            lg: px(1024.),
+            xl: px(1280.),
+        }
+    }
+}
+
+impl Breakpoints {
+    /// Returns the minimum width of the breakpoint.
+    pub fn min_width(&self, breakpoint: Breakpoint) -> Pixels {
+        match breakpoint {
+            Breakpoint::Xs => px(0.),
+            Breakpoint::Sm => self.sm,
+            Breakpoint::Md => self.md,
+            Breakpoint::Lg => self.lg,
+            Breakpoint::Xl => self.xl,
+        }
+    }
+
+    /// Returns the breakpoint of the width.
+    pub fn breakpoint(&self, width: Pixels) -> Breakpoint {
+        [
+            Breakpoint::Xl,
+            Breakpoint::Lg,
+            Breakpoint::Md,
+            Breakpoint::Sm,
+        ]
+        .into_iter()
+        .find(|breakpoint| width >= self.min_width(*breakpoint))
+        .unwrap_or(Breakpoint::Xs)
+    }
+}
+
+/// The width to evaluate the breakpoints against, the window or a container.
+pub trait ResponsiveWidth {
+    fn responsive_width(&self) -> Pixels;
+}
+
+impl ResponsiveWidth for Window {
+    fn responsive_width(&self) -> Pixels {
+        self.viewport_size().width
+    }
+}
+
+impl ResponsiveWidth for Size<Pixels> {
+    fn responsive_width(&self) -> Pixels {
+        self.width
+    }
+}
+
+impl ResponsiveWidth for Pixels {
+    fn responsive_width(&self) -> Pixels {
+        *self
+    }
+}
+
+/// Builder helpers to apply the styles by the width of the window or a container,
+/// e.g. the size measured by the [`Measured`](crate::measured::Measured).
+///
+/// ```ignore
+/// h_flex()
+///     .when_narrower_than(px(600.), window, |this| this.flex_col())
+///     .when_breakpoint(Breakpoint::Lg, window, cx, |this| this.gap_6())
+/// ```
+pub trait ResponsiveExt: Sized {
+    /// Apply the function if the `of` is wider than or equal to the `width`.
+    fn when_wider_than(
+        self,
+        width: impl Into<Pixels>,
+        of: &impl ResponsiveWidth,
+        f: impl FnOnce(Self) -> Self,
+    ) -> Self {
+        if of.responsive_width() >= width.into() {
+            f(self)
+        } else {
+            self
+        }
+    }
+
+    /// Apply the function if the `of` is narrower than the `width`.
+    fn when_narrower_than(
+        self,
+        width: impl Into<Pixels>,
+        of: &impl ResponsiveWidth,
+        f: impl FnOnce(Self) -> Self,
+    ) -> Self {
+        if of.responsive_width() < width.into() {
+            f(self)
+        } else {
+            self
+        }
+    }
+
+    /// Apply the function if the `of` is at the `breakpoint` or wider, like `md:` in Tailwind CSS.
+    fn when_breakpoint(
+        self,
+        breakpoint: Breakpoint,
+        of: &impl ResponsiveWidth,
+        cx: &App,
+        f: impl FnOnce(Self) -> Self,
+    ) -> Self {
+        if Breakpoint::of(of, cx) >= breakpoint {
+            f(self)
+        } else {
+            self
+        }
+    }
+}
+
+impl<T: IntoElement> ResponsiveExt for T {}
+
+#[cfg(test)]
+mod tests {
+    use gpui::px;
+
+    use super::{Breakpoint, Breakpoints};
+
+    #[test]
+    fn test_breakpoint() {
+        let breakpoints = Breakpoints::default();
+        assert_eq!(breakpoints.breakpoint(px(320.)), Breakpoint::Xs);
+        assert_eq!(breakpoints.breakpoint(px(640.)), Breakpoint::Sm);
+        assert_eq!(breakpoints.breakpoint(px(800.)), Breakpoint::Md);
+        assert_eq!(breakpoints.breakpoint(px(1100.)), Breakpoint::Lg);
+        assert_eq!(breakpoints.breakpoint(px(1920.)), Breakpoint::Xl);
+        assert!(Breakpoint::Lg > Breakpoint::Md);
+    }
+}
@@ -1,10 +1,21 @@
 use std::rc::Rc;
 
-use gpui::{px, App, Background, Bounds, Hsla, Pixels, SharedString, TextAlign, Window};
+use gpui::{
+    point, px, App, Background, Bounds, Entity, Hsla, Pixels, SharedString, TextAlign, Window,
+};
 use gpui_component_macros::IntoPlot;
 use num_traits::{Num, ToPrimitive};
 
+use super::{
+    interaction::{ChartInteraction, TooltipRow},
+    legend::SeriesState,
+    stream::StreamTimeline,
+    y_axis_labels, ChartPalette, ChartState, ChartStream, SeriesStyle,
+};
+
 use crate::{
+    export,
+    format::format_compact,
     plot::{
         scale::{Scale, ScaleLinear, ScalePoint, Sealed},
         shape::Area,
@@ -26,7 +37,12 @@ where
     strokes: Vec<Hsla>,
     stroke_styles: Vec<StrokeStyle>,
     fills: Vec<Background>,
+    palette: Option<ChartPalette>,
+    styles: Vec<SeriesStyle>,
     tick_margin: usize,
+    y_axis: Option<Rc<dyn Fn(f64) -> SharedString>>,
+    stream: Option<StreamTimeline>,
+    state: Option<Entity<ChartState>>,
 }
 
 impl<T, X, Y> AreaChart<T, X, Y>
@@ -43,9 +59,14 @@ where
             stroke_styles: vec![],
             strokes: vec![],
             fills: vec![],
+            palette: None,
+            styles: vec![],
             tick_margin: 1,
             x: None,
             y: vec![],
+            y_axis: None,
+            stream: None,
+            state: None,
         }
     }
 
@@ -69,6 +90,28 @@ where
         self
     }
 
+    /// Use the colors of the palette for the series without the stroke and fill,
+    /// the fill is the color with 0.4 opacity.
+    pub fn palette(mut self, palette: ChartPalette) -> Self {
+        self.palette = Some(palette);
+        self
+    }
+
+    /// Override the color, width and dash of the stroke of the next series, in the order of `y`.
+    pub fn series_style(mut self, style: SeriesStyle) -> Self {
+        self.styles.push(style);
+        self
+    }
+
+    /// Bind the [`ChartState`] to show the crosshair and the tooltip on hover, zoom the x-axis
+    /// by the wheel and select a range by dragging, not work in the streaming mode.
+    ///
+    /// The charts sharing the state show the crosshair at the same data index.
+    pub fn state(mut self, state: &Entity<ChartState>) -> Self {
+        self.state = Some(state.clone());
+        self
+    }
+
     pub fn natural(mut self) -> Self {
         self.stroke_styles.push(StrokeStyle::Natural);
         self
@@ -88,6 +131,37 @@ where
         self.tick_margin = tick_margin;
         self
     }
+    /// Show the labels of the y-axis, formatted in the compact numbers of the current locale, e.g.: `1.2K`.
+    pub fn y_axis(self) -> Self {
+        self.y_axis_format(|value| format_compact(value))
+    }
+
+    /// Show the labels of the y-axis, formatted with the function.
+    pub fn y_axis_format<S>(mut self, format: impl Fn(f64) -> S + 'static) -> Self
+    where
+        S: Into<SharedString> + 'static,
+    {
+        self.y_axis = Some(Rc::new(move |value| format(value).into()));
+        self
+    }
+}
+
+impl<T, Y> AreaChart<T, SharedString, Y>
+where
+    T: Clone,
+    Y: Clone + Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
+{
+    /// Create an area chart in the streaming mode, shows the items of the [`ChartStream`]
+    /// in its time window, the x-axis is the time and the `x` is not required.
+    ///
+    /// The dense items are decimated to at most one point per pixel for each series.
+    pub fn stream(stream: &ChartStream<T>) -> Self {
+        let (timeline, data) = stream.snapshot();
+        Self {
+            stream: Some(timeline),
+            ..Self::new(data)
+        }
+    }
 }
 
 impl<T, X, Y> Plot for AreaChart<T, X, Y>
@@ -96,57 +170,107 @@ where
     Y: Clone + Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
 {
     fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
-        let Some(x_fn) = self.x.as_ref() else {
-            return;
-        };
-
         if self.y.len() == 0 {
             return;
         }
+        let x_fn = match (&self.stream, self.x.as_ref()) {
+            (None, None) => return,
+            (_, x_fn) => x_fn,
+        };
 
         let width = bounds.size.width.as_f32();
         let height = bounds.size.height.as_f32() - AXIS_GAP;
 
-        // X scale
-        let x = ScalePoint::new(self.data.iter().map(|v| x_fn(v)).collect(), vec![0., width]);
+        let interaction = match (&self.state, &self.stream) {
+            (Some(state), None) => Some(ChartInteraction::new(state, self.data.len(), cx)),
+            _ => None,
+        };
+        let data = match &interaction {
+            Some(interaction) => &self.data[interaction.range()],
+            None => &self.data[..],
+        };
+        let series_state = SeriesState::read(self.state.as_ref(), cx);
 
-        // Y scale
-        let domain = self
-            .data
+        // Y scale, the hidden series are excluded.
+        let domain = data
             .iter()
-            .flat_map(|v| self.y.iter().map(|y_fn| y_fn(v)))
+            .flat_map(|v| {
+                self.y
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| series_state.visibility(*i) > 0.)
+                    .map(|(_, y_fn)| y_fn(v))
+            })
             .chain(Some(Y::zero()))
             .collect::<Vec<_>>();
         let y = ScaleLinear::new(domain, vec![height, 10.]);
 
         // Draw X axis
-        let data_len = self.data.len();
-        let x_label = self.data.iter().enumerate().filter_map(|(i, d)| {
-            if (i + 1) % self.tick_margin == 0 {
-                x.tick(&x_fn(d)).map(|x_tick| {
-                    let align = match i {
-                        0 => {
-                            if data_len == 1 {
-                                TextAlign::Center
-                            } else {
-                                TextAlign::Left
+        let (series, xs) = if let Some(stream) = &self.stream {
+            Axis::new()
+                .x(height)
+                .x_label(stream.x_labels(width, cx.theme().muted_foreground))
+                .stroke(cx.theme().border)
+                .paint(&bounds, window, cx);
+
+            let series = self
+                .y
+                .iter()
+                .map(|y_fn| stream.points(data, |d| y.tick(&y_fn(d)), width))
+                .collect::<Vec<_>>();
+            (series, vec![])
+        } else {
+            let Some(x_fn) = x_fn else {
+                return;
+            };
+
+            // X scale
+            let x = ScalePoint::new(data.iter().map(|v| x_fn(v)).collect(), vec![0., width]);
+
+            let data_len = data.len();
+            let x_label = data.iter().enumerate().filter_map(|(i, d)| {
+                if (i + 1) % self.tick_margin == 0 {
+                    x.tick(&x_fn(d)).map(|x_tick| {
+                        let align = match i {
+                            0 => {
+                                if data_len == 1 {
+                                    TextAlign::Center
+                                } else {
+                                    TextAlign::Left
+                                }
                             }
-                        }
-                        i if i == data_len - 1 => TextAlign::Right,
-                        _ => TextAlign::Center,
-                    };
-                    AxisText::new(x_fn(d).into(), x_tick, cx.theme().muted_foreground).align(align)
-                })
-            } else {
-                None
-            }
-        });
+                            i if i == data_len - 1 => TextAlign::Right,
+                            _ => TextAlign::Center,
+                        };
+                        AxisText::new(x_fn(d).into(), x_tick, cx.theme().muted_foreground)
+                            .align(align)
+                    })
+                } else {
+                    None
+                }
+            });
 
-        Axis::new()
-            .x(height)
-            .x_label(x_label)
-            .stroke(cx.theme().border)
-            .paint(&bounds, window, cx);
+            Axis::new()
+                .x(height)
+                .x_label(x_label)
+                .stroke(cx.theme().border)
+                .paint(&bounds, window, cx);
+
+            let series = self
+                .y
+                .iter()
+                .map(|y_fn| {
+                    data.iter()
+                        .filter_map(|d| Some(point(x.tick(&x_fn(d))?, y.tick(&y_fn(d))?)))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            let xs = data
+                .iter()
+                .map(|d| x.tick(&x_fn(d)).unwrap_or_default())
+                .collect();
+            (series, xs)
+        };
 
         // Draw grid
         Grid::new()
@@ -155,34 +279,104 @@ where
             .dash_array(&[px(4.), px(2.)])
             .paint(&bounds, window);
 
-        // Draw area
-        for (i, y_fn) in self.y.iter().enumerate() {
-            let x = x.clone();
-            let y = y.clone();
-            let x_fn = x_fn.clone();
-            let y_fn = y_fn.clone();
+        // Draw Y axis labels
+        if let Some(format) = self.y_axis.as_ref() {
+            Axis::new()
+                .hide_x_axis()
+                .y(px(0.))
+                .y_label(y_axis_labels(
+                    &y,
+                    height,
+                    format.as_ref(),
+                    cx.theme().muted_foreground,
+                ))
+                .paint(&bounds, window, cx);
+        }
 
-            let fill = *self
+        // Draw area
+        let mut strokes = vec![];
+        for (i, points) in series.into_iter().enumerate() {
+            let style = self.styles.get(i).cloned().unwrap_or_default();
+            let color = style
+                .color
+                .or_else(|| self.palette.as_ref().map(|palette| palette.color(i, cx)))
+                .unwrap_or(cx.theme().chart_2);
+            let fill = self
                 .fills
                 .get(i)
-                .unwrap_or(&cx.theme().chart_2.opacity(0.4).into());
+                .copied()
+                .unwrap_or(color.opacity(0.4).into());
+            let stroke = style
+                .color
+                .or(self.strokes.get(i).copied())
+                .unwrap_or(color);
+            strokes.push(stroke);
 
-            let stroke = *self.strokes.get(i).unwrap_or(&cx.theme().chart_2);
+            // Grow from or shrink to the baseline in the transition of hiding or showing.
+            let visibility = series_state.visibility(i);
+            if visibility == 0. {
+                continue;
+            }
+            let points = points
+                .into_iter()
+                .map(|p| point(p.x, height - (height - p.y) * visibility))
+                .collect::<Vec<_>>();
+            let opacity = series_state.opacity(i);
+            let (stroke, fill) = (stroke.opacity(opacity), fill.opacity(opacity));
 
             let stroke_style = *self
                 .stroke_styles
                 .get(i)
                 .unwrap_or(self.stroke_styles.first().unwrap_or(&Default::default()));
 
-            Area::new()
-                .data(&self.data)
-                .x(move |d| x.tick(&x_fn(d)))
+            let mut area = Area::new()
+                .data(points)
+                .x(|p| Some(p.x))
                 .y0(height)
-                .y1(move |d| y.tick(&y_fn(d)))
+                .y1(|p| Some(p.y))
                 .stroke(stroke)
                 .stroke_style(stroke_style)
-                .fill(fill)
-                .paint(&bounds, window);
+                .fill(fill);
+            if let Some(width) = style.width {
+                area = area.stroke_width(width);
+            }
+            if let Some(dash) = &style.dash {
+                area = area.dash_array(dash);
+            }
+
+            // The area of the stream enters from the left edge.
+            export::with_content_mask(window, bounds, |window| {
+                area.paint(&bounds, window);
+            });
+        }
+
+        if let Some(stream) = &self.stream {
+            stream.request_frame(window);
+        }
+        series_state.request_frame(window);
+
+        if let Some(interaction) = interaction {
+            let tooltip = interaction.hovered().zip(x_fn).map(|(ix, x_fn)| {
+                let d = &self.data[ix];
+                let rows = self
+                    .y
+                    .iter()
+                    .zip(strokes)
+                    .enumerate()
+                    .filter(|(i, _)| !series_state.is_hidden(*i))
+                    .map(|(_, (y_fn, stroke))| {
+                        let value = y_fn(d);
+                        TooltipRow::new(
+                            stroke,
+                            value.to_f64(),
+                            self.y_axis.as_ref(),
+                            y.tick(&value),
+                        )
+                    })
+                    .collect();
+                (x_fn(d).into(), rows)
+            });
+            interaction.paint(xs, height, tooltip, bounds, window, cx);
         }
     }
 }
@@ -1,10 +1,17 @@
 use std::rc::Rc;
 
-use gpui::{px, App, Bounds, Hsla, Pixels, SharedString, TextAlign, Window};
+use gpui::{px, App, Bounds, Entity, Hsla, Pixels, SharedString, TextAlign, Window};
 use gpui_component_macros::IntoPlot;
 use num_traits::{Num, ToPrimitive};
 
+use super::{
+    interaction::{ChartInteraction, TooltipRow},
+    legend::SeriesState,
+    y_axis_labels, ChartPalette, ChartState,
+};
+
 use crate::{
+    format::format_compact,
     plot::{
         label::Text,
         scale::{Scale, ScaleBand, ScaleLinear, Sealed},
@@ -25,8 +32,11 @@ where
     x: Option<Rc<dyn Fn(&T) -> X>>,
     y: Option<Rc<dyn Fn(&T) -> Y>>,
     fill: Option<Rc<dyn Fn(&T) -> Hsla>>,
+    palette: Option<ChartPalette>,
     tick_margin: usize,
     label: Option<Rc<dyn Fn(&T) -> SharedString>>,
+    y_axis: Option<Rc<dyn Fn(f64) -> SharedString>>,
+    state: Option<Entity<ChartState>>,
 }
 
 impl<T, X, Y> BarChart<T, X, Y>
@@ -43,8 +53,11 @@ where
             x: None,
             y: None,
             fill: None,
+            palette: None,
             tick_margin: 1,
             label: None,
+            y_axis: None,
+            state: None,
         }
     }
 
@@ -66,6 +79,21 @@ where
         self
     }
 
+    /// Fill the bars by the colors of the palette in order, if the `fill` is not set.
+    pub fn palette(mut self, palette: ChartPalette) -> Self {
+        self.palette = Some(palette);
+        self
+    }
+
+    /// Bind the [`ChartState`] to show the crosshair and the tooltip on hover, zoom the x-axis
+    /// by the wheel and select a range by dragging.
+    ///
+    /// The charts sharing the state show the crosshair at the same data index.
+    pub fn state(mut self, state: &Entity<ChartState>) -> Self {
+        self.state = Some(state.clone());
+        self
+    }
+
     pub fn tick_margin(mut self, tick_margin: usize) -> Self {
         self.tick_margin = tick_margin;
         self
@@ -78,6 +106,19 @@ where
         self.label = Some(Rc::new(move |t| label(t).into()));
         self
     }
+    /// Show the labels of the y-axis, formatted in the compact numbers of the current locale, e.g.: `1.2K`.
+    pub fn y_axis(self) -> Self {
+        self.y_axis_format(|value| format_compact(value))
+    }
+
+    /// Show the labels of the y-axis, formatted with the function.
+    pub fn y_axis_format<S>(mut self, format: impl Fn(f64) -> S + 'static) -> Self
+    where
+        S: Into<SharedString> + 'static,
+    {
+        self.y_axis = Some(Rc::new(move |value| format(value).into()));
+        self
+    }
 }
 
 impl<T, X, Y> Plot for BarChart<T, X, Y>
@@ -93,16 +134,24 @@ where
         let width = bounds.size.width.as_f32();
         let height = bounds.size.height.as_f32() - AXIS_GAP;
 
+        let interaction = self
+            .state
+            .as_ref()
+            .map(|state| ChartInteraction::new(state, self.data.len(), cx));
+        let range = interaction
+            .as_ref()
+            .map_or(0..self.data.len(), |interaction| interaction.range());
+        let data = &self.data[range.clone()];
+
         // X scale
-        let x = ScaleBand::new(self.data.iter().map(|v| x_fn(v)).collect(), vec![0., width])
+        let x = ScaleBand::new(data.iter().map(|v| x_fn(v)).collect(), vec![0., width])
             .padding_inner(0.4)
             .padding_outer(0.2);
         let band_width = x.band_width();
 
         // Y scale, ensure start from 0.
         let y = ScaleLinear::new(
-            self.data
-                .iter()
+            data.iter()
                 .map(|v| y_fn(v))
                 .chain(Some(Y::zero()))
                 .collect(),
@@ -110,7 +159,7 @@ where
         );
 
         // Draw X axis
-        let x_label = self.data.iter().enumerate().filter_map(|(i, d)| {
+        let x_label = data.iter().enumerate().filter_map(|(i, d)| {
             if (i + 1) % self.tick_margin == 0 {
                 x.tick(&x_fn(d)).map(|x_tick| {
                     AxisText::new(
@@ -138,27 +187,86 @@ where
             .dash_array(&[px(4.), px(2.)])
             .paint(&bounds, window);
 
-        // Draw bars
-        let x_fn = x_fn.clone();
-        let y_fn = y_fn.clone();
+        // Draw Y axis labels
+        if let Some(format) = self.y_axis.as_ref() {
+            Axis::new()
+                .hide_x_axis()
+                .y(px(0.))
+                .y_label(y_axis_labels(
+                    &y,
+                    height,
+                    format.as_ref(),
+                    cx.theme().muted_foreground,
+                ))
+                .paint(&bounds, window, cx);
+        }
+
+        // Draw bars, grow from or shrink to the bottom in the transition of hiding or showing.
+        let series_state = SeriesState::read(self.state.as_ref(), cx);
+        let visibility = series_state.visibility(0);
+        let opacity = series_state.opacity(0);
         let default_fill = cx.theme().chart_2;
+        let palette = self.palette.as_ref().map(|palette| palette.colors(cx));
         let fill = self.fill.clone();
+        let fill_at = Rc::new(move |ix: usize, d: &T| match (&fill, &palette) {
+            (Some(fill), _) => fill(d),
+            (None, Some(colors)) if !colors.is_empty() => colors[ix % colors.len()],
+            _ => default_fill,
+        });
         let label_color = cx.theme().foreground;
+        let x_ticks = Rc::new(data.iter().map(|d| x.tick(&x_fn(d))).collect::<Vec<_>>());
         let mut bar = Bar::new()
-            .data(&self.data)
+            .data(data.iter().enumerate().map(|(ix, d)| (range.start + ix, d)))
             .band_width(band_width)
-            .x(move |d| x.tick(&x_fn(d)))
+            .x({
+                let x_ticks = x_ticks.clone();
+                let start = range.start;
+                move |(ix, _)| x_ticks[ix - start]
+            })
             .y0(height)
-            .y1(move |d| y.tick(&y_fn(d)))
-            .fill(move |d| fill.as_ref().map(|f| f(d)).unwrap_or(default_fill));
+            .y1({
+                let y = y.clone();
+                let y_fn = y_fn.clone();
+                move |(_, d)| {
+                    y.tick(&y_fn(d))
+                        .map(|tick| height - (height - tick) * visibility)
+                }
+            })
+            .fill({
+                let fill_at = fill_at.clone();
+                move |(ix, d)| fill_at(*ix, d).opacity(opacity)
+            });
 
         if let Some(label) = self.label.as_ref() {
             let label = label.clone();
-            bar = bar.label(move |d, p| {
+            bar = bar.label(move |(_, d), p| {
                 vec![Text::new(label(d), p, label_color).align(TextAlign::Center)]
             });
         }
 
-        bar.paint(&bounds, window, cx);
+        if visibility > 0. {
+            bar.paint(&bounds, window, cx);
+        }
+        series_state.request_frame(window);
+
+        if let Some(interaction) = interaction {
+            let xs = x_ticks
+                .iter()
+                .map(|x_tick| x_tick.unwrap_or_default() + band_width / 2.)
+                .collect();
+            let hovered = interaction.hovered().filter(|_| !series_state.is_hidden(0));
+            let tooltip = hovered.map(|ix| {
+                let d = &self.data[ix];
+                let value = y_fn(d);
+                let row = TooltipRow::new(
+                    fill_at(ix, d),
+                    value.to_f64(),
+                    self.y_axis.as_ref(),
+                    y.tick(&value),
+                );
+                (x_fn(d).into(), vec![row])
+            });
+            interaction.paint(xs, height, tooltip, bounds, window, cx);
+        }
     }
 }
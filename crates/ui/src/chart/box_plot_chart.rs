@@ -0,0 +1,391 @@
+use std::rc::Rc;
+
+use gpui::{
+    fill, point, px, quad, size, App, BorderStyle, Bounds, Entity, Hsla, Pixels, SharedString,
+    TextAlign, Window,
+};
+use gpui_component_macros::IntoPlot;
+
+use super::{
+    interaction::{ChartInteraction, TooltipRow},
+    legend::SeriesState,
+    stats::BoxStats,
+    y_axis_labels, ChartPalette, ChartState,
+};
+
+use crate::{
+    export,
+    format::format_compact,
+    plot::{
+        origin_point,
+        scale::{Scale, ScaleBand, ScaleLinear},
+        Axis, AxisText, Grid, Plot, PlotPath, AXIS_GAP,
+    },
+    ActiveTheme, PixelsExt,
+};
+
+/// The number of the points of the density of the violin.
+const VIOLIN_STEPS: usize = 48;
+/// The size of the outlier dots.
+const OUTLIER_SIZE: f32 = 5.;
+
+/// A box plot of the distributions of the groups, the box is the interquartile range with
+/// the median line, the whiskers extend to the furthest values within 1.5 IQR, and the values
+/// beyond are drawn as the outlier dots.
+///
+/// Set [`violin`](Self::violin) to draw the density of the values around a narrow box.
+#[derive(IntoPlot)]
+pub struct BoxPlotChart<T, X>
+where
+    T: 'static,
+    X: PartialEq + Into<SharedString> + 'static,
+{
+    data: Vec<T>,
+    x: Option<Rc<dyn Fn(&T) -> X>>,
+    values: Option<Rc<dyn Fn(&T) -> Vec<f64>>>,
+    fill: Option<Rc<dyn Fn(&T) -> Hsla>>,
+    palette: Option<ChartPalette>,
+    violin: bool,
+    tick_margin: usize,
+    y_axis: Option<Rc<dyn Fn(f64) -> SharedString>>,
+    state: Option<Entity<ChartState>>,
+}
+
+impl<T, X> BoxPlotChart<T, X>
+where
+    X: PartialEq + Into<SharedString> + 'static,
+{
+    pub fn new<I>(data: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Self {
+            data: data.into_iter().collect(),
+            x: None,
+            values: None,
+            fill: None,
+            palette: None,
+            violin: false,
+            tick_margin: 1,
+            y_axis: None,
+            state: None,
+        }
+    }
+
+    /// Set the name of the group on the x-axis.
+    pub fn x(mut self, x: impl Fn(&T) -> X + 'static) -> Self {
+        self.x = Some(Rc::new(x));
+        self
+    }
+
+    /// Set the values of the group to summarize, the non-finite values are ignored.
+    pub fn values<I>(mut self, values: impl Fn(&T) -> I + 'static) -> Self
+    where
+        I: IntoIterator<Item = f64>,
+    {
+        self.values = Some(Rc::new(move |t| values(t).into_iter().collect()));
+        self
+    }
+
+    /// Set the color of the group.
+    pub fn fill<H>(mut self, fill: impl Fn(&T) -> H + 'static) -> Self
+    where
+        H: Into<Hsla> + 'static,
+    {
+        self.fill = Some(Rc::new(move |t| fill(t).into()));
+        self
+    }
+
+    /// Color the groups by the colors of the palette in order, if the `fill` is not set.
+    pub fn palette(mut self, palette: ChartPalette) -> Self {
+        self.palette = Some(palette);
+        self
+    }
+
+    /// Set to draw the violin of the density of the values, default: false
+    pub fn violin(mut self, violin: bool) -> Self {
+        self.violin = violin;
+        self
+    }
+
+    /// Bind the [`ChartState`] to show the crosshair and the tooltip of the summary on hover,
+    /// and hide or highlight the groups by a [`ChartLegend`](super::ChartLegend).
+    ///
+    /// The series of the box plot are the groups in the order of the data.
+    pub fn state(mut self, state: &Entity<ChartState>) -> Self {
+        self.state = Some(state.clone());
+        self
+    }
+
+    pub fn tick_margin(mut self, tick_margin: usize) -> Self {
+        self.tick_margin = tick_margin;
+        self
+    }
+
+    /// Show the labels of the y-axis, formatted in the compact numbers of the current locale, e.g.: `1.2K`.
+    pub fn y_axis(self) -> Self {
+        self.y_axis_format(|value| format_compact(value))
+    }
+
+    /// Show the labels of the y-axis, formatted with the function.
+    pub fn y_axis_format<S>(mut self, format: impl Fn(f64) -> S + 'static) -> Self
+    where
+        S: Into<SharedString> + 'static,
+    {
+        self.y_axis = Some(Rc::new(move |value| format(value).into()));
+        self
+    }
+}
+
+impl<T, X> Plot for BoxPlotChart<T, X>
+where
+    X: PartialEq + Into<SharedString> + 'static,
+{
+    fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
+        let (Some(x_fn), Some(values_fn)) = (self.x.as_ref(), self.values.as_ref()) else {
+            return;
+        };
+
+        let width = bounds.size.width.as_f32();
+        let height = bounds.size.height.as_f32() - AXIS_GAP;
+        let origin = bounds.origin;
+
+        let interaction = self
+            .state
+            .as_ref()
+            .map(|state| ChartInteraction::new(state, self.data.len(), cx));
+        let range = interaction
+            .as_ref()
+            .map_or(0..self.data.len(), |interaction| interaction.range());
+        let data = &self.data[range.clone()];
+        let stats = data
+            .iter()
+            .map(|d| BoxStats::new(&values_fn(d)))
+            .collect::<Vec<_>>();
+
+        // X scale
+        let x = ScaleBand::new(data.iter().map(|v| x_fn(v)).collect(), vec![0., width])
+            .padding_inner(0.4)
+            .padding_outer(0.2);
+        let band_width = x.band_width();
+
+        // Y scale of all the values, including the outliers.
+        let y = ScaleLinear::new(
+            stats
+                .iter()
+                .flatten()
+                .flat_map(|stats| {
+                    let (min, max) = stats.extent();
+                    [min, max]
+                })
+                .collect(),
+            vec![height, 10.],
+        );
+
+        // Draw X axis
+        let x_label = data.iter().enumerate().filter_map(|(i, d)| {
+            if (i + 1) % self.tick_margin.max(1) == 0 {
+                x.tick(&x_fn(d)).map(|x_tick| {
+                    AxisText::new(
+                        x_fn(d).into(),
+                        x_tick + band_width / 2.,
+                        cx.theme().muted_foreground,
+                    )
+                    .align(TextAlign::Center)
+                })
+            } else {
+                None
+            }
+        });
+
+        Axis::new()
+            .x(height)
+            .x_label(x_label)
+            .stroke(cx.theme().border)
+            .paint(&bounds, window, cx);
+
+        // Draw grid
+        Grid::new()
+            .y((0..=3).map(|i| height * i as f32 / 4.0).collect())
+            .stroke(cx.theme().border)
+            .dash_array(&[px(4.), px(2.)])
+            .paint(&bounds, window);
+
+        // Draw Y axis labels
+        if let Some(format) = self.y_axis.as_ref() {
+            Axis::new()
+                .hide_x_axis()
+                .y(px(0.))
+                .y_label(y_axis_labels(
+                    &y,
+                    height,
+                    format.as_ref(),
+                    cx.theme().muted_foreground,
+                ))
+                .paint(&bounds, window, cx);
+        }
+
+        // Draw the groups, shrink to or grow from the median in the transition of hiding or showing.
+        let series_state = SeriesState::read(self.state.as_ref(), cx);
+        let palette = self.palette.as_ref().map(|palette| palette.colors(cx));
+        let fill_at = |ix: usize, d: &T| match (&self.fill, &palette) {
+            (Some(fill), _) => fill(d),
+            (None, Some(colors)) if !colors.is_empty() => colors[ix % colors.len()],
+            _ => cx.theme().chart_2,
+        };
+        let x_ticks = data.iter().map(|d| x.tick(&x_fn(d))).collect::<Vec<_>>();
+
+        for (i, (d, stats)) in data.iter().zip(&stats).enumerate() {
+            let ix = range.start + i;
+            let visibility = series_state.visibility(ix);
+            let (Some(x_tick), Some(stats)) = (x_ticks[i], stats) else {
+                continue;
+            };
+            if visibility == 0. {
+                continue;
+            }
+
+            let color = fill_at(ix, d).opacity(series_state.opacity(ix));
+            let center = x_tick + band_width / 2.;
+            let median = y.tick(&stats.median).unwrap_or(height / 2.);
+            let y_at = |value: f64| {
+                let tick = y.tick(&value).unwrap_or(median);
+                median + (tick - median) * visibility
+            };
+
+            if self.violin {
+                let density = stats.density(VIOLIN_STEPS);
+                let max_density = density.iter().map(|(_, d)| *d).fold(0., f64::max);
+                if max_density > 0. {
+                    let half_width = |d: f64| (d / max_density) as f32 * band_width / 2.;
+                    let mut builder = PlotPath::fill();
+                    for (ix, (value, d)) in density.iter().enumerate() {
+                        let p = origin_point(px(center - half_width(*d)), px(y_at(*value)), origin);
+                        if ix == 0 {
+                            builder.move_to(p);
+                        } else {
+                            builder.line_to(p);
+                        }
+                    }
+                    for (value, d) in density.iter().rev() {
+                        builder.line_to(origin_point(
+                            px(center + half_width(*d)),
+                            px(y_at(*value)),
+                            origin,
+                        ));
+                    }
+                    builder.close();
+                    builder.paint(color.opacity(color.a * 0.4), window);
+                }
+            }
+
+            // The whisker from the min to the max.
+            let (whisker_top, whisker_bottom) = (y_at(stats.max), y_at(stats.min));
+            export::paint_quad(
+                window,
+                fill(
+                    Bounds::new(
+                        origin + point(px(center - 0.5), px(whisker_top)),
+                        size(px(1.), px(whisker_bottom - whisker_top)),
+                    ),
+                    color,
+                ),
+            );
+
+            let box_width = if self.violin {
+                band_width * 0.2
+            } else {
+                band_width
+            };
+            if !self.violin {
+                for value in [stats.min, stats.max] {
+                    export::paint_quad(
+                        window,
+                        fill(
+                            Bounds::new(
+                                origin + point(px(center - box_width / 4.), px(y_at(value) - 0.5)),
+                                size(px(box_width / 2.), px(1.)),
+                            ),
+                            color,
+                        ),
+                    );
+                }
+            }
+
+            // The box from the q1 to the q3, with the median line.
+            let (box_top, box_bottom) = (y_at(stats.q3), y_at(stats.q1));
+            export::paint_quad(
+                window,
+                quad(
+                    Bounds::new(
+                        origin + point(px(center - box_width / 2.), px(box_top)),
+                        size(px(box_width), px((box_bottom - box_top).max(1.))),
+                    ),
+                    px(2.),
+                    cx.theme().background.blend(color.opacity(color.a * 0.3)),
+                    px(1.),
+                    color,
+                    BorderStyle::default(),
+                ),
+            );
+            export::paint_quad(
+                window,
+                fill(
+                    Bounds::new(
+                        origin + point(px(center - box_width / 2.), px(median - 1.)),
+                        size(px(box_width), px(2.)),
+                    ),
+                    color,
+                ),
+            );
+
+            for value in &stats.outliers {
+                export::paint_quad(
+                    window,
+                    quad(
+                        Bounds::centered_at(
+                            origin + point(px(center), px(y_at(*value))),
+                            size(px(OUTLIER_SIZE), px(OUTLIER_SIZE)),
+                        ),
+                        px(OUTLIER_SIZE / 2.),
+                        cx.theme().background,
+                        px(1.),
+                        color,
+                        BorderStyle::default(),
+                    ),
+                );
+            }
+        }
+        series_state.request_frame(window);
+
+        if let Some(interaction) = interaction {
+            let xs = x_ticks
+                .iter()
+                .map(|x_tick| x_tick.unwrap_or_default() + band_width / 2.)
+                .collect();
+            let hovered = interaction
+                .hovered()
+                .filter(|ix| !series_state.is_hidden(*ix));
+            let tooltip = hovered.and_then(|ix| {
+                let d = &self.data[ix];
+                let stats = stats[ix - range.start].as_ref()?;
+                let color = fill_at(ix, d);
+                let rows = [
+                    ("Max", stats.max),
+                    ("Q3", stats.q3),
+                    ("Median", stats.median),
+                    ("Q1", stats.q1),
+                    ("Min", stats.min),
+                ]
+                .into_iter()
+                .map(|(label, value)| {
+                    TooltipRow::new(color, Some(value), self.y_axis.as_ref(), y.tick(&value))
+                        .label(label)
+                })
+                .collect();
+                Some((x_fn(d).into(), rows))
+            });
+            interaction.paint(xs, height, tooltip, bounds, window, cx);
+        }
+    }
+}
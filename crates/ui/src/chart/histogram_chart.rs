@@ -0,0 +1,254 @@
+use std::rc::Rc;
+
+use gpui::{px, App, Bounds, Entity, Hsla, Pixels, SharedString, TextAlign, Window};
+use gpui_component_macros::IntoPlot;
+
+use super::{
+    interaction::{ChartInteraction, TooltipRow},
+    legend::SeriesState,
+    stats::{bins, Binning},
+    y_axis_labels, ChartPalette, ChartState,
+};
+
+use crate::{
+    format::format_compact,
+    plot::{
+        scale::{Scale, ScaleLinear},
+        shape::Bar,
+        Axis, AxisText, Grid, Plot, AXIS_GAP,
+    },
+    ActiveTheme, PixelsExt,
+};
+
+/// A histogram of the distribution of the values, the values are grouped in the bins of
+/// the equal width, and the height of the bar is the count of the values in the bin.
+#[derive(IntoPlot)]
+pub struct HistogramChart<T: 'static> {
+    data: Vec<T>,
+    value: Option<Rc<dyn Fn(&T) -> f64>>,
+    binning: Binning,
+    fill: Option<Hsla>,
+    palette: Option<ChartPalette>,
+    tick_margin: usize,
+    x_axis: Option<Rc<dyn Fn(f64) -> SharedString>>,
+    y_axis: Option<Rc<dyn Fn(f64) -> SharedString>>,
+    state: Option<Entity<ChartState>>,
+}
+
+impl<T> HistogramChart<T> {
+    pub fn new<I>(data: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Self {
+            data: data.into_iter().collect(),
+            value: None,
+            binning: Binning::default(),
+            fill: None,
+            palette: None,
+            tick_margin: 1,
+            x_axis: None,
+            y_axis: None,
+            state: None,
+        }
+    }
+
+    /// Set the value of the data to count in the bins.
+    pub fn value(mut self, value: impl Fn(&T) -> f64 + 'static) -> Self {
+        self.value = Some(Rc::new(value));
+        self
+    }
+
+    /// Set the binning of the values, default: [`Binning::Sturges`]
+    pub fn binning(mut self, binning: Binning) -> Self {
+        self.binning = binning;
+        self
+    }
+
+    /// Set the fill color of the bars.
+    pub fn fill(mut self, fill: impl Into<Hsla>) -> Self {
+        self.fill = Some(fill.into());
+        self
+    }
+
+    /// Fill the bars by the first color of the palette, if the `fill` is not set.
+    pub fn palette(mut self, palette: ChartPalette) -> Self {
+        self.palette = Some(palette);
+        self
+    }
+
+    /// Bind the [`ChartState`] to show the crosshair and the tooltip of the bins on hover,
+    /// zoom the x-axis by the wheel and select a range of the bins by dragging.
+    pub fn state(mut self, state: &Entity<ChartState>) -> Self {
+        self.state = Some(state.clone());
+        self
+    }
+
+    /// Show the label of every `tick_margin` bin edges on the x-axis.
+    pub fn tick_margin(mut self, tick_margin: usize) -> Self {
+        self.tick_margin = tick_margin;
+        self
+    }
+
+    /// Format the bin edges on the x-axis and the tooltip, default in the compact numbers.
+    pub fn x_axis_format<S>(mut self, format: impl Fn(f64) -> S + 'static) -> Self
+    where
+        S: Into<SharedString> + 'static,
+    {
+        self.x_axis = Some(Rc::new(move |value| format(value).into()));
+        self
+    }
+
+    /// Show the labels of the y-axis, formatted in the compact numbers of the current locale, e.g.: `1.2K`.
+    pub fn y_axis(self) -> Self {
+        self.y_axis_format(|value| format_compact(value))
+    }
+
+    /// Show the labels of the y-axis, formatted with the function.
+    pub fn y_axis_format<S>(mut self, format: impl Fn(f64) -> S + 'static) -> Self
+    where
+        S: Into<SharedString> + 'static,
+    {
+        self.y_axis = Some(Rc::new(move |value| format(value).into()));
+        self
+    }
+}
+
+impl<T> Plot for HistogramChart<T> {
+    fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
+        let Some(value_fn) = self.value.as_ref() else {
+            return;
+        };
+
+        let values = self.data.iter().map(|d| value_fn(d)).collect::<Vec<_>>();
+        let bins = bins(&values, self.binning);
+        if bins.is_empty() {
+            return;
+        }
+
+        let width = bounds.size.width.as_f32();
+        let height = bounds.size.height.as_f32() - AXIS_GAP;
+        let format_x = |value: f64| -> SharedString {
+            match self.x_axis.as_ref() {
+                Some(format) => format(value),
+                None => format_compact(value).into(),
+            }
+        };
+
+        let interaction = self
+            .state
+            .as_ref()
+            .map(|state| ChartInteraction::new(state, bins.len(), cx));
+        let range = interaction
+            .as_ref()
+            .map_or(0..bins.len(), |interaction| interaction.range());
+        let visible_bins = &bins[range.clone()];
+
+        // X scale of the values in the visible bins.
+        let x = ScaleLinear::new(
+            vec![
+                visible_bins[0].range.start,
+                visible_bins[visible_bins.len() - 1].range.end,
+            ],
+            vec![0., width],
+        );
+        let x_start = |ix: usize| x.tick(&visible_bins[ix].range.start).unwrap_or_default();
+        let x_end = |ix: usize| x.tick(&visible_bins[ix].range.end).unwrap_or(width);
+
+        // Y scale of the counts, ensure start from 0.
+        let y = ScaleLinear::new(
+            visible_bins
+                .iter()
+                .map(|bin| bin.count as f64)
+                .chain(Some(0.))
+                .collect(),
+            vec![height, 10.],
+        );
+
+        // Draw X axis with the bin edges.
+        let x_label = (0..visible_bins.len())
+            .filter(|ix| ix % self.tick_margin.max(1) == 0)
+            .map(|ix| {
+                AxisText::new(
+                    format_x(visible_bins[ix].range.start),
+                    x_start(ix),
+                    cx.theme().muted_foreground,
+                )
+                .align(TextAlign::Center)
+            });
+
+        Axis::new()
+            .x(height)
+            .x_label(x_label)
+            .stroke(cx.theme().border)
+            .paint(&bounds, window, cx);
+
+        // Draw grid
+        Grid::new()
+            .y((0..=3).map(|i| height * i as f32 / 4.0).collect())
+            .stroke(cx.theme().border)
+            .dash_array(&[px(4.), px(2.)])
+            .paint(&bounds, window);
+
+        // Draw Y axis labels
+        if let Some(format) = self.y_axis.as_ref() {
+            Axis::new()
+                .hide_x_axis()
+                .y(px(0.))
+                .y_label(y_axis_labels(
+                    &y,
+                    height,
+                    format.as_ref(),
+                    cx.theme().muted_foreground,
+                ))
+                .paint(&bounds, window, cx);
+        }
+
+        // Draw bars with a gap of 1px, grow from or shrink to the bottom in the transition.
+        let series_state = SeriesState::read(self.state.as_ref(), cx);
+        let visibility = series_state.visibility(0);
+        let opacity = series_state.opacity(0);
+        let fill = self
+            .fill
+            .or_else(|| self.palette.as_ref().map(|palette| palette.color(0, cx)))
+            .unwrap_or(cx.theme().chart_2);
+        let band_width = (x_end(0) - x_start(0) - 1.).max(1.);
+        let bar = Bar::new()
+            .data(
+                visible_bins
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, bin)| (x_start(ix), y.tick(&(bin.count as f64))))
+                    .collect::<Vec<_>>(),
+            )
+            .band_width(band_width)
+            .x(|(x, _)| Some(*x))
+            .y0(height)
+            .y1(move |(_, y)| y.map(|y| height - (height - y) * visibility))
+            .fill(move |_| fill.opacity(opacity));
+
+        if visibility > 0. {
+            bar.paint(&bounds, window, cx);
+        }
+        series_state.request_frame(window);
+
+        if let Some(interaction) = interaction {
+            let xs = (0..visible_bins.len())
+                .map(|ix| (x_start(ix) + x_end(ix)) / 2.)
+                .collect();
+            let hovered = interaction.hovered().filter(|_| !series_state.is_hidden(0));
+            let tooltip = hovered.map(|ix| {
+                let bin = &bins[ix];
+                let count = bin.count as f64;
+                let row = TooltipRow::new(fill, Some(count), self.y_axis.as_ref(), y.tick(&count));
+                let title = format!(
+                    "{} – {}",
+                    format_x(bin.range.start),
+                    format_x(bin.range.end)
+                );
+                (title.into(), vec![row])
+            });
+            interaction.paint(xs, height, tooltip, bounds, window, cx);
+        }
+    }
+}
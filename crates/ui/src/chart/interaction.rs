@@ -0,0 +1,625 @@
+use std::{ops::Range, rc::Rc};
+
+use gpui::{
+    fill, point, px, quad, size, App, BorderStyle, Bounds, Context, Entity, EventEmitter, Hsla,
+    MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels, Point, ScrollWheelEvent,
+    SharedString, TextRun, Window,
+};
+
+use super::legend::SeriesState;
+use crate::{export, format::format_compact, ActiveTheme, PixelsExt};
+
+const TOOLTIP_PADDING: Pixels = px(8.);
+const TOOLTIP_FONT_SIZE: Pixels = px(12.);
+const TOOLTIP_LINE_HEIGHT: Pixels = px(18.);
+const TOOLTIP_GAP: Pixels = px(12.);
+const CROSSHAIR_DOT_SIZE: Pixels = px(8.);
+/// The zoom factor of one line of the wheel.
+const WHEEL_ZOOM: f32 = 0.1;
+
+/// Events emitted by the [`ChartState`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChartEvent {
+    /// The hovered data index is changed.
+    Hover(Option<usize>),
+    /// The visible data range of the x-axis is changed by the wheel or the brush,
+    /// `None` if the zoom is reset.
+    Zoom(Option<Range<usize>>),
+    /// The data range is selected by dragging on the chart, e.g. for the drill-down.
+    Brush(Range<usize>),
+    /// The series is hidden or shown, e.g. by clicking the [`ChartLegend`](super::ChartLegend).
+    SeriesToggled { series: usize, hidden: bool },
+    /// The data is clicked, e.g. a slice of the [`PieChart`](super::PieChart) for the drill-down.
+    Click(usize),
+}
+
+/// The interaction state of the [`LineChart`](super::LineChart),
+/// [`AreaChart`](super::AreaChart) and [`BarChart`](super::BarChart).
+///
+/// - Hover to show the crosshair and the tooltip, the charts sharing the state show them
+///   at the same data index, e.g. the stacked charts of the same x-axis.
+/// - Scroll the wheel to zoom the x-axis at the mouse, double-click to reset.
+/// - Drag to select a data range, see [`ChartEvent::Brush`].
+/// - Hide, show or highlight the series by the [`ChartLegend`](super::ChartLegend).
+///
+/// ```ignore
+/// let state = cx.new(|_| ChartState::new().brush_zoom(true));
+///
+/// LineChart::new(data.clone()).x(|d| d.date.clone()).y(|d| d.cpu).state(&state);
+/// LineChart::new(data).x(|d| d.date.clone()).y(|d| d.memory).state(&state);
+/// ```
+#[derive(Default)]
+pub struct ChartState {
+    hovered: Option<usize>,
+    zoom: Option<Range<usize>>,
+    brush: Option<Range<usize>>,
+    brush_anchor: Option<usize>,
+    brush_zoom: bool,
+    /// The bounds of the chart under the mouse, to distinguish the charts sharing the state.
+    source: Option<Bounds<Pixels>>,
+    pub(super) series: SeriesState,
+}
+
+impl EventEmitter<ChartEvent> for ChartState {}
+
+impl ChartState {
+    /// Create a new chart state.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Zoom to the data range selected by dragging, default: false.
+    pub fn brush_zoom(mut self, brush_zoom: bool) -> Self {
+        self.brush_zoom = brush_zoom;
+        self
+    }
+
+    /// Returns the hovered data index.
+    pub fn hovered(&self) -> Option<usize> {
+        self.hovered
+    }
+
+    /// Returns the zoomed data range, `None` if not zoomed.
+    pub fn zoom(&self) -> Option<&Range<usize>> {
+        self.zoom.as_ref()
+    }
+
+    /// Returns the data range in selecting by dragging.
+    pub fn brush(&self) -> Option<&Range<usize>> {
+        self.brush.as_ref()
+    }
+
+    /// Set the hovered data index, to show the crosshair from the outside of the charts.
+    pub fn set_hovered(&mut self, ix: Option<usize>, cx: &mut Context<Self>) {
+        if self.hovered == ix {
+            return;
+        }
+
+        self.hovered = ix;
+        cx.emit(ChartEvent::Hover(ix));
+        cx.notify();
+    }
+
+    /// Zoom the x-axis to the data range.
+    pub fn zoom_to(&mut self, range: Range<usize>, cx: &mut Context<Self>) {
+        if range.is_empty() || self.zoom.as_ref() == Some(&range) {
+            return;
+        }
+
+        self.zoom = Some(range.clone());
+        cx.emit(ChartEvent::Zoom(Some(range)));
+        cx.notify();
+    }
+
+    /// Reset the zoom to show all the data.
+    pub fn reset_zoom(&mut self, cx: &mut Context<Self>) {
+        if self.zoom.take().is_some() {
+            cx.emit(ChartEvent::Zoom(None));
+            cx.notify();
+        }
+    }
+
+    /// Returns the visible data range of the `len` data.
+    pub(super) fn visible_range(&self, len: usize) -> Range<usize> {
+        match &self.zoom {
+            Some(range) if range.start < len => range.start..range.end.min(len),
+            _ => 0..len,
+        }
+    }
+
+    /// Zoom the visible range by the `factor` at the `anchor` data index, the anchor keeps
+    /// at the same position, e.g. 0.5 to zoom in twice.
+    fn zoom_by(&mut self, factor: f32, anchor: usize, len: usize, cx: &mut Context<Self>) {
+        let visible = self.visible_range(len);
+        if visible.is_empty() {
+            return;
+        }
+
+        let visible_len = visible.len() as f32;
+        // Round to at least one data, so the small wheel deltas still zoom.
+        let new_len = if factor < 1. {
+            (visible_len * factor).floor()
+        } else {
+            (visible_len * factor).ceil()
+        };
+        let new_len = (new_len as usize).clamp(2.min(len), len);
+        let ratio = (anchor.saturating_sub(visible.start)) as f32 / visible_len;
+        let start = (anchor as f32 - ratio * new_len as f32).round().max(0.) as usize;
+        let start = start.min(len - new_len);
+        let range = start..start + new_len;
+        if range == visible {
+            return;
+        }
+
+        if range == (0..len) {
+            self.reset_zoom(cx);
+        } else {
+            self.zoom_to(range, cx);
+        }
+    }
+
+    fn begin_brush(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.brush_anchor = Some(ix);
+        self.brush = Some(ix..ix + 1);
+        cx.notify();
+    }
+
+    fn update_brush(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let Some(anchor) = self.brush_anchor else {
+            return;
+        };
+
+        let range = anchor.min(ix)..anchor.max(ix) + 1;
+        if self.brush.as_ref() != Some(&range) {
+            self.brush = Some(range);
+            cx.notify();
+        }
+    }
+
+    /// Finish the brush, emits the [`ChartEvent::Brush`] if more than one data is selected.
+    fn end_brush(&mut self, cx: &mut Context<Self>) {
+        self.brush_anchor = None;
+        let Some(range) = self.brush.take() else {
+            return;
+        };
+
+        if range.len() > 1 {
+            cx.emit(ChartEvent::Brush(range.clone()));
+            if self.brush_zoom {
+                self.zoom_to(range, cx);
+            }
+        }
+        cx.notify();
+    }
+}
+
+/// A row of the tooltip, the color, the text and the y of the crosshair dot.
+pub(super) struct TooltipRow {
+    pub(super) color: Hsla,
+    pub(super) text: SharedString,
+    pub(super) y: Option<f32>,
+}
+
+impl TooltipRow {
+    /// The row of the value, formatted by the y-axis format of the chart, or in the compact
+    /// number if not set.
+    pub(super) fn new(
+        color: Hsla,
+        value: Option<f64>,
+        format: Option<&Rc<dyn Fn(f64) -> SharedString>>,
+        y: Option<f32>,
+    ) -> Self {
+        let text = match (value, format) {
+            (Some(value), Some(format)) => format(value),
+            (Some(value), None) => format_compact(value).into(),
+            (None, _) => "-".into(),
+        };
+        Self { color, text, y }
+    }
+
+    /// Prefix the text with the label, e.g. `Median: 12`.
+    pub(super) fn label(mut self, label: &str) -> Self {
+        self.text = format!("{}: {}", label, self.text).into();
+        self
+    }
+}
+
+/// The interactions of a chart with a [`ChartState`], for the data in the visible range.
+pub(super) struct ChartInteraction {
+    state: Entity<ChartState>,
+    len: usize,
+    range: Range<usize>,
+    hovered: Option<usize>,
+    brush: Option<Range<usize>>,
+}
+
+impl ChartInteraction {
+    pub(super) fn new(state: &Entity<ChartState>, len: usize, cx: &App) -> Self {
+        let chart_state = state.read(cx);
+        let range = chart_state.visible_range(len);
+        Self {
+            state: state.clone(),
+            len,
+            hovered: chart_state.hovered.filter(|ix| range.contains(ix)),
+            brush: chart_state.brush.clone(),
+            range,
+        }
+    }
+
+    /// Returns the visible data range.
+    pub(super) fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Returns the hovered data index in the visible range.
+    pub(super) fn hovered(&self) -> Option<usize> {
+        self.hovered
+    }
+
+    /// Paint the brush, the crosshair and the tooltip of the hovered data, and handle the mouse.
+    ///
+    /// The `xs` are the x of the visible data, relative to the `bounds`, and the `height` is
+    /// the height of the plot area.
+    pub(super) fn paint(
+        self,
+        xs: Vec<f32>,
+        height: f32,
+        tooltip: Option<(SharedString, Vec<TooltipRow>)>,
+        bounds: Bounds<Pixels>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        // The exported image is static, without the hover and the mouse.
+        if export::is_recording() {
+            return;
+        }
+
+        let origin = bounds.origin;
+
+        if let Some(brush) = self.brush.as_ref() {
+            let x_at = |ix: usize| xs.get(ix.checked_sub(self.range.start)?).copied();
+            if let (Some(start), Some(end)) = (x_at(brush.start), x_at(brush.end - 1)) {
+                window.paint_quad(fill(
+                    Bounds::new(
+                        origin + point(px(start), px(0.)),
+                        size(px((end - start).max(1.)), px(height)),
+                    ),
+                    cx.theme().primary.opacity(0.15),
+                ));
+            }
+        }
+
+        if let Some(x) = self
+            .hovered
+            .and_then(|ix| xs.get(ix - self.range.start).copied())
+        {
+            window.paint_quad(fill(
+                Bounds::new(origin + point(px(x), px(0.)), size(px(1.), px(height))),
+                cx.theme().muted_foreground.opacity(0.5),
+            ));
+
+            if let Some((title, rows)) = tooltip {
+                for row in &rows {
+                    if let Some(y) = row.y {
+                        let center = origin + point(px(x), px(y));
+                        window.paint_quad(quad(
+                            Bounds::centered_at(
+                                center,
+                                size(CROSSHAIR_DOT_SIZE, CROSSHAIR_DOT_SIZE),
+                            ),
+                            CROSSHAIR_DOT_SIZE / 2.,
+                            row.color,
+                            px(1.5),
+                            cx.theme().background,
+                            BorderStyle::default(),
+                        ));
+                    }
+                }
+                paint_tooltip(&title, &rows, x, bounds, window, cx);
+            }
+        }
+
+        self.register_mouse_events(xs, bounds, window);
+    }
+
+    fn register_mouse_events(self, xs: Vec<f32>, bounds: Bounds<Pixels>, window: &mut Window) {
+        let Self {
+            state, len, range, ..
+        } = self;
+        let index_at = move |x: Pixels| -> Option<usize> {
+            let x = (x - bounds.origin.x).as_f32();
+            xs.iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| (*a - x).abs().total_cmp(&(*b - x).abs()))
+                .map(|(ix, _)| range.start + ix)
+        };
+        let index_at = Rc::new(index_at);
+
+        window.on_mouse_event({
+            let state = state.clone();
+            let index_at = index_at.clone();
+            move |event: &MouseMoveEvent, phase, window, cx| {
+                if !phase.bubble() {
+                    return;
+                }
+
+                let hovered = bounds.contains(&event.position);
+                let changed = state.update(cx, |state, cx| {
+                    let prev = (state.hovered, state.brush.clone());
+                    if hovered {
+                        state.source = Some(bounds);
+                        let ix = index_at(event.position.x);
+                        state.set_hovered(ix, cx);
+                        if event.pressed_button == Some(MouseButton::Left) {
+                            if let Some(ix) = ix {
+                                state.update_brush(ix, cx);
+                            }
+                        }
+                    } else if state.source == Some(bounds) {
+                        state.source = None;
+                        state.set_hovered(None, cx);
+                    }
+                    prev != (state.hovered, state.brush.clone())
+                });
+                if changed {
+                    window.refresh();
+                }
+            }
+        });
+
+        window.on_mouse_event({
+            let state = state.clone();
+            let index_at = index_at.clone();
+            move |event: &MouseDownEvent, phase, window, cx| {
+                if !phase.bubble()
+                    || event.button != MouseButton::Left
+                    || !bounds.contains(&event.position)
+                {
+                    return;
+                }
+
+                state.update(cx, |state, cx| {
+                    if event.click_count == 2 {
+                        state.reset_zoom(cx);
+                    } else if let Some(ix) = index_at(event.position.x) {
+                        state.begin_brush(ix, cx);
+                    }
+                });
+                window.refresh();
+            }
+        });
+
+        window.on_mouse_event({
+            let state = state.clone();
+            move |_: &MouseUpEvent, phase, window, cx| {
+                if !phase.bubble() || state.read(cx).brush_anchor.is_none() {
+                    return;
+                }
+
+                state.update(cx, |state, cx| state.end_brush(cx));
+                window.refresh();
+            }
+        });
+
+        window.on_mouse_event(move |event: &ScrollWheelEvent, phase, window, cx| {
+            if !phase.bubble() || !bounds.contains(&event.position) {
+                return;
+            }
+            let Some(anchor) = index_at(event.position.x) else {
+                return;
+            };
+
+            let delta = event.delta.pixel_delta(window.line_height()).y / window.line_height();
+            if delta == 0. {
+                return;
+            }
+
+            state.update(cx, |state, cx| {
+                state.zoom_by((1. - delta * WHEEL_ZOOM).clamp(0.5, 2.), anchor, len, cx);
+            });
+            cx.stop_propagation();
+            window.refresh();
+        });
+    }
+}
+
+/// Handle the hover and the click of the charts without the x-axis, e.g. the [`PieChart`](super::PieChart),
+/// the `hit_test` returns the data index at the mouse position.
+pub(super) fn register_hit_events(
+    state: &Entity<ChartState>,
+    bounds: Bounds<Pixels>,
+    hit_test: impl Fn(Point<Pixels>) -> Option<usize> + 'static,
+    window: &mut Window,
+) {
+    if export::is_recording() {
+        return;
+    }
+
+    let hit_test = Rc::new(hit_test);
+
+    window.on_mouse_event({
+        let state = state.clone();
+        let hit_test = hit_test.clone();
+        move |event: &MouseMoveEvent, phase, window, cx| {
+            if !phase.bubble() {
+                return;
+            }
+
+            let changed = state.update(cx, |state, cx| {
+                let prev = state.hovered;
+                if bounds.contains(&event.position) {
+                    state.source = Some(bounds);
+                    state.set_hovered(hit_test(event.position), cx);
+                } else if state.source == Some(bounds) {
+                    state.source = None;
+                    state.set_hovered(None, cx);
+                }
+                prev != state.hovered
+            });
+            if changed {
+                window.refresh();
+            }
+        }
+    });
+
+    window.on_mouse_event({
+        let state = state.clone();
+        move |event: &MouseDownEvent, phase, _, cx| {
+            if !phase.bubble()
+                || event.button != MouseButton::Left
+                || !bounds.contains(&event.position)
+            {
+                return;
+            }
+
+            if let Some(ix) = hit_test(event.position) {
+                state.update(cx, |_, cx| cx.emit(ChartEvent::Click(ix)));
+            }
+        }
+    });
+}
+
+/// Paint the tooltip box beside the crosshair at the `x`, flipped to the left if overflow.
+fn paint_tooltip(
+    title: &SharedString,
+    rows: &[TooltipRow],
+    x: f32,
+    bounds: Bounds<Pixels>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let font = window.text_style().font();
+    let shape = |text: &SharedString, color: Hsla, window: &mut Window| {
+        let run = TextRun {
+            len: text.len(),
+            font: font.clone(),
+            color,
+            background_color: None,
+            underline: None,
+            strikethrough: None,
+        };
+        window
+            .text_system()
+            .shape_line(text.clone(), TOOLTIP_FONT_SIZE, &[run], None)
+    };
+
+    let title = shape(title, cx.theme().foreground, window);
+    let lines = rows
+        .iter()
+        .map(|row| {
+            (
+                row.color,
+                shape(&row.text, cx.theme().muted_foreground, window),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let swatch = px(8.);
+    let width = lines
+        .iter()
+        .map(|(_, line)| line.width + swatch + TOOLTIP_PADDING / 2.)
+        .fold(title.width, |a, b| a.max(b))
+        + TOOLTIP_PADDING * 2.;
+    let height = TOOLTIP_LINE_HEIGHT * (lines.len() + 1) as f32 + TOOLTIP_PADDING * 2.;
+
+    let mut left = bounds.origin.x + px(x) + TOOLTIP_GAP;
+    if left + width > bounds.right() {
+        left = bounds.origin.x + px(x) - TOOLTIP_GAP - width;
+    }
+    let tooltip_bounds = Bounds::new(
+        point(left.max(bounds.left()), bounds.top()),
+        size(width, height),
+    );
+
+    window.paint_layer(tooltip_bounds, |window| {
+        window.paint_quad(quad(
+            tooltip_bounds,
+            px(4.),
+            cx.theme().background.opacity(0.95),
+            px(1.),
+            cx.theme().border,
+            BorderStyle::default(),
+        ));
+
+        let mut origin = tooltip_bounds.origin + point(TOOLTIP_PADDING, TOOLTIP_PADDING);
+        let _ = title.paint(origin, TOOLTIP_LINE_HEIGHT, window, cx);
+        for (color, line) in lines {
+            origin.y += TOOLTIP_LINE_HEIGHT;
+            window.paint_quad(fill(
+                Bounds::new(
+                    origin + point(px(0.), (TOOLTIP_LINE_HEIGHT - swatch) / 2.),
+                    size(swatch, swatch),
+                ),
+                color,
+            ));
+            let _ = line.paint(
+                origin + point(swatch + TOOLTIP_PADDING / 2., px(0.)),
+                TOOLTIP_LINE_HEIGHT,
+                window,
+                cx,
+            );
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{AppContext as _, TestAppContext};
+
+    use super::{ChartEvent, ChartState};
+
+    #[gpui::test]
+    fn test_chart_state(cx: &mut TestAppContext) {
+        let state = cx.new(|_| ChartState::new().brush_zoom(true));
+        let events = std::rc::Rc::new(std::cell::RefCell::new(vec![]));
+        cx.update(|cx| {
+            let events = events.clone();
+            cx.subscribe(&state, move |_, event: &ChartEvent, _| {
+                events.borrow_mut().push(event.clone())
+            })
+            .detach();
+        });
+
+        state.update(cx, |state, cx| {
+            assert_eq!(state.visible_range(100), 0..100);
+
+            // Zoom in twice at the center, the anchor keeps at the same position.
+            state.zoom_by(0.5, 50, 100, cx);
+            assert_eq!(state.zoom(), Some(&(25..75)));
+            // Zoom out at the start.
+            state.zoom_by(1.5, 25, 100, cx);
+            assert_eq!(state.zoom(), Some(&(25..100)));
+            assert_eq!(state.visible_range(50), 25..50);
+            state.zoom_by(10., 50, 100, cx);
+            assert_eq!(state.zoom(), None);
+
+            // Brush from right to left.
+            state.begin_brush(30, cx);
+            state.update_brush(20, cx);
+            assert_eq!(state.brush(), Some(&(20..31)));
+            state.end_brush(cx);
+            assert_eq!(state.brush(), None);
+            assert_eq!(state.zoom(), Some(&(20..31)));
+
+            // A click is not a brush.
+            state.begin_brush(10, cx);
+            state.end_brush(cx);
+            assert_eq!(state.zoom(), Some(&(20..31)));
+
+            state.set_hovered(Some(3), cx);
+            state.set_hovered(Some(3), cx);
+        });
+
+        assert_eq!(
+            *events.borrow(),
+            vec![
+                ChartEvent::Zoom(Some(25..75)),
+                ChartEvent::Zoom(Some(25..100)),
+                ChartEvent::Zoom(None),
+                ChartEvent::Brush(20..31),
+                ChartEvent::Zoom(Some(20..31)),
+                ChartEvent::Hover(Some(3)),
+            ]
+        );
+    }
+}
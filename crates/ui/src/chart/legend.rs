@@ -0,0 +1,373 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    time::{Duration, Instant},
+};
+
+use gpui::{
+    div, ease_in_out, prelude::FluentBuilder as _, px, App, AppContext as _, Context, Corner,
+    ElementId, Entity, Hsla, InteractiveElement as _, IntoElement, ParentElement, RenderOnce,
+    SharedString, StatefulInteractiveElement as _, StyleRefinement, Styled, Window,
+};
+
+use super::{ChartEvent, ChartState};
+use crate::{
+    animation::MotionSettings,
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    popover::{Popover, PopoverContent},
+    v_flex, ActiveTheme as _, Sizable as _, StyledExt as _,
+};
+
+/// The duration of the transition to hide or show a series.
+const SERIES_TRANSITION: Duration = Duration::from_millis(300);
+/// The opacity of the other series when a series is highlighted.
+const DIMMED_OPACITY: f32 = 0.2;
+
+/// The hidden and the highlighted series of a [`ChartState`], toggled by the [`ChartLegend`].
+#[derive(Debug, Default, Clone)]
+pub(super) struct SeriesState {
+    hidden: BTreeSet<usize>,
+    highlighted: Option<usize>,
+    /// The time of the last toggle of the series, for the transition.
+    toggled_at: HashMap<usize, Instant>,
+}
+
+impl SeriesState {
+    /// Returns the series state of the chart, all the series are shown without the state.
+    pub(super) fn read(state: Option<&Entity<ChartState>>, cx: &App) -> Self {
+        state
+            .map(|state| state.read(cx).series.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns true if the series is hidden, even it's still in the transition.
+    pub(super) fn is_hidden(&self, ix: usize) -> bool {
+        self.hidden.contains(&ix)
+    }
+
+    /// Returns the visibility of the series from 0 (hidden) to 1 (shown), it's between
+    /// in the transition after toggled.
+    ///
+    /// The charts scale the values of the series by the visibility, to grow from or shrink to the baseline.
+    pub(super) fn visibility(&self, ix: usize) -> f32 {
+        let progress = self.toggled_at.get(&ix).map_or(1., |toggled_at| {
+            (toggled_at.elapsed().as_secs_f32() / SERIES_TRANSITION.as_secs_f32()).min(1.)
+        });
+        let progress = ease_in_out(progress);
+        if self.hidden.contains(&ix) {
+            1. - progress
+        } else {
+            progress
+        }
+    }
+
+    /// Returns the opacity of the series, the other series are dimmed when a series is highlighted.
+    pub(super) fn opacity(&self, ix: usize) -> f32 {
+        match self.highlighted {
+            Some(highlighted) if highlighted != ix => DIMMED_OPACITY,
+            _ => 1.,
+        }
+    }
+
+    /// Request the next frame if any series is in the transition.
+    pub(super) fn request_frame(&self, window: &mut Window) {
+        if self
+            .toggled_at
+            .values()
+            .any(|toggled_at| toggled_at.elapsed() < SERIES_TRANSITION)
+        {
+            window.request_animation_frame();
+        }
+    }
+}
+
+impl ChartState {
+    /// Returns true if the series at the index is hidden.
+    ///
+    /// The series are the `y` of the [`AreaChart`](super::AreaChart) in order, the slices
+    /// of the [`PieChart`](super::PieChart), the groups of the [`BoxPlotChart`](super::BoxPlotChart),
+    /// or the only series of the other charts.
+    pub fn is_series_hidden(&self, ix: usize) -> bool {
+        self.series.is_hidden(ix)
+    }
+
+    /// Hide or show the series, and emit the [`ChartEvent::SeriesToggled`].
+    pub fn set_series_hidden(&mut self, ix: usize, hidden: bool, cx: &mut Context<Self>) {
+        if self.is_series_hidden(ix) == hidden {
+            return;
+        }
+
+        if hidden {
+            self.series.hidden.insert(ix);
+        } else {
+            self.series.hidden.remove(&ix);
+        }
+        // Show or hide the series without the transition if the motion is reduced.
+        if MotionSettings::global(cx).is_full() {
+            self.series.toggled_at.insert(ix, Instant::now());
+        } else {
+            self.series.toggled_at.remove(&ix);
+        }
+        cx.emit(ChartEvent::SeriesToggled { series: ix, hidden });
+        cx.notify();
+    }
+
+    /// Toggle the series between hidden and shown.
+    pub fn toggle_series(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let hidden = !self.is_series_hidden(ix);
+        self.set_series_hidden(ix, hidden, cx);
+    }
+
+    /// Show all the hidden series.
+    pub fn show_all_series(&mut self, cx: &mut Context<Self>) {
+        for ix in self.series.hidden.clone() {
+            self.set_series_hidden(ix, false, cx);
+        }
+    }
+
+    /// Returns the highlighted series, e.g. the hovered legend item.
+    pub fn highlighted_series(&self) -> Option<usize> {
+        self.series.highlighted
+    }
+
+    /// Highlight the series, the other series are dimmed.
+    pub fn set_highlighted_series(&mut self, ix: Option<usize>, cx: &mut Context<Self>) {
+        if self.series.highlighted != ix {
+            self.series.highlighted = ix;
+            cx.notify();
+        }
+    }
+}
+
+/// A legend of the series of the charts with a [`ChartState`].
+///
+/// - Click an item to hide or show the series, with a transition.
+/// - Hover an item to highlight the series, the other series are dimmed.
+/// - The items more than the [`max_items`](Self::max_items) are in a popover.
+///
+/// ```ignore
+/// v_flex()
+///     .child(AreaChart::new(data).x(|d| d.date.clone()).y(|d| d.desktop).y(|d| d.mobile).state(&state))
+///     .child(ChartLegend::new("legend", &state).item("Desktop", desktop_color).item("Mobile", mobile_color))
+/// ```
+#[derive(IntoElement)]
+pub struct ChartLegend {
+    id: ElementId,
+    state: Entity<ChartState>,
+    style: StyleRefinement,
+    items: Vec<(SharedString, Hsla)>,
+    max_items: usize,
+}
+
+impl ChartLegend {
+    /// Create a legend of the series of the charts with the state.
+    pub fn new(id: impl Into<ElementId>, state: &Entity<ChartState>) -> Self {
+        Self {
+            id: id.into(),
+            state: state.clone(),
+            style: StyleRefinement::default(),
+            items: vec![],
+            max_items: 8,
+        }
+    }
+
+    /// Add an item of the next series, with the label and the color of the series.
+    pub fn item(mut self, label: impl Into<SharedString>, color: impl Into<Hsla>) -> Self {
+        self.items.push((label.into(), color.into()));
+        self
+    }
+
+    /// Add the items of the series in order.
+    pub fn items<S, C>(mut self, items: impl IntoIterator<Item = (S, C)>) -> Self
+    where
+        S: Into<SharedString>,
+        C: Into<Hsla>,
+    {
+        self.items.extend(
+            items
+                .into_iter()
+                .map(|(label, color)| (label.into(), color.into())),
+        );
+        self
+    }
+
+    /// Set the max number of the items shown inline, the rest are in a popover, default: 8
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.max_items = max_items;
+        self
+    }
+}
+
+impl Styled for ChartLegend {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+/// Render a legend item to toggle the series by click and highlight it by hover.
+fn legend_item(
+    state: &Entity<ChartState>,
+    ix: usize,
+    label: SharedString,
+    color: Hsla,
+    cx: &App,
+) -> impl IntoElement {
+    let hidden = state.read(cx).is_series_hidden(ix);
+
+    h_flex()
+        .id(("legend-item", ix))
+        .gap_1p5()
+        .cursor_pointer()
+        .when(hidden, |this| {
+            this.text_color(cx.theme().muted_foreground).line_through()
+        })
+        .child(div().size_2().flex_shrink_0().rounded_full().map(|this| {
+            if hidden {
+                this.border_1().border_color(color)
+            } else {
+                this.bg(color)
+            }
+        }))
+        .child(label)
+        .on_click({
+            let state = state.clone();
+            move |_, window, cx| {
+                state.update(cx, |state, cx| state.toggle_series(ix, cx));
+                window.refresh();
+            }
+        })
+        .on_hover({
+            let state = state.clone();
+            move |hovered, window, cx| {
+                state.update(cx, |state, cx| {
+                    if *hovered {
+                        state.set_highlighted_series(Some(ix), cx);
+                    } else if state.highlighted_series() == Some(ix) {
+                        state.set_highlighted_series(None, cx);
+                    }
+                });
+                window.refresh();
+            }
+        })
+}
+
+impl RenderOnce for ChartLegend {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let state = self.state;
+        let overflow_items = self
+            .items
+            .iter()
+            .enumerate()
+            .skip(self.max_items)
+            .map(|(ix, (label, color))| (ix, label.clone(), *color))
+            .collect::<Vec<_>>();
+
+        h_flex()
+            .id(self.id)
+            .flex_wrap()
+            .justify_center()
+            .gap_x_4()
+            .gap_y_1()
+            .text_xs()
+            .refine_style(&self.style)
+            .children(
+                self.items
+                    .into_iter()
+                    .take(self.max_items)
+                    .enumerate()
+                    .map(|(ix, (label, color))| legend_item(&state, ix, label, color, cx)),
+            )
+            .when(!overflow_items.is_empty(), |this| {
+                this.child(
+                    Popover::new("legend-more")
+                        .anchor(Corner::TopRight)
+                        .trigger(
+                            Button::new("legend-more-button")
+                                .ghost()
+                                .xsmall()
+                                .label(format!("+{}", overflow_items.len())),
+                        )
+                        .content(move |window, cx| {
+                            let state = state.clone();
+                            let items = overflow_items.clone();
+                            cx.new(|cx| {
+                                PopoverContent::new(window, cx, move |_, cx| {
+                                    v_flex()
+                                        .gap_1()
+                                        .min_w(px(120.))
+                                        .text_xs()
+                                        .children(items.iter().map(|(ix, label, color)| {
+                                            legend_item(&state, *ix, label.clone(), *color, cx)
+                                        }))
+                                        .into_any_element()
+                                })
+                            })
+                        }),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{AppContext as _, TestAppContext};
+
+    use super::super::{ChartEvent, ChartState};
+
+    #[gpui::test]
+    fn test_series_toggle(cx: &mut TestAppContext) {
+        let state = cx.new(|_| ChartState::new());
+        let events = std::rc::Rc::new(std::cell::RefCell::new(vec![]));
+        cx.update(|cx| {
+            let events = events.clone();
+            cx.subscribe(&state, move |_, event: &ChartEvent, _| {
+                events.borrow_mut().push(event.clone())
+            })
+            .detach();
+        });
+
+        state.update(cx, |state, cx| {
+            assert_eq!(state.series.visibility(0), 1.);
+
+            state.toggle_series(1, cx);
+            assert!(state.is_series_hidden(1));
+            assert!(!state.is_series_hidden(0));
+            // Hiding is in the transition.
+            assert!(state.series.visibility(1) > 0.);
+            state.set_series_hidden(1, true, cx);
+
+            state.set_highlighted_series(Some(0), cx);
+            assert_eq!(state.series.opacity(0), 1.);
+            assert!(state.series.opacity(2) < 1.);
+            state.set_highlighted_series(None, cx);
+            assert_eq!(state.series.opacity(2), 1.);
+
+            state.toggle_series(2, cx);
+            state.show_all_series(cx);
+            assert!(!state.is_series_hidden(1));
+            assert!(!state.is_series_hidden(2));
+        });
+
+        assert_eq!(
+            *events.borrow(),
+            vec![
+                ChartEvent::SeriesToggled {
+                    series: 1,
+                    hidden: true
+                },
+                ChartEvent::SeriesToggled {
+                    series: 2,
+                    hidden: true
+                },
+                ChartEvent::SeriesToggled {
+                    series: 1,
+                    hidden: false
+                },
+                ChartEvent::SeriesToggled {
+                    series: 2,
+                    hidden: false
+                },
+            ]
+        );
+    }
+}
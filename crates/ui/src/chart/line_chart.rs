@@ -1,12 +1,22 @@
 use std::rc::Rc;
 
-use gpui::{px, App, Bounds, Hsla, Pixels, SharedString, TextAlign, Window};
+use chrono::NaiveDateTime;
+use gpui::{point, px, App, Bounds, Entity, Hsla, Pixels, SharedString, TextAlign, Window};
 use gpui_component_macros::IntoPlot;
 use num_traits::{Num, ToPrimitive};
 
+use super::{
+    interaction::{ChartInteraction, TooltipRow},
+    legend::SeriesState,
+    stream::StreamTimeline,
+    AxisScale, ChartPalette, ChartState, ChartStream, SeriesStyle, YScale,
+};
+
 use crate::{
+    export,
+    format::format_compact,
     plot::{
-        scale::{Scale, ScaleLinear, ScalePoint, Sealed},
+        scale::{Scale, ScalePoint, ScaleTime, Sealed},
         shape::Line,
         Axis, AxisText, Grid, Plot, StrokeStyle, AXIS_GAP,
     },
@@ -25,8 +35,16 @@ where
     y: Option<Rc<dyn Fn(&T) -> Y>>,
     stroke: Option<Hsla>,
     stroke_style: StrokeStyle,
+    palette: Option<ChartPalette>,
+    style: SeriesStyle,
     dot: bool,
     tick_margin: usize,
+    y_axis: Option<Rc<dyn Fn(f64) -> SharedString>>,
+    y_scale: AxisScale,
+    x_time: Option<Rc<dyn Fn(&T) -> NaiveDateTime>>,
+    x_axis: Option<Rc<dyn Fn(&NaiveDateTime) -> SharedString>>,
+    stream: Option<StreamTimeline>,
+    state: Option<Entity<ChartState>>,
 }
 
 impl<T, X, Y> LineChart<T, X, Y>
@@ -42,10 +60,18 @@ where
             data: data.into_iter().collect(),
             stroke: None,
             stroke_style: Default::default(),
+            palette: None,
+            style: SeriesStyle::default(),
             dot: false,
             x: None,
             y: None,
             tick_margin: 1,
+            y_axis: None,
+            y_scale: AxisScale::default(),
+            x_time: None,
+            x_axis: None,
+            stream: None,
+            state: None,
         }
     }
 
@@ -59,6 +85,33 @@ where
         self
     }
 
+    /// Set the stroke color of the line, default is the `chart_2` of the theme.
+    pub fn stroke(mut self, stroke: impl Into<Hsla>) -> Self {
+        self.stroke = Some(stroke.into());
+        self
+    }
+
+    /// Use the first color of the palette as the stroke color.
+    pub fn palette(mut self, palette: ChartPalette) -> Self {
+        self.palette = Some(palette);
+        self
+    }
+
+    /// Override the color, width, dash and marker of the line.
+    pub fn series_style(mut self, style: SeriesStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Bind the [`ChartState`] to show the crosshair and the tooltip on hover, zoom the x-axis
+    /// by the wheel and select a range by dragging, not work in the streaming mode.
+    ///
+    /// The charts sharing the state show the crosshair at the same data index.
+    pub fn state(mut self, state: &Entity<ChartState>) -> Self {
+        self.state = Some(state.clone());
+        self
+    }
+
     pub fn natural(mut self) -> Self {
         self.stroke_style = StrokeStyle::Natural;
         self
@@ -83,6 +136,65 @@ where
         self.tick_margin = tick_margin;
         self
     }
+    /// Show the labels of the y-axis, formatted in the compact numbers of the current locale, e.g.: `1.2K`.
+    pub fn y_axis(self) -> Self {
+        self.y_axis_format(|value| format_compact(value))
+    }
+
+    /// Show the labels of the y-axis, formatted with the function.
+    pub fn y_axis_format<S>(mut self, format: impl Fn(f64) -> S + 'static) -> Self
+    where
+        S: Into<SharedString> + 'static,
+    {
+        self.y_axis = Some(Rc::new(move |value| format(value).into()));
+        self
+    }
+
+    /// Set the scale of the y-axis, default: [`AxisScale::Linear`]
+    pub fn y_scale(mut self, y_scale: AxisScale) -> Self {
+        self.y_scale = y_scale;
+        self
+    }
+
+    /// Format the time labels of the x-axis set by the [`x_time`](LineChart::x_time), default
+    /// in the format of the tick interval, e.g. `%H:%M` for the hours.
+    pub fn x_axis_format<S>(mut self, format: impl Fn(&NaiveDateTime) -> S + 'static) -> Self
+    where
+        S: Into<SharedString> + 'static,
+    {
+        self.x_axis = Some(Rc::new(move |time| format(time).into()));
+        self
+    }
+}
+
+impl<T, Y> LineChart<T, SharedString, Y>
+where
+    T: Clone,
+    Y: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
+{
+    /// Set the time of the data on the x-axis, the points are placed by the time and the ticks
+    /// are at the nice intervals, e.g. every 15 minutes, instead of at each data.
+    pub fn x_time(mut self, time: impl Fn(&T) -> NaiveDateTime + 'static) -> Self {
+        let time = Rc::new(time);
+        self.x = Some(Rc::new({
+            let time = time.clone();
+            move |d| time(d).format("%Y-%m-%d %H:%M:%S").to_string().into()
+        }));
+        self.x_time = Some(time);
+        self
+    }
+
+    /// Create a line chart in the streaming mode, shows the items of the [`ChartStream`]
+    /// in its time window, the x-axis is the time and the `x` is not required.
+    ///
+    /// The dense items are decimated to at most one point per pixel.
+    pub fn stream(stream: &ChartStream<T>) -> Self {
+        let (timeline, data) = stream.snapshot();
+        Self {
+            stream: Some(timeline),
+            ..Self::new(data)
+        }
+    }
 }
 
 impl<T, X, Y> Plot for LineChart<T, X, Y>
@@ -91,78 +203,188 @@ where
     Y: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
 {
     fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
-        let (Some(x_fn), Some(y_fn)) = (self.x.as_ref(), self.y.as_ref()) else {
+        let Some(y_fn) = self.y.as_ref() else {
             return;
         };
+        let x_fn = match (&self.stream, self.x.as_ref()) {
+            (None, None) => return,
+            (_, x_fn) => x_fn,
+        };
 
         let width = bounds.size.width.as_f32();
         let height = bounds.size.height.as_f32() - AXIS_GAP;
 
-        // X scale
-        let x = ScalePoint::new(self.data.iter().map(|v| x_fn(v)).collect(), vec![0., width]);
+        let interaction = match (&self.state, &self.stream) {
+            (Some(state), None) => Some(ChartInteraction::new(state, self.data.len(), cx)),
+            _ => None,
+        };
+        let data = match &interaction {
+            Some(interaction) => &self.data[interaction.range()],
+            None => &self.data[..],
+        };
 
-        // Y scale, ensure start from 0.
-        let y = ScaleLinear::new(
-            self.data
-                .iter()
-                .map(|v| y_fn(v))
-                .chain(Some(Y::zero()))
-                .collect(),
+        // Y scale, ensure start from 0 in the linear scale.
+        let y = YScale::new(
+            self.y_scale,
+            data.iter().filter_map(|v| y_fn(v).to_f64()),
             vec![height, 10.],
         );
+        let y_tick = |d: &T| y_fn(d).to_f64().and_then(|value| y.tick(value));
 
         // Draw X axis
-        let data_len = self.data.len();
-        let x_label = self.data.iter().enumerate().filter_map(|(i, d)| {
-            if (i + 1) % self.tick_margin == 0 {
-                x.tick(&x_fn(d)).map(|x_tick| {
-                    let align = match i {
-                        0 => {
-                            if data_len == 1 {
-                                TextAlign::Center
-                            } else {
-                                TextAlign::Left
-                            }
-                        }
-                        i if i == data_len - 1 => TextAlign::Right,
-                        _ => TextAlign::Center,
-                    };
-                    AxisText::new(x_fn(d).into(), x_tick, cx.theme().muted_foreground).align(align)
+        let (points, xs) = if let Some(stream) = &self.stream {
+            Axis::new()
+                .x(height)
+                .x_label(stream.x_labels(width, cx.theme().muted_foreground))
+                .stroke(cx.theme().border)
+                .paint(&bounds, window, cx);
+
+            (stream.points(data, y_tick, width), vec![])
+        } else if let Some(time_fn) = self.x_time.as_ref() {
+            // X scale of the time, about one tick per 80px.
+            let x = ScaleTime::new(data.iter().map(|d| time_fn(d)).collect(), vec![0., width]);
+            let (interval, ticks) = x.ticks((width / 80.).max(2.) as usize);
+            let x_label = ticks.iter().filter_map(|time| {
+                let text = match self.x_axis.as_ref() {
+                    Some(format) => format(time),
+                    None => time.format(interval.format()).to_string().into(),
+                };
+                x.tick(time).map(|x_tick| {
+                    AxisText::new(text, x_tick, cx.theme().muted_foreground)
+                        .align(TextAlign::Center)
                 })
-            } else {
-                None
-            }
-        });
-
-        Axis::new()
-            .x(height)
-            .x_label(x_label)
-            .stroke(cx.theme().border)
-            .paint(&bounds, window, cx);
+            });
+
+            Axis::new()
+                .x(height)
+                .x_label(x_label)
+                .stroke(cx.theme().border)
+                .paint(&bounds, window, cx);
+
+            (
+                data.iter()
+                    .filter_map(|d| Some(point(x.tick(&time_fn(d))?, y_tick(d)?)))
+                    .collect(),
+                data.iter()
+                    .map(|d| x.tick(&time_fn(d)).unwrap_or_default())
+                    .collect(),
+            )
+        } else {
+            let Some(x_fn) = x_fn else {
+                return;
+            };
+
+            // X scale
+            let x = ScalePoint::new(data.iter().map(|v| x_fn(v)).collect(), vec![0., width]);
+
+            let data_len = data.len();
+            let x_label = data.iter().enumerate().filter_map(|(i, d)| {
+                if (i + 1) % self.tick_margin == 0 {
+                    x.tick(&x_fn(d)).map(|x_tick| {
+                        let align = match i {
+                            0 => {
+                                if data_len == 1 {
+                                    TextAlign::Center
+                                } else {
+                                    TextAlign::Left
+                                }
+                            }
+                            i if i == data_len - 1 => TextAlign::Right,
+                            _ => TextAlign::Center,
+                        };
+                        AxisText::new(x_fn(d).into(), x_tick, cx.theme().muted_foreground)
+                            .align(align)
+                    })
+                } else {
+                    None
+                }
+            });
+
+            Axis::new()
+                .x(height)
+                .x_label(x_label)
+                .stroke(cx.theme().border)
+                .paint(&bounds, window, cx);
+
+            (
+                data.iter()
+                    .filter_map(|d| Some(point(x.tick(&x_fn(d))?, y_tick(d)?)))
+                    .collect(),
+                data.iter()
+                    .map(|d| x.tick(&x_fn(d)).unwrap_or_default())
+                    .collect(),
+            )
+        };
 
         // Draw grid
         Grid::new()
-            .y((0..=3).map(|i| height * i as f32 / 4.0).collect())
+            .y(y.grid(height))
             .stroke(cx.theme().border)
             .dash_array(&[px(4.), px(2.)])
             .paint(&bounds, window);
 
-        // Draw line
-        let stroke = self.stroke.unwrap_or(cx.theme().chart_2);
-        let x_fn = x_fn.clone();
-        let y_fn = y_fn.clone();
+        // Draw Y axis labels
+        if let Some(format) = self.y_axis.as_ref() {
+            Axis::new()
+                .hide_x_axis()
+                .y(px(0.))
+                .y_label(y.labels(height, format.as_ref(), cx.theme().muted_foreground))
+                .paint(&bounds, window, cx);
+        }
+
+        // Draw line, grow from or shrink to the bottom in the transition of hiding or showing.
+        let series_state = SeriesState::read(self.state.as_ref(), cx);
+        let visibility = series_state.visibility(0);
+        let points = points
+            .into_iter()
+            .map(|p| point(p.x, height - (height - p.y) * visibility))
+            .collect::<Vec<_>>();
+        let stroke = self
+            .style
+            .color
+            .or(self.stroke)
+            .or_else(|| self.palette.as_ref().map(|palette| palette.color(0, cx)))
+            .unwrap_or(cx.theme().chart_2);
         let mut line = Line::new()
-            .data(&self.data)
-            .x(move |d| x.tick(&x_fn(d)))
-            .y(move |d| y.tick(&y_fn(d)))
-            .stroke(stroke)
+            .data(points)
+            .x(|p| Some(p.x))
+            .y(|p| Some(p.y))
+            .stroke(stroke.opacity(series_state.opacity(0)))
             .stroke_style(self.stroke_style)
-            .stroke_width(2.);
+            .stroke_width(self.style.width.unwrap_or(px(2.)));
+
+        if let Some(dash) = &self.style.dash {
+            line = line.dash_array(dash);
+        }
+        if self.dot || self.style.marker.is_some() {
+            line = line
+                .dot()
+                .dot_marker(self.style.marker.unwrap_or_default())
+                .dot_size(8.)
+                .dot_fill_color(stroke.opacity(series_state.opacity(0)));
+        }
 
-        if self.dot {
-            line = line.dot().dot_size(8.).dot_fill_color(stroke);
+        // The line of the stream enters from the left edge.
+        if visibility > 0. {
+            export::with_content_mask(window, bounds, |window| {
+                line.paint(&bounds, window);
+            });
         }
 
-        line.paint(&bounds, window);
+        if let Some(stream) = &self.stream {
+            stream.request_frame(window);
+        }
+        series_state.request_frame(window);
+
+        if let Some(interaction) = interaction {
+            let hovered = interaction.hovered().filter(|_| !series_state.is_hidden(0));
+            let tooltip = hovered.zip(x_fn).map(|(ix, x_fn)| {
+                let d = &self.data[ix];
+                let value = y_fn(d);
+                let row = TooltipRow::new(stroke, value.to_f64(), self.y_axis.as_ref(), y_tick(d));
+                (x_fn(d).into(), vec![row])
+            });
+            interaction.paint(xs, height, tooltip, bounds, window, cx);
+        }
     }
 }
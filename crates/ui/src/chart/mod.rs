@@ -1,9 +1,121 @@
 mod area_chart;
 mod bar_chart;
+mod box_plot_chart;
+mod histogram_chart;
+mod interaction;
+mod legend;
 mod line_chart;
+mod palette;
 mod pie_chart;
+mod series;
+mod stats;
+mod stream;
 
 pub use area_chart::AreaChart;
 pub use bar_chart::BarChart;
+pub use box_plot_chart::BoxPlotChart;
+pub use histogram_chart::HistogramChart;
+pub use interaction::{ChartEvent, ChartState};
+pub use legend::ChartLegend;
 pub use line_chart::LineChart;
-pub use pie_chart::PieChart;
+pub use palette::{contrast_color, contrast_ratio, relative_luminance, ChartPalette, PaletteKind};
+pub use pie_chart::{PieChart, PieLabelPosition};
+pub use series::SeriesStyle;
+pub use stats::Binning;
+pub use stream::ChartStream;
+
+use gpui::{px, Hsla, SharedString};
+use num_traits::{Num, ToPrimitive};
+
+use crate::plot::{
+    label::TEXT_GAP,
+    scale::{Scale, ScaleLinear, ScaleLog, Sealed},
+    AxisText,
+};
+
+/// The scale of the y-axis.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum AxisScale {
+    /// The linear scale starts from 0.
+    #[default]
+    Linear,
+    /// The logarithmic scale of base 10, extended to the powers of 10, the non-positive
+    /// values are not drawn.
+    Log,
+}
+
+/// The y scale of the [`AxisScale`].
+enum YScale {
+    Linear(ScaleLinear<f64>),
+    Log(ScaleLog),
+}
+
+impl YScale {
+    fn new(scale: AxisScale, values: impl Iterator<Item = f64>, range: Vec<f32>) -> Self {
+        match scale {
+            AxisScale::Linear => {
+                Self::Linear(ScaleLinear::new(values.chain(Some(0.)).collect(), range))
+            }
+            AxisScale::Log => Self::Log(ScaleLog::new(values.collect(), range).nice()),
+        }
+    }
+
+    fn tick(&self, value: f64) -> Option<f32> {
+        match self {
+            Self::Linear(scale) => scale.tick(&value),
+            Self::Log(scale) => scale.tick(&value),
+        }
+    }
+
+    /// Returns the y of the grid lines, at the quarters of the `height` or the powers of 10.
+    fn grid(&self, height: f32) -> Vec<f32> {
+        match self {
+            Self::Linear(_) => (0..=3).map(|i| height * i as f32 / 4.0).collect(),
+            Self::Log(scale) => scale
+                .ticks()
+                .iter()
+                .filter_map(|value| scale.tick(value))
+                .filter(|y| *y < height)
+                .collect(),
+        }
+    }
+
+    /// Returns the labels of the y-axis below the grid lines.
+    fn labels(
+        &self,
+        height: f32,
+        format: &dyn Fn(f64) -> SharedString,
+        color: Hsla,
+    ) -> Vec<AxisText> {
+        match self {
+            Self::Linear(scale) => y_axis_labels(scale, height, format, color),
+            Self::Log(scale) => scale
+                .ticks()
+                .into_iter()
+                .filter_map(|value| {
+                    let tick = scale.tick(&value).filter(|y| *y < height)?;
+                    Some(AxisText::new(format(value), px(tick + TEXT_GAP), color))
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Returns the labels of the y-axis, below the grid lines at the quarters of the `height`.
+fn y_axis_labels<Y>(
+    y: &ScaleLinear<Y>,
+    height: f32,
+    format: &dyn Fn(f64) -> SharedString,
+    color: Hsla,
+) -> Vec<AxisText>
+where
+    Y: Copy + PartialOrd + Num + ToPrimitive + Sealed,
+{
+    (0..=3)
+        .filter_map(|i| {
+            let tick = height * i as f32 / 4.0;
+            y.invert(tick)
+                .map(|value| AxisText::new(format(value), px(tick + TEXT_GAP), color))
+        })
+        .collect()
+}
@@ -0,0 +1,238 @@
+use gpui::{App, Hsla, Rgba};
+
+use crate::{hsl, ActiveTheme as _, Colorize as _};
+
+/// The colorblind-safe categorical colors by Okabe and Ito.
+///
+/// https://jfly.uni-koeln.de/color/
+const OKABE_ITO: [(f32, f32, f32); 7] = [
+    (41., 100., 45.),  // Orange
+    (202., 77., 63.),  // Sky blue
+    (164., 100., 31.), // Bluish green
+    (56., 85., 60.),   // Yellow
+    (202., 100., 35.), // Blue
+    (27., 100., 42.),  // Vermillion
+    (326., 42., 64.),  // Reddish purple
+];
+
+/// The kind of a [`ChartPalette`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteKind {
+    /// Distinct colors for the unordered categories, e.g. the series of a chart.
+    Categorical,
+    /// Colors from light to dark for the ordered values, e.g. a heatmap.
+    Sequential,
+    /// Colors from one hue to another through a neutral middle, e.g. the values above and below zero.
+    Diverging,
+}
+
+/// The colors of the chart series, derived from the theme when painting.
+///
+/// ```ignore
+/// AreaChart::new(data)
+///     .palette(ChartPalette::categorical().colorblind_safe())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartPalette {
+    kind: PaletteKind,
+    colorblind_safe: bool,
+    steps: Option<usize>,
+    colors: Option<Vec<Hsla>>,
+}
+
+impl ChartPalette {
+    /// The categorical palette of the theme colors: blue, green, yellow, magenta, cyan and red.
+    pub fn categorical() -> Self {
+        Self::new(PaletteKind::Categorical)
+    }
+
+    /// The sequential palette of the `chart_1` to `chart_5` of the theme.
+    pub fn sequential() -> Self {
+        Self::new(PaletteKind::Sequential)
+    }
+
+    /// The diverging palette from the `red` to the `green` of the theme, through the `muted` color.
+    pub fn diverging() -> Self {
+        Self::new(PaletteKind::Diverging)
+    }
+
+    /// A palette of the custom colors.
+    pub fn custom(colors: impl IntoIterator<Item = impl Into<Hsla>>) -> Self {
+        Self {
+            colors: Some(colors.into_iter().map(Into::into).collect()),
+            ..Self::new(PaletteKind::Categorical)
+        }
+    }
+
+    fn new(kind: PaletteKind) -> Self {
+        Self {
+            kind,
+            colorblind_safe: false,
+            steps: None,
+            colors: None,
+        }
+    }
+
+    /// Use the colors distinguishable with the color vision deficiency.
+    ///
+    /// - Categorical: the Okabe-Ito colors.
+    /// - Sequential: the shades of the `blue` of the theme.
+    /// - Diverging: from the orange to the `blue` of the theme.
+    pub fn colorblind_safe(mut self) -> Self {
+        self.colorblind_safe = true;
+        self
+    }
+
+    /// Set the number of the colors of the sequential and diverging palettes, default: 5.
+    pub fn steps(mut self, steps: usize) -> Self {
+        self.steps = Some(steps.max(1));
+        self
+    }
+
+    /// Returns the kind of the palette.
+    pub fn kind(&self) -> PaletteKind {
+        self.kind
+    }
+
+    /// Returns the colors of the palette in the current theme.
+    pub fn colors(&self, cx: &App) -> Vec<Hsla> {
+        if let Some(colors) = &self.colors {
+            return colors.clone();
+        }
+
+        let theme = cx.theme();
+        let steps = self.steps.unwrap_or(5);
+        match (self.kind, self.colorblind_safe) {
+            (PaletteKind::Categorical, false) => vec![
+                theme.blue,
+                theme.green,
+                theme.yellow,
+                theme.magenta,
+                theme.cyan,
+                theme.red,
+            ],
+            (PaletteKind::Categorical, true) => {
+                OKABE_ITO.iter().map(|(h, s, l)| hsl(*h, *s, *l)).collect()
+            }
+            (PaletteKind::Sequential, false) => {
+                let stops = [
+                    theme.chart_1,
+                    theme.chart_2,
+                    theme.chart_3,
+                    theme.chart_4,
+                    theme.chart_5,
+                ];
+                ramp(&stops, steps)
+            }
+            (PaletteKind::Sequential, true) => ramp(
+                &[theme.blue.lightness(0.85), theme.blue.lightness(0.3)],
+                steps,
+            ),
+            (PaletteKind::Diverging, false) => ramp(&[theme.red, theme.muted, theme.green], steps),
+            (PaletteKind::Diverging, true) => {
+                let (h, s, l) = OKABE_ITO[5];
+                ramp(&[hsl(h, s, l), theme.muted, theme.blue], steps)
+            }
+        }
+    }
+
+    /// Returns the color at the index, the colors are repeated for the index out of the palette.
+    pub fn color(&self, ix: usize, cx: &App) -> Hsla {
+        let colors = self.colors(cx);
+        if colors.is_empty() {
+            return cx.theme().chart_2;
+        }
+        colors[ix % colors.len()]
+    }
+}
+
+/// Interpolate the `stops` evenly into the `steps` colors.
+fn ramp(stops: &[Hsla], steps: usize) -> Vec<Hsla> {
+    if steps == 1 || stops.len() == 1 {
+        return stops.iter().copied().take(steps).collect();
+    }
+
+    (0..steps)
+        .map(|ix| {
+            let t = ix as f32 / (steps - 1) as f32 * (stops.len() - 1) as f32;
+            let start = (t.floor() as usize).min(stops.len() - 2);
+            lerp(stops[start], stops[start + 1], t - start as f32)
+        })
+        .collect()
+}
+
+/// Interpolate the colors in the RGB space, `t` is the factor of the `b`.
+fn lerp(a: Hsla, b: Hsla, t: f32) -> Hsla {
+    let (a, b) = (a.to_rgb(), b.to_rgb());
+    Rgba {
+        r: a.r + (b.r - a.r) * t,
+        g: a.g + (b.g - a.g) * t,
+        b: a.b + (b.b - a.b) * t,
+        a: a.a + (b.a - a.a) * t,
+    }
+    .into()
+}
+
+/// Returns the relative luminance of the color, see
+/// [WCAG](https://www.w3.org/TR/WCAG21/#dfn-relative-luminance).
+pub fn relative_luminance(color: Hsla) -> f32 {
+    let rgb = color.to_rgb();
+    let channel = |c: f32| {
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * channel(rgb.r) + 0.7152 * channel(rgb.g) + 0.0722 * channel(rgb.b)
+}
+
+/// Returns the contrast ratio of the two colors, from 1 to 21.
+pub fn contrast_ratio(a: Hsla, b: Hsla) -> f32 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Returns the `foreground` or the `background` of the theme with the higher contrast
+/// on the `fill`, e.g. for the labels on the bars or the pie slices.
+pub fn contrast_color(fill: Hsla, cx: &App) -> Hsla {
+    let theme = cx.theme();
+    if contrast_ratio(fill, theme.foreground) >= contrast_ratio(fill, theme.background) {
+        theme.foreground
+    } else {
+        theme.background
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{black, white};
+
+    use super::{contrast_ratio, ramp};
+    use crate::hsl;
+
+    #[test]
+    fn test_contrast_ratio() {
+        assert!((contrast_ratio(black(), white()) - 21.).abs() < 0.01);
+        assert!((contrast_ratio(white(), white()) - 1.).abs() < 0.01);
+        // Yellow is lighter than blue.
+        let yellow = hsl(56., 85., 60.);
+        let blue = hsl(202., 100., 35.);
+        assert!(contrast_ratio(yellow, black()) > contrast_ratio(yellow, white()));
+        assert!(contrast_ratio(blue, white()) > contrast_ratio(blue, black()));
+    }
+
+    #[test]
+    fn test_ramp() {
+        let colors = ramp(&[black(), white()], 3);
+        assert_eq!(colors.len(), 3);
+        assert_eq!(colors[0].to_rgb(), black().to_rgb());
+        assert!((colors[1].to_rgb().r - 0.5).abs() < 0.01);
+        assert_eq!(colors[2].to_rgb(), white().to_rgb());
+
+        let colors = ramp(&[black(), hsl(0., 100., 50.), white()], 5);
+        assert_eq!(colors.len(), 5);
+        assert!((colors[2].to_rgb().r - 1.).abs() < 0.01);
+        assert_eq!(ramp(&[black(), white()], 1).len(), 1);
+    }
+}
@@ -1,17 +1,60 @@
-use std::rc::Rc;
+use std::{
+    cell::Cell,
+    f32::consts::{FRAC_PI_2, TAU},
+    rc::Rc,
+};
 
-use gpui::{App, Bounds, Hsla, Pixels, Window};
+use gpui::{
+    point, px, App, Bounds, Entity, FontWeight, Hsla, Pixels, SharedString, TextAlign, Window,
+};
 use gpui_component_macros::IntoPlot;
 use num_traits::Zero;
 
+use super::{
+    contrast_color, interaction::register_hit_events, legend::SeriesState, ChartPalette, ChartState,
+};
+
 use crate::{
     plot::{
+        label::{Label, Text, TEXT_GAP, TEXT_HEIGHT},
         shape::{Arc, ArcData, Pie},
-        Plot,
+        Plot, PlotPath,
     },
     ActiveTheme, PixelsExt,
 };
 
+/// The length of the leader line from the slice to the outside label.
+const LEADER_LENGTH: f32 = 12.;
+/// The horizontal length of the leader line before the outside label.
+const LEADER_TAIL: f32 = 8.;
+/// The gap between the leader line and the outside label.
+const LABEL_GAP: f32 = 4.;
+/// The outer radius offset of the hovered slice.
+const HOVER_OFFSET: f32 = 4.;
+/// The font size of the center label of the donut.
+const CENTER_FONT_SIZE: f32 = 20.;
+
+/// The position of the labels of the [`PieChart`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum PieLabelPosition {
+    /// Draw the labels at the center of the slices.
+    #[default]
+    Inside,
+    /// Draw the labels beside the pie with the leader lines, the overlapped labels are
+    /// spread vertically.
+    Outside,
+}
+
+/// A slice of the pie for the hit test, the angles start from 12 o'clock clockwise.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Slice {
+    index: usize,
+    start_angle: f32,
+    end_angle: f32,
+    inner_radius: f32,
+    outer_radius: f32,
+}
+
 #[derive(IntoPlot)]
 pub struct PieChart<T: 'static> {
     data: Vec<T>,
@@ -22,6 +65,12 @@ pub struct PieChart<T: 'static> {
     pad_angle: f32,
     value: Option<Rc<dyn Fn(&T) -> f32>>,
     color: Option<Rc<dyn Fn(&T) -> Hsla>>,
+    palette: Option<ChartPalette>,
+    label: Option<Rc<dyn Fn(&T) -> SharedString>>,
+    label_position: PieLabelPosition,
+    center_label: Option<Rc<dyn Fn(f32) -> SharedString>>,
+    center_title: Option<SharedString>,
+    state: Option<Entity<ChartState>>,
 }
 
 impl<T> PieChart<T> {
@@ -38,6 +87,12 @@ impl<T> PieChart<T> {
             pad_angle: 0.,
             value: None,
             color: None,
+            palette: None,
+            label: None,
+            label_position: PieLabelPosition::default(),
+            center_label: None,
+            center_title: None,
+            state: None,
         }
     }
 
@@ -106,6 +161,83 @@ impl<T> PieChart<T> {
         self.color = Some(Rc::new(move |t| color(t).into()));
         self
     }
+
+    /// Color the slices by the colors of the palette in order, if the `color` is not set.
+    pub fn palette(mut self, palette: ChartPalette) -> Self {
+        self.palette = Some(palette);
+        self
+    }
+
+    /// Set the label of each slice, the inside label is drawn at the center of the slice,
+    /// the text color is the `foreground` or the `background` of the theme, whichever has
+    /// the higher contrast on the slice.
+    pub fn label<S>(mut self, label: impl Fn(&T) -> S + 'static) -> Self
+    where
+        S: Into<SharedString> + 'static,
+    {
+        self.label = Some(Rc::new(move |t| label(t).into()));
+        self
+    }
+
+    /// Set the position of the labels, default: [`PieLabelPosition::Inside`]
+    pub fn label_position(mut self, label_position: PieLabelPosition) -> Self {
+        self.label_position = label_position;
+        self
+    }
+
+    /// Set the text at the center of the donut, the argument is the total of the visible slices.
+    pub fn center_label<S>(mut self, label: impl Fn(f32) -> S + 'static) -> Self
+    where
+        S: Into<SharedString> + 'static,
+    {
+        self.center_label = Some(Rc::new(move |total| label(total).into()));
+        self
+    }
+
+    /// Set the caption below the center label, e.g. `Total`.
+    pub fn center_title(mut self, title: impl Into<SharedString>) -> Self {
+        self.center_title = Some(title.into());
+        self
+    }
+
+    /// Set the state to hide or highlight the slices by a [`ChartLegend`](super::ChartLegend),
+    /// pop out the hovered slice, and emit the [`ChartEvent::Click`](super::ChartEvent::Click)
+    /// of the clicked slice.
+    ///
+    /// The series of the pie chart are the slices in the order of the data.
+    pub fn state(mut self, state: &Entity<ChartState>) -> Self {
+        self.state = Some(state.clone());
+        self
+    }
+}
+
+/// Returns the index of the slice at the point, relative to the center of the pie.
+fn slice_at(slices: &[Slice], x: f32, y: f32) -> Option<usize> {
+    let radius = x.hypot(y);
+    let angle = (y.atan2(x) + FRAC_PI_2).rem_euclid(TAU);
+    slices
+        .iter()
+        .find(|slice| {
+            (slice.start_angle..slice.end_angle).contains(&angle)
+                && (slice.inner_radius..=slice.outer_radius).contains(&radius)
+        })
+        .map(|slice| slice.index)
+}
+
+/// Spread the sorted centers of the labels apart by at least the `gap` within the `min` and
+/// the `max`, push down from the top first, then push up from the bottom if overflow.
+fn spread_labels(ys: &mut [f32], gap: f32, min: f32, max: f32) {
+    let mut prev = min - gap;
+    for y in ys.iter_mut() {
+        *y = y.max(prev + gap);
+        prev = *y;
+    }
+
+    let mut next = max + gap;
+    for y in ys.iter_mut().rev() {
+        *y = y.min(next - gap);
+        next = *y;
+    }
 }
 
 impl<T> Plot for PieChart<T> {
@@ -114,35 +246,228 @@ impl<T> Plot for PieChart<T> {
             return;
         };
 
-        let outer_radius = if self.outer_radius.is_zero() {
-            bounds.size.height.as_f32() * 0.4
-        } else {
+        let outside = self.label.is_some() && self.label_position == PieLabelPosition::Outside;
+        let outer_radius = if !self.outer_radius.is_zero() {
             self.outer_radius
+        } else if outside {
+            // Leave the room for the leader lines.
+            bounds.size.height.as_f32() * 0.4 - LEADER_LENGTH
+        } else {
+            bounds.size.height.as_f32() * 0.4
         };
 
         let arc = Arc::new()
             .inner_radius(self.inner_radius)
             .outer_radius(outer_radius);
+        // Scale the values by the visibility, the `Pie` reads the values of the data in order.
+        let series_state = SeriesState::read(self.state.as_ref(), cx);
+        let hovered = self
+            .state
+            .as_ref()
+            .and_then(|state| state.read(cx).hovered());
         let value_fn = value_fn.clone();
-        let mut pie = Pie::<T>::new().value(move |d| Some(value_fn(d)));
+        let next_ix = Cell::new(0);
+        let mut pie = Pie::<T>::new().value({
+            let series_state = series_state.clone();
+            move |d| {
+                let ix = next_ix.replace(next_ix.get() + 1);
+                Some(value_fn(d) * series_state.visibility(ix))
+            }
+        });
         pie = pie.pad_angle(self.pad_angle);
         let arcs = pie.arcs(&self.data);
+        let palette = self.palette.as_ref().map(|palette| palette.colors(cx));
+        let (width, height) = (bounds.size.width.as_f32(), bounds.size.height.as_f32());
+        let mut slices = vec![];
+        let mut max_radius = 0_f32;
+        let mut labels = vec![];
+        // The outside labels of the left and the right side: the angle, the radius, the y,
+        // the text and the color of the leader line.
+        let mut outside_labels: [Vec<(f32, f32, f32, SharedString, Hsla)>; 2] = [vec![], vec![]];
 
         for a in &arcs {
             let inner_radius = self.get_inner_radius(a);
-            let outer_radius = self.get_outer_radius(a);
+            let mut outer_radius = Some(self.get_outer_radius(a))
+                .filter(|radius| !radius.is_zero())
+                .unwrap_or(outer_radius);
+            max_radius = max_radius.max(outer_radius);
+            if hovered == Some(a.index) {
+                outer_radius += HOVER_OFFSET;
+            }
+            let color = match (self.color.as_ref(), palette.as_ref()) {
+                (Some(color_fn), _) => color_fn(a.data),
+                (None, Some(colors)) if !colors.is_empty() => colors[a.index % colors.len()],
+                _ => cx.theme().chart_2,
+            };
+            let color = color.opacity(series_state.opacity(a.index));
             arc.paint(
                 a,
-                if let Some(color_fn) = self.color.as_ref() {
-                    color_fn(a.data)
-                } else {
-                    cx.theme().chart_2
-                },
+                color,
                 Some(inner_radius),
                 Some(outer_radius),
                 &bounds,
                 window,
             );
+            slices.push(Slice {
+                index: a.index,
+                start_angle: a.start_angle,
+                end_angle: a.end_angle,
+                inner_radius,
+                outer_radius,
+            });
+
+            let Some(label_fn) = self.label.as_ref() else {
+                continue;
+            };
+            if outside {
+                let angle = (a.start_angle + a.end_angle) / 2. - FRAC_PI_2;
+                let y = (outer_radius + LEADER_LENGTH) * angle.sin();
+                let side = if angle.cos() >= 0. { 1 } else { 0 };
+                outside_labels[side].push((angle, outer_radius, y, label_fn(a.data), color));
+            } else {
+                let centroid = Arc::new()
+                    .inner_radius(inner_radius)
+                    .outer_radius(outer_radius)
+                    .centroid(a);
+                let origin = point(
+                    px(width / 2. + centroid.x),
+                    px(height / 2. + centroid.y - TEXT_HEIGHT / 2.),
+                );
+                labels.push(
+                    Text::new(label_fn(a.data), origin, contrast_color(color, cx))
+                        .align(TextAlign::Center),
+                );
+            }
+        }
+
+        // Draw the outside labels with the leader lines, spread the overlapped labels on each side.
+        let label_radius = max_radius + HOVER_OFFSET + LEADER_LENGTH;
+        for (side, items) in outside_labels.iter_mut().enumerate() {
+            items.sort_by(|a, b| a.2.total_cmp(&b.2));
+            let mut ys = items.iter().map(|item| item.2).collect::<Vec<_>>();
+            let half_height = height / 2. - TEXT_HEIGHT / 2.;
+            spread_labels(&mut ys, TEXT_HEIGHT, -half_height, half_height);
+
+            let sign = if side == 1 { 1. } else { -1. };
+            for ((angle, radius, _, text, color), y) in items.drain(..).zip(ys) {
+                let center = bounds.center();
+                let at = |x: f32, y: f32| center + point(px(x), px(y));
+                let tail_x = sign * (label_radius + LEADER_TAIL);
+                let mut builder = PlotPath::stroke(px(1.));
+                builder.move_to(at(radius * angle.cos(), radius * angle.sin()));
+                builder.line_to(at((radius + LEADER_LENGTH) * angle.cos(), y));
+                builder.line_to(at(tail_x, y));
+                builder.paint(color, window);
+
+                labels.push(
+                    Text::new(
+                        text,
+                        point(
+                            px(width / 2. + tail_x + sign * LABEL_GAP),
+                            px(height / 2. + y - TEXT_HEIGHT / 2.),
+                        ),
+                        cx.theme().foreground,
+                    )
+                    .align(if side == 1 {
+                        TextAlign::Left
+                    } else {
+                        TextAlign::Right
+                    }),
+                );
+            }
         }
+
+        // Draw the total of the visible slices at the center.
+        if let Some(center_label) = self.center_label.as_ref() {
+            let total = arcs.iter().map(|a| a.value).sum::<f32>();
+            let title_height = if self.center_title.is_some() {
+                TEXT_HEIGHT
+            } else {
+                0.
+            };
+            let top = height / 2. - (CENTER_FONT_SIZE + title_height) / 2.;
+            labels.push(
+                Text::new(
+                    center_label(total),
+                    point(px(width / 2.), px(top)),
+                    cx.theme().foreground,
+                )
+                .font_size(px(CENTER_FONT_SIZE))
+                .font_weight(FontWeight::SEMIBOLD)
+                .align(TextAlign::Center),
+            );
+            if let Some(title) = self.center_title.clone() {
+                labels.push(
+                    Text::new(
+                        title,
+                        point(px(width / 2.), px(top + CENTER_FONT_SIZE + TEXT_GAP)),
+                        cx.theme().muted_foreground,
+                    )
+                    .align(TextAlign::Center),
+                );
+            }
+        }
+
+        Label::new(labels).paint(&bounds, window, cx);
+        series_state.request_frame(window);
+
+        if let Some(state) = self.state.as_ref() {
+            let center = bounds.center();
+            register_hit_events(
+                state,
+                bounds,
+                move |position| {
+                    let position = position - center;
+                    slice_at(&slices, position.x.as_f32(), position.y.as_f32())
+                },
+                window,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{slice_at, spread_labels, Slice};
+
+    #[test]
+    fn test_slice_at() {
+        let slices = [
+            Slice {
+                index: 0,
+                start_angle: 0.,
+                end_angle: std::f32::consts::PI,
+                inner_radius: 10.,
+                outer_radius: 50.,
+            },
+            Slice {
+                index: 2,
+                start_angle: std::f32::consts::PI,
+                end_angle: std::f32::consts::TAU,
+                inner_radius: 10.,
+                outer_radius: 50.,
+            },
+        ];
+
+        // The right half is the first slice, clockwise from 12 o'clock.
+        assert_eq!(slice_at(&slices, 20., 0.), Some(0));
+        assert_eq!(slice_at(&slices, 0., -20.), Some(0));
+        assert_eq!(slice_at(&slices, -20., 0.), Some(2));
+        assert_eq!(slice_at(&slices, -1., -20.), Some(2));
+        // In the hole or outside of the pie.
+        assert_eq!(slice_at(&slices, 5., 0.), None);
+        assert_eq!(slice_at(&slices, 60., 0.), None);
+    }
+
+    #[test]
+    fn test_spread_labels() {
+        let mut ys = [0., 1., 2., 30.];
+        spread_labels(&mut ys, 10., -50., 50.);
+        assert_eq!(ys, [0., 10., 20., 30.]);
+
+        // Push up from the bottom if overflow.
+        let mut ys = [40., 45., 50.];
+        spread_labels(&mut ys, 10., -50., 50.);
+        assert_eq!(ys, [30., 40., 50.]);
     }
 }
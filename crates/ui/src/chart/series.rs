@@ -0,0 +1,47 @@
+use gpui::{Hsla, Pixels};
+
+use crate::plot::Marker;
+
+/// The style overrides of a series of the chart, the unset fields are the defaults of the chart.
+///
+/// ```ignore
+/// LineChart::new(data)
+///     .series_style(SeriesStyle::new().dash(&[px(4.), px(2.)]).marker(Marker::Square))
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SeriesStyle {
+    pub(super) color: Option<Hsla>,
+    pub(super) width: Option<Pixels>,
+    pub(super) dash: Option<Vec<Pixels>>,
+    pub(super) marker: Option<Marker>,
+}
+
+impl SeriesStyle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the color of the series, instead of the color of the palette.
+    pub fn color(mut self, color: impl Into<Hsla>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Set the stroke width of the series.
+    pub fn width(mut self, width: impl Into<Pixels>) -> Self {
+        self.width = Some(width.into());
+        self
+    }
+
+    /// Set the dash array of the stroke, e.g. `&[px(4.), px(2.)]`.
+    pub fn dash(mut self, dash: &[Pixels]) -> Self {
+        self.dash = Some(dash.to_vec());
+        self
+    }
+
+    /// Show the markers at the data points.
+    pub fn marker(mut self, marker: Marker) -> Self {
+        self.marker = Some(marker);
+        self
+    }
+}
@@ -0,0 +1,243 @@
+use std::ops::Range;
+
+/// The max number of the bins of the [`HistogramChart`](super::HistogramChart).
+const MAX_BINS: usize = 1_000;
+
+/// The binning of the values in the [`HistogramChart`](super::HistogramChart).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Binning {
+    /// The number of the bins by the Sturges' formula, `log2(n) + 1`, good for the normal distribution.
+    #[default]
+    Sturges,
+    /// The bin width by the Scott's rule, `3.49 * σ / n^(1/3)`.
+    Scott,
+    /// The bin width by the Freedman-Diaconis rule, `2 * IQR / n^(1/3)`, robust to the outliers.
+    FreedmanDiaconis,
+    /// The fixed number of the bins.
+    Count(usize),
+    /// The fixed width of the bins.
+    Width(f64),
+}
+
+/// A bin of the histogram, the `range` includes the start, the last bin also includes the end.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Bin {
+    pub(super) range: Range<f64>,
+    pub(super) count: usize,
+}
+
+/// Returns the bins of the values in the equal width, the non-finite values are ignored.
+pub(super) fn bins(values: &[f64], binning: Binning) -> Vec<Bin> {
+    let mut values = values
+        .iter()
+        .copied()
+        .filter(|v| v.is_finite())
+        .collect::<Vec<_>>();
+    if values.is_empty() {
+        return vec![];
+    }
+    values.sort_by(f64::total_cmp);
+
+    let (min, max) = (values[0], values[values.len() - 1]);
+    if min == max {
+        return vec![Bin {
+            range: min - 0.5..max + 0.5,
+            count: values.len(),
+        }];
+    }
+
+    let n = values.len() as f64;
+    let sturges = || (max - min) / (n.log2().ceil() + 1.);
+    let width = match binning {
+        Binning::Sturges => sturges(),
+        Binning::Scott => 3.49 * std_dev(&values) / n.cbrt(),
+        Binning::FreedmanDiaconis => {
+            2. * (quantile(&values, 0.75) - quantile(&values, 0.25)) / n.cbrt()
+        }
+        Binning::Count(count) => (max - min) / count.max(1) as f64,
+        Binning::Width(width) => width,
+    };
+    let width = if width.is_finite() && width > 0. {
+        width
+    } else {
+        sturges()
+    };
+
+    let count = (((max - min) / width).ceil() as usize).clamp(1, MAX_BINS);
+    let width = width.max((max - min) / count as f64);
+    let mut bins = (0..count)
+        .map(|ix| Bin {
+            range: min + width * ix as f64..min + width * (ix + 1) as f64,
+            count: 0,
+        })
+        .collect::<Vec<_>>();
+    for value in values {
+        let ix = (((value - min) / width) as usize).min(count - 1);
+        bins[ix].count += 1;
+    }
+
+    bins
+}
+
+/// The five-number summary of the values for the box plot, the whiskers are extended to the
+/// furthest values within 1.5 IQR from the box, the values beyond are the outliers.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct BoxStats {
+    pub(super) min: f64,
+    pub(super) q1: f64,
+    pub(super) median: f64,
+    pub(super) q3: f64,
+    pub(super) max: f64,
+    pub(super) outliers: Vec<f64>,
+    /// The sorted finite values, for the density of the violin.
+    pub(super) values: Vec<f64>,
+}
+
+impl BoxStats {
+    /// Returns None if there is no finite value.
+    pub(super) fn new(values: &[f64]) -> Option<Self> {
+        let mut values = values
+            .iter()
+            .copied()
+            .filter(|v| v.is_finite())
+            .collect::<Vec<_>>();
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by(f64::total_cmp);
+
+        let q1 = quantile(&values, 0.25);
+        let median = quantile(&values, 0.5);
+        let q3 = quantile(&values, 0.75);
+        let fence = (q3 - q1) * 1.5;
+        let (lower, upper) = (q1 - fence, q3 + fence);
+
+        let mut inliers = values
+            .iter()
+            .copied()
+            .filter(|v| (lower..=upper).contains(v));
+        let min = inliers.next().unwrap_or(q1);
+        let max = inliers.next_back().unwrap_or(min);
+        let outliers = values
+            .iter()
+            .copied()
+            .filter(|v| !(lower..=upper).contains(v))
+            .collect();
+
+        Some(Self {
+            min,
+            q1,
+            median,
+            q3,
+            max,
+            outliers,
+            values,
+        })
+    }
+
+    /// Returns the range of all the values, including the outliers.
+    pub(super) fn extent(&self) -> (f64, f64) {
+        (self.values[0], self.values[self.values.len() - 1])
+    }
+
+    /// Returns the kernel density estimation at the `steps` points evenly spaced in the extent,
+    /// with the Gaussian kernel and the Silverman's bandwidth.
+    pub(super) fn density(&self, steps: usize) -> Vec<(f64, f64)> {
+        let (min, max) = self.extent();
+        let n = self.values.len() as f64;
+        let spread = std_dev(&self.values).min((self.q3 - self.q1) / 1.34);
+        let spread = if spread > 0. {
+            spread
+        } else {
+            std_dev(&self.values)
+        };
+        let bandwidth = 0.9 * spread * n.powf(-0.2);
+        let steps = steps.max(2);
+
+        (0..steps)
+            .map(|ix| {
+                let x = min + (max - min) * ix as f64 / (steps - 1) as f64;
+                let density = if bandwidth > 0. {
+                    self.values
+                        .iter()
+                        .map(|v| (-0.5 * ((x - v) / bandwidth).powi(2)).exp())
+                        .sum::<f64>()
+                        / (n * bandwidth * (2. * std::f64::consts::PI).sqrt())
+                } else {
+                    1.
+                };
+                (x, density)
+            })
+            .collect()
+    }
+}
+
+/// Returns the quantile of the sorted values by the linear interpolation.
+fn quantile(sorted: &[f64], p: f64) -> f64 {
+    let pos = (sorted.len() - 1) as f64 * p;
+    let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64)
+}
+
+fn std_dev(values: &[f64]) -> f64 {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bins, Binning, BoxStats};
+
+    #[test]
+    fn test_bins() {
+        let values = (0..100).map(|v| v as f64).collect::<Vec<_>>();
+
+        let result = bins(&values, Binning::Count(4));
+        assert_eq!(result.len(), 4);
+        assert_eq!(result[0].range, 0.0..24.75);
+        assert_eq!(
+            result.iter().map(|bin| bin.count).collect::<Vec<_>>(),
+            vec![25, 25, 25, 25]
+        );
+
+        // log2(100) + 1 = 8 bins.
+        assert_eq!(bins(&values, Binning::Sturges).len(), 8);
+        assert_eq!(bins(&values, Binning::Width(10.)).len(), 10);
+        assert!(!bins(&values, Binning::Scott).is_empty());
+        assert!(!bins(&values, Binning::FreedmanDiaconis).is_empty());
+
+        // The invalid widths fallback to the Sturges' formula.
+        assert_eq!(bins(&values, Binning::Width(0.)).len(), 8);
+        assert_eq!(bins(&values, Binning::Count(0)).len(), 1);
+
+        let result = bins(&[1., 1., f64::NAN], Binning::Sturges);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].count, 2);
+        assert!(bins(&[], Binning::Sturges).is_empty());
+    }
+
+    #[test]
+    fn test_box_stats() {
+        let stats = BoxStats::new(&[1., 2., 3., 4., 5., 6., 7., 8., 9., 100.]).unwrap();
+        assert_eq!(stats.q1, 3.25);
+        assert_eq!(stats.median, 5.5);
+        assert_eq!(stats.q3, 7.75);
+        assert_eq!(stats.min, 1.);
+        assert_eq!(stats.max, 9.);
+        assert_eq!(stats.outliers, vec![100.]);
+        assert_eq!(stats.extent(), (1., 100.));
+
+        let density = stats.density(10);
+        assert_eq!(density.len(), 10);
+        assert_eq!(density[0].0, 1.);
+        assert_eq!(density[9].0, 100.);
+        // The density is higher in the box than at the outlier.
+        assert!(density[0].1 > density[5].1);
+
+        assert_eq!(BoxStats::new(&[f64::NAN]), None);
+        let stats = BoxStats::new(&[2.]).unwrap();
+        assert_eq!((stats.min, stats.median, stats.max), (2., 2., 2.));
+        assert_eq!(stats.density(3), vec![(2., 1.), (2., 1.), (2., 1.)]);
+    }
+}
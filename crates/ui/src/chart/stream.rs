@@ -0,0 +1,300 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use gpui::{point, px, Hsla, Point, TextAlign, Window};
+
+use crate::plot::AxisText;
+
+/// A ring buffer of the real-time data for the streaming mode of the
+/// [`LineChart`](super::LineChart) and [`AreaChart`](super::AreaChart).
+///
+/// Each item is timestamped when pushed, the chart shows the items in the fixed time window
+/// ending at now, and scrolls smoothly until paused.
+///
+/// ```ignore
+/// let mut stream = ChartStream::new(10_000).duration(Duration::from_secs(30));
+/// stream.push(Sample { cpu: 0.42 });
+///
+/// LineChart::stream(&stream).y(|d| d.cpu)
+/// ```
+pub struct ChartStream<T> {
+    items: VecDeque<(Instant, T)>,
+    capacity: usize,
+    duration: Duration,
+    paused_at: Option<Instant>,
+}
+
+impl<T> ChartStream<T> {
+    /// Create a stream keeping the latest `capacity` items, the oldest items are dropped
+    /// when it's full.
+    ///
+    /// The capacity should cover the items pushed in the time window.
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            items: VecDeque::with_capacity(capacity),
+            capacity,
+            duration: Duration::from_secs(60),
+            paused_at: None,
+        }
+    }
+
+    /// Set the time window of the chart, default: 60 seconds.
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Returns the time window of the chart.
+    pub fn window_duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Push an item timestamped now.
+    pub fn push(&mut self, item: T) {
+        self.push_at(Instant::now(), item);
+    }
+
+    /// Push an item with the timestamp, the timestamps are expected in order.
+    pub fn push_at(&mut self, time: Instant, item: T) {
+        if self.items.len() == self.capacity {
+            self.items.pop_front();
+        }
+        self.items.push_back((time, item));
+    }
+
+    /// Returns the number of the items in the buffer.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if the buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns the items in the buffer, from the oldest.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
+        self.items.iter().map(|(_, item)| item)
+    }
+
+    /// Remove all the items.
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    /// Freeze the time window of the chart, the items pushed are still kept in the buffer.
+    pub fn pause(&mut self) {
+        if self.paused_at.is_none() {
+            self.paused_at = Some(Instant::now());
+        }
+    }
+
+    /// Resume to scroll the time window to now.
+    pub fn resume(&mut self) {
+        self.paused_at = None;
+    }
+
+    /// Returns true if the stream is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused_at.is_some()
+    }
+
+    /// Returns the items in the time window ending at the `end`, with the timeline of them.
+    ///
+    /// The last item before the window is included, so the line enters from the left edge.
+    pub(super) fn snapshot_at(&self, end: Instant) -> (StreamTimeline, Vec<T>)
+    where
+        T: Clone,
+    {
+        let start = end.checked_sub(self.duration).unwrap_or(end);
+        let first = self.items.partition_point(|(time, _)| *time < start);
+        let last = self.items.partition_point(|(time, _)| *time <= end);
+
+        let (offsets, data) = self
+            .items
+            .range(first.saturating_sub(1)..last)
+            .map(|(time, item)| {
+                let offset = if *time < start {
+                    -(start - *time).as_secs_f32()
+                } else {
+                    (*time - start).as_secs_f32()
+                };
+                (offset, item.clone())
+            })
+            .unzip();
+
+        (
+            StreamTimeline {
+                offsets,
+                duration: self.duration.as_secs_f32(),
+                paused: self.is_paused(),
+            },
+            data,
+        )
+    }
+
+    pub(super) fn snapshot(&self) -> (StreamTimeline, Vec<T>)
+    where
+        T: Clone,
+    {
+        self.snapshot_at(self.paused_at.unwrap_or_else(Instant::now))
+    }
+}
+
+/// The time offsets of the items in the window of a [`ChartStream`].
+#[derive(Clone)]
+pub(super) struct StreamTimeline {
+    /// The seconds from the window start of each item.
+    offsets: Vec<f32>,
+    duration: f32,
+    paused: bool,
+}
+
+impl StreamTimeline {
+    /// Returns the points of the items in the `width`, decimated to at most one point per pixel.
+    pub(super) fn points<T>(
+        &self,
+        data: &[T],
+        y: impl Fn(&T) -> Option<f32>,
+        width: f32,
+    ) -> Vec<Point<f32>> {
+        if self.duration <= 0. {
+            return vec![];
+        }
+
+        let points = self
+            .offsets
+            .iter()
+            .zip(data)
+            .filter_map(|(offset, d)| Some(point(offset / self.duration * width, y(d)?)))
+            .collect::<Vec<_>>();
+        lttb(&points, width.max(0.) as usize)
+    }
+
+    /// Returns the labels of the x-axis at the quarters of the window, e.g.: `-30s`, `now`.
+    pub(super) fn x_labels(&self, width: f32, color: Hsla) -> Vec<AxisText> {
+        (0..=4)
+            .map(|i| {
+                let seconds = self.duration * (4 - i) as f32 / 4.;
+                let text = if i == 4 {
+                    "now".to_string()
+                } else if seconds.fract() == 0. {
+                    format!("-{}s", seconds)
+                } else {
+                    format!("-{:.1}s", seconds)
+                };
+                let align = match i {
+                    0 => TextAlign::Left,
+                    4 => TextAlign::Right,
+                    _ => TextAlign::Center,
+                };
+                AxisText::new(text, px(width * i as f32 / 4.), color).align(align)
+            })
+            .collect()
+    }
+
+    /// Request the next frame to scroll the window, unless paused.
+    pub(super) fn request_frame(&self, window: &mut Window) {
+        if !self.paused {
+            window.request_animation_frame();
+        }
+    }
+}
+
+/// Decimate the points by the Largest-Triangle-Three-Buckets algorithm, keeps the first
+/// and the last points and the shape of the peaks.
+///
+/// https://skemman.is/bitstream/1946/15343/3/SS_MSthesis.pdf
+fn lttb(points: &[Point<f32>], threshold: usize) -> Vec<Point<f32>> {
+    if threshold < 3 || points.len() <= threshold {
+        return points.to_vec();
+    }
+
+    let bucket_size = (points.len() - 2) as f32 / (threshold - 2) as f32;
+    let bucket_start = |i: usize| ((i as f32 * bucket_size) as usize + 1).min(points.len() - 1);
+
+    let mut sampled = Vec::with_capacity(threshold);
+    let mut a = points[0];
+    sampled.push(a);
+
+    for i in 0..threshold - 2 {
+        let (start, end) = (bucket_start(i), bucket_start(i + 1));
+
+        // The average point of the next bucket, the last point for the last bucket.
+        let next = &points[end..bucket_start(i + 2).max(end + 1).min(points.len())];
+        let avg = next
+            .iter()
+            .fold(point(0., 0.), |sum, p| point(sum.x + p.x, sum.y + p.y));
+        let avg = point(avg.x / next.len() as f32, avg.y / next.len() as f32);
+
+        let area =
+            |p: &Point<f32>| ((a.x - avg.x) * (p.y - a.y) - (a.x - p.x) * (avg.y - a.y)).abs();
+        if let Some(p) = points[start..end.max(start + 1)]
+            .iter()
+            .max_by(|p1, p2| area(p1).total_cmp(&area(p2)))
+        {
+            a = *p;
+            sampled.push(a);
+        }
+    }
+
+    sampled.push(points[points.len() - 1]);
+    sampled
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use gpui::point;
+
+    use super::{lttb, ChartStream};
+
+    #[test]
+    fn test_lttb() {
+        let mut points = (0..1000).map(|i| point(i as f32, 0.)).collect::<Vec<_>>();
+        points[500].y = 100.;
+
+        let sampled = lttb(&points, 50);
+        assert_eq!(sampled.len(), 50);
+        assert_eq!(sampled.first(), points.first());
+        assert_eq!(sampled.last(), points.last());
+        assert!(sampled.iter().any(|p| p.y == 100.));
+        assert!(sampled.windows(2).all(|w| w[0].x < w[1].x));
+
+        assert_eq!(lttb(&points[..10], 50).len(), 10);
+    }
+
+    #[test]
+    fn test_stream() {
+        let now = Instant::now();
+        let mut stream = ChartStream::new(5).duration(Duration::from_secs(10));
+        for i in 0..8u64 {
+            stream.push_at(now + Duration::from_secs(i * 3), i);
+        }
+        assert_eq!(stream.len(), 5);
+        assert_eq!(
+            stream.iter().copied().collect::<Vec<_>>(),
+            vec![3, 4, 5, 6, 7]
+        );
+
+        // The window is 11s..21s, includes the item at 9s to enter from the left edge.
+        let (timeline, data) = stream.snapshot_at(now + Duration::from_secs(21));
+        assert_eq!(data, vec![3, 4, 5, 6, 7]);
+        assert_eq!(timeline.offsets, vec![-2., 1., 4., 7., 10.]);
+
+        let points = timeline.points(&data, |d| Some(*d as f32), 100.);
+        assert_eq!(points[1], point(10., 4.));
+
+        let (_, data) = stream.snapshot_at(now + Duration::from_secs(16));
+        assert_eq!(data, vec![3, 4, 5]);
+
+        stream.pause();
+        assert!(stream.is_paused());
+        stream.resume();
+        assert!(!stream.is_paused());
+    }
+}
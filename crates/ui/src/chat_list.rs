@@ -0,0 +1,564 @@
+use std::rc::Rc;
+
+use chrono::{DateTime, Local, NaiveDate};
+use gpui::{
+    div, list, prelude::FluentBuilder as _, px, AnyElement, App, Context, ElementId, Entity,
+    EventEmitter, FocusHandle, InteractiveElement as _, IntoElement, ListAlignment, ListOffset,
+    ListState, ParentElement, Render, RenderOnce, SharedString, StyleRefinement, Styled, Window,
+};
+use rust_i18n::t;
+
+use crate::{
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    indicator::Indicator,
+    scroll::{Scrollbar, ScrollbarState},
+    v_flex, ActiveTheme, IconName, Sizable as _, StyledExt as _,
+};
+
+/// A message in the [`ChatList`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChatMessage {
+    /// The unique id of the message.
+    pub id: SharedString,
+    /// The display name of the author.
+    pub author: SharedString,
+    /// The text content of the message.
+    pub content: SharedString,
+    /// The time the message was sent, used for the day separators.
+    pub timestamp: DateTime<Local>,
+    /// Whether the message was sent by the current user.
+    pub own: bool,
+    /// Whether the message is still receiving content.
+    pub streaming: bool,
+}
+
+impl ChatMessage {
+    /// Create a new message sent now.
+    pub fn new(
+        id: impl Into<SharedString>,
+        author: impl Into<SharedString>,
+        content: impl Into<SharedString>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            author: author.into(),
+            content: content.into(),
+            timestamp: Local::now(),
+            own: false,
+            streaming: false,
+        }
+    }
+
+    /// Set the time the message was sent.
+    pub fn timestamp(mut self, timestamp: DateTime<Local>) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Set whether the message was sent by the current user, default: false
+    pub fn own(mut self, own: bool) -> Self {
+        self.own = own;
+        self
+    }
+
+    /// Set whether the message is still receiving content, default: false
+    ///
+    /// Use [`ChatListState::append_to_message`] to stream content into it.
+    pub fn streaming(mut self, streaming: bool) -> Self {
+        self.streaming = streaming;
+        self
+    }
+}
+
+/// Emitted by the [`ChatListState`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChatListEvent {
+    /// The number of unread messages has changed.
+    UnreadChanged(usize),
+}
+
+/// A row in the virtual list.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ChatRow {
+    DaySeparator(NaiveDate),
+    Message(usize),
+}
+
+fn push_rows(rows: &mut Vec<ChatRow>, messages: &[ChatMessage], start_ix: usize) {
+    let mut last_date = start_ix
+        .checked_sub(1)
+        .and_then(|ix| messages.get(ix))
+        .map(|m| m.timestamp.date_naive());
+
+    for (ix, message) in messages.iter().enumerate().skip(start_ix) {
+        let date = message.timestamp.date_naive();
+        if last_date != Some(date) {
+            rows.push(ChatRow::DaySeparator(date));
+            last_date = Some(date);
+        }
+        rows.push(ChatRow::Message(ix));
+    }
+}
+
+fn day_label(date: NaiveDate, today: NaiveDate) -> SharedString {
+    if date == today {
+        t!("ChatList.Today").into()
+    } else if today.pred_opt() == Some(date) {
+        t!("ChatList.Yesterday").into()
+    } else {
+        date.format("%Y-%m-%d").to_string().into()
+    }
+}
+
+/// State of the [`ChatList`].
+///
+/// The list is anchored to the bottom, only the visible messages are laid out, and streaming
+/// updates only re-measure the changed message.
+pub struct ChatListState {
+    focus_handle: FocusHandle,
+    messages: Vec<ChatMessage>,
+    rows: Vec<ChatRow>,
+    list_state: ListState,
+    scrollbar_state: ScrollbarState,
+    typing: Vec<SharedString>,
+    /// Whether to keep the newest message in view when messages are added.
+    following: bool,
+    /// The message index of the first unread message.
+    first_unread_ix: Option<usize>,
+    render_message: Option<Rc<dyn Fn(&ChatMessage, &mut Window, &mut App) -> AnyElement>>,
+}
+
+impl EventEmitter<ChatListEvent> for ChatListState {}
+
+impl ChatListState {
+    /// Create a new empty chat list state.
+    pub fn new(_: &mut Window, cx: &mut Context<Self>) -> Self {
+        let list_state = ListState::new(0, ListAlignment::Bottom, px(1000.));
+
+        let view = cx.entity().downgrade();
+        list_state.set_scroll_handler(move |event, _, cx| {
+            let following = !event.is_scrolled;
+            _ = view.update(cx, |this, cx| {
+                this.following = following;
+                if following {
+                    this.mark_as_read(cx);
+                }
+            });
+        });
+
+        Self {
+            focus_handle: cx.focus_handle(),
+            messages: vec![],
+            rows: vec![],
+            list_state,
+            scrollbar_state: ScrollbarState::default(),
+            typing: vec![],
+            following: true,
+            first_unread_ix: None,
+            render_message: None,
+        }
+    }
+
+    /// Set the messages, and scroll to the bottom.
+    pub fn messages(mut self, messages: impl Into<Vec<ChatMessage>>) -> Self {
+        self.reset_messages(messages.into());
+        self
+    }
+
+    /// Replace all messages, and scroll to the bottom.
+    pub fn set_messages(&mut self, messages: impl Into<Vec<ChatMessage>>, cx: &mut Context<Self>) {
+        self.reset_messages(messages.into());
+        cx.emit(ChatListEvent::UnreadChanged(0));
+        cx.notify();
+    }
+
+    fn reset_messages(&mut self, messages: Vec<ChatMessage>) {
+        self.messages = messages;
+        self.rows.clear();
+        push_rows(&mut self.rows, &self.messages, 0);
+        self.list_state.reset(self.rows.len());
+        self.following = true;
+        self.first_unread_ix = None;
+    }
+
+    /// Set a custom renderer for the messages, default is a chat bubble.
+    pub fn render_message<F, E>(mut self, f: F) -> Self
+    where
+        F: Fn(&ChatMessage, &mut Window, &mut App) -> E + 'static,
+        E: IntoElement,
+    {
+        self.render_message = Some(Rc::new(move |message, window, cx| {
+            f(message, window, cx).into_any_element()
+        }));
+        self
+    }
+
+    /// Get all messages.
+    pub fn all_messages(&self) -> &[ChatMessage] {
+        &self.messages
+    }
+
+    /// Get a message by id.
+    pub fn message(&self, id: &str) -> Option<&ChatMessage> {
+        self.messages.iter().rev().find(|m| m.id.as_ref() == id)
+    }
+
+    /// Append a new message to the end of the list.
+    ///
+    /// If the list is scrolled up, the message is counted as unread instead of scrolling.
+    pub fn push_message(&mut self, message: ChatMessage, cx: &mut Context<Self>) {
+        let ix = self.messages.len();
+        let old_rows = self.rows.len();
+        self.messages.push(message);
+        push_rows(&mut self.rows, &self.messages, ix);
+        self.list_state
+            .splice(old_rows..old_rows, self.rows.len() - old_rows);
+
+        if self.following || self.messages[ix].own {
+            self.scroll_to_bottom(cx);
+        } else {
+            self.first_unread_ix.get_or_insert(ix);
+            cx.emit(ChatListEvent::UnreadChanged(self.unread_count()));
+        }
+        cx.notify();
+    }
+
+    /// Append text to the content of a message, e.g. for streaming tokens.
+    ///
+    /// Only the updated message is laid out again.
+    pub fn append_to_message(&mut self, id: &str, text: &str, cx: &mut Context<Self>) {
+        self.update_message(
+            id,
+            |message| {
+                message.content = format!("{}{}", message.content, text).into();
+            },
+            cx,
+        );
+    }
+
+    /// Update a message by id, e.g. to finish streaming with `message.streaming = false`.
+    pub fn update_message(
+        &mut self,
+        id: &str,
+        f: impl FnOnce(&mut ChatMessage),
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ix) = self.messages.iter().rposition(|m| m.id.as_ref() == id) else {
+            return;
+        };
+        f(&mut self.messages[ix]);
+
+        let Some(row_ix) = self
+            .rows
+            .iter()
+            .rposition(|row| *row == ChatRow::Message(ix))
+        else {
+            return;
+        };
+        self.list_state.splice(row_ix..row_ix + 1, 1);
+        if self.following {
+            self.scroll_to_end();
+        }
+        cx.notify();
+    }
+
+    /// Set the names of the users who are typing, an empty list hides the indicator.
+    pub fn set_typing(&mut self, names: impl Into<Vec<SharedString>>, cx: &mut Context<Self>) {
+        self.typing = names.into();
+        cx.notify();
+    }
+
+    /// Returns the number of messages received while the list was scrolled up.
+    pub fn unread_count(&self) -> usize {
+        self.first_unread_ix
+            .map(|ix| self.messages.len() - ix)
+            .unwrap_or(0)
+    }
+
+    /// Returns true if the list is showing the newest message.
+    pub fn is_at_bottom(&self) -> bool {
+        self.following
+    }
+
+    /// Scroll to the newest message, and mark all messages as read.
+    pub fn scroll_to_bottom(&mut self, cx: &mut Context<Self>) {
+        self.following = true;
+        self.scroll_to_end();
+        self.mark_as_read(cx);
+        cx.notify();
+    }
+
+    /// Scroll to the first unread message, or to the bottom if there is none.
+    pub fn scroll_to_unread(&mut self, cx: &mut Context<Self>) {
+        let Some(ix) = self.first_unread_ix else {
+            self.scroll_to_bottom(cx);
+            return;
+        };
+
+        if let Some(row_ix) = self
+            .rows
+            .iter()
+            .position(|row| *row == ChatRow::Message(ix))
+        {
+            self.list_state.scroll_to(ListOffset {
+                item_ix: row_ix,
+                offset_in_item: px(0.),
+            });
+        }
+        self.mark_as_read(cx);
+        cx.notify();
+    }
+
+    fn scroll_to_end(&self) {
+        self.list_state.scroll_to(ListOffset {
+            item_ix: self.rows.len(),
+            offset_in_item: px(0.),
+        });
+    }
+
+    fn mark_as_read(&mut self, cx: &mut Context<Self>) {
+        if self.first_unread_ix.take().is_some() {
+            cx.emit(ChatListEvent::UnreadChanged(0));
+            cx.notify();
+        }
+    }
+
+    fn render_row(&self, ix: usize, window: &mut Window, cx: &mut App) -> AnyElement {
+        match self.rows.get(ix) {
+            Some(ChatRow::DaySeparator(date)) => {
+                let today = Local::now().date_naive();
+                h_flex()
+                    .py_2()
+                    .gap_3()
+                    .text_xs()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(div().flex_1().h(px(1.)).bg(cx.theme().border))
+                    .child(day_label(*date, today))
+                    .child(div().flex_1().h(px(1.)).bg(cx.theme().border))
+                    .into_any_element()
+            }
+            Some(ChatRow::Message(ix)) => {
+                let message = &self.messages[*ix];
+                let content = match self.render_message.as_ref() {
+                    Some(render_message) => render_message(message, window, cx),
+                    None => render_bubble(message, cx),
+                };
+                div().py_1().child(content).into_any_element()
+            }
+            None => div().into_any_element(),
+        }
+    }
+
+    fn render_typing(&self, cx: &App) -> Option<AnyElement> {
+        let label = match self.typing.as_slice() {
+            [] => return None,
+            [name] => t!("ChatList.typing", name = name).to_string(),
+            names => t!("ChatList.typing_many", count = names.len()).to_string(),
+        };
+
+        Some(
+            h_flex()
+                .gap_2()
+                .py_1()
+                .text_xs()
+                .text_color(cx.theme().muted_foreground)
+                .child(Indicator::new().xsmall())
+                .child(label)
+                .into_any_element(),
+        )
+    }
+}
+
+fn render_bubble(message: &ChatMessage, cx: &App) -> AnyElement {
+    let (bg, fg) = if message.own {
+        (cx.theme().primary, cx.theme().primary_foreground)
+    } else {
+        (cx.theme().secondary, cx.theme().secondary_foreground)
+    };
+
+    v_flex()
+        .w_full()
+        .gap_1()
+        .map(|this| {
+            if message.own {
+                this.items_end()
+            } else {
+                this.items_start()
+            }
+        })
+        .when(!message.own, |this| {
+            this.child(
+                div()
+                    .text_xs()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(message.author.clone()),
+            )
+        })
+        .child(
+            h_flex()
+                .max_w(px(480.))
+                .gap_1()
+                .px_3()
+                .py_2()
+                .rounded(cx.theme().radius_lg)
+                .bg(bg)
+                .text_color(fg)
+                .child(div().flex_1().child(message.content.clone()))
+                .when(message.streaming, |this| {
+                    this.child(Indicator::new().xsmall().color(fg))
+                }),
+        )
+        .child(
+            div()
+                .text_xs()
+                .text_color(cx.theme().muted_foreground)
+                .child(message.timestamp.format("%H:%M").to_string()),
+        )
+        .into_any_element()
+}
+
+impl Render for ChatListState {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let view = cx.entity();
+        let unread_count = self.unread_count();
+
+        v_flex()
+            .size_full()
+            .child(
+                div()
+                    .relative()
+                    .flex_1()
+                    .w_full()
+                    .child(
+                        list(self.list_state.clone(), move |ix, window, cx| {
+                            view.update(cx, |this, cx| this.render_row(ix, window, cx))
+                        })
+                        .size_full()
+                        .px_3(),
+                    )
+                    .child(
+                        div()
+                            .absolute()
+                            .top_0()
+                            .right_0()
+                            .bottom_0()
+                            .w(Scrollbar::width())
+                            .child(Scrollbar::vertical(&self.scrollbar_state, &self.list_state)),
+                    )
+                    .when(unread_count > 0, |this| {
+                        this.child(
+                            h_flex()
+                                .absolute()
+                                .bottom_2()
+                                .w_full()
+                                .justify_center()
+                                .child(
+                                    Button::new("scroll-to-unread")
+                                        .primary()
+                                        .small()
+                                        .icon(IconName::ArrowDown)
+                                        .label(t!("ChatList.new_messages", count = unread_count))
+                                        .on_click(cx.listener(|this, _, _, cx| {
+                                            this.scroll_to_unread(cx);
+                                        })),
+                                ),
+                        )
+                    }),
+            )
+            .children(self.render_typing(cx).map(|el| div().px_3().child(el)))
+    }
+}
+
+/// A virtualized message list for chat UIs, anchored to the bottom.
+///
+/// ```ignore
+/// let state = cx.new(|cx| {
+///     ChatListState::new(window, cx).messages(vec![
+///         ChatMessage::new("1", "Jason", "Hello"),
+///         ChatMessage::new("2", "Me", "Hi!").own(true),
+///     ])
+/// });
+///
+/// ChatList::new(&state)
+/// ```
+#[derive(IntoElement)]
+pub struct ChatList {
+    id: ElementId,
+    state: Entity<ChatListState>,
+    style: StyleRefinement,
+}
+
+impl ChatList {
+    /// Create a new chat list with the given state.
+    pub fn new(state: &Entity<ChatListState>) -> Self {
+        Self {
+            id: ElementId::Name(format!("chat-list-{}", state.entity_id()).into()),
+            state: state.clone(),
+            style: StyleRefinement::default(),
+        }
+    }
+}
+
+impl Styled for ChatList {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for ChatList {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let focus_handle = self.state.read(cx).focus_handle.clone();
+
+        div()
+            .id(self.id)
+            .track_focus(&focus_handle)
+            .size_full()
+            .child(self.state)
+            .refine_style(&self.style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Local, NaiveDate, TimeZone as _};
+
+    use super::{push_rows, ChatMessage, ChatRow};
+
+    fn message(id: &'static str, day: u32, hour: u32) -> ChatMessage {
+        ChatMessage::new(id, "Jason", "Hello")
+            .timestamp(Local.with_ymd_and_hms(2025, 5, day, hour, 0, 0).unwrap())
+    }
+
+    #[test]
+    fn test_push_rows() {
+        let date = |day| NaiveDate::from_ymd_opt(2025, 5, day).unwrap();
+        let mut messages = vec![message("1", 1, 9), message("2", 1, 10), message("3", 2, 9)];
+
+        let mut rows = vec![];
+        push_rows(&mut rows, &messages, 0);
+        assert_eq!(
+            rows,
+            vec![
+                ChatRow::DaySeparator(date(1)),
+                ChatRow::Message(0),
+                ChatRow::Message(1),
+                ChatRow::DaySeparator(date(2)),
+                ChatRow::Message(2),
+            ]
+        );
+
+        // Appending only adds a separator when the day changes.
+        messages.push(message("4", 2, 10));
+        messages.push(message("5", 3, 10));
+        push_rows(&mut rows, &messages, 3);
+        assert_eq!(
+            rows[5..],
+            [
+                ChatRow::Message(3),
+                ChatRow::DaySeparator(date(3)),
+                ChatRow::Message(4),
+            ]
+        );
+    }
+}
@@ -1,8 +1,8 @@
 use std::{rc::Rc, time::Duration};
 
 use crate::{
-    text::Text, v_flex, ActiveTheme, Disableable, FocusableExt, IconName, Selectable, Sizable,
-    Size, StyledExt as _,
+    animation::MotionSettings, text::Text, v_flex, ActiveTheme, Disableable, FocusableExt,
+    IconName, Selectable, Sizable, Size, StyledExt as _,
 };
 use gpui::{
     div, prelude::FluentBuilder as _, px, relative, rems, svg, Animation, AnimationExt, AnyElement,
@@ -165,7 +165,8 @@ pub(crate) fn checkbox_check_icon(
         })
         .map(|this| {
             if !disabled && checked != *toggle_state.read(cx) {
-                let duration = Duration::from_secs_f64(0.25);
+                let motion = MotionSettings::global(cx);
+                let duration = motion.duration(Duration::from_secs_f64(0.25));
                 cx.spawn({
                     let toggle_state = toggle_state.clone();
                     async move |cx| {
@@ -177,7 +178,7 @@ pub(crate) fn checkbox_check_icon(
 
                 this.with_animation(
                     ElementId::NamedInteger("toggle".into(), checked as u64),
-                    Animation::new(Duration::from_secs_f64(0.25)),
+                    motion.animation(Animation::new(Duration::from_secs_f64(0.25))),
                     move |this, delta| {
                         this.opacity(if checked { 1.0 * delta } else { 1.0 - delta })
                     },
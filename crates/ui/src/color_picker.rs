@@ -10,6 +10,7 @@ use crate::{
     actions::{Cancel, Confirm},
     button::{Button, ButtonVariants},
     divider::Divider,
+    elevation::Layer,
     h_flex,
     input::{Input, InputEvent, InputState},
     tooltip::Tooltip,
@@ -28,7 +29,7 @@ pub enum ColorPickerEvent {
     Change(Option<Hsla>),
 }
 
-fn color_palettes() -> Vec<Vec<Hsla>> {
+pub(crate) fn color_palettes() -> Vec<Vec<Hsla>> {
     use crate::theme::DEFAULT_COLORS;
     use itertools::Itertools as _;
 
@@ -469,7 +470,7 @@ impl RenderOnce for ColorPicker {
                                     .p_3()
                                     .border_1()
                                     .border_color(cx.theme().border)
-                                    .shadow_lg()
+                                    .elevation(Layer::Dropdown.elevation(), cx)
                                     .rounded(cx.theme().radius)
                                     .bg(cx.theme().background)
                                     .child(self.render_colors(window, cx))
@@ -481,7 +482,7 @@ impl RenderOnce for ColorPicker {
                                     ),
                             ),
                     )
-                    .with_priority(1),
+                    .with_priority(Layer::Dropdown.priority()),
                 )
             })
     }
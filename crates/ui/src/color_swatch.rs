@@ -0,0 +1,371 @@
+use std::rc::Rc;
+
+use gpui::{
+    canvas, div, fill, point, prelude::FluentBuilder as _, px, size, App, Bounds, ClickEvent,
+    ClipboardItem, ElementId, Hsla, InteractiveElement as _, IntoElement, ParentElement, Pixels,
+    RenderOnce, SharedString, StatefulInteractiveElement as _, StyleRefinement, Styled, Window,
+};
+
+use crate::{
+    color_picker::color_palettes, h_flex, tooltip::Tooltip, v_flex, ActiveTheme as _,
+    Colorize as _, Selectable, Sizable, Size, StyledExt as _,
+};
+
+fn swatch_size(size: Size) -> Pixels {
+    match size {
+        Size::XSmall => px(16.),
+        Size::Small => px(20.),
+        Size::Medium => px(24.),
+        Size::Large => px(32.),
+        Size::Size(size) => size,
+    }
+}
+
+/// Returns the hex, RGBA and HSLA values of the color, one per line.
+fn color_values(color: Hsla) -> Vec<String> {
+    let rgb = color.to_rgb();
+    vec![
+        color.to_hex(),
+        format!(
+            "rgba({}, {}, {}, {:.2})",
+            (rgb.r * 255.).round() as u8,
+            (rgb.g * 255.).round() as u8,
+            (rgb.b * 255.).round() as u8,
+            color.a
+        ),
+        format!(
+            "hsla({:.0}, {:.0}%, {:.0}%, {:.2})",
+            color.h * 360.,
+            color.s * 100.,
+            color.l * 100.,
+            color.a
+        ),
+    ]
+}
+
+/// Paint a checkerboard to show the transparency of the color above it.
+fn checkerboard() -> impl IntoElement {
+    canvas(
+        |_, _, _| {},
+        |bounds, _, window, _| {
+            let cell = px(4.);
+            let light = gpui::white();
+            let dark = gpui::hsla(0., 0., 0.8, 1.);
+
+            window.paint_quad(fill(bounds, light));
+            let cols = (bounds.size.width / cell).ceil() as usize;
+            let rows = (bounds.size.height / cell).ceil() as usize;
+            for row in 0..rows {
+                for col in (row % 2..cols).step_by(2) {
+                    let origin = bounds.origin + point(cell * col as f32, cell * row as f32);
+                    let cell_bounds = Bounds::new(origin, size(cell, cell)).intersect(&bounds);
+                    window.paint_quad(fill(cell_bounds, dark));
+                }
+            }
+        },
+    )
+    .absolute()
+    .size_full()
+}
+
+/// A swatch to display a color.
+///
+/// The transparent colors are displayed over a checkerboard, the tooltip shows the color values,
+/// and clicking it copies the hex value to the clipboard.
+#[derive(IntoElement)]
+pub struct ColorSwatch {
+    id: ElementId,
+    color: Hsla,
+    label: Option<SharedString>,
+    size: Size,
+    style: StyleRefinement,
+    selected: bool,
+    copyable: bool,
+    tooltip: bool,
+    on_click: Option<Rc<dyn Fn(&ClickEvent, &mut Window, &mut App)>>,
+}
+
+impl ColorSwatch {
+    /// Create a new color swatch.
+    pub fn new(id: impl Into<ElementId>, color: impl Into<Hsla>) -> Self {
+        Self {
+            id: id.into(),
+            color: color.into(),
+            label: None,
+            size: Size::default(),
+            style: StyleRefinement::default(),
+            selected: false,
+            copyable: true,
+            tooltip: true,
+            on_click: None,
+        }
+    }
+
+    /// Set the label of the color, e.g.: the name of a theme color, it is shown in the tooltip.
+    pub fn label(mut self, label: impl Into<SharedString>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set true to copy the hex value to the clipboard on click, default: true
+    pub fn copyable(mut self, copyable: bool) -> Self {
+        self.copyable = copyable;
+        self
+    }
+
+    /// Set true to show the color values in a tooltip, default: true
+    pub fn tooltip(mut self, tooltip: bool) -> Self {
+        self.tooltip = tooltip;
+        self
+    }
+
+    /// Add a click handler.
+    pub fn on_click(
+        mut self,
+        handler: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_click = Some(Rc::new(handler));
+        self
+    }
+}
+
+impl Sizable for ColorSwatch {
+    fn with_size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+}
+
+impl Selectable for ColorSwatch {
+    fn selected(mut self, selected: bool) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    fn is_selected(&self) -> bool {
+        self.selected
+    }
+}
+
+impl Styled for ColorSwatch {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for ColorSwatch {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let color = self.color;
+        let label = self.label;
+        let copyable = self.copyable;
+        let on_click = self.on_click;
+        let clickable = copyable || on_click.is_some();
+
+        div()
+            .id(self.id)
+            .relative()
+            .flex_shrink_0()
+            .size(swatch_size(self.size))
+            .border_1()
+            .border_color(cx.theme().border)
+            .when(color.a < 1., |this| this.child(checkerboard()))
+            .child(div().absolute().size_full().bg(color))
+            .when(self.selected, |this| {
+                this.border_2().border_color(cx.theme().ring)
+            })
+            .when(self.tooltip, |this| {
+                this.tooltip(move |window, cx| {
+                    let label = label.clone();
+                    Tooltip::element(move |_, _| {
+                        v_flex()
+                            .when_some(label.clone(), |this, label| {
+                                this.child(div().font_semibold().child(label))
+                            })
+                            .children(color_values(color))
+                    })
+                    .build(window, cx)
+                })
+            })
+            .when(clickable, |this| {
+                this.cursor_pointer()
+                    .hover(|this| this.border_color(color.darken(0.3)))
+                    .on_click(move |event, window, cx| {
+                        if copyable {
+                            cx.write_to_clipboard(ClipboardItem::new_string(color.to_hex()));
+                        }
+                        if let Some(on_click) = &on_click {
+                            on_click(event, window, cx);
+                        }
+                    })
+            })
+            .refine_style(&self.style)
+    }
+}
+
+/// A grid of [`ColorSwatch`] to display and pick colors.
+///
+/// ```ignore
+/// Palette::new("palette")
+///     .default_colors()
+///     .selected(Some(color))
+///     .on_select(|color, _, _| println!("{}", color.to_hex()))
+/// ```
+#[derive(IntoElement)]
+pub struct Palette {
+    id: ElementId,
+    colors: Vec<(Option<SharedString>, Hsla)>,
+    columns: Option<usize>,
+    size: Size,
+    style: StyleRefinement,
+    selected: Option<Hsla>,
+    copyable: bool,
+    on_select: Option<Rc<dyn Fn(&Hsla, &mut Window, &mut App)>>,
+}
+
+impl Palette {
+    /// Create a new empty palette.
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            colors: vec![],
+            columns: None,
+            size: Size::default(),
+            style: StyleRefinement::default(),
+            selected: None,
+            copyable: true,
+            on_select: None,
+        }
+    }
+
+    /// Add colors to the palette.
+    pub fn colors(mut self, colors: impl IntoIterator<Item = impl Into<Hsla>>) -> Self {
+        self.colors
+            .extend(colors.into_iter().map(|color| (None, color.into())));
+        self
+    }
+
+    /// Add colors with labels to the palette, e.g.: the theme colors with their names.
+    pub fn labeled_colors(
+        mut self,
+        colors: impl IntoIterator<Item = (impl Into<SharedString>, impl Into<Hsla>)>,
+    ) -> Self {
+        self.colors.extend(
+            colors
+                .into_iter()
+                .map(|(label, color)| (Some(label.into()), color.into())),
+        );
+        self
+    }
+
+    /// Add the default color scales, the same as the [`ColorPicker`](crate::color_picker::ColorPicker).
+    ///
+    /// One row per color, from light to dark.
+    pub fn default_colors(mut self) -> Self {
+        let palettes = color_palettes();
+        self.columns = palettes.first().map(|colors| colors.len());
+        for colors in palettes {
+            self = self.colors(colors.into_iter().rev());
+        }
+        self
+    }
+
+    /// Set the number of columns, default: None to wrap the colors by the width.
+    pub fn columns(mut self, columns: usize) -> Self {
+        self.columns = Some(columns.max(1));
+        self
+    }
+
+    /// Set the selected color.
+    pub fn selected(mut self, color: Option<Hsla>) -> Self {
+        self.selected = color;
+        self
+    }
+
+    /// Set true to copy the hex value of the color to the clipboard on click, default: true
+    pub fn copyable(mut self, copyable: bool) -> Self {
+        self.copyable = copyable;
+        self
+    }
+
+    /// Set a handler to be called when a color is clicked.
+    pub fn on_select(mut self, handler: impl Fn(&Hsla, &mut Window, &mut App) + 'static) -> Self {
+        self.on_select = Some(Rc::new(handler));
+        self
+    }
+}
+
+impl Sizable for Palette {
+    fn with_size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+}
+
+impl Styled for Palette {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for Palette {
+    fn render(self, _: &mut Window, _: &mut App) -> impl IntoElement {
+        let swatches = self
+            .colors
+            .into_iter()
+            .enumerate()
+            .map(|(ix, (label, color))| {
+                let on_select = self.on_select.clone();
+                ColorSwatch::new(ix, color)
+                    .with_size(self.size)
+                    .copyable(self.copyable)
+                    .selected(self.selected == Some(color))
+                    .when_some(label, |this, label| this.label(label))
+                    .when_some(on_select, |this, on_select| {
+                        this.on_click(move |_, window, cx| on_select(&color, window, cx))
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        let gap = if self.size == Size::Large {
+            px(6.)
+        } else {
+            px(4.)
+        };
+
+        div()
+            .id(self.id)
+            .map(|this| match self.columns {
+                Some(columns) => {
+                    let mut swatches = swatches.into_iter().peekable();
+                    let mut rows = vec![];
+                    while swatches.peek().is_some() {
+                        rows.push(h_flex().gap(gap).children(swatches.by_ref().take(columns)));
+                    }
+                    this.child(v_flex().gap(gap).children(rows))
+                }
+                None => this.child(h_flex().flex_wrap().gap(gap).children(swatches)),
+            })
+            .refine_style(&self.style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::color_values;
+    use crate::Colorize as _;
+    use gpui::Hsla;
+
+    #[test]
+    fn test_color_values() {
+        let color = Hsla::parse_hex("#00FF00").unwrap();
+        let values = color_values(color);
+        assert_eq!(values[0], "#00FF00");
+        assert_eq!(values[1], "rgba(0, 255, 0, 1.00)");
+        assert_eq!(values[2], "hsla(120, 100%, 50%, 1.00)");
+
+        let color = Hsla::parse_hex("#FF000080").unwrap();
+        let values = color_values(color);
+        assert_eq!(values[0], "#FF000080");
+        assert_eq!(values[1], "rgba(255, 0, 0, 0.50)");
+    }
+}
@@ -0,0 +1,379 @@
+use std::{collections::HashSet, ops::Range};
+
+use gpui::SharedString;
+use similar::{ChangeTag, TextDiff};
+
+/// The kind of a line in the diff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLineKind {
+    /// The line is unchanged.
+    Context,
+    /// The line only exists in the new text.
+    Added,
+    /// The line only exists in the old text.
+    Removed,
+}
+
+/// A line in the diff.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    /// The 0-based line number in the old text.
+    pub old_line: Option<usize>,
+    /// The 0-based line number in the new text.
+    pub new_line: Option<usize>,
+    /// The text without the line break.
+    pub text: SharedString,
+    /// The changed byte ranges within the line.
+    pub(crate) changes: Vec<Range<usize>>,
+}
+
+impl DiffLine {
+    fn new(
+        kind: DiffLineKind,
+        old_line: Option<usize>,
+        new_line: Option<usize>,
+        text: &str,
+    ) -> Self {
+        Self {
+            kind,
+            old_line,
+            new_line,
+            text: text.trim_end_matches(['\n', '\r']).to_string().into(),
+            changes: vec![],
+        }
+    }
+}
+
+/// Compute the line diff of two texts.
+pub(crate) fn diff_texts(old: &str, new: &str) -> Vec<DiffLine> {
+    let diff = TextDiff::from_lines(old, new);
+    let mut lines = diff
+        .iter_all_changes()
+        .map(|change| {
+            let kind = match change.tag() {
+                ChangeTag::Equal => DiffLineKind::Context,
+                ChangeTag::Insert => DiffLineKind::Added,
+                ChangeTag::Delete => DiffLineKind::Removed,
+            };
+            DiffLine::new(kind, change.old_index(), change.new_index(), change.value())
+        })
+        .collect::<Vec<_>>();
+
+    highlight_changes(&mut lines);
+    lines
+}
+
+/// Parse a unified diff, e.g.: the output of `git diff`.
+pub(crate) fn parse_patch(patch: &str) -> Vec<DiffLine> {
+    let mut lines = vec![];
+    let mut old_line = 0;
+    let mut new_line = 0;
+    let mut in_hunk = false;
+
+    for line in patch.lines() {
+        if let Some(header) = line.strip_prefix("@@ ") {
+            // @@ -1,3 +1,4 @@
+            let mut parts = header.split_whitespace();
+            let start = |part: Option<&str>, prefix: char| {
+                part.and_then(|p| p.strip_prefix(prefix))
+                    .and_then(|p| p.split(',').next())
+                    .and_then(|p| p.parse::<usize>().ok())
+                    .map(|n| n.saturating_sub(1))
+            };
+            old_line = start(parts.next(), '-').unwrap_or(0);
+            new_line = start(parts.next(), '+').unwrap_or(0);
+            in_hunk = true;
+            continue;
+        }
+        if !in_hunk {
+            continue;
+        }
+
+        let (kind, text) = match line.chars().next() {
+            Some('+') => (DiffLineKind::Added, &line[1..]),
+            Some('-') => (DiffLineKind::Removed, &line[1..]),
+            Some(' ') => (DiffLineKind::Context, &line[1..]),
+            None => (DiffLineKind::Context, ""),
+            // e.g.: `\ No newline at end of file`, or the header of the next file.
+            _ => {
+                in_hunk = line.starts_with('\\');
+                continue;
+            }
+        };
+
+        let (old, new) = match kind {
+            DiffLineKind::Context => (Some(old_line), Some(new_line)),
+            DiffLineKind::Added => (None, Some(new_line)),
+            DiffLineKind::Removed => (Some(old_line), None),
+        };
+        old_line += old.is_some() as usize;
+        new_line += new.is_some() as usize;
+        lines.push(DiffLine::new(kind, old, new, text));
+    }
+
+    highlight_changes(&mut lines);
+    lines
+}
+
+/// Pair the removed and added lines of each change block, and compute the changed ranges within them.
+fn highlight_changes(lines: &mut [DiffLine]) {
+    for hunk in hunks(lines) {
+        let removed = hunk
+            .clone()
+            .filter(|ix| lines[*ix].kind == DiffLineKind::Removed)
+            .collect::<Vec<_>>();
+        let added = hunk
+            .filter(|ix| lines[*ix].kind == DiffLineKind::Added)
+            .collect::<Vec<_>>();
+
+        for (old_ix, new_ix) in removed.into_iter().zip(added) {
+            let (old_changes, new_changes) =
+                inline_changes(&lines[old_ix].text, &lines[new_ix].text);
+            lines[old_ix].changes = old_changes;
+            lines[new_ix].changes = new_changes;
+        }
+    }
+}
+
+/// Returns the changed byte ranges in the old and new line, empty if the lines are too different.
+fn inline_changes(old: &str, new: &str) -> (Vec<Range<usize>>, Vec<Range<usize>>) {
+    let diff = TextDiff::from_chars(old, new);
+    if diff.ratio() < 0.5 {
+        return (vec![], vec![]);
+    }
+
+    let mut old_changes: Vec<Range<usize>> = vec![];
+    let mut new_changes: Vec<Range<usize>> = vec![];
+    let (mut old_offset, mut new_offset) = (0, 0);
+    let push = |changes: &mut Vec<Range<usize>>, range: Range<usize>| match changes.last_mut() {
+        Some(last) if last.end == range.start => last.end = range.end,
+        _ => changes.push(range),
+    };
+
+    for change in diff.iter_all_changes() {
+        let len = change.value().len();
+        match change.tag() {
+            ChangeTag::Equal => {
+                old_offset += len;
+                new_offset += len;
+            }
+            ChangeTag::Delete => {
+                push(&mut old_changes, old_offset..old_offset + len);
+                old_offset += len;
+            }
+            ChangeTag::Insert => {
+                push(&mut new_changes, new_offset..new_offset + len);
+                new_offset += len;
+            }
+        }
+    }
+
+    (old_changes, new_changes)
+}
+
+/// Returns the ranges of the consecutive changed lines.
+pub(crate) fn hunks(lines: &[DiffLine]) -> Vec<Range<usize>> {
+    let mut hunks: Vec<Range<usize>> = vec![];
+    for (ix, line) in lines.iter().enumerate() {
+        if line.kind == DiffLineKind::Context {
+            continue;
+        }
+        match hunks.last_mut() {
+            Some(last) if last.end == ix => last.end = ix + 1,
+            _ => hunks.push(ix..ix + 1),
+        }
+    }
+    hunks
+}
+
+/// A row to display in the [`super::DiffView`].
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum DiffRow {
+    /// A line in the unified view.
+    Line(usize),
+    /// The old and new line in the side-by-side view.
+    Pair(Option<usize>, Option<usize>),
+    /// The collapsed unchanged lines.
+    Collapsed(Range<usize>),
+    /// The lines between the hunks of a patch are unknown.
+    Gap,
+}
+
+impl DiffRow {
+    /// The first line index of the row.
+    pub(crate) fn first_line(&self) -> Option<usize> {
+        match self {
+            DiffRow::Line(ix) => Some(*ix),
+            DiffRow::Pair(old, new) => old.or(*new),
+            DiffRow::Collapsed(range) => Some(range.start),
+            DiffRow::Gap => None,
+        }
+    }
+}
+
+/// Returns true if the line numbers are not continuous from `prev` to `line`.
+fn is_gap(prev: &DiffLine, line: &DiffLine) -> bool {
+    let gap = |prev: Option<usize>, next: Option<usize>| match (prev, next) {
+        (Some(prev), Some(next)) => next != prev + 1,
+        _ => false,
+    };
+    gap(prev.old_line, line.old_line) || gap(prev.new_line, line.new_line)
+}
+
+/// Build the rows to display.
+///
+/// Unchanged lines more than `context` lines away from a change are collapsed,
+/// unless the first collapsed line index is in `expanded`.
+pub(crate) fn build_rows(
+    lines: &[DiffLine],
+    side_by_side: bool,
+    context: usize,
+    expanded: &HashSet<usize>,
+) -> Vec<DiffRow> {
+    let mut rows = vec![];
+    let mut ix = 0;
+
+    while ix < lines.len() {
+        if ix > 0 && is_gap(&lines[ix - 1], &lines[ix]) {
+            rows.push(DiffRow::Gap);
+        }
+
+        let start = ix;
+        let is_context = lines[ix].kind == DiffLineKind::Context;
+        ix += 1;
+        while ix < lines.len()
+            && (lines[ix].kind == DiffLineKind::Context) == is_context
+            && !is_gap(&lines[ix - 1], &lines[ix])
+        {
+            ix += 1;
+        }
+
+        if is_context {
+            let at_start = start == 0 || rows.last() == Some(&DiffRow::Gap);
+            let at_end = ix == lines.len() || is_gap(&lines[ix - 1], &lines[ix]);
+            let lead = if at_start { 0 } else { context };
+            let trail = if at_end { 0 } else { context };
+
+            let hidden = (start + lead)..ix.saturating_sub(trail).max(start + lead);
+            if hidden.len() > 1 && !expanded.contains(&hidden.start) {
+                push_context_rows(&mut rows, start..hidden.start, side_by_side);
+                rows.push(DiffRow::Collapsed(hidden.clone()));
+                push_context_rows(&mut rows, hidden.end..ix, side_by_side);
+            } else {
+                push_context_rows(&mut rows, start..ix, side_by_side);
+            }
+        } else if side_by_side {
+            let removed = (start..ix).filter(|i| lines[*i].kind == DiffLineKind::Removed);
+            let added = (start..ix)
+                .filter(|i| lines[*i].kind == DiffLineKind::Added)
+                .collect::<Vec<_>>();
+            let removed = removed.collect::<Vec<_>>();
+            for i in 0..removed.len().max(added.len()) {
+                rows.push(DiffRow::Pair(
+                    removed.get(i).copied(),
+                    added.get(i).copied(),
+                ));
+            }
+        } else {
+            rows.extend((start..ix).map(DiffRow::Line));
+        }
+    }
+
+    rows
+}
+
+fn push_context_rows(rows: &mut Vec<DiffRow>, range: Range<usize>, side_by_side: bool) {
+    rows.extend(range.map(|ix| {
+        if side_by_side {
+            DiffRow::Pair(Some(ix), Some(ix))
+        } else {
+            DiffRow::Line(ix)
+        }
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use indoc::indoc;
+
+    use super::{build_rows, diff_texts, hunks, parse_patch, DiffLineKind, DiffRow};
+
+    #[test]
+    fn test_diff_texts() {
+        let lines = diff_texts("a\nb\nc\n", "a\nB\nc\nd\n");
+        let kinds = lines.iter().map(|l| l.kind).collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                DiffLineKind::Context,
+                DiffLineKind::Removed,
+                DiffLineKind::Added,
+                DiffLineKind::Context,
+                DiffLineKind::Added,
+            ]
+        );
+        assert_eq!(lines[4].text.as_ref(), "d");
+        assert_eq!(lines[4].new_line, Some(3));
+        assert_eq!(hunks(&lines), vec![1..3, 4..5]);
+    }
+
+    #[test]
+    fn test_inline_changes() {
+        let lines = diff_texts("let a = 1;\n", "let a = 2;\n");
+        assert_eq!(lines[0].changes, vec![8..9]);
+        assert_eq!(lines[1].changes, vec![8..9]);
+
+        // Too different lines are not highlighted.
+        let lines = diff_texts("foo\n", "bar\n");
+        assert!(lines[0].changes.is_empty());
+    }
+
+    #[test]
+    fn test_parse_patch() {
+        let patch = indoc! {r#"
+            diff --git a/foo.rs b/foo.rs
+            --- a/foo.rs
+            +++ b/foo.rs
+            @@ -10,3 +10,3 @@ fn main() {
+             one
+            -two
+            +TWO
+             three
+            @@ -20,2 +20,3 @@
+             four
+            +five
+             six
+        "#};
+
+        let lines = parse_patch(patch);
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[0].old_line, Some(9));
+        assert_eq!(lines[2].kind, DiffLineKind::Added);
+        assert_eq!(lines[2].new_line, Some(10));
+        assert_eq!(lines[5].new_line, Some(20));
+
+        let rows = build_rows(&lines, false, 3, &HashSet::new());
+        assert_eq!(rows.iter().filter(|r| **r == DiffRow::Gap).count(), 1);
+    }
+
+    #[test]
+    fn test_build_rows() {
+        let old = (0..20).map(|i| format!("{}\n", i)).collect::<String>();
+        let new = old.replace("10\n", "ten\n");
+        let lines = diff_texts(&old, &new);
+
+        let rows = build_rows(&lines, false, 2, &HashSet::new());
+        assert_eq!(rows[0], DiffRow::Collapsed(0..8));
+        assert_eq!(rows[1], DiffRow::Line(8));
+        assert_eq!(rows[5], DiffRow::Line(12));
+        assert_eq!(rows[7], DiffRow::Collapsed(14..21));
+
+        let expanded = HashSet::from([0]);
+        let rows = build_rows(&lines, true, 2, &expanded);
+        assert_eq!(rows[0], DiffRow::Pair(Some(0), Some(0)));
+        assert_eq!(rows[10], DiffRow::Pair(Some(10), Some(11)));
+    }
+}
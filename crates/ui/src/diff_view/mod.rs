@@ -0,0 +1,414 @@
+mod diff;
+
+use std::{collections::HashSet, ops::Range};
+
+use gpui::{
+    actions, div, px, uniform_list, AnyElement, App, Context, ElementId, Entity, FocusHandle,
+    HighlightStyle, Hsla, InteractiveElement as _, IntoElement, KeyBinding, ListSizingBehavior,
+    ParentElement, Render, RenderOnce, ScrollStrategy, SharedString,
+    StatefulInteractiveElement as _, StyleRefinement, Styled, StyledText, UniformListScrollHandle,
+    Window,
+};
+use rust_i18n::t;
+
+use crate::{
+    h_flex,
+    scroll::{Scrollbar, ScrollbarState},
+    ActiveTheme, Icon, IconName, Sizable as _, StyledExt as _,
+};
+use diff::{build_rows, diff_texts, hunks, parse_patch, DiffRow};
+
+pub use diff::{DiffLine, DiffLineKind};
+
+actions!(diff_view, [NextHunk, PreviousHunk]);
+
+const CONTEXT: &str = "DiffView";
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("f7", NextHunk, Some(CONTEXT)),
+        KeyBinding::new("shift-f7", PreviousHunk, Some(CONTEXT)),
+    ]);
+}
+
+/// The display mode of the [`DiffView`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DiffViewMode {
+    /// The removed and added lines are interleaved in one column.
+    #[default]
+    Unified,
+    /// The old text on the left, and the new text on the right.
+    SideBySide,
+}
+
+/// State of the [`DiffView`].
+pub struct DiffViewState {
+    focus_handle: FocusHandle,
+    lines: Vec<DiffLine>,
+    hunks: Vec<Range<usize>>,
+    rows: Vec<DiffRow>,
+    mode: DiffViewMode,
+    context_lines: usize,
+    /// The first line index of the collapsed regions that were expanded.
+    expanded: HashSet<usize>,
+    current_hunk: Option<usize>,
+    scroll_handle: UniformListScrollHandle,
+    scrollbar_state: ScrollbarState,
+}
+
+impl DiffViewState {
+    /// Create a new empty diff view state.
+    pub fn new(_: &mut Window, cx: &mut Context<Self>) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            lines: vec![],
+            hunks: vec![],
+            rows: vec![],
+            mode: DiffViewMode::default(),
+            context_lines: 3,
+            expanded: HashSet::new(),
+            current_hunk: None,
+            scroll_handle: UniformListScrollHandle::default(),
+            scrollbar_state: ScrollbarState::default(),
+        }
+    }
+
+    /// Set the display mode, default: [`DiffViewMode::Unified`]
+    pub fn mode(mut self, mode: DiffViewMode) -> Self {
+        self.mode = mode;
+        self.rebuild_rows();
+        self
+    }
+
+    /// Set the number of unchanged lines to show around the changes, default: 3
+    ///
+    /// The other unchanged lines are collapsed.
+    pub fn context_lines(mut self, context_lines: usize) -> Self {
+        self.context_lines = context_lines;
+        self.rebuild_rows();
+        self
+    }
+
+    /// Set the old and new text to compare.
+    pub fn texts(mut self, old: &str, new: &str) -> Self {
+        self.set_lines(diff_texts(old, new));
+        self
+    }
+
+    /// Set the old and new text to compare.
+    pub fn set_texts(&mut self, old: &str, new: &str, cx: &mut Context<Self>) {
+        self.set_lines(diff_texts(old, new));
+        cx.notify();
+    }
+
+    /// Set a precomputed diff in the unified format, e.g.: the output of `git diff`.
+    pub fn set_patch(&mut self, patch: &str, cx: &mut Context<Self>) {
+        self.set_lines(parse_patch(patch));
+        cx.notify();
+    }
+
+    fn set_lines(&mut self, lines: Vec<DiffLine>) {
+        self.hunks = hunks(&lines);
+        self.lines = lines;
+        self.expanded.clear();
+        self.current_hunk = None;
+        self.rebuild_rows();
+    }
+
+    fn rebuild_rows(&mut self) {
+        self.rows = build_rows(
+            &self.lines,
+            self.mode == DiffViewMode::SideBySide,
+            self.context_lines,
+            &self.expanded,
+        );
+    }
+
+    /// Set the display mode.
+    pub fn set_mode(&mut self, mode: DiffViewMode, cx: &mut Context<Self>) {
+        self.mode = mode;
+        self.rebuild_rows();
+        cx.notify();
+    }
+
+    /// Returns the display mode.
+    pub fn display_mode(&self) -> DiffViewMode {
+        self.mode
+    }
+
+    /// Returns all lines of the diff.
+    pub fn lines(&self) -> &[DiffLine] {
+        &self.lines
+    }
+
+    /// Returns the number of hunks (the groups of consecutive changed lines).
+    pub fn hunks_count(&self) -> usize {
+        self.hunks.len()
+    }
+
+    /// Returns the index of the hunk navigated to, if any.
+    pub fn current_hunk(&self) -> Option<usize> {
+        self.current_hunk
+    }
+
+    /// Expand all collapsed unchanged lines.
+    pub fn expand_all(&mut self, cx: &mut Context<Self>) {
+        for row in self.rows.iter() {
+            if let DiffRow::Collapsed(range) = row {
+                self.expanded.insert(range.start);
+            }
+        }
+        self.rebuild_rows();
+        cx.notify();
+    }
+
+    fn expand(&mut self, start: usize, cx: &mut Context<Self>) {
+        self.expanded.insert(start);
+        self.rebuild_rows();
+        cx.notify();
+    }
+
+    /// Scroll to the next hunk, wraps around to the first one.
+    pub fn next_hunk(&mut self, cx: &mut Context<Self>) {
+        if self.hunks.is_empty() {
+            return;
+        }
+
+        let ix = match self.current_hunk {
+            Some(ix) if ix + 1 < self.hunks.len() => ix + 1,
+            _ => 0,
+        };
+        self.scroll_to_hunk(ix, cx);
+    }
+
+    /// Scroll to the previous hunk, wraps around to the last one.
+    pub fn previous_hunk(&mut self, cx: &mut Context<Self>) {
+        if self.hunks.is_empty() {
+            return;
+        }
+
+        let ix = match self.current_hunk {
+            Some(ix) if ix > 0 => ix - 1,
+            _ => self.hunks.len() - 1,
+        };
+        self.scroll_to_hunk(ix, cx);
+    }
+
+    fn scroll_to_hunk(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let line_ix = self.hunks[ix].start;
+        if let Some(row_ix) = self
+            .rows
+            .iter()
+            .position(|row| row.first_line().map_or(false, |l| l >= line_ix))
+        {
+            self.scroll_handle.scroll_to_item(
+                row_ix.saturating_sub(self.context_lines),
+                ScrollStrategy::Top,
+            );
+        }
+        self.current_hunk = Some(ix);
+        cx.notify();
+    }
+
+    fn on_action_next_hunk(&mut self, _: &NextHunk, _: &mut Window, cx: &mut Context<Self>) {
+        self.next_hunk(cx);
+    }
+
+    fn on_action_previous_hunk(
+        &mut self,
+        _: &PreviousHunk,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.previous_hunk(cx);
+    }
+
+    fn render_half(&self, line_ix: Option<usize>, old: bool, cx: &App) -> AnyElement {
+        let Some(line) = line_ix.and_then(|ix| self.lines.get(ix)) else {
+            return div()
+                .flex_1()
+                .h_full()
+                .bg(cx.theme().muted.opacity(0.5))
+                .into_any_element();
+        };
+
+        let number = if old { line.old_line } else { line.new_line };
+        h_flex()
+            .flex_1()
+            .overflow_hidden()
+            .h_full()
+            .bg(line_bg(line.kind, cx))
+            .child(render_gutter(number, cx))
+            .child(render_text(line, cx))
+            .into_any_element()
+    }
+
+    fn render_row(&self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        match &self.rows[ix] {
+            DiffRow::Line(line_ix) => {
+                let line = &self.lines[*line_ix];
+                h_flex()
+                    .w_full()
+                    .bg(line_bg(line.kind, cx))
+                    .child(render_gutter(line.old_line, cx))
+                    .child(render_gutter(line.new_line, cx))
+                    .child(render_text(line, cx))
+                    .into_any_element()
+            }
+            DiffRow::Pair(old, new) => h_flex()
+                .w_full()
+                .child(self.render_half(*old, true, cx))
+                .child(div().w(px(1.)).h_full().bg(cx.theme().border))
+                .child(self.render_half(*new, false, cx))
+                .into_any_element(),
+            DiffRow::Collapsed(range) => {
+                let start = range.start;
+                h_flex()
+                    .id(ix)
+                    .w_full()
+                    .px_2()
+                    .gap_2()
+                    .bg(cx.theme().muted)
+                    .text_color(cx.theme().muted_foreground)
+                    .cursor_pointer()
+                    .hover(|this| this.text_color(cx.theme().foreground))
+                    .child(Icon::new(IconName::ChevronsUpDown).xsmall())
+                    .child(t!("DiffView.unchanged_lines", count = range.len()).to_string())
+                    .on_click(cx.listener(move |this, _, _, cx| this.expand(start, cx)))
+                    .into_any_element()
+            }
+            DiffRow::Gap => h_flex()
+                .w_full()
+                .px_2()
+                .bg(cx.theme().muted)
+                .text_color(cx.theme().muted_foreground)
+                .child(Icon::new(IconName::Ellipsis).xsmall())
+                .into_any_element(),
+        }
+    }
+}
+
+fn line_bg(kind: DiffLineKind, cx: &App) -> Hsla {
+    match kind {
+        DiffLineKind::Context => cx.theme().transparent,
+        DiffLineKind::Added => cx.theme().success.opacity(0.15),
+        DiffLineKind::Removed => cx.theme().danger.opacity(0.15),
+    }
+}
+
+fn render_gutter(number: Option<usize>, cx: &App) -> impl IntoElement {
+    div()
+        .flex_shrink_0()
+        .w(px(48.))
+        .px_2()
+        .text_right()
+        .text_color(cx.theme().muted_foreground)
+        .children(number.map(|n| SharedString::from((n + 1).to_string())))
+}
+
+fn render_text(line: &DiffLine, cx: &App) -> impl IntoElement {
+    let (sign, change_color) = match line.kind {
+        DiffLineKind::Context => (" ", cx.theme().transparent),
+        DiffLineKind::Added => ("+", cx.theme().success.opacity(0.35)),
+        DiffLineKind::Removed => ("-", cx.theme().danger.opacity(0.35)),
+    };
+    let highlights = line.changes.iter().map(|range| {
+        (
+            range.clone(),
+            HighlightStyle {
+                background_color: Some(change_color),
+                ..Default::default()
+            },
+        )
+    });
+
+    h_flex()
+        .flex_1()
+        .whitespace_nowrap()
+        .child(div().w(px(16.)).flex_shrink_0().child(sign))
+        .child(StyledText::new(line.text.clone()).with_highlights(highlights))
+}
+
+impl Render for DiffViewState {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .relative()
+            .size_full()
+            .child(
+                uniform_list("rows", self.rows.len(), {
+                    cx.processor(move |state, visible_range: Range<usize>, _, cx| {
+                        visible_range
+                            .map(|ix| state.render_row(ix, cx))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .size_full()
+                .track_scroll(self.scroll_handle.clone())
+                .with_sizing_behavior(ListSizingBehavior::Auto),
+            )
+            .child(
+                div()
+                    .absolute()
+                    .top_0()
+                    .right_0()
+                    .bottom_0()
+                    .w(Scrollbar::width())
+                    .child(Scrollbar::vertical(
+                        &self.scrollbar_state,
+                        &self.scroll_handle,
+                    )),
+            )
+    }
+}
+
+/// A view to display the differences between two texts.
+///
+/// ```ignore
+/// let state = cx.new(|cx| {
+///     DiffViewState::new(window, cx)
+///         .mode(DiffViewMode::SideBySide)
+///         .texts("old text", "new text")
+/// });
+///
+/// DiffView::new(&state)
+/// ```
+#[derive(IntoElement)]
+pub struct DiffView {
+    id: ElementId,
+    state: Entity<DiffViewState>,
+    style: StyleRefinement,
+}
+
+impl DiffView {
+    /// Create a new diff view with the given state.
+    pub fn new(state: &Entity<DiffViewState>) -> Self {
+        Self {
+            id: ElementId::Name(format!("diff-view-{}", state.entity_id()).into()),
+            state: state.clone(),
+            style: StyleRefinement::default(),
+        }
+    }
+}
+
+impl Styled for DiffView {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for DiffView {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let focus_handle = self.state.read(cx).focus_handle.clone();
+
+        div()
+            .id(self.id)
+            .key_context(CONTEXT)
+            .track_focus(&focus_handle)
+            .on_action(window.listener_for(&self.state, DiffViewState::on_action_next_hunk))
+            .on_action(window.listener_for(&self.state, DiffViewState::on_action_previous_hunk))
+            .size_full()
+            .font_family("Menlo, Monaco, Consolas, monospace")
+            .text_xs()
+            .child(self.state)
+            .refine_style(&self.style)
+    }
+}
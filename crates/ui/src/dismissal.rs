@@ -0,0 +1,169 @@
+use std::{collections::BTreeSet, io, path::PathBuf};
+
+use gpui::{App, Global, SharedString};
+use serde::{Deserialize, Serialize};
+
+/// The ids of the dismissed UI saved in the file, see [`Dismissals::load`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DismissalState {
+    /// The ids dismissed by [`Dismissals::dismiss`], sorted.
+    #[serde(default)]
+    pub dismissed: BTreeSet<SharedString>,
+}
+
+/// The global memory of the dismissed UI keyed by string ids, e.g.: alerts, onboarding tours
+/// or "what's new" banners, to not show them again.
+///
+/// The ids dismissed by [`Dismissals::dismiss`] are saved to the file set by [`Dismissals::load`]
+/// to remember them across restarts, and the [`Dismissals::dismiss_for_session`] only until
+/// the app quits.
+///
+/// ```ignore
+/// Dismissals::load(paths::config_dir().join("dismissals.json"), cx)?;
+///
+/// if !dismissed("welcome-banner", cx) {
+///     Alert::info("welcome-banner", "Welcome!").dismiss_id("welcome-banner")
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Dismissals {
+    path: Option<PathBuf>,
+    state: DismissalState,
+    session: BTreeSet<SharedString>,
+}
+
+impl Global for Dismissals {}
+
+impl Dismissals {
+    /// Returns the global dismissals.
+    pub fn global(cx: &App) -> &Self {
+        cx.global::<Self>()
+    }
+
+    /// Load the dismissed ids from the file at `path`, and save to it on the later changes.
+    ///
+    /// A missing file is treated as empty, it's created on the first [`Self::dismiss`].
+    pub fn load(path: impl Into<PathBuf>, cx: &mut App) -> io::Result<()> {
+        let path = path.into();
+        let state = match std::fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json)?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => DismissalState::default(),
+            Err(err) => return Err(err),
+        };
+
+        let this = cx.global_mut::<Self>();
+        this.state.dismissed.extend(state.dismissed);
+        this.path = Some(path);
+        Ok(())
+    }
+
+    /// Returns the persisted state, e.g. to save it in the settings of the app instead of a file.
+    pub fn state(&self) -> &DismissalState {
+        &self.state
+    }
+
+    /// Replace the persisted state, e.g. loaded from the settings of the app.
+    pub fn set_state(state: DismissalState, cx: &mut App) {
+        cx.global_mut::<Self>().state = state;
+    }
+
+    /// Returns true if the `id` is dismissed, in the persisted state or this session.
+    pub fn is_dismissed(&self, id: &str) -> bool {
+        self.state.dismissed.contains(id) || self.session.contains(id)
+    }
+
+    /// Dismiss the `id` and remember it across restarts.
+    pub fn dismiss(id: impl Into<SharedString>, cx: &mut App) -> io::Result<()> {
+        let this = cx.global_mut::<Self>();
+        if this.state.dismissed.insert(id.into()) {
+            this.save()?;
+        }
+        Ok(())
+    }
+
+    /// Dismiss the `id` until the app quits, it's shown again on the next launch.
+    pub fn dismiss_for_session(id: impl Into<SharedString>, cx: &mut App) {
+        cx.global_mut::<Self>().session.insert(id.into());
+    }
+
+    /// Show the `id` again, e.g. by a "Show tips again" menu.
+    pub fn restore(id: &str, cx: &mut App) -> io::Result<()> {
+        let this = cx.global_mut::<Self>();
+        this.session.remove(id);
+        if this.state.dismissed.remove(id) {
+            this.save()?;
+        }
+        Ok(())
+    }
+
+    /// Show all the dismissed ids again.
+    pub fn reset(cx: &mut App) -> io::Result<()> {
+        let this = cx.global_mut::<Self>();
+        this.session.clear();
+        this.state.dismissed.clear();
+        this.save()
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(&self.state)?)
+    }
+}
+
+/// Returns true if the `id` is dismissed, see [`Dismissals`].
+pub fn dismissed(id: &str, cx: &App) -> bool {
+    cx.try_global::<Dismissals>()
+        .is_some_and(|this| this.is_dismissed(id))
+}
+
+pub(crate) fn init(cx: &mut App) {
+    cx.set_global(Dismissals::default());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dismissed, DismissalState, Dismissals};
+
+    #[gpui::test]
+    fn test_dismissals(cx: &mut gpui::TestAppContext) {
+        let path = std::env::temp_dir()
+            .join("gpui-component-test-dismissals")
+            .join("dismissals.json");
+        _ = std::fs::remove_file(&path);
+
+        cx.update(|cx| {
+            super::init(cx);
+            Dismissals::load(&path, cx).unwrap();
+            assert!(!dismissed("welcome-banner", cx));
+
+            Dismissals::dismiss("welcome-banner", cx).unwrap();
+            Dismissals::dismiss_for_session("tour", cx);
+            assert!(dismissed("welcome-banner", cx));
+            assert!(dismissed("tour", cx));
+        });
+
+        // Restart
+        cx.update(|cx| {
+            super::init(cx);
+            assert!(!dismissed("welcome-banner", cx));
+            Dismissals::load(&path, cx).unwrap();
+            assert!(dismissed("welcome-banner", cx));
+            assert!(!dismissed("tour", cx));
+
+            Dismissals::restore("welcome-banner", cx).unwrap();
+            assert!(!dismissed("welcome-banner", cx));
+        });
+
+        let json = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            serde_json::from_str::<DismissalState>(&json).unwrap(),
+            DismissalState::default()
+        );
+        _ = std::fs::remove_file(&path);
+    }
+}
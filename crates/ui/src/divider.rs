@@ -1,7 +1,7 @@
-use crate::{ActiveTheme, StyledExt};
+use crate::{dpi::hairline, ActiveTheme, StyledExt};
 use gpui::{
-    div, prelude::FluentBuilder as _, px, App, Axis, Div, Hsla, IntoElement, ParentElement,
-    RenderOnce, SharedString, StyleRefinement, Styled, Window,
+    div, prelude::FluentBuilder as _, App, Axis, Div, Hsla, IntoElement, ParentElement, RenderOnce,
+    SharedString, StyleRefinement, Styled, Window,
 };
 
 /// A divider that can be either vertical or horizontal.
@@ -57,7 +57,9 @@ impl Styled for Divider {
 }
 
 impl RenderOnce for Divider {
-    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let line_width = hairline(window);
+
         self.base
             .flex()
             .flex_shrink_0()
@@ -68,8 +70,8 @@ impl RenderOnce for Divider {
                 div()
                     .absolute()
                     .map(|this| match self.axis {
-                        Axis::Vertical => this.w(px(1.)).h_full(),
-                        Axis::Horizontal => this.h(px(1.)).w_full(),
+                        Axis::Vertical => this.w(line_width).h_full(),
+                        Axis::Horizontal => this.h(line_width).w_full(),
                     })
                     .bg(self.color.unwrap_or(cx.theme().border)),
             )
@@ -0,0 +1,79 @@
+use gpui::{px, Context, Pixels, Subscription, Window};
+
+use crate::Root;
+
+/// Emitted by the [`Root`] when the scale factor of the window is changed,
+/// e.g. the window is moved to a monitor with a different DPI.
+///
+/// The images and SVGs rendered by GPUI are rasterized again by the new scale factor,
+/// use this event to refresh the caches rasterized by yourself.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScaleChanged {
+    /// The previous scale factor.
+    pub old: f32,
+    /// The new scale factor.
+    pub new: f32,
+}
+
+/// Subscribe the [`ScaleChanged`] event of the window.
+///
+/// Returns `None` if the root view of the window is not a [`Root`].
+///
+/// ```ignore
+/// let subscription = observe_scale_changed(window, cx, |this, event, _, cx| {
+///     this.thumbnail = None;
+///     cx.notify();
+/// });
+/// ```
+pub fn observe_scale_changed<T: 'static>(
+    window: &mut Window,
+    cx: &mut Context<T>,
+    f: impl Fn(&mut T, &ScaleChanged, &mut Window, &mut Context<T>) + 'static,
+) -> Option<Subscription> {
+    let root = window.root::<Root>().flatten()?;
+    Some(
+        cx.subscribe_in(&root, window, move |this, _, event, window, cx| {
+            f(this, event, window, cx)
+        }),
+    )
+}
+
+/// Round the `value` to the device pixels, to keep the lines and borders crisp
+/// with the fractional scale factors, e.g. 1px is 2 device pixels at 150%.
+///
+/// A positive value is at least 1 device pixel.
+pub fn snap_to_device(value: Pixels, scale_factor: f32) -> Pixels {
+    if scale_factor <= 0. {
+        return value;
+    }
+
+    let device = (f32::from(value) * scale_factor).round();
+    let device = if value > px(0.) {
+        device.max(1.)
+    } else {
+        device
+    };
+    px(device / scale_factor)
+}
+
+/// Returns the width of a hairline, the 1px snapped to the device pixels of the window.
+pub fn hairline(window: &Window) -> Pixels {
+    snap_to_device(px(1.), window.scale_factor())
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::px;
+
+    use super::snap_to_device;
+
+    #[test]
+    fn test_snap_to_device() {
+        assert_eq!(snap_to_device(px(1.), 1.), px(1.));
+        assert_eq!(snap_to_device(px(1.), 2.), px(1.));
+        assert_eq!(snap_to_device(px(1.), 1.5), px(2. / 1.5));
+        assert_eq!(snap_to_device(px(0.2), 1.), px(1.));
+        assert_eq!(snap_to_device(px(0.), 1.25), px(0.));
+        assert_eq!(snap_to_device(px(10.3), 2.), px(10.5));
+    }
+}
@@ -0,0 +1,531 @@
+use std::io::Cursor;
+
+use gpui::{
+    canvas, div, fill, point, prelude::FluentBuilder as _, px, size, App, Bounds, Context,
+    ElementId, Entity, EventEmitter, FocusHandle, Focusable, Hsla, InteractiveElement as _,
+    IntoElement, KeyBinding, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
+    ParentElement, PathBuilder, Pixels, Point, Render, RenderOnce, SharedString, Size,
+    StyleRefinement, Styled, Window,
+};
+use image::{ImageFormat, Rgba, RgbaImage};
+
+use crate::{
+    input::{Redo, Undo},
+    ActiveTheme, Colorize as _, StyledExt as _,
+};
+
+const CONTEXT: &str = "DrawPad";
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-z", Undo, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-shift-z", Redo, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-z", Undo, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-y", Redo, Some(CONTEXT)),
+    ]);
+}
+
+/// A point of the [`Stroke`], relative to the pad.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StrokePoint {
+    pub position: Point<Pixels>,
+    /// The pressure in range: 0.0 ..= 1.0, the mouse input is always 1.0.
+    pub pressure: f32,
+}
+
+impl StrokePoint {
+    pub fn new(position: Point<Pixels>, pressure: f32) -> Self {
+        Self {
+            position,
+            pressure: pressure.clamp(0., 1.),
+        }
+    }
+}
+
+/// A freehand stroke on the [`DrawPad`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stroke {
+    pub points: Vec<StrokePoint>,
+    pub color: Hsla,
+    /// The width at the full pressure.
+    pub width: Pixels,
+}
+
+impl Stroke {
+    /// Returns the width at the pressure, at least 20% of the width.
+    fn width_at(&self, pressure: f32) -> Pixels {
+        self.width * (0.2 + 0.8 * pressure)
+    }
+
+    /// Returns the segments with their widths, a single point is a segment to itself.
+    fn segments(&self) -> impl Iterator<Item = (Point<Pixels>, Point<Pixels>, Pixels)> + '_ {
+        let single = (self.points.len() == 1).then(|| {
+            let p = self.points[0];
+            (p.position, p.position, self.width_at(p.pressure))
+        });
+        self.points
+            .windows(2)
+            .map(|w| {
+                let width = self.width_at((w[0].pressure + w[1].pressure) / 2.);
+                (w[0].position, w[1].position, width)
+            })
+            .chain(single)
+    }
+}
+
+/// Events emitted by the [`DrawPadState`].
+#[derive(Debug, Clone)]
+pub enum DrawPadEvent {
+    /// The strokes were changed by drawing, undo, redo or clear.
+    Change,
+}
+
+/// State of the [`DrawPad`].
+pub struct DrawPadState {
+    focus_handle: FocusHandle,
+    strokes: Vec<Stroke>,
+    /// The undone strokes, cleared when a new stroke is drawn.
+    redo_stack: Vec<Stroke>,
+    /// The stroke being drawn.
+    current: Option<Stroke>,
+    color: Option<Hsla>,
+    width: Pixels,
+    bounds: Bounds<Pixels>,
+}
+
+impl DrawPadState {
+    pub fn new(_: &mut Window, cx: &mut Context<Self>) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            strokes: vec![],
+            redo_stack: vec![],
+            current: None,
+            color: None,
+            width: px(2.5),
+            bounds: Bounds::default(),
+        }
+    }
+
+    /// Set the color of the new strokes, default is the foreground color of the theme.
+    pub fn color(mut self, color: impl Into<Hsla>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Set the width of the new strokes at the full pressure, default: 2.5px
+    pub fn width(mut self, width: impl Into<Pixels>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    /// Set the color of the new strokes.
+    pub fn set_color(&mut self, color: impl Into<Hsla>, cx: &mut Context<Self>) {
+        self.color = Some(color.into());
+        cx.notify();
+    }
+
+    /// Set the width of the new strokes.
+    pub fn set_width(&mut self, width: impl Into<Pixels>, cx: &mut Context<Self>) {
+        self.width = width.into();
+        cx.notify();
+    }
+
+    /// Returns the strokes.
+    pub fn strokes(&self) -> &[Stroke] {
+        &self.strokes
+    }
+
+    /// Returns true if nothing is drawn.
+    pub fn is_empty(&self) -> bool {
+        self.strokes.is_empty()
+    }
+
+    /// Returns the size of the pad.
+    pub fn size(&self) -> Size<Pixels> {
+        self.bounds.size
+    }
+
+    /// Add a stroke, e.g.: from a pen input with the pressure.
+    pub fn add_stroke(&mut self, stroke: Stroke, cx: &mut Context<Self>) {
+        if stroke.points.is_empty() {
+            return;
+        }
+
+        self.strokes.push(stroke);
+        self.redo_stack.clear();
+        cx.emit(DrawPadEvent::Change);
+        cx.notify();
+    }
+
+    /// Undo the last stroke.
+    pub fn undo(&mut self, cx: &mut Context<Self>) {
+        if let Some(stroke) = self.strokes.pop() {
+            self.redo_stack.push(stroke);
+            cx.emit(DrawPadEvent::Change);
+            cx.notify();
+        }
+    }
+
+    /// Redo the last undone stroke.
+    pub fn redo(&mut self, cx: &mut Context<Self>) {
+        if let Some(stroke) = self.redo_stack.pop() {
+            self.strokes.push(stroke);
+            cx.emit(DrawPadEvent::Change);
+            cx.notify();
+        }
+    }
+
+    /// Clear all the strokes.
+    pub fn clear(&mut self, cx: &mut Context<Self>) {
+        if self.strokes.is_empty() {
+            return;
+        }
+
+        self.strokes.clear();
+        self.redo_stack.clear();
+        self.current = None;
+        cx.emit(DrawPadEvent::Change);
+        cx.notify();
+    }
+
+    /// Export the strokes as SVG in the size of the pad.
+    pub fn to_svg(&self) -> String {
+        to_svg(&self.strokes, self.bounds.size)
+    }
+
+    /// Export the strokes as PNG in the size of the pad multiplied by the `scale`,
+    /// the background is transparent.
+    pub fn to_png(&self, scale: f32) -> anyhow::Result<Vec<u8>> {
+        let image = rasterize(&self.strokes, self.bounds.size, scale);
+        let mut bytes = Cursor::new(vec![]);
+        image.write_to(&mut bytes, ImageFormat::Png)?;
+        Ok(bytes.into_inner())
+    }
+
+    fn on_mouse_down(
+        &mut self,
+        event: &MouseDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.focus_handle.focus(window);
+        let position = event.position - self.bounds.origin;
+        self.current = Some(Stroke {
+            points: vec![StrokePoint::new(position, 1.)],
+            color: self.color.unwrap_or(cx.theme().foreground),
+            width: self.width,
+        });
+        cx.notify();
+    }
+
+    fn on_mouse_move(&mut self, event: &MouseMoveEvent, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(current) = self.current.as_mut() else {
+            return;
+        };
+
+        let position = event.position - self.bounds.origin;
+        // Skip the points too close to the last one.
+        if current.points.last().is_some_and(|last| {
+            let delta = position - last.position;
+            delta.x.abs() + delta.y.abs() < px(1.)
+        }) {
+            return;
+        }
+
+        current.points.push(StrokePoint::new(position, 1.));
+        cx.notify();
+    }
+
+    fn on_mouse_up(&mut self, _: &MouseUpEvent, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(stroke) = self.current.take() {
+            self.add_stroke(stroke, cx);
+        }
+    }
+
+    fn on_action_undo(&mut self, _: &Undo, _: &mut Window, cx: &mut Context<Self>) {
+        self.undo(cx);
+    }
+
+    fn on_action_redo(&mut self, _: &Redo, _: &mut Window, cx: &mut Context<Self>) {
+        self.redo(cx);
+    }
+}
+
+impl EventEmitter<DrawPadEvent> for DrawPadState {}
+
+impl Focusable for DrawPadState {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for DrawPadState {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let state = cx.entity();
+        let strokes = self
+            .strokes
+            .iter()
+            .chain(self.current.as_ref())
+            .cloned()
+            .collect::<Vec<_>>();
+
+        div()
+            .id("draw-pad")
+            .key_context(CONTEXT)
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::on_action_undo))
+            .on_action(cx.listener(Self::on_action_redo))
+            .size_full()
+            .cursor_crosshair()
+            .on_mouse_down(MouseButton::Left, cx.listener(Self::on_mouse_down))
+            .on_mouse_move(cx.listener(Self::on_mouse_move))
+            .on_mouse_up(MouseButton::Left, cx.listener(Self::on_mouse_up))
+            .on_mouse_up_out(MouseButton::Left, cx.listener(Self::on_mouse_up))
+            .child(
+                canvas(
+                    move |bounds, _, cx| state.update(cx, |r, _| r.bounds = bounds),
+                    move |bounds, _, window, _| {
+                        window.with_content_mask(Some(gpui::ContentMask { bounds }), |window| {
+                            for stroke in strokes.iter() {
+                                for (a, b, width) in stroke.segments() {
+                                    let (a, b) = (bounds.origin + a, bounds.origin + b);
+                                    if a != b {
+                                        let mut builder = PathBuilder::stroke(width);
+                                        builder.move_to(a);
+                                        builder.line_to(b);
+                                        if let Ok(path) = builder.build() {
+                                            window.paint_path(path, stroke.color);
+                                        }
+                                    }
+                                    // Round the joins and the caps.
+                                    window.paint_quad(
+                                        fill(
+                                            Bounds::centered_at(b, size(width, width)),
+                                            stroke.color,
+                                        )
+                                        .corner_radii(width / 2.),
+                                    );
+                                }
+                            }
+                        });
+                    },
+                )
+                .size_full(),
+            )
+    }
+}
+
+/// Returns the strokes as SVG in the size.
+fn to_svg(strokes: &[Stroke], size: Size<Pixels>) -> String {
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = f32::from(size.width),
+        h = f32::from(size.height),
+    );
+    for stroke in strokes {
+        let color = stroke.color.to_hex();
+        for (a, b, width) in stroke.segments() {
+            svg.push_str(&format!(
+                r#"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="{}" stroke-width="{:.2}" stroke-linecap="round"/>"#,
+                f32::from(a.x),
+                f32::from(a.y),
+                f32::from(b.x),
+                f32::from(b.y),
+                color,
+                f32::from(width),
+            ));
+        }
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Rasterize the strokes with anti-aliasing, in the size multiplied by the `scale`.
+fn rasterize(strokes: &[Stroke], size: Size<Pixels>, scale: f32) -> RgbaImage {
+    let width = (f32::from(size.width) * scale).ceil().max(1.) as u32;
+    let height = (f32::from(size.height) * scale).ceil().max(1.) as u32;
+    let mut image = RgbaImage::new(width, height);
+    let mut coverage = vec![0f32; (width * height) as usize];
+
+    for stroke in strokes {
+        coverage.fill(0.);
+        for (a, b, stroke_width) in stroke.segments() {
+            let a = point(f32::from(a.x) * scale, f32::from(a.y) * scale);
+            let b = point(f32::from(b.x) * scale, f32::from(b.y) * scale);
+            let radius = f32::from(stroke_width) * scale / 2.;
+
+            let min_x = (a.x.min(b.x) - radius - 1.).floor().max(0.) as u32;
+            let min_y = (a.y.min(b.y) - radius - 1.).floor().max(0.) as u32;
+            let max_x = ((a.x.max(b.x) + radius + 1.).ceil().max(0.) as u32).min(width);
+            let max_y = ((a.y.max(b.y) + radius + 1.).ceil().max(0.) as u32).min(height);
+            for y in min_y..max_y {
+                for x in min_x..max_x {
+                    let p = point(x as f32 + 0.5, y as f32 + 0.5);
+                    let (dx, dy) = (b.x - a.x, b.y - a.y);
+                    let len = dx * dx + dy * dy;
+                    let t = if len == 0. {
+                        0.
+                    } else {
+                        (((p.x - a.x) * dx + (p.y - a.y) * dy) / len).clamp(0., 1.)
+                    };
+                    let distance =
+                        ((p.x - a.x - t * dx).powi(2) + (p.y - a.y - t * dy).powi(2)).sqrt();
+                    let value = (radius + 0.5 - distance).clamp(0., 1.);
+                    let c = &mut coverage[(y * width + x) as usize];
+                    *c = c.max(value);
+                }
+            }
+        }
+
+        let color = stroke.color.to_rgb();
+        for (ix, c) in coverage.iter().enumerate() {
+            if *c <= 0. {
+                continue;
+            }
+
+            let pixel = image.get_pixel_mut(ix as u32 % width, ix as u32 / width);
+            let src_a = color.a * c;
+            let dst_a = pixel[3] as f32 / 255.;
+            let out_a = src_a + dst_a * (1. - src_a);
+            let blend = |src: f32, dst: u8| {
+                let dst = dst as f32 / 255.;
+                ((src * src_a + dst * dst_a * (1. - src_a)) / out_a * 255.).round() as u8
+            };
+            *pixel = Rgba([
+                blend(color.r, pixel[0]),
+                blend(color.g, pixel[1]),
+                blend(color.b, pixel[2]),
+                (out_a * 255.).round() as u8,
+            ]);
+        }
+    }
+
+    image
+}
+
+/// A freehand drawing pad, for signature capture and quick sketches.
+///
+/// Press `Cmd+Z` / `Ctrl+Z` to undo the last stroke.
+///
+/// ```ignore
+/// let state = cx.new(|cx| DrawPadState::new(window, cx).width(px(3.)));
+///
+/// DrawPad::new(&state).placeholder("Sign here").h(px(160.))
+///
+/// let png = state.read(cx).to_png(2.)?;
+/// ```
+#[derive(IntoElement)]
+pub struct DrawPad {
+    id: ElementId,
+    state: Entity<DrawPadState>,
+    placeholder: Option<SharedString>,
+    style: StyleRefinement,
+}
+
+impl DrawPad {
+    /// Create a new draw pad with the given state.
+    pub fn new(state: &Entity<DrawPadState>) -> Self {
+        Self {
+            id: ElementId::Name(format!("draw-pad-{}", state.entity_id()).into()),
+            state: state.clone(),
+            placeholder: None,
+            style: StyleRefinement::default(),
+        }
+    }
+
+    /// Set the placeholder shown on the baseline when nothing is drawn.
+    pub fn placeholder(mut self, placeholder: impl Into<SharedString>) -> Self {
+        self.placeholder = Some(placeholder.into());
+        self
+    }
+}
+
+impl Styled for DrawPad {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for DrawPad {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let is_empty = self.state.read(cx).is_empty();
+        let focused = self.state.read(cx).focus_handle.is_focused(window);
+
+        div()
+            .id(self.id)
+            .relative()
+            .size_full()
+            .bg(cx.theme().background)
+            .border_1()
+            .border_color(cx.theme().input)
+            .rounded(cx.theme().radius)
+            .overflow_hidden()
+            .when(focused, |this| this.focused_border(cx))
+            .when_some(
+                self.placeholder.filter(|_| is_empty),
+                |this, placeholder| {
+                    this.child(
+                        div()
+                            .absolute()
+                            .left_4()
+                            .right_4()
+                            .bottom_4()
+                            .pb_1()
+                            .border_b_1()
+                            .border_color(cx.theme().border)
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(placeholder),
+                    )
+                },
+            )
+            .child(self.state)
+            .refine_style(&self.style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{point, px, size, Hsla};
+
+    use super::{rasterize, to_svg, Stroke, StrokePoint};
+
+    fn stroke() -> Stroke {
+        Stroke {
+            points: vec![
+                StrokePoint::new(point(px(2.), px(5.)), 1.),
+                StrokePoint::new(point(px(8.), px(5.)), 1.),
+            ],
+            color: Hsla::black(),
+            width: px(2.),
+        }
+    }
+
+    #[test]
+    fn test_rasterize() {
+        let image = rasterize(&[stroke()], size(px(10.), px(10.)), 1.);
+        assert_eq!((image.width(), image.height()), (10, 10));
+        assert_eq!(image.get_pixel(5, 4).0, [0, 0, 0, 255]);
+        assert_eq!(image.get_pixel(5, 0).0[3], 0);
+        assert_eq!(image.get_pixel(0, 5).0[3], 0);
+
+        let image = rasterize(&[stroke()], size(px(10.), px(10.)), 2.);
+        assert_eq!((image.width(), image.height()), (20, 20));
+        assert_eq!(image.get_pixel(10, 9).0, [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_svg() {
+        assert_eq!(
+            to_svg(&[stroke()], size(px(10.), px(10.))),
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10" viewBox="0 0 10 10">"#,
+                r##"<line x1="2.0" y1="5.0" x2="8.0" y2="5.0" stroke="#000000" stroke-width="2.00" stroke-linecap="round"/>"##,
+                "</svg>"
+            )
+        );
+    }
+}
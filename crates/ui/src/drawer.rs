@@ -9,7 +9,9 @@ use gpui::{
 
 use crate::{
     actions::Cancel,
+    animation::MotionSettings,
     button::{Button, ButtonVariants as _},
+    elevation::Elevation,
     h_flex,
     modal::overlay_color,
     root::ContextModal as _,
@@ -125,6 +127,7 @@ impl Styled for Drawer {
 impl RenderOnce for Drawer {
     fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
         let placement = self.placement;
+        let motion = MotionSettings::global(cx);
         let titlebar_height = self.margin_top;
         let window_paddings = crate::window_border::window_paddings(window);
         let size = window.viewport_size()
@@ -178,7 +181,7 @@ impl RenderOnce for Drawer {
                             .occlude()
                             .bg(cx.theme().background)
                             .border_color(cx.theme().border)
-                            .shadow_xl()
+                            .elevation(Elevation::Modal, cx)
                             .map(|this| {
                                 // Set the size of the drawer.
                                 if placement.is_horizontal() {
@@ -236,15 +239,17 @@ impl RenderOnce for Drawer {
                             })
                             .with_animation(
                                 "slide",
-                                Animation::new(Duration::from_secs_f64(0.15)),
+                                motion.animation(Animation::new(Duration::from_secs_f64(0.15))),
                                 move |this, delta| {
-                                    let y = px(-100.) + delta * px(100.);
+                                    let y = px(-100.) + motion.motion_delta(delta) * px(100.);
                                     this.map(|this| match placement {
                                         Placement::Top => this.top(y),
                                         Placement::Right => this.right(y),
                                         Placement::Bottom => this.bottom(y),
                                         Placement::Left => this.left(y),
                                     })
+                                    // Fade in instead of sliding in the reduced motion.
+                                    .when(!motion.is_full(), |this| this.opacity(delta))
                                 },
                             ),
                     ),
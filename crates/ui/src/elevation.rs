@@ -0,0 +1,105 @@
+use gpui::{hsla, point, px, App, BoxShadow};
+
+use crate::ActiveTheme;
+
+/// The elevation of a surface, the higher one casts a larger shadow.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Elevation {
+    /// No shadow.
+    #[default]
+    Flat,
+    /// The controls raised from the background, e.g. the Input and Button.
+    ///
+    /// Only if the [`Theme::shadow`](crate::Theme::shadow) is enabled.
+    Raised,
+    /// The overlays close to the content, e.g. the Tooltip, Notification and the dropdown of Select.
+    Overlay,
+    /// The floating panels, e.g. the Popover and menus.
+    Popup,
+    /// The Modal and Drawer.
+    Modal,
+}
+
+impl Elevation {
+    /// Returns the shadows of the elevation, it is stronger in the dark theme to be visible.
+    pub fn shadow(self, cx: &App) -> Vec<BoxShadow> {
+        let alpha = if cx.theme().is_dark() { 0.3 } else { 0.1 };
+        let shadow = |y: f32, blur: f32, spread: f32, alpha: f32| BoxShadow {
+            color: hsla(0., 0., 0., alpha),
+            offset: point(px(0.), px(y)),
+            blur_radius: px(blur),
+            spread_radius: px(spread),
+        };
+
+        match self {
+            Self::Flat => vec![],
+            Self::Raised if !cx.theme().shadow => vec![],
+            Self::Raised => vec![shadow(1., 2., 0., alpha / 2.)],
+            Self::Overlay => vec![shadow(4., 6., -1., alpha), shadow(2., 4., -2., alpha)],
+            Self::Popup => vec![shadow(10., 15., -3., alpha), shadow(4., 6., -4., alpha)],
+            Self::Modal => vec![shadow(20., 25., -5., alpha), shadow(8., 10., -6., alpha)],
+        }
+    }
+}
+
+/// The layers of the overlays from bottom to top, to keep the stacking consistent.
+///
+/// The layers painted with `deferred` use the [`Layer::priority`] as the priority,
+/// the Modal, Drawer and Notification layers are rendered by the [`Root`](crate::Root),
+/// and the Tooltip is always painted above all by the window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Layer {
+    /// The fullscreen content, e.g. the VideoPlayer in fullscreen.
+    Fullscreen,
+    /// The dropdown of a form control, e.g. the Select, DatePicker and completion menus.
+    Dropdown,
+    /// The Popover.
+    Popover,
+    /// The context menu, popup menu and menu bar.
+    Menu,
+    /// The hover cards and Tooltip.
+    Tooltip,
+}
+
+impl Layer {
+    /// Returns the priority of the `deferred` element, the higher one is painted above.
+    pub fn priority(self) -> usize {
+        match self {
+            Self::Fullscreen => 1,
+            Self::Dropdown => 2,
+            Self::Popover => 3,
+            Self::Menu => 4,
+            Self::Tooltip => 5,
+        }
+    }
+
+    /// Returns the elevation of the surfaces in the layer.
+    pub fn elevation(self) -> Elevation {
+        match self {
+            Self::Fullscreen => Elevation::Flat,
+            Self::Dropdown | Self::Tooltip => Elevation::Overlay,
+            Self::Popover | Self::Menu => Elevation::Popup,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Elevation, Layer};
+
+    #[test]
+    fn test_layer_order() {
+        let layers = [
+            Layer::Fullscreen,
+            Layer::Dropdown,
+            Layer::Popover,
+            Layer::Menu,
+            Layer::Tooltip,
+        ];
+        for pair in layers.windows(2) {
+            assert!(pair[0].priority() < pair[1].priority());
+        }
+        assert!(Layer::Menu.elevation() > Layer::Dropdown.elevation());
+        assert_eq!(Layer::Fullscreen.elevation(), Elevation::Flat);
+    }
+}
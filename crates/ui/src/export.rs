@@ -0,0 +1,487 @@
+//! Export the charts and the tables as SVG or PNG, e.g. to include them in the reports.
+//!
+//! See [`PlotExport`](crate::plot::PlotExport) and `TableState::export_svg`.
+
+use std::{
+    fmt::Write as _,
+    sync::{Arc, LazyLock},
+};
+
+use anyhow::Context as _;
+use gpui::{Background, Bounds, FontWeight, Hsla, Pixels, Point, SharedString, Size, TextAlign};
+use regex::Regex;
+
+use crate::Colorize as _;
+
+#[cfg(feature = "chart")]
+pub(crate) use recording::{is_recording, paint_quad, record, recording, with_content_mask};
+
+/// A SVG document of the shapes and texts in the pixels, the origin is the top left.
+#[derive(Debug, Clone)]
+pub struct SvgDocument {
+    size: Size<Pixels>,
+    font_family: SharedString,
+    defs: String,
+    body: String,
+    next_id: usize,
+}
+
+impl SvgDocument {
+    /// Create a new empty document in the `size`.
+    pub fn new(size: Size<Pixels>) -> Self {
+        Self {
+            size,
+            font_family: "sans-serif".into(),
+            defs: String::new(),
+            body: String::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Set the font family of the texts, default: `sans-serif`.
+    pub fn font_family(mut self, font_family: impl Into<SharedString>) -> Self {
+        self.font_family = font_family.into();
+        self
+    }
+
+    /// Returns the size of the document.
+    pub fn size(&self) -> Size<Pixels> {
+        self.size
+    }
+
+    /// Fill the rectangle, with the border if the `border_width` is positive.
+    pub fn rect(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        corner_radius: Pixels,
+        fill: impl Into<Background>,
+        border_width: Pixels,
+        border_color: Hsla,
+    ) {
+        let fill = self.paint("fill", &fill.into());
+        let _ = write!(
+            self.body,
+            r#"<rect x="{}" y="{}" width="{}" height="{}""#,
+            num(bounds.origin.x),
+            num(bounds.origin.y),
+            num(bounds.size.width),
+            num(bounds.size.height),
+        );
+        if corner_radius > Pixels::ZERO {
+            let _ = write!(self.body, r#" rx="{}""#, num(corner_radius));
+        }
+        self.body.push_str(&fill);
+        if border_width > Pixels::ZERO && !border_color.is_transparent() {
+            let _ = write!(
+                self.body,
+                r#" stroke-width="{}"{}"#,
+                num(border_width),
+                color_attr("stroke", border_color)
+            );
+        }
+        self.body.push_str("/>");
+    }
+
+    /// Fill the path of the SVG path `data`, e.g. `M0 0L10 10Z`.
+    pub fn fill_path(&mut self, data: &str, fill: impl Into<Background>) {
+        let fill = self.paint("fill", &fill.into());
+        let _ = write!(self.body, r#"<path d="{}"{}/>"#, data, fill);
+    }
+
+    /// Stroke the path of the SVG path `data`, with the optional dash array.
+    pub fn stroke_path(
+        &mut self,
+        data: &str,
+        stroke: impl Into<Background>,
+        width: Pixels,
+        dash_array: Option<&[Pixels]>,
+    ) {
+        let stroke = self.paint("stroke", &stroke.into());
+        let _ = write!(
+            self.body,
+            r#"<path d="{}" fill="none" stroke-width="{}"{}"#,
+            data,
+            num(width),
+            stroke
+        );
+        if let Some(dash_array) = dash_array.filter(|dash_array| !dash_array.is_empty()) {
+            let dash_array = dash_array.iter().map(|d| num(*d)).collect::<Vec<_>>();
+            let _ = write!(self.body, r#" stroke-dasharray="{}""#, dash_array.join(" "));
+        }
+        self.body.push_str("/>");
+    }
+
+    /// Draw a single line `text`, the `origin` is the top of the line, and the `x` of the
+    /// origin is the left, center or right of the text by the `align`.
+    pub fn text(
+        &mut self,
+        text: &str,
+        origin: Point<Pixels>,
+        font_size: Pixels,
+        font_weight: FontWeight,
+        color: Hsla,
+        align: TextAlign,
+    ) {
+        let anchor = match align {
+            TextAlign::Left => "start",
+            TextAlign::Center => "middle",
+            TextAlign::Right => "end",
+        };
+        // The baseline is about 80% of the font size from the top.
+        let _ = write!(
+            self.body,
+            r#"<text x="{}" y="{}" font-size="{}" font-weight="{}" text-anchor="{}"{}>{}</text>"#,
+            num(origin.x),
+            num(origin.y + font_size * 0.8),
+            num(font_size),
+            font_weight.0,
+            anchor,
+            color_attr("fill", color),
+            escape(text),
+        );
+    }
+
+    /// Clip the following shapes and texts to the `bounds`, until the [`Self::pop_clip`].
+    pub fn push_clip(&mut self, bounds: Bounds<Pixels>) {
+        let id = self.next_id();
+        let _ = write!(
+            self.defs,
+            r#"<clipPath id="{}"><rect x="{}" y="{}" width="{}" height="{}"/></clipPath>"#,
+            id,
+            num(bounds.origin.x),
+            num(bounds.origin.y),
+            num(bounds.size.width),
+            num(bounds.size.height),
+        );
+        let _ = write!(self.body, r#"<g clip-path="url(#{})">"#, id);
+    }
+
+    /// End the clip of the [`Self::push_clip`].
+    pub fn pop_clip(&mut self) {
+        self.body.push_str("</g>");
+    }
+
+    /// Returns the SVG text of the document.
+    pub fn to_svg(&self) -> String {
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="{}">"#,
+            escape(&self.font_family),
+            w = num(self.size.width),
+            h = num(self.size.height),
+        );
+        if !self.defs.is_empty() {
+            let _ = write!(svg, "<defs>{}</defs>", self.defs);
+        }
+        svg.push_str(&self.body);
+        svg.push_str("</svg>");
+        svg
+    }
+
+    /// Rasterize the document as PNG in the size multiplied by the `scale`,
+    /// the texts are rendered with the system fonts.
+    pub fn to_png(&self, scale: f32) -> anyhow::Result<Vec<u8>> {
+        static FONT_DB: LazyLock<Arc<resvg::usvg::fontdb::Database>> = LazyLock::new(|| {
+            let mut db = resvg::usvg::fontdb::Database::new();
+            db.load_system_fonts();
+            Arc::new(db)
+        });
+
+        let options = resvg::usvg::Options {
+            fontdb: FONT_DB.clone(),
+            ..Default::default()
+        };
+        let tree = resvg::usvg::Tree::from_str(&self.to_svg(), &options)?;
+        let width = (f32::from(self.size.width) * scale).ceil().max(1.) as u32;
+        let height = (f32::from(self.size.height) * scale).ceil().max(1.) as u32;
+        let mut pixmap = resvg::tiny_skia::Pixmap::new(width, height)
+            .context("the size of the image is invalid")?;
+        resvg::render(
+            &tree,
+            resvg::tiny_skia::Transform::from_scale(scale, scale),
+            &mut pixmap.as_mut(),
+        );
+        Ok(pixmap.encode_png()?)
+    }
+
+    fn next_id(&mut self) -> String {
+        self.next_id += 1;
+        format!("e{}", self.next_id)
+    }
+
+    /// Returns the attributes of the `name` (`fill` or `stroke`) in the background,
+    /// the linear gradient is added to the defs.
+    fn paint(&mut self, name: &str, background: &Background) -> String {
+        match BackgroundPaint::parse(background) {
+            BackgroundPaint::Solid(hsla) => color_attr(name, hsla),
+            BackgroundPaint::LinearGradient(angle, stops) => {
+                let id = self.next_id();
+                // The angle is in CSS, 0 is to the top and 90 is to the right.
+                let (sin, cos) = angle.to_radians().sin_cos();
+                let _ = write!(
+                    self.defs,
+                    r#"<linearGradient id="{}" x1="{}" y1="{}" x2="{}" y2="{}">"#,
+                    id,
+                    0.5 - sin / 2.,
+                    0.5 + cos / 2.,
+                    0.5 + sin / 2.,
+                    0.5 - cos / 2.
+                );
+                for (hsla, offset) in stops {
+                    let _ = write!(
+                        self.defs,
+                        r#"<stop offset="{}" stop-color="{}" stop-opacity="{}"/>"#,
+                        num_f32(offset),
+                        hex(hsla),
+                        num_f32(hsla.a)
+                    );
+                }
+                self.defs.push_str("</linearGradient>");
+                format!(r#" {}="url(#{})""#, name, id)
+            }
+        }
+    }
+}
+
+/// The paint of the [`Background`], gpui doesn't expose the colors of the background,
+/// so they are parsed from the `Debug` output.
+#[derive(Debug, PartialEq)]
+enum BackgroundPaint {
+    Solid(Hsla),
+    LinearGradient(f32, Vec<(Hsla, f32)>),
+}
+
+impl BackgroundPaint {
+    fn parse(background: &Background) -> Self {
+        static HSLA: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r"Hsla \{ h: ([^,]+), s: ([^,]+), l: ([^,]+), a: ([^ ]+) \}").unwrap()
+        });
+        static PERCENTAGE: LazyLock<Regex> =
+            LazyLock::new(|| Regex::new(r"percentage: ([^ ]+) \}").unwrap());
+
+        let debug = format!("{:?}", background);
+        let colors = HSLA
+            .captures_iter(&debug)
+            .map(|caps| {
+                let value = |ix: usize| caps[ix].parse::<f32>().unwrap_or_default();
+                gpui::hsla(value(1), value(2), value(3), value(4))
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(args) = debug.strip_prefix("LinearGradient(") {
+            let angle = args
+                .split(',')
+                .next()
+                .and_then(|angle| angle.parse::<f32>().ok())
+                .unwrap_or_default();
+            let percentages = PERCENTAGE
+                .captures_iter(&debug)
+                .map(|caps| caps[1].parse::<f32>().unwrap_or_default());
+            return Self::LinearGradient(angle, colors.into_iter().zip(percentages).collect());
+        }
+
+        Self::Solid(colors.first().copied().unwrap_or_default())
+    }
+}
+
+/// Returns the ` name="#rrggbb"` with the ` name-opacity` if the color is translucent.
+fn color_attr(name: &str, hsla: Hsla) -> String {
+    if hsla.is_transparent() {
+        return format!(r#" {}="none""#, name);
+    }
+
+    if hsla.a < 1. {
+        format!(
+            r#" {}="{}" {}-opacity="{}""#,
+            name,
+            hex(hsla),
+            name,
+            num_f32(hsla.a)
+        )
+    } else {
+        format!(r#" {}="{}""#, name, hex(hsla))
+    }
+}
+
+/// Returns the `#rrggbb` of the color without the alpha.
+fn hex(hsla: Hsla) -> String {
+    Hsla { a: 1., ..hsla }.to_hex().to_lowercase()
+}
+
+fn num(value: Pixels) -> String {
+    num_f32(f32::from(value))
+}
+
+/// Format the number with at most 2 decimals, without the trailing zeros.
+fn num_f32(value: f32) -> String {
+    let s = format!("{:.2}", value);
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    if s == "-0" {
+        "0".into()
+    } else {
+        s.into()
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Call the `f` in the light theme for printing, the theme is restored after.
+#[cfg(any(feature = "chart", feature = "table"))]
+pub(crate) fn with_light_theme<R>(cx: &mut gpui::App, f: impl FnOnce(&mut gpui::App) -> R) -> R {
+    use crate::{Theme, ThemeMode};
+
+    if !Theme::global(cx).is_dark() {
+        return f(cx);
+    }
+
+    let theme = Theme::global(cx).clone();
+    Theme::change(ThemeMode::Light, None, cx);
+    let result = f(cx);
+    *Theme::global_mut(cx) = theme;
+    result
+}
+
+#[cfg(feature = "chart")]
+mod recording {
+    use std::cell::RefCell;
+
+    use gpui::{Bounds, ContentMask, PaintQuad, Pixels, Size, Window};
+
+    use super::SvgDocument;
+
+    thread_local! {
+        /// The document to record the painting into, see [`record`].
+        static RECORDING: RefCell<Option<SvgDocument>> = const { RefCell::new(None) };
+    }
+
+    /// Paint into the `document` instead of the window while calling the `f`, returns the document.
+    ///
+    /// The shapes of the [`plot`](crate::plot) check the [`recording`] to skip the window.
+    pub(crate) fn record(document: SvgDocument, f: impl FnOnce()) -> SvgDocument {
+        let prev = RECORDING.with(|recording| recording.borrow_mut().replace(document));
+        f();
+        RECORDING
+            .with(|recording| std::mem::replace(&mut *recording.borrow_mut(), prev))
+            .unwrap_or_else(|| SvgDocument::new(Size::default()))
+    }
+
+    /// Call the `f` with the document in the [`record`], returns false if not recording.
+    pub(crate) fn recording(f: impl FnOnce(&mut SvgDocument)) -> bool {
+        RECORDING.with(|recording| match recording.borrow_mut().as_mut() {
+            Some(document) => {
+                f(document);
+                true
+            }
+            None => false,
+        })
+    }
+
+    /// Returns true if in the [`record`].
+    pub(crate) fn is_recording() -> bool {
+        RECORDING.with(|recording| recording.borrow().is_some())
+    }
+
+    /// Paint the quad into the recording document, or the window.
+    pub(crate) fn paint_quad(window: &mut Window, quad: PaintQuad) {
+        let painted = recording(|document| {
+            document.rect(
+                quad.bounds,
+                quad.corner_radii.top_left,
+                quad.background,
+                quad.border_widths.top,
+                quad.border_color,
+            )
+        });
+        if !painted {
+            window.paint_quad(quad);
+        }
+    }
+
+    /// Clip the painting in the `f` to the `bounds`, in the recording document or the window.
+    pub(crate) fn with_content_mask(
+        window: &mut Window,
+        bounds: Bounds<Pixels>,
+        f: impl FnOnce(&mut Window),
+    ) {
+        if recording(|document| document.push_clip(bounds)) {
+            f(window);
+            recording(|document| document.pop_clip());
+            return;
+        }
+
+        window.with_content_mask(Some(ContentMask { bounds }), f);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{linear_gradient, point, px, size, Bounds, FontWeight, Hsla, TextAlign};
+
+    use super::{num_f32, BackgroundPaint, SvgDocument};
+
+    #[test]
+    fn test_background_paint() {
+        let red = gpui::hsla(0., 1., 0.5, 1.);
+        let blue = gpui::hsla(0.66, 1., 0.5, 0.5);
+        assert_eq!(
+            BackgroundPaint::parse(&red.into()),
+            BackgroundPaint::Solid(red)
+        );
+        assert_eq!(
+            BackgroundPaint::parse(&linear_gradient(
+                180.,
+                gpui::linear_color_stop(red, 0.),
+                gpui::linear_color_stop(blue, 1.)
+            )),
+            BackgroundPaint::LinearGradient(180., vec![(red, 0.), (blue, 1.)])
+        );
+    }
+
+    #[test]
+    fn test_svg_document() {
+        assert_eq!(num_f32(1.5), "1.5");
+        assert_eq!(num_f32(2.), "2");
+        assert_eq!(num_f32(-0.001), "0");
+
+        let mut document = SvgDocument::new(size(px(100.), px(50.)));
+        document.rect(
+            Bounds::new(point(px(0.), px(0.)), size(px(10.), px(20.))),
+            px(0.),
+            gpui::black(),
+            px(0.),
+            Hsla::transparent_black(),
+        );
+        document.stroke_path(
+            "M0 0L10 10",
+            gpui::hsla(0., 0., 0., 0.5),
+            px(1.),
+            Some(&[px(4.), px(2.)]),
+        );
+        document.text(
+            "A & B",
+            point(px(50.), px(10.)),
+            px(10.),
+            FontWeight::BOLD,
+            gpui::black(),
+            TextAlign::Center,
+        );
+        assert_eq!(
+            document.to_svg(),
+            [
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="50" viewBox="0 0 100 50" font-family="sans-serif">"#,
+                r##"<rect x="0" y="0" width="10" height="20" fill="#000000"/>"##,
+                r##"<path d="M0 0L10 10" fill="none" stroke-width="1" stroke="#000000" stroke-opacity="0.5" stroke-dasharray="4 2"/>"##,
+                r##"<text x="50" y="18" font-size="10" font-weight="700" text-anchor="middle" fill="#000000">A &amp; B</text>"##,
+                "</svg>",
+            ]
+            .concat()
+        );
+
+        let png = document.to_png(2.).unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+    }
+}
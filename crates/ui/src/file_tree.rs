@@ -0,0 +1,782 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use anyhow::{anyhow, Result};
+use gpui::{
+    actions, div, prelude::FluentBuilder as _, px, App, AppContext as _, Context, ElementId,
+    Entity, EventEmitter, FocusHandle, Focusable, InteractiveElement as _, IntoElement, KeyBinding,
+    MouseButton, ParentElement, Render, RenderOnce, SharedString, StyleRefinement, Styled,
+    Subscription, Window,
+};
+use rust_i18n::t;
+
+use crate::{
+    h_flex,
+    input::{Escape, Input, InputEvent, InputState},
+    list::ListItem,
+    menu::ContextMenuExt as _,
+    tree::{tree, TreeEntry, TreeEvent, TreeItem, TreeState},
+    ActiveTheme, Icon, IconName, Sizable as _, StyledExt as _,
+};
+
+actions!(file_tree, [NewFile, NewFolder, Rename, Delete]);
+
+const CONTEXT: &str = "FileTree";
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("f2", Rename, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-backspace", Delete, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("delete", Delete, Some(CONTEXT)),
+    ]);
+}
+
+/// Events emitted by the [`FileTreeState`].
+#[derive(Debug, Clone)]
+pub enum FileTreeEvent {
+    /// An item was selected by the user.
+    Select(PathBuf),
+    /// A file or directory was created.
+    Created(PathBuf),
+    /// A file or directory was renamed.
+    Renamed { from: PathBuf, to: PathBuf },
+    /// A file or directory was deleted.
+    Deleted(PathBuf),
+    /// A file system operation failed.
+    Error(SharedString),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FileEntry {
+    path: PathBuf,
+    name: SharedString,
+    is_dir: bool,
+}
+
+/// List the directory, the directories come first, then sorted by name case-insensitively.
+fn read_dir_entries(dir: &Path, show_hidden: bool) -> io::Result<Vec<FileEntry>> {
+    let mut entries = vec![];
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !show_hidden && name.starts_with('.') {
+            continue;
+        }
+
+        entries.push(FileEntry {
+            path: entry.path(),
+            name: name.into(),
+            is_dir: entry.file_type()?.is_dir(),
+        });
+    }
+
+    entries.sort_by(|a, b| {
+        b.is_dir
+            .cmp(&a.is_dir)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+    Ok(entries)
+}
+
+/// Returns a path in the `dir` that does not exist yet, e.g.: `untitled`, `untitled-1`.
+fn unique_path(dir: &Path, name: &str) -> PathBuf {
+    let mut path = dir.join(name);
+    let mut n = 0;
+    while path.exists() {
+        n += 1;
+        path = dir.join(format!("{}-{}", name, n));
+    }
+    path
+}
+
+/// The default icon of the [`FileTree`] by the file type.
+pub fn file_icon(path: &Path, is_dir: bool, expanded: bool) -> IconName {
+    if is_dir {
+        return if expanded {
+            IconName::FolderOpen
+        } else {
+            IconName::Folder
+        };
+    }
+
+    let ext = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "md" | "mdx" | "txt" | "rst" => IconName::BookOpen,
+        "json" | "toml" | "yaml" | "yml" | "ini" | "conf" | "lock" => IconName::Settings2,
+        "png" | "jpg" | "jpeg" | "gif" | "svg" | "webp" | "bmp" | "ico" => IconName::Frame,
+        "sh" | "bash" | "zsh" | "fish" | "ps1" | "bat" => IconName::SquareTerminal,
+        "html" | "htm" => IconName::Globe,
+        _ => IconName::File,
+    }
+}
+
+struct Renaming {
+    path: PathBuf,
+    input: Entity<InputState>,
+    _subscription: Subscription,
+}
+
+/// State of the [`FileTree`].
+///
+/// The directories are listed lazily when they are expanded.
+pub struct FileTreeState {
+    root: PathBuf,
+    tree: Entity<TreeState>,
+    /// The listed directories.
+    dirs: HashMap<PathBuf, Vec<FileEntry>>,
+    expanded: HashSet<PathBuf>,
+    show_hidden: bool,
+    renaming: Option<Renaming>,
+    file_icon: Rc<dyn Fn(&Path, bool, bool) -> Icon>,
+    #[cfg(not(target_family = "wasm"))]
+    watcher: Option<(notify::RecommendedWatcher, gpui::Task<()>)>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl FileTreeState {
+    /// Create a new file tree state of the `root` directory.
+    pub fn new(root: impl Into<PathBuf>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let root = root.into();
+        let tree = cx.new(|cx| TreeState::new(cx));
+        let _subscriptions =
+            vec![
+                cx.subscribe(&tree, |this, _, event: &TreeEvent, cx| match event {
+                    TreeEvent::Toggle(entry) => this.on_toggle(entry, cx),
+                    TreeEvent::Select(entry) => cx.emit(FileTreeEvent::Select(PathBuf::from(
+                        entry.item().id.as_str(),
+                    ))),
+                }),
+            ];
+
+        // Defer the listing to after the builder methods, e.g.: `show_hidden`.
+        cx.defer_in(window, |this, _, cx| this.load_dir(this.root.clone(), cx));
+
+        Self {
+            root,
+            tree,
+            dirs: HashMap::new(),
+            expanded: HashSet::new(),
+            show_hidden: false,
+            renaming: None,
+            file_icon: Rc::new(|path, is_dir, expanded| file_icon(path, is_dir, expanded).into()),
+            #[cfg(not(target_family = "wasm"))]
+            watcher: None,
+            _subscriptions,
+        }
+    }
+
+    /// Set true to show the hidden files (starts with `.`), default: false
+    pub fn show_hidden(mut self, show_hidden: bool) -> Self {
+        self.show_hidden = show_hidden;
+        self
+    }
+
+    /// Set the icon for the items, default: [`file_icon`]
+    ///
+    /// The arguments are the path, whether it is a directory, and whether it is expanded.
+    pub fn file_icon<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&Path, bool, bool) -> Icon + 'static,
+    {
+        self.file_icon = Rc::new(f);
+        self
+    }
+
+    /// Returns the root directory.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns the path of the selected item, if any.
+    pub fn selected_path(&self, cx: &App) -> Option<PathBuf> {
+        self.tree
+            .read(cx)
+            .selected_entry()
+            .map(|entry| PathBuf::from(entry.item().id.as_str()))
+    }
+
+    /// Reload all the listed directories.
+    pub fn reload(&mut self, cx: &mut Context<Self>) {
+        let dirs = self.dirs.keys().cloned().collect::<Vec<_>>();
+        for dir in dirs {
+            self.load_dir(dir, cx);
+        }
+    }
+
+    fn on_toggle(&mut self, entry: &TreeEntry, cx: &mut Context<Self>) {
+        let path = PathBuf::from(entry.item().id.as_str());
+        if entry.is_expanded() {
+            self.expanded.insert(path.clone());
+            if !self.dirs.contains_key(&path) {
+                self.load_dir(path, cx);
+            }
+        } else {
+            self.expanded.remove(&path);
+        }
+    }
+
+    /// List the directory in the background, and update the tree.
+    fn load_dir(&mut self, dir: PathBuf, cx: &mut Context<Self>) {
+        let show_hidden = self.show_hidden;
+        let task = cx.background_spawn({
+            let dir = dir.clone();
+            async move { read_dir_entries(&dir, show_hidden) }
+        });
+
+        cx.spawn(async move |this, cx| {
+            let result = task.await;
+            _ = this.update(cx, |this, cx| this.apply_dir_entries(dir, result, cx));
+        })
+        .detach();
+    }
+
+    /// List the directory immediately, used after the file system operations.
+    fn refresh_dir(&mut self, dir: PathBuf, cx: &mut Context<Self>) {
+        let result = read_dir_entries(&dir, self.show_hidden);
+        self.apply_dir_entries(dir, result, cx);
+    }
+
+    fn apply_dir_entries(
+        &mut self,
+        dir: PathBuf,
+        result: io::Result<Vec<FileEntry>>,
+        cx: &mut Context<Self>,
+    ) {
+        match result {
+            Ok(entries) => {
+                self.dirs.insert(dir, entries);
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                self.dirs.retain(|path, _| !path.starts_with(&dir));
+                self.expanded.retain(|path| !path.starts_with(&dir));
+            }
+            Err(err) => cx.emit(FileTreeEvent::Error(err.to_string().into())),
+        }
+        self.rebuild_items(cx);
+    }
+
+    fn build_items(&self, dir: &Path) -> Vec<TreeItem> {
+        let Some(entries) = self.dirs.get(dir) else {
+            return vec![];
+        };
+
+        entries
+            .iter()
+            .map(|entry| {
+                let item =
+                    TreeItem::new(entry.path.to_string_lossy().to_string(), entry.name.clone());
+                if entry.is_dir {
+                    item.folder(true)
+                        .expanded(self.expanded.contains(&entry.path))
+                        .children(self.build_items(&entry.path))
+                } else {
+                    item
+                }
+            })
+            .collect()
+    }
+
+    fn rebuild_items(&mut self, cx: &mut Context<Self>) {
+        let items = self.build_items(&self.root);
+        self.tree.update(cx, |tree, cx| {
+            let selected_id = tree.selected_entry().map(|entry| entry.item().id.clone());
+            tree.set_items(items, cx);
+            let ix =
+                selected_id.and_then(|id| tree.entries().iter().position(|e| e.item().id == id));
+            tree.set_selected_index(ix, cx);
+        });
+        cx.notify();
+    }
+
+    /// The directory to create the new items in, by the selected item.
+    fn target_dir(&self, cx: &App) -> PathBuf {
+        match self.tree.read(cx).selected_entry() {
+            Some(entry) if entry.is_folder() => PathBuf::from(entry.item().id.as_str()),
+            Some(entry) => PathBuf::from(entry.item().id.as_str())
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| self.root.clone()),
+            None => self.root.clone(),
+        }
+    }
+
+    /// Select the item of the `path` if it is visible.
+    pub fn select_path(&mut self, path: &Path, cx: &mut Context<Self>) {
+        self.tree.update(cx, |tree, cx| {
+            let id = path.to_string_lossy();
+            let ix = tree
+                .entries()
+                .iter()
+                .position(|e| e.item().id.as_str() == id);
+            tree.set_selected_index(ix, cx);
+            if let Some(ix) = ix {
+                tree.scroll_to_item(ix, gpui::ScrollStrategy::Center);
+            }
+        });
+    }
+
+    /// Expand all the ancestor directories of the `path`, then select it.
+    pub fn reveal(&mut self, path: &Path, cx: &mut Context<Self>) {
+        if !path.starts_with(&self.root) {
+            return;
+        }
+
+        let mut dirs = path
+            .ancestors()
+            .skip(1)
+            .take_while(|dir| dir.starts_with(&self.root))
+            .map(Path::to_path_buf)
+            .collect::<Vec<_>>();
+        dirs.reverse();
+        let mut changed = false;
+        for dir in dirs {
+            if dir != self.root {
+                changed |= self.expanded.insert(dir.clone());
+            }
+            if !self.dirs.contains_key(&dir) {
+                if let Ok(entries) = read_dir_entries(&dir, self.show_hidden) {
+                    self.dirs.insert(dir, entries);
+                    changed = true;
+                }
+            }
+        }
+        if changed {
+            self.rebuild_items(cx);
+        }
+        self.select_path(path, cx);
+    }
+
+    /// Returns the first item in the `dir`, list it if it was not listed yet.
+    pub(crate) fn first_child(&mut self, dir: &Path) -> Option<PathBuf> {
+        if !self.dirs.contains_key(dir) {
+            let entries = read_dir_entries(dir, self.show_hidden).ok()?;
+            self.dirs.insert(dir.to_path_buf(), entries);
+        }
+
+        self.dirs
+            .get(dir)
+            .and_then(|entries| entries.first())
+            .map(|entry| entry.path.clone())
+    }
+
+    /// Create a new file in the `dir`, then start to rename it.
+    pub fn create_file(
+        &mut self,
+        dir: &Path,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Result<PathBuf> {
+        let path = unique_path(dir, "untitled");
+        std::fs::File::create_new(&path)?;
+        self.after_create(path.clone(), window, cx);
+        Ok(path)
+    }
+
+    /// Create a new directory in the `dir`, then start to rename it.
+    pub fn create_dir(
+        &mut self,
+        dir: &Path,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Result<PathBuf> {
+        let path = unique_path(dir, "untitled");
+        std::fs::create_dir(&path)?;
+        self.after_create(path.clone(), window, cx);
+        Ok(path)
+    }
+
+    fn after_create(&mut self, path: PathBuf, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(dir) = path.parent() {
+            if dir != self.root {
+                self.expanded.insert(dir.to_path_buf());
+            }
+            self.refresh_dir(dir.to_path_buf(), cx);
+        }
+
+        self.select_path(&path, cx);
+        cx.emit(FileTreeEvent::Created(path.clone()));
+        self.start_rename(path, window, cx);
+    }
+
+    /// Rename the file or directory at `from` to `to`.
+    pub fn rename(&mut self, from: &Path, to: &Path, cx: &mut Context<Self>) -> Result<()> {
+        if to.exists() {
+            return Err(anyhow!("{} already exists", to.display()));
+        }
+
+        std::fs::rename(from, to)?;
+        if self.expanded.remove(from) {
+            self.expanded.insert(to.to_path_buf());
+        }
+        self.dirs.retain(|path, _| !path.starts_with(from));
+        for dir in [from.parent(), to.parent()].into_iter().flatten() {
+            self.refresh_dir(dir.to_path_buf(), cx);
+        }
+        self.select_path(to, cx);
+        cx.emit(FileTreeEvent::Renamed {
+            from: from.to_path_buf(),
+            to: to.to_path_buf(),
+        });
+        Ok(())
+    }
+
+    /// Delete the file or directory (recursively) at `path`.
+    pub fn delete(&mut self, path: &Path, cx: &mut Context<Self>) -> Result<()> {
+        if path.is_dir() {
+            std::fs::remove_dir_all(path)?;
+        } else {
+            std::fs::remove_file(path)?;
+        }
+
+        self.dirs.retain(|dir, _| !dir.starts_with(path));
+        self.expanded.retain(|dir| !dir.starts_with(path));
+        if let Some(dir) = path.parent() {
+            self.refresh_dir(dir.to_path_buf(), cx);
+        }
+        cx.emit(FileTreeEvent::Deleted(path.to_path_buf()));
+        Ok(())
+    }
+
+    /// Show an input in place of the item's label to rename it.
+    pub fn start_rename(&mut self, path: PathBuf, window: &mut Window, cx: &mut Context<Self>) {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        // Select the name without the extension.
+        let stem_len = match name.rfind('.') {
+            Some(ix) if ix > 0 => ix,
+            _ => name.len(),
+        };
+
+        let input = cx.new(|cx| InputState::new(window, cx).default_value(name));
+        input.update(cx, |input, cx| {
+            input.focus(window, cx);
+            input.select_to(stem_len, cx);
+        });
+        let _subscription =
+            cx.subscribe_in(&input, window, |this, _, event, window, cx| match event {
+                InputEvent::PressEnter { .. } | InputEvent::Blur => this.confirm_rename(window, cx),
+                _ => {}
+            });
+
+        self.renaming = Some(Renaming {
+            path,
+            input,
+            _subscription,
+        });
+        cx.notify();
+    }
+
+    fn confirm_rename(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(renaming) = self.renaming.take() else {
+            return;
+        };
+
+        let name = renaming.input.read(cx).value().trim().to_string();
+        if !name.is_empty()
+            && Some(name.as_str()) != renaming.path.file_name().and_then(|n| n.to_str())
+        {
+            let to = renaming.path.with_file_name(&name);
+            if let Err(err) = self.rename(&renaming.path, &to, cx) {
+                cx.emit(FileTreeEvent::Error(err.to_string().into()));
+            }
+        }
+
+        self.tree.read(cx).focus_handle(cx).focus(window);
+        cx.notify();
+    }
+
+    fn cancel_rename(&mut self, _: &Escape, window: &mut Window, cx: &mut Context<Self>) {
+        if self.renaming.take().is_none() {
+            cx.propagate();
+            return;
+        }
+
+        self.tree.read(cx).focus_handle(cx).focus(window);
+        cx.notify();
+    }
+
+    fn on_action_new_file(&mut self, _: &NewFile, window: &mut Window, cx: &mut Context<Self>) {
+        let dir = self.target_dir(cx);
+        if let Err(err) = self.create_file(&dir, window, cx) {
+            cx.emit(FileTreeEvent::Error(err.to_string().into()));
+        }
+    }
+
+    fn on_action_new_folder(&mut self, _: &NewFolder, window: &mut Window, cx: &mut Context<Self>) {
+        let dir = self.target_dir(cx);
+        if let Err(err) = self.create_dir(&dir, window, cx) {
+            cx.emit(FileTreeEvent::Error(err.to_string().into()));
+        }
+    }
+
+    fn on_action_rename(&mut self, _: &Rename, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(path) = self.selected_path(cx) {
+            self.start_rename(path, window, cx);
+        }
+    }
+
+    fn on_action_delete(&mut self, _: &Delete, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(path) = self.selected_path(cx) {
+            if let Err(err) = self.delete(&path, cx) {
+                cx.emit(FileTreeEvent::Error(err.to_string().into()));
+            }
+        }
+    }
+
+    /// Watch the file system changes of the root directory, and update the tree automatically.
+    ///
+    /// This is not supported on the web, returns an error.
+    #[cfg(target_family = "wasm")]
+    pub fn set_watch(&mut self, watch: bool, _: &mut Context<Self>) -> Result<()> {
+        if !watch {
+            return Ok(());
+        }
+
+        Err(anyhow!(
+            "Watching the file system is not supported on the web."
+        ))
+    }
+
+    /// Watch the file system changes of the root directory, and update the tree automatically.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn set_watch(&mut self, watch: bool, cx: &mut Context<Self>) -> Result<()> {
+        use notify::Watcher as _;
+
+        if !watch {
+            self.watcher = None;
+            return Ok(());
+        }
+
+        let (tx, rx) = smol::channel::unbounded::<PathBuf>();
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                if let Ok(event) = event {
+                    match event.kind {
+                        notify::EventKind::Create(_)
+                        | notify::EventKind::Remove(_)
+                        | notify::EventKind::Modify(notify::event::ModifyKind::Name(_)) => {
+                            for path in event.paths {
+                                _ = tx.send_blocking(path);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            })?;
+        watcher.watch(&self.root, notify::RecursiveMode::Recursive)?;
+
+        let task = cx.spawn(async move |this, cx| {
+            while let Ok(path) = rx.recv().await {
+                let mut dirs = HashSet::new();
+                dirs.extend(path.parent().map(Path::to_path_buf));
+                while let Ok(path) = rx.try_recv() {
+                    dirs.extend(path.parent().map(Path::to_path_buf));
+                }
+
+                let result = this.update(cx, |this, cx| {
+                    for dir in dirs {
+                        if this.dirs.contains_key(&dir) {
+                            this.load_dir(dir, cx);
+                        }
+                    }
+                });
+                if result.is_err() {
+                    return;
+                }
+            }
+        });
+
+        self.watcher = Some((watcher, task));
+        Ok(())
+    }
+}
+
+impl EventEmitter<FileTreeEvent> for FileTreeState {}
+
+impl Focusable for FileTreeState {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.tree.read(cx).focus_handle(cx)
+    }
+}
+
+/// A file tree of a directory, with file-type icons, inline rename and a context menu to create, rename and delete files.
+///
+/// ```ignore
+/// let state = cx.new(|cx| FileTreeState::new("./", window, cx));
+///
+/// FileTree::new(&state)
+/// ```
+#[derive(IntoElement)]
+pub struct FileTree {
+    id: ElementId,
+    state: Entity<FileTreeState>,
+    style: StyleRefinement,
+}
+
+impl FileTree {
+    /// Create a new file tree with the given state.
+    pub fn new(state: &Entity<FileTreeState>) -> Self {
+        Self {
+            id: ElementId::Name(format!("file-tree-{}", state.entity_id()).into()),
+            state: state.clone(),
+            style: StyleRefinement::default(),
+        }
+    }
+}
+
+impl Styled for FileTree {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl Render for FileTreeState {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let state = cx.entity();
+        let tree_state = self.tree.clone();
+        let focus_handle = self.focus_handle(cx);
+
+        div()
+            .id("file-tree-state")
+            .size_full()
+            .child(tree(&tree_state, {
+                let tree_state = tree_state.clone();
+                move |ix, entry, _, _, cx| {
+                    let state = state.read(cx);
+                    let path = PathBuf::from(entry.item().id.as_str());
+                    let icon = (state.file_icon)(&path, entry.is_folder(), entry.is_expanded());
+                    let rename_input = state
+                        .renaming
+                        .as_ref()
+                        .filter(|renaming| renaming.path == path)
+                        .map(|renaming| renaming.input.clone());
+
+                    ListItem::new(ix)
+                        .w_full()
+                        .rounded(cx.theme().radius)
+                        .px_3()
+                        .pl(px(16.) * entry.depth() + px(12.))
+                        .child(
+                            h_flex()
+                                .id("content")
+                                .flex_1()
+                                .gap_2()
+                                .overflow_hidden()
+                                .child(icon)
+                                .map(|this| match rename_input {
+                                    Some(input) => this.child(Input::new(&input).xsmall()),
+                                    None => this.child(entry.item().label.clone()),
+                                })
+                                .on_mouse_down(MouseButton::Right, {
+                                    let tree_state = tree_state.clone();
+                                    move |_, _, cx| {
+                                        tree_state.update(cx, |tree, cx| {
+                                            tree.set_selected_index(Some(ix), cx)
+                                        });
+                                    }
+                                }),
+                        )
+                }
+            }))
+            .context_menu(move |menu, _, _| {
+                menu.action_context(focus_handle.clone())
+                    .menu(t!("FileTree.New File"), Box::new(NewFile))
+                    .menu(t!("FileTree.New Folder"), Box::new(NewFolder))
+                    .separator()
+                    .menu(t!("FileTree.Rename"), Box::new(Rename))
+                    .menu(t!("FileTree.Delete"), Box::new(Delete))
+            })
+    }
+}
+
+impl RenderOnce for FileTree {
+    fn render(self, window: &mut Window, _: &mut App) -> impl IntoElement {
+        div()
+            .id(self.id)
+            .key_context(CONTEXT)
+            .on_action(window.listener_for(&self.state, FileTreeState::on_action_new_file))
+            .on_action(window.listener_for(&self.state, FileTreeState::on_action_new_folder))
+            .on_action(window.listener_for(&self.state, FileTreeState::on_action_rename))
+            .on_action(window.listener_for(&self.state, FileTreeState::on_action_delete))
+            .on_action(window.listener_for(&self.state, FileTreeState::cancel_rename))
+            .size_full()
+            .child(self.state)
+            .refine_style(&self.style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::{file_icon, read_dir_entries, unique_path};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("gpui-file-tree-{}", name));
+        _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_read_dir_entries() {
+        let dir = temp_dir("read-dir");
+        std::fs::create_dir(dir.join("src")).unwrap();
+        std::fs::write(dir.join("README.md"), "").unwrap();
+        std::fs::write(dir.join("build.rs"), "").unwrap();
+        std::fs::write(dir.join(".gitignore"), "").unwrap();
+
+        let names = |show_hidden| {
+            read_dir_entries(&dir, show_hidden)
+                .unwrap()
+                .into_iter()
+                .map(|e| e.name.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(false), vec!["src", "build.rs", "README.md"]);
+        assert_eq!(
+            names(true),
+            vec!["src", ".gitignore", "build.rs", "README.md"]
+        );
+
+        assert_eq!(unique_path(&dir, "src"), dir.join("src-1"));
+        assert_eq!(unique_path(&dir, "lib.rs"), dir.join("lib.rs"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_file_icon() {
+        use crate::IconName;
+
+        assert!(matches!(
+            file_icon(Path::new("src"), true, false),
+            IconName::Folder
+        ));
+        assert!(matches!(
+            file_icon(Path::new("src"), true, true),
+            IconName::FolderOpen
+        ));
+        assert!(matches!(
+            file_icon(Path::new("docs/README.MD"), false, false),
+            IconName::BookOpen
+        ));
+        assert!(matches!(
+            file_icon(Path::new("Cargo.toml"), false, false),
+            IconName::Settings2
+        ));
+        assert!(matches!(
+            file_icon(Path::new("src/lib.rs"), false, false),
+            IconName::File
+        ));
+    }
+}
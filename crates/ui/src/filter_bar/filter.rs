@@ -0,0 +1,291 @@
+use std::fmt::Display;
+
+use gpui::SharedString;
+use rust_i18n::t;
+
+/// The operator of a [`Filter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FilterOperator {
+    Is,
+    IsNot,
+    Contains,
+    NotContains,
+    GreaterThan,
+    LessThan,
+}
+
+impl FilterOperator {
+    /// Returns all the operators.
+    pub fn all() -> [FilterOperator; 6] {
+        [
+            FilterOperator::Is,
+            FilterOperator::IsNot,
+            FilterOperator::Contains,
+            FilterOperator::NotContains,
+            FilterOperator::GreaterThan,
+            FilterOperator::LessThan,
+        ]
+    }
+
+    /// The symbol of the operator in the text expression, e.g.: `status:open`.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            FilterOperator::Is => ":",
+            FilterOperator::IsNot => "!:",
+            FilterOperator::Contains => "~",
+            FilterOperator::NotContains => "!~",
+            FilterOperator::GreaterThan => ">",
+            FilterOperator::LessThan => "<",
+        }
+    }
+
+    /// The label to display in the filter bar.
+    pub fn label(&self) -> SharedString {
+        match self {
+            FilterOperator::Is => t!("FilterBar.is"),
+            FilterOperator::IsNot => t!("FilterBar.is not"),
+            FilterOperator::Contains => t!("FilterBar.contains"),
+            FilterOperator::NotContains => t!("FilterBar.not contains"),
+            FilterOperator::GreaterThan => t!("FilterBar.greater than"),
+            FilterOperator::LessThan => t!("FilterBar.less than"),
+        }
+        .into()
+    }
+}
+
+/// A field that can be filtered in the [`FilterBar`](super::FilterBar).
+#[derive(Debug, Clone)]
+pub struct FilterField {
+    pub name: SharedString,
+    pub label: SharedString,
+    pub operators: Vec<FilterOperator>,
+    /// The suggested values.
+    pub values: Vec<SharedString>,
+}
+
+impl FilterField {
+    /// Create a new field with the `name` used in the expression,
+    /// the default operators are [`FilterOperator::Is`] and [`FilterOperator::IsNot`].
+    pub fn new(name: impl Into<SharedString>) -> Self {
+        let name = name.into();
+        Self {
+            label: name.clone(),
+            name,
+            operators: vec![FilterOperator::Is, FilterOperator::IsNot],
+            values: vec![],
+        }
+    }
+
+    /// Set the label to display, default is the name.
+    pub fn label(mut self, label: impl Into<SharedString>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Set the operators can be used with this field.
+    pub fn operators(mut self, operators: impl Into<Vec<FilterOperator>>) -> Self {
+        let operators = operators.into();
+        if !operators.is_empty() {
+            self.operators = operators;
+        }
+        self
+    }
+
+    /// Set the suggested values, the other values can also be typed.
+    pub fn values(mut self, values: impl IntoIterator<Item = impl Into<SharedString>>) -> Self {
+        self.values = values.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+/// A filter of `field`, `operator` and `value`, e.g.: `status:open`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Filter {
+    pub field: SharedString,
+    pub operator: FilterOperator,
+    pub value: SharedString,
+}
+
+impl Filter {
+    pub fn new(
+        field: impl Into<SharedString>,
+        operator: FilterOperator,
+        value: impl Into<SharedString>,
+    ) -> Self {
+        Self {
+            field: field.into(),
+            operator,
+            value: value.into(),
+        }
+    }
+}
+
+fn quote(value: &str) -> String {
+    if value.is_empty() || value.contains(char::is_whitespace) || value.contains('"') {
+        format!("\"{}\"", value.replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+impl Display for Filter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{}{}",
+            self.field,
+            self.operator.symbol(),
+            quote(&self.value)
+        )
+    }
+}
+
+/// The filters and the free text composed in the [`FilterBar`](super::FilterBar).
+///
+/// It can be converted to and parsed from a text, e.g.: `status:open priority>2 "crash on start"`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FilterExpression {
+    pub filters: Vec<Filter>,
+    /// The text not belongs to any filter.
+    pub text: SharedString,
+}
+
+/// Split the text by whitespace, the quoted parts are kept together.
+fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = vec![];
+    let mut current = String::new();
+    let mut in_quote = false;
+    let mut escaped = false;
+
+    for c in text.chars() {
+        if escaped {
+            current.push(c);
+            escaped = false;
+        } else if c == '\\' && in_quote {
+            escaped = true;
+        } else if c == '"' {
+            in_quote = !in_quote;
+        } else if c.is_whitespace() && !in_quote {
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+impl FilterExpression {
+    /// Returns true if there is no filter and text.
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty() && self.text.is_empty()
+    }
+
+    /// Parse the expression text, only the `fields` and their operators are recognized as filters,
+    /// the other words are the free text.
+    pub fn parse(text: &str, fields: &[FilterField]) -> Self {
+        // The longer symbols first, e.g.: `!:` before `:`.
+        let mut operators = FilterOperator::all();
+        operators.sort_by_key(|op| std::cmp::Reverse(op.symbol().len()));
+
+        let mut filters = vec![];
+        let mut words = vec![];
+        'tokens: for token in tokenize(text) {
+            for field in fields {
+                let Some(rest) = token.strip_prefix(field.name.as_str()) else {
+                    continue;
+                };
+                for operator in operators.iter() {
+                    if let Some(value) = rest.strip_prefix(operator.symbol()) {
+                        if field.operators.contains(operator) {
+                            filters.push(Filter::new(
+                                field.name.clone(),
+                                *operator,
+                                value.to_string(),
+                            ));
+                            continue 'tokens;
+                        }
+                    }
+                }
+            }
+            words.push(quote(&token));
+        }
+
+        Self {
+            filters,
+            text: words.join(" ").into(),
+        }
+    }
+}
+
+impl Display for FilterExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = self
+            .filters
+            .iter()
+            .map(|filter| filter.to_string())
+            .collect::<Vec<_>>();
+        if !self.text.is_empty() {
+            parts.push(self.text.to_string());
+        }
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Filter, FilterExpression, FilterField, FilterOperator};
+
+    fn fields() -> Vec<FilterField> {
+        vec![
+            FilterField::new("status").values(["open", "closed"]),
+            FilterField::new("priority").operators([
+                FilterOperator::Is,
+                FilterOperator::GreaterThan,
+                FilterOperator::LessThan,
+            ]),
+            FilterField::new("title").operators([FilterOperator::Contains]),
+        ]
+    }
+
+    #[test]
+    fn test_filter_to_string() {
+        let filter = Filter::new("status", FilterOperator::IsNot, "open");
+        assert_eq!(filter.to_string(), "status!:open");
+        let filter = Filter::new("title", FilterOperator::Contains, "crash on start");
+        assert_eq!(filter.to_string(), "title~\"crash on start\"");
+    }
+
+    #[test]
+    fn test_parse_expression() {
+        let fields = fields();
+        let expr = FilterExpression::parse(
+            r#"status!:open priority>2 title~"crash on start" label:bug panic"#,
+            &fields,
+        );
+        assert_eq!(
+            expr.filters,
+            vec![
+                Filter::new("status", FilterOperator::IsNot, "open"),
+                Filter::new("priority", FilterOperator::GreaterThan, "2"),
+                Filter::new("title", FilterOperator::Contains, "crash on start"),
+            ]
+        );
+        assert_eq!(expr.text.as_ref(), "label:bug panic");
+        assert_eq!(
+            expr.to_string(),
+            r#"status!:open priority>2 title~"crash on start" label:bug panic"#
+        );
+
+        // The operator is not allowed for the field.
+        let expr = FilterExpression::parse("title:foo", &fields);
+        assert!(expr.filters.is_empty());
+        assert_eq!(expr.text.as_ref(), "title:foo");
+
+        assert!(FilterExpression::parse("  ", &fields).is_empty());
+    }
+}
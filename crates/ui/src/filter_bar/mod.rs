@@ -0,0 +1,530 @@
+mod filter;
+
+use gpui::{
+    div, prelude::FluentBuilder as _, App, AppContext as _, Context, ElementId, Entity,
+    EventEmitter, FocusHandle, Focusable, InteractiveElement as _, IntoElement, MouseButton,
+    ParentElement, Render, RenderOnce, SharedString, StatefulInteractiveElement as _,
+    StyleRefinement, Styled, Subscription, Window,
+};
+use rust_i18n::t;
+
+use crate::{
+    elevation::Layer,
+    h_flex,
+    input::{Backspace, Enter, Escape, Input, InputEvent, InputState, MoveDown, MoveUp},
+    portal::Portal,
+    tag::Tag,
+    v_flex, ActiveTheme, Icon, IconName, Sizable as _, StyledExt as _,
+};
+
+pub use filter::*;
+
+/// Events emitted by the [`FilterBarState`].
+#[derive(Debug, Clone)]
+pub enum FilterBarEvent {
+    /// The filters or the free text was changed.
+    Change(FilterExpression),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Suggestion {
+    Field(usize),
+    Operator(FilterOperator),
+    Value(SharedString),
+}
+
+/// State of the [`FilterBar`].
+pub struct FilterBarState {
+    fields: Vec<FilterField>,
+    filters: Vec<Filter>,
+    input: Entity<InputState>,
+    /// The field index and operator of the filter being composed.
+    draft_field: Option<usize>,
+    draft_operator: Option<FilterOperator>,
+    text: SharedString,
+    open: bool,
+    highlighted_ix: usize,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl FilterBarState {
+    /// Create a new filter bar state.
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let input =
+            cx.new(|cx| InputState::new(window, cx).placeholder(t!("FilterBar.placeholder")));
+        let _subscriptions = vec![cx.subscribe_in(&input, window, Self::on_input_event)];
+
+        Self {
+            fields: vec![],
+            filters: vec![],
+            input,
+            draft_field: None,
+            draft_operator: None,
+            text: SharedString::default(),
+            open: false,
+            highlighted_ix: 0,
+            _subscriptions,
+        }
+    }
+
+    /// Set the fields can be filtered.
+    pub fn fields(mut self, fields: impl Into<Vec<FilterField>>) -> Self {
+        self.fields = fields.into();
+        self
+    }
+
+    /// Set the fields can be filtered.
+    pub fn set_fields(&mut self, fields: impl Into<Vec<FilterField>>, cx: &mut Context<Self>) {
+        self.fields = fields.into();
+        self.draft_field = None;
+        self.draft_operator = None;
+        cx.notify();
+    }
+
+    /// Returns the committed filters.
+    pub fn filters(&self) -> &[Filter] {
+        &self.filters
+    }
+
+    /// Returns the current filter expression.
+    pub fn expression(&self) -> FilterExpression {
+        FilterExpression {
+            filters: self.filters.clone(),
+            text: self.text.clone(),
+        }
+    }
+
+    /// Set the filters and the free text by the expression.
+    pub fn set_expression(
+        &mut self,
+        expression: FilterExpression,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.filters = expression.filters;
+        self.text = expression.text.clone();
+        self.draft_field = None;
+        self.draft_operator = None;
+        self.input
+            .update(cx, |input, cx| input.set_value(expression.text, window, cx));
+        self.open = false;
+        cx.notify();
+    }
+
+    /// Remove the filter at the index.
+    pub fn remove_filter(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if ix < self.filters.len() {
+            self.filters.remove(ix);
+            self.emit_change(cx);
+        }
+    }
+
+    /// Remove all the filters and the free text.
+    pub fn clear(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_expression(FilterExpression::default(), window, cx);
+        self.emit_change(cx);
+    }
+
+    fn emit_change(&mut self, cx: &mut Context<Self>) {
+        cx.emit(FilterBarEvent::Change(self.expression()));
+        cx.notify();
+    }
+
+    fn suggestions(&self, cx: &App) -> Vec<Suggestion> {
+        let query = self.input.read(cx).value().to_lowercase();
+        let matches = |text: &str| text.to_lowercase().contains(&query);
+
+        match (self.draft_field, self.draft_operator) {
+            (None, _) => self
+                .fields
+                .iter()
+                .enumerate()
+                .filter(|(_, field)| matches(&field.name) || matches(&field.label))
+                .map(|(ix, _)| Suggestion::Field(ix))
+                .collect(),
+            (Some(field_ix), None) => self.fields[field_ix]
+                .operators
+                .iter()
+                .map(|op| Suggestion::Operator(*op))
+                .collect(),
+            (Some(field_ix), Some(_)) => self.fields[field_ix]
+                .values
+                .iter()
+                .filter(|value| matches(value))
+                .map(|value| Suggestion::Value(value.clone()))
+                .collect(),
+        }
+    }
+
+    fn clear_input(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.input
+            .update(cx, |input, cx| input.set_value("", window, cx));
+    }
+
+    fn select_suggestion(
+        &mut self,
+        suggestion: Suggestion,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match suggestion {
+            Suggestion::Field(ix) => {
+                self.draft_field = Some(ix);
+                if let [operator] = self.fields[ix].operators.as_slice() {
+                    self.draft_operator = Some(*operator);
+                }
+                self.clear_input(window, cx);
+            }
+            Suggestion::Operator(operator) => {
+                self.draft_operator = Some(operator);
+                self.clear_input(window, cx);
+            }
+            Suggestion::Value(value) => self.commit_value(value, window, cx),
+        }
+
+        self.open = true;
+        self.highlighted_ix = 0;
+        self.input.read(cx).focus_handle(cx).focus(window);
+        cx.notify();
+    }
+
+    fn commit_value(&mut self, value: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(field_ix), Some(operator)) = (self.draft_field, self.draft_operator) else {
+            return;
+        };
+
+        self.filters.push(Filter::new(
+            self.fields[field_ix].name.clone(),
+            operator,
+            value,
+        ));
+        self.draft_field = None;
+        self.draft_operator = None;
+        self.text = SharedString::default();
+        self.clear_input(window, cx);
+        self.emit_change(cx);
+    }
+
+    fn on_input_event(
+        &mut self,
+        _: &Entity<InputState>,
+        event: &InputEvent,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            InputEvent::Change | InputEvent::Focus => {
+                self.open = true;
+                self.highlighted_ix = 0;
+                cx.notify();
+            }
+            InputEvent::Blur => {
+                self.open = false;
+                cx.notify();
+            }
+            _ => {}
+        }
+    }
+
+    fn on_action_enter(&mut self, _: &Enter, window: &mut Window, cx: &mut Context<Self>) {
+        let suggestions = self.suggestions(cx);
+        if self.open {
+            if let Some(suggestion) = suggestions.get(self.highlighted_ix).cloned() {
+                self.select_suggestion(suggestion, window, cx);
+                cx.stop_propagation();
+                return;
+            }
+        }
+
+        let value = self.input.read(cx).value();
+        if self.draft_operator.is_some() {
+            if !value.is_empty() {
+                self.commit_value(value, window, cx);
+            }
+        } else if self.draft_field.is_none() {
+            // Search by the free text.
+            self.text = value;
+            self.open = false;
+            self.emit_change(cx);
+        }
+        cx.stop_propagation();
+    }
+
+    fn on_action_backspace(&mut self, _: &Backspace, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.input.read(cx).value().is_empty() {
+            return;
+        }
+
+        if self.draft_operator.is_some() {
+            self.draft_operator = None;
+            // Skip the operator segment if there is only one operator.
+            if let Some(field_ix) = self.draft_field {
+                if self.fields[field_ix].operators.len() == 1 {
+                    self.draft_field = None;
+                }
+            }
+        } else if self.draft_field.is_some() {
+            self.draft_field = None;
+        } else if self.filters.pop().is_some() {
+            self.emit_change(cx);
+        } else {
+            return;
+        }
+
+        self.highlighted_ix = 0;
+        cx.stop_propagation();
+        cx.notify();
+    }
+
+    fn on_action_escape(&mut self, _: &Escape, _: &mut Window, cx: &mut Context<Self>) {
+        if self.open {
+            self.open = false;
+        } else if self.draft_field.is_some() {
+            self.draft_field = None;
+            self.draft_operator = None;
+        } else {
+            return;
+        }
+
+        cx.stop_propagation();
+        cx.notify();
+    }
+
+    fn on_action_up(&mut self, _: &MoveUp, _: &mut Window, cx: &mut Context<Self>) {
+        let count = self.suggestions(cx).len();
+        if !self.open || count == 0 {
+            return;
+        }
+
+        self.highlighted_ix = if self.highlighted_ix == 0 {
+            count - 1
+        } else {
+            self.highlighted_ix - 1
+        };
+        cx.stop_propagation();
+        cx.notify();
+    }
+
+    fn on_action_down(&mut self, _: &MoveDown, _: &mut Window, cx: &mut Context<Self>) {
+        let count = self.suggestions(cx).len();
+        if !self.open || count == 0 {
+            return;
+        }
+
+        self.highlighted_ix = (self.highlighted_ix + 1) % count;
+        cx.stop_propagation();
+        cx.notify();
+    }
+
+    fn render_pill(&self, ix: usize, filter: &Filter, cx: &mut Context<Self>) -> impl IntoElement {
+        let label = self
+            .fields
+            .iter()
+            .find(|field| field.name == filter.field)
+            .map(|field| field.label.clone())
+            .unwrap_or(filter.field.clone());
+
+        Tag::secondary().small().child(
+            h_flex()
+                .gap_1()
+                .child(div().font_semibold().child(label))
+                .child(
+                    div()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(filter.operator.label()),
+                )
+                .child(filter.value.clone())
+                .child(
+                    div()
+                        .id(("remove-filter", ix))
+                        .ml_0p5()
+                        .cursor_pointer()
+                        .text_color(cx.theme().muted_foreground)
+                        .hover(|this| this.text_color(cx.theme().foreground))
+                        .child(Icon::new(IconName::Close).xsmall())
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.remove_filter(ix, cx);
+                            cx.stop_propagation();
+                        })),
+                ),
+        )
+    }
+
+    fn render_suggestion(
+        &self,
+        ix: usize,
+        suggestion: &Suggestion,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let label: SharedString = match suggestion {
+            Suggestion::Field(field_ix) => self.fields[*field_ix].label.clone(),
+            Suggestion::Operator(operator) => operator.label(),
+            Suggestion::Value(value) => value.clone(),
+        };
+
+        h_flex()
+            .id(ix)
+            .px_2()
+            .py_1()
+            .rounded(cx.theme().radius)
+            .cursor_pointer()
+            .when(ix == self.highlighted_ix, |this| {
+                this.bg(cx.theme().accent)
+                    .text_color(cx.theme().accent_foreground)
+            })
+            .hover(|this| this.bg(cx.theme().accent.opacity(0.5)))
+            .child(label)
+            .on_mouse_down(MouseButton::Left, {
+                let suggestion = suggestion.clone();
+                cx.listener(move |this, _, window, cx| {
+                    this.select_suggestion(suggestion.clone(), window, cx);
+                    cx.stop_propagation();
+                })
+            })
+    }
+}
+
+impl EventEmitter<FilterBarEvent> for FilterBarState {}
+
+impl Focusable for FilterBarState {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.input.focus_handle(cx)
+    }
+}
+
+impl Render for FilterBarState {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let suggestions = if self.open {
+            self.suggestions(cx)
+        } else {
+            vec![]
+        };
+        let draft_field = self.draft_field.map(|ix| self.fields[ix].label.clone());
+        let draft_operator = self.draft_operator.map(|op| op.label());
+
+        h_flex()
+            .relative()
+            .w_full()
+            .flex_wrap()
+            .gap_1()
+            .px_2()
+            .py_1()
+            .min_h_8()
+            .border_1()
+            .border_color(cx.theme().input)
+            .rounded(cx.theme().radius)
+            .bg(cx.theme().background)
+            .child(
+                Icon::new(IconName::Search)
+                    .small()
+                    .text_color(cx.theme().muted_foreground),
+            )
+            .children(
+                self.filters
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, filter)| self.render_pill(ix, filter, cx))
+                    .collect::<Vec<_>>(),
+            )
+            .when_some(draft_field, |this, field| {
+                this.child(
+                    Tag::secondary().outline().small().child(
+                        h_flex()
+                            .gap_1()
+                            .child(div().font_semibold().child(field))
+                            .when_some(draft_operator, |this, operator| {
+                                this.child(
+                                    div()
+                                        .text_color(cx.theme().muted_foreground)
+                                        .child(operator),
+                                )
+                            }),
+                    ),
+                )
+            })
+            .child(
+                div()
+                    .flex_1()
+                    .min_w_24()
+                    .child(Input::new(&self.input).appearance(false).small()),
+            )
+            .when(!suggestions.is_empty(), |this| {
+                this.child(
+                    div().absolute().left_0().top_full().child(
+                        Portal::new().layer(Layer::Dropdown).child(
+                            v_flex()
+                                .occlude()
+                                .mt_1()
+                                .p_1()
+                                .min_w_40()
+                                .max_h_64()
+                                .popover_style(cx)
+                                .text_sm()
+                                .children(
+                                    suggestions
+                                        .iter()
+                                        .enumerate()
+                                        .map(|(ix, suggestion)| {
+                                            self.render_suggestion(ix, suggestion, cx)
+                                        })
+                                        .collect::<Vec<_>>(),
+                                ),
+                        ),
+                    ),
+                )
+            })
+    }
+}
+
+/// A search bar to compose structured filters as removable pills (field, operator and value),
+/// with the suggestions for each segment.
+///
+/// ```ignore
+/// let state = cx.new(|cx| {
+///     FilterBarState::new(window, cx).fields(vec![
+///         FilterField::new("status").values(["open", "closed"]),
+///         FilterField::new("author"),
+///     ])
+/// });
+///
+/// FilterBar::new(&state)
+/// ```
+#[derive(IntoElement)]
+pub struct FilterBar {
+    id: ElementId,
+    state: Entity<FilterBarState>,
+    style: StyleRefinement,
+}
+
+impl FilterBar {
+    /// Create a new filter bar with the given state.
+    pub fn new(state: &Entity<FilterBarState>) -> Self {
+        Self {
+            id: ElementId::Name(format!("filter-bar-{}", state.entity_id()).into()),
+            state: state.clone(),
+            style: StyleRefinement::default(),
+        }
+    }
+}
+
+impl Styled for FilterBar {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for FilterBar {
+    fn render(self, window: &mut Window, _: &mut App) -> impl IntoElement {
+        // Capture the input actions to handle the suggestions and pills before the input.
+        div()
+            .id(self.id)
+            .w_full()
+            .capture_action(window.listener_for(&self.state, FilterBarState::on_action_enter))
+            .capture_action(window.listener_for(&self.state, FilterBarState::on_action_backspace))
+            .capture_action(window.listener_for(&self.state, FilterBarState::on_action_escape))
+            .capture_action(window.listener_for(&self.state, FilterBarState::on_action_up))
+            .capture_action(window.listener_for(&self.state, FilterBarState::on_action_down))
+            .child(self.state)
+            .refine_style(&self.style)
+    }
+}
@@ -0,0 +1,207 @@
+use gpui::{
+    actions, div, prelude::FluentBuilder as _, AnyElement, App, ElementId, FocusHandle,
+    InteractiveElement as _, IntoElement, KeyBinding, ParentElement, RenderOnce, StyleRefinement,
+    Styled, WeakFocusHandle, Window,
+};
+use smallvec::SmallVec;
+
+use crate::{ActiveTheme, Root, StyledExt as _};
+
+actions!(focus_region, [FocusNextRegion, FocusPrevRegion]);
+
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("f6", FocusNextRegion, Some("Root")),
+        KeyBinding::new("shift-f6", FocusPrevRegion, Some("Root")),
+    ]);
+}
+
+/// Returns the index of the region to focus from the `current`, wraps around.
+fn cycle_index(len: usize, current: Option<usize>, forward: bool) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+
+    Some(match (current, forward) {
+        (Some(ix), true) => (ix + 1) % len,
+        (Some(ix), false) => (ix + len - 1) % len,
+        (None, true) => 0,
+        (None, false) => len - 1,
+    })
+}
+
+struct RegionEntry {
+    focus_handle: WeakFocusHandle,
+    /// The focused element in the region when it was left, to restore when back.
+    last_focused: Option<WeakFocusHandle>,
+}
+
+/// The [`FocusRegion`]s rendered in a window in order, kept by the [`Root`].
+#[derive(Default)]
+pub(crate) struct FocusRegions {
+    entries: Vec<RegionEntry>,
+    previous_entries: Vec<RegionEntry>,
+    /// The region focused by keyboard, to show the focus ring.
+    active: Option<WeakFocusHandle>,
+}
+
+impl FocusRegions {
+    /// Start to collect the regions rendered in the frame.
+    pub(crate) fn begin_frame(&mut self) {
+        self.previous_entries = std::mem::take(&mut self.entries);
+    }
+
+    fn register(&mut self, focus_handle: &FocusHandle) {
+        if self
+            .entries
+            .iter()
+            .any(|entry| entry.focus_handle == *focus_handle)
+        {
+            return;
+        }
+
+        let entry = match self
+            .previous_entries
+            .iter()
+            .position(|entry| entry.focus_handle == *focus_handle)
+        {
+            Some(ix) => self.previous_entries.swap_remove(ix),
+            None => RegionEntry {
+                focus_handle: focus_handle.downgrade(),
+                last_focused: None,
+            },
+        };
+        self.entries.push(entry);
+    }
+
+    fn is_active(&self, focus_handle: &FocusHandle) -> bool {
+        self.active
+            .as_ref()
+            .is_some_and(|active| active == focus_handle)
+    }
+
+    /// Clear the focus ring, returns true if it was shown.
+    pub(crate) fn clear_active(&mut self) -> bool {
+        self.active.take().is_some()
+    }
+
+    /// Move the focus to the next or previous region.
+    pub(crate) fn cycle(&mut self, forward: bool, window: &mut Window, cx: &App) {
+        let regions = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(ix, entry)| Some((ix, entry.focus_handle.upgrade()?)))
+            .collect::<Vec<_>>();
+
+        let current = regions
+            .iter()
+            .rposition(|(_, focus_handle)| focus_handle.contains_focused(window, cx));
+        if let Some(current) = current {
+            self.entries[regions[current].0].last_focused =
+                window.focused(cx).map(|focused| focused.downgrade());
+        }
+
+        let Some(next) = cycle_index(regions.len(), current, forward) else {
+            return;
+        };
+        let (ix, focus_handle) = &regions[next];
+        let last_focused = self.entries[*ix]
+            .last_focused
+            .as_ref()
+            .and_then(|focused| focused.upgrade())
+            .filter(|focused| focus_handle.contains(focused, window));
+
+        window.focus(last_focused.as_ref().unwrap_or(focus_handle));
+        self.active = Some(focus_handle.downgrade());
+    }
+}
+
+/// A major region of the window, like the sidebar, content, panel or status bar.
+///
+/// Press `F6` / `Shift-F6` to cycle the focus between the regions in the order they are rendered,
+/// the previously focused element in the region is focused again when back.
+///
+/// The region must be rendered in every frame, so it can't be in a cached view, e.g. a dock panel.
+///
+/// ```ignore
+/// h_flex()
+///     .child(FocusRegion::new("sidebar", &self.sidebar_focus).child(sidebar))
+///     .child(FocusRegion::new("content", &self.content_focus).flex_1().child(content))
+/// ```
+#[derive(IntoElement)]
+pub struct FocusRegion {
+    id: ElementId,
+    focus_handle: FocusHandle,
+    style: StyleRefinement,
+    children: SmallVec<[AnyElement; 1]>,
+}
+
+impl FocusRegion {
+    /// Create a new region, the `focus_handle` is focused when the region has no focused element before.
+    pub fn new(id: impl Into<ElementId>, focus_handle: &FocusHandle) -> Self {
+        Self {
+            id: id.into(),
+            focus_handle: focus_handle.clone(),
+            style: StyleRefinement::default(),
+            children: SmallVec::new(),
+        }
+    }
+}
+
+impl ParentElement for FocusRegion {
+    fn extend(&mut self, elements: impl IntoIterator<Item = AnyElement>) {
+        self.children.extend(elements);
+    }
+}
+
+impl Styled for FocusRegion {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for FocusRegion {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let mut is_active = false;
+        if let Some(Some(root)) = window.root::<Root>() {
+            is_active = root.update(cx, |root, _| {
+                root.regions.register(&self.focus_handle);
+                root.regions.is_active(&self.focus_handle)
+            });
+        }
+        let show_ring = is_active && self.focus_handle.contains_focused(window, cx);
+
+        div()
+            .id(self.id)
+            .track_focus(&self.focus_handle)
+            .relative()
+            .refine_style(&self.style)
+            .children(self.children)
+            .when(show_ring, |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .border_2()
+                        .border_color(cx.theme().ring)
+                        .rounded(cx.theme().radius),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cycle_index;
+
+    #[test]
+    fn test_cycle_index() {
+        assert_eq!(cycle_index(0, None, true), None);
+        assert_eq!(cycle_index(3, None, true), Some(0));
+        assert_eq!(cycle_index(3, None, false), Some(2));
+        assert_eq!(cycle_index(3, Some(1), true), Some(2));
+        assert_eq!(cycle_index(3, Some(2), true), Some(0));
+        assert_eq!(cycle_index(3, Some(0), false), Some(2));
+    }
+}
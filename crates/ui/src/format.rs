@@ -0,0 +1,254 @@
+//! Locale-aware formatting of numbers, currencies, percentages, compact numbers,
+//! file sizes and durations, driven by the current [`locale`](crate::locale).
+
+use std::time::Duration;
+
+/// The number conventions of a locale.
+struct NumberLocale {
+    decimal: &'static str,
+    group: &'static str,
+    /// The compact units from the largest.
+    compact: &'static [(f64, &'static str)],
+    /// Place the currency symbol after the number.
+    currency_suffix: bool,
+    /// The units of hours, minutes and seconds, and the separator between them.
+    duration: [&'static str; 4],
+}
+
+impl NumberLocale {
+    fn new(locale: &str) -> Self {
+        if locale.starts_with("zh") {
+            Self {
+                decimal: ".",
+                group: ",",
+                compact: &[(1e8, "亿"), (1e4, "万")],
+                currency_suffix: false,
+                duration: ["小时", "分", "秒", ""],
+            }
+        } else if locale.starts_with("it") {
+            Self {
+                decimal: ",",
+                group: ".",
+                compact: &[(1e12, " Bln"), (1e9, " Mrd"), (1e6, " Mln"), (1e3, "k")],
+                currency_suffix: true,
+                duration: ["h", "m", "s", " "],
+            }
+        } else {
+            Self {
+                decimal: ".",
+                group: ",",
+                compact: &[(1e12, "T"), (1e9, "B"), (1e6, "M"), (1e3, "K")],
+                currency_suffix: false,
+                duration: ["h", "m", "s", " "],
+            }
+        }
+    }
+
+    fn number(&self, value: f64, decimals: usize) -> String {
+        let formatted = format!("{:.*}", decimals, value.abs());
+        let (int, frac) = formatted.split_once('.').unwrap_or((&formatted, ""));
+
+        let mut out = String::new();
+        if value < 0. && formatted.chars().any(|c| c.is_ascii_digit() && c != '0') {
+            out.push('-');
+        }
+        for (ix, c) in int.chars().enumerate() {
+            if ix > 0 && (int.len() - ix) % 3 == 0 {
+                out.push_str(self.group);
+            }
+            out.push(c);
+        }
+        if !frac.is_empty() {
+            out.push_str(self.decimal);
+            out.push_str(frac);
+        }
+        out
+    }
+
+    /// Format with at most 1 decimal place, without the trailing `.0`.
+    fn short(&self, value: f64) -> String {
+        let value = (value * 10.).round() / 10.;
+        self.number(value, if value.fract() == 0. { 0 } else { 1 })
+    }
+}
+
+fn currency_symbol(currency: &str) -> (&str, usize) {
+    match currency {
+        "USD" => ("$", 2),
+        "EUR" => ("€", 2),
+        "GBP" => ("£", 2),
+        "CNY" => ("¥", 2),
+        "JPY" => ("¥", 0),
+        "HKD" => ("HK$", 2),
+        _ => (currency, 2),
+    }
+}
+
+fn format_currency_in(locale: &NumberLocale, value: f64, currency: &str) -> String {
+    let (symbol, decimals) = currency_symbol(currency);
+    let number = locale.number(value.abs(), decimals);
+    let sign = if value < 0. && number.chars().any(|c| c.is_ascii_digit() && c != '0') {
+        "-"
+    } else {
+        ""
+    };
+    // The currency codes are separated by a space.
+    let space = if symbol.chars().all(|c| c.is_ascii_uppercase()) {
+        " "
+    } else {
+        ""
+    };
+
+    if locale.currency_suffix {
+        format!("{}{} {}", sign, number, symbol)
+    } else {
+        format!("{}{}{}{}", sign, symbol, space, number)
+    }
+}
+
+fn format_compact_in(locale: &NumberLocale, value: f64) -> String {
+    let abs = value.abs();
+    let sign = if value < 0. { "-" } else { "" };
+    let units = locale.compact;
+    let Some(ix) = units.iter().position(|(threshold, _)| abs >= *threshold) else {
+        return locale.short(value);
+    };
+
+    // Use the larger unit if it is rounded up to, e.g.: 999_999 is 1M not 1000K.
+    let (threshold, unit) = units[ix];
+    let rounded = (abs / threshold * 10.).round() / 10.;
+    let (threshold, unit) = match ix.checked_sub(1).map(|ix| units[ix]) {
+        Some(larger) if rounded * threshold >= larger.0 => larger,
+        _ => (threshold, unit),
+    };
+
+    format!("{}{}{}", sign, locale.short(abs / threshold), unit)
+}
+
+fn format_file_size_in(locale: &NumberLocale, bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while unit < UNITS.len() - 1 && (size * 10.).round() / 10. >= 1024. {
+        size /= 1024.;
+        unit += 1;
+    }
+    format!("{} {}", locale.short(size), UNITS[unit])
+}
+
+fn format_duration_in(locale: &NumberLocale, duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    let [h, m, s, separator] = locale.duration;
+
+    let mut parts = vec![];
+    if hours > 0 {
+        parts.push(format!("{}{}", hours, h));
+    }
+    if minutes > 0 {
+        parts.push(format!("{}{}", minutes, m));
+    }
+    if seconds > 0 || parts.is_empty() {
+        parts.push(format!("{}{}", seconds, s));
+    }
+    parts.join(separator)
+}
+
+/// Format the number with the grouping separators and the decimal places,
+/// e.g.: `1,234.50` in English, `1.234,50` in Italian.
+pub fn format_number(value: f64, decimals: usize) -> String {
+    NumberLocale::new(&crate::locale()).number(value, decimals)
+}
+
+/// Format the amount in the [ISO 4217](https://en.wikipedia.org/wiki/ISO_4217) currency code,
+/// e.g.: `$1,234.50`, `1.234,50 €`.
+pub fn format_currency(value: f64, currency: &str) -> String {
+    format_currency_in(&NumberLocale::new(&crate::locale()), value, currency)
+}
+
+/// Format the ratio as percentage, e.g.: `0.256` is `25.6%` with 1 decimal place.
+pub fn format_percent(value: f64, decimals: usize) -> String {
+    format!("{}%", format_number(value * 100., decimals))
+}
+
+/// Format the number in the compact units, e.g.: `1.2K`, `3.4M` in English, `1.2万` in Chinese.
+pub fn format_compact(value: f64) -> String {
+    format_compact_in(&NumberLocale::new(&crate::locale()), value)
+}
+
+/// Format the bytes in the binary units, e.g.: `1.5 KB`, `12 MB`.
+pub fn format_file_size(bytes: u64) -> String {
+    format_file_size_in(&NumberLocale::new(&crate::locale()), bytes)
+}
+
+/// Format the duration in hours, minutes and seconds, e.g.: `1h 5m`, `2m 30s`.
+pub fn format_duration(duration: Duration) -> String {
+    format_duration_in(&NumberLocale::new(&crate::locale()), duration)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_number() {
+        let en = NumberLocale::new("en");
+        assert_eq!(en.number(1234567.891, 2), "1,234,567.89");
+        assert_eq!(en.number(-999.5, 0), "-1,000");
+        assert_eq!(en.number(-0.001, 2), "0.00");
+        assert_eq!(en.number(12., 0), "12");
+        assert_eq!(NumberLocale::new("it").number(1234.5, 2), "1.234,50");
+    }
+
+    #[test]
+    fn test_currency() {
+        let en = NumberLocale::new("en");
+        assert_eq!(format_currency_in(&en, 1234.5, "USD"), "$1,234.50");
+        assert_eq!(format_currency_in(&en, -3.2, "EUR"), "-€3.20");
+        assert_eq!(format_currency_in(&en, 1234.6, "JPY"), "¥1,235");
+        assert_eq!(format_currency_in(&en, 5., "CHF"), "CHF 5.00");
+        let it = NumberLocale::new("it");
+        assert_eq!(format_currency_in(&it, 1234.5, "EUR"), "1.234,50 €");
+    }
+
+    #[test]
+    fn test_compact() {
+        let en = NumberLocale::new("en");
+        assert_eq!(format_compact_in(&en, 999.), "999");
+        assert_eq!(format_compact_in(&en, 1200.), "1.2K");
+        assert_eq!(format_compact_in(&en, 1000.), "1K");
+        assert_eq!(format_compact_in(&en, 999_999.), "1M");
+        assert_eq!(format_compact_in(&en, -2_500_000.), "-2.5M");
+        assert_eq!(format_compact_in(&en, 0.25), "0.3");
+
+        let zh = NumberLocale::new("zh-CN");
+        assert_eq!(format_compact_in(&zh, 12_000.), "1.2万");
+        assert_eq!(format_compact_in(&zh, 350_000_000.), "3.5亿");
+        assert_eq!(format_compact_in(&NumberLocale::new("it"), 1500.), "1,5k");
+    }
+
+    #[test]
+    fn test_file_size() {
+        let en = NumberLocale::new("en");
+        assert_eq!(format_file_size_in(&en, 512), "512 B");
+        assert_eq!(format_file_size_in(&en, 1536), "1.5 KB");
+        assert_eq!(format_file_size_in(&en, 1024 * 1024 - 1), "1 MB");
+        assert_eq!(format_file_size_in(&en, 5 * 1024 * 1024 * 1024), "5 GB");
+    }
+
+    #[test]
+    fn test_duration() {
+        let en = NumberLocale::new("en");
+        assert_eq!(format_duration_in(&en, Duration::from_secs(0)), "0s");
+        assert_eq!(format_duration_in(&en, Duration::from_secs(150)), "2m 30s");
+        assert_eq!(format_duration_in(&en, Duration::from_secs(3900)), "1h 5m");
+        let zh = NumberLocale::new("zh-CN");
+        assert_eq!(
+            format_duration_in(&zh, Duration::from_secs(3903)),
+            "1小时5分3秒"
+        );
+    }
+}
@@ -0,0 +1,370 @@
+//! A fuzzy matcher with the fzf style scoring, shared by the search of the components
+//! to rank the results consistently.
+
+use gpui::{App, SharedString, Task};
+
+const SCORE_MATCH: i32 = 16;
+const SCORE_GAP_START: i32 = -3;
+const SCORE_GAP_EXTENSION: i32 = -1;
+const BONUS_BOUNDARY: i32 = SCORE_MATCH / 2;
+const BONUS_BOUNDARY_WHITE: i32 = BONUS_BOUNDARY + 2;
+const BONUS_BOUNDARY_DELIMITER: i32 = BONUS_BOUNDARY + 1;
+const BONUS_NON_WORD: i32 = SCORE_MATCH / 2;
+const BONUS_CAMEL: i32 = BONUS_BOUNDARY + SCORE_GAP_EXTENSION;
+const BONUS_CONSECUTIVE: i32 = -(SCORE_GAP_START + SCORE_GAP_EXTENSION);
+const BONUS_FIRST_CHAR_MULTIPLIER: i32 = 2;
+
+/// The result of [`fuzzy_match`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// The score of the match, the higher is better.
+    pub score: i32,
+    /// The byte indices of the matched characters in the text, e.g. to highlight them.
+    pub indices: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    White,
+    Delimiter,
+    NonWord,
+    Lower,
+    Upper,
+    Letter,
+    Number,
+}
+
+impl CharClass {
+    fn of(c: char) -> Self {
+        if c.is_whitespace() {
+            Self::White
+        } else if matches!(c, '/' | '\\' | ',' | ':' | ';' | '|') {
+            Self::Delimiter
+        } else if c.is_lowercase() {
+            Self::Lower
+        } else if c.is_uppercase() {
+            Self::Upper
+        } else if c.is_numeric() {
+            Self::Number
+        } else if c.is_alphabetic() {
+            Self::Letter
+        } else {
+            Self::NonWord
+        }
+    }
+
+    fn is_word(self) -> bool {
+        !matches!(self, Self::White | Self::Delimiter | Self::NonWord)
+    }
+}
+
+/// The bonus of a character at the boundary of a word, like fzf.
+fn bonus(prev: CharClass, class: CharClass) -> i32 {
+    if class.is_word() {
+        match prev {
+            CharClass::White => BONUS_BOUNDARY_WHITE,
+            CharClass::Delimiter => BONUS_BOUNDARY_DELIMITER,
+            CharClass::NonWord => BONUS_BOUNDARY,
+            CharClass::Lower if class == CharClass::Upper => BONUS_CAMEL,
+            _ if prev != CharClass::Number && class == CharClass::Number => BONUS_CAMEL,
+            _ => 0,
+        }
+    } else if class == CharClass::White {
+        BONUS_BOUNDARY_WHITE
+    } else {
+        BONUS_NON_WORD
+    }
+}
+
+fn fold(c: char, case_sensitive: bool) -> char {
+    if case_sensitive {
+        c
+    } else {
+        c.to_lowercase().next().unwrap_or(c)
+    }
+}
+
+/// Match the `pattern` in the `text` with the fzf style scoring, returns `None` if not matched.
+///
+/// All characters of the `pattern` must be in the `text` in order, the matches at the start
+/// of the words and the consecutive matches have higher scores, and the gaps are penalized.
+///
+/// It is case insensitive unless the `pattern` has uppercase characters (smart case),
+/// the whitespaces in the `pattern` are ignored, and an empty `pattern` matches any text with 0 score.
+///
+/// ```
+/// use gpui_component::fuzzy::fuzzy_match;
+///
+/// let m = fuzzy_match("fb", "foo_bar").unwrap();
+/// assert_eq!(m.indices, vec![0, 4]);
+/// assert!(fuzzy_match("fb", "foobar").unwrap().score < m.score);
+/// assert!(fuzzy_match("bf", "foo_bar").is_none());
+/// ```
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<FuzzyMatch> {
+    let case_sensitive = pattern.chars().any(char::is_uppercase);
+    let pattern = pattern
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| fold(c, case_sensitive))
+        .collect::<Vec<_>>();
+    if pattern.is_empty() {
+        return Some(FuzzyMatch {
+            score: 0,
+            indices: vec![],
+        });
+    }
+
+    let chars = text.char_indices().collect::<Vec<_>>();
+    let folded = chars
+        .iter()
+        .map(|(_, c)| fold(*c, case_sensitive))
+        .collect::<Vec<_>>();
+
+    // Quick check if the pattern is a subsequence of the text.
+    let mut pattern_chars = pattern.iter().peekable();
+    for c in &folded {
+        if pattern_chars.peek() == Some(&c) {
+            pattern_chars.next();
+        }
+    }
+    if pattern_chars.peek().is_some() {
+        return None;
+    }
+
+    let mut prev_class = CharClass::White;
+    let bonuses = chars
+        .iter()
+        .map(|(_, c)| {
+            let class = CharClass::of(*c);
+            let bonus = bonus(prev_class, class);
+            prev_class = class;
+            bonus
+        })
+        .collect::<Vec<_>>();
+
+    let (n, m) = (folded.len(), pattern.len());
+    // The best score ending with the `i`th pattern char matched at the `j`th text char,
+    // and the text index of the previous pattern char matched for the traceback.
+    let mut scores = vec![vec![None::<i32>; n]; m];
+    let mut prevs = vec![vec![0usize; n]; m];
+
+    for i in 0..m {
+        // The best score of the previous row with a gap ending before `j`, and its index.
+        let mut gapped: Option<(i32, usize)> = None;
+        for j in i..n {
+            if i > 0 && j >= 2 {
+                let extended = gapped.map(|(score, k)| (score + SCORE_GAP_EXTENSION, k));
+                let started = scores[i - 1][j - 2].map(|score| (score + SCORE_GAP_START, j - 2));
+                gapped = match (extended, started) {
+                    (Some(a), Some(b)) => Some(if b.0 >= a.0 { b } else { a }),
+                    (a, b) => a.or(b),
+                };
+            }
+            if folded[j] != pattern[i] {
+                continue;
+            }
+
+            if i == 0 {
+                scores[i][j] = Some(SCORE_MATCH + bonuses[j] * BONUS_FIRST_CHAR_MULTIPLIER);
+                continue;
+            }
+
+            let consecutive = j
+                .checked_sub(1)
+                .and_then(|k| scores[i - 1][k])
+                .map(|score| {
+                    (
+                        score + SCORE_MATCH + bonuses[j].max(BONUS_CONSECUTIVE),
+                        j - 1,
+                    )
+                });
+            let gap = gapped.map(|(score, k)| (score + SCORE_MATCH + bonuses[j], k));
+            let best = match (consecutive, gap) {
+                (Some(a), Some(b)) => Some(if a.0 >= b.0 { a } else { b }),
+                (a, b) => a.or(b),
+            };
+            if let Some((score, k)) = best {
+                scores[i][j] = Some(score);
+                prevs[i][j] = k;
+            }
+        }
+    }
+
+    let (mut j, score) = scores[m - 1]
+        .iter()
+        .enumerate()
+        .filter_map(|(j, score)| score.map(|score| (j, score)))
+        .max_by_key(|(j, score)| (*score, std::cmp::Reverse(*j)))?;
+
+    let mut indices = vec![0; m];
+    for i in (0..m).rev() {
+        indices[i] = chars[j].0;
+        j = prevs[i][j];
+    }
+
+    Some(FuzzyMatch { score, indices })
+}
+
+/// Filter the `items` by the `pattern` and sort them by the score of [`fuzzy_match`] with the text of the `key`,
+/// the items with the same score keep the original order.
+pub fn fuzzy_filter<T>(
+    pattern: &str,
+    items: impl IntoIterator<Item = T>,
+    key: impl Fn(&T) -> &str,
+) -> Vec<(T, FuzzyMatch)> {
+    let mut matches = items
+        .into_iter()
+        .filter_map(|item| fuzzy_match(pattern, key(&item)).map(|m| (item, m)))
+        .collect::<Vec<_>>();
+    matches.sort_by_key(|(_, m)| std::cmp::Reverse(m.score));
+    matches
+}
+
+/// Returns the bit mask of the case folded characters in the `text`.
+///
+/// The ASCII letters and digits have their own bits, other characters share the rest bits by hash,
+/// so the mask of a matched text always contains the mask of the pattern.
+fn char_mask(text: &str) -> u64 {
+    text.chars()
+        .filter(|c| !c.is_whitespace())
+        .fold(0, |mask, c| {
+            let c = fold(c, false);
+            let bit = match c {
+                'a'..='z' => c as u32 - 'a' as u32,
+                '0'..='9' => 26 + c as u32 - '0' as u32,
+                _ => 36 + (c as u32 % 28),
+            };
+            mask | (1 << bit)
+        })
+}
+
+/// A pre-built index of the texts to speed up the [`fuzzy_filter`] on the large datasets.
+///
+/// The index keeps the character mask of each text, the texts that not contain all characters
+/// of the pattern are skipped without scoring. The search can also be narrowed to the matches of
+/// the previous pattern when typing, because the matches of `abc` are always in the matches of `ab`.
+#[derive(Debug, Clone, Default)]
+pub struct FuzzyIndex {
+    texts: Vec<SharedString>,
+    masks: Vec<u64>,
+}
+
+impl FuzzyIndex {
+    /// Create a new index of the texts.
+    pub fn new(texts: impl IntoIterator<Item = impl Into<SharedString>>) -> Self {
+        let texts = texts.into_iter().map(Into::into).collect::<Vec<_>>();
+        let masks = texts.iter().map(|text| char_mask(text)).collect();
+        Self { texts, masks }
+    }
+
+    /// Build the index of the texts in the background.
+    pub fn build(texts: Vec<SharedString>, cx: &App) -> Task<Self> {
+        cx.background_executor()
+            .spawn(async move { Self::new(texts) })
+    }
+
+    /// Returns the number of the texts in the index.
+    pub fn len(&self) -> usize {
+        self.texts.len()
+    }
+
+    /// Returns true if the index has no text.
+    pub fn is_empty(&self) -> bool {
+        self.texts.is_empty()
+    }
+
+    /// Returns the text at the `ix`.
+    pub fn text(&self, ix: usize) -> Option<&SharedString> {
+        self.texts.get(ix)
+    }
+
+    /// Search the `pattern` like the [`fuzzy_filter`], returns the indices of the matched texts
+    /// with the matches, sorted by the score.
+    ///
+    /// If `within` is set, only the texts at these indices (in ascending order) are searched,
+    /// e.g. the matches of the previous pattern that is a prefix of the `pattern`.
+    pub fn search(&self, pattern: &str, within: Option<&[usize]>) -> Vec<(usize, FuzzyMatch)> {
+        let mask = char_mask(pattern);
+        let matches = |ix: usize| {
+            if self.masks[ix] & mask != mask {
+                return None;
+            }
+            fuzzy_match(pattern, &self.texts[ix]).map(|m| (ix, m))
+        };
+
+        let mut results = match within {
+            Some(within) => within
+                .iter()
+                .filter(|ix| **ix < self.texts.len())
+                .filter_map(|ix| matches(*ix))
+                .collect::<Vec<_>>(),
+            None => (0..self.texts.len()).filter_map(matches).collect(),
+        };
+        results.sort_by_key(|(_, m)| std::cmp::Reverse(m.score));
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{char_mask, fuzzy_filter, fuzzy_match, FuzzyIndex};
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert_eq!(fuzzy_match("", "abc").unwrap().score, 0);
+        assert!(fuzzy_match("abd", "abc").is_none());
+
+        let m = fuzzy_match("oba", "foo/bar").unwrap();
+        assert_eq!(m.indices, vec![2, 4, 5]);
+
+        // Prefer the boundary of the words.
+        let m = fuzzy_match("ui", "build_ui").unwrap();
+        assert_eq!(m.indices, vec![6, 7]);
+
+        // Smart case.
+        assert!(fuzzy_match("Fb", "foo_bar").is_none());
+        assert!(fuzzy_match("fb", "Foo_Bar").is_some());
+
+        // Unicode, the indices are in bytes.
+        let m = fuzzy_match("中文", "中a文").unwrap();
+        assert_eq!(m.indices, vec![0, 4]);
+        let m = fuzzy_match("é", "CAFÉ").unwrap();
+        assert_eq!(m.indices, vec![3]);
+    }
+
+    #[test]
+    fn test_fuzzy_filter() {
+        let items = ["select_item", "sidebar", "search_input", "slider"];
+        let results = fuzzy_filter("si", items, |item| item);
+        let results = results.iter().map(|(item, _)| *item).collect::<Vec<_>>();
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0], "sidebar");
+        assert_eq!(results[3], "slider");
+        assert_eq!(fuzzy_filter("zz", items, |item| item).len(), 0);
+    }
+
+    #[test]
+    fn test_fuzzy_index() {
+        assert_eq!(char_mask("ab"), char_mask("BA"));
+        assert_eq!(char_mask("a b") & char_mask("ab"), char_mask("ab"));
+
+        let items = ["select_item", "sidebar", "search_input", "slider", "中文"];
+        let index = FuzzyIndex::new(items);
+        assert_eq!(index.len(), 5);
+
+        let results = index.search("si", None);
+        let expected = fuzzy_filter("si", items, |item| item);
+        assert_eq!(
+            results.iter().map(|(ix, _)| items[*ix]).collect::<Vec<_>>(),
+            expected.iter().map(|(item, _)| *item).collect::<Vec<_>>()
+        );
+        assert_eq!(index.search("中", None).len(), 1);
+
+        // Narrow to the matches of the previous pattern.
+        let mut within = results.iter().map(|(ix, _)| *ix).collect::<Vec<_>>();
+        within.sort();
+        let results = index.search("sdb", Some(&within));
+        assert_eq!(results.len(), 1);
+        assert_eq!(index.text(results[0].0).unwrap(), "sidebar");
+    }
+}
@@ -0,0 +1,349 @@
+use gpui::{point, px, size, Bounds, Pixels, Point, SharedString, Size};
+
+pub(super) const HEADER_HEIGHT: Pixels = px(28.);
+pub(super) const ROW_HEIGHT: Pixels = px(24.);
+
+/// The id of a [`GraphNode`] in the [`Graph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(pub usize);
+
+/// The side of a port on the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PortSide {
+    Input,
+    Output,
+}
+
+/// A reference to a port of a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PortRef {
+    pub node: NodeId,
+    pub side: PortSide,
+    pub index: usize,
+}
+
+impl PortRef {
+    pub fn input(node: NodeId, index: usize) -> Self {
+        Self {
+            node,
+            side: PortSide::Input,
+            index,
+        }
+    }
+
+    pub fn output(node: NodeId, index: usize) -> Self {
+        Self {
+            node,
+            side: PortSide::Output,
+            index,
+        }
+    }
+}
+
+/// A typed port of a node, e.g.: `color: vec3`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Port {
+    pub name: SharedString,
+    /// The type of the port, the ports with the same kind can be connected by default.
+    ///
+    /// Empty to accept any kind.
+    pub kind: SharedString,
+}
+
+impl Port {
+    pub fn new(name: impl Into<SharedString>, kind: impl Into<SharedString>) -> Self {
+        Self {
+            name: name.into(),
+            kind: kind.into(),
+        }
+    }
+}
+
+/// A node in the [`Graph`].
+///
+/// The node is laid out with the title, a row for each pair of the input and output ports,
+/// and then the body rendered by the [`GraphDelegate`](super::GraphDelegate).
+#[derive(Debug, Clone)]
+pub struct GraphNode {
+    pub id: NodeId,
+    pub title: SharedString,
+    /// The position in the content coordinates.
+    pub position: Point<Pixels>,
+    pub inputs: Vec<Port>,
+    pub outputs: Vec<Port>,
+    pub width: Pixels,
+    pub body_height: Pixels,
+}
+
+impl GraphNode {
+    /// Create a new node, the id is assigned when it is added to the [`Graph`].
+    pub fn new(title: impl Into<SharedString>) -> Self {
+        Self {
+            id: NodeId(0),
+            title: title.into(),
+            position: Point::default(),
+            inputs: vec![],
+            outputs: vec![],
+            width: px(180.),
+            body_height: px(0.),
+        }
+    }
+
+    /// Set the position in the content coordinates.
+    pub fn position(mut self, position: Point<Pixels>) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Add an input port.
+    pub fn input(mut self, port: Port) -> Self {
+        self.inputs.push(port);
+        self
+    }
+
+    /// Add an output port.
+    pub fn output(mut self, port: Port) -> Self {
+        self.outputs.push(port);
+        self
+    }
+
+    /// Set the width of the node, default: 180px
+    pub fn width(mut self, width: impl Into<Pixels>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    /// Set the height of the body below the ports, default: 0px
+    pub fn body_height(mut self, height: impl Into<Pixels>) -> Self {
+        self.body_height = height.into();
+        self
+    }
+
+    /// Returns the size of the node.
+    pub fn size(&self) -> Size<Pixels> {
+        let rows = self.inputs.len().max(self.outputs.len());
+        size(
+            self.width,
+            HEADER_HEIGHT + ROW_HEIGHT * rows as f32 + self.body_height,
+        )
+    }
+
+    /// Returns the bounds of the node in the content coordinates.
+    pub fn bounds(&self) -> Bounds<Pixels> {
+        Bounds::new(self.position, self.size())
+    }
+
+    /// Returns the center of the port in the content coordinates.
+    pub fn port_position(&self, side: PortSide, index: usize) -> Point<Pixels> {
+        let y = self.position.y + HEADER_HEIGHT + ROW_HEIGHT * (index as f32 + 0.5);
+        match side {
+            PortSide::Input => point(self.position.x, y),
+            PortSide::Output => point(self.position.x + self.width, y),
+        }
+    }
+}
+
+/// An edge from an output port to an input port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Edge {
+    pub from: PortRef,
+    pub to: PortRef,
+}
+
+/// The nodes and the edges of the [`GraphEditor`](super::GraphEditor).
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    nodes: Vec<GraphNode>,
+    edges: Vec<Edge>,
+    next_id: usize,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a node and returns its id.
+    pub fn add_node(&mut self, mut node: GraphNode) -> NodeId {
+        let id = NodeId(self.next_id);
+        self.next_id += 1;
+        node.id = id;
+        self.nodes.push(node);
+        id
+    }
+
+    /// Remove the node and its edges.
+    pub fn remove_node(&mut self, id: NodeId) -> Option<GraphNode> {
+        let ix = self.nodes.iter().position(|node| node.id == id)?;
+        self.edges
+            .retain(|edge| edge.from.node != id && edge.to.node != id);
+        Some(self.nodes.remove(ix))
+    }
+
+    pub fn nodes(&self) -> &[GraphNode] {
+        &self.nodes
+    }
+
+    pub fn node(&self, id: NodeId) -> Option<&GraphNode> {
+        self.nodes.iter().find(|node| node.id == id)
+    }
+
+    pub fn node_mut(&mut self, id: NodeId) -> Option<&mut GraphNode> {
+        self.nodes.iter_mut().find(|node| node.id == id)
+    }
+
+    pub fn edges(&self) -> &[Edge] {
+        &self.edges
+    }
+
+    /// Returns the port of the reference.
+    pub fn port(&self, port: PortRef) -> Option<&Port> {
+        let node = self.node(port.node)?;
+        match port.side {
+            PortSide::Input => node.inputs.get(port.index),
+            PortSide::Output => node.outputs.get(port.index),
+        }
+    }
+
+    /// Returns the center of the port in the content coordinates.
+    pub fn port_position(&self, port: PortRef) -> Option<Point<Pixels>> {
+        self.port(port)?;
+        Some(self.node(port.node)?.port_position(port.side, port.index))
+    }
+
+    /// Returns the edge connected to the input port.
+    pub fn input_edge(&self, port: PortRef) -> Option<Edge> {
+        self.edges.iter().find(|edge| edge.to == port).copied()
+    }
+
+    /// Add the edge, an input port only accepts one edge, the existing one is replaced and returned.
+    ///
+    /// The edge must be from an output port to an input port.
+    pub fn connect(&mut self, edge: Edge) -> Option<Edge> {
+        if edge.from.side != PortSide::Output || edge.to.side != PortSide::Input {
+            return None;
+        }
+
+        let replaced = self.input_edge(edge.to);
+        self.edges.retain(|e| e.to != edge.to);
+        self.edges.push(edge);
+        replaced.filter(|e| *e != edge)
+    }
+
+    /// Remove the edge, returns true if it existed.
+    pub fn disconnect(&mut self, edge: Edge) -> bool {
+        let len = self.edges.len();
+        self.edges.retain(|e| *e != edge);
+        self.edges.len() != len
+    }
+
+    /// Returns the bounds of all the nodes.
+    pub fn bounds(&self) -> Option<Bounds<Pixels>> {
+        self.nodes
+            .iter()
+            .map(GraphNode::bounds)
+            .reduce(|a, b| a.union(&b))
+    }
+}
+
+/// Snap the point to the grid.
+pub(super) fn snap_to_grid(p: Point<Pixels>, grid: Pixels) -> Point<Pixels> {
+    if grid <= px(0.) {
+        return p;
+    }
+
+    point(grid * (p.x / grid).round(), grid * (p.y / grid).round())
+}
+
+/// Returns the control points of the bezier curve of an edge from `from` to `to`,
+/// the curve leaves the output port and enters the input port horizontally.
+pub(super) fn edge_control_points(
+    from: Point<Pixels>,
+    to: Point<Pixels>,
+) -> (Point<Pixels>, Point<Pixels>) {
+    let dx = ((to.x - from.x).abs() / 2.).max(px(40.));
+    (point(from.x + dx, from.y), point(to.x - dx, to.y))
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{point, px};
+
+    use super::*;
+
+    fn graph() -> (Graph, NodeId, NodeId) {
+        let mut graph = Graph::new();
+        let a = graph.add_node(
+            GraphNode::new("Texture")
+                .position(point(px(0.), px(0.)))
+                .output(Port::new("color", "vec3"))
+                .output(Port::new("alpha", "float")),
+        );
+        let b = graph.add_node(
+            GraphNode::new("Output")
+                .position(point(px(300.), px(100.)))
+                .input(Port::new("color", "vec3")),
+        );
+        (graph, a, b)
+    }
+
+    #[test]
+    fn test_node_layout() {
+        let (graph, a, b) = graph();
+        let a = graph.node(a).unwrap();
+        assert_eq!(a.size(), gpui::size(px(180.), px(28. + 24. * 2.)));
+        assert_eq!(
+            a.port_position(PortSide::Output, 1),
+            point(px(180.), px(28. + 24. * 1.5))
+        );
+        assert_eq!(
+            graph.port_position(PortRef::input(b, 0)),
+            Some(point(px(300.), px(100. + 28. + 12.)))
+        );
+        assert_eq!(graph.port_position(PortRef::input(b, 1)), None);
+    }
+
+    #[test]
+    fn test_connect() {
+        let (mut graph, a, b) = graph();
+        let edge = Edge {
+            from: PortRef::output(a, 0),
+            to: PortRef::input(b, 0),
+        };
+        assert_eq!(graph.connect(edge), None);
+        assert_eq!(graph.connect(edge), None);
+        assert_eq!(graph.edges().len(), 1);
+
+        // The input port only accepts one edge.
+        let other = Edge {
+            from: PortRef::output(a, 1),
+            to: PortRef::input(b, 0),
+        };
+        assert_eq!(graph.connect(other), Some(edge));
+        assert_eq!(graph.edges(), &[other]);
+
+        // Wrong direction.
+        let reversed = Edge {
+            from: other.to,
+            to: other.from,
+        };
+        assert_eq!(graph.connect(reversed), None);
+        assert_eq!(graph.edges().len(), 1);
+
+        graph.remove_node(a);
+        assert!(graph.edges().is_empty());
+        assert_eq!(graph.nodes().len(), 1);
+    }
+
+    #[test]
+    fn test_snap_to_grid() {
+        assert_eq!(
+            snap_to_grid(point(px(14.), px(26.)), px(10.)),
+            point(px(10.), px(30.))
+        );
+        assert_eq!(
+            snap_to_grid(point(px(14.), px(26.)), px(0.)),
+            point(px(14.), px(26.))
+        );
+    }
+}
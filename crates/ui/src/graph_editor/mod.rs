@@ -0,0 +1,748 @@
+mod graph;
+
+use std::{
+    collections::BTreeSet,
+    hash::{DefaultHasher, Hash as _, Hasher as _},
+};
+
+use gpui::{
+    actions, canvas, div, fill, prelude::FluentBuilder as _, px, AnyElement, App, AppContext as _,
+    Bounds, Context, ElementId, Entity, EventEmitter, Focusable as _, Hsla,
+    InteractiveElement as _, IntoElement, KeyBinding, MouseButton, MouseDownEvent, MouseMoveEvent,
+    MouseUpEvent, ParentElement, PathBuilder, Pixels, Point, Render, RenderOnce, StyleRefinement,
+    Styled, Window,
+};
+
+use crate::{
+    actions::Cancel,
+    h_flex, input, v_flex,
+    viewport::{Viewport, ViewportState, ViewportTransform},
+    ActiveTheme, StyledExt as _,
+};
+
+pub use graph::*;
+
+actions!(graph_editor, [DeleteSelected]);
+
+const CONTEXT: &str = "GraphEditor";
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("backspace", DeleteSelected, Some(CONTEXT)),
+        KeyBinding::new("delete", DeleteSelected, Some(CONTEXT)),
+        KeyBinding::new("escape", Cancel, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-a", input::SelectAll, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-a", input::SelectAll, Some(CONTEXT)),
+    ]);
+}
+
+const PORT_SIZE: Pixels = px(10.);
+
+/// A delegate to render the nodes and validate the connections of the [`GraphEditor`].
+#[allow(unused)]
+pub trait GraphDelegate: Sized + 'static {
+    /// Render the body of the node below the ports, the height is `node.body_height`.
+    ///
+    /// Default is None.
+    fn render_node_body(
+        &self,
+        node: &GraphNode,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<AnyElement> {
+        None
+    }
+
+    /// Return true if the output port `from` can be connected to the input port `to`.
+    ///
+    /// Default: the ports are on different nodes and have the same kind, an empty kind accepts any kind.
+    fn can_connect(&self, graph: &Graph, from: PortRef, to: PortRef, cx: &App) -> bool {
+        let (Some(from_port), Some(to_port)) = (graph.port(from), graph.port(to)) else {
+            return false;
+        };
+
+        from.node != to.node
+            && (from_port.kind.is_empty()
+                || to_port.kind.is_empty()
+                || from_port.kind == to_port.kind)
+    }
+}
+
+/// Events emitted by the [`GraphEditorState`].
+#[derive(Debug, Clone)]
+pub enum GraphEditorEvent {
+    /// An edge was added.
+    Connect(Edge),
+    /// An edge was removed.
+    Disconnect(Edge),
+    /// The nodes were moved by dragging.
+    Move(Vec<NodeId>),
+    /// The selected nodes were changed.
+    Select(Vec<NodeId>),
+    /// The nodes were deleted.
+    Delete(Vec<NodeId>),
+}
+
+enum Drag {
+    /// Move the nodes from their origins.
+    Nodes {
+        start: Point<Pixels>,
+        origins: Vec<(NodeId, Point<Pixels>)>,
+        moved: bool,
+    },
+    /// Box selection, the `base` is the selection before the drag.
+    Select {
+        start: Point<Pixels>,
+        end: Point<Pixels>,
+        base: BTreeSet<NodeId>,
+    },
+    /// Connect from a port to the position.
+    Connect {
+        from: PortRef,
+        position: Point<Pixels>,
+    },
+}
+
+/// State of the [`GraphEditor`].
+pub struct GraphEditorState<D: GraphDelegate> {
+    delegate: D,
+    graph: Graph,
+    viewport: Entity<ViewportState>,
+    selected: BTreeSet<NodeId>,
+    grid_size: Option<Pixels>,
+    drag: Option<Drag>,
+}
+
+impl<D> GraphEditorState<D>
+where
+    D: GraphDelegate,
+{
+    /// Create a new graph editor state with the delegate.
+    pub fn new(delegate: D, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        Self {
+            delegate,
+            graph: Graph::new(),
+            viewport: cx.new(|cx| ViewportState::new(window, cx)),
+            selected: BTreeSet::new(),
+            grid_size: None,
+            drag: None,
+        }
+    }
+
+    /// Snap the nodes to the grid when dragging, default: None
+    pub fn snap_to_grid(mut self, grid_size: impl Into<Pixels>) -> Self {
+        self.grid_size = Some(grid_size.into());
+        self
+    }
+
+    pub fn delegate(&self) -> &D {
+        &self.delegate
+    }
+
+    pub fn delegate_mut(&mut self) -> &mut D {
+        &mut self.delegate
+    }
+
+    /// Returns the state of the [`Viewport`].
+    pub fn viewport(&self) -> &Entity<ViewportState> {
+        &self.viewport
+    }
+
+    /// Returns the graph.
+    pub fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    /// Set the graph, the selection is cleared.
+    pub fn set_graph(&mut self, graph: Graph, cx: &mut Context<Self>) {
+        self.graph = graph;
+        self.selected.clear();
+        self.drag = None;
+        cx.notify();
+    }
+
+    /// Update the graph, the selection of the removed nodes is dropped.
+    pub fn update_graph<R>(
+        &mut self,
+        f: impl FnOnce(&mut Graph) -> R,
+        cx: &mut Context<Self>,
+    ) -> R {
+        let result = f(&mut self.graph);
+        let graph = &self.graph;
+        self.selected.retain(|id| graph.node(*id).is_some());
+        cx.notify();
+        result
+    }
+
+    /// Returns the selected nodes.
+    pub fn selected_nodes(&self) -> Vec<NodeId> {
+        self.selected.iter().copied().collect()
+    }
+
+    /// Set the selected nodes.
+    pub fn set_selected_nodes(
+        &mut self,
+        nodes: impl IntoIterator<Item = NodeId>,
+        cx: &mut Context<Self>,
+    ) {
+        let selected = nodes.into_iter().collect::<BTreeSet<_>>();
+        if selected != self.selected {
+            self.selected = selected;
+            cx.emit(GraphEditorEvent::Select(self.selected_nodes()));
+            cx.notify();
+        }
+    }
+
+    /// Delete the selected nodes and their edges.
+    pub fn delete_selected(&mut self, cx: &mut Context<Self>) {
+        if self.selected.is_empty() {
+            return;
+        }
+
+        let deleted = self.selected_nodes();
+        for id in deleted.iter() {
+            self.graph.remove_node(*id);
+        }
+        self.selected.clear();
+        cx.emit(GraphEditorEvent::Delete(deleted));
+        cx.emit(GraphEditorEvent::Select(vec![]));
+        cx.notify();
+    }
+
+    /// Zoom and pan to fit all the nodes in the viewport.
+    pub fn fit_to_content(&mut self, cx: &mut Context<Self>) {
+        let Some(bounds) = self.graph.bounds() else {
+            return;
+        };
+
+        self.viewport.update(cx, |viewport, cx| {
+            viewport.set_content_bounds(bounds, cx);
+            viewport.fit_to_content(px(32.), cx);
+        });
+    }
+
+    fn content_position(&self, position: Point<Pixels>, cx: &App) -> Point<Pixels> {
+        self.viewport.read(cx).content_position(position)
+    }
+
+    fn is_pan_key_pressed(&self, cx: &App) -> bool {
+        self.viewport.read(cx).is_pan_key_pressed()
+    }
+
+    fn on_background_mouse_down(
+        &mut self,
+        event: &MouseDownEvent,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.is_pan_key_pressed(cx) {
+            return;
+        }
+
+        let position = self.content_position(event.position, cx);
+        let base = if event.modifiers.shift {
+            self.selected.clone()
+        } else {
+            BTreeSet::new()
+        };
+        self.set_selected_nodes(base.clone(), cx);
+        self.drag = Some(Drag::Select {
+            start: position,
+            end: position,
+            base,
+        });
+    }
+
+    fn on_node_mouse_down(
+        &mut self,
+        id: NodeId,
+        event: &MouseDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.is_pan_key_pressed(cx) {
+            return;
+        }
+        cx.stop_propagation();
+        self.viewport.read(cx).focus_handle(cx).focus(window);
+
+        if event.modifiers.shift {
+            let mut selected = self.selected.clone();
+            if !selected.remove(&id) {
+                selected.insert(id);
+            }
+            self.set_selected_nodes(selected, cx);
+        } else if !self.selected.contains(&id) {
+            self.set_selected_nodes([id], cx);
+        }
+
+        let origins = self
+            .selected
+            .iter()
+            .filter_map(|id| Some((*id, self.graph.node(*id)?.position)))
+            .collect();
+        self.drag = Some(Drag::Nodes {
+            start: self.content_position(event.position, cx),
+            origins,
+            moved: false,
+        });
+    }
+
+    fn on_port_mouse_down(
+        &mut self,
+        port: PortRef,
+        event: &MouseDownEvent,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.is_pan_key_pressed(cx) {
+            return;
+        }
+        cx.stop_propagation();
+
+        // Drag an connected input port to detach the edge.
+        let from = match self.graph.input_edge(port) {
+            Some(edge) if port.side == PortSide::Input => {
+                self.graph.disconnect(edge);
+                cx.emit(GraphEditorEvent::Disconnect(edge));
+                edge.from
+            }
+            _ => port,
+        };
+        self.drag = Some(Drag::Connect {
+            from,
+            position: self.content_position(event.position, cx),
+        });
+        cx.notify();
+    }
+
+    fn on_port_mouse_up(
+        &mut self,
+        port: PortRef,
+        _: &MouseUpEvent,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(Drag::Connect { from, .. }) = self.drag else {
+            return;
+        };
+        self.drag = None;
+        cx.notify();
+
+        let edge = match (from.side, port.side) {
+            (PortSide::Output, PortSide::Input) => Edge { from, to: port },
+            (PortSide::Input, PortSide::Output) => Edge {
+                from: port,
+                to: from,
+            },
+            _ => return,
+        };
+        if !self
+            .delegate
+            .can_connect(&self.graph, edge.from, edge.to, cx)
+        {
+            return;
+        }
+
+        if let Some(replaced) = self.graph.connect(edge) {
+            cx.emit(GraphEditorEvent::Disconnect(replaced));
+        }
+        cx.emit(GraphEditorEvent::Connect(edge));
+    }
+
+    fn on_mouse_move(&mut self, event: &MouseMoveEvent, _: &mut Window, cx: &mut Context<Self>) {
+        let position = self.content_position(event.position, cx);
+        match self.drag.as_mut() {
+            Some(Drag::Nodes {
+                start,
+                origins,
+                moved,
+            }) => {
+                let delta = position - *start;
+                for (id, origin) in origins.iter() {
+                    if let Some(node) = self.graph.node_mut(*id) {
+                        let position = *origin + delta;
+                        node.position = match self.grid_size {
+                            Some(grid_size) => snap_to_grid(position, grid_size),
+                            None => position,
+                        };
+                    }
+                }
+                *moved = true;
+            }
+            Some(Drag::Select { start, end, base }) => {
+                *end = position;
+                let rect = Bounds::from_corners(start.min(end), start.max(end));
+                self.selected = base
+                    .iter()
+                    .copied()
+                    .chain(
+                        self.graph
+                            .nodes()
+                            .iter()
+                            .filter(|node| node.bounds().intersects(&rect))
+                            .map(|node| node.id),
+                    )
+                    .collect();
+            }
+            Some(Drag::Connect { position: p, .. }) => *p = position,
+            None => return,
+        }
+        cx.notify();
+    }
+
+    fn on_mouse_up(&mut self, _: &MouseUpEvent, _: &mut Window, cx: &mut Context<Self>) {
+        match self.drag.take() {
+            Some(Drag::Nodes {
+                origins,
+                moved: true,
+                ..
+            }) => {
+                cx.emit(GraphEditorEvent::Move(
+                    origins.into_iter().map(|(id, _)| id).collect(),
+                ));
+            }
+            Some(Drag::Select { .. }) => {
+                cx.emit(GraphEditorEvent::Select(self.selected_nodes()));
+            }
+            Some(_) => {}
+            None => return,
+        }
+        cx.notify();
+    }
+
+    fn on_action_delete(&mut self, _: &DeleteSelected, _: &mut Window, cx: &mut Context<Self>) {
+        self.delete_selected(cx);
+    }
+
+    fn on_action_select_all(
+        &mut self,
+        _: &input::SelectAll,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let all = self
+            .graph
+            .nodes()
+            .iter()
+            .map(|node| node.id)
+            .collect::<Vec<_>>();
+        self.set_selected_nodes(all, cx);
+    }
+
+    fn on_action_cancel(&mut self, _: &Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        if self.drag.take().is_some() {
+            cx.notify();
+        } else {
+            self.set_selected_nodes([], cx);
+        }
+    }
+
+    /// Returns the color of the port by its kind.
+    fn port_color(kind: &str, cx: &App) -> Hsla {
+        if kind.is_empty() {
+            return cx.theme().muted_foreground;
+        }
+
+        let mut hasher = DefaultHasher::new();
+        kind.hash(&mut hasher);
+        match hasher.finish() % 5 {
+            0 => cx.theme().chart_1,
+            1 => cx.theme().chart_2,
+            2 => cx.theme().chart_3,
+            3 => cx.theme().chart_4,
+            _ => cx.theme().chart_5,
+        }
+    }
+
+    fn render_port(
+        &self,
+        port: PortRef,
+        kind: &str,
+        node: &GraphNode,
+        transform: ViewportTransform,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let zoom = transform.zoom;
+        let center = node.port_position(port.side, port.index) - node.position;
+        let color = Self::port_color(kind, cx);
+
+        div()
+            .id(ElementId::NamedInteger(
+                match port.side {
+                    PortSide::Input => "input".into(),
+                    PortSide::Output => "output".into(),
+                },
+                port.index as u64,
+            ))
+            .absolute()
+            .left((center.x - PORT_SIZE / 2.) * zoom)
+            .top((center.y - PORT_SIZE / 2.) * zoom)
+            .size(PORT_SIZE * zoom)
+            .rounded_full()
+            .border_2()
+            .border_color(color)
+            .bg(cx.theme().background)
+            .hover(|this| this.bg(color))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, event, window, cx| {
+                    this.on_port_mouse_down(port, event, window, cx);
+                }),
+            )
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(move |this, event, window, cx| {
+                    this.on_port_mouse_up(port, event, window, cx);
+                }),
+            )
+    }
+
+    fn render_node(
+        &self,
+        node: &GraphNode,
+        transform: ViewportTransform,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let id = node.id;
+        let zoom = transform.zoom;
+        let bounds = transform.apply_bounds(node.bounds());
+        let selected = self.selected.contains(&id);
+        let rows = node.inputs.len().max(node.outputs.len());
+        let body = self.delegate.render_node_body(node, window, cx);
+
+        div()
+            .id(("node", id.0))
+            .absolute()
+            .left(bounds.origin.x)
+            .top(bounds.origin.y)
+            .w(bounds.size.width)
+            .h(bounds.size.height)
+            .text_size(px(12.) * zoom)
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, event, window, cx| {
+                    this.on_node_mouse_down(id, event, window, cx);
+                }),
+            )
+            .child(
+                v_flex()
+                    .size_full()
+                    .overflow_hidden()
+                    .bg(cx.theme().popover)
+                    .text_color(cx.theme().popover_foreground)
+                    .border_1()
+                    .border_color(if selected {
+                        cx.theme().ring
+                    } else {
+                        cx.theme().border
+                    })
+                    .rounded(cx.theme().radius * zoom)
+                    .shadow_md()
+                    .child(
+                        h_flex()
+                            .h(HEADER_HEIGHT * zoom)
+                            .px(px(8.) * zoom)
+                            .bg(cx.theme().muted)
+                            .font_semibold()
+                            .child(node.title.clone()),
+                    )
+                    .children((0..rows).map(|ix| {
+                        let label = |port: Option<&Port>| port.map(|port| port.name.clone());
+                        h_flex()
+                            .h(ROW_HEIGHT * zoom)
+                            .px(px(10.) * zoom)
+                            .justify_between()
+                            .child(div().children(label(node.inputs.get(ix))))
+                            .child(div().children(label(node.outputs.get(ix))))
+                    }))
+                    .when_some(body, |this, body| {
+                        this.child(
+                            div()
+                                .h(node.body_height * zoom)
+                                .overflow_hidden()
+                                .child(body),
+                        )
+                    }),
+            )
+            .children(node.inputs.iter().enumerate().map(|(ix, port)| {
+                self.render_port(PortRef::input(id, ix), &port.kind, node, transform, cx)
+            }))
+            .children(node.outputs.iter().enumerate().map(|(ix, port)| {
+                self.render_port(PortRef::output(id, ix), &port.kind, node, transform, cx)
+            }))
+    }
+
+    fn render_graph(
+        &mut self,
+        transform: ViewportTransform,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let edges = self
+            .graph
+            .edges()
+            .iter()
+            .filter_map(|edge| {
+                let from = self.graph.port_position(edge.from)?;
+                let to = self.graph.port_position(edge.to)?;
+                let kind = &self.graph.port(edge.from)?.kind;
+                Some((
+                    transform.apply(from),
+                    transform.apply(to),
+                    Self::port_color(kind, cx),
+                ))
+            })
+            .collect::<Vec<_>>();
+        let pending_edge = match &self.drag {
+            Some(Drag::Connect { from, position }) => {
+                self.graph.port_position(*from).map(|port_position| {
+                    let (a, b) = (transform.apply(port_position), transform.apply(*position));
+                    match from.side {
+                        PortSide::Output => (a, b),
+                        PortSide::Input => (b, a),
+                    }
+                })
+            }
+            _ => None,
+        };
+        let select_rect = match &self.drag {
+            Some(Drag::Select { start, end, .. }) => Some(Bounds::from_corners(
+                transform.apply(start.min(end)),
+                transform.apply(start.max(end)),
+            )),
+            _ => None,
+        };
+        let pending_color = cx.theme().ring;
+        let select_color = cx.theme().selection.opacity(0.3);
+        let select_border_color = cx.theme().ring;
+        let stroke_width = (px(2.) * transform.zoom).max(px(1.));
+
+        div()
+            .size_full()
+            .relative()
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(Self::on_background_mouse_down),
+            )
+            .on_mouse_move(cx.listener(Self::on_mouse_move))
+            .on_mouse_up(MouseButton::Left, cx.listener(Self::on_mouse_up))
+            .on_mouse_up_out(MouseButton::Left, cx.listener(Self::on_mouse_up))
+            .child(
+                canvas(
+                    |_, _, _| {},
+                    move |bounds, _, window, _| {
+                        let origin = bounds.origin;
+                        let paths = edges
+                            .iter()
+                            .map(|(from, to, color)| (*from, *to, *color))
+                            .chain(pending_edge.map(|(from, to)| (from, to, pending_color)));
+                        for (from, to, color) in paths {
+                            let (from, to) = (origin + from, origin + to);
+                            let (ctrl_a, ctrl_b) = edge_control_points(from, to);
+                            let mut builder = PathBuilder::stroke(stroke_width);
+                            builder.move_to(from);
+                            builder.cubic_bezier_to(to, ctrl_a, ctrl_b);
+                            if let Ok(path) = builder.build() {
+                                window.paint_path(path, color);
+                            }
+                        }
+
+                        if let Some(rect) = select_rect {
+                            let rect = Bounds::new(origin + rect.origin, rect.size);
+                            window.paint_quad(fill(rect, select_color));
+                            window.paint_quad(gpui::outline(
+                                rect,
+                                select_border_color,
+                                gpui::BorderStyle::Solid,
+                            ));
+                        }
+                    },
+                )
+                .absolute()
+                .size_full(),
+            )
+            .children(
+                self.graph
+                    .nodes()
+                    .iter()
+                    .map(|node| self.render_node(node, transform, window, cx))
+                    .collect::<Vec<_>>(),
+            )
+            .into_any_element()
+    }
+}
+
+impl<D: GraphDelegate> EventEmitter<GraphEditorEvent> for GraphEditorState<D> {}
+
+impl<D: GraphDelegate> Render for GraphEditorState<D> {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let state = cx.entity();
+        Viewport::new(&self.viewport).content(move |transform, window, cx| {
+            state.update(cx, |state, cx| state.render_graph(transform, window, cx))
+        })
+    }
+}
+
+/// A node graph editor built on the [`Viewport`], for workflow and shader graph tools.
+///
+/// - Drag the nodes to move them, drag on the background to select in a box.
+/// - Drag from a port to another port to connect them, drag a connected input port to detach.
+/// - Press `Delete` or `Backspace` to delete the selected nodes.
+///
+/// ```ignore
+/// let mut graph = Graph::new();
+/// graph.add_node(
+///     GraphNode::new("Texture")
+///         .position(point(px(0.), px(0.)))
+///         .output(Port::new("color", "vec3")),
+/// );
+///
+/// let state = cx.new(|cx| {
+///     let mut state = GraphEditorState::new(MyDelegate, window, cx).snap_to_grid(px(16.));
+///     state.set_graph(graph, cx);
+///     state
+/// });
+///
+/// GraphEditor::new(&state)
+/// ```
+#[derive(IntoElement)]
+pub struct GraphEditor<D: GraphDelegate> {
+    id: ElementId,
+    state: Entity<GraphEditorState<D>>,
+    style: StyleRefinement,
+}
+
+impl<D: GraphDelegate> GraphEditor<D> {
+    /// Create a new graph editor with the given state.
+    pub fn new(state: &Entity<GraphEditorState<D>>) -> Self {
+        Self {
+            id: ElementId::Name(format!("graph-editor-{}", state.entity_id()).into()),
+            state: state.clone(),
+            style: StyleRefinement::default(),
+        }
+    }
+}
+
+impl<D: GraphDelegate> Styled for GraphEditor<D> {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl<D: GraphDelegate> RenderOnce for GraphEditor<D> {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        div()
+            .id(self.id)
+            .key_context(CONTEXT)
+            .on_action(window.listener_for(&self.state, GraphEditorState::on_action_delete))
+            .on_action(window.listener_for(&self.state, GraphEditorState::on_action_select_all))
+            .on_action(window.listener_for(&self.state, GraphEditorState::on_action_cancel))
+            .size_full()
+            .bg(cx.theme().background)
+            .child(self.state)
+            .refine_style(&self.style)
+    }
+}
@@ -0,0 +1,513 @@
+use std::{collections::HashMap, rc::Rc};
+
+use chrono::{Datelike as _, Days, NaiveDate};
+use gpui::{
+    div, prelude::FluentBuilder as _, px, App, ElementId, Hsla, InteractiveElement as _,
+    IntoElement, ParentElement, Pixels, RenderOnce, SharedString, StatefulInteractiveElement as _,
+    StyleRefinement, Styled, Window,
+};
+use rust_i18n::t;
+
+use crate::{h_flex, tooltip::Tooltip, v_flex, ActiveTheme as _, StyledExt as _};
+
+/// A cell of the [`Heatmap`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeatmapCell {
+    pub row: usize,
+    pub column: usize,
+    pub value: Option<f64>,
+    /// The date of the cell in the calendar heatmap.
+    pub date: Option<NaiveDate>,
+    /// The label of the cell, e.g.: `2025-01-01` or `Mon, 9:00`.
+    pub label: SharedString,
+}
+
+enum HeatmapData {
+    Matrix {
+        values: Vec<Vec<Option<f64>>>,
+        row_labels: Vec<SharedString>,
+        column_labels: Vec<SharedString>,
+    },
+    Calendar {
+        start: NaiveDate,
+        end: NaiveDate,
+        values: HashMap<NaiveDate, f64>,
+    },
+}
+
+/// Returns the first day (Sunday) of the week of the date.
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Days::new(date.weekday().num_days_from_sunday() as u64)
+}
+
+/// Returns the color level of the value from 0 (empty) to `levels`.
+fn level(value: Option<f64>, min: f64, max: f64, levels: usize) -> usize {
+    let Some(value) = value else {
+        return 0;
+    };
+    if value <= min || levels == 0 {
+        return 0;
+    }
+    if max <= min {
+        return levels;
+    }
+
+    let ratio = ((value - min) / (max - min)).min(1.);
+    ((ratio * levels as f64).ceil() as usize).clamp(1, levels)
+}
+
+/// A heatmap to show the values in a grid with a color scale, like a GitHub-style contribution
+/// calendar, or a generic matrix.
+///
+/// ```ignore
+/// // A contribution calendar of a year.
+/// Heatmap::calendar("contributions", start, end, contributions)
+///     .on_click(|cell, _, _| println!("{:?}: {:?}", cell.date, cell.value))
+///
+/// // A matrix with labels.
+/// Heatmap::matrix("activity", values)
+///     .row_labels(["Mon", "Tue", "Wed"])
+///     .column_labels(["9:00", "12:00", "15:00"])
+/// ```
+#[derive(IntoElement)]
+pub struct Heatmap {
+    id: ElementId,
+    style: StyleRefinement,
+    data: HeatmapData,
+    color: Option<Hsla>,
+    levels: usize,
+    range: Option<(f64, f64)>,
+    cell_size: Pixels,
+    gap: Pixels,
+    legend: bool,
+    tooltip: Option<Rc<dyn Fn(&HeatmapCell) -> SharedString>>,
+    on_click: Option<Rc<dyn Fn(&HeatmapCell, &mut Window, &mut App)>>,
+}
+
+impl Heatmap {
+    fn new(id: impl Into<ElementId>, data: HeatmapData) -> Self {
+        Self {
+            id: id.into(),
+            style: StyleRefinement::default(),
+            data,
+            color: None,
+            levels: 4,
+            range: None,
+            cell_size: px(12.),
+            gap: px(3.),
+            legend: false,
+            tooltip: None,
+            on_click: None,
+        }
+    }
+
+    /// Create a heatmap of a matrix, the `values` are the rows.
+    pub fn matrix(id: impl Into<ElementId>, values: Vec<Vec<Option<f64>>>) -> Self {
+        Self::new(
+            id,
+            HeatmapData::Matrix {
+                values,
+                row_labels: vec![],
+                column_labels: vec![],
+            },
+        )
+    }
+
+    /// Create a contribution calendar from `start` to `end`, the columns are the weeks,
+    /// and the rows are the days from Sunday to Saturday.
+    ///
+    /// The legend is shown by default.
+    pub fn calendar(
+        id: impl Into<ElementId>,
+        start: NaiveDate,
+        end: NaiveDate,
+        values: impl IntoIterator<Item = (NaiveDate, f64)>,
+    ) -> Self {
+        let mut counts = HashMap::new();
+        for (date, value) in values {
+            *counts.entry(date).or_insert(0.) += value;
+        }
+
+        let mut this = Self::new(
+            id,
+            HeatmapData::Calendar {
+                start: start.min(end),
+                end: end.max(start),
+                values: counts,
+            },
+        );
+        this.legend = true;
+        this
+    }
+
+    /// Set the labels of the rows of the matrix.
+    pub fn row_labels(mut self, labels: impl IntoIterator<Item = impl Into<SharedString>>) -> Self {
+        if let HeatmapData::Matrix { row_labels, .. } = &mut self.data {
+            *row_labels = labels.into_iter().map(Into::into).collect();
+        }
+        self
+    }
+
+    /// Set the labels of the columns of the matrix.
+    pub fn column_labels(
+        mut self,
+        labels: impl IntoIterator<Item = impl Into<SharedString>>,
+    ) -> Self {
+        if let HeatmapData::Matrix { column_labels, .. } = &mut self.data {
+            *column_labels = labels.into_iter().map(Into::into).collect();
+        }
+        self
+    }
+
+    /// Set the base color of the scale, default is the `chart_2` of the theme.
+    pub fn color(mut self, color: impl Into<Hsla>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Set the number of the color levels, default: 4
+    pub fn levels(mut self, levels: usize) -> Self {
+        self.levels = levels.max(1);
+        self
+    }
+
+    /// Set the range of the values, default is from 0 to the max value.
+    ///
+    /// The values less than or equal to the `min` are shown as empty.
+    pub fn range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Set the size of the cells, default: 12px
+    pub fn cell_size(mut self, size: impl Into<Pixels>) -> Self {
+        self.cell_size = size.into();
+        self
+    }
+
+    /// Set the gap between the cells, default: 3px
+    pub fn gap(mut self, gap: impl Into<Pixels>) -> Self {
+        self.gap = gap.into();
+        self
+    }
+
+    /// Set true to show the legend of the color scale.
+    pub fn legend(mut self, legend: bool) -> Self {
+        self.legend = legend;
+        self
+    }
+
+    /// Set the text of the tooltip of the cells, default is the label and the value.
+    pub fn tooltip(mut self, f: impl Fn(&HeatmapCell) -> SharedString + 'static) -> Self {
+        self.tooltip = Some(Rc::new(f));
+        self
+    }
+
+    /// Set the click handler of the cells.
+    pub fn on_click(
+        mut self,
+        handler: impl Fn(&HeatmapCell, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_click = Some(Rc::new(handler));
+        self
+    }
+
+    /// Returns the cells in rows, None for the cells out of the calendar range.
+    fn cells(&self) -> Vec<Vec<Option<HeatmapCell>>> {
+        match &self.data {
+            HeatmapData::Matrix {
+                values, row_labels, ..
+            } => values
+                .iter()
+                .enumerate()
+                .map(|(row, values)| {
+                    values
+                        .iter()
+                        .enumerate()
+                        .map(|(column, value)| {
+                            let label = match (row_labels.get(row), self.column_label(column)) {
+                                (Some(row_label), Some(column_label)) => {
+                                    format!("{}, {}", row_label, column_label).into()
+                                }
+                                _ => format!("{}, {}", row + 1, column + 1).into(),
+                            };
+                            Some(HeatmapCell {
+                                row,
+                                column,
+                                value: *value,
+                                date: None,
+                                label,
+                            })
+                        })
+                        .collect()
+                })
+                .collect(),
+            HeatmapData::Calendar { start, end, values } => {
+                let first = week_start(*start);
+                let columns = (*end - first).num_days() as usize / 7 + 1;
+                (0..7)
+                    .map(|row| {
+                        (0..columns)
+                            .map(|column| {
+                                let date = first + Days::new((column * 7 + row) as u64);
+                                if date < *start || date > *end {
+                                    return None;
+                                }
+                                Some(HeatmapCell {
+                                    row,
+                                    column,
+                                    value: Some(values.get(&date).copied().unwrap_or(0.)),
+                                    date: Some(date),
+                                    label: date.format("%Y-%m-%d").to_string().into(),
+                                })
+                            })
+                            .collect()
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    fn column_label(&self, column: usize) -> Option<SharedString> {
+        match &self.data {
+            HeatmapData::Matrix { column_labels, .. } => column_labels.get(column).cloned(),
+            HeatmapData::Calendar { start, .. } => {
+                // Label the month at the week that it starts.
+                let first = week_start(*start);
+                let month =
+                    |column: usize| (first + Days::new(column as u64 * 7)).max(*start).month();
+                if column > 0 && month(column - 1) == month(column) {
+                    return None;
+                }
+                Some(month_name(month(column)))
+            }
+        }
+    }
+
+    fn row_label(&self, row: usize) -> Option<SharedString> {
+        match &self.data {
+            HeatmapData::Matrix { row_labels, .. } => row_labels.get(row).cloned(),
+            HeatmapData::Calendar { .. } => match row {
+                1 => Some(t!("Calendar.week.1").into()),
+                3 => Some(t!("Calendar.week.3").into()),
+                5 => Some(t!("Calendar.week.5").into()),
+                _ => None,
+            },
+        }
+    }
+
+    fn has_column_labels(&self) -> bool {
+        match &self.data {
+            HeatmapData::Matrix { column_labels, .. } => !column_labels.is_empty(),
+            HeatmapData::Calendar { .. } => true,
+        }
+    }
+
+    fn has_row_labels(&self) -> bool {
+        match &self.data {
+            HeatmapData::Matrix { row_labels, .. } => !row_labels.is_empty(),
+            HeatmapData::Calendar { .. } => true,
+        }
+    }
+}
+
+/// Returns the short name of the month.
+fn month_name(month: u32) -> SharedString {
+    let name = match month {
+        1 => t!("Calendar.month.January"),
+        2 => t!("Calendar.month.February"),
+        3 => t!("Calendar.month.March"),
+        4 => t!("Calendar.month.April"),
+        5 => t!("Calendar.month.May"),
+        6 => t!("Calendar.month.June"),
+        7 => t!("Calendar.month.July"),
+        8 => t!("Calendar.month.August"),
+        9 => t!("Calendar.month.September"),
+        10 => t!("Calendar.month.October"),
+        11 => t!("Calendar.month.November"),
+        _ => t!("Calendar.month.December"),
+    };
+    name.chars().take(3).collect::<String>().into()
+}
+
+impl Styled for Heatmap {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for Heatmap {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let cells = self.cells();
+        let columns = cells.iter().map(Vec::len).max().unwrap_or(0);
+        let (min, max) = self.range.unwrap_or_else(|| {
+            let max = cells
+                .iter()
+                .flatten()
+                .flatten()
+                .filter_map(|cell| cell.value)
+                .fold(0., f64::max);
+            (0., max)
+        });
+
+        let base_color = self.color.unwrap_or(cx.theme().chart_2);
+        let empty_color = cx.theme().muted;
+        let levels = self.levels;
+        let level_color = move |level: usize| -> Hsla {
+            if level == 0 {
+                empty_color
+            } else {
+                base_color.opacity(0.25 + 0.75 * (level - 1) as f32 / (levels.max(2) - 1) as f32)
+            }
+        };
+
+        let cell_size = self.cell_size;
+        let gap = self.gap;
+        let label_width = px(32.);
+        let has_row_labels = self.has_row_labels();
+        let row_labels = (0..cells.len())
+            .map(|row| self.row_label(row))
+            .collect::<Vec<_>>();
+        let column_labels = self.has_column_labels().then(|| {
+            (0..columns)
+                .map(|c| self.column_label(c))
+                .collect::<Vec<_>>()
+        });
+
+        v_flex()
+            .id(self.id)
+            .gap(gap)
+            .text_xs()
+            .text_color(cx.theme().muted_foreground)
+            .refine_style(&self.style)
+            .when_some(column_labels, |this, labels| {
+                this.child(
+                    h_flex()
+                        .gap(gap)
+                        .when(has_row_labels, |this| {
+                            this.child(div().flex_shrink_0().w(label_width))
+                        })
+                        .children(labels.into_iter().map(|label| {
+                            div()
+                                .flex_shrink_0()
+                                .w(cell_size)
+                                .whitespace_nowrap()
+                                .children(label)
+                        })),
+                )
+            })
+            .children(cells.into_iter().zip(row_labels).enumerate().map(
+                |(row, (cells, row_label))| {
+                    h_flex()
+                        .gap(gap)
+                        .when(has_row_labels, |this| {
+                            this.child(
+                                div()
+                                    .flex_shrink_0()
+                                    .w(label_width)
+                                    .overflow_hidden()
+                                    .whitespace_nowrap()
+                                    .children(row_label),
+                            )
+                        })
+                        .children(cells.into_iter().enumerate().map(|(column, cell)| {
+                            let Some(cell) = cell else {
+                                return div().flex_shrink_0().size(cell_size).into_any_element();
+                            };
+
+                            let color = level_color(level(cell.value, min, max, levels));
+                            let tooltip = match &self.tooltip {
+                                Some(tooltip) => tooltip(&cell),
+                                None => match cell.value {
+                                    Some(value) => format!("{}: {}", cell.label, value).into(),
+                                    None => cell.label.clone(),
+                                },
+                            };
+                            let on_click = self.on_click.clone();
+
+                            div()
+                                .id(("cell", row * columns + column))
+                                .flex_shrink_0()
+                                .size(cell_size)
+                                .rounded(px(2.))
+                                .bg(color)
+                                .tooltip(move |window, cx| {
+                                    Tooltip::new(tooltip.clone()).build(window, cx)
+                                })
+                                .when_some(on_click, |this, on_click| {
+                                    this.cursor_pointer()
+                                        .hover(|this| this.border_1().border_color(cx.theme().ring))
+                                        .on_click(move |_, window, cx| on_click(&cell, window, cx))
+                                })
+                                .into_any_element()
+                        }))
+                },
+            ))
+            .when(self.legend, |this| {
+                this.child(
+                    h_flex()
+                        .mt_1()
+                        .gap_1()
+                        .justify_end()
+                        .child(t!("Heatmap.Less").to_string())
+                        .children((0..=levels).map(|level| {
+                            div()
+                                .flex_shrink_0()
+                                .size(cell_size)
+                                .rounded(px(2.))
+                                .bg(level_color(level))
+                        }))
+                        .child(t!("Heatmap.More").to_string()),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::{level, week_start, Heatmap};
+
+    #[test]
+    fn test_level() {
+        assert_eq!(level(None, 0., 10., 4), 0);
+        assert_eq!(level(Some(0.), 0., 10., 4), 0);
+        assert_eq!(level(Some(1.), 0., 10., 4), 1);
+        assert_eq!(level(Some(5.), 0., 10., 4), 2);
+        assert_eq!(level(Some(10.), 0., 10., 4), 4);
+        assert_eq!(level(Some(20.), 0., 10., 4), 4);
+        assert_eq!(level(Some(3.), 0., 0., 4), 4);
+    }
+
+    #[test]
+    fn test_calendar_cells() {
+        let date = |m, d| NaiveDate::from_ymd_opt(2025, m, d).unwrap();
+        // 2025-01-01 is a Wednesday.
+        assert_eq!(
+            week_start(date(1, 1)),
+            NaiveDate::from_ymd_opt(2024, 12, 29).unwrap()
+        );
+
+        let heatmap = Heatmap::calendar(
+            "calendar",
+            date(1, 1),
+            date(1, 31),
+            [(date(1, 1), 2.), (date(1, 1), 1.), (date(1, 4), 5.)],
+        );
+        let cells = heatmap.cells();
+        assert_eq!(cells.len(), 7);
+        assert_eq!(cells[0].len(), 5);
+        // Out of the range.
+        assert_eq!(cells[0][0], None);
+
+        let cell = cells[3][0].as_ref().unwrap();
+        assert_eq!(cell.date, Some(date(1, 1)));
+        assert_eq!(cell.value, Some(3.));
+        assert_eq!(cells[6][0].as_ref().unwrap().value, Some(5.));
+        assert_eq!(cells[0][1].as_ref().unwrap().value, Some(0.));
+
+        assert!(heatmap.column_label(0).is_some());
+        assert!(heatmap.column_label(1).is_none());
+    }
+}
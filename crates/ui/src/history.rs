@@ -30,6 +30,8 @@ pub struct History<I: HistoryItem> {
     max_undos: usize,
     group_interval: Option<Duration>,
     grouping: bool,
+    /// Force the next change to start a new version.
+    separate: bool,
     unique: bool,
 }
 
@@ -47,6 +49,7 @@ where
             max_undos: 1000,
             group_interval: None,
             grouping: false,
+            separate: false,
             unique: false,
         }
     }
@@ -80,12 +83,20 @@ where
         self.grouping = false;
     }
 
+    /// Start a new version for the next change, even it is within the group interval.
+    pub fn separate(&mut self) {
+        self.separate = true;
+    }
+
     /// Increment the version number if the last change was made more than `GROUP_INTERVAL` milliseconds ago.
     fn inc_version(&mut self) -> usize {
         let t = Instant::now();
-        if !self.grouping && Some(self.last_changed_at.elapsed()) > self.group_interval {
+        if !self.grouping
+            && (self.separate || Some(self.last_changed_at.elapsed()) > self.group_interval)
+        {
             self.version += 1;
         }
+        self.separate = false;
 
         self.last_changed_at = t;
         self.version
@@ -302,4 +313,20 @@ mod tests {
         assert_eq!(history.undos().len(), 0);
         assert_eq!(history.redos().len(), 4);
     }
+
+    #[test]
+    fn test_separate_history() {
+        let mut history: History<TabIndex> =
+            History::new().group_interval(std::time::Duration::from_secs(60));
+
+        history.push(0.into());
+        history.push(1.into());
+        assert_eq!(history.version(), 0);
+
+        history.separate();
+        history.push(2.into());
+        history.push(3.into());
+        assert_eq!(history.version(), 1);
+        assert_eq!(history.undo().map(|changes| changes.len()), Some(2));
+    }
 }
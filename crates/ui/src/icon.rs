@@ -15,6 +15,7 @@ pub enum IconName {
     ArrowUp,
     Asterisk,
     Bell,
+    Bold,
     BookOpen,
     Bot,
     Building2,
@@ -47,12 +48,19 @@ pub enum IconName {
     GalleryVerticalEnd,
     GitHub,
     Globe,
+    Heading1,
+    Heading2,
     Heart,
     HeartOff,
     Inbox,
     Info,
     Inspector,
+    Italic,
     LayoutDashboard,
+    Link,
+    List,
+    ListFilter,
+    ListOrdered,
     Loader,
     LoaderCircle,
     Map,
@@ -70,6 +78,8 @@ pub enum IconName {
     PanelRight,
     PanelRightClose,
     PanelRightOpen,
+    Pause,
+    Play,
     Plus,
     Replace,
     ResizeCorner,
@@ -85,6 +95,7 @@ pub enum IconName {
     ThumbsDown,
     ThumbsUp,
     TriangleAlert,
+    Underline,
     User,
     WindowClose,
     WindowMaximize,
@@ -102,6 +113,7 @@ impl IconName {
             Self::ArrowUp => "icons/arrow-up.svg",
             Self::Asterisk => "icons/asterisk.svg",
             Self::Bell => "icons/bell.svg",
+            Self::Bold => "icons/bold.svg",
             Self::BookOpen => "icons/book-open.svg",
             Self::Bot => "icons/bot.svg",
             Self::Building2 => "icons/building-2.svg",
@@ -134,12 +146,19 @@ impl IconName {
             Self::GalleryVerticalEnd => "icons/gallery-vertical-end.svg",
             Self::GitHub => "icons/github.svg",
             Self::Globe => "icons/globe.svg",
+            Self::Heading1 => "icons/heading-1.svg",
+            Self::Heading2 => "icons/heading-2.svg",
             Self::Heart => "icons/heart.svg",
             Self::HeartOff => "icons/heart-off.svg",
             Self::Inbox => "icons/inbox.svg",
             Self::Info => "icons/info.svg",
             Self::Inspector => "icons/inspector.svg",
+            Self::Italic => "icons/italic.svg",
             Self::LayoutDashboard => "icons/layout-dashboard.svg",
+            Self::Link => "icons/link.svg",
+            Self::List => "icons/list.svg",
+            Self::ListFilter => "icons/list-filter.svg",
+            Self::ListOrdered => "icons/list-ordered.svg",
             Self::Loader => "icons/loader.svg",
             Self::LoaderCircle => "icons/loader-circle.svg",
             Self::Map => "icons/map.svg",
@@ -157,6 +176,8 @@ impl IconName {
             Self::PanelRight => "icons/panel-right.svg",
             Self::PanelRightClose => "icons/panel-right-close.svg",
             Self::PanelRightOpen => "icons/panel-right-open.svg",
+            Self::Pause => "icons/pause.svg",
+            Self::Play => "icons/play.svg",
             Self::Plus => "icons/plus.svg",
             Self::Replace => "icons/replace.svg",
             Self::ResizeCorner => "icons/resize-corner.svg",
@@ -172,6 +193,7 @@ impl IconName {
             Self::ThumbsDown => "icons/thumbs-down.svg",
             Self::ThumbsUp => "icons/thumbs-up.svg",
             Self::TriangleAlert => "icons/triangle-alert.svg",
+            Self::Underline => "icons/underline.svg",
             Self::User => "icons/user.svg",
             Self::WindowClose => "icons/window-close.svg",
             Self::WindowMaximize => "icons/window-maximize.svg",
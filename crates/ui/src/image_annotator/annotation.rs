@@ -0,0 +1,290 @@
+use gpui::{point, px, Bounds, Pixels, Point, SharedString};
+use serde::{Deserialize, Serialize};
+
+/// A point in the image pixel coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct ImagePoint {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl ImagePoint {
+    pub fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    fn distance(&self, other: &Self) -> f32 {
+        ((self.x - other.x).powi(2) + (self.y - other.y).powi(2)).sqrt()
+    }
+}
+
+impl From<Point<Pixels>> for ImagePoint {
+    fn from(p: Point<Pixels>) -> Self {
+        Self::new(p.x.into(), p.y.into())
+    }
+}
+
+impl From<ImagePoint> for Point<Pixels> {
+    fn from(p: ImagePoint) -> Self {
+        point(px(p.x), px(p.y))
+    }
+}
+
+/// The shape of an [`Annotation`], in the image pixel coordinates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Shape {
+    Rect { min: ImagePoint, max: ImagePoint },
+    Polygon { points: Vec<ImagePoint> },
+    Point { point: ImagePoint },
+}
+
+impl Shape {
+    /// Create a rectangle from any two opposite corners.
+    pub fn rect(a: ImagePoint, b: ImagePoint) -> Self {
+        Self::Rect {
+            min: ImagePoint::new(a.x.min(b.x), a.y.min(b.y)),
+            max: ImagePoint::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    /// Returns the vertices, which have the handles to resize the shape.
+    ///
+    /// The corners of a rectangle are in the order of top-left, top-right, bottom-right, bottom-left.
+    pub fn vertices(&self) -> Vec<ImagePoint> {
+        match self {
+            Self::Rect { min, max } => vec![
+                *min,
+                ImagePoint::new(max.x, min.y),
+                *max,
+                ImagePoint::new(min.x, max.y),
+            ],
+            Self::Polygon { points } => points.clone(),
+            Self::Point { point } => vec![*point],
+        }
+    }
+
+    /// Move the vertex at the index to the position.
+    ///
+    /// The opposite corner of a rectangle is kept.
+    pub fn set_vertex(&mut self, ix: usize, position: ImagePoint) {
+        match self {
+            Self::Rect { .. } => {
+                let vertices = self.vertices();
+                if let Some(opposite) = vertices.get((ix + 2) % 4) {
+                    *self = Self::rect(*opposite, position);
+                }
+            }
+            Self::Polygon { points } => {
+                if let Some(p) = points.get_mut(ix) {
+                    *p = position;
+                }
+            }
+            Self::Point { point } => *point = position,
+        }
+    }
+
+    /// Move the shape by the delta.
+    pub fn translate(&mut self, dx: f32, dy: f32) {
+        let offset = |p: &mut ImagePoint| {
+            p.x += dx;
+            p.y += dy;
+        };
+        match self {
+            Self::Rect { min, max } => {
+                offset(min);
+                offset(max);
+            }
+            Self::Polygon { points } => points.iter_mut().for_each(offset),
+            Self::Point { point } => offset(point),
+        }
+    }
+
+    /// Returns the bounds of the shape.
+    pub fn bounds(&self) -> Bounds<Pixels> {
+        let vertices = self.vertices();
+        let Some(first) = vertices.first() else {
+            return Bounds::default();
+        };
+
+        let (mut min, mut max) = (*first, *first);
+        for p in vertices.iter().skip(1) {
+            min = ImagePoint::new(min.x.min(p.x), min.y.min(p.y));
+            max = ImagePoint::new(max.x.max(p.x), max.y.max(p.y));
+        }
+        Bounds::from_corners(min.into(), max.into())
+    }
+
+    /// Returns true if the position is in the shape, or within the `tolerance` to its outline.
+    pub fn contains(&self, position: ImagePoint, tolerance: f32) -> bool {
+        match self {
+            Self::Rect { min, max } => {
+                position.x >= min.x - tolerance
+                    && position.x <= max.x + tolerance
+                    && position.y >= min.y - tolerance
+                    && position.y <= max.y + tolerance
+            }
+            Self::Polygon { points } => {
+                polygon_contains(points, position)
+                    || points.iter().enumerate().any(|(ix, a)| {
+                        let b = &points[(ix + 1) % points.len()];
+                        segment_distance(position, *a, *b) <= tolerance
+                    })
+            }
+            Self::Point { point } => point.distance(&position) <= tolerance,
+        }
+    }
+}
+
+/// Returns true if the point is inside the polygon, by the even-odd rule.
+fn polygon_contains(points: &[ImagePoint], p: ImagePoint) -> bool {
+    let mut inside = false;
+    for (ix, a) in points.iter().enumerate() {
+        let b = &points[(ix + points.len() - 1) % points.len()];
+        if (a.y > p.y) != (b.y > p.y) && p.x < (b.x - a.x) * (p.y - a.y) / (b.y - a.y) + a.x {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Returns the distance from the point to the segment `a` to `b`.
+fn segment_distance(p: ImagePoint, a: ImagePoint, b: ImagePoint) -> f32 {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let len = dx * dx + dy * dy;
+    if len == 0. {
+        return p.distance(&a);
+    }
+
+    let t = (((p.x - a.x) * dx + (p.y - a.y) * dy) / len).clamp(0., 1.);
+    p.distance(&ImagePoint::new(a.x + t * dx, a.y + t * dy))
+}
+
+/// A labeled shape on the image.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    pub label: SharedString,
+    pub shape: Shape,
+}
+
+impl Annotation {
+    pub fn new(label: impl Into<SharedString>, shape: Shape) -> Self {
+        Self {
+            label: label.into(),
+            shape,
+        }
+    }
+}
+
+/// The result of the hit testing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Hit {
+    /// The vertex handle of the annotation.
+    Vertex(usize, usize),
+    /// The annotation.
+    Shape(usize),
+}
+
+/// Hit test the annotations at the position, the `tolerance` is in the image pixels.
+///
+/// The vertices of the selected annotation are tested first, then the annotations from top to bottom.
+pub(super) fn hit_test(
+    annotations: &[Annotation],
+    selected: Option<usize>,
+    position: ImagePoint,
+    tolerance: f32,
+) -> Option<Hit> {
+    if let Some(ix) = selected {
+        if let Some(annotation) = annotations.get(ix) {
+            let vertex = annotation
+                .shape
+                .vertices()
+                .iter()
+                .position(|p| p.distance(&position) <= tolerance);
+            if let Some(vertex) = vertex {
+                return Some(Hit::Vertex(ix, vertex));
+            }
+        }
+    }
+
+    annotations
+        .iter()
+        .rposition(|annotation| annotation.shape.contains(position, tolerance))
+        .map(Hit::Shape)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(x: f32, y: f32) -> ImagePoint {
+        ImagePoint::new(x, y)
+    }
+
+    #[test]
+    fn test_shape() {
+        let mut rect = Shape::rect(p(10., 20.), p(0., 0.));
+        assert_eq!(
+            rect,
+            Shape::Rect {
+                min: p(0., 0.),
+                max: p(10., 20.)
+            }
+        );
+
+        // Drag the top-left corner over the bottom-right one.
+        rect.set_vertex(0, p(15., 25.));
+        assert_eq!(rect, Shape::rect(p(10., 20.), p(15., 25.)));
+        assert!(rect.contains(p(12., 22.), 0.));
+        assert!(!rect.contains(p(9., 22.), 0.));
+        assert!(rect.contains(p(9., 22.), 1.));
+
+        let triangle = Shape::Polygon {
+            points: vec![p(0., 0.), p(10., 0.), p(0., 10.)],
+        };
+        assert!(triangle.contains(p(2., 2.), 0.));
+        assert!(!triangle.contains(p(8., 8.), 0.));
+        assert!(triangle.contains(p(5.5, 5.5), 1.));
+        assert_eq!(
+            triangle.bounds(),
+            Bounds::from_corners(point(px(0.), px(0.)), point(px(10.), px(10.)))
+        );
+    }
+
+    #[test]
+    fn test_hit_test() {
+        let annotations = vec![
+            Annotation::new("a", Shape::rect(p(0., 0.), p(100., 100.))),
+            Annotation::new("b", Shape::Point { point: p(50., 50.) }),
+        ];
+
+        assert_eq!(
+            hit_test(&annotations, None, p(51., 50.), 2.),
+            Some(Hit::Shape(1))
+        );
+        assert_eq!(
+            hit_test(&annotations, None, p(10., 10.), 2.),
+            Some(Hit::Shape(0))
+        );
+        assert_eq!(
+            hit_test(&annotations, Some(0), p(99., 101.), 2.),
+            Some(Hit::Vertex(0, 2))
+        );
+        // A smaller tolerance when zoomed in.
+        assert_eq!(hit_test(&annotations, None, p(103., 50.), 2.), None);
+    }
+
+    #[test]
+    fn test_serialize() {
+        let annotations = vec![Annotation::new("cat", Shape::Point { point: p(1., 2.) })];
+        let json = serde_json::to_string(&annotations).unwrap();
+        assert_eq!(
+            json,
+            r#"[{"label":"cat","shape":{"type":"point","point":{"x":1.0,"y":2.0}}}]"#
+        );
+        assert_eq!(
+            serde_json::from_str::<Vec<Annotation>>(&json).unwrap(),
+            annotations
+        );
+    }
+}
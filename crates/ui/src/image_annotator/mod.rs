@@ -0,0 +1,651 @@
+mod annotation;
+
+use std::hash::{DefaultHasher, Hash as _, Hasher as _};
+
+use gpui::{
+    actions, canvas, div, fill, img, prelude::FluentBuilder as _, px, size, AnyElement, App,
+    AppContext as _, Bounds, Context, ElementId, Entity, EventEmitter, Focusable as _, Hsla,
+    ImageSource, InteractiveElement as _, IntoElement, KeyBinding, MouseButton, MouseDownEvent,
+    MouseMoveEvent, MouseUpEvent, ParentElement, PathBuilder, Pixels, Point, Render, RenderOnce,
+    SharedString, Size, StyleRefinement, Styled, Window,
+};
+
+use crate::{
+    actions::{Cancel, Confirm},
+    viewport::{Viewport, ViewportState, ViewportTransform},
+    ActiveTheme, StyledExt as _,
+};
+
+pub use annotation::*;
+
+actions!(image_annotator, [DeleteSelected]);
+
+const CONTEXT: &str = "ImageAnnotator";
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("backspace", DeleteSelected, Some(CONTEXT)),
+        KeyBinding::new("delete", DeleteSelected, Some(CONTEXT)),
+        KeyBinding::new("enter", Confirm { secondary: false }, Some(CONTEXT)),
+        KeyBinding::new("escape", Cancel, Some(CONTEXT)),
+    ]);
+}
+
+/// The size of the vertex handles and the hit testing tolerance, in the screen pixels.
+const HANDLE_SIZE: Pixels = px(8.);
+const POINT_SIZE: Pixels = px(10.);
+
+/// The tool to draw on the [`ImageAnnotator`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnnotationTool {
+    /// Select, move and resize the annotations.
+    #[default]
+    Select,
+    /// Drag to draw a rectangle.
+    Rect,
+    /// Click to add the vertices, click the first vertex, double click or press `Enter` to finish.
+    Polygon,
+    /// Click to add a point.
+    Point,
+}
+
+/// Events emitted by the [`ImageAnnotatorState`].
+#[derive(Debug, Clone)]
+pub enum ImageAnnotatorEvent {
+    /// An annotation was drawn at the index.
+    Add(usize),
+    /// The annotation at the index was moved or resized.
+    Change(usize),
+    /// The selected annotation was changed.
+    Select(Option<usize>),
+    /// The annotation was deleted.
+    Delete(Annotation),
+}
+
+enum Drag {
+    /// Move the annotation from its origin shape.
+    Move {
+        ix: usize,
+        start: ImagePoint,
+        origin: Shape,
+        moved: bool,
+    },
+    /// Move a vertex of the annotation.
+    Vertex {
+        ix: usize,
+        vertex: usize,
+        moved: bool,
+    },
+    /// Draw a rectangle.
+    Rect { start: ImagePoint, end: ImagePoint },
+}
+
+/// State of the [`ImageAnnotator`].
+pub struct ImageAnnotatorState {
+    source: ImageSource,
+    image_size: Size<Pixels>,
+    viewport: Entity<ViewportState>,
+    annotations: Vec<Annotation>,
+    selected: Option<usize>,
+    tool: AnnotationTool,
+    default_label: SharedString,
+    drag: Option<Drag>,
+    /// The vertices of the polygon being drawn.
+    draft: Vec<ImagePoint>,
+    /// The mouse position in the image, for the preview of the polygon being drawn.
+    mouse_position: Option<ImagePoint>,
+}
+
+impl ImageAnnotatorState {
+    /// Create a new image annotator state with the image and its size in pixels.
+    pub fn new(
+        source: impl Into<ImageSource>,
+        image_size: Size<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let viewport = cx.new(|cx| ViewportState::new(window, cx));
+        viewport.update(cx, |viewport, cx| {
+            viewport.set_content_bounds(Bounds::new(Point::default(), image_size), cx);
+        });
+
+        Self {
+            source: source.into(),
+            image_size,
+            viewport,
+            annotations: vec![],
+            selected: None,
+            tool: AnnotationTool::default(),
+            default_label: SharedString::default(),
+            drag: None,
+            draft: vec![],
+            mouse_position: None,
+        }
+    }
+
+    /// Set the label of the new annotations, default is empty.
+    pub fn default_label(mut self, label: impl Into<SharedString>) -> Self {
+        self.default_label = label.into();
+        self
+    }
+
+    /// Returns the state of the [`Viewport`].
+    pub fn viewport(&self) -> &Entity<ViewportState> {
+        &self.viewport
+    }
+
+    /// Returns the current tool.
+    pub fn tool(&self) -> AnnotationTool {
+        self.tool
+    }
+
+    /// Set the current tool, the polygon being drawn is discarded.
+    pub fn set_tool(&mut self, tool: AnnotationTool, cx: &mut Context<Self>) {
+        self.tool = tool;
+        self.drag = None;
+        self.draft.clear();
+        cx.notify();
+    }
+
+    /// Returns the annotations, to export them.
+    pub fn annotations(&self) -> &[Annotation] {
+        &self.annotations
+    }
+
+    /// Set the annotations, to import them, the selection is cleared.
+    pub fn set_annotations(&mut self, annotations: Vec<Annotation>, cx: &mut Context<Self>) {
+        self.annotations = annotations;
+        self.drag = None;
+        self.draft.clear();
+        self.select_annotation(None, cx);
+        cx.notify();
+    }
+
+    /// Export the annotations as JSON.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(&self.annotations)
+    }
+
+    /// Import the annotations from JSON, the existing annotations are replaced.
+    pub fn load_json(&mut self, json: &str, cx: &mut Context<Self>) -> serde_json::Result<()> {
+        let annotations = serde_json::from_str(json)?;
+        self.set_annotations(annotations, cx);
+        Ok(())
+    }
+
+    /// Add an annotation and returns its index.
+    pub fn add_annotation(&mut self, annotation: Annotation, cx: &mut Context<Self>) -> usize {
+        self.annotations.push(annotation);
+        let ix = self.annotations.len() - 1;
+        cx.emit(ImageAnnotatorEvent::Add(ix));
+        cx.notify();
+        ix
+    }
+
+    /// Set the label of the annotation at the index.
+    pub fn set_label(&mut self, ix: usize, label: impl Into<SharedString>, cx: &mut Context<Self>) {
+        if let Some(annotation) = self.annotations.get_mut(ix) {
+            annotation.label = label.into();
+            cx.emit(ImageAnnotatorEvent::Change(ix));
+            cx.notify();
+        }
+    }
+
+    /// Remove the annotation at the index.
+    pub fn remove_annotation(&mut self, ix: usize, cx: &mut Context<Self>) -> Option<Annotation> {
+        if ix >= self.annotations.len() {
+            return None;
+        }
+
+        let annotation = self.annotations.remove(ix);
+        self.drag = None;
+        match self.selected {
+            Some(selected) if selected == ix => self.select_annotation(None, cx),
+            Some(selected) if selected > ix => self.selected = Some(selected - 1),
+            _ => {}
+        }
+        cx.emit(ImageAnnotatorEvent::Delete(annotation.clone()));
+        cx.notify();
+        Some(annotation)
+    }
+
+    /// Returns the index of the selected annotation.
+    pub fn selected_annotation(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Select the annotation at the index, None to clear the selection.
+    pub fn select_annotation(&mut self, ix: Option<usize>, cx: &mut Context<Self>) {
+        let ix = ix.filter(|ix| *ix < self.annotations.len());
+        if ix != self.selected {
+            self.selected = ix;
+            cx.emit(ImageAnnotatorEvent::Select(ix));
+            cx.notify();
+        }
+    }
+
+    /// Delete the selected annotation.
+    pub fn delete_selected(&mut self, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected {
+            self.remove_annotation(ix, cx);
+        }
+    }
+
+    /// Zoom and pan to fit the image in the viewport.
+    pub fn fit_to_image(&mut self, cx: &mut Context<Self>) {
+        self.viewport.update(cx, |viewport, cx| {
+            viewport.fit_to_content(px(16.), cx);
+        });
+    }
+
+    /// Returns the position in the image, clamped to the image bounds.
+    fn image_position(&self, position: Point<Pixels>, cx: &App) -> ImagePoint {
+        let p = self.viewport.read(cx).content_position(position);
+        ImagePoint::new(
+            f32::from(p.x).clamp(0., self.image_size.width.into()),
+            f32::from(p.y).clamp(0., self.image_size.height.into()),
+        )
+    }
+
+    /// Returns the hit testing tolerance in the image pixels, so the handles keep the same size on screen.
+    fn tolerance(&self, cx: &App) -> f32 {
+        f32::from(HANDLE_SIZE) / self.viewport.read(cx).zoom()
+    }
+
+    fn finish_polygon(&mut self, cx: &mut Context<Self>) {
+        let points = std::mem::take(&mut self.draft);
+        cx.notify();
+        if points.len() < 3 {
+            return;
+        }
+
+        let ix = self.add_annotation(
+            Annotation::new(self.default_label.clone(), Shape::Polygon { points }),
+            cx,
+        );
+        self.select_annotation(Some(ix), cx);
+    }
+
+    fn on_mouse_down(
+        &mut self,
+        event: &MouseDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.viewport.read(cx).is_pan_key_pressed() {
+            return;
+        }
+        self.viewport.read(cx).focus_handle(cx).focus(window);
+
+        let position = self.image_position(event.position, cx);
+        let tolerance = self.tolerance(cx);
+        match self.tool {
+            AnnotationTool::Select => {
+                match hit_test(&self.annotations, self.selected, position, tolerance) {
+                    Some(Hit::Vertex(ix, vertex)) => {
+                        self.drag = Some(Drag::Vertex {
+                            ix,
+                            vertex,
+                            moved: false,
+                        });
+                    }
+                    Some(Hit::Shape(ix)) => {
+                        self.select_annotation(Some(ix), cx);
+                        self.drag = Some(Drag::Move {
+                            ix,
+                            start: position,
+                            origin: self.annotations[ix].shape.clone(),
+                            moved: false,
+                        });
+                    }
+                    None => self.select_annotation(None, cx),
+                }
+            }
+            AnnotationTool::Rect => {
+                self.drag = Some(Drag::Rect {
+                    start: position,
+                    end: position,
+                });
+            }
+            AnnotationTool::Point => {
+                let ix = self.add_annotation(
+                    Annotation::new(self.default_label.clone(), Shape::Point { point: position }),
+                    cx,
+                );
+                self.select_annotation(Some(ix), cx);
+            }
+            AnnotationTool::Polygon => {
+                let close_to_first = self.draft.len() >= 3
+                    && self.draft.first().is_some_and(|first| {
+                        Shape::Point { point: *first }.contains(position, tolerance)
+                    });
+                if close_to_first || event.click_count >= 2 {
+                    self.finish_polygon(cx);
+                } else {
+                    self.draft.push(position);
+                }
+            }
+        }
+        cx.notify();
+    }
+
+    fn on_mouse_move(&mut self, event: &MouseMoveEvent, _: &mut Window, cx: &mut Context<Self>) {
+        let position = self.image_position(event.position, cx);
+        match self.drag.as_mut() {
+            Some(Drag::Move {
+                ix,
+                start,
+                origin,
+                moved,
+            }) => {
+                let mut shape = origin.clone();
+                shape.translate(position.x - start.x, position.y - start.y);
+                self.annotations[*ix].shape = shape;
+                *moved = true;
+            }
+            Some(Drag::Vertex { ix, vertex, moved }) => {
+                let shape = &mut self.annotations[*ix].shape;
+                shape.set_vertex(*vertex, position);
+                // The vertex index of a rectangle changes when it is dragged over the opposite corner.
+                if let Shape::Rect { .. } = shape {
+                    if let Some(v) = shape.vertices().iter().position(|p| *p == position) {
+                        *vertex = v;
+                    }
+                }
+                *moved = true;
+            }
+            Some(Drag::Rect { end, .. }) => *end = position,
+            None if !self.draft.is_empty() => self.mouse_position = Some(position),
+            None => return,
+        }
+        cx.notify();
+    }
+
+    fn on_mouse_up(&mut self, _: &MouseUpEvent, _: &mut Window, cx: &mut Context<Self>) {
+        match self.drag.take() {
+            Some(Drag::Move {
+                ix, moved: true, ..
+            })
+            | Some(Drag::Vertex {
+                ix, moved: true, ..
+            }) => cx.emit(ImageAnnotatorEvent::Change(ix)),
+            Some(Drag::Rect { start, end }) => {
+                let tolerance = self.tolerance(cx);
+                if (start.x - end.x).abs() > tolerance && (start.y - end.y).abs() > tolerance {
+                    let ix = self.add_annotation(
+                        Annotation::new(self.default_label.clone(), Shape::rect(start, end)),
+                        cx,
+                    );
+                    self.select_annotation(Some(ix), cx);
+                }
+            }
+            Some(_) => {}
+            None => return,
+        }
+        cx.notify();
+    }
+
+    fn on_action_delete(&mut self, _: &DeleteSelected, _: &mut Window, cx: &mut Context<Self>) {
+        self.delete_selected(cx);
+    }
+
+    fn on_action_confirm(&mut self, _: &Confirm, _: &mut Window, cx: &mut Context<Self>) {
+        self.finish_polygon(cx);
+    }
+
+    fn on_action_cancel(&mut self, _: &Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        if self.drag.take().is_some() || !self.draft.is_empty() {
+            self.draft.clear();
+            cx.notify();
+        } else {
+            self.select_annotation(None, cx);
+        }
+    }
+
+    /// Returns the color of the annotation by its label.
+    fn label_color(label: &str, cx: &App) -> Hsla {
+        if label.is_empty() {
+            return cx.theme().primary;
+        }
+
+        let mut hasher = DefaultHasher::new();
+        label.hash(&mut hasher);
+        match hasher.finish() % 5 {
+            0 => cx.theme().chart_1,
+            1 => cx.theme().chart_2,
+            2 => cx.theme().chart_3,
+            3 => cx.theme().chart_4,
+            _ => cx.theme().chart_5,
+        }
+    }
+
+    fn render_content(
+        &mut self,
+        transform: ViewportTransform,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let image_bounds = transform.apply_bounds(Bounds::new(Point::default(), self.image_size));
+        let apply = move |p: ImagePoint| transform.apply(p.into());
+
+        let shapes = self
+            .annotations
+            .iter()
+            .enumerate()
+            .map(|(ix, annotation)| {
+                let points = annotation
+                    .shape
+                    .vertices()
+                    .into_iter()
+                    .map(apply)
+                    .collect::<Vec<_>>();
+                let color = Self::label_color(&annotation.label, cx);
+                (
+                    annotation.shape.clone(),
+                    points,
+                    color,
+                    self.selected == Some(ix),
+                )
+            })
+            .collect::<Vec<_>>();
+        let rect_draft = match &self.drag {
+            Some(Drag::Rect { start, end }) => Some(Bounds::from_corners(
+                apply(ImagePoint::new(start.x.min(end.x), start.y.min(end.y))),
+                apply(ImagePoint::new(start.x.max(end.x), start.y.max(end.y))),
+            )),
+            _ => None,
+        };
+        let polygon_draft = self
+            .draft
+            .iter()
+            .copied()
+            .chain(self.mouse_position.filter(|_| !self.draft.is_empty()))
+            .map(apply)
+            .collect::<Vec<_>>();
+        let draft_color = cx.theme().ring;
+        let handle_color = cx.theme().background;
+
+        let labels = self
+            .annotations
+            .iter()
+            .filter(|annotation| !annotation.label.is_empty())
+            .map(|annotation| {
+                let origin = transform.apply(annotation.shape.bounds().origin);
+                div()
+                    .absolute()
+                    .left(origin.x)
+                    .top(origin.y - px(18.))
+                    .h(px(18.))
+                    .px_1()
+                    .text_xs()
+                    .whitespace_nowrap()
+                    .bg(Self::label_color(&annotation.label, cx))
+                    .text_color(cx.theme().background)
+                    .child(annotation.label.clone())
+            })
+            .collect::<Vec<_>>();
+
+        div()
+            .size_full()
+            .relative()
+            .when(self.tool != AnnotationTool::Select, |this| {
+                this.cursor_crosshair()
+            })
+            .on_mouse_down(MouseButton::Left, cx.listener(Self::on_mouse_down))
+            .on_mouse_move(cx.listener(Self::on_mouse_move))
+            .on_mouse_up(MouseButton::Left, cx.listener(Self::on_mouse_up))
+            .on_mouse_up_out(MouseButton::Left, cx.listener(Self::on_mouse_up))
+            .child(
+                img(self.source.clone())
+                    .absolute()
+                    .left(image_bounds.origin.x)
+                    .top(image_bounds.origin.y)
+                    .w(image_bounds.size.width)
+                    .h(image_bounds.size.height),
+            )
+            .child(
+                canvas(
+                    |_, _, _| {},
+                    move |bounds, _, window, _| {
+                        let origin = bounds.origin;
+                        for (shape, points, color, selected) in shapes.iter() {
+                            let points = points.iter().map(|p| origin + *p).collect::<Vec<_>>();
+                            match shape {
+                                Shape::Point { .. } => {
+                                    let rect = Bounds::centered_at(
+                                        points[0],
+                                        size(POINT_SIZE, POINT_SIZE),
+                                    );
+                                    window.paint_quad(
+                                        fill(rect, *color)
+                                            .corner_radii(POINT_SIZE / 2.)
+                                            .border_widths(px(2.))
+                                            .border_color(handle_color),
+                                    );
+                                }
+                                _ => {
+                                    let mut builder = PathBuilder::fill();
+                                    builder.add_polygon(&points, true);
+                                    if let Ok(path) = builder.build() {
+                                        window.paint_path(path, color.opacity(0.15));
+                                    }
+                                    let mut builder = PathBuilder::stroke(px(2.));
+                                    builder.add_polygon(&points, true);
+                                    if let Ok(path) = builder.build() {
+                                        window.paint_path(path, *color);
+                                    }
+                                }
+                            }
+
+                            if *selected {
+                                for p in points.iter() {
+                                    window.paint_quad(
+                                        fill(
+                                            Bounds::centered_at(*p, size(HANDLE_SIZE, HANDLE_SIZE)),
+                                            handle_color,
+                                        )
+                                        .border_widths(px(1.))
+                                        .border_color(*color),
+                                    );
+                                }
+                            }
+                        }
+
+                        if let Some(rect) = rect_draft {
+                            let rect = Bounds::new(origin + rect.origin, rect.size);
+                            window.paint_quad(fill(rect, draft_color.opacity(0.15)));
+                            window.paint_quad(gpui::outline(
+                                rect,
+                                draft_color,
+                                gpui::BorderStyle::Dashed,
+                            ));
+                        }
+
+                        if !polygon_draft.is_empty() {
+                            let points = polygon_draft
+                                .iter()
+                                .map(|p| origin + *p)
+                                .collect::<Vec<_>>();
+                            let mut builder = PathBuilder::stroke(px(2.));
+                            builder.add_polygon(&points, false);
+                            if let Ok(path) = builder.build() {
+                                window.paint_path(path, draft_color);
+                            }
+                            for p in points.iter() {
+                                window.paint_quad(fill(
+                                    Bounds::centered_at(*p, size(HANDLE_SIZE, HANDLE_SIZE)),
+                                    draft_color,
+                                ));
+                            }
+                        }
+                    },
+                )
+                .absolute()
+                .size_full(),
+            )
+            .children(labels)
+            .into_any_element()
+    }
+}
+
+impl EventEmitter<ImageAnnotatorEvent> for ImageAnnotatorState {}
+
+impl Render for ImageAnnotatorState {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let state = cx.entity();
+        Viewport::new(&self.viewport).content(move |transform, _, cx| {
+            state.update(cx, |state, cx| state.render_content(transform, cx))
+        })
+    }
+}
+
+/// An image with the annotation overlay, for labeling and review tools, built on the [`Viewport`].
+///
+/// - Draw rectangles, polygons and points by the [`AnnotationTool`].
+/// - Select an annotation to move it, drag its handles to resize.
+/// - Press `Delete` or `Backspace` to delete the selected annotation.
+///
+/// ```ignore
+/// let state = cx.new(|cx| {
+///     ImageAnnotatorState::new("photo.jpg", size(px(1920.), px(1080.)), window, cx)
+///         .default_label("cat")
+/// });
+///
+/// ImageAnnotator::new(&state)
+/// ```
+#[derive(IntoElement)]
+pub struct ImageAnnotator {
+    id: ElementId,
+    state: Entity<ImageAnnotatorState>,
+    style: StyleRefinement,
+}
+
+impl ImageAnnotator {
+    /// Create a new image annotator with the given state.
+    pub fn new(state: &Entity<ImageAnnotatorState>) -> Self {
+        Self {
+            id: ElementId::Name(format!("image-annotator-{}", state.entity_id()).into()),
+            state: state.clone(),
+            style: StyleRefinement::default(),
+        }
+    }
+}
+
+impl Styled for ImageAnnotator {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for ImageAnnotator {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        div()
+            .id(self.id)
+            .key_context(CONTEXT)
+            .on_action(window.listener_for(&self.state, ImageAnnotatorState::on_action_delete))
+            .on_action(window.listener_for(&self.state, ImageAnnotatorState::on_action_confirm))
+            .on_action(window.listener_for(&self.state, ImageAnnotatorState::on_action_cancel))
+            .size_full()
+            .bg(cx.theme().muted)
+            .child(self.state)
+            .refine_style(&self.style)
+    }
+}
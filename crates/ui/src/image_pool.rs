@@ -0,0 +1,711 @@
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash as _, Hasher as _},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use gpui::{
+    App, DevicePixels, Global, Hsla, Image, ImageCacheError, ImageFormat, ImageSource, RenderImage,
+    Size,
+};
+use image::{imageops::FilterType, Frame, RgbaImage};
+use smallvec::SmallVec;
+
+/// The pending request is canceled if the element is not rendered in this duration,
+/// e.g. it has been scrolled out or removed.
+const STALE_TIMEOUT: Duration = Duration::from_secs(1);
+/// The decoded image is released if the element is not rendered in this duration.
+const CACHE_TIMEOUT: Duration = Duration::from_secs(60);
+/// The default memory budget of the decoded images, 256 MB.
+const DEFAULT_CACHE_BUDGET: usize = 256 * 1024 * 1024;
+
+/// The priority to decode an image in the [`ImagePool`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DecodePriority {
+    /// The image is out of the viewport, e.g. preload for the next page.
+    Offscreen,
+    /// The image is near the viewport, will be visible soon when scrolling.
+    NearViewport,
+    /// The image is visible in the viewport.
+    #[default]
+    Visible,
+}
+
+/// The source of an image to decode in the [`ImagePool`].
+#[derive(Debug, Clone)]
+pub enum DecodeSource {
+    /// An image file, the format is detected by the extension.
+    Path(Arc<Path>),
+    /// The encoded bytes of an image.
+    Image(Arc<Image>),
+}
+
+impl DecodeSource {
+    fn key(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        match self {
+            Self::Path(path) => {
+                0u8.hash(&mut hasher);
+                path.hash(&mut hasher);
+            }
+            Self::Image(image) => {
+                1u8.hash(&mut hasher);
+                image.id().hash(&mut hasher);
+            }
+        }
+        hasher.finish()
+    }
+}
+
+impl From<PathBuf> for DecodeSource {
+    fn from(path: PathBuf) -> Self {
+        Self::Path(path.into())
+    }
+}
+
+impl From<&Path> for DecodeSource {
+    fn from(path: &Path) -> Self {
+        Self::Path(path.into())
+    }
+}
+
+impl From<&str> for DecodeSource {
+    fn from(path: &str) -> Self {
+        Self::Path(Path::new(path).into())
+    }
+}
+
+impl From<Image> for DecodeSource {
+    fn from(image: Image) -> Self {
+        Self::Image(Arc::new(image))
+    }
+}
+
+impl From<Arc<Image>> for DecodeSource {
+    fn from(image: Arc<Image>) -> Self {
+        Self::Image(image)
+    }
+}
+
+/// The variant of the decoded image, each variant of a source is cached separately.
+///
+/// ```ignore
+/// // A 32x32 thumbnail, tinted with the foreground color of the theme.
+/// ImageVariant::default()
+///     .size(size(DevicePixels(32), DevicePixels(32)))
+///     .tint(cx.theme().foreground)
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ImageVariant {
+    size: Option<Size<DevicePixels>>,
+    tint: Option<Hsla>,
+}
+
+impl ImageVariant {
+    /// Scale the image to fit in the size, keeping the aspect ratio.
+    pub fn size(mut self, size: Size<DevicePixels>) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Fill the image with the color, keeping the alpha, e.g. a monochrome icon.
+    ///
+    /// Use the theme color to get a variant for each theme.
+    pub fn tint(mut self, color: impl Into<Hsla>) -> Self {
+        self.tint = Some(color.into());
+        self
+    }
+
+    fn is_original(&self) -> bool {
+        self.size.is_none() && self.tint.is_none()
+    }
+
+    fn key(&self, source: &DecodeSource) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        source.key().hash(&mut hasher);
+        if let Some(size) = self.size {
+            (size.width.0, size.height.0).hash(&mut hasher);
+        }
+        if let Some(tint) = self.tint {
+            [tint.h, tint.s, tint.l, tint.a]
+                .map(f32::to_bits)
+                .hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+}
+
+/// Returns the size to fit the `size` in the `bounds`, keeping the aspect ratio.
+fn fit_size(size: (u32, u32), bounds: (u32, u32)) -> (u32, u32) {
+    let (width, height) = size;
+    if width == 0 || height == 0 {
+        return size;
+    }
+
+    let scale = (bounds.0 as f32 / width as f32).min(bounds.1 as f32 / height as f32);
+    (
+        ((width as f32 * scale).round() as u32).max(1),
+        ((height as f32 * scale).round() as u32).max(1),
+    )
+}
+
+/// Fill the BGRA pixels with the color, the alpha is multiplied by the alpha of the color.
+fn tint_pixels(pixels: &mut [u8], color: Hsla) {
+    let rgba = color.to_rgb();
+    let [r, g, b] = [rgba.r, rgba.g, rgba.b].map(|c| (c * 255.).round() as u8);
+    for pixel in pixels.chunks_exact_mut(4) {
+        pixel[0] = b;
+        pixel[1] = g;
+        pixel[2] = r;
+        pixel[3] = (pixel[3] as f32 * rgba.a).round() as u8;
+    }
+}
+
+/// Render the variant of the decoded image, this is running in the background thread.
+fn render_variant(image: Arc<RenderImage>, variant: ImageVariant) -> Arc<RenderImage> {
+    if variant.is_original() {
+        return image;
+    }
+
+    let frames = (0..image.frame_count())
+        .filter_map(|ix| {
+            let size = image.size(ix);
+            let (width, height) = (size.width.0 as u32, size.height.0 as u32);
+            let mut buffer = RgbaImage::from_raw(width, height, image.as_bytes(ix)?.to_vec())?;
+            if let Some(bounds) = variant.size {
+                let (width, height) = fit_size(
+                    (width, height),
+                    (bounds.width.0 as u32, bounds.height.0 as u32),
+                );
+                if (width, height) != buffer.dimensions() {
+                    buffer = image::imageops::resize(&buffer, width, height, FilterType::Triangle);
+                }
+            }
+            if let Some(tint) = variant.tint {
+                tint_pixels(&mut buffer, tint);
+            }
+            Some(Frame::from_parts(buffer, 0, 0, image.delay(ix)))
+        })
+        .collect::<SmallVec<[Frame; 1]>>();
+
+    Arc::new(RenderImage::new(frames))
+}
+
+/// Returns the bytes of the pixels of the image.
+fn image_bytes(image: &RenderImage) -> usize {
+    (0..image.frame_count())
+        .filter_map(|ix| image.as_bytes(ix))
+        .map(|bytes| bytes.len())
+        .sum()
+}
+
+/// Returns the image format by the extension of the path.
+fn format_from_path(path: &Path) -> Option<ImageFormat> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    match ext.as_str() {
+        "png" => Some(ImageFormat::Png),
+        "jpg" | "jpeg" => Some(ImageFormat::Jpeg),
+        "webp" => Some(ImageFormat::Webp),
+        "gif" => Some(ImageFormat::Gif),
+        "svg" => Some(ImageFormat::Svg),
+        "bmp" => Some(ImageFormat::Bmp),
+        "tif" | "tiff" => Some(ImageFormat::Tiff),
+        _ => None,
+    }
+}
+
+/// Load the encoded image of the source, this is running in the background thread.
+fn load_image(source: &DecodeSource) -> Result<Arc<Image>, ImageCacheError> {
+    match source {
+        DecodeSource::Image(image) => Ok(image.clone()),
+        DecodeSource::Path(path) => {
+            let format = format_from_path(path).ok_or_else(|| {
+                ImageCacheError::Asset(format!("unsupported image: {}", path.display()).into())
+            })?;
+            let bytes = std::fs::read(path).map_err(|err| ImageCacheError::Io(Arc::new(err)))?;
+            Ok(Arc::new(Image::from_bytes(format, bytes)))
+        }
+    }
+}
+
+enum EntryState {
+    Pending,
+    Decoding,
+    Ready(Arc<RenderImage>),
+    Failed(ImageCacheError),
+}
+
+struct Entry {
+    source: DecodeSource,
+    variant: ImageVariant,
+    state: EntryState,
+    /// The bytes of the decoded image.
+    bytes: usize,
+    priority: DecodePriority,
+    /// The order of the request, the earlier request is decoded first in the same priority.
+    seq: usize,
+    last_requested: Instant,
+}
+
+/// The queue of the requests, without the executor.
+#[derive(Default)]
+struct DecodeQueue {
+    entries: HashMap<u64, Entry>,
+    running: usize,
+    seq: usize,
+}
+
+impl DecodeQueue {
+    /// Request the image, returns the result if it has been decoded.
+    fn request(
+        &mut self,
+        source: &DecodeSource,
+        variant: ImageVariant,
+        priority: DecodePriority,
+        now: Instant,
+    ) -> Option<Result<Arc<RenderImage>, ImageCacheError>> {
+        let entry = self.entries.entry(variant.key(source)).or_insert_with(|| {
+            self.seq += 1;
+            Entry {
+                source: source.clone(),
+                variant,
+                state: EntryState::Pending,
+                bytes: 0,
+                priority,
+                seq: self.seq,
+                last_requested: now,
+            }
+        });
+        entry.priority = priority;
+        entry.last_requested = now;
+
+        match &entry.state {
+            EntryState::Ready(image) => Some(Ok(image.clone())),
+            EntryState::Failed(err) => Some(Err(err.clone())),
+            EntryState::Pending | EntryState::Decoding => None,
+        }
+    }
+
+    /// Take the next pending request to decode, by the priority and then the order.
+    ///
+    /// The stale requests are canceled, and the unused decoded images are released.
+    fn take_next(&mut self, now: Instant) -> Option<(u64, DecodeSource, ImageVariant)> {
+        self.entries.retain(|_, entry| {
+            let idle = now.saturating_duration_since(entry.last_requested);
+            match entry.state {
+                EntryState::Pending => idle < STALE_TIMEOUT,
+                EntryState::Decoding => true,
+                EntryState::Ready(_) | EntryState::Failed(_) => idle < CACHE_TIMEOUT,
+            }
+        });
+
+        let (key, entry) = self
+            .entries
+            .iter_mut()
+            .filter(|(_, entry)| matches!(entry.state, EntryState::Pending))
+            .max_by(|(_, a), (_, b)| a.priority.cmp(&b.priority).then(b.seq.cmp(&a.seq)))?;
+        entry.state = EntryState::Decoding;
+        self.running += 1;
+        Some((*key, entry.source.clone(), entry.variant))
+    }
+
+    fn finish(&mut self, key: u64, result: Result<Arc<RenderImage>, ImageCacheError>) {
+        self.running = self.running.saturating_sub(1);
+        if let Some(entry) = self.entries.get_mut(&key) {
+            entry.state = match result {
+                Ok(image) => {
+                    entry.bytes = image_bytes(&image);
+                    EntryState::Ready(image)
+                }
+                Err(err) => EntryState::Failed(err),
+            };
+        }
+    }
+
+    /// Returns the total bytes of the decoded images.
+    fn cache_bytes(&self) -> usize {
+        self.entries.values().map(|entry| entry.bytes).sum()
+    }
+
+    /// Release the least recently used images until the cache is in the budget.
+    fn evict_to_budget(&mut self, budget: usize) {
+        let mut bytes = self.cache_bytes();
+        if bytes <= budget {
+            return;
+        }
+
+        let mut ready = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| matches!(entry.state, EntryState::Ready(_)))
+            .map(|(key, entry)| (entry.last_requested, *key, entry.bytes))
+            .collect::<Vec<_>>();
+        ready.sort_by_key(|(last_requested, _, _)| *last_requested);
+        for (_, key, entry_bytes) in ready {
+            if bytes <= budget {
+                break;
+            }
+            self.entries.remove(&key);
+            bytes -= entry_bytes;
+        }
+    }
+}
+
+/// The statistics of the [`ImagePool`] cache.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ImageCacheStats {
+    /// The number of the decoded images, each variant is counted separately.
+    pub entries: usize,
+    /// The bytes of the decoded images.
+    pub bytes: usize,
+    /// The number of the requests waiting to decode.
+    pub pending: usize,
+    /// The number of the images are decoding.
+    pub decoding: usize,
+    /// The memory budget of the decoded images.
+    pub budget: usize,
+}
+
+/// A shared pool to decode and rasterize the images in the background.
+///
+/// - The visible images are decoded first, then the images near the viewport,
+///   and the offscreen images last, see [`DecodePriority`].
+/// - The pending request is canceled when the element is not rendered anymore,
+///   e.g. scrolled out of a list, so scrolling fast does not decode all the images passed.
+/// - The number of the concurrent decoding is limited, to not saturate the CPU.
+/// - The resized or tinted [`ImageVariant`]s are cached separately, the least recently
+///   used images are released when the cache is over the budget.
+///
+/// ```ignore
+/// Avatar::new().src(ImagePool::source("avatars/1.png", DecodePriority::Visible))
+/// img(ImagePool::source(path, DecodePriority::NearViewport))
+/// ```
+pub struct ImagePool {
+    queue: DecodeQueue,
+    max_concurrency: usize,
+    cache_budget: usize,
+}
+
+impl Global for ImagePool {}
+
+impl Default for ImagePool {
+    fn default() -> Self {
+        let max_concurrency = std::thread::available_parallelism()
+            .map_or(2, |n| n.get() / 2)
+            .clamp(1, 4);
+
+        Self {
+            queue: DecodeQueue::default(),
+            max_concurrency,
+            cache_budget: DEFAULT_CACHE_BUDGET,
+        }
+    }
+}
+
+impl ImagePool {
+    /// Returns an [`ImageSource`] to decode the image in the pool with the priority.
+    pub fn source(source: impl Into<DecodeSource>, priority: DecodePriority) -> ImageSource {
+        Self::variant(source, ImageVariant::default(), priority)
+    }
+
+    /// Returns an [`ImageSource`] to decode the variant of the image in the pool with the priority.
+    pub fn variant(
+        source: impl Into<DecodeSource>,
+        variant: ImageVariant,
+        priority: DecodePriority,
+    ) -> ImageSource {
+        let source = source.into();
+        ImageSource::Custom(Arc::new(move |_, cx| {
+            Self::request(&source, variant, priority, cx)
+        }))
+    }
+
+    /// Set the memory budget in bytes of the decoded images, default: 256 MB
+    pub fn set_cache_budget(budget: usize, cx: &mut App) {
+        let pool = cx.default_global::<Self>();
+        pool.cache_budget = budget;
+        pool.queue.evict_to_budget(budget);
+    }
+
+    /// Returns the statistics of the cache.
+    pub fn stats(cx: &App) -> ImageCacheStats {
+        let Some(pool) = cx.try_global::<Self>() else {
+            return ImageCacheStats {
+                budget: DEFAULT_CACHE_BUDGET,
+                ..Default::default()
+            };
+        };
+
+        let mut stats = ImageCacheStats {
+            bytes: pool.queue.cache_bytes(),
+            budget: pool.cache_budget,
+            ..Default::default()
+        };
+        for entry in pool.queue.entries.values() {
+            match entry.state {
+                EntryState::Pending => stats.pending += 1,
+                EntryState::Decoding => stats.decoding += 1,
+                EntryState::Ready(_) => stats.entries += 1,
+                EntryState::Failed(_) => {}
+            }
+        }
+        stats
+    }
+
+    /// Set the max number of the images to decode at the same time,
+    /// default is half of the CPU cores, up to 4.
+    pub fn set_max_concurrency(max_concurrency: usize, cx: &mut App) {
+        cx.default_global::<Self>().max_concurrency = max_concurrency.max(1);
+        Self::pump(cx);
+    }
+
+    /// Release all the decoded images, the pending requests are canceled.
+    pub fn clear(cx: &mut App) {
+        cx.default_global::<Self>()
+            .queue
+            .entries
+            .retain(|_, entry| matches!(entry.state, EntryState::Decoding));
+    }
+
+    fn request(
+        source: &DecodeSource,
+        variant: ImageVariant,
+        priority: DecodePriority,
+        cx: &mut App,
+    ) -> Option<Result<Arc<RenderImage>, ImageCacheError>> {
+        let result =
+            cx.default_global::<Self>()
+                .queue
+                .request(source, variant, priority, Instant::now());
+        if result.is_none() {
+            Self::pump(cx);
+        }
+        result
+    }
+
+    /// Start decoding the pending requests up to the max concurrency.
+    fn pump(cx: &mut App) {
+        loop {
+            let pool = cx.default_global::<Self>();
+            if pool.queue.running >= pool.max_concurrency {
+                break;
+            }
+            let Some((key, source, variant)) = pool.queue.take_next(Instant::now()) else {
+                break;
+            };
+
+            let svg_renderer = cx.svg_renderer();
+            let task = cx.background_executor().spawn(async move {
+                let image = load_image(&source)?
+                    .to_image_data(svg_renderer)
+                    .map_err(|err| ImageCacheError::Other(Arc::new(err)))?;
+                Ok(render_variant(image, variant))
+            });
+            cx.spawn(async move |cx| {
+                let result = task.await;
+                _ = cx.update(|cx| {
+                    let pool = cx.default_global::<Self>();
+                    pool.queue.finish(key, result);
+                    pool.queue.evict_to_budget(pool.cache_budget);
+                    Self::pump(cx);
+                    cx.refresh_windows();
+                });
+            })
+            .detach();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, time::Instant};
+
+    use gpui::ImageFormat;
+
+    use gpui::{hsla, size, DevicePixels};
+
+    use super::{
+        fit_size, format_from_path, tint_pixels, DecodePriority, DecodeQueue, DecodeSource,
+        ImageVariant, STALE_TIMEOUT,
+    };
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            format_from_path(Path::new("a/b.PNG")),
+            Some(ImageFormat::Png)
+        );
+        assert_eq!(
+            format_from_path(Path::new("photo.jpeg")),
+            Some(ImageFormat::Jpeg)
+        );
+        assert_eq!(
+            format_from_path(Path::new("icon.svg")),
+            Some(ImageFormat::Svg)
+        );
+        assert_eq!(format_from_path(Path::new("README")), None);
+        assert_eq!(format_from_path(Path::new("a.txt")), None);
+    }
+
+    #[test]
+    fn test_decode_queue_priority() {
+        let now = Instant::now();
+        let mut queue = DecodeQueue::default();
+        let offscreen = DecodeSource::from("offscreen.png");
+        let near = DecodeSource::from("near.png");
+        let visible1 = DecodeSource::from("visible1.png");
+        let visible2 = DecodeSource::from("visible2.png");
+
+        assert!(queue
+            .request(
+                &offscreen,
+                ImageVariant::default(),
+                DecodePriority::Offscreen,
+                now
+            )
+            .is_none());
+        queue.request(
+            &near,
+            ImageVariant::default(),
+            DecodePriority::NearViewport,
+            now,
+        );
+        queue.request(
+            &visible1,
+            ImageVariant::default(),
+            DecodePriority::Visible,
+            now,
+        );
+        queue.request(
+            &visible2,
+            ImageVariant::default(),
+            DecodePriority::Visible,
+            now,
+        );
+        // Request again is deduplicated.
+        queue.request(
+            &visible1,
+            ImageVariant::default(),
+            DecodePriority::Visible,
+            now,
+        );
+        assert_eq!(queue.entries.len(), 4);
+
+        let order = std::iter::from_fn(|| queue.take_next(now))
+            .map(|(key, _, _)| key)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            order,
+            vec![
+                ImageVariant::default().key(&visible1),
+                ImageVariant::default().key(&visible2),
+                ImageVariant::default().key(&near),
+                ImageVariant::default().key(&offscreen)
+            ]
+        );
+        assert_eq!(queue.running, 4);
+    }
+
+    #[test]
+    fn test_decode_queue_cancel_stale() {
+        let now = Instant::now();
+        let mut queue = DecodeQueue::default();
+        let scrolled_out = DecodeSource::from("scrolled_out.png");
+        let visible = DecodeSource::from("visible.png");
+
+        queue.request(
+            &scrolled_out,
+            ImageVariant::default(),
+            DecodePriority::Visible,
+            now,
+        );
+        let later = now + STALE_TIMEOUT;
+        queue.request(
+            &visible,
+            ImageVariant::default(),
+            DecodePriority::Offscreen,
+            later,
+        );
+
+        let (key, _, _) = queue.take_next(later).unwrap();
+        assert_eq!(key, ImageVariant::default().key(&visible));
+        assert!(queue.take_next(later).is_none());
+        assert_eq!(queue.entries.len(), 1);
+    }
+
+    #[test]
+    fn test_variant_key() {
+        let source = DecodeSource::from("icon.svg");
+        let original = ImageVariant::default();
+        let small = ImageVariant::default().size(size(DevicePixels(16), DevicePixels(16)));
+        let dark = ImageVariant::default().tint(hsla(0., 0., 0., 1.));
+        let light = ImageVariant::default().tint(hsla(0., 0., 1., 1.));
+
+        assert!(original.is_original());
+        assert_eq!(original.key(&source), ImageVariant::default().key(&source));
+        assert_ne!(original.key(&source), small.key(&source));
+        assert_ne!(dark.key(&source), light.key(&source));
+        assert_ne!(
+            original.key(&source),
+            original.key(&DecodeSource::from("icon2.svg"))
+        );
+    }
+
+    #[test]
+    fn test_fit_size() {
+        assert_eq!(fit_size((200, 100), (50, 50)), (50, 25));
+        assert_eq!(fit_size((100, 200), (50, 50)), (25, 50));
+        assert_eq!(fit_size((10, 10), (40, 20)), (20, 20));
+        assert_eq!(fit_size((0, 10), (40, 20)), (0, 10));
+    }
+
+    #[test]
+    fn test_tint_pixels() {
+        let mut pixels = vec![10, 20, 30, 255, 0, 0, 0, 128];
+        tint_pixels(&mut pixels, hsla(0., 1., 0.5, 1.));
+        assert_eq!(pixels, vec![0, 0, 255, 255, 0, 0, 255, 128]);
+
+        tint_pixels(&mut pixels, hsla(0., 1., 0.5, 0.5));
+        assert_eq!(&pixels[3..4], &[128]);
+    }
+
+    #[test]
+    fn test_evict_to_budget() {
+        let now = Instant::now();
+        let mut queue = DecodeQueue::default();
+        let sources = ["a.png", "b.png", "c.png"].map(DecodeSource::from);
+        for (ix, source) in sources.iter().enumerate() {
+            let later = now + std::time::Duration::from_millis(ix as u64);
+            queue.request(
+                source,
+                ImageVariant::default(),
+                DecodePriority::Visible,
+                later,
+            );
+        }
+        for key in sources
+            .iter()
+            .map(|source| ImageVariant::default().key(source))
+        {
+            let entry = queue.entries.get_mut(&key).unwrap();
+            entry.state = super::EntryState::Decoding;
+            entry.bytes = 100;
+        }
+        // Only the ready images are released.
+        queue.evict_to_budget(150);
+        assert_eq!(queue.entries.len(), 3);
+
+        for entry in queue.entries.values_mut() {
+            entry.state = super::EntryState::Ready(std::sync::Arc::new(gpui::RenderImage::new(
+                smallvec::SmallVec::new(),
+            )));
+        }
+        queue.evict_to_budget(150);
+        assert_eq!(queue.cache_bytes(), 100);
+        assert!(queue
+            .entries
+            .contains_key(&ImageVariant::default().key(&sources[2])));
+    }
+}
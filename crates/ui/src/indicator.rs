@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use crate::{Icon, IconName, Sizable, Size};
+use crate::{animation::MotionSettings, Icon, IconName, Sizable, Size};
 use gpui::{
     div, ease_in_out, percentage, prelude::FluentBuilder as _, Animation, AnimationExt as _, App,
     Hsla, IntoElement, ParentElement, RenderOnce, Styled as _, Transformation, Window,
@@ -51,18 +51,25 @@ impl Sizable for Indicator {
 }
 
 impl RenderOnce for Indicator {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let icon = self
+            .icon
+            .with_size(self.size)
+            .when_some(self.color, |this, color| this.text_color(color));
+
         div()
-            .child(
-                self.icon
-                    .with_size(self.size)
-                    .when_some(self.color, |this, color| this.text_color(color))
-                    .with_animation(
-                        "circle",
-                        Animation::new(self.speed).repeat().with_easing(ease_in_out),
-                        |this, delta| this.transform(Transformation::rotate(percentage(delta))),
-                    ),
-            )
+            .map(|this| {
+                // Keep spinning in the reduced motion to show the progress.
+                if MotionSettings::global(cx).is_off() {
+                    return this.child(icon);
+                }
+
+                this.child(icon.with_animation(
+                    "circle",
+                    Animation::new(self.speed).repeat().with_easing(ease_in_out),
+                    |this, delta| this.transform(Transformation::rotate(percentage(delta))),
+                ))
+            })
             .into_element()
     }
 }
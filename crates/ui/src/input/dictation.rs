@@ -0,0 +1,89 @@
+use gpui::{Context, EntityInputHandler as _, Window};
+use std::ops::Range;
+
+use crate::input::InputState;
+
+/// Adjust the range of the interim dictation text after the `range` is replaced by `new_len` bytes.
+///
+/// The dictation is canceled if the replaced range overlaps the interim text.
+pub(super) fn adjust_dictation(
+    dictation: &mut Option<Range<usize>>,
+    range: &Range<usize>,
+    new_len: usize,
+) {
+    let Some(dictation_range) = dictation.as_mut() else {
+        return;
+    };
+
+    if range.end <= dictation_range.start {
+        let start = dictation_range.start - range.len() + new_len;
+        *dictation_range = start..start + dictation_range.len();
+    } else if range.start < dictation_range.end {
+        *dictation = None;
+    }
+}
+
+impl InputState {
+    /// Set the interim text of the dictation (e.g. from the speech-to-text), it replaces the
+    /// previous interim text, or the selected text for the first time.
+    ///
+    /// The interim text is displayed in the muted color until [`Self::finish_dictation`] or
+    /// [`Self::cancel_dictation`] is called.
+    pub fn set_dictation_text(&mut self, text: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let range = self
+            .dictation_range
+            .clone()
+            .unwrap_or_else(|| self.selected_range.into());
+        let range_utf16 = self.range_to_utf16(&range);
+        let old_len = self.text.len();
+        self.replace_text_in_range_silent(Some(range_utf16), text, window, cx);
+
+        // The text may be rejected by the validation of the input.
+        self.dictation_range = if self.text.len() + range.len() == old_len + text.len() {
+            Some(range.start..range.start + text.len())
+        } else {
+            None
+        };
+        cx.notify();
+    }
+
+    /// Finish the dictation with the final `text`, it replaces the interim text as a normal typing.
+    pub fn finish_dictation(&mut self, text: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let range_utf16 = self
+            .dictation_range
+            .take()
+            .map(|range| self.range_to_utf16(&range));
+        self.replace_text_in_range(range_utf16, text, window, cx);
+    }
+
+    /// Cancel the dictation, and remove the interim text.
+    pub fn cancel_dictation(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(range) = self.dictation_range.take() else {
+            return;
+        };
+
+        let range_utf16 = self.range_to_utf16(&range);
+        self.replace_text_in_range_silent(Some(range_utf16), "", window, cx);
+    }
+
+    /// Returns true if there is an interim dictation text.
+    pub fn is_dictating(&self) -> bool {
+        self.dictation_range.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::adjust_dictation;
+
+    #[test]
+    fn test_adjust_dictation() {
+        let mut dictation = Some(5..10);
+        adjust_dictation(&mut dictation, &(0..2), 4);
+        assert_eq!(dictation, Some(7..12));
+        adjust_dictation(&mut dictation, &(12..14), 0);
+        assert_eq!(dictation, Some(7..12));
+        adjust_dictation(&mut dictation, &(10..13), 1);
+        assert_eq!(dictation, None);
+    }
+}
@@ -433,6 +433,22 @@ impl TextElement {
         paths
     }
 
+    fn layout_mentions(
+        &self,
+        last_layout: &LastLayout,
+        bounds: &Bounds<Pixels>,
+        cx: &mut App,
+    ) -> Vec<Path<Pixels>> {
+        self.state
+            .read(cx)
+            .mentions
+            .iter()
+            .filter_map(|mention| {
+                Self::layout_match_range(mention.range.clone(), last_layout, bounds)
+            })
+            .collect()
+    }
+
     fn layout_selections(
         &self,
         last_layout: &LastLayout,
@@ -711,6 +727,7 @@ pub(super) struct PrepaintState {
     hover_highlight_path: Option<Path<Pixels>>,
     search_match_paths: Vec<(Path<Pixels>, bool)>,
     document_color_paths: Vec<(Path<Pixels>, Hsla)>,
+    mention_paths: Vec<Path<Pixels>>,
     hover_definition_hitbox: Option<Hitbox>,
     indent_guides_path: Option<Path<Pixels>>,
     bounds: Bounds<Pixels>,
@@ -938,6 +955,39 @@ impl Element for TextElement {
             vec![run]
         };
 
+        // The runs of the multi-line are started from the visible range.
+        let runs_offset = if multi_line { visible_start_offset } else { 0 };
+        let is_plain_text = !is_empty && !state.masked && !state.mode.is_code_editor();
+
+        // The interim dictation text in the muted color.
+        let mut runs = runs;
+        if let Some(range) = state.dictation_range.as_ref().filter(|_| is_plain_text) {
+            let muted_color = cx.theme().muted_foreground;
+            runs = refine_runs(
+                runs,
+                &(range.start.saturating_sub(runs_offset)..range.end.saturating_sub(runs_offset)),
+                |run| run.color = muted_color,
+            );
+        }
+
+        // The squiggly underlines of the misspelled words.
+        if let Some(spell_checker) = state.spell_checker.as_ref().filter(|_| is_plain_text) {
+            let visible_text = if multi_line {
+                text.slice_lines(last_layout.visible_range.clone())
+                    .to_string()
+            } else {
+                text.to_string()
+            };
+            let underline = UnderlineStyle {
+                thickness: px(1.),
+                color: Some(cx.theme().danger),
+                wavy: true,
+            };
+            for range in spell_checker.misspelled(&visible_text) {
+                runs = refine_runs(runs, &range, |run| run.underline = Some(underline));
+            }
+        }
+
         let document_colors = state
             .lsp
             .document_colors_for_range(&text, &last_layout.visible_range);
@@ -1035,6 +1085,7 @@ impl Element for TextElement {
         let hover_highlight_path = self.layout_hover_highlight(&last_layout, &mut bounds, cx);
         let document_color_paths =
             self.layout_document_colors(&document_colors, &last_layout, &bounds);
+        let mention_paths = self.layout_mentions(&last_layout, &bounds, cx);
 
         let state = self.state.read(cx);
         let line_numbers = if state.mode.line_number() {
@@ -1100,6 +1151,7 @@ impl Element for TextElement {
             hover_highlight_path,
             hover_definition_hitbox,
             document_color_paths,
+            mention_paths,
             indent_guides_path,
         }
     }
@@ -1224,6 +1276,11 @@ impl Element for TextElement {
             window.paint_path(path.clone(), *color);
         }
 
+        // Paint mention tokens
+        for path in prepaint.mention_paths.iter() {
+            window.paint_path(path.clone(), cx.theme().accent);
+        }
+
         // Paint text
         let mut offset_y = mask_offset_y + invisible_top_padding;
         for line in prepaint.last_layout.lines.iter() {
@@ -1235,10 +1292,14 @@ impl Element for TextElement {
             offset_y += line.size(line_height).height;
         }
 
+        let cursor_bounds = prepaint.cursor_bounds.take().map(|mut cursor_bounds| {
+            cursor_bounds.origin.y += prepaint.cursor_scroll_offset.y;
+            cursor_bounds
+        });
+
         // Paint blinking cursor
         if focused && show_cursor {
-            if let Some(mut cursor_bounds) = prepaint.cursor_bounds.take() {
-                cursor_bounds.origin.y += prepaint.cursor_scroll_offset.y;
+            if let Some(cursor_bounds) = cursor_bounds {
                 window.paint_quad(fill(cursor_bounds, cx.theme().caret));
             }
         }
@@ -1289,6 +1350,7 @@ impl Element for TextElement {
             state.last_layout = Some(prepaint.last_layout.clone());
             state.last_bounds = Some(bounds);
             state.last_cursor = Some(state.cursor());
+            state.last_cursor_bounds = cursor_bounds;
             state.set_input_bounds(input_bounds, cx);
             state.last_selected_range = Some(selected_range);
             state.scroll_size = prepaint.scroll_size;
@@ -1345,6 +1407,47 @@ pub(super) fn runs_for_range(
     result
 }
 
+/// Refine the runs in the `range` by the `f`, the runs are split at the bounds of the range.
+pub(super) fn refine_runs(
+    runs: Vec<TextRun>,
+    range: &Range<usize>,
+    f: impl Fn(&mut TextRun),
+) -> Vec<TextRun> {
+    let mut result = Vec::with_capacity(runs.len() + 2);
+    let mut offset = 0;
+    for run in runs {
+        let run_range = offset..offset + run.len;
+        offset = run_range.end;
+
+        let start = run_range.start.max(range.start);
+        let end = run_range.end.min(range.end);
+        if start >= end {
+            result.push(run);
+            continue;
+        }
+
+        if start > run_range.start {
+            result.push(TextRun {
+                len: start - run_range.start,
+                ..run.clone()
+            });
+        }
+        let mut refined = TextRun {
+            len: end - start,
+            ..run.clone()
+        };
+        f(&mut refined);
+        result.push(refined);
+        if end < run_range.end {
+            result.push(TextRun {
+                len: run_range.end - end,
+                ..run
+            });
+        }
+    }
+    result
+}
+
 fn split_runs_by_bg_segments(
     start_offset: usize,
     runs: &[TextRun],
@@ -1508,4 +1611,29 @@ mod tests {
         assert_eq!(result[4].color, gpui::black());
         assert_eq!(result[5].color, gpui::blue());
     }
+
+    #[test]
+    fn test_refine_runs() {
+        let run = |len| TextRun {
+            len,
+            font: gpui::font("Arial"),
+            color: gpui::black(),
+            background_color: None,
+            underline: None,
+            strikethrough: None,
+        };
+        let muted = gpui::hsla(0., 0., 0.5, 1.);
+
+        let runs = refine_runs(vec![run(4), run(6)], &(2..7), |run| run.color = muted);
+        assert_eq!(
+            runs.iter().map(|run| run.len).collect::<Vec<_>>(),
+            vec![2, 2, 3, 3]
+        );
+        assert_eq!(
+            runs.iter()
+                .map(|run| run.color == muted)
+                .collect::<Vec<_>>(),
+            vec![false, true, true, false]
+        );
+    }
 }
@@ -112,6 +112,11 @@ impl InputState {
                     handled = menu.handle_action(action, window, cx)
                 });
             }
+            ContextMenu::Mention(menu) => {
+                _ = menu.update(cx, |menu, cx| {
+                    handled = menu.handle_action(action, window, cx)
+                });
+            }
             ContextMenu::MouseContext(..) => {}
         };
 
@@ -0,0 +1,257 @@
+use anyhow::Result;
+use gpui::{Context, SharedString, Task, Window};
+use std::ops::Range;
+use sum_tree::Bias;
+
+use crate::input::{
+    popovers::{ContextMenu, MentionMenu},
+    InputState, RopeExt as _,
+};
+
+/// A suggestion item returned by the [`MentionProvider`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MentionItem {
+    /// The unique id of the item, e.g.: user id or command name.
+    pub id: SharedString,
+    /// The label to insert after the trigger character.
+    pub label: SharedString,
+    /// The optional detail text to display in the suggestion menu.
+    pub detail: Option<SharedString>,
+}
+
+impl MentionItem {
+    /// Create a new mention item with the given `id` and `label`.
+    pub fn new(id: impl Into<SharedString>, label: impl Into<SharedString>) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+            detail: None,
+        }
+    }
+
+    /// Set the detail text to display in the suggestion menu.
+    pub fn detail(mut self, detail: impl Into<SharedString>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+/// A structured token inserted into the input by selecting a [`MentionItem`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mention {
+    /// The trigger character, e.g.: `@` or `/`.
+    pub trigger: char,
+    /// The id of the selected [`MentionItem`].
+    pub id: SharedString,
+    /// The label of the selected [`MentionItem`].
+    pub label: SharedString,
+    /// The byte range of the token (including the trigger character) in the text.
+    pub range: Range<usize>,
+}
+
+/// A trait for providing mention (`@user`) or slash-command (`/command`) suggestions.
+pub trait MentionProvider {
+    /// The characters that open the suggestion menu, e.g.: `['@', '/']`.
+    ///
+    /// The trigger only activates at the start of the text or after a whitespace.
+    fn trigger_characters(&self) -> Vec<char>;
+
+    /// Fetches the suggestions for the given trigger and query.
+    ///
+    /// The `query` is the text typed after the trigger character.
+    fn mentions(
+        &self,
+        trigger: char,
+        query: &str,
+        window: &mut Window,
+        cx: &mut Context<InputState>,
+    ) -> Task<Result<Vec<MentionItem>>>;
+}
+
+/// Adjust the mention ranges after the text in `range` was replaced with `new_len` bytes.
+///
+/// Mentions overlapped by the edit are removed, and mentions after the edit are shifted.
+pub(super) fn adjust_mentions(mentions: &mut Vec<Mention>, range: &Range<usize>, new_len: usize) {
+    mentions.retain_mut(|mention| {
+        if range.end <= mention.range.start {
+            let start = mention.range.start - range.len() + new_len;
+            mention.range = start..start + mention.range.len();
+            return true;
+        }
+
+        range.start >= mention.range.end
+    });
+}
+
+impl InputState {
+    /// Return the mention tokens in the current text, sorted by position.
+    ///
+    /// Read this when receiving [`super::InputEvent::PressEnter`] to get the structured tokens of a submit.
+    pub fn mentions(&self) -> &[Mention] {
+        &self.mentions
+    }
+
+    pub(super) fn mention_at_end(&self, offset: usize) -> Option<&Mention> {
+        self.mentions.iter().find(|m| m.range.end == offset)
+    }
+
+    pub(crate) fn handle_mention_trigger(
+        &mut self,
+        range: &Range<usize>,
+        new_text: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(provider) = self.mention_provider.clone() else {
+            return;
+        };
+
+        let menu = match self.context_menu.as_ref() {
+            Some(ContextMenu::Mention(menu)) => Some(menu.clone()),
+            _ => None,
+        };
+
+        let (trigger, start_offset) = match menu.as_ref().and_then(|menu| menu.read(cx).trigger()) {
+            Some(trigger) => trigger,
+            None => {
+                let mut chars = new_text.chars();
+                let (Some(c), None) = (chars.next(), chars.next()) else {
+                    return;
+                };
+                if !provider.trigger_characters().contains(&c) {
+                    return;
+                }
+                let prev_offset = self
+                    .text
+                    .clip_offset(range.start.saturating_sub(1), Bias::Left);
+                let after_whitespace = range.start == 0
+                    || self
+                        .text
+                        .char_at(prev_offset)
+                        .map_or(true, |c| c.is_whitespace());
+                if !after_whitespace {
+                    return;
+                }
+
+                (c, range.start)
+            }
+        };
+
+        let cursor = self.cursor();
+        let query_start = start_offset + trigger.len_utf8();
+        if cursor < query_start {
+            self.hide_context_menu(cx);
+            return;
+        }
+
+        let query = self.text.slice(query_start..cursor).to_string();
+        if query.contains(char::is_whitespace) {
+            self.hide_context_menu(cx);
+            return;
+        }
+
+        let menu = match menu {
+            Some(menu) => menu,
+            None => {
+                let menu = MentionMenu::new(cx.entity(), window, cx);
+                self.context_menu = Some(ContextMenu::Mention(menu.clone()));
+                menu
+            }
+        };
+        menu.update(cx, |menu, _| menu.set_trigger(trigger, start_offset));
+
+        let items = provider.mentions(trigger, &query, window, cx);
+        self._context_menu_task = cx.spawn_in(window, async move |state, cx| {
+            let items = items.await.unwrap_or_default();
+
+            state
+                .update_in(cx, |state, window, cx| {
+                    if items.is_empty() || !state.focus_handle.is_focused(window) {
+                        _ = menu.update(cx, |menu, cx| menu.hide(cx));
+                        return;
+                    }
+
+                    _ = menu.update(cx, |menu, cx| menu.show(items, window, cx));
+                    cx.notify();
+                })
+                .ok();
+
+            Ok(())
+        });
+    }
+
+    /// Replace the trigger and query with the selected item, and record it as a [`Mention`].
+    pub(crate) fn insert_mention(
+        &mut self,
+        trigger: char,
+        start_offset: usize,
+        item: &MentionItem,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let token = format!("{}{}", trigger, item.label);
+        let end_offset = self.cursor().max(start_offset);
+        self.replace_text_in_range_silent(
+            Some(self.range_to_utf16(&(start_offset..end_offset))),
+            &format!("{} ", token),
+            window,
+            cx,
+        );
+
+        let mention = Mention {
+            trigger,
+            id: item.id.clone(),
+            label: item.label.clone(),
+            range: start_offset..start_offset + token.len(),
+        };
+        let ix = self
+            .mentions
+            .partition_point(|m| m.range.start < mention.range.start);
+        self.mentions.insert(ix, mention);
+        cx.notify();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{adjust_mentions, Mention};
+
+    fn mention(range: std::ops::Range<usize>) -> Mention {
+        Mention {
+            trigger: '@',
+            id: "id".into(),
+            label: "label".into(),
+            range,
+        }
+    }
+
+    #[test]
+    fn test_adjust_mentions() {
+        // "Hi @jason and @mike"
+        let mut mentions = vec![mention(3..9), mention(14..19)];
+
+        // Insert "!" before all mentions.
+        adjust_mentions(&mut mentions, &(2..2), 1);
+        assert_eq!(mentions[0].range, 4..10);
+        assert_eq!(mentions[1].range, 15..20);
+
+        // Insert text right after the first mention keeps it.
+        adjust_mentions(&mut mentions, &(10..10), 3);
+        assert_eq!(mentions[0].range, 4..10);
+        assert_eq!(mentions[1].range, 18..23);
+
+        // Delete text between the mentions.
+        adjust_mentions(&mut mentions, &(10..13), 0);
+        assert_eq!(mentions[0].range, 4..10);
+        assert_eq!(mentions[1].range, 15..20);
+
+        // Editing inside a mention removes it.
+        adjust_mentions(&mut mentions, &(6..6), 1);
+        assert_eq!(mentions.len(), 1);
+        assert_eq!(mentions[0].range, 16..21);
+
+        // Deleting across a mention removes it.
+        adjust_mentions(&mut mentions, &(15..22), 0);
+        assert!(mentions.is_empty());
+    }
+}
@@ -2,11 +2,13 @@ mod blink_cursor;
 mod change;
 mod clear_button;
 mod cursor;
+mod dictation;
 mod element;
 mod indent;
 mod input;
 mod lsp;
 mod mask_pattern;
+mod mention;
 mod mode;
 mod movement;
 mod number_input;
@@ -23,6 +25,7 @@ pub use indent::TabSize;
 pub use input::*;
 pub use lsp::*;
 pub use mask_pattern::MaskPattern;
+pub use mention::*;
 pub use number_input::{NumberInput, NumberInputEvent, StepAction};
 pub use otp_input::*;
 pub use state::*;
@@ -12,7 +12,9 @@ const MAX_MENU_WIDTH: Pixels = px(320.);
 const MAX_MENU_HEIGHT: Pixels = px(480.);
 
 use crate::{
-    actions, h_flex,
+    actions,
+    elevation::Layer,
+    h_flex,
     input::{self, popovers::editor_popover, InputState},
     list::{List, ListDelegate, ListEvent, ListState},
     ActiveTheme, IndexPath, Selectable,
@@ -340,6 +342,7 @@ impl Render for CodeActionMenu {
                     this.hide(cx);
                 })),
         )
+        .with_priority(Layer::Dropdown.priority())
         .into_any_element()
     }
 }
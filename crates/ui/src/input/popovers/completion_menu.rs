@@ -13,7 +13,9 @@ const MAX_MENU_HEIGHT: Pixels = px(240.);
 const POPOVER_GAP: Pixels = px(4.);
 
 use crate::{
-    actions, h_flex,
+    actions,
+    elevation::Layer,
+    h_flex,
     input::{
         self,
         popovers::{editor_popover, render_markdown},
@@ -460,6 +462,7 @@ impl Render for CompletionMenu {
                     this.hide(cx);
                 })),
         )
+        .with_priority(Layer::Dropdown.priority())
         .into_any_element()
     }
 }
@@ -1,13 +1,16 @@
 use gpui::{
     anchored, deferred, div, prelude::FluentBuilder as _, px, App, AppContext as _, Context,
-    Corner, DismissEvent, Entity, IntoElement, MouseDownEvent, ParentElement as _, Pixels, Point,
-    Render, Styled, Subscription, Window,
+    Corner, DismissEvent, Entity, EntityInputHandler as _, IntoElement, MouseDownEvent,
+    ParentElement as _, Pixels, Point, Render, SharedString, Styled, Subscription, Window,
 };
 use rust_i18n::t;
+use std::ops::Range;
 
 use crate::{
-    input::{self, popovers::ContextMenu, InputState},
-    menu::PopupMenu,
+    elevation::Layer,
+    input::{self, popovers::ContextMenu, InputState, RopeExt as _},
+    menu::{PopupMenu, PopupMenuItem},
+    spellcheck,
 };
 
 /// Context menu for mouse right clicks.
@@ -46,11 +49,50 @@ impl InputState {
         let is_selected = !self.selected_range.is_empty();
         let has_paste = is_enable && cx.read_from_clipboard().is_some();
 
+        let misspelled = self.misspelled_word_at(offset);
+        let editor = cx.entity();
+
         let action_context = self.focus_handle.clone();
         self.mouse_context_menu.update(cx, |this, cx| {
             this.mouse_position = event.position;
             this.menu.update(cx, |menu, cx| {
                 let new_menu = PopupMenu::new(cx)
+                    .when_some(misspelled, |mut m, (range, word, suggestions)| {
+                        if suggestions.is_empty() {
+                            m = m.item(
+                                PopupMenuItem::new(t!("Input.No Suggestions")).disabled(true),
+                            );
+                        }
+                        for suggestion in suggestions {
+                            let editor = editor.clone();
+                            let range = range.clone();
+                            m = m.item(PopupMenuItem::new(suggestion.clone()).on_click(
+                                move |_, window, cx| {
+                                    editor.update(cx, |state, cx| {
+                                        let range_utf16 = state.range_to_utf16(&range);
+                                        state.replace_text_in_range(
+                                            Some(range_utf16),
+                                            &suggestion,
+                                            window,
+                                            cx,
+                                        );
+                                    });
+                                },
+                            ));
+                        }
+                        let editor = editor.clone();
+                        m.item(PopupMenuItem::new(t!("Input.Add to Dictionary")).on_click(
+                            move |_, _, cx| {
+                                editor.update(cx, |state, cx| {
+                                    if let Some(spell_checker) = &state.spell_checker {
+                                        spell_checker.add_word(&word);
+                                    }
+                                    cx.notify();
+                                });
+                            },
+                        ))
+                        .separator()
+                    })
                     .when(is_code_editor, |m| {
                         m.menu_with_enable(
                             t!("Input.Go to Definition"),
@@ -83,6 +125,33 @@ impl InputState {
             cx.notify();
         });
     }
+
+    /// Returns the range, the word and the suggestions of the misspelled word at the `offset`.
+    fn misspelled_word_at(
+        &self,
+        offset: usize,
+    ) -> Option<(Range<usize>, String, Vec<SharedString>)> {
+        let spell_checker = self.spell_checker.as_ref()?;
+        if self.disabled || self.masked || self.mode.is_code_editor() {
+            return None;
+        }
+
+        let row = self.text.offset_to_point(offset).row;
+        let line_start = self.text.line_start_offset(row);
+        let line = self.text.slice_line(row).to_string();
+        let range = spellcheck::word_at(&line, offset - line_start)?;
+        let word = line[range.clone()].to_string();
+        if spell_checker.check(&word) {
+            return None;
+        }
+
+        let suggestions = spell_checker.suggestions(&word);
+        Some((
+            line_start + range.start..line_start + range.end,
+            word,
+            suggestions,
+        ))
+    }
 }
 
 impl MouseContextMenu {
@@ -143,6 +212,7 @@ impl Render for MouseContextMenu {
                         .child(self.menu.clone()),
                 ),
         )
+        .with_priority(Layer::Menu.priority())
         .into_any_element()
     }
 }
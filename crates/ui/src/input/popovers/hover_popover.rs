@@ -8,6 +8,7 @@ use gpui::{
 };
 
 use crate::{
+    elevation::Layer,
     input::{popovers::render_markdown, InputState},
     StyledExt,
 };
@@ -198,13 +199,14 @@ impl Element for Popover {
                 .p_1()
                 .text_xs()
                 .popover_style(cx)
-                .shadow_md()
+                .elevation(Layer::Tooltip.elevation(), cx)
                 .max_w(max_width)
                 .max_h(max_height)
                 .overflow_y_scroll()
                 .refine_style(&self.style)
                 .child((self.content_builder)(window, cx)),
         )
+        .with_priority(Layer::Tooltip.priority())
         .into_any_element();
 
         let popover_size = popover.layout_as_root(AvailableSpace::min_size(), window, cx);
@@ -0,0 +1,339 @@
+use std::rc::Rc;
+
+use gpui::{
+    deferred, div, prelude::FluentBuilder, px, relative, Action, AnyElement, App, AppContext,
+    Context, DismissEvent, Empty, Entity, EventEmitter, InteractiveElement as _, IntoElement,
+    ParentElement, Pixels, Point, Render, RenderOnce, Styled, Subscription, Window,
+};
+
+const MAX_MENU_WIDTH: Pixels = px(320.);
+const MAX_MENU_HEIGHT: Pixels = px(240.);
+
+use crate::{
+    actions,
+    elevation::Layer,
+    h_flex,
+    input::{self, popovers::editor_popover, InputState, MentionItem},
+    label::Label,
+    list::{List, ListDelegate, ListEvent, ListState},
+    ActiveTheme, IndexPath, Selectable,
+};
+
+struct MenuDelegate {
+    menu: Entity<MentionMenu>,
+    trigger: char,
+    items: Vec<Rc<MentionItem>>,
+    selected_ix: usize,
+}
+
+impl MenuDelegate {
+    fn set_items(&mut self, items: Vec<MentionItem>) {
+        self.items = items.into_iter().map(Rc::new).collect();
+        self.selected_ix = 0;
+    }
+
+    fn selected_item(&self) -> Option<&Rc<MentionItem>> {
+        self.items.get(self.selected_ix)
+    }
+}
+
+#[derive(IntoElement)]
+struct MenuItem {
+    ix: usize,
+    trigger: char,
+    item: Rc<MentionItem>,
+    children: Vec<AnyElement>,
+    selected: bool,
+}
+
+impl MenuItem {
+    fn new(ix: usize, trigger: char, item: Rc<MentionItem>) -> Self {
+        Self {
+            ix,
+            trigger,
+            item,
+            children: vec![],
+            selected: false,
+        }
+    }
+}
+
+impl Selectable for MenuItem {
+    fn selected(mut self, selected: bool) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    fn is_selected(&self) -> bool {
+        self.selected
+    }
+}
+
+impl ParentElement for MenuItem {
+    fn extend(&mut self, elements: impl IntoIterator<Item = AnyElement>) {
+        self.children.extend(elements);
+    }
+}
+
+impl RenderOnce for MenuItem {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let item = self.item;
+
+        h_flex()
+            .id(self.ix)
+            .gap_2()
+            .p_1()
+            .text_xs()
+            .line_height(relative(1.))
+            .rounded_sm()
+            .hover(|this| this.bg(cx.theme().accent.opacity(0.8)))
+            .when(self.selected, |this| {
+                this.bg(cx.theme().accent)
+                    .text_color(cx.theme().accent_foreground)
+            })
+            .child(div().child(format!("{}{}", self.trigger, item.label)))
+            .when_some(item.detail.clone(), |this, detail| {
+                this.child(Label::new(detail).text_color(cx.theme().muted_foreground))
+            })
+            .children(self.children)
+    }
+}
+
+impl EventEmitter<DismissEvent> for MenuDelegate {}
+
+impl ListDelegate for MenuDelegate {
+    type Item = MenuItem;
+
+    fn items_count(&self, _: usize, _: &App) -> usize {
+        self.items.len()
+    }
+
+    fn render_item(&self, ix: IndexPath, _: &mut Window, _: &mut App) -> Option<Self::Item> {
+        let item = self.items.get(ix.row)?;
+        Some(MenuItem::new(ix.row, self.trigger, item.clone()))
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: Option<IndexPath>,
+        _: &mut Window,
+        cx: &mut Context<ListState<Self>>,
+    ) {
+        self.selected_ix = ix.map(|i| i.row).unwrap_or(0);
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<ListState<Self>>) {
+        let Some(item) = self.selected_item().cloned() else {
+            return;
+        };
+
+        self.menu.update(cx, |this, cx| {
+            this.select_item(&item, window, cx);
+        });
+    }
+}
+
+/// A suggestion menu for mentions and slash-commands.
+pub struct MentionMenu {
+    state: Entity<InputState>,
+    list: Entity<ListState<MenuDelegate>>,
+    open: bool,
+    /// The trigger character and its offset, `None` if the menu is not active.
+    trigger: Option<(char, usize)>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl MentionMenu {
+    /// Creates a new `MentionMenu`.
+    ///
+    /// NOTE: This element should not call from InputState::new, unless that will stack overflow.
+    pub(crate) fn new(
+        state: Entity<InputState>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Entity<Self> {
+        cx.new(|cx| {
+            let view = cx.entity();
+            let menu = MenuDelegate {
+                menu: view,
+                trigger: '@',
+                items: vec![],
+                selected_ix: 0,
+            };
+
+            let list = cx.new(|cx| ListState::new(menu, window, cx));
+
+            let _subscriptions =
+                vec![
+                    cx.subscribe(&list, |this: &mut Self, _, ev: &ListEvent, cx| {
+                        match ev {
+                            ListEvent::Confirm(_) => {
+                                this.hide(cx);
+                            }
+                            _ => {}
+                        }
+                        cx.notify();
+                    }),
+                ];
+
+            Self {
+                state,
+                list,
+                open: false,
+                trigger: None,
+                _subscriptions,
+            }
+        })
+    }
+
+    /// Return the trigger character and its offset, if the menu is active.
+    pub(crate) fn trigger(&self) -> Option<(char, usize)> {
+        self.trigger
+    }
+
+    pub(crate) fn set_trigger(&mut self, trigger: char, start_offset: usize) {
+        self.trigger = Some((trigger, start_offset));
+    }
+
+    fn select_item(&mut self, item: &MentionItem, window: &mut Window, cx: &mut Context<Self>) {
+        let state = self.state.clone();
+        let item = item.clone();
+        let Some((trigger, start_offset)) = self.trigger else {
+            return;
+        };
+
+        cx.spawn_in(window, async move |_, cx| {
+            state.update_in(cx, |state, window, cx| {
+                state.insert_mention(trigger, start_offset, &item, window, cx);
+                state.focus(window, cx);
+            })
+        })
+        .detach();
+
+        self.hide(cx);
+    }
+
+    pub(crate) fn handle_action(
+        &mut self,
+        action: Box<dyn Action>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        if !self.open {
+            return false;
+        }
+
+        cx.propagate();
+        if action.partial_eq(&input::Enter { secondary: false }) {
+            self.on_action_enter(window, cx);
+        } else if action.partial_eq(&input::Escape) {
+            self.hide(cx);
+        } else if action.partial_eq(&input::MoveUp) {
+            self.list.update(cx, |this, cx| {
+                this.on_action_select_prev(&actions::SelectUp, window, cx)
+            });
+        } else if action.partial_eq(&input::MoveDown) {
+            self.list.update(cx, |this, cx| {
+                this.on_action_select_next(&actions::SelectDown, window, cx)
+            });
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    fn on_action_enter(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(item) = self.list.read(cx).delegate().selected_item().cloned() else {
+            return;
+        };
+        self.select_item(&item, window, cx);
+    }
+
+    pub(crate) fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Hide the menu and reset the trigger.
+    pub(crate) fn hide(&mut self, cx: &mut Context<Self>) {
+        self.open = false;
+        self.trigger = None;
+        cx.notify();
+    }
+
+    pub(crate) fn show(
+        &mut self,
+        items: impl Into<Vec<MentionItem>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let items = items.into();
+        self.open = true;
+        self.list.update(cx, |this, cx| {
+            let longest_ix = items
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, item)| {
+                    item.label.len() + item.detail.as_ref().map(|d| d.len()).unwrap_or(0)
+                })
+                .map(|(ix, _)| ix)
+                .unwrap_or(0);
+
+            if let Some((trigger, _)) = self.trigger {
+                this.delegate_mut().trigger = trigger;
+            }
+            this.delegate_mut().set_items(items);
+            this.set_selected_index(Some(IndexPath::new(0)), window, cx);
+            this.set_item_to_measure_index(IndexPath::new(longest_ix), window, cx);
+        });
+
+        cx.notify();
+    }
+
+    fn origin(&self, cx: &App) -> Option<Point<Pixels>> {
+        let state = self.state.read(cx);
+        let last_layout = state.last_layout.as_ref()?;
+        let cursor_origin = last_layout.cursor_bounds.map(|b| b.origin)?;
+        let scroll_origin = state.scroll_handle.offset();
+
+        Some(
+            scroll_origin + cursor_origin - state.input_bounds.origin
+                + Point::new(-px(4.), last_layout.line_height + px(4.)),
+        )
+    }
+}
+
+impl Render for MentionMenu {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if !self.open {
+            return Empty.into_any_element();
+        }
+
+        if self.list.read(cx).delegate().items.is_empty() {
+            self.open = false;
+            return Empty.into_any_element();
+        }
+
+        let Some(pos) = self.origin(cx) else {
+            return Empty.into_any_element();
+        };
+
+        let max_width = MAX_MENU_WIDTH.min(window.bounds().size.width - pos.x);
+
+        deferred(
+            editor_popover("mention-menu", cx)
+                .absolute()
+                .left(pos.x)
+                .top(pos.y)
+                .max_w(max_width)
+                .min_w(px(120.))
+                .child(List::new(&self.list).max_h(MAX_MENU_HEIGHT))
+                .on_mouse_down_out(cx.listener(|this, _, _, cx| {
+                    this.hide(cx);
+                })),
+        )
+        .with_priority(Layer::Dropdown.priority())
+        .into_any_element()
+    }
+}
@@ -3,12 +3,14 @@ mod completion_menu;
 mod context_menu;
 mod diagnostic_popover;
 mod hover_popover;
+mod mention_menu;
 
 pub(crate) use code_action_menu::*;
 pub(crate) use completion_menu::*;
 pub(crate) use context_menu::*;
 pub(crate) use diagnostic_popover::*;
 pub(crate) use hover_popover::*;
+pub(crate) use mention_menu::*;
 
 use gpui::{
     div, px, rems, App, Div, ElementId, Entity, InteractiveElement as _, IntoElement, SharedString,
@@ -16,6 +18,7 @@ use gpui::{
 };
 
 use crate::{
+    elevation::Layer,
     text::{TextView, TextViewStyle},
     ActiveTheme, StyledExt as _,
 };
@@ -24,6 +27,7 @@ pub(crate) enum ContextMenu {
     Completion(Entity<CompletionMenu>),
     CodeAction(Entity<CodeActionMenu>),
     MouseContext(Entity<MouseContextMenu>),
+    Mention(Entity<MentionMenu>),
 }
 
 impl ContextMenu {
@@ -32,6 +36,7 @@ impl ContextMenu {
             ContextMenu::Completion(menu) => menu.read(cx).is_open(),
             ContextMenu::CodeAction(menu) => menu.read(cx).is_open(),
             ContextMenu::MouseContext(menu) => menu.read(cx).is_open(),
+            ContextMenu::Mention(menu) => menu.read(cx).is_open(),
         }
     }
 
@@ -40,6 +45,7 @@ impl ContextMenu {
             ContextMenu::Completion(menu) => menu.clone().into_any_element(),
             ContextMenu::CodeAction(menu) => menu.clone().into_any_element(),
             ContextMenu::MouseContext(menu) => menu.clone().into_any_element(),
+            ContextMenu::Mention(menu) => menu.clone().into_any_element(),
         }
     }
 }
@@ -75,7 +81,7 @@ pub(super) fn editor_popover(id: impl Into<ElementId>, cx: &App) -> Stateful<Div
         .flex_none()
         .occlude()
         .popover_style(cx)
-        .shadow_md()
+        .elevation(Layer::Dropdown.elevation(), cx)
         .text_xs()
         .p_1()
 }
@@ -7,6 +7,7 @@ use gpui::{
     Entity, FocusHandle, Focusable, Half, InteractiveElement as _, IntoElement, KeyBinding,
     ParentElement as _, Pixels, Render, Styled, Subscription, Window,
 };
+use regex::{Regex, RegexBuilder};
 use ropey::Rope;
 
 use crate::{
@@ -34,6 +35,8 @@ pub(super) fn init(cx: &mut App) {
 pub struct SearchMatcher {
     text: Rope,
     pub query: Option<AhoCorasick>,
+    /// The query in regular expression mode, the replace text can refer the capture groups.
+    regex: Option<Regex>,
 
     pub(super) matched_ranges: Rc<Vec<Range<usize>>>,
     pub(super) current_match_ix: usize,
@@ -46,6 +49,7 @@ impl SearchMatcher {
         Self {
             text: "".into(),
             query: None,
+            regex: None,
             matched_ranges: Rc::new(Vec::new()),
             current_match_ix: 0,
             replacing: false,
@@ -64,7 +68,15 @@ impl SearchMatcher {
 
     fn update_matches(&mut self) {
         let mut new_ranges = Vec::new();
-        if let Some(query) = &self.query {
+        if let Some(regex) = &self.regex {
+            let text = self.text.to_string();
+            new_ranges.extend(
+                regex
+                    .find_iter(&text)
+                    .filter(|m| !m.is_empty())
+                    .map(|m| m.range()),
+            );
+        } else if let Some(query) = &self.query {
             let text = self.text.to_string();
             // FIXME: Use stream find
             let matches = query.stream_find_iter(text.as_bytes());
@@ -82,20 +94,61 @@ impl SearchMatcher {
     }
 
     /// Update the search query and reset the current match index.
-    pub fn update_query(&mut self, query: &str, case_insensitive: bool) {
-        if query.len() > 0 {
+    ///
+    /// If `use_regex` is true, the query is a regular expression, an invalid expression matches nothing.
+    pub fn update_query(&mut self, query: &str, case_insensitive: bool, use_regex: bool) {
+        self.query = None;
+        self.regex = None;
+        if use_regex {
+            self.regex = RegexBuilder::new(query)
+                .case_insensitive(case_insensitive)
+                .build()
+                .ok()
+                .filter(|_| !query.is_empty());
+        } else if query.len() > 0 {
             self.query = Some(
                 AhoCorasick::builder()
                     .ascii_case_insensitive(case_insensitive)
                     .build(&[query.to_string()])
                     .expect("failed to build AhoCorasick query in SearchMatcher"),
             );
-        } else {
-            self.query = None;
         }
         self.update_matches();
     }
 
+    /// Returns the replace text of each of the matched `ranges`.
+    ///
+    /// In regex mode, the `$1` or `${name}` in the `replace_text` are expanded to the capture groups.
+    /// If `preserve_case` is true, the replacement follows the case of the matched text.
+    fn replacements(
+        &self,
+        ranges: &[Range<usize>],
+        replace_text: &str,
+        preserve_case: bool,
+    ) -> Vec<String> {
+        let text = self.text.to_string();
+        ranges
+            .iter()
+            .map(|range| {
+                let mut new_text = String::new();
+                match self
+                    .regex
+                    .as_ref()
+                    .and_then(|regex| regex.captures_at(&text, range.start))
+                {
+                    Some(captures) => captures.expand(replace_text, &mut new_text),
+                    None => new_text.push_str(replace_text),
+                }
+
+                if preserve_case {
+                    apply_case(&text[range.clone()], &new_text)
+                } else {
+                    new_text
+                }
+            })
+            .collect()
+    }
+
     /// Returns the number of matches found.
     #[allow(unused)]
     #[inline]
@@ -125,6 +178,29 @@ impl SearchMatcher {
     }
 }
 
+/// Returns the `new_text` in the case of the `matched` text: all uppercase, all lowercase or capitalized.
+fn apply_case(matched: &str, new_text: &str) -> String {
+    let mut letters = matched.chars().filter(|c| c.is_alphabetic());
+    let Some(first) = letters.next() else {
+        return new_text.to_string();
+    };
+    let rest = letters.collect::<Vec<_>>();
+
+    if first.is_uppercase() && rest.iter().all(|c| c.is_uppercase()) && !rest.is_empty() {
+        new_text.to_uppercase()
+    } else if first.is_lowercase() && rest.iter().all(|c| c.is_lowercase()) {
+        new_text.to_lowercase()
+    } else if first.is_uppercase() && rest.iter().all(|c| c.is_lowercase()) {
+        let mut chars = new_text.chars();
+        match chars.next() {
+            Some(c) => c.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        }
+    } else {
+        new_text.to_string()
+    }
+}
+
 impl Iterator for SearchMatcher {
     type Item = Range<usize>;
 
@@ -165,6 +241,10 @@ pub(super) struct SearchPanel {
     search_input: Entity<InputState>,
     replace_input: Entity<InputState>,
     case_insensitive: bool,
+    /// Use the regular expression to search.
+    use_regex: bool,
+    /// Preserve the case of the matched text on replace.
+    preserve_case: bool,
     replace_mode: bool,
     matcher: SearchMatcher,
     input_width: Pixels,
@@ -238,6 +318,8 @@ impl SearchPanel {
                 search_input,
                 replace_input,
                 case_insensitive: true,
+                use_regex: false,
+                preserve_case: false,
                 replace_mode: false,
                 matcher: SearchMatcher::new(),
                 open: true,
@@ -275,7 +357,7 @@ impl SearchPanel {
             .map(|l| l.visible_range_offset.clone());
 
         self.matcher
-            .update_query(query.as_str(), self.case_insensitive);
+            .update_query(query.as_str(), self.case_insensitive, self.use_regex);
 
         if let Some(visible_range_offset) = visible_range_offset {
             self.matcher
@@ -331,7 +413,7 @@ impl SearchPanel {
     }
 
     fn replace_next(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        let new_text = self.replace_input.read(cx).value();
+        let replace_text = self.replace_input.read(cx).value();
         self.matcher.replacing = true;
         if let Some(range) = self
             .matcher
@@ -340,6 +422,14 @@ impl SearchPanel {
             .cloned()
         {
             let text_state = self.editor.clone();
+            let new_text = self
+                .matcher
+                .replacements(
+                    std::slice::from_ref(&range),
+                    &replace_text,
+                    self.preserve_case,
+                )
+                .remove(0);
 
             let next_range = self.matcher.peek().unwrap_or(range.clone());
             cx.spawn_in(window, async move |_, cx| {
@@ -347,6 +437,8 @@ impl SearchPanel {
                     text_state.update(cx, |state, cx| {
                         let range_utf16 = state.range_to_utf16(&range);
                         state.scroll_to(next_range.end, cx);
+                        // Each replace is an undo step, not grouped with the typing.
+                        state.history.separate();
                         state.replace_text_in_range_silent(
                             Some(range_utf16),
                             new_text.as_str(),
@@ -361,12 +453,15 @@ impl SearchPanel {
     }
 
     fn replace_all(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        let new_text = self.replace_input.read(cx).value();
+        let replace_text = self.replace_input.read(cx).value();
         self.matcher.replacing = true;
         let ranges = self.matcher.matched_ranges.clone();
         if ranges.is_empty() {
             return;
         }
+        let new_texts = self
+            .matcher
+            .replacements(&ranges, &replace_text, self.preserve_case);
 
         let editor = self.editor.clone();
         cx.spawn_in(window, async move |_, cx| {
@@ -374,9 +469,11 @@ impl SearchPanel {
                 editor.update(cx, |state, cx| {
                     // Replace from the end to avoid messing up the ranges.
                     let mut rope = state.text.clone();
-                    for range in ranges.iter().rev() {
+                    for (range, new_text) in ranges.iter().zip(new_texts.iter()).rev() {
                         rope.replace(range.clone(), new_text.as_str());
                     }
+                    // Replace the whole text at once to undo all the replacements in one step.
+                    state.history.separate();
                     state.replace_text_in_range_silent(
                         Some(0..state.text.len()),
                         &rope.to_string(),
@@ -436,17 +533,35 @@ impl Render for SearchPanel {
                                 Input::new(&self.search_input)
                                     .focus_bordered(false)
                                     .suffix(
-                                        Button::new("case-insensitive")
-                                            .selected(!self.case_insensitive)
-                                            .xsmall()
-                                            .compact()
-                                            .ghost()
-                                            .icon(IconName::CaseSensitive)
-                                            .on_click(cx.listener(|this, _, _, cx| {
-                                                this.case_insensitive = !this.case_insensitive;
-                                                this.update_search_query(cx);
-                                                cx.notify();
-                                            })),
+                                        h_flex()
+                                            .child(
+                                                Button::new("case-insensitive")
+                                                    .selected(!self.case_insensitive)
+                                                    .xsmall()
+                                                    .compact()
+                                                    .ghost()
+                                                    .icon(IconName::CaseSensitive)
+                                                    .on_click(cx.listener(|this, _, _, cx| {
+                                                        this.case_insensitive =
+                                                            !this.case_insensitive;
+                                                        this.update_search_query(cx);
+                                                        cx.notify();
+                                                    })),
+                                            )
+                                            .child(
+                                                Button::new("use-regex")
+                                                    .selected(self.use_regex)
+                                                    .xsmall()
+                                                    .compact()
+                                                    .ghost()
+                                                    .icon(IconName::Asterisk)
+                                                    .tooltip(t!("Input.Use Regular Expression"))
+                                                    .on_click(cx.listener(|this, _, _, cx| {
+                                                        this.use_regex = !this.use_regex;
+                                                        this.update_search_query(cx);
+                                                        cx.notify();
+                                                    })),
+                                            ),
                                     )
                                     .small()
                                     .w_full()
@@ -531,6 +646,19 @@ impl Render for SearchPanel {
                         .child(
                             Input::new(&self.replace_input)
                                 .focus_bordered(false)
+                                .suffix(
+                                    Button::new("preserve-case")
+                                        .selected(self.preserve_case)
+                                        .xsmall()
+                                        .compact()
+                                        .ghost()
+                                        .icon(IconName::ALargeSmall)
+                                        .tooltip(t!("Input.Preserve Case"))
+                                        .on_click(cx.listener(|this, _, _, cx| {
+                                            this.preserve_case = !this.preserve_case;
+                                            cx.notify();
+                                        })),
+                                )
                                 .small()
                                 .w(self.input_width)
                                 .shadow_none(),
@@ -567,7 +695,7 @@ mod tests {
     fn test_search() {
         let mut matcher = SearchMatcher::new();
         matcher.update(&Rope::from("Hello 世界 this is a Is test string."));
-        matcher.update_query("Is", true);
+        matcher.update_query("Is", true, false);
 
         assert_eq!(matcher.len(), 3);
         let mut matches = matcher.clone();
@@ -585,7 +713,7 @@ mod tests {
         assert_eq!(matches.current_match_ix, 0);
         assert_eq!(matches.next_back(), Some(23..25));
 
-        matcher.update_query("IS", false);
+        matcher.update_query("IS", false, false);
         assert_eq!(matcher.len(), 0);
         assert_eq!(matcher.next(), None);
         assert_eq!(matcher.next_back(), None);
@@ -595,7 +723,7 @@ mod tests {
     fn test_search_label() {
         let mut matcher = SearchMatcher::new();
         matcher.update(&Rope::from("Hello 世界 this is a Is test string."));
-        matcher.update_query("Is", true);
+        matcher.update_query("Is", true, false);
         assert_eq!(matcher.label(), "1/3");
         matcher.next();
         assert_eq!(matcher.label(), "2/3");
@@ -604,7 +732,7 @@ mod tests {
         matcher.next();
         assert_eq!(matcher.label(), "1/3");
 
-        matcher.update_query("IS", false);
+        matcher.update_query("IS", false, false);
         assert_eq!(matcher.label(), "0/0");
     }
 
@@ -630,4 +758,39 @@ mod tests {
         matcher.update_cursor_by_offset(31);
         assert_eq!(matcher.current_match_ix, 2);
     }
+
+    #[test]
+    fn test_regex_replacements() {
+        let mut matcher = SearchMatcher::new();
+        matcher.update(&Rope::from("let foo_bar = 1;\nlet baz_qux = 2;"));
+        matcher.update_query(r"(\w+)_(\w+)", true, true);
+        assert_eq!(matcher.matched_ranges.as_slice(), &[4..11, 21..28]);
+
+        let ranges = matcher.matched_ranges.clone();
+        assert_eq!(
+            matcher.replacements(&ranges, "${2}_$1", false),
+            vec!["bar_foo", "qux_baz"]
+        );
+
+        // Invalid regex matches nothing.
+        matcher.update_query("(", true, true);
+        assert_eq!(matcher.len(), 0);
+    }
+
+    #[test]
+    fn test_preserve_case() {
+        let mut matcher = SearchMatcher::new();
+        matcher.update(&Rope::from("foo Foo FOO fOO"));
+        matcher.update_query("foo", true, false);
+
+        let ranges = matcher.matched_ranges.clone();
+        assert_eq!(
+            matcher.replacements(&ranges, "bar", true),
+            vec!["bar", "Bar", "BAR", "bar"]
+        );
+        assert_eq!(
+            matcher.replacements(&ranges, "bar", false),
+            vec!["bar", "bar", "bar", "bar"]
+        );
+    }
 }
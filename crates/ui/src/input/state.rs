@@ -19,8 +19,9 @@ use sum_tree::Bias;
 use unicode_segmentation::*;
 
 use super::{
-    blink_cursor::BlinkCursor, change::Change, element::TextElement, mask_pattern::MaskPattern,
-    mode::InputMode, number_input, text_wrapper::TextWrapper, TabSize,
+    blink_cursor::BlinkCursor, change::Change, dictation, element::TextElement,
+    mask_pattern::MaskPattern, mention, mode::InputMode, number_input, text_wrapper::TextWrapper,
+    Mention, MentionProvider, TabSize,
 };
 use crate::actions::{SelectDown, SelectLeft, SelectRight, SelectUp};
 use crate::input::{
@@ -32,7 +33,7 @@ use crate::input::{
 };
 use crate::input::{RopeExt as _, Selection};
 use crate::{highlighter::DiagnosticSet, input::text_wrapper::LineItem};
-use crate::{history::History, scroll::ScrollbarState, Root};
+use crate::{history::History, scroll::ScrollbarState, spellcheck::SpellChecker, Root};
 
 #[derive(Action, Clone, PartialEq, Eq, Deserialize)]
 #[action(namespace = input, no_json)]
@@ -277,8 +278,12 @@ pub struct InputState {
     pub(super) selection_reversed: bool,
     /// The marked range is the temporary insert text on IME typing.
     pub(super) ime_marked_range: Option<Selection>,
+    /// The range of the interim dictation text.
+    pub(super) dictation_range: Option<Range<usize>>,
     pub(super) last_layout: Option<LastLayout>,
     pub(super) last_cursor: Option<usize>,
+    /// The painted cursor bounds in the window.
+    pub(super) last_cursor_bounds: Option<Bounds<Pixels>>,
     /// The input container bounds
     pub(super) input_bounds: Bounds<Pixels>,
     /// The text bounds
@@ -315,6 +320,13 @@ pub struct InputState {
 
     pub lsp: Lsp,
 
+    /// The provider for mention and slash-command suggestions.
+    pub(super) mention_provider: Option<Rc<dyn MentionProvider>>,
+    /// The mention tokens inserted in the text, sorted by position.
+    pub(super) mentions: Vec<Mention>,
+    /// The spell checker to underline the misspelled words.
+    pub(super) spell_checker: Option<Rc<dyn SpellChecker>>,
+
     /// A flag to indicate if we have a pending update to the text.
     ///
     /// If true, will call some update (for example LSP, Syntax Highlight) before render.
@@ -380,6 +392,7 @@ impl InputState {
             selected_word_range: None,
             selection_reversed: false,
             ime_marked_range: None,
+            dictation_range: None,
             input_bounds: Bounds::default(),
             selecting: false,
             disabled: false,
@@ -394,6 +407,7 @@ impl InputState {
             last_bounds: None,
             last_selected_range: None,
             last_cursor: None,
+            last_cursor_bounds: None,
             scroll_handle: ScrollHandle::new(),
             scroll_state: ScrollbarState::default(),
             scroll_size: gpui::size(px(0.), px(0.)),
@@ -402,6 +416,9 @@ impl InputState {
             placeholder: SharedString::default(),
             mask_pattern: MaskPattern::default(),
             lsp: Lsp::default(),
+            mention_provider: None,
+            mentions: vec![],
+            spell_checker: None,
             diagnostic_popover: None,
             context_menu: None,
             mouse_context_menu,
@@ -477,6 +494,33 @@ impl InputState {
         self
     }
 
+    /// Set the [`MentionProvider`] to show suggestions when typing a trigger character like `@` or `/`.
+    ///
+    /// The selected suggestion is inserted as a [`Mention`] token, see also [`Self::mentions`].
+    pub fn mention_provider(mut self, provider: impl MentionProvider + 'static) -> Self {
+        self.mention_provider = Some(Rc::new(provider));
+        self
+    }
+
+    /// Set the [`SpellChecker`] to underline the misspelled words, and show the suggestions
+    /// in the context menu.
+    ///
+    /// The spell checking is skipped for the masked input and the code editor.
+    pub fn spell_checker(mut self, spell_checker: impl SpellChecker) -> Self {
+        self.spell_checker = Some(Rc::new(spell_checker));
+        self
+    }
+
+    /// Set the [`SpellChecker`], `None` to disable the spell checking.
+    pub fn set_spell_checker(
+        &mut self,
+        spell_checker: Option<Rc<dyn SpellChecker>>,
+        cx: &mut Context<Self>,
+    ) {
+        self.spell_checker = spell_checker;
+        cx.notify();
+    }
+
     /// Set placeholder
     pub fn placeholder(mut self, placeholder: impl Into<SharedString>) -> Self {
         self.placeholder = placeholder.into();
@@ -1040,7 +1084,12 @@ impl InputState {
 
     pub(super) fn backspace(&mut self, _: &Backspace, window: &mut Window, cx: &mut Context<Self>) {
         if self.selected_range.is_empty() {
-            self.select_to(self.previous_boundary(self.cursor()), cx)
+            // Delete the whole mention token at once.
+            if let Some(range) = self.mention_at_end(self.cursor()).map(|m| m.range.clone()) {
+                self.selected_range = range.into();
+            } else {
+                self.select_to(self.previous_boundary(self.cursor()), cx)
+            }
         }
         self.replace_text_in_range(None, "", window, cx);
         self.pause_blink_cursor(cx);
@@ -1476,6 +1525,14 @@ impl InputState {
         }
     }
 
+    /// Returns the bounds of the cursor in the window from the last paint, `None` if not painted.
+    ///
+    /// This is reported to the OS to position the IME candidate window when the composing
+    /// text is out of the visible lines.
+    pub fn cursor_bounds(&self) -> Option<Bounds<Pixels>> {
+        self.last_cursor_bounds
+    }
+
     pub(crate) fn index_for_mouse_position(&self, position: Point<Pixels>) -> usize {
         // If the text is empty, always return 0
         if self.text.len() == 0 {
@@ -2012,6 +2069,8 @@ impl EntityInputHandler for InputState {
 
         self.push_history(&old_text, &range, &new_text);
         self.history.end_grouping();
+        mention::adjust_mentions(&mut self.mentions, &range, new_text.len());
+        dictation::adjust_dictation(&mut self.dictation_range, &range, new_text.len());
         if let Some(diagnostics) = self.mode.diagnostics_mut() {
             diagnostics.reset(&self.text)
         }
@@ -2027,6 +2086,7 @@ impl EntityInputHandler for InputState {
         self.mode.update_auto_grow(&self.text_wrapper);
         if !self.silent_replace_text {
             self.handle_completion_trigger(&range, &new_text, window, cx);
+            self.handle_mention_trigger(&range, &new_text, window, cx);
         }
         cx.emit(InputEvent::Change);
         cx.notify();
@@ -2067,6 +2127,8 @@ impl EntityInputHandler for InputState {
             }
         }
 
+        mention::adjust_mentions(&mut self.mentions, &range, new_text.len());
+        dictation::adjust_dictation(&mut self.dictation_range, &range, new_text.len());
         if let Some(diagnostics) = self.mode.diagnostics_mut() {
             diagnostics.reset(&self.text)
         }
@@ -2138,8 +2200,12 @@ impl EntityInputHandler for InputState {
             y_offset += line.size(line_height).height;
         }
 
-        let start_origin = start_origin.unwrap_or_default();
-        let mut end_origin = end_origin.unwrap_or_default();
+        // Report the cursor if the range is out of the visible lines,
+        // to avoid the IME candidate window at the corner of the window.
+        let Some(start_origin) = start_origin else {
+            return self.cursor_bounds();
+        };
+        let mut end_origin = end_origin.unwrap_or(start_origin);
         // Ensure at same line.
         end_origin.y = start_origin.y;
 
@@ -0,0 +1,515 @@
+use std::{collections::BTreeMap, rc::Rc};
+
+use gpui::{
+    div, prelude::FluentBuilder as _, Action, App, AppContext as _, Context, ElementId, Entity,
+    EventEmitter, FocusHandle, InteractiveElement as _, IntoElement, KeyBinding,
+    KeyBindingContextPredicate, Keystroke, NoAction, ParentElement, Render, RenderOnce,
+    SharedString, StatefulInteractiveElement as _, StyleRefinement, Styled, Subscription, Window,
+};
+use rust_i18n::t;
+
+use crate::{
+    fuzzy::fuzzy_match,
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    kbd::Kbd,
+    v_flex, ActiveTheme, IconName, Sizable as _, StyledExt as _,
+};
+
+/// The max number of the keystrokes can be recorded for a binding, e.g. `cmd-k cmd-s`.
+const MAX_KEYSTROKES: usize = 2;
+
+/// Events emitted by the [`KeymapViewState`].
+#[derive(Debug, Clone)]
+pub enum KeymapViewEvent {
+    /// An action was rebound by the user, the new binding has been applied to the app,
+    /// save it to the user keymap to keep it after restarting.
+    Rebind(KeymapRebind),
+}
+
+/// A binding changed in the [`KeymapView`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeymapRebind {
+    /// The name of the action, e.g. `editor::Save`.
+    pub action: SharedString,
+    /// The context of the binding, `None` for the global binding.
+    pub context: Option<SharedString>,
+    /// The previous keystrokes, e.g. `cmd-s`, `None` if it was unbound.
+    pub old_keystrokes: Option<SharedString>,
+    /// The new keystrokes, e.g. `cmd-shift-s`.
+    pub new_keystrokes: SharedString,
+}
+
+/// An action with its binding in the [`KeymapView`].
+struct KeymapEntry {
+    name: SharedString,
+    title: SharedString,
+    action: Option<Rc<dyn Action>>,
+    keystrokes: Vec<Keystroke>,
+    context: Option<SharedString>,
+}
+
+impl KeymapEntry {
+    fn keystrokes_text(&self) -> String {
+        self.keystrokes
+            .iter()
+            .map(|keystroke| keystroke.unparse())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        [
+            self.title.as_ref(),
+            self.name.as_ref(),
+            &self.keystrokes_text(),
+            self.context.as_ref().map_or("", |context| context.as_ref()),
+        ]
+        .iter()
+        .any(|text| fuzzy_match(query, text).is_some())
+    }
+}
+
+/// Returns the title of the action name, e.g. `Select All` for `input::SelectAll`.
+fn humanize_action_name(name: &str) -> String {
+    let name = name.rsplit("::").next().unwrap_or(name);
+    let mut title = String::with_capacity(name.len() + 4);
+    let mut prev: Option<char> = None;
+    for c in name.chars() {
+        if c == '_' {
+            title.push(' ');
+        } else {
+            if c.is_uppercase() && prev.is_some_and(|prev| prev.is_lowercase() || prev.is_numeric())
+            {
+                title.push(' ');
+            }
+            title.push(c);
+        }
+        prev = Some(c);
+    }
+    title
+}
+
+/// Returns the entries of the bindings in the order of the keymap,
+/// the bindings disabled by the later `NoAction` are removed.
+fn load_entries(cx: &App, show_unbound: bool) -> Vec<KeymapEntry> {
+    let mut entries: Vec<KeymapEntry> = vec![];
+    for binding in cx.key_bindings().borrow().bindings() {
+        let keystrokes = binding
+            .keystrokes()
+            .iter()
+            .map(|keystroke| keystroke.inner().clone())
+            .collect::<Vec<_>>();
+        let context = binding
+            .predicate()
+            .map(|predicate| SharedString::from(predicate.to_string()));
+
+        if gpui::is_no_action(binding.action()) {
+            entries.retain(|entry| entry.context != context || entry.keystrokes != keystrokes);
+            continue;
+        }
+
+        let name = SharedString::from(binding.action().name());
+        entries.push(KeymapEntry {
+            title: humanize_action_name(&name).into(),
+            name,
+            action: Some(binding.action().boxed_clone().into()),
+            keystrokes,
+            context,
+        });
+    }
+
+    if show_unbound {
+        for name in cx.all_action_names() {
+            if *name == NoAction.name() || entries.iter().any(|entry| entry.name == *name) {
+                continue;
+            }
+
+            entries.push(KeymapEntry {
+                name: SharedString::from(*name),
+                title: humanize_action_name(name).into(),
+                action: None,
+                keystrokes: vec![],
+                context: None,
+            });
+        }
+    }
+
+    entries
+}
+
+/// State of the [`KeymapView`].
+pub struct KeymapViewState {
+    focus_handle: FocusHandle,
+    search_input: Entity<InputState>,
+    entries: Vec<KeymapEntry>,
+    show_unbound: bool,
+    /// The index of the entry is recording the new keystrokes.
+    recording: Option<usize>,
+    recorded: Vec<Keystroke>,
+    /// Intercept the keystrokes before the actions while recording.
+    _intercept: Option<Subscription>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl EventEmitter<KeymapViewEvent> for KeymapViewState {}
+
+impl KeymapViewState {
+    /// Create a new keymap view state with the bindings of the app.
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let search_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder(t!("KeymapView.placeholder")));
+        let _subscriptions =
+            vec![
+                cx.subscribe_in(&search_input, window, |_, _, event: &InputEvent, _, cx| {
+                    if let InputEvent::Change = event {
+                        cx.notify();
+                    }
+                }),
+            ];
+
+        Self {
+            focus_handle: cx.focus_handle(),
+            search_input,
+            entries: load_entries(cx, true),
+            show_unbound: true,
+            recording: None,
+            recorded: vec![],
+            _intercept: None,
+            _subscriptions,
+        }
+    }
+
+    /// Set whether to list the registered actions without any binding, default: true
+    pub fn show_unbound(mut self, show_unbound: bool, cx: &mut Context<Self>) -> Self {
+        self.show_unbound = show_unbound;
+        self.entries = load_entries(cx, show_unbound);
+        self
+    }
+
+    /// Reload the bindings, e.g. after the keymap of the app is changed.
+    pub fn reload(&mut self, cx: &mut Context<Self>) {
+        self.entries = load_entries(cx, self.show_unbound);
+        self.cancel_recording(cx);
+    }
+
+    fn start_recording(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        self.recording = Some(ix);
+        self.recorded.clear();
+        self.focus_handle.focus(window);
+
+        let view = cx.entity().downgrade();
+        self._intercept = Some(cx.intercept_keystrokes(move |event, window, cx| {
+            _ = view.update(cx, |this, cx| {
+                if this.focus_handle.is_focused(window) {
+                    cx.stop_propagation();
+                    this.on_keystroke(&event.keystroke, cx);
+                }
+            });
+        }));
+        cx.notify();
+    }
+
+    fn cancel_recording(&mut self, cx: &mut Context<Self>) {
+        self.recording = None;
+        self.recorded.clear();
+        self._intercept = None;
+        cx.notify();
+    }
+
+    fn on_keystroke(&mut self, keystroke: &Keystroke, cx: &mut Context<Self>) {
+        let no_modifiers = !keystroke.modifiers.modified();
+        match keystroke.key.as_str() {
+            "escape" if no_modifiers => self.cancel_recording(cx),
+            "enter" if no_modifiers && !self.recorded.is_empty() => self.save_recording(cx),
+            _ => {
+                if self.recorded.len() == MAX_KEYSTROKES {
+                    self.recorded.clear();
+                }
+                self.recorded.push(Keystroke {
+                    key_char: None,
+                    ..keystroke.clone()
+                });
+                cx.notify();
+            }
+        }
+    }
+
+    /// Bind the recorded keystrokes to the action, and unbind the previous keystrokes.
+    fn save_recording(&mut self, cx: &mut Context<Self>) {
+        let Some(entry) = self.recording.and_then(|ix| self.entries.get(ix)) else {
+            return;
+        };
+        let action = match &entry.action {
+            Some(action) => action.boxed_clone(),
+            None => match cx.build_action(&entry.name, None) {
+                Ok(action) => action,
+                Err(err) => {
+                    tracing::error!("failed to build action {}: {}", entry.name, err);
+                    self.cancel_recording(cx);
+                    return;
+                }
+            },
+        };
+
+        let predicate = entry
+            .context
+            .as_ref()
+            .and_then(|context| KeyBindingContextPredicate::parse(context).ok())
+            .map(Rc::new);
+        let old_keystrokes = entry.keystrokes_text();
+        let new_keystrokes = self
+            .recorded
+            .iter()
+            .map(|keystroke| keystroke.unparse())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let keyboard_mapper = cx.keyboard_mapper().clone();
+        let mut bindings = vec![];
+        if !old_keystrokes.is_empty() {
+            bindings.extend(
+                KeyBinding::load(
+                    &old_keystrokes,
+                    Box::new(NoAction),
+                    predicate.clone(),
+                    false,
+                    None,
+                    keyboard_mapper.as_ref(),
+                )
+                .ok(),
+            );
+        }
+        match KeyBinding::load(
+            &new_keystrokes,
+            action,
+            predicate,
+            false,
+            None,
+            keyboard_mapper.as_ref(),
+        ) {
+            Ok(binding) => bindings.push(binding),
+            Err(err) => {
+                tracing::error!("invalid keystrokes {}: {}", new_keystrokes, err);
+                self.cancel_recording(cx);
+                return;
+            }
+        }
+
+        let rebind = KeymapRebind {
+            action: entry.name.clone(),
+            context: entry.context.clone(),
+            old_keystrokes: (!old_keystrokes.is_empty()).then(|| old_keystrokes.into()),
+            new_keystrokes: new_keystrokes.into(),
+        };
+        cx.bind_keys(bindings);
+        self.reload(cx);
+        cx.emit(KeymapViewEvent::Rebind(rebind));
+    }
+
+    /// Returns the indices of the matched entries grouped by the context, the global first.
+    fn groups(&self, cx: &App) -> BTreeMap<Option<SharedString>, Vec<usize>> {
+        let query = self.search_input.read(cx).value();
+        let mut groups: BTreeMap<Option<SharedString>, Vec<usize>> = BTreeMap::new();
+        for (ix, entry) in self.entries.iter().enumerate() {
+            if entry.matches(&query) {
+                groups.entry(entry.context.clone()).or_default().push(ix);
+            }
+        }
+        for indices in groups.values_mut() {
+            indices.sort_by(|a, b| self.entries[*a].title.cmp(&self.entries[*b].title));
+        }
+        groups
+    }
+
+    fn render_keystrokes(&self, ix: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        let entry = &self.entries[ix];
+        let is_recording = self.recording == Some(ix);
+        let keystrokes = if is_recording {
+            &self.recorded
+        } else {
+            &entry.keystrokes
+        };
+
+        h_flex()
+            .id(("keystrokes", ix))
+            .min_w_32()
+            .h_7()
+            .px_2()
+            .gap_1()
+            .justify_end()
+            .rounded(cx.theme().radius)
+            .border_1()
+            .cursor_pointer()
+            .map(|this| {
+                if is_recording {
+                    this.border_color(cx.theme().ring)
+                } else {
+                    this.border_color(gpui::transparent_black())
+                        .hover(|this| this.bg(cx.theme().secondary_hover))
+                }
+            })
+            .children(
+                keystrokes
+                    .iter()
+                    .map(|keystroke| Kbd::new(keystroke.clone())),
+            )
+            .when(keystrokes.is_empty(), |this| {
+                this.text_xs()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(SharedString::from(if is_recording {
+                        t!("KeymapView.recording")
+                    } else {
+                        t!("KeymapView.unbound")
+                    }))
+            })
+            .on_click(cx.listener(move |this, _, window, cx| {
+                if this.recording != Some(ix) {
+                    this.start_recording(ix, window, cx);
+                }
+            }))
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        let entry = &self.entries[ix];
+
+        h_flex()
+            .w_full()
+            .px_3()
+            .py_1()
+            .gap_3()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(
+                v_flex().min_w_0().child(entry.title.clone()).child(
+                    div()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(entry.name.clone()),
+                ),
+            )
+            .child(self.render_keystrokes(ix, cx))
+    }
+}
+
+impl Render for KeymapViewState {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let groups = self.groups(cx);
+
+        v_flex()
+            .size_full()
+            .child(
+                div().p_2().child(
+                    Input::new(&self.search_input)
+                        .prefix(IconName::Search)
+                        .cleanable(true)
+                        .small(),
+                ),
+            )
+            .when(self.recording.is_some(), |this| {
+                this.child(
+                    div()
+                        .px_3()
+                        .pb_2()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(SharedString::from(t!("KeymapView.hint"))),
+                )
+            })
+            .child(
+                v_flex()
+                    .id("keymap-entries")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .text_sm()
+                    .when(groups.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .p_4()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(SharedString::from(t!("KeymapView.empty"))),
+                        )
+                    })
+                    .children(groups.into_iter().map(|(context, indices)| {
+                        v_flex()
+                            .child(
+                                div()
+                                    .px_3()
+                                    .py_1()
+                                    .text_xs()
+                                    .font_semibold()
+                                    .bg(cx.theme().muted)
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(
+                                        context.unwrap_or_else(|| t!("KeymapView.global").into()),
+                                    ),
+                            )
+                            .children(indices.into_iter().map(|ix| self.render_entry(ix, cx)))
+                    })),
+            )
+    }
+}
+
+/// A "Keyboard Shortcuts" page lists the actions with their bindings, grouped by the context.
+///
+/// The bindings can be searched by the action name, keystrokes or context, and click the
+/// keystrokes to record the new keystrokes, press `enter` to save or `escape` to cancel.
+/// The new binding is applied to the app immediately, and emits the [`KeymapViewEvent::Rebind`]
+/// to save it.
+///
+/// ```ignore
+/// let state = cx.new(|cx| KeymapViewState::new(window, cx));
+/// cx.subscribe(&state, |_, _, KeymapViewEvent::Rebind(rebind), _| save_keymap(rebind)).detach();
+///
+/// KeymapView::new(&state)
+/// ```
+#[derive(IntoElement)]
+pub struct KeymapView {
+    id: ElementId,
+    state: Entity<KeymapViewState>,
+    style: StyleRefinement,
+}
+
+impl KeymapView {
+    /// Create a new keymap view with the given state.
+    pub fn new(state: &Entity<KeymapViewState>) -> Self {
+        Self {
+            id: ElementId::Name(format!("keymap-view-{}", state.entity_id()).into()),
+            state: state.clone(),
+            style: StyleRefinement::default(),
+        }
+    }
+}
+
+impl Styled for KeymapView {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for KeymapView {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let focus_handle = self.state.read(cx).focus_handle.clone();
+
+        div()
+            .id(self.id)
+            .track_focus(&focus_handle)
+            .size_full()
+            .child(self.state)
+            .refine_style(&self.style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::humanize_action_name;
+
+    #[test]
+    fn test_humanize_action_name() {
+        assert_eq!(humanize_action_name("input::SelectAll"), "Select All");
+        assert_eq!(humanize_action_name("Copy"), "Copy");
+        assert_eq!(humanize_action_name("editor::go_to_line"), "go to line");
+        assert_eq!(humanize_action_name("zed::OpenFile2"), "Open File2");
+    }
+}
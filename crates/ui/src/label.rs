@@ -1,11 +1,11 @@
 use std::ops::Range;
 
 use gpui::{
-    div, prelude::FluentBuilder, rems, App, HighlightStyle, IntoElement, ParentElement, RenderOnce,
-    SharedString, StyleRefinement, Styled, StyledText, Window,
+    div, prelude::FluentBuilder, rems, App, ElementId, HighlightStyle, IntoElement, ParentElement,
+    RenderOnce, SharedString, StyleRefinement, Styled, StyledText, Window,
 };
 
-use crate::{ActiveTheme, StyledExt};
+use crate::{text::SelectableText, ActiveTheme, StyledExt};
 
 const MASKED: &'static str = "•";
 
@@ -56,6 +56,7 @@ pub struct Label {
     secondary: Option<SharedString>,
     masked: bool,
     highlights_text: Option<HighlightsMatch>,
+    selectable: Option<ElementId>,
 }
 
 impl Label {
@@ -68,6 +69,7 @@ impl Label {
             secondary: None,
             masked: false,
             highlights_text: None,
+            selectable: None,
         }
     }
 
@@ -90,6 +92,17 @@ impl Label {
         self
     }
 
+    /// Set whether the label text can be selected and copied, default is false.
+    ///
+    /// The selection is kept by the location of the caller, so the labels rendered
+    /// in a loop should use the [`SelectableText`] with the unique ids instead.
+    #[track_caller]
+    pub fn selectable(mut self, selectable: bool) -> Self {
+        self.selectable =
+            selectable.then(|| ElementId::CodeLocation(*std::panic::Location::caller()));
+        self
+    }
+
     fn full_text(&self) -> SharedString {
         match &self.secondary {
             Some(secondary) => format!("{} {}", self.label, secondary).into(),
@@ -206,9 +219,15 @@ impl RenderOnce for Label {
             .line_height(rems(1.25))
             .text_color(cx.theme().foreground)
             .refine_style(&self.style)
-            .child(
-                StyledText::new(&text).when_some(highlights, |this, hl| this.with_highlights(hl)),
-            )
+            .map(|this| match self.selectable {
+                Some(id) => this.child(
+                    SelectableText::new(id, text).highlights(highlights.unwrap_or_default()),
+                ),
+                None => this.child(
+                    StyledText::new(&text)
+                        .when_some(highlights, |this, hl| this.with_highlights(hl)),
+                ),
+            })
     }
 }
 
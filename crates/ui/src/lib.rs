@@ -18,58 +18,112 @@ pub(crate) mod actions;
 
 pub mod accordion;
 pub mod alert;
+pub mod animated_number;
 pub mod animation;
 pub mod avatar;
+pub mod backdrop;
 pub mod badge;
 pub mod breadcrumb;
+pub mod breakpoint;
 pub mod button;
-pub mod chart;
+pub mod chat_list;
 pub mod checkbox;
 pub mod clipboard;
 pub mod collapsible;
 pub mod color_picker;
+pub mod color_swatch;
 pub mod description_list;
+pub mod dismissal;
 pub mod divider;
 pub mod dock;
+pub mod dpi;
 pub mod drawer;
+pub mod elevation;
+pub mod export;
+pub mod file_tree;
+pub mod filter_bar;
+pub mod focus_region;
 pub mod form;
+pub mod format;
+pub mod fuzzy;
+pub mod graph_editor;
 pub mod group_box;
+pub mod heatmap;
 pub mod highlighter;
 pub mod history;
+pub mod image_pool;
 pub mod indicator;
 pub mod input;
 pub mod kbd;
+pub mod keymap_view;
 pub mod label;
 pub mod link;
 pub mod list;
+pub mod log_view;
+pub mod map_view;
+pub mod measured;
 pub mod menu;
 pub mod modal;
+pub mod modal_wizard;
 pub mod notification;
-pub mod plot;
+pub mod pagination;
+pub mod path_navigator;
+pub mod popconfirm;
 pub mod popover;
+pub mod portal;
+pub mod presence;
 pub mod progress;
 pub mod radio;
+pub mod relative_time;
 pub mod resizable;
+pub mod rich_text_editor;
+pub mod router;
+pub mod roving_focus;
 pub mod scroll;
 pub mod select;
+pub mod selectable_list;
 pub mod sidebar;
 pub mod skeleton;
+pub mod skip_link;
 pub mod slider;
+pub mod sound;
+pub mod spellcheck;
+pub mod sticky;
 pub mod switch;
 pub mod tab;
-pub mod table;
 pub mod tag;
 pub mod text;
 pub mod theme;
+pub mod toc;
 pub mod tooltip;
 pub mod tree;
+pub mod truncated_text;
+pub mod viewport;
+pub mod whats_new;
+pub mod window_chrome;
 pub use time::{calendar, date_picker};
 
-#[cfg(feature = "webview")]
+#[cfg(feature = "chart")]
+pub mod chart;
+#[cfg(feature = "diff")]
+pub mod diff_view;
+#[cfg(feature = "media")]
+pub mod draw_pad;
+#[cfg(feature = "media")]
+pub mod image_annotator;
+#[cfg(feature = "chart")]
+pub mod plot;
+#[cfg(feature = "table")]
+pub mod table;
+#[cfg(all(feature = "terminal", not(target_family = "wasm")))]
+pub mod terminal;
+#[cfg(feature = "media")]
+pub mod video_player;
+#[cfg(all(feature = "webview", not(target_family = "wasm")))]
 pub mod webview;
 
 // re-export
-#[cfg(feature = "webview")]
+#[cfg(all(feature = "webview", not(target_family = "wasm")))]
 pub use wry;
 
 pub use crate::Disableable;
@@ -93,23 +147,48 @@ rust_i18n::i18n!("locales", fallback = "en");
 /// You must initialize the components at your application's entry point.
 pub fn init(cx: &mut App) {
     theme::init(cx);
+    animation::init(cx);
+    sound::init(cx);
+    dismissal::init(cx);
     global_state::init(cx);
     #[cfg(any(feature = "inspector", debug_assertions))]
     inspector::init(cx);
     root::init(cx);
+    focus_region::init(cx);
     date_picker::init(cx);
     color_picker::init(cx);
+    #[cfg(feature = "diff")]
+    diff_view::init(cx);
     dock::init(cx);
+    #[cfg(feature = "media")]
+    draw_pad::init(cx);
     drawer::init(cx);
+    file_tree::init(cx);
+    graph_editor::init(cx);
     select::init(cx);
+    selectable_list::init(cx);
+    sidebar::init(cx);
+    skip_link::init(cx);
+    #[cfg(feature = "media")]
+    image_annotator::init(cx);
     input::init(cx);
     list::init(cx);
+    log_view::init(cx);
     modal::init(cx);
+    path_navigator::init(cx);
+    popconfirm::init(cx);
     popover::init(cx);
+    rich_text_editor::init(cx);
     menu::init(cx);
+    #[cfg(feature = "table")]
     table::init(cx);
     text::init(cx);
     tree::init(cx);
+    #[cfg(feature = "media")]
+    video_player::init(cx);
+    viewport::init(cx);
+    #[cfg(all(feature = "terminal", not(target_family = "wasm")))]
+    terminal::init(cx);
 }
 
 #[inline]
@@ -122,9 +201,15 @@ pub fn set_locale(locale: &str) {
     rust_i18n::set_locale(locale)
 }
 
+/// Read the env once, the `measure_enable` is called for every table cell in each frame.
+static MEASURE_ENABLE: std::sync::LazyLock<bool> = std::sync::LazyLock::new(|| {
+    std::env::var_os("ZED_MEASUREMENTS").is_some()
+        || std::env::var_os("GPUI_MEASUREMENTS").is_some()
+});
+
 #[inline]
 pub(crate) fn measure_enable() -> bool {
-    std::env::var("ZED_MEASUREMENTS").is_ok() || std::env::var("GPUI_MEASUREMENTS").is_ok()
+    *MEASURE_ENABLE
 }
 
 /// Measures the execution time of a function and logs it if `if_` is true.
@@ -1,17 +1,44 @@
+use std::collections::HashSet;
+
 use gpui::{
-    div, AnyElement, ClickEvent, ElementId, InteractiveElement, IntoElement, MouseButton,
-    ParentElement, RenderOnce, SharedString, StatefulInteractiveElement, StyleRefinement, Styled,
+    div, prelude::FluentBuilder as _, px, AnyElement, App, ClickEvent, ElementId, Global,
+    InteractiveElement, IntoElement, MouseButton, ParentElement, RenderOnce, SharedString,
+    StatefulInteractiveElement, StyleRefinement, Styled, Window,
 };
 
-use crate::{ActiveTheme as _, StyledExt};
+use crate::{styled::FocusableExt as _, tooltip::Tooltip, ActiveTheme as _, StyledExt};
+
+/// The hrefs opened by the [`Link`] in this app session.
+#[derive(Default)]
+struct VisitedLinks(HashSet<SharedString>);
+
+impl Global for VisitedLinks {}
+
+/// Returns true if the `href` has been opened by a [`Link`].
+pub fn is_visited(href: &str, cx: &App) -> bool {
+    cx.try_global::<VisitedLinks>()
+        .is_some_and(|visited| visited.0.contains(href))
+}
+
+fn open_href(href: &SharedString, cx: &mut App) {
+    cx.default_global::<VisitedLinks>().0.insert(href.clone());
+    cx.open_url(href);
+}
 
 /// A Link element like a `<a>` tag in HTML.
+///
+/// - Click, or press `enter` or `space` when focused, to call the `on_click`,
+///   or open the `href` by the system if no `on_click`.
+/// - Middle click to open the `href`, like to open it in a new tab in the browser.
+/// - The opened `href` is displayed with the `link.visited` color.
 #[derive(IntoElement)]
 pub struct Link {
     id: ElementId,
     style: StyleRefinement,
     href: Option<SharedString>,
     disabled: bool,
+    visited: Option<bool>,
+    tooltip: bool,
     on_click: Option<Box<dyn Fn(&ClickEvent, &mut gpui::Window, &mut gpui::App) + 'static>>,
     children: Vec<AnyElement>,
 }
@@ -25,6 +52,8 @@ impl Link {
             href: None,
             on_click: None,
             disabled: false,
+            visited: None,
+            tooltip: true,
             children: Vec::new(),
         }
     }
@@ -52,6 +81,18 @@ impl Link {
         self.disabled = disabled;
         self
     }
+
+    /// Set the visited state, default is true if the href has been opened by a [`Link`].
+    pub fn visited(mut self, visited: bool) -> Self {
+        self.visited = Some(visited);
+        self
+    }
+
+    /// Set to show the href in a tooltip when hovered, default: true
+    pub fn href_tooltip(mut self, tooltip: bool) -> Self {
+        self.tooltip = tooltip;
+        self
+    }
 }
 
 impl Styled for Link {
@@ -67,37 +108,72 @@ impl ParentElement for Link {
 }
 
 impl RenderOnce for Link {
-    fn render(self, _: &mut gpui::Window, cx: &mut gpui::App) -> impl IntoElement {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let focus_handle = window
+            .use_keyed_state(self.id.clone(), cx, |_, cx| cx.focus_handle())
+            .read(cx)
+            .clone();
+        let is_focused = focus_handle.is_focused(window);
         let href = self.href.clone();
         let on_click = self.on_click;
+        let visited = self.visited.unwrap_or_else(|| {
+            href.as_ref()
+                .is_some_and(|href| is_visited(href.as_ref(), cx))
+        });
+        let color = if visited {
+            cx.theme().link_visited
+        } else {
+            cx.theme().link
+        };
 
         div()
             .id(self.id)
-            .text_color(cx.theme().link)
+            .text_color(color)
             .text_decoration_1()
-            .text_decoration_color(cx.theme().link)
-            .hover(|this| {
-                this.text_color(cx.theme().link.opacity(0.8))
-                    .text_decoration_1()
-            })
-            .active(|this| {
-                this.text_color(cx.theme().link.opacity(0.6))
-                    .text_decoration_1()
+            .text_decoration_color(color)
+            .map(|this| {
+                if self.disabled {
+                    this.opacity(0.5).cursor_not_allowed()
+                } else {
+                    this.track_focus(&focus_handle.tab_stop(true))
+                        .hover(|this| {
+                            this.text_color(cx.theme().link_hover)
+                                .text_decoration_color(cx.theme().link_hover)
+                        })
+                        .active(|this| {
+                            this.text_color(cx.theme().link_active)
+                                .text_decoration_color(cx.theme().link_active)
+                        })
+                        .cursor_pointer()
+                }
             })
-            .cursor_pointer()
+            .focus_ring(is_focused, px(0.), window, cx)
             .refine_style(&self.style)
-            .on_mouse_down(MouseButton::Left, |_, _, cx| {
-                cx.stop_propagation();
+            .when(self.tooltip, |this| {
+                this.when_some(href.clone(), |this, href| {
+                    this.tooltip(move |window, cx| Tooltip::new(href.clone()).build(window, cx))
+                })
             })
-            .on_click({
-                move |e, window, cx| {
-                    if let Some(href) = &href {
-                        cx.open_url(&href.clone());
+            .when(!self.disabled, |this| {
+                this.on_mouse_down(MouseButton::Left, |_, _, cx| {
+                    cx.stop_propagation();
+                })
+                .on_mouse_up(MouseButton::Middle, {
+                    let href = href.clone();
+                    move |_, _, cx| {
+                        if let Some(href) = &href {
+                            cx.stop_propagation();
+                            open_href(href, cx);
+                        }
                     }
+                })
+                .on_click(move |e, window, cx| {
                     if let Some(on_click) = &on_click {
                         on_click(e, window, cx);
+                    } else if let Some(href) = &href {
+                        open_href(href, cx);
                     }
-                }
+                })
             })
             .children(self.children)
     }
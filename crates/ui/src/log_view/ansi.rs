@@ -0,0 +1,260 @@
+use std::ops::Range;
+
+use gpui::{FontStyle, FontWeight, HighlightStyle, Hsla, Rgba, UnderlineStyle};
+
+/// A color from the ANSI SGR escape sequences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnsiColor {
+    /// One of the 256 indexed colors, `0..16` are the standard and bright colors.
+    Indexed(u8),
+    /// A 24-bit true color.
+    Rgb(u8, u8, u8),
+}
+
+/// The xterm default colors for the 16 standard and bright colors.
+const STANDARD_COLORS: [u32; 16] = [
+    0x000000, 0xcd3131, 0x0dbc79, 0xe5e510, 0x2472c8, 0xbc3fbc, 0x11a8cd, 0xe5e5e5, 0x666666,
+    0xf14c4c, 0x23d18b, 0xf5f543, 0x3b8eea, 0xd670d6, 0x29b8db, 0xffffff,
+];
+
+impl AnsiColor {
+    /// Convert the color to [`Hsla`] using the xterm 256 color palette.
+    pub fn to_hsla(self) -> Hsla {
+        let (r, g, b) = match self {
+            AnsiColor::Rgb(r, g, b) => (r, g, b),
+            AnsiColor::Indexed(ix @ 0..=15) => {
+                let hex = STANDARD_COLORS[ix as usize];
+                ((hex >> 16) as u8, (hex >> 8) as u8, hex as u8)
+            }
+            AnsiColor::Indexed(ix @ 16..=231) => {
+                let ix = ix - 16;
+                let level = |v: u8| if v == 0 { 0 } else { v * 40 + 55 };
+                (level(ix / 36), level((ix / 6) % 6), level(ix % 6))
+            }
+            AnsiColor::Indexed(ix) => {
+                let v = (ix - 232) * 10 + 8;
+                (v, v, v)
+            }
+        };
+
+        Rgba {
+            r: r as f32 / 255.,
+            g: g as f32 / 255.,
+            b: b as f32 / 255.,
+            a: 1.,
+        }
+        .into()
+    }
+}
+
+/// The text style from the ANSI SGR escape sequences.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AnsiStyle {
+    pub fg: Option<AnsiColor>,
+    pub bg: Option<AnsiColor>,
+    pub bold: bool,
+    pub dim: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub inverse: bool,
+}
+
+impl AnsiStyle {
+    /// Apply the SGR parameters, e.g.: `1;31` of `\x1b[1;31m`.
+    pub(crate) fn apply_sgr(&mut self, params: &str) {
+        self.apply_sgr_params(
+            params
+                .split([';', ':'])
+                .map(|p| p.parse::<u16>().unwrap_or(0)),
+        );
+    }
+
+    /// Apply the parsed SGR parameters, empty is the same as `0` (reset).
+    pub(crate) fn apply_sgr_params(&mut self, params: impl IntoIterator<Item = u16>) {
+        let mut params = params.into_iter();
+        let Some(mut code) = params.next() else {
+            *self = Self::default();
+            return;
+        };
+
+        loop {
+            match code {
+                0 => *self = Self::default(),
+                1 => self.bold = true,
+                2 => self.dim = true,
+                3 => self.italic = true,
+                4 => self.underline = true,
+                7 => self.inverse = true,
+                22 => {
+                    self.bold = false;
+                    self.dim = false;
+                }
+                23 => self.italic = false,
+                24 => self.underline = false,
+                27 => self.inverse = false,
+                30..=37 => self.fg = Some(AnsiColor::Indexed((code - 30) as u8)),
+                38 => self.fg = parse_extended_color(&mut params),
+                39 => self.fg = None,
+                40..=47 => self.bg = Some(AnsiColor::Indexed((code - 40) as u8)),
+                48 => self.bg = parse_extended_color(&mut params),
+                49 => self.bg = None,
+                90..=97 => self.fg = Some(AnsiColor::Indexed((code - 90 + 8) as u8)),
+                100..=107 => self.bg = Some(AnsiColor::Indexed((code - 100 + 8) as u8)),
+                _ => {}
+            }
+
+            match params.next() {
+                Some(next) => code = next,
+                None => break,
+            }
+        }
+    }
+
+    /// Convert to [`HighlightStyle`], returns `None` for the default style.
+    pub fn to_highlight(&self) -> Option<HighlightStyle> {
+        if *self == Self::default() {
+            return None;
+        }
+
+        let (fg, bg) = if self.inverse {
+            (self.bg, self.fg)
+        } else {
+            (self.fg, self.bg)
+        };
+
+        Some(HighlightStyle {
+            color: fg.map(|c| c.to_hsla()),
+            background_color: bg.map(|c| c.to_hsla()),
+            font_weight: self.bold.then_some(FontWeight::BOLD),
+            font_style: self.italic.then_some(FontStyle::Italic),
+            underline: self.underline.then(|| UnderlineStyle {
+                thickness: gpui::px(1.),
+                ..Default::default()
+            }),
+            fade_out: self.dim.then_some(0.4),
+            ..Default::default()
+        })
+    }
+}
+
+fn parse_extended_color(params: &mut impl Iterator<Item = u16>) -> Option<AnsiColor> {
+    match params.next()? {
+        5 => Some(AnsiColor::Indexed(params.next()?.min(255) as u8)),
+        2 => {
+            let mut next = || params.next().map(|v| v.min(255) as u8);
+            Some(AnsiColor::Rgb(next()?, next()?, next()?))
+        }
+        _ => None,
+    }
+}
+
+/// Parse the text with ANSI escape sequences.
+///
+/// Returns the plain text and the styled ranges (byte offsets into the plain text).
+/// Other escape sequences than SGR (e.g. cursor movement) are stripped.
+pub fn parse_ansi(input: &str) -> (String, Vec<(Range<usize>, AnsiStyle)>) {
+    let mut text = String::with_capacity(input.len());
+    let mut styles: Vec<(Range<usize>, AnsiStyle)> = vec![];
+    let mut style = AnsiStyle::default();
+    let mut run_start = 0;
+
+    let mut chars = input.char_indices().peekable();
+    while let Some((_, c)) = chars.next() {
+        if c != '\x1b' {
+            text.push(c);
+            continue;
+        }
+
+        let mut new_style = style;
+        match chars.next() {
+            // CSI: ESC [ params final
+            Some((start, '[')) => {
+                let params_start = start + 1;
+                let mut params_end = input.len();
+                let mut final_byte = None;
+                for (ix, c) in chars.by_ref() {
+                    if ('\x40'..='\x7e').contains(&c) {
+                        params_end = ix;
+                        final_byte = Some(c);
+                        break;
+                    }
+                }
+                if final_byte == Some('m') {
+                    new_style.apply_sgr(&input[params_start..params_end]);
+                }
+            }
+            // OSC: ESC ] ... (BEL | ESC \)
+            Some((_, ']')) => {
+                while let Some((_, c)) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' {
+                        if chars.peek().map(|(_, c)| *c) == Some('\\') {
+                            chars.next();
+                        }
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        if new_style != style {
+            if run_start < text.len() && style != AnsiStyle::default() {
+                styles.push((run_start..text.len(), style));
+            }
+            run_start = text.len();
+            style = new_style;
+        }
+    }
+
+    if run_start < text.len() && style != AnsiStyle::default() {
+        styles.push((run_start..text.len(), style));
+    }
+
+    (text, styles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_ansi, AnsiColor, AnsiStyle};
+
+    #[test]
+    fn test_parse_ansi() {
+        let (text, styles) = parse_ansi("plain \x1b[1;31merror\x1b[0m done");
+        assert_eq!(text, "plain error done");
+        assert_eq!(
+            styles,
+            vec![(
+                6..11,
+                AnsiStyle {
+                    fg: Some(AnsiColor::Indexed(1)),
+                    bold: true,
+                    ..Default::default()
+                }
+            )]
+        );
+
+        let (text, styles) = parse_ansi("\x1b[38;5;208mA\x1b[48;2;1;2;3mB\x1b[mC");
+        assert_eq!(text, "ABC");
+        assert_eq!(styles.len(), 2);
+        assert_eq!(styles[0].0, 0..1);
+        assert_eq!(styles[0].1.fg, Some(AnsiColor::Indexed(208)));
+        assert_eq!(styles[1].0, 1..2);
+        assert_eq!(styles[1].1.bg, Some(AnsiColor::Rgb(1, 2, 3)));
+
+        // Non-SGR sequences are stripped.
+        let (text, styles) = parse_ansi("\x1b[2K\x1b]0;title\x07line\x1b[1A");
+        assert_eq!(text, "line");
+        assert!(styles.is_empty());
+    }
+
+    #[test]
+    fn test_ansi_color_to_hsla() {
+        let black = AnsiColor::Indexed(0).to_hsla();
+        assert_eq!(black.l, 0.);
+        let white = AnsiColor::Indexed(231).to_hsla();
+        assert_eq!(white.l, 1.);
+    }
+}
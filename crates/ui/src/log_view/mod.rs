@@ -0,0 +1,581 @@
+mod ansi;
+
+use std::{collections::VecDeque, ops::Range, rc::Rc};
+
+use gpui::{
+    combine_highlights, div, prelude::FluentBuilder as _, px, uniform_list, App, ClipboardItem,
+    Context, Div, ElementId, Entity, FocusHandle, HighlightStyle, InteractiveElement as _,
+    IntoElement, KeyBinding, ListSizingBehavior, MouseButton, MouseDownEvent, ParentElement,
+    Render, RenderOnce, ScrollStrategy, ScrollWheelEvent, SharedString, Stateful, StyleRefinement,
+    Styled, UniformListScrollHandle, Window,
+};
+use rust_i18n::t;
+
+use crate::{
+    button::{Button, ButtonVariants as _},
+    h_flex, input,
+    scroll::{Minimap, MinimapMarker, Scrollbar, ScrollbarState},
+    text::CachedText,
+    ActiveTheme, IconName, Sizable as _, StyledExt as _,
+};
+
+pub use ansi::{parse_ansi, AnsiColor, AnsiStyle};
+
+const CONTEXT: &str = "LogView";
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-c", input::Copy, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-c", input::Copy, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-a", input::SelectAll, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-a", input::SelectAll, Some(CONTEXT)),
+    ]);
+}
+
+/// The severity level of a log line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// Detect the level from an uppercase keyword (e.g.: `INFO`, `WARN`, `ERROR`) near the start of the line.
+    pub fn detect(line: &str) -> Option<Self> {
+        let head = match line.char_indices().nth(120) {
+            Some((ix, _)) => &line[..ix],
+            None => line,
+        };
+
+        head.split(|c: char| !c.is_ascii_alphabetic())
+            .find_map(|word| match word {
+                "TRACE" => Some(Self::Trace),
+                "DEBUG" => Some(Self::Debug),
+                "INFO" => Some(Self::Info),
+                "WARN" | "WARNING" => Some(Self::Warn),
+                "ERROR" | "FATAL" => Some(Self::Error),
+                _ => None,
+            })
+    }
+}
+
+/// A parsed line in the [`LogView`].
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    text: SharedString,
+    level: Option<LogLevel>,
+    styles: Rc<[(Range<usize>, AnsiStyle)]>,
+}
+
+impl LogLine {
+    /// Parse a line with ANSI escape sequences, and detect its level.
+    pub fn new(line: &str) -> Self {
+        let (text, styles) = parse_ansi(line);
+        let level = LogLevel::detect(&text);
+        Self {
+            text: text.into(),
+            level,
+            styles: styles.into(),
+        }
+    }
+
+    /// Set the level of the line, instead of the detected one.
+    pub fn level(mut self, level: Option<LogLevel>) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// The plain text of the line, without the ANSI escape sequences.
+    pub fn text(&self) -> &SharedString {
+        &self.text
+    }
+
+    /// The level of the line, if any.
+    pub fn log_level(&self) -> Option<LogLevel> {
+        self.level
+    }
+}
+
+/// State of the [`LogView`].
+///
+/// The lines are kept in a ring buffer, the oldest lines are dropped when it is full.
+pub struct LogViewState {
+    focus_handle: FocusHandle,
+    lines: VecDeque<LogLine>,
+    max_lines: usize,
+    /// The line number of the first line in the buffer.
+    first_line: usize,
+    min_level: Option<LogLevel>,
+    /// The line numbers of the lines that pass the level filter.
+    visible: Vec<usize>,
+    search: SharedString,
+    follow: bool,
+    /// The selected line numbers as `(anchor, head)`.
+    selection: Option<(usize, usize)>,
+    scroll_handle: UniformListScrollHandle,
+    scrollbar_state: ScrollbarState,
+}
+
+impl LogViewState {
+    /// Create a new empty log view state, default to keep 10,000 lines.
+    pub fn new(_: &mut Window, cx: &mut Context<Self>) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            lines: VecDeque::new(),
+            max_lines: 10_000,
+            first_line: 0,
+            min_level: None,
+            visible: vec![],
+            search: SharedString::default(),
+            follow: true,
+            selection: None,
+            scroll_handle: UniformListScrollHandle::default(),
+            scrollbar_state: ScrollbarState::default(),
+        }
+    }
+
+    /// Set the maximum number of lines to keep, default: 10,000
+    pub fn max_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = max_lines.max(1);
+        self
+    }
+
+    /// Append a line, it can contain ANSI escape sequences.
+    pub fn push_line(&mut self, line: &str, cx: &mut Context<Self>) {
+        self.push_log_lines([LogLine::new(line)], cx);
+    }
+
+    /// Append the text, split by line breaks.
+    pub fn push_text(&mut self, text: &str, cx: &mut Context<Self>) {
+        self.push_log_lines(text.lines().map(LogLine::new), cx);
+    }
+
+    /// Append parsed lines.
+    pub fn push_log_lines(
+        &mut self,
+        lines: impl IntoIterator<Item = LogLine>,
+        cx: &mut Context<Self>,
+    ) {
+        for line in lines {
+            if self.lines.len() == self.max_lines {
+                self.lines.pop_front();
+                self.first_line += 1;
+            }
+
+            let number = self.first_line + self.lines.len();
+            if self.is_level_visible(line.level) {
+                self.visible.push(number);
+            }
+            self.lines.push_back(line);
+        }
+
+        let dropped = self.visible.partition_point(|n| *n < self.first_line);
+        self.visible.drain(..dropped);
+        if let Some((anchor, head)) = self.selection {
+            if head < self.first_line {
+                self.selection = None;
+            } else {
+                self.selection = Some((anchor.max(self.first_line), head));
+            }
+        }
+
+        if self.follow {
+            self.scroll_to_end();
+        }
+        cx.notify();
+    }
+
+    /// Remove all lines.
+    pub fn clear(&mut self, cx: &mut Context<Self>) {
+        self.first_line += self.lines.len();
+        self.lines.clear();
+        self.visible.clear();
+        self.selection = None;
+        cx.notify();
+    }
+
+    /// Returns the number of lines in the buffer.
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Returns true if there are no lines.
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Only show the lines with at least the given level, `None` to show all lines.
+    ///
+    /// Lines without a level are always shown.
+    pub fn set_min_level(&mut self, level: Option<LogLevel>, cx: &mut Context<Self>) {
+        self.min_level = level;
+        self.visible = self
+            .lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| self.is_level_visible(line.level))
+            .map(|(ix, _)| self.first_line + ix)
+            .collect();
+        if self.follow {
+            self.scroll_to_end();
+        }
+        cx.notify();
+    }
+
+    /// Returns the minimum level of the visible lines.
+    pub fn min_level(&self) -> Option<LogLevel> {
+        self.min_level
+    }
+
+    fn is_level_visible(&self, level: Option<LogLevel>) -> bool {
+        match (self.min_level, level) {
+            (Some(min_level), Some(level)) => level >= min_level,
+            _ => true,
+        }
+    }
+
+    /// Highlight the matches of the query (case-insensitive), empty to clear.
+    pub fn set_search(&mut self, query: impl Into<SharedString>, cx: &mut Context<Self>) {
+        self.search = query.into();
+        cx.notify();
+    }
+
+    /// Returns the number of visible lines that match the search query.
+    pub fn search_match_count(&self) -> usize {
+        if self.search.is_empty() {
+            return 0;
+        }
+
+        self.visible
+            .iter()
+            .filter_map(|n| self.line(*n))
+            .filter(|line| !search_ranges(&line.text, &self.search).is_empty())
+            .count()
+    }
+
+    /// Set whether to keep scrolling to the newest line.
+    ///
+    /// Following is paused when the user scrolls up.
+    pub fn set_follow(&mut self, follow: bool, cx: &mut Context<Self>) {
+        self.follow = follow;
+        if follow {
+            self.scroll_to_end();
+        }
+        cx.notify();
+    }
+
+    /// Returns true if the view keeps scrolling to the newest line.
+    pub fn is_following(&self) -> bool {
+        self.follow
+    }
+
+    /// Returns the text of the selected lines, joined by `\n`.
+    pub fn selected_text(&self) -> Option<String> {
+        let range = self.selected_range()?;
+        let text = self
+            .visible
+            .iter()
+            .filter(|n| range.contains(n))
+            .filter_map(|n| self.line(*n))
+            .map(|line| line.text.as_ref())
+            .collect::<Vec<_>>()
+            .join("\n");
+        Some(text)
+    }
+
+    fn selected_range(&self) -> Option<Range<usize>> {
+        let (anchor, head) = self.selection?;
+        Some(anchor.min(head)..anchor.max(head) + 1)
+    }
+
+    fn line(&self, number: usize) -> Option<&LogLine> {
+        self.lines.get(number.checked_sub(self.first_line)?)
+    }
+
+    fn scroll_to_end(&self) {
+        if let Some(ix) = self.visible.len().checked_sub(1) {
+            self.scroll_handle
+                .scroll_to_item(ix, ScrollStrategy::Bottom);
+        }
+    }
+
+    fn on_action_copy(&mut self, _: &input::Copy, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(text) = self.selected_text() {
+            cx.write_to_clipboard(ClipboardItem::new_string(text));
+        }
+    }
+
+    fn on_action_select_all(
+        &mut self,
+        _: &input::SelectAll,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let (Some(first), Some(last)) = (self.visible.first(), self.visible.last()) {
+            self.selection = Some((*first, *last));
+            cx.notify();
+        }
+    }
+
+    fn on_line_mouse_down(
+        &mut self,
+        number: usize,
+        event: &MouseDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.focus_handle.focus(window);
+        self.selection = match self.selection {
+            Some((anchor, _)) if event.modifiers.shift => Some((anchor, number)),
+            _ => Some((number, number)),
+        };
+        cx.notify();
+    }
+
+    fn on_scroll_wheel(
+        &mut self,
+        event: &ScrollWheelEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let delta = event.delta.pixel_delta(window.line_height());
+        if self.follow && delta.y > px(0.) {
+            self.follow = false;
+            cx.notify();
+        }
+    }
+
+    /// Returns the markers of the errors and the search matches for the [`Minimap`].
+    fn minimap_markers(&self, cx: &App) -> Vec<MinimapMarker> {
+        self.visible
+            .iter()
+            .enumerate()
+            .filter_map(|(ix, n)| {
+                let line = self.line(*n)?;
+                if !search_ranges(&line.text, &self.search).is_empty() {
+                    Some(MinimapMarker::new(ix, cx.theme().warning))
+                } else if line.level == Some(LogLevel::Error) {
+                    Some(MinimapMarker::new(ix, cx.theme().danger))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn render_line(&self, number: usize, cx: &App) -> Stateful<Div> {
+        let line = self.line(number).expect("visible line must exist");
+        let selected = self
+            .selected_range()
+            .map_or(false, |range| range.contains(&number));
+
+        let ansi_highlights = line
+            .styles
+            .iter()
+            .filter_map(|(range, style)| Some((range.clone(), style.to_highlight()?)))
+            .collect::<Vec<_>>();
+        let search_highlights = search_ranges(&line.text, &self.search)
+            .into_iter()
+            .map(|range| {
+                (
+                    range,
+                    HighlightStyle {
+                        background_color: Some(cx.theme().warning.opacity(0.4)),
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+        let highlights = combine_highlights(ansi_highlights, search_highlights).collect::<Vec<_>>();
+
+        let text_color = match line.level {
+            Some(LogLevel::Error) => cx.theme().danger,
+            Some(LogLevel::Warn) => cx.theme().warning,
+            Some(LogLevel::Trace) | Some(LogLevel::Debug) => cx.theme().muted_foreground,
+            _ => cx.theme().foreground,
+        };
+
+        h_flex()
+            .id(number)
+            .w_full()
+            .px_2()
+            .gap_3()
+            .text_color(text_color)
+            .when(selected, |this| this.bg(cx.theme().selection))
+            .child(
+                div()
+                    .flex_shrink_0()
+                    .min_w(px(40.))
+                    .text_right()
+                    .text_color(cx.theme().muted_foreground)
+                    .child((number + 1).to_string()),
+            )
+            .child(
+                div()
+                    .whitespace_nowrap()
+                    .child(CachedText::new(line.text.clone()).with_highlights(highlights)),
+            )
+    }
+}
+
+/// Returns the byte ranges of the case-insensitive matches of `query` in `text`.
+fn search_ranges(text: &str, query: &str) -> Vec<Range<usize>> {
+    if query.is_empty() {
+        return vec![];
+    }
+
+    // Only ASCII lowercase keeps the byte offsets stable.
+    let text = text.to_ascii_lowercase();
+    let query = query.to_ascii_lowercase();
+    text.match_indices(&query)
+        .map(|(ix, m)| ix..ix + m.len())
+        .collect()
+}
+
+impl Render for LogViewState {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .relative()
+            .size_full()
+            .on_scroll_wheel(cx.listener(Self::on_scroll_wheel))
+            .child(
+                uniform_list("lines", self.visible.len(), {
+                    cx.processor(move |state, visible_range: Range<usize>, _, cx| {
+                        visible_range
+                            .filter_map(|ix| state.visible.get(ix).copied())
+                            .map(|number| {
+                                state.render_line(number, cx).on_mouse_down(
+                                    MouseButton::Left,
+                                    cx.listener(move |this, event, window, cx| {
+                                        this.on_line_mouse_down(number, event, window, cx);
+                                    }),
+                                )
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .size_full()
+                .track_scroll(self.scroll_handle.clone())
+                .with_sizing_behavior(ListSizingBehavior::Auto),
+            )
+            .child(
+                Minimap::new("minimap", self.visible.len())
+                    .absolute()
+                    .top_0()
+                    .right_0()
+                    .markers(self.minimap_markers(cx)),
+            )
+            .child(
+                div()
+                    .absolute()
+                    .top_0()
+                    .right_0()
+                    .bottom_0()
+                    .w(Scrollbar::width())
+                    .child(Scrollbar::vertical(
+                        &self.scrollbar_state,
+                        &self.scroll_handle,
+                    )),
+            )
+            .when(!self.follow, |this| {
+                this.child(
+                    div().absolute().bottom_2().right_4().child(
+                        Button::new("follow-tail")
+                            .primary()
+                            .small()
+                            .icon(IconName::ArrowDown)
+                            .label(t!("LogView.Follow"))
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.set_follow(true, cx);
+                            })),
+                    ),
+                )
+            })
+    }
+}
+
+/// A viewer for large append-only logs.
+///
+/// ```ignore
+/// let state = cx.new(|cx| LogViewState::new(window, cx).max_lines(50_000));
+/// state.update(cx, |state, cx| state.push_line("\x1b[32mINFO\x1b[0m started", cx));
+///
+/// LogView::new(&state)
+/// ```
+#[derive(IntoElement)]
+pub struct LogView {
+    id: ElementId,
+    state: Entity<LogViewState>,
+    style: StyleRefinement,
+}
+
+impl LogView {
+    /// Create a new log view with the given state.
+    pub fn new(state: &Entity<LogViewState>) -> Self {
+        Self {
+            id: ElementId::Name(format!("log-view-{}", state.entity_id()).into()),
+            state: state.clone(),
+            style: StyleRefinement::default(),
+        }
+    }
+}
+
+impl Styled for LogView {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for LogView {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let focus_handle = self.state.read(cx).focus_handle.clone();
+
+        div()
+            .id(self.id)
+            .key_context(CONTEXT)
+            .track_focus(&focus_handle)
+            .on_action(window.listener_for(&self.state, LogViewState::on_action_copy))
+            .on_action(window.listener_for(&self.state, LogViewState::on_action_select_all))
+            .size_full()
+            .font_family("Menlo, Monaco, Consolas, monospace")
+            .text_xs()
+            .child(self.state)
+            .refine_style(&self.style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{search_ranges, LogLevel};
+
+    #[test]
+    fn test_detect_level() {
+        assert_eq!(
+            LogLevel::detect("2025-01-01T00:00:00Z ERROR server: failed"),
+            Some(LogLevel::Error)
+        );
+        assert_eq!(
+            LogLevel::detect("[WARN] disk is full"),
+            Some(LogLevel::Warn)
+        );
+        assert_eq!(LogLevel::detect(" INFO  app started"), Some(LogLevel::Info));
+        assert_eq!(LogLevel::detect("an error in lowercase"), None);
+        assert_eq!(LogLevel::detect("INFORMATION"), None);
+        assert!(LogLevel::Error > LogLevel::Warn);
+    }
+
+    #[test]
+    fn test_search_ranges() {
+        assert_eq!(search_ranges("Foo foo FOO", "foo"), vec![0..3, 4..7, 8..11]);
+        assert_eq!(
+            search_ranges("Foo", ""),
+            Vec::<std::ops::Range<usize>>::new()
+        );
+    }
+}
@@ -0,0 +1,633 @@
+use std::{f64::consts::PI, rc::Rc};
+
+use gpui::{
+    canvas, div, img, point, prelude::FluentBuilder as _, px, AnyElement, App, Bounds, Context,
+    ElementId, Entity, EventEmitter, Hsla, InteractiveElement as _, IntoElement, MouseButton,
+    MouseDownEvent, MouseMoveEvent, MouseUpEvent, ParentElement, PathBuilder, Pixels, Point,
+    Render, RenderOnce, ScrollWheelEvent, SharedString, StatefulInteractiveElement as _,
+    StyleRefinement, Styled, Window,
+};
+
+use crate::{button::Button, v_flex, ActiveTheme, IconName, Sizable as _, StyledExt as _};
+
+const TILE_SIZE: f64 = 256.;
+const MAX_LATITUDE: f64 = 85.051_128_78;
+const MARKER_SIZE: Pixels = px(14.);
+
+/// A geographic position in degrees.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct LatLng {
+    pub lat: f64,
+    pub lng: f64,
+}
+
+impl LatLng {
+    pub fn new(lat: f64, lng: f64) -> Self {
+        Self { lat, lng }
+    }
+}
+
+/// Project the position to the Web Mercator world pixels at the `zoom`.
+fn project(position: LatLng, zoom: f64) -> (f64, f64) {
+    let world_size = TILE_SIZE * 2f64.powf(zoom);
+    let lat = position.lat.clamp(-MAX_LATITUDE, MAX_LATITUDE).to_radians();
+    let x = (position.lng + 180.) / 360. * world_size;
+    let y = (1. - (lat.tan() + 1. / lat.cos()).ln() / PI) / 2. * world_size;
+    (x, y)
+}
+
+/// Convert the Web Mercator world pixels at the `zoom` to the position.
+fn unproject(x: f64, y: f64, zoom: f64) -> LatLng {
+    let world_size = TILE_SIZE * 2f64.powf(zoom);
+    let lng = x / world_size * 360. - 180.;
+    let n = PI * (1. - 2. * y / world_size);
+    let lat = n.sinh().atan().to_degrees();
+    LatLng::new(lat.clamp(-MAX_LATITUDE, MAX_LATITUDE), lng)
+}
+
+/// Returns the URL of the tile by the template with `{z}`, `{x}`, `{y}` and `{r}`,
+/// the `{r}` is `@2x` on the high DPI displays.
+fn tile_url(template: &str, z: u32, x: i64, y: i64, scale_factor: f32) -> SharedString {
+    template
+        .replace("{r}", if scale_factor > 1. { "@2x" } else { "" })
+        .replace("{z}", &z.to_string())
+        .replace("{x}", &x.to_string())
+        .replace("{y}", &y.to_string())
+        .into()
+}
+
+/// A marker on the [`MapView`], click it to show the popover.
+#[derive(Clone)]
+pub struct MapMarker {
+    pub position: LatLng,
+    pub title: SharedString,
+    pub description: Option<SharedString>,
+    pub color: Option<Hsla>,
+    render_popover: Option<Rc<dyn Fn(&mut Window, &mut App) -> AnyElement>>,
+}
+
+impl MapMarker {
+    pub fn new(position: LatLng, title: impl Into<SharedString>) -> Self {
+        Self {
+            position,
+            title: title.into(),
+            description: None,
+            color: None,
+            render_popover: None,
+        }
+    }
+
+    /// Set the description to show in the popover.
+    pub fn description(mut self, description: impl Into<SharedString>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the color of the marker, default is the primary color of the theme.
+    pub fn color(mut self, color: impl Into<Hsla>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Set the content of the popover, default is the title and the description.
+    pub fn popover<F, E>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut Window, &mut App) -> E + 'static,
+        E: IntoElement,
+    {
+        self.render_popover = Some(Rc::new(move |window, cx| f(window, cx).into_any_element()));
+        self
+    }
+}
+
+/// A polyline overlay on the [`MapView`], e.g.: a route.
+#[derive(Debug, Clone)]
+pub struct MapPolyline {
+    pub points: Vec<LatLng>,
+    pub color: Option<Hsla>,
+    pub width: Pixels,
+}
+
+impl MapPolyline {
+    pub fn new(points: impl IntoIterator<Item = LatLng>) -> Self {
+        Self {
+            points: points.into_iter().collect(),
+            color: None,
+            width: px(3.),
+        }
+    }
+
+    /// Set the color of the line, default is the primary color of the theme.
+    pub fn color(mut self, color: impl Into<Hsla>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Set the width of the line, default: 3px
+    pub fn width(mut self, width: impl Into<Pixels>) -> Self {
+        self.width = width.into();
+        self
+    }
+}
+
+/// Events emitted by the [`MapViewState`].
+#[derive(Debug, Clone)]
+pub enum MapViewEvent {
+    /// The center or the zoom was changed.
+    Move { center: LatLng, zoom: f64 },
+    /// The marker at the index was clicked.
+    ClickMarker(usize),
+}
+
+/// State of the [`MapView`].
+pub struct MapViewState {
+    center: LatLng,
+    zoom: f64,
+    min_zoom: f64,
+    max_zoom: f64,
+    tile_url: SharedString,
+    attribution: Option<SharedString>,
+    markers: Vec<MapMarker>,
+    polylines: Vec<MapPolyline>,
+    selected_marker: Option<usize>,
+    /// The bounds of the map in the window.
+    bounds: Bounds<Pixels>,
+    /// The last mouse position when dragging.
+    drag_position: Option<Point<Pixels>>,
+}
+
+impl MapViewState {
+    /// Create a new map view state, default to show the OpenStreetMap tiles.
+    pub fn new(_: &mut Window, _: &mut Context<Self>) -> Self {
+        Self {
+            center: LatLng::default(),
+            zoom: 2.,
+            min_zoom: 0.,
+            max_zoom: 19.,
+            tile_url: "https://tile.openstreetmap.org/{z}/{x}/{y}.png".into(),
+            attribution: Some("© OpenStreetMap contributors".into()),
+            markers: vec![],
+            polylines: vec![],
+            selected_marker: None,
+            bounds: Bounds::default(),
+            drag_position: None,
+        }
+    }
+
+    /// Set the URL template of the tiles with `{z}`, `{x}` and `{y}`, and the optional `{r}`
+    /// for the `@2x` tiles on the high DPI displays, they are loaded again when the scale factor is changed.
+    ///
+    /// Default: `https://tile.openstreetmap.org/{z}/{x}/{y}.png`
+    pub fn tile_url(mut self, template: impl Into<SharedString>) -> Self {
+        self.tile_url = template.into();
+        self
+    }
+
+    /// Set the attribution of the tiles, shown at the bottom right.
+    pub fn attribution(mut self, attribution: Option<impl Into<SharedString>>) -> Self {
+        self.attribution = attribution.map(Into::into);
+        self
+    }
+
+    /// Set the zoom range, default: 0 to 19
+    pub fn zoom_range(mut self, min: f64, max: f64) -> Self {
+        self.min_zoom = min.min(max).max(0.);
+        self.max_zoom = max.max(min);
+        self
+    }
+
+    /// Returns the center of the map.
+    pub fn center(&self) -> LatLng {
+        self.center
+    }
+
+    /// Returns the zoom of the map.
+    pub fn zoom(&self) -> f64 {
+        self.zoom
+    }
+
+    /// Move the map to the `center` with the `zoom`.
+    pub fn set_view(&mut self, center: LatLng, zoom: f64, cx: &mut Context<Self>) {
+        let center = LatLng::new(
+            center.lat.clamp(-MAX_LATITUDE, MAX_LATITUDE),
+            (center.lng + 180.).rem_euclid(360.) - 180.,
+        );
+        let zoom = zoom.clamp(self.min_zoom, self.max_zoom);
+        if center == self.center && zoom == self.zoom {
+            return;
+        }
+
+        self.center = center;
+        self.zoom = zoom;
+        cx.emit(MapViewEvent::Move { center, zoom });
+        cx.notify();
+    }
+
+    /// Set the markers.
+    pub fn set_markers(&mut self, markers: Vec<MapMarker>, cx: &mut Context<Self>) {
+        self.markers = markers;
+        self.selected_marker = None;
+        cx.notify();
+    }
+
+    /// Set the polylines.
+    pub fn set_polylines(&mut self, polylines: Vec<MapPolyline>, cx: &mut Context<Self>) {
+        self.polylines = polylines;
+        cx.notify();
+    }
+
+    /// Show the popover of the marker at the index, None to close it.
+    pub fn select_marker(&mut self, ix: Option<usize>, cx: &mut Context<Self>) {
+        self.selected_marker = ix.filter(|ix| *ix < self.markers.len());
+        cx.notify();
+    }
+
+    /// Returns the position in the map of the `position` in the window.
+    pub fn position_at(&self, position: Point<Pixels>) -> LatLng {
+        let (cx, cy) = project(self.center, self.zoom);
+        let offset = position - self.bounds.origin;
+        let x = cx + f64::from(f32::from(offset.x - self.bounds.size.width / 2.));
+        let y = cy + f64::from(f32::from(offset.y - self.bounds.size.height / 2.));
+        unproject(x, y, self.zoom)
+    }
+
+    /// Returns the point of the `position` relative to the map bounds.
+    fn to_screen(&self, position: LatLng) -> Point<Pixels> {
+        let (cx, cy) = project(self.center, self.zoom);
+        let (x, y) = project(position, self.zoom);
+        point(
+            px((x - cx) as f32) + self.bounds.size.width / 2.,
+            px((y - cy) as f32) + self.bounds.size.height / 2.,
+        )
+    }
+
+    /// Zoom by `delta`, keeping the position under the `anchor` in the window fixed.
+    fn zoom_at(&mut self, delta: f64, anchor: Point<Pixels>, cx: &mut Context<Self>) {
+        let zoom = (self.zoom + delta).clamp(self.min_zoom, self.max_zoom);
+        let position = self.position_at(anchor);
+        let offset = anchor
+            - self.bounds.origin
+            - point(self.bounds.size.width / 2., self.bounds.size.height / 2.);
+        let (x, y) = project(position, zoom);
+        let center = unproject(
+            x - f64::from(f32::from(offset.x)),
+            y - f64::from(f32::from(offset.y)),
+            zoom,
+        );
+        self.set_view(center, zoom, cx);
+    }
+
+    fn on_mouse_down(&mut self, event: &MouseDownEvent, _: &mut Window, cx: &mut Context<Self>) {
+        self.drag_position = Some(event.position);
+        if self.selected_marker.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    fn on_mouse_move(&mut self, event: &MouseMoveEvent, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(last_position) = self.drag_position else {
+            return;
+        };
+
+        self.drag_position = Some(event.position);
+        let delta = event.position - last_position;
+        let (x, y) = project(self.center, self.zoom);
+        let center = unproject(
+            x - f64::from(f32::from(delta.x)),
+            y - f64::from(f32::from(delta.y)),
+            self.zoom,
+        );
+        self.set_view(center, self.zoom, cx);
+    }
+
+    fn on_mouse_up(&mut self, _: &MouseUpEvent, _: &mut Window, _: &mut Context<Self>) {
+        self.drag_position = None;
+    }
+
+    fn on_scroll_wheel(
+        &mut self,
+        event: &ScrollWheelEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let delta = event.delta.pixel_delta(window.line_height());
+        self.zoom_at(f64::from(f32::from(delta.y)) / 100., event.position, cx);
+        cx.stop_propagation();
+    }
+
+    fn render_tiles(&self, scale_factor: f32) -> Vec<AnyElement> {
+        let size = self.bounds.size;
+        if size.width <= px(0.) || size.height <= px(0.) {
+            return vec![];
+        }
+
+        let z = self.zoom.floor() as u32;
+        let scale = 2f64.powf(self.zoom - z as f64);
+        let tile_size = TILE_SIZE * scale;
+        let count = 1i64 << z;
+
+        let (cx, cy) = project(self.center, self.zoom);
+        let left = cx - f64::from(f32::from(size.width)) / 2.;
+        let top = cy - f64::from(f32::from(size.height)) / 2.;
+        let right = left + f64::from(f32::from(size.width));
+        let bottom = top + f64::from(f32::from(size.height));
+
+        let mut tiles = vec![];
+        for ty in (top / tile_size).floor() as i64..=(bottom / tile_size).floor() as i64 {
+            if ty < 0 || ty >= count {
+                continue;
+            }
+            for tx in (left / tile_size).floor() as i64..=(right / tile_size).floor() as i64 {
+                let url = tile_url(&self.tile_url, z, tx.rem_euclid(count), ty, scale_factor);
+                tiles.push(
+                    img(url)
+                        .absolute()
+                        .left(px((tx as f64 * tile_size - left) as f32))
+                        .top(px((ty as f64 * tile_size - top) as f32))
+                        // Overlap a bit to avoid the seams between the tiles.
+                        .size(px(tile_size as f32 + 0.5))
+                        .into_any_element(),
+                );
+            }
+        }
+        tiles
+    }
+
+    fn render_marker(&self, ix: usize, marker: &MapMarker, cx: &mut Context<Self>) -> AnyElement {
+        let center = self.to_screen(marker.position);
+        let color = marker.color.unwrap_or(cx.theme().primary);
+
+        div()
+            .id(("marker", ix))
+            .absolute()
+            .left(center.x - MARKER_SIZE / 2.)
+            .top(center.y - MARKER_SIZE / 2.)
+            .size(MARKER_SIZE)
+            .rounded_full()
+            .border_2()
+            .border_color(cx.theme().background)
+            .bg(color)
+            .shadow_md()
+            .cursor_pointer()
+            .on_mouse_down(MouseButton::Left, |_, _, cx| cx.stop_propagation())
+            .on_click(cx.listener(move |this, _, _, cx| {
+                this.select_marker(Some(ix), cx);
+                cx.emit(MapViewEvent::ClickMarker(ix));
+            }))
+            .into_any_element()
+    }
+
+    fn render_popover(
+        &self,
+        marker: &MapMarker,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let center = self.to_screen(marker.position);
+        let content = match &marker.render_popover {
+            Some(render_popover) => render_popover(window, cx),
+            None => v_flex()
+                .gap_1()
+                .child(div().font_semibold().child(marker.title.clone()))
+                .when_some(marker.description.clone(), |this, description| {
+                    this.child(
+                        div()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(description),
+                    )
+                })
+                .into_any_element(),
+        };
+
+        // Anchor the popover above the marker, centered by a wide enough container.
+        let width = px(480.);
+        div()
+            .absolute()
+            .left(center.x - width / 2.)
+            .bottom(self.bounds.size.height - center.y + MARKER_SIZE)
+            .w(width)
+            .flex()
+            .justify_center()
+            .child(
+                div()
+                    .id("marker-popover")
+                    .max_w(px(240.))
+                    .p_2()
+                    .text_sm()
+                    .bg(cx.theme().popover)
+                    .text_color(cx.theme().popover_foreground)
+                    .border_1()
+                    .border_color(cx.theme().border)
+                    .rounded(cx.theme().radius)
+                    .shadow_md()
+                    .on_mouse_down(MouseButton::Left, |_, _, cx| cx.stop_propagation())
+                    .child(content),
+            )
+            .into_any_element()
+    }
+}
+
+impl EventEmitter<MapViewEvent> for MapViewState {}
+
+impl Render for MapViewState {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let state = cx.entity();
+        let polylines = self
+            .polylines
+            .iter()
+            .map(|polyline| {
+                (
+                    polyline
+                        .points
+                        .iter()
+                        .map(|p| self.to_screen(*p))
+                        .collect::<Vec<_>>(),
+                    polyline.color.unwrap_or(cx.theme().primary),
+                    polyline.width,
+                )
+            })
+            .collect::<Vec<_>>();
+        let markers = self
+            .markers
+            .iter()
+            .enumerate()
+            .map(|(ix, marker)| self.render_marker(ix, marker, cx))
+            .collect::<Vec<_>>();
+        let popover = self
+            .selected_marker
+            .and_then(|ix| self.markers.get(ix))
+            .map(|marker| self.render_popover(marker, window, cx));
+
+        div()
+            .relative()
+            .size_full()
+            .overflow_hidden()
+            .bg(cx.theme().muted)
+            .cursor_grab()
+            .when(self.drag_position.is_some(), |this| this.cursor_grabbing())
+            .on_mouse_down(MouseButton::Left, cx.listener(Self::on_mouse_down))
+            .on_mouse_move(cx.listener(Self::on_mouse_move))
+            .on_mouse_up(MouseButton::Left, cx.listener(Self::on_mouse_up))
+            .on_mouse_up_out(MouseButton::Left, cx.listener(Self::on_mouse_up))
+            .on_scroll_wheel(cx.listener(Self::on_scroll_wheel))
+            .children(self.render_tiles(window.scale_factor()))
+            .child(
+                canvas(
+                    move |bounds, _, cx| state.update(cx, |r, _| r.bounds = bounds),
+                    move |bounds, _, window, _| {
+                        for (points, color, width) in polylines.iter() {
+                            if points.len() < 2 {
+                                continue;
+                            }
+
+                            let mut builder = PathBuilder::stroke(*width);
+                            builder.move_to(bounds.origin + points[0]);
+                            for p in &points[1..] {
+                                builder.line_to(bounds.origin + *p);
+                            }
+                            if let Ok(path) = builder.build() {
+                                window.paint_path(path, *color);
+                            }
+                        }
+                    },
+                )
+                .absolute()
+                .size_full(),
+            )
+            .children(markers)
+            .children(popover)
+            .child(
+                v_flex()
+                    .absolute()
+                    .top_2()
+                    .right_2()
+                    .gap_1()
+                    .child(
+                        Button::new("zoom-in")
+                            .icon(IconName::Plus)
+                            .outline()
+                            .small()
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.set_view(this.center, this.zoom.floor() + 1., cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("zoom-out")
+                            .icon(IconName::Minus)
+                            .outline()
+                            .small()
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.set_view(this.center, this.zoom.ceil() - 1., cx);
+                            })),
+                    ),
+            )
+            .when_some(self.attribution.clone(), |this, attribution| {
+                this.child(
+                    div()
+                        .absolute()
+                        .bottom_0()
+                        .right_0()
+                        .px_1()
+                        .text_xs()
+                        .bg(cx.theme().background.opacity(0.7))
+                        .text_color(cx.theme().muted_foreground)
+                        .child(attribution),
+                )
+            })
+    }
+}
+
+/// A map view with slippy-map tiles, markers with popovers and polyline overlays.
+///
+/// Drag to pan, scroll to zoom at the cursor. The tiles are loaded by the image asset cache.
+///
+/// ```ignore
+/// let state = cx.new(|cx| MapViewState::new(window, cx));
+/// state.update(cx, |state, cx| {
+///     state.set_view(LatLng::new(51.505, -0.09), 12., cx);
+///     state.set_markers(vec![MapMarker::new(LatLng::new(51.5, -0.09), "London")], cx);
+/// });
+///
+/// MapView::new(&state).h(px(400.))
+/// ```
+#[derive(IntoElement)]
+pub struct MapView {
+    id: ElementId,
+    state: Entity<MapViewState>,
+    style: StyleRefinement,
+}
+
+impl MapView {
+    /// Create a new map view with the given state.
+    pub fn new(state: &Entity<MapViewState>) -> Self {
+        Self {
+            id: ElementId::Name(format!("map-view-{}", state.entity_id()).into()),
+            state: state.clone(),
+            style: StyleRefinement::default(),
+        }
+    }
+}
+
+impl Styled for MapView {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for MapView {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        div()
+            .id(self.id)
+            .size_full()
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded(cx.theme().radius)
+            .overflow_hidden()
+            .child(self.state)
+            .refine_style(&self.style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{project, tile_url, unproject, LatLng};
+
+    #[test]
+    fn test_projection() {
+        assert_eq!(project(LatLng::new(0., 0.), 0.), (128., 128.));
+        assert_eq!(project(LatLng::new(0., -180.), 1.), (0., 256.));
+
+        let position = LatLng::new(51.505, -0.09);
+        let (x, y) = project(position, 12.5);
+        let result = unproject(x, y, 12.5);
+        assert!((result.lat - position.lat).abs() < 1e-9);
+        assert!((result.lng - position.lng).abs() < 1e-9);
+
+        // The latitude is clamped.
+        let (_, y) = project(LatLng::new(90., 0.), 0.);
+        assert!(y.abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_tile_url() {
+        assert_eq!(
+            tile_url(
+                "https://tile.openstreetmap.org/{z}/{x}/{y}.png",
+                3,
+                4,
+                2,
+                2.
+            )
+            .as_ref(),
+            "https://tile.openstreetmap.org/3/4/2.png"
+        );
+        assert_eq!(
+            tile_url("https://example.com/{z}/{x}/{y}{r}.png", 3, 4, 2, 1.).as_ref(),
+            "https://example.com/3/4/2.png"
+        );
+        assert_eq!(
+            tile_url("https://example.com/{z}/{x}/{y}{r}.png", 3, 4, 2, 2.).as_ref(),
+            "https://example.com/3/4/2@2x.png"
+        );
+    }
+}
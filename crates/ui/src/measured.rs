@@ -0,0 +1,127 @@
+use std::rc::Rc;
+
+use gpui::{
+    canvas, div, prelude::FluentBuilder as _, size, AnyElement, App, ElementId,
+    InteractiveElement as _, IntoElement, ParentElement, Pixels, RenderOnce, Size, StyleRefinement,
+    Styled, Window,
+};
+use smallvec::SmallVec;
+
+use crate::StyledExt as _;
+
+type ContentBuilder = Box<dyn FnOnce(Size<Pixels>, &mut Window, &mut App) -> AnyElement>;
+
+/// A container that measures its own size, so the content can adapt to the width of the
+/// container instead of the window, like the CSS container queries.
+///
+/// The size is measured after the layout, so the content is built with the size of the
+/// previous frame, and it is zero in the first frame.
+///
+/// ```ignore
+/// Measured::new("toolbar")
+///     .w_full()
+///     .content(|size, _, _| {
+///         if size.width < px(480.) {
+///             h_flex().child(compact_toolbar())
+///         } else {
+///             h_flex().child(full_toolbar())
+///         }
+///     })
+///     .on_resize(|size, _, _| println!("toolbar resized: {:?}", size))
+/// ```
+#[derive(IntoElement)]
+pub struct Measured {
+    id: ElementId,
+    style: StyleRefinement,
+    children: SmallVec<[AnyElement; 1]>,
+    content: Option<ContentBuilder>,
+    on_resize: Option<Rc<dyn Fn(&Size<Pixels>, &mut Window, &mut App)>>,
+}
+
+impl Measured {
+    /// Create a new measured container, the `id` keeps the measured size.
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            style: StyleRefinement::default(),
+            children: SmallVec::new(),
+            content: None,
+            on_resize: None,
+        }
+    }
+
+    /// Build the content with the size of the container, it is rendered after the children.
+    pub fn content<E>(
+        mut self,
+        content: impl FnOnce(Size<Pixels>, &mut Window, &mut App) -> E + 'static,
+    ) -> Self
+    where
+        E: IntoElement,
+    {
+        self.content = Some(Box::new(move |size, window, cx| {
+            content(size, window, cx).into_any_element()
+        }));
+        self
+    }
+
+    /// Add a listener for the size of the container is changed.
+    pub fn on_resize(
+        mut self,
+        handler: impl Fn(&Size<Pixels>, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_resize = Some(Rc::new(handler));
+        self
+    }
+}
+
+impl ParentElement for Measured {
+    fn extend(&mut self, elements: impl IntoIterator<Item = AnyElement>) {
+        self.children.extend(elements);
+    }
+}
+
+impl Styled for Measured {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for Measured {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let state =
+            window.use_keyed_state(self.id.clone(), cx, |_, _| size(Pixels::ZERO, Pixels::ZERO));
+        let measured_size = *state.read(cx);
+        let has_content = self.content.is_some();
+        let on_resize = self.on_resize;
+
+        div()
+            .id(self.id)
+            .relative()
+            .refine_style(&self.style)
+            .children(self.children)
+            .when_some(self.content, |this, content| {
+                this.child(content(measured_size, window, cx))
+            })
+            .child(
+                canvas(
+                    move |bounds, window, cx| {
+                        if *state.read(cx) == bounds.size {
+                            return;
+                        }
+
+                        state.update(cx, |state, _| *state = bounds.size);
+                        if let Some(on_resize) = on_resize.as_ref() {
+                            on_resize(&bounds.size, window, cx);
+                        }
+                        // Render the content again with the new size.
+                        if has_content {
+                            window.refresh();
+                        }
+                    },
+                    |_, _, _, _| {},
+                )
+                .absolute()
+                .size_full(),
+            )
+    }
+}
@@ -1,14 +1,17 @@
 use crate::{
-    actions::{Cancel, SelectLeft, SelectRight},
+    actions::{Cancel, Confirm, SelectDown, SelectLeft, SelectRight},
     button::{Button, ButtonVariants},
+    elevation::Layer,
     h_flex,
     menu::PopupMenu,
-    Selectable, Sizable,
+    portal::Portal,
+    roving_focus::RovingFocus,
+    ActiveTheme, FocusableExt as _, Selectable, Sizable,
 };
 use gpui::{
-    anchored, deferred, div, prelude::FluentBuilder, px, App, AppContext as _, ClickEvent, Context,
-    DismissEvent, Entity, Focusable, InteractiveElement as _, IntoElement, KeyBinding, OwnedMenu,
-    ParentElement, Render, SharedString, StatefulInteractiveElement, Styled, Subscription, Window,
+    div, prelude::FluentBuilder, px, App, AppContext as _, ClickEvent, Context, DismissEvent,
+    Entity, Focusable, InteractiveElement as _, IntoElement, KeyBinding, OwnedMenu, ParentElement,
+    Render, SharedString, StatefulInteractiveElement, Styled, Subscription, Window,
 };
 
 const CONTEXT: &str = "AppMenuBar";
@@ -17,13 +20,18 @@ pub fn init(cx: &mut App) {
         KeyBinding::new("escape", Cancel, Some(CONTEXT)),
         KeyBinding::new("left", SelectLeft, Some(CONTEXT)),
         KeyBinding::new("right", SelectRight, Some(CONTEXT)),
+        KeyBinding::new("down", SelectDown, Some(CONTEXT)),
+        KeyBinding::new("enter", Confirm { secondary: false }, Some(CONTEXT)),
     ]);
 }
 
 /// The application menu bar, for Windows and Linux.
+///
+/// It takes a single Tab stop, and the Left / Right keys move the focus between the menus.
 pub struct AppMenuBar {
     menus: Vec<Entity<AppMenu>>,
     selected_ix: Option<usize>,
+    roving: RovingFocus,
 }
 
 impl AppMenuBar {
@@ -31,7 +39,7 @@ impl AppMenuBar {
     pub fn new(window: &mut Window, cx: &mut App) -> Entity<Self> {
         cx.new(|cx| {
             let menu_bar = cx.entity();
-            let menus = cx
+            let menus: Vec<_> = cx
                 .get_menus()
                 .unwrap_or_default()
                 .iter()
@@ -41,6 +49,7 @@ impl AppMenuBar {
 
             Self {
                 selected_ix: None,
+                roving: RovingFocus::new(menus.len(), cx),
                 menus,
             }
         })
@@ -48,6 +57,10 @@ impl AppMenuBar {
 
     fn on_move_left(&mut self, _: &SelectLeft, window: &mut Window, cx: &mut Context<Self>) {
         let Some(selected_ix) = self.selected_ix else {
+            if self.roving.focused_ix(window).is_some() {
+                self.roving.focus_prev(window);
+                cx.notify();
+            }
             return;
         };
 
@@ -61,6 +74,10 @@ impl AppMenuBar {
 
     fn on_move_right(&mut self, _: &SelectRight, window: &mut Window, cx: &mut Context<Self>) {
         let Some(selected_ix) = self.selected_ix else {
+            if self.roving.focused_ix(window).is_some() {
+                self.roving.focus_next(window);
+                cx.notify();
+            }
             return;
         };
 
@@ -76,8 +93,30 @@ impl AppMenuBar {
         self.set_selected_ix(None, window, cx);
     }
 
+    /// Open the focused menu.
+    fn on_open(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_ix.is_some() {
+            cx.propagate();
+            return;
+        }
+        if let Some(ix) = self.roving.focused_ix(window) {
+            self.set_selected_ix(Some(ix), window, cx);
+        }
+    }
+
+    fn on_confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        self.on_open(window, cx);
+    }
+
+    fn on_move_down(&mut self, _: &SelectDown, window: &mut Window, cx: &mut Context<Self>) {
+        self.on_open(window, cx);
+    }
+
     fn set_selected_ix(&mut self, ix: Option<usize>, _: &mut Window, cx: &mut Context<Self>) {
         self.selected_ix = ix;
+        if let Some(ix) = ix {
+            self.roving.set_active_ix(ix);
+        }
         cx.notify();
     }
 
@@ -95,6 +134,8 @@ impl Render for AppMenuBar {
             .on_action(cx.listener(Self::on_move_left))
             .on_action(cx.listener(Self::on_move_right))
             .on_action(cx.listener(Self::on_cancel))
+            .on_action(cx.listener(Self::on_confirm))
+            .on_action(cx.listener(Self::on_move_down))
             .size_full()
             .gap_x_1()
             .overflow_x_scroll()
@@ -212,34 +253,41 @@ impl Render for AppMenu {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let menu_bar = self.menu_bar.read(cx);
         let is_selected = menu_bar.selected_ix == Some(self.ix);
+        let focus_handle = menu_bar.roving.focus_handle(self.ix).cloned();
+        let is_focused = focus_handle
+            .as_ref()
+            .is_some_and(|focus_handle| focus_handle.is_focused(window));
 
         div()
             .id(self.ix)
             .relative()
+            .rounded(cx.theme().radius)
+            .when_some(focus_handle, |this, focus_handle| {
+                this.track_focus(&focus_handle)
+            })
+            .focus_ring(is_focused, px(0.), window, cx)
             .child(
                 Button::new("menu")
                     .small()
                     .py_0p5()
                     .compact()
                     .ghost()
+                    .tab_stop(false)
                     .label(self.name.clone())
                     .selected(is_selected)
                     .on_click(cx.listener(Self::handle_trigger_click)),
             )
             .on_hover(cx.listener(Self::handle_hover))
             .when(is_selected, |this| {
-                this.child(deferred(
-                    anchored()
-                        .anchor(gpui::Corner::TopLeft)
-                        .snap_to_window_with_margin(px(8.))
-                        .child(
-                            div()
-                                .size_full()
-                                .occlude()
-                                .top_1()
-                                .child(self.build_popup_menu(window, cx)),
-                        ),
-                ))
+                this.child(
+                    Portal::new().layer(Layer::Menu).child(
+                        div()
+                            .size_full()
+                            .occlude()
+                            .top_1()
+                            .child(self.build_popup_menu(window, cx)),
+                    ),
+                )
             })
     }
 }
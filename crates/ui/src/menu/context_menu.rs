@@ -7,7 +7,7 @@ use gpui::{
     ParentElement, Pixels, Point, Position, Stateful, Style, Subscription, Window,
 };
 
-use crate::menu::PopupMenu;
+use crate::{elevation::Layer, menu::PopupMenu};
 
 /// A extension trait for adding a context menu to an element.
 pub trait ContextMenuExt: ParentElement + Sized {
@@ -162,7 +162,7 @@ impl Element for ContextMenu {
                                     this.child(div().occlude().child(menu.clone()))
                                 }),
                         )
-                        .with_priority(1)
+                        .with_priority(Layer::Menu.priority())
                         .into_any();
 
                         let menu_layout_id = menu_element.request_layout(window, cx);
@@ -2,17 +2,21 @@ use std::{rc::Rc, time::Duration};
 
 use gpui::{
     anchored, div, hsla, point, prelude::FluentBuilder, px, relative, Animation, AnimationExt as _,
-    AnyElement, App, Axis, Bounds, BoxShadow, ClickEvent, Div, Edges, FocusHandle, Hsla,
-    InteractiveElement, IntoElement, KeyBinding, MouseButton, ParentElement, Pixels, Point,
-    RenderOnce, SharedString, StyleRefinement, Styled, Window,
+    AnyElement, App, Axis, Bounds, ClickEvent, Div, Edges, FocusHandle, Hsla, InteractiveElement,
+    IntoElement, KeyBinding, MouseButton, ParentElement, Pixels, Point, RenderOnce, SharedString,
+    StyleRefinement, Styled, Window,
 };
 use rust_i18n::t;
 
 use crate::{
     actions::{Cancel, Confirm},
-    animation::cubic_bezier,
+    animation::{cubic_bezier, MotionSettings},
+    backdrop::Material,
     button::{Button, ButtonVariant, ButtonVariants as _},
-    h_flex, v_flex, ActiveTheme as _, ContextModal, IconName, Root, Sizable as _, StyledExt,
+    elevation::Elevation,
+    h_flex,
+    sound::{Sound, SoundFeedback},
+    v_flex, ActiveTheme as _, ContextModal, IconName, Root, Sizable as _, StyledExt,
 };
 
 const CONTEXT: &str = "Modal";
@@ -103,7 +107,7 @@ pub(crate) fn overlay_color(overlay: bool, cx: &App) -> Hsla {
         return hsla(0., 0., 0., 0.);
     }
 
-    cx.theme().overlay
+    Material::Scrim.color(cx)
 }
 
 impl Modal {
@@ -305,6 +309,7 @@ impl RenderOnce for Modal {
                                 if !on_ok(&ClickEvent::default(), window, cx) {
                                     return;
                                 }
+                                SoundFeedback::play(Sound::Confirm, cx);
                             }
 
                             on_close(&ClickEvent::default(), window, cx);
@@ -373,8 +378,12 @@ impl RenderOnce for Modal {
             paddings.bottom = pb.to_pixels(base_size, rem_size);
         }
 
-        let animation = Animation::new(Duration::from_secs_f64(0.25))
-            .with_easing(cubic_bezier(0.32, 0.72, 0., 1.));
+        let shadow = Elevation::Modal.shadow(cx);
+        let motion = MotionSettings::global(cx);
+        let animation = motion.animation(
+            Animation::new(Duration::from_secs_f64(0.25))
+                .with_easing(cubic_bezier(0.32, 0.72, 0., 1.)),
+        );
 
         anchored()
             .position(point(window_paddings.left, window_paddings.top))
@@ -445,6 +454,7 @@ impl RenderOnce for Modal {
                                     move |_: &Confirm, window, cx| {
                                         if let Some(on_ok) = &on_ok {
                                             if on_ok(&ClickEvent::default(), window, cx) {
+                                                SoundFeedback::play(Sound::Confirm, cx);
                                                 on_close(&ClickEvent::default(), window, cx);
                                                 window.close_modal(cx);
                                             }
@@ -511,22 +521,16 @@ impl RenderOnce for Modal {
                                 )
                             })
                             .with_animation("slide-down", animation.clone(), move |this, delta| {
-                                let y_offset = px(0.) + delta * px(30.);
-                                // This is equivalent to `shadow_xl` with an extra opacity.
-                                let shadow = vec![
-                                    BoxShadow {
-                                        color: hsla(0., 0., 0., 0.1 * delta),
-                                        offset: point(px(0.), px(20.)),
-                                        blur_radius: px(25.),
-                                        spread_radius: px(-5.),
-                                    },
-                                    BoxShadow {
-                                        color: hsla(0., 0., 0., 0.1 * delta),
-                                        offset: point(px(0.), px(8.)),
-                                        blur_radius: px(10.),
-                                        spread_radius: px(-6.),
-                                    },
-                                ];
+                                let y_offset = px(0.) + motion.motion_delta(delta) * px(30.);
+                                // Fade in the shadow with the Modal.
+                                let shadow = shadow
+                                    .iter()
+                                    .cloned()
+                                    .map(|mut shadow| {
+                                        shadow.color.a *= delta;
+                                        shadow
+                                    })
+                                    .collect::<Vec<_>>();
                                 this.top(y + y_offset).shadow(shadow)
                             }),
                     )
@@ -0,0 +1,362 @@
+use std::{cell::RefCell, rc::Rc};
+
+use gpui::{
+    div, prelude::FluentBuilder as _, px, AnyElement, App, AppContext as _, Entity, IntoElement,
+    ParentElement, Pixels, SharedString, Styled, Window,
+};
+use rust_i18n::t;
+
+use crate::{
+    button::Button, h_flex, modal::ModalButtonProps, v_flex, ActiveTheme as _, ContextModal as _,
+    Icon, IconName, Sizable as _, StyledExt as _,
+};
+
+type RenderPageFn<T> = Rc<dyn Fn(&Entity<T>, &mut Window, &mut App) -> AnyElement>;
+type ValidateFn<T> = Rc<dyn Fn(&T, &App) -> Result<(), SharedString>>;
+type FinishFn<T> = Rc<dyn Fn(&T, &mut Window, &mut App)>;
+
+/// A page of the [`ModalWizard`].
+pub struct WizardPage<T: 'static> {
+    title: SharedString,
+    render: RenderPageFn<T>,
+    validate: Option<ValidateFn<T>>,
+}
+
+impl<T: 'static> WizardPage<T> {
+    /// Create a new page with the title and the content.
+    ///
+    /// The content is rendered with the data entity of the wizard, keep the state of
+    /// the page (e.g. the `InputState`) in the data to preserve it when navigating back.
+    pub fn new<E>(
+        title: impl Into<SharedString>,
+        render: impl Fn(&Entity<T>, &mut Window, &mut App) -> E + 'static,
+    ) -> Self
+    where
+        E: IntoElement,
+    {
+        Self {
+            title: title.into(),
+            render: Rc::new(move |data, window, cx| render(data, window, cx).into_any_element()),
+            validate: None,
+        }
+    }
+
+    /// Set the validation of the page, it is called before going to the next page.
+    ///
+    /// Return `Err` with the message to stay on this page and show the message.
+    pub fn validate(
+        mut self,
+        validate: impl Fn(&T, &App) -> Result<(), SharedString> + 'static,
+    ) -> Self {
+        self.validate = Some(Rc::new(validate));
+        self
+    }
+}
+
+/// The navigation state of the [`ModalWizard`].
+#[derive(Debug, Default, Clone, PartialEq)]
+struct WizardState {
+    step: usize,
+    error: Option<SharedString>,
+}
+
+impl WizardState {
+    /// Go to the next step, returns false if it is already the last step.
+    fn next(&mut self, steps: usize) -> bool {
+        self.error = None;
+        if self.step + 1 >= steps {
+            return false;
+        }
+        self.step += 1;
+        true
+    }
+
+    /// Go back to the previous step.
+    fn back(&mut self) {
+        self.error = None;
+        self.step = self.step.saturating_sub(1);
+    }
+
+    fn is_last(&self, steps: usize) -> bool {
+        self.step + 1 >= steps
+    }
+}
+
+/// A multi-page [`Modal`](crate::modal::Modal) to collect the data step by step.
+///
+/// - Each page can validate the data before going to the next page.
+/// - Go back to the previous page keeps the data, the pages are rendered from the same data.
+/// - The optional summary page is shown at the end to review the data before finishing.
+///
+/// ```ignore
+/// ModalWizard::new(Profile::new(window, cx))
+///     .title("Create Profile")
+///     .page(
+///         WizardPage::new("Name", |data, _, cx| Input::new(&data.read(cx).name))
+///             .validate(|data, cx| data.validate_name(cx)),
+///     )
+///     .page(WizardPage::new("Email", |data, _, cx| Input::new(&data.read(cx).email)))
+///     .summary(|data, _, cx| data.summary(cx))
+///     .on_finish(|data, _, cx| data.save(cx))
+///     .open(window, cx);
+/// ```
+pub struct ModalWizard<T: 'static> {
+    data: T,
+    title: Option<SharedString>,
+    pages: Vec<WizardPage<T>>,
+    summary: Option<RenderPageFn<T>>,
+    on_finish: Option<FinishFn<T>>,
+    width: Pixels,
+}
+
+impl<T: 'static> ModalWizard<T> {
+    /// Create a new wizard with the initial data.
+    pub fn new(data: T) -> Self {
+        Self {
+            data,
+            title: None,
+            pages: vec![],
+            summary: None,
+            on_finish: None,
+            width: px(520.),
+        }
+    }
+
+    /// Set the title of the wizard.
+    pub fn title(mut self, title: impl Into<SharedString>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Add a page to the wizard.
+    pub fn page(mut self, page: WizardPage<T>) -> Self {
+        self.pages.push(page);
+        self
+    }
+
+    /// Set the summary page to confirm the collected data, it is shown after all pages.
+    pub fn summary<E>(mut self, render: impl Fn(&T, &mut Window, &mut App) -> E + 'static) -> Self
+    where
+        E: IntoElement,
+    {
+        self.summary = Some(Rc::new(move |data, window, cx| {
+            data.update(cx, |data, cx| render(data, window, cx).into_any_element())
+        }));
+        self
+    }
+
+    /// Set the callback with the collected data when the wizard is finished.
+    pub fn on_finish(mut self, on_finish: impl Fn(&T, &mut Window, &mut App) + 'static) -> Self {
+        self.on_finish = Some(Rc::new(on_finish));
+        self
+    }
+
+    /// Set the width of the modal, default: 520px
+    pub fn width(mut self, width: impl Into<Pixels>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    /// Open the wizard in a modal.
+    pub fn open(self, window: &mut Window, cx: &mut App) {
+        let data = cx.new(|_| self.data);
+        let state = Rc::new(RefCell::new(WizardState::default()));
+
+        let mut titles = self
+            .pages
+            .iter()
+            .map(|page| page.title.clone())
+            .collect::<Vec<_>>();
+        let mut renders = self
+            .pages
+            .iter()
+            .map(|page| page.render.clone())
+            .collect::<Vec<_>>();
+        let validates = self
+            .pages
+            .iter()
+            .map(|page| page.validate.clone())
+            .collect::<Rc<[_]>>();
+        if let Some(summary) = self.summary {
+            titles.push(t!("ModalWizard.summary").into());
+            renders.push(summary);
+        }
+        let titles: Rc<[SharedString]> = titles.into();
+        let steps = titles.len();
+        let title = self.title;
+        let on_finish = self.on_finish;
+        let width = self.width;
+
+        window.open_modal(cx, move |modal, window, cx| {
+            let current = state.borrow().clone();
+            let is_last = current.is_last(steps);
+            let content = renders
+                .get(current.step)
+                .map(|render| render(&data, window, cx));
+
+            modal
+                .width(width)
+                .overlay_closable(false)
+                .when_some(title.clone(), |this, title| this.title(title))
+                .child(
+                    v_flex()
+                        .gap_4()
+                        .child(render_progress(&titles, current.step, cx))
+                        .children(content)
+                        .when_some(current.error.clone(), |this, error| {
+                            this.child(div().text_sm().text_color(cx.theme().danger).child(error))
+                        }),
+                )
+                .button_props(ModalButtonProps::default().ok_text(if is_last {
+                    t!("ModalWizard.finish")
+                } else {
+                    t!("ModalWizard.next")
+                }))
+                .footer({
+                    let state = state.clone();
+                    move |ok, cancel, window, cx| {
+                        let step = state.borrow().step;
+                        let mut buttons = vec![];
+                        if step > 0 {
+                            buttons.push(
+                                Button::new("back")
+                                    .label(t!("ModalWizard.back"))
+                                    .outline()
+                                    .on_click({
+                                        let state = state.clone();
+                                        move |_, window, _| {
+                                            state.borrow_mut().back();
+                                            window.refresh();
+                                        }
+                                    })
+                                    .into_any_element(),
+                            );
+                        }
+                        buttons.push(div().flex_1().into_any_element());
+                        buttons.push(cancel(window, cx));
+                        buttons.push(ok(window, cx));
+                        buttons
+                    }
+                })
+                .on_ok({
+                    let state = state.clone();
+                    let data = data.clone();
+                    let validates = validates.clone();
+                    let on_finish = on_finish.clone();
+                    move |_, window, cx| {
+                        let step = state.borrow().step;
+                        if let Some(Some(validate)) = validates.get(step) {
+                            if let Err(err) = validate(data.read(cx), cx) {
+                                state.borrow_mut().error = Some(err);
+                                window.refresh();
+                                return false;
+                            }
+                        }
+
+                        if state.borrow_mut().next(steps) {
+                            window.refresh();
+                            return false;
+                        }
+
+                        if let Some(on_finish) = &on_finish {
+                            data.update(cx, |data, cx| on_finish(data, window, cx));
+                        }
+                        true
+                    }
+                })
+        });
+    }
+}
+
+/// Render the progress indicator of the steps.
+fn render_progress(titles: &[SharedString], step: usize, cx: &App) -> impl IntoElement {
+    v_flex()
+        .gap_2()
+        .child(
+            h_flex()
+                .gap_2()
+                .children(titles.iter().enumerate().map(|(ix, title)| {
+                    let is_done = ix < step;
+                    let is_active = ix == step;
+
+                    h_flex()
+                        .gap_2()
+                        .when(ix + 1 < titles.len(), |this| this.flex_1())
+                        .child(
+                            h_flex()
+                                .flex_shrink_0()
+                                .size_6()
+                                .justify_center()
+                                .rounded_full()
+                                .text_xs()
+                                .map(|this| {
+                                    if is_done || is_active {
+                                        this.bg(cx.theme().primary)
+                                            .text_color(cx.theme().primary_foreground)
+                                    } else {
+                                        this.border_1()
+                                            .border_color(cx.theme().border)
+                                            .text_color(cx.theme().muted_foreground)
+                                    }
+                                })
+                                .map(|this| {
+                                    if is_done {
+                                        this.child(Icon::new(IconName::Check).xsmall())
+                                    } else {
+                                        this.child(format!("{}", ix + 1))
+                                    }
+                                }),
+                        )
+                        .child(
+                            div()
+                                .text_sm()
+                                .whitespace_nowrap()
+                                .when(is_active, |this| this.font_semibold())
+                                .when(!is_active, |this| {
+                                    this.text_color(cx.theme().muted_foreground)
+                                })
+                                .child(title.clone()),
+                        )
+                        .when(ix + 1 < titles.len(), |this| {
+                            this.child(div().flex_1().min_w_4().h_px().bg(if is_done {
+                                cx.theme().primary
+                            } else {
+                                cx.theme().border
+                            }))
+                        })
+                })),
+        )
+        .child(
+            div()
+                .text_xs()
+                .text_color(cx.theme().muted_foreground)
+                .child(
+                    t!("ModalWizard.step", current = step + 1, total = titles.len()).to_string(),
+                ),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WizardState;
+
+    #[test]
+    fn test_wizard_navigation() {
+        let mut state = WizardState::default();
+        assert!(!state.is_last(3));
+        assert!(state.next(3));
+        assert!(state.next(3));
+        assert_eq!(state.step, 2);
+        assert!(state.is_last(3));
+        assert!(!state.next(3));
+        assert_eq!(state.step, 2);
+
+        state.error = Some("Required".into());
+        state.back();
+        assert_eq!(state.step, 1);
+        assert_eq!(state.error, None);
+        state.back();
+        state.back();
+        assert_eq!(state.step, 0);
+    }
+}
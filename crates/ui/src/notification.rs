@@ -2,24 +2,27 @@ use std::{
     any::TypeId,
     collections::{HashMap, VecDeque},
     rc::Rc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use gpui::{
     div, prelude::FluentBuilder, px, Animation, AnimationExt, AnyElement, App, AppContext,
-    ClickEvent, Context, DismissEvent, ElementId, Entity, EventEmitter, InteractiveElement as _,
-    IntoElement, ParentElement as _, Render, SharedString, StatefulInteractiveElement,
-    StyleRefinement, Styled, Subscription, Window,
+    ClickEvent, Context, DismissEvent, ElementId, Entity, EventEmitter, Global,
+    InteractiveElement as _, IntoElement, ParentElement as _, Render, SharedString,
+    StatefulInteractiveElement, StyleRefinement, Styled, Subscription, Task, Window,
 };
 use smol::Timer;
 
 use crate::{
-    animation::cubic_bezier,
+    animation::{cubic_bezier, MotionSettings},
     button::{Button, ButtonVariants as _},
-    h_flex, v_flex, ActiveTheme as _, Icon, IconName, Sizable as _, StyledExt,
+    elevation::Elevation,
+    h_flex,
+    sound::{Sound, SoundFeedback},
+    v_flex, ActiveTheme as _, Icon, IconName, Sizable as _, StyledExt,
 };
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum NotificationType {
     #[default]
     Info,
@@ -37,6 +40,64 @@ impl NotificationType {
             Self::Error => Icon::new(IconName::CircleX).text_color(cx.theme().danger),
         }
     }
+
+    fn sound(&self) -> Sound {
+        match self {
+            Self::Error => Sound::Error,
+            _ => Sound::Toast,
+        }
+    }
+}
+
+/// The global settings of the notifications, to keep the noisy background tasks from
+/// flooding the UI.
+///
+/// ```ignore
+/// NotificationSettings {
+///     do_not_disturb: true,
+///     ..NotificationSettings::global(cx)
+/// }
+/// .set_global(cx);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotificationSettings {
+    /// Hold the notifications until the do-not-disturb is turned off, except the errors,
+    /// default is false.
+    pub do_not_disturb: bool,
+    /// The max number of the notifications from the same [`Notification::source`] in the
+    /// `rate_limit_window`, the rest are dropped, default is 3.
+    pub rate_limit: usize,
+    /// Default is 10 seconds.
+    pub rate_limit_window: Duration,
+    /// The max number of the notifications held by the `do_not_disturb`, the oldest are
+    /// dropped, default is 50.
+    pub max_held: usize,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            do_not_disturb: false,
+            rate_limit: 3,
+            rate_limit_window: Duration::from_secs(10),
+            max_held: 50,
+        }
+    }
+}
+
+impl Global for NotificationSettings {}
+
+impl NotificationSettings {
+    /// Returns the global notification settings.
+    pub fn global(cx: &App) -> Self {
+        cx.try_global::<Self>().copied().unwrap_or_default()
+    }
+
+    /// Set the global notification settings, the held notifications are shown if the
+    /// do-not-disturb is turned off.
+    pub fn set_global(self, cx: &mut App) {
+        cx.set_global(self);
+    }
 }
 
 #[derive(Debug, PartialEq, Clone, Hash, Eq)]
@@ -70,10 +131,15 @@ pub struct Notification {
     message: Option<SharedString>,
     icon: Option<Icon>,
     autohide: bool,
+    silent: bool,
+    source: Option<SharedString>,
+    /// The number of the identical notifications collapsed into this one.
+    count: usize,
     action_builder: Option<Rc<dyn Fn(&mut Window, &mut Context<Self>) -> Button>>,
     content_builder: Option<Rc<dyn Fn(&mut Window, &mut Context<Self>) -> AnyElement>>,
     on_click: Option<Rc<dyn Fn(&ClickEvent, &mut Window, &mut App)>>,
     closing: bool,
+    _autohide_task: Task<()>,
 }
 
 impl From<String> for Notification {
@@ -122,10 +188,14 @@ impl Notification {
             type_: None,
             icon: None,
             autohide: true,
+            silent: false,
+            source: None,
+            count: 1,
             action_builder: None,
             content_builder: None,
             on_click: None,
             closing: false,
+            _autohide_task: Task::ready(()),
         }
     }
 
@@ -208,6 +278,48 @@ impl Notification {
         self
     }
 
+    /// Set to not play the [`Sound`] of the notification type, default is false.
+    pub fn silent(mut self, silent: bool) -> Self {
+        self.silent = silent;
+        self
+    }
+
+    /// Set the source of the notification, e.g. the name of the background task, to limit the
+    /// rate of the notifications from the same source, see [`NotificationSettings::rate_limit`].
+    pub fn source(mut self, source: impl Into<SharedString>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Returns the number of the identical notifications collapsed into this one.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the id set by [`Self::id`] or [`Self::id1`], `None` for the default unique id.
+    fn explicit_id(&self) -> Option<&NotificationId> {
+        match &self.id {
+            NotificationId::IdAndElementId(type_id, _)
+                if *type_id == TypeId::of::<DefaultIdType>() =>
+            {
+                None
+            }
+            id => Some(id),
+        }
+    }
+
+    /// Returns true if the `other` has the same explicit id, type, title, message and source,
+    /// the notifications with custom content are never identical.
+    fn is_identical(&self, other: &Self) -> bool {
+        self.content_builder.is_none()
+            && other.content_builder.is_none()
+            && self.explicit_id() == other.explicit_id()
+            && self.type_ == other.type_
+            && self.title == other.title
+            && self.message == other.message
+            && self.source == other.source
+    }
+
     /// Set the click callback of the notification.
     pub fn on_click(
         mut self,
@@ -232,8 +344,9 @@ impl Notification {
         cx.notify();
 
         // Dismiss the notification after 0.15s to show the animation.
+        let delay = MotionSettings::global(cx).duration(Duration::from_secs_f32(0.15));
         cx.spawn(async move |view, cx| {
-            Timer::after(Duration::from_secs_f32(0.15)).await;
+            Timer::after(delay).await;
             cx.update(|cx| {
                 if let Some(view) = view.upgrade() {
                     view.update(cx, |view, cx| {
@@ -265,6 +378,7 @@ impl Styled for Notification {
 impl Render for Notification {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let closing = self.closing;
+        let motion = MotionSettings::global(cx);
         let icon = match self.type_ {
             None => self.icon.clone(),
             Some(type_) => Some(type_.icon(cx)),
@@ -281,7 +395,7 @@ impl Render for Notification {
             .border_color(cx.theme().border)
             .bg(cx.theme().popover)
             .rounded(cx.theme().radius_lg)
-            .shadow_md()
+            .elevation(Elevation::Overlay, cx)
             .py_3p5()
             .px_4()
             .gap_3()
@@ -304,6 +418,18 @@ impl Render for Notification {
                         this.child(child_builder(window, cx))
                     }),
             )
+            .when(self.count > 1, |this| {
+                this.child(
+                    div()
+                        .flex_shrink_0()
+                        .px_1p5()
+                        .rounded_full()
+                        .bg(cx.theme().muted)
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(format!("×{}", self.count)),
+                )
+            })
             .when_some(self.action_builder.clone(), |this, action_builder| {
                 this.child(action_builder(window, cx).small().mr_3p5())
             })
@@ -330,18 +456,20 @@ impl Render for Notification {
             )
             .with_animation(
                 ElementId::NamedInteger("slide-down".into(), closing as u64),
-                Animation::new(Duration::from_secs_f64(0.25))
-                    .with_easing(cubic_bezier(0.4, 0., 0.2, 1.)),
+                motion.animation(
+                    Animation::new(Duration::from_secs_f64(0.25))
+                        .with_easing(cubic_bezier(0.4, 0., 0.2, 1.)),
+                ),
                 move |this, delta| {
                     if closing {
-                        let x_offset = px(0.) + delta * px(45.);
+                        let x_offset = px(0.) + motion.motion_delta(delta) * px(45.);
                         let opacity = 1. - delta;
                         this.left(px(0.) + x_offset)
                             .shadow_none()
                             .opacity(opacity)
                             .when(opacity < 0.85, |this| this.shadow_none())
                     } else {
-                        let y_offset = px(-45.) + delta * px(45.);
+                        let y_offset = px(-45.) + motion.motion_delta(delta) * px(45.);
                         let opacity = delta;
                         this.top(px(0.) + y_offset)
                             .opacity(opacity)
@@ -357,15 +485,41 @@ pub struct NotificationList {
     /// Notifications that will be auto hidden.
     pub(crate) notifications: VecDeque<Entity<Notification>>,
     expanded: bool,
+    /// The notifications held by the [`NotificationSettings::do_not_disturb`].
+    held: VecDeque<Notification>,
+    /// The times of the recent notifications by the source, for the rate limiting.
+    source_times: HashMap<SharedString, VecDeque<Instant>>,
     _subscriptions: HashMap<NotificationId, Subscription>,
+    _settings_subscription: Subscription,
 }
 
 impl NotificationList {
-    pub fn new(_window: &mut Window, _cx: &mut Context<Self>) -> Self {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let _settings_subscription =
+            cx.observe_global_in::<NotificationSettings>(window, |this, window, cx| {
+                if NotificationSettings::global(cx).do_not_disturb {
+                    return;
+                }
+
+                // Play the sound once for all the held notifications.
+                let mut sound_played = false;
+                for notification in std::mem::take(&mut this.held) {
+                    if this.collapse_identical(&notification, window, cx) {
+                        continue;
+                    }
+                    let silent = notification.silent;
+                    this.show(notification, !sound_played, window, cx);
+                    sound_played |= !silent;
+                }
+            });
+
         Self {
             notifications: VecDeque::new(),
             expanded: false,
+            held: VecDeque::new(),
+            source_times: HashMap::new(),
             _subscriptions: HashMap::new(),
+            _settings_subscription,
         }
     }
 
@@ -376,8 +530,96 @@ impl NotificationList {
         cx: &mut Context<Self>,
     ) {
         let notification = notification.into();
+        let settings = NotificationSettings::global(cx);
+        let hold = settings.do_not_disturb && notification.type_ != Some(NotificationType::Error);
+
+        // Collapse the identical notifications into one with a counter.
+        if hold {
+            if let Some(identical) = self
+                .held
+                .iter_mut()
+                .find(|note| note.is_identical(&notification))
+            {
+                identical.count += notification.count;
+                cx.notify();
+                return;
+            }
+        } else if self.collapse_identical(&notification, window, cx) {
+            return;
+        }
+
+        // Remove the expired times, and the sources without the recent notifications.
+        let now = Instant::now();
+        self.source_times.retain(|_, times| {
+            times.retain(|time| now.duration_since(*time) < settings.rate_limit_window);
+            !times.is_empty()
+        });
+        if let Some(source) = notification.source.clone() {
+            let times = self.source_times.entry(source).or_default();
+            if times.len() >= settings.rate_limit {
+                return;
+            }
+            times.push_back(now);
+        }
+
+        if hold {
+            self.held.retain(|note| note.id != notification.id);
+            self.held.push_back(notification);
+            while self.held.len() > settings.max_held {
+                self.held.pop_front();
+            }
+            cx.notify();
+            return;
+        }
+
+        self.show(notification, true, window, cx);
+    }
+
+    /// Add the count of the `notification` to the identical visible one, and restart its
+    /// auto hide timer, returns false if not found.
+    fn collapse_identical(
+        &mut self,
+        notification: &Notification,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let Some(identical) = self
+            .notifications
+            .iter()
+            .find(|note| {
+                let note = note.read(cx);
+                !note.closing && note.is_identical(notification)
+            })
+            .cloned()
+        else {
+            return false;
+        };
+
+        identical.update(cx, |note, cx| {
+            note.count += notification.count;
+            cx.notify();
+        });
+        if identical.read(cx).autohide {
+            Self::autohide(&identical, window, cx);
+        }
+        true
+    }
+
+    /// Show the `notification`, the previous one with the same id is replaced.
+    ///
+    /// The sound is played if `play_sound` and the notification is not silent.
+    fn show(
+        &mut self,
+        notification: Notification,
+        play_sound: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
         let id = notification.id.clone();
         let autohide = notification.autohide;
+        if play_sound && !notification.silent {
+            SoundFeedback::play(notification.type_.unwrap_or_default().sound(), cx);
+        }
 
         // Remove the notification by id, for keep unique.
         self.notifications.retain(|note| note.read(cx).id != id);
@@ -394,21 +636,26 @@ impl NotificationList {
 
         self.notifications.push_back(notification.clone());
         if autohide {
-            // Sleep for 5 seconds to autohide the notification
-            cx.spawn_in(window, async move |_, cx| {
-                Timer::after(Duration::from_secs(5)).await;
-
-                if let Err(err) =
-                    notification.update_in(cx, |note, window, cx| note.dismiss(window, cx))
-                {
-                    tracing::error!("failed to auto hide notification: {:?}", err);
-                }
-            })
-            .detach();
+            Self::autohide(&notification, window, cx);
         }
         cx.notify();
     }
 
+    /// Dismiss the notification after 5 seconds, the previous timer is canceled.
+    fn autohide(notification: &Entity<Notification>, window: &mut Window, cx: &mut Context<Self>) {
+        let weak_notification = notification.downgrade();
+        let task = cx.spawn_in(window, async move |_, cx| {
+            Timer::after(Duration::from_secs(5)).await;
+            _ = weak_notification.update_in(cx, |note, window, cx| note.dismiss(window, cx));
+        });
+        notification.update(cx, |note, _| note._autohide_task = task);
+    }
+
+    /// Returns the number of the notifications held by the [`NotificationSettings::do_not_disturb`].
+    pub fn held_count(&self) -> usize {
+        self.held.len()
+    }
+
     pub(crate) fn close(
         &mut self,
         id: impl Into<NotificationId>,
@@ -424,6 +671,7 @@ impl NotificationList {
 
     pub fn clear(&mut self, _: &mut Window, cx: &mut Context<Self>) {
         self.notifications.clear();
+        self.held.clear();
         cx.notify();
     }
 
@@ -454,3 +702,150 @@ impl Render for NotificationList {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc, time::Duration};
+
+    use gpui::{App, SharedString};
+
+    use super::{Notification, NotificationList, NotificationSettings};
+    use crate::sound::{Sound, SoundBackend, SoundFeedback, SoundSource};
+
+    struct Saved;
+
+    struct TestBackend(Rc<Cell<usize>>);
+
+    impl SoundBackend for TestBackend {
+        fn play(&self, _: &SoundSource, _: f32, _: &mut App) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[gpui::test]
+    fn test_notification_list(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let (list, cx) = cx.add_window_view(NotificationList::new);
+
+        list.update_in(cx, |list, window, cx| {
+            // The identical notifications are collapsed.
+            list.push(Notification::info("Saved"), window, cx);
+            list.push(Notification::info("Saved"), window, cx);
+            list.push(Notification::warning("Saved"), window, cx);
+            assert_eq!(list.notifications.len(), 2);
+            assert_eq!(list.notifications[0].read(cx).count(), 2);
+
+            // Not collapsed with the different ids.
+            list.push(Notification::info("Saved").id1::<Saved>(1), window, cx);
+            list.push(Notification::info("Saved").id1::<Saved>(2), window, cx);
+            list.push(Notification::info("Saved").id1::<Saved>(2), window, cx);
+            assert_eq!(list.notifications.len(), 4);
+            assert_eq!(list.notifications[3].read(cx).count(), 2);
+            list.notifications.truncate(2);
+
+            // Rate limited by the source.
+            for ix in 0..5 {
+                let note = Notification::info(format!("Synced {}", ix)).source("sync");
+                list.push(note, window, cx);
+            }
+            assert_eq!(list.notifications.len(), 5);
+        });
+
+        cx.update(|_, cx| {
+            NotificationSettings {
+                do_not_disturb: true,
+                ..NotificationSettings::global(cx)
+            }
+            .set_global(cx)
+        });
+        list.update_in(cx, |list, window, cx| {
+            list.push(Notification::info("Held"), window, cx);
+            list.push(Notification::info("Held"), window, cx);
+            list.push(Notification::error("Failed"), window, cx);
+            assert_eq!(list.held_count(), 1);
+            assert_eq!(list.notifications.len(), 6);
+
+            // Rate limited while held.
+            for ix in 0..5 {
+                let note = Notification::info(format!("Indexed {}", ix)).source("index");
+                list.push(note, window, cx);
+            }
+            assert_eq!(list.held_count(), 4);
+        });
+
+        cx.update(|_, cx| {
+            NotificationSettings {
+                do_not_disturb: false,
+                ..NotificationSettings::global(cx)
+            }
+            .set_global(cx)
+        });
+        cx.run_until_parked();
+        list.update(cx, |list, cx| {
+            assert_eq!(list.held_count(), 0);
+            assert_eq!(list.notifications.len(), 10);
+            assert_eq!(list.notifications[6].read(cx).count(), 2);
+        });
+    }
+
+    #[gpui::test]
+    fn test_max_held(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let (list, cx) = cx.add_window_view(NotificationList::new);
+        cx.update(|_, cx| {
+            NotificationSettings {
+                do_not_disturb: true,
+                max_held: 2,
+                ..NotificationSettings::global(cx)
+            }
+            .set_global(cx)
+        });
+
+        list.update_in(cx, |list, window, cx| {
+            for ix in 0..5 {
+                list.push(Notification::info(format!("Held {}", ix)), window, cx);
+            }
+            assert_eq!(list.held_count(), 2);
+            assert_eq!(list.held[0].message, Some("Held 3".into()));
+        });
+
+        // The sound is played once for the held notifications.
+        let played = Rc::new(Cell::new(0));
+        cx.update(|_, cx| {
+            SoundFeedback::global_mut(cx)
+                .set_backend(TestBackend(played.clone()))
+                .register(Sound::Toast, b"toast".as_slice());
+            NotificationSettings {
+                do_not_disturb: false,
+                ..NotificationSettings::global(cx)
+            }
+            .set_global(cx)
+        });
+        cx.run_until_parked();
+        list.update(cx, |list, _| assert_eq!(list.notifications.len(), 2));
+        assert_eq!(played.get(), 1);
+    }
+
+    #[gpui::test]
+    fn test_source_times(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let (list, cx) = cx.add_window_view(NotificationList::new);
+        cx.update(|_, cx| {
+            NotificationSettings {
+                rate_limit_window: Duration::ZERO,
+                ..NotificationSettings::global(cx)
+            }
+            .set_global(cx)
+        });
+
+        list.update_in(cx, |list, window, cx| {
+            list.push(Notification::info("Synced").source("sync"), window, cx);
+            list.push(Notification::info("Indexed").source("index"), window, cx);
+            // The expired source is removed.
+            assert_eq!(
+                list.source_times.keys().collect::<Vec<_>>(),
+                vec![&SharedString::from("index")]
+            );
+        });
+    }
+}
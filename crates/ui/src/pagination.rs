@@ -0,0 +1,233 @@
+use std::rc::Rc;
+
+use gpui::{
+    prelude::FluentBuilder as _, App, ElementId, InteractiveElement as _, IntoElement,
+    ParentElement, RenderOnce, StyleRefinement, Styled, Window,
+};
+
+use crate::{
+    button::{Button, ButtonVariants as _},
+    h_flex, ActiveTheme as _, Disableable, Icon, IconName, Selectable as _, Sizable, Size,
+    StyledExt as _,
+};
+
+/// A pagination control to navigate the pages, with the previous and the next buttons.
+///
+/// The pages are 0-based, the first and the last pages are always shown, and the pages far
+/// from the current page are collapsed into the ellipsis.
+///
+/// ```ignore
+/// Pagination::new("pagination")
+///     .page(self.page)
+///     .total_pages(20)
+///     .on_click(cx.listener(|this, page, _, cx| {
+///         this.page = *page;
+///         cx.notify();
+///     }))
+/// ```
+#[derive(IntoElement)]
+pub struct Pagination {
+    id: ElementId,
+    style: StyleRefinement,
+    page: usize,
+    total_pages: usize,
+    siblings: usize,
+    size: Size,
+    disabled: bool,
+    on_click: Option<Rc<dyn Fn(&usize, &mut Window, &mut App) + 'static>>,
+}
+
+impl Pagination {
+    /// Create a new Pagination with the given id.
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            style: StyleRefinement::default(),
+            page: 0,
+            total_pages: 1,
+            siblings: 1,
+            size: Size::default(),
+            disabled: false,
+            on_click: None,
+        }
+    }
+
+    /// Set the current page, 0-based.
+    pub fn page(mut self, page: usize) -> Self {
+        self.page = page;
+        self
+    }
+
+    /// Set the number of the pages, default: 1
+    pub fn total_pages(mut self, total_pages: usize) -> Self {
+        self.total_pages = total_pages;
+        self
+    }
+
+    /// Set the number of the pages shown on each side of the current page, default: 1
+    pub fn siblings(mut self, siblings: usize) -> Self {
+        self.siblings = siblings;
+        self
+    }
+
+    /// Set the handler when a page is clicked, the argument is the 0-based page.
+    pub fn on_click(mut self, handler: impl Fn(&usize, &mut Window, &mut App) + 'static) -> Self {
+        self.on_click = Some(Rc::new(handler));
+        self
+    }
+}
+
+impl Disableable for Pagination {
+    fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+impl Sizable for Pagination {
+    fn with_size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+}
+
+impl Styled for Pagination {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+/// Returns the pages to show, `None` is the ellipsis.
+///
+/// The number of the items is constant when the pages are collapsed, so the buttons do not
+/// jump around when navigating.
+fn page_items(page: usize, total_pages: usize, siblings: usize) -> Vec<Option<usize>> {
+    // The first, the last, the current, the siblings and the two ellipses.
+    let max_items = siblings * 2 + 5;
+    if total_pages <= max_items {
+        return (0..total_pages).map(Some).collect();
+    }
+
+    let last = total_pages - 1;
+    let page = page.min(last);
+    let left = page.saturating_sub(siblings).max(1);
+    let right = (page + siblings).min(last - 1);
+    let show_left_ellipsis = left > 2;
+    let show_right_ellipsis = right + 2 < last;
+
+    match (show_left_ellipsis, show_right_ellipsis) {
+        (false, _) => (0..max_items - 2)
+            .map(Some)
+            .chain([None, Some(last)])
+            .collect(),
+        (true, false) => [Some(0), None]
+            .into_iter()
+            .chain((total_pages - (max_items - 2)..total_pages).map(Some))
+            .collect(),
+        (true, true) => [Some(0), None]
+            .into_iter()
+            .chain((left..=right).map(Some))
+            .chain([None, Some(last)])
+            .collect(),
+    }
+}
+
+impl RenderOnce for Pagination {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let total_pages = self.total_pages.max(1);
+        let page = self.page.min(total_pages - 1);
+        let on_click = self.on_click;
+        let size = self.size;
+        let disabled = self.disabled;
+
+        let page_button = |id: ElementId, to_page: usize| {
+            Button::new(id)
+                .ghost()
+                .with_size(size)
+                .disabled(disabled)
+                .when_some(on_click.clone(), |this, on_click| {
+                    this.on_click(move |_, window, cx| on_click(&to_page, window, cx))
+                })
+        };
+
+        h_flex()
+            .id(self.id)
+            .gap_1()
+            .refine_style(&self.style)
+            .child(
+                page_button("prev".into(), page.saturating_sub(1))
+                    .icon(IconName::ChevronLeft)
+                    .disabled(disabled || page == 0),
+            )
+            .children(
+                page_items(page, total_pages, self.siblings)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(ix, item)| match item {
+                        Some(item) => page_button(("page", item).into(), item)
+                            .label((item + 1).to_string())
+                            .selected(item == page)
+                            .into_any_element(),
+                        None => h_flex()
+                            .id(("ellipsis", ix))
+                            .px_1()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(Icon::new(IconName::Ellipsis).with_size(size))
+                            .into_any_element(),
+                    }),
+            )
+            .child(
+                page_button("next".into(), (page + 1).min(total_pages - 1))
+                    .icon(IconName::ChevronRight)
+                    .disabled(disabled || page + 1 == total_pages),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::page_items;
+
+    #[test]
+    fn test_page_items() {
+        assert_eq!(page_items(0, 0, 1), vec![]);
+        assert_eq!(
+            page_items(2, 5, 1),
+            vec![Some(0), Some(1), Some(2), Some(3), Some(4)]
+        );
+        assert_eq!(
+            page_items(0, 20, 1),
+            vec![Some(0), Some(1), Some(2), Some(3), Some(4), None, Some(19)]
+        );
+        assert_eq!(
+            page_items(10, 20, 1),
+            vec![Some(0), None, Some(9), Some(10), Some(11), None, Some(19)]
+        );
+        assert_eq!(
+            page_items(19, 20, 1),
+            vec![
+                Some(0),
+                None,
+                Some(15),
+                Some(16),
+                Some(17),
+                Some(18),
+                Some(19)
+            ]
+        );
+        assert_eq!(
+            page_items(10, 20, 2),
+            vec![
+                Some(0),
+                None,
+                Some(8),
+                Some(9),
+                Some(10),
+                Some(11),
+                Some(12),
+                None,
+                Some(19)
+            ]
+        );
+    }
+}
@@ -0,0 +1,332 @@
+use std::{
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use gpui::{
+    actions, div, px, AnyElement, App, AppContext as _, Context, ElementId, Entity, EventEmitter,
+    InteractiveElement as _, IntoElement, KeyBinding, ParentElement, Pixels, Render, RenderOnce,
+    SharedString, StyleRefinement, Styled, Subscription, Window,
+};
+
+use crate::{
+    breadcrumb::{Breadcrumb, BreadcrumbItem},
+    file_tree::{FileTree, FileTreeEvent, FileTreeState},
+    h_flex, v_flex, ActiveTheme, StyledExt as _,
+};
+
+actions!(path_navigator, [GoUp, Descend]);
+
+const CONTEXT: &str = "PathNavigator";
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("backspace", GoUp, Some(CONTEXT)),
+        KeyBinding::new("enter", Descend, Some(CONTEXT)),
+    ]);
+}
+
+/// Events emitted by the [`PathNavigatorState`].
+#[derive(Debug, Clone)]
+pub enum PathNavigatorEvent {
+    /// The current path was changed.
+    Change(PathBuf),
+}
+
+/// Returns the breadcrumb segments from the `root` to the `path`, each with the label and the path.
+fn segments(root: &Path, path: &Path) -> Vec<(SharedString, PathBuf)> {
+    let root_label = root
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| root.display().to_string());
+
+    let mut segments = vec![(root_label.into(), root.to_path_buf())];
+    if let Ok(relative) = path.strip_prefix(root) {
+        let mut current = root.to_path_buf();
+        for component in relative.components() {
+            current.push(component);
+            segments.push((
+                component.as_os_str().to_string_lossy().to_string().into(),
+                current.clone(),
+            ));
+        }
+    }
+    segments
+}
+
+/// A state to keep a [`Breadcrumb`], a [`FileTree`] selection and a content pane in sync,
+/// from a single current path.
+pub struct PathNavigatorState {
+    root: PathBuf,
+    path: PathBuf,
+    file_tree: Entity<FileTreeState>,
+    render_content: Option<Rc<dyn Fn(&Path, &mut Window, &mut App) -> AnyElement>>,
+    tree_width: Pixels,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl PathNavigatorState {
+    /// Create a new path navigator of the `root` directory, the current path is the `root`.
+    pub fn new(root: impl Into<PathBuf>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let root = root.into();
+        let file_tree = cx.new(|cx| FileTreeState::new(root.clone(), window, cx));
+        let _subscriptions = vec![cx.subscribe(&file_tree, Self::on_file_tree_event)];
+
+        Self {
+            path: root.clone(),
+            root,
+            file_tree,
+            render_content: None,
+            tree_width: px(240.),
+            _subscriptions,
+        }
+    }
+
+    /// Returns the root directory.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns the current path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the state of the [`FileTree`].
+    pub fn file_tree(&self) -> &Entity<FileTreeState> {
+        &self.file_tree
+    }
+
+    /// Set the current path, the paths outside the root directory are ignored.
+    pub fn set_path(&mut self, path: impl Into<PathBuf>, cx: &mut Context<Self>) {
+        let path = path.into();
+        if !path.starts_with(&self.root) || path == self.path {
+            return;
+        }
+
+        self.path = path.clone();
+        self.file_tree
+            .update(cx, |file_tree, cx| file_tree.reveal(&path, cx));
+        cx.emit(PathNavigatorEvent::Change(path));
+        cx.notify();
+    }
+
+    /// Go to the parent directory of the current path, until the root directory.
+    pub fn go_up(&mut self, cx: &mut Context<Self>) {
+        if self.path == self.root {
+            return;
+        }
+
+        if let Some(parent) = self.path.parent().map(Path::to_path_buf) {
+            self.set_path(parent, cx);
+        }
+    }
+
+    /// Go to the first item of the current path, if it is a directory.
+    pub fn descend(&mut self, cx: &mut Context<Self>) {
+        if !self.path.is_dir() {
+            return;
+        }
+
+        let path = self.path.clone();
+        if let Some(child) = self
+            .file_tree
+            .update(cx, |file_tree, _| file_tree.first_child(&path))
+        {
+            self.set_path(child, cx);
+        }
+    }
+
+    fn on_file_tree_event(
+        &mut self,
+        _: Entity<FileTreeState>,
+        event: &FileTreeEvent,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            FileTreeEvent::Select(path) => self.set_path(path.clone(), cx),
+            FileTreeEvent::Renamed { from, to } => {
+                if let Ok(rest) = self.path.strip_prefix(from) {
+                    let path = to.join(rest);
+                    self.set_path(path, cx);
+                }
+            }
+            FileTreeEvent::Deleted(path) => {
+                if self.path.starts_with(path) {
+                    let parent = path.parent().unwrap_or(&self.root).to_path_buf();
+                    self.set_path(parent, cx);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn on_action_go_up(&mut self, _: &GoUp, _: &mut Window, cx: &mut Context<Self>) {
+        self.go_up(cx);
+    }
+
+    fn on_action_descend(&mut self, _: &Descend, _: &mut Window, cx: &mut Context<Self>) {
+        self.descend(cx);
+    }
+}
+
+impl EventEmitter<PathNavigatorEvent> for PathNavigatorState {}
+
+impl Render for PathNavigatorState {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let state = cx.entity();
+        let breadcrumb =
+            Breadcrumb::new().children(segments(&self.root, &self.path).into_iter().map(
+                |(label, path)| {
+                    let state = state.clone();
+                    BreadcrumbItem::new(label).on_click(move |_, _, cx| {
+                        state.update(cx, |state, cx| state.set_path(path.clone(), cx));
+                    })
+                },
+            ));
+        let content = self
+            .render_content
+            .clone()
+            .map(|render_content| render_content(&self.path, window, cx));
+
+        v_flex()
+            .size_full()
+            .child(
+                h_flex()
+                    .px_3()
+                    .py_2()
+                    .border_b_1()
+                    .border_color(cx.theme().border)
+                    .child(breadcrumb),
+            )
+            .child(
+                h_flex()
+                    .flex_1()
+                    .w_full()
+                    .overflow_hidden()
+                    .child(
+                        div()
+                            .h_full()
+                            .flex_shrink_0()
+                            .w(self.tree_width)
+                            .border_r_1()
+                            .border_color(cx.theme().border)
+                            .child(FileTree::new(&self.file_tree)),
+                    )
+                    .child(div().flex_1().h_full().overflow_hidden().children(content)),
+            )
+    }
+}
+
+/// A file browser layout with a [`Breadcrumb`] on the top, a [`FileTree`] on the left,
+/// and a content pane of the current path on the right.
+///
+/// Press `Backspace` to go to the parent directory, `Enter` to go into the directory.
+///
+/// ```ignore
+/// let state = cx.new(|cx| PathNavigatorState::new("./", window, cx));
+///
+/// PathNavigator::new(&state).content(|path, _, _| {
+///     div().child(format!("{}", path.display()))
+/// })
+/// ```
+#[derive(IntoElement)]
+pub struct PathNavigator {
+    id: ElementId,
+    state: Entity<PathNavigatorState>,
+    style: StyleRefinement,
+    render_content: Option<Rc<dyn Fn(&Path, &mut Window, &mut App) -> AnyElement>>,
+    tree_width: Option<Pixels>,
+}
+
+impl PathNavigator {
+    /// Create a new path navigator with the given state.
+    pub fn new(state: &Entity<PathNavigatorState>) -> Self {
+        Self {
+            id: ElementId::Name(format!("path-navigator-{}", state.entity_id()).into()),
+            state: state.clone(),
+            style: StyleRefinement::default(),
+            render_content: None,
+            tree_width: None,
+        }
+    }
+
+    /// Set the content pane to render for the current path.
+    pub fn content<F, E>(mut self, f: F) -> Self
+    where
+        F: Fn(&Path, &mut Window, &mut App) -> E + 'static,
+        E: IntoElement,
+    {
+        self.render_content = Some(Rc::new(move |path, window, cx| {
+            f(path, window, cx).into_any_element()
+        }));
+        self
+    }
+
+    /// Set the width of the file tree, default: 240px
+    pub fn tree_width(mut self, width: impl Into<Pixels>) -> Self {
+        self.tree_width = Some(width.into());
+        self
+    }
+}
+
+impl Styled for PathNavigator {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for PathNavigator {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        self.state.update(cx, |state, _| {
+            state.render_content = self.render_content;
+            if let Some(tree_width) = self.tree_width {
+                state.tree_width = tree_width;
+            }
+        });
+
+        div()
+            .id(self.id)
+            .key_context(CONTEXT)
+            .on_action(window.listener_for(&self.state, PathNavigatorState::on_action_go_up))
+            .on_action(window.listener_for(&self.state, PathNavigatorState::on_action_descend))
+            .size_full()
+            .child(self.state)
+            .refine_style(&self.style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::segments;
+
+    #[test]
+    fn test_segments() {
+        let root = Path::new("/home/user/project");
+        let segments = |path: &str| {
+            segments(root, Path::new(path))
+                .into_iter()
+                .map(|(label, path)| (label.to_string(), path))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            segments("/home/user/project"),
+            vec![("project".to_string(), PathBuf::from("/home/user/project"))]
+        );
+        assert_eq!(
+            segments("/home/user/project/src/lib.rs"),
+            vec![
+                ("project".to_string(), PathBuf::from("/home/user/project")),
+                ("src".to_string(), PathBuf::from("/home/user/project/src")),
+                (
+                    "lib.rs".to_string(),
+                    PathBuf::from("/home/user/project/src/lib.rs")
+                ),
+            ]
+        );
+        assert_eq!(segments("/etc").len(), 1);
+        assert_eq!(super::segments(Path::new("/"), Path::new("/etc"))[0].0, "/");
+    }
+}
@@ -1,9 +1,8 @@
 use gpui::{
-    point, px, App, Bounds, FontWeight, Hsla, PathBuilder, Pixels, Point, SharedString, TextAlign,
-    Window,
+    point, px, App, Bounds, FontWeight, Hsla, Pixels, Point, SharedString, TextAlign, Window,
 };
 
-use super::{label::Label, label::Text, label::TEXT_GAP, label::TEXT_SIZE, origin_point};
+use super::{label::Label, label::Text, label::TEXT_GAP, label::TEXT_SIZE, origin_point, PlotPath};
 
 pub const AXIS_GAP: f32 = 18.;
 
@@ -123,12 +122,10 @@ impl Axis {
     }
 
     fn draw_axis(&self, start_point: Point<Pixels>, end_point: Point<Pixels>, window: &mut Window) {
-        let mut builder = PathBuilder::stroke(px(1.));
+        let mut builder = PlotPath::stroke(px(1.));
         builder.move_to(start_point);
         builder.line_to(end_point);
-        if let Ok(path) = builder.build() {
-            window.paint_path(path, self.stroke);
-        }
+        builder.paint(self.stroke, window);
     }
 
     /// Paint the Axis.
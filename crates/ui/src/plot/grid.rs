@@ -1,6 +1,6 @@
-use gpui::{px, Bounds, Hsla, PathBuilder, Pixels, Point, Window};
+use gpui::{px, Bounds, Hsla, Pixels, Point, Window};
 
-use super::origin_point;
+use super::{origin_point, PlotPath};
 
 pub struct Grid {
     x: Vec<Pixels>,
@@ -79,7 +79,7 @@ impl Grid {
         let points = self.points(bounds);
 
         for (start, end) in points {
-            let mut builder = PathBuilder::stroke(px(1.));
+            let mut builder = PlotPath::stroke(px(1.));
 
             if let Some(dash_array) = &self.dash_array {
                 builder = builder.dash_array(&dash_array);
@@ -87,9 +87,7 @@ impl Grid {
 
             builder.move_to(start);
             builder.line_to(end);
-            if let Ok(line) = builder.build() {
-                window.paint_path(line, self.stroke);
-            }
+            builder.paint(self.stroke, window);
         }
     }
 }
@@ -6,6 +6,7 @@ use gpui::{
 };
 
 use super::origin_point;
+use crate::export;
 
 pub const TEXT_SIZE: f32 = 10.;
 pub const TEXT_GAP: f32 = 2.;
@@ -85,6 +86,12 @@ impl Label {
         } in self.0.iter()
         {
             let origin = origin_point(origin.x, origin.y, bounds.origin);
+            let recorded = export::recording(|document| {
+                document.text(text, origin, *font_size, *font_weight, *color, *align)
+            });
+            if recorded {
+                continue;
+            }
 
             let text_run = TextRun {
                 len: text.len(),
@@ -1,6 +1,7 @@
 mod axis;
 mod grid;
 pub mod label;
+mod path;
 pub mod scale;
 pub mod shape;
 pub mod tooltip;
@@ -9,18 +10,75 @@ pub use gpui_component_macros::IntoPlot;
 
 use std::{fmt::Debug, ops::Add};
 
-use gpui::{point, px, App, Bounds, IntoElement, Path, PathBuilder, Pixels, Point, Window};
+use gpui::{
+    point, px, App, Bounds, Hsla, IntoElement, Path, PathBuilder, Pixels, Point, Size, Window,
+};
 
-use crate::PixelsExt;
+use crate::{
+    export::{self, SvgDocument},
+    ActiveTheme as _, PixelsExt,
+};
 
 pub use axis::{Axis, AxisText, AXIS_GAP};
 pub use grid::Grid;
 pub use label::Label;
+pub(crate) use path::PlotPath;
 
 pub trait Plot: IntoElement {
     fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App);
 }
 
+/// Export the [`Plot`] as SVG or PNG in the light theme, e.g. to include the charts in the reports.
+///
+/// The plot is painted in the `size` without the hover and the tooltip, the shapes and the
+/// texts are kept as vectors in the SVG.
+///
+/// ```ignore
+/// let svg = BarChart::new(data)
+///     .x(|d| d.month.clone())
+///     .y(|d| d.revenue)
+///     .export_svg(size(px(600.), px(300.)), window, cx);
+/// ```
+pub trait PlotExport: Plot + Sized {
+    /// Export the plot as SVG in the `size`.
+    fn export_svg(self, size: Size<Pixels>, window: &mut Window, cx: &mut App) -> String {
+        export_document(self, size, window, cx).to_svg()
+    }
+
+    /// Export the plot as PNG in the `size` multiplied by the `scale`.
+    fn export_png(
+        self,
+        size: Size<Pixels>,
+        scale: f32,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> anyhow::Result<Vec<u8>> {
+        export_document(self, size, window, cx).to_png(scale)
+    }
+}
+
+impl<T: Plot> PlotExport for T {}
+
+fn export_document(
+    mut plot: impl Plot,
+    size: Size<Pixels>,
+    window: &mut Window,
+    cx: &mut App,
+) -> SvgDocument {
+    let bounds = Bounds::new(Point::default(), size);
+    export::with_light_theme(cx, |cx| {
+        let mut document = SvgDocument::new(size).font_family(cx.theme().font_family.clone());
+        document.rect(
+            bounds,
+            px(0.),
+            cx.theme().background,
+            px(0.),
+            Hsla::transparent_black(),
+        );
+        export::record(document, || plot.paint(bounds, window, cx))
+    })
+}
+
 #[derive(Clone, Copy, Default)]
 pub enum StrokeStyle {
     #[default]
@@ -29,6 +87,15 @@ pub enum StrokeStyle {
     StepAfter,
 }
 
+/// The shape of the markers at the data points of a line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Marker {
+    #[default]
+    Circle,
+    Square,
+    Diamond,
+}
+
 pub fn origin_point<T>(x: T, y: T, origin: Point<T>) -> Point<T>
 where
     T: Default + Clone + Debug + PartialEq + Add<Output = T>,
@@ -53,3 +120,26 @@ where
     path.add_polygon(points, false);
     path.build().ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use gpui::{px, size};
+
+    use super::PlotExport as _;
+    use crate::chart::BarChart;
+
+    #[gpui::test]
+    fn test_export(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let cx = cx.add_empty_window();
+        cx.update(|window, cx| {
+            let chart = BarChart::new(vec![("Jan", 10.), ("Feb", 20.)])
+                .x(|d| d.0)
+                .y(|d| d.1);
+            let svg = chart.export_svg(size(px(300.), px(200.)), window, cx);
+            assert!(svg.starts_with("<svg"));
+            assert_eq!(svg.matches("<rect").count(), 3);
+            assert!(svg.contains(">Feb</text>"));
+        });
+    }
+}
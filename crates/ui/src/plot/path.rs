@@ -0,0 +1,118 @@
+use std::fmt::Write as _;
+
+use gpui::{Background, Path, PathBuilder, Pixels, Point, Window};
+
+use crate::export;
+
+/// The [`PathBuilder`] that also keeps the SVG path data, to paint the path into the window,
+/// or into the [`SvgDocument`](crate::export::SvgDocument) in the exporting.
+pub(crate) struct PlotPath {
+    builder: PathBuilder,
+    data: String,
+    /// The width and the dash array of the stroke, `None` to fill the path.
+    stroke: Option<(Pixels, Option<Vec<Pixels>>)>,
+}
+
+impl PlotPath {
+    pub fn fill() -> Self {
+        Self {
+            builder: PathBuilder::fill(),
+            data: String::new(),
+            stroke: None,
+        }
+    }
+
+    pub fn stroke(width: Pixels) -> Self {
+        Self {
+            builder: PathBuilder::stroke(width),
+            data: String::new(),
+            stroke: Some((width, None)),
+        }
+    }
+
+    pub fn dash_array(mut self, dash_array: &[Pixels]) -> Self {
+        self.builder = self.builder.dash_array(dash_array);
+        if let Some((_, dash)) = self.stroke.as_mut() {
+            *dash = Some(dash_array.to_vec());
+        }
+        self
+    }
+
+    pub fn move_to(&mut self, to: Point<Pixels>) {
+        self.builder.move_to(to);
+        let _ = write!(self.data, "M{} {}", f32::from(to.x), f32::from(to.y));
+    }
+
+    pub fn line_to(&mut self, to: Point<Pixels>) {
+        self.builder.line_to(to);
+        let _ = write!(self.data, "L{} {}", f32::from(to.x), f32::from(to.y));
+    }
+
+    pub fn cubic_bezier_to(
+        &mut self,
+        to: Point<Pixels>,
+        control_a: Point<Pixels>,
+        control_b: Point<Pixels>,
+    ) {
+        self.builder.cubic_bezier_to(to, control_a, control_b);
+        let _ = write!(
+            self.data,
+            "C{} {} {} {} {} {}",
+            f32::from(control_a.x),
+            f32::from(control_a.y),
+            f32::from(control_b.x),
+            f32::from(control_b.y),
+            f32::from(to.x),
+            f32::from(to.y)
+        );
+    }
+
+    pub fn arc_to(
+        &mut self,
+        radii: Point<Pixels>,
+        x_rotation: Pixels,
+        large_arc: bool,
+        sweep: bool,
+        to: Point<Pixels>,
+    ) {
+        self.builder.arc_to(radii, x_rotation, large_arc, sweep, to);
+        let _ = write!(
+            self.data,
+            "A{} {} {} {} {} {} {}",
+            f32::from(radii.x),
+            f32::from(radii.y),
+            f32::from(x_rotation),
+            large_arc as u8,
+            sweep as u8,
+            f32::from(to.x),
+            f32::from(to.y)
+        );
+    }
+
+    pub fn close(&mut self) {
+        self.builder.close();
+        self.data.push('Z');
+    }
+
+    pub fn build(self) -> Option<Path<Pixels>> {
+        self.builder.build().ok()
+    }
+
+    /// Paint the path in the `color`, into the recording document if exporting.
+    pub fn paint(self, color: impl Into<Background>, window: &mut Window) {
+        let color = color.into();
+        let painted = export::recording(|document| match &self.stroke {
+            Some((width, dash_array)) => {
+                document.stroke_path(&self.data, color, *width, dash_array.as_deref())
+            }
+            None => document.fill_path(&self.data, color),
+        });
+        if painted {
+            return;
+        }
+
+        if let Some(path) = self.build() {
+            window.paint_path(path, color);
+        }
+    }
+}
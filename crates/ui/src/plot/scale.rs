@@ -1,12 +1,16 @@
 mod band;
 mod linear;
+mod log;
 mod point;
 mod sealed;
+mod time;
 
 pub use band::ScaleBand;
 pub use linear::ScaleLinear;
+pub use log::ScaleLog;
 pub use point::ScalePoint;
 pub(crate) use sealed::Sealed;
+pub use time::{ScaleTime, TimeInterval};
 
 pub trait Scale<T> {
     /// Get the tick of the scale.
@@ -49,6 +49,16 @@ where
             range_diff: range_end - range_start,
         }
     }
+
+    /// Returns the domain value of the tick, the inverse of [`Scale::tick`].
+    pub fn invert(&self, tick: f32) -> Option<f64> {
+        if self.range_diff == 0. {
+            return None;
+        }
+
+        let ratio = ((tick - self.range_start) / self.range_diff) as f64;
+        Some(self.domain_start.to_f64()? + self.domain_diff.to_f64()? * ratio)
+    }
 }
 
 impl<T> Scale<T> for ScaleLinear<T>
@@ -137,6 +147,17 @@ mod tests {
         assert_eq!(scale.tick(&3.), Some(0.));
     }
 
+    #[test]
+    fn test_scale_linear_invert() {
+        let scale = ScaleLinear::new(vec![0., 50., 100.], vec![200., 0.]);
+        assert_eq!(scale.invert(200.), Some(0.));
+        assert_eq!(scale.invert(100.), Some(50.));
+        assert_eq!(scale.invert(0.), Some(100.));
+
+        let scale = ScaleLinear::new(vec![1., 2.], vec![]);
+        assert_eq!(scale.invert(0.), None);
+    }
+
     #[test]
     fn test_scale_linear_least_index_with_domain() {
         let scale = ScaleLinear::new(vec![1., 2., 3.], vec![0., 100.]);
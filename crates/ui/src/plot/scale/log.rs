@@ -0,0 +1,121 @@
+// @reference: https://d3js.org/d3-scale/log
+
+use itertools::Itertools;
+
+use super::Scale;
+
+/// A logarithmic scale of the positive values, the non-positive values are ignored.
+#[derive(Clone)]
+pub struct ScaleLog {
+    base: f64,
+    domain_min: f64,
+    domain_max: f64,
+    range_start: f32,
+    range_diff: f32,
+}
+
+impl ScaleLog {
+    pub fn new(domain: Vec<f64>, range: Vec<f32>) -> Self {
+        let (domain_min, domain_max) = domain
+            .into_iter()
+            .filter(|v| v.is_finite() && *v > 0.)
+            .minmax_by(f64::total_cmp)
+            .into_option()
+            .unwrap_or((1., 1.));
+        let range_start = range.first().copied().unwrap_or_default();
+        let range_end = range.last().copied().unwrap_or_default();
+
+        Self {
+            base: 10.,
+            domain_min,
+            domain_max,
+            range_start,
+            range_diff: range_end - range_start,
+        }
+    }
+
+    /// Set the base of the logarithm, default: 10
+    pub fn base(mut self, base: f64) -> Self {
+        if base > 1. {
+            self.base = base;
+        }
+        self
+    }
+
+    /// Extend the domain to the powers of the base, e.g. `[3, 420]` to `[1, 1000]`.
+    pub fn nice(mut self) -> Self {
+        self.domain_min = self.base.powf(self.log(self.domain_min).floor());
+        self.domain_max = self.base.powf(self.log(self.domain_max).ceil());
+        self
+    }
+
+    /// The logarithm snapped to the integer within the float error, e.g. `log10(1000) = 3`.
+    fn log(&self, value: f64) -> f64 {
+        let log = value.ln() / self.base.ln();
+        if (log - log.round()).abs() < 1e-9 {
+            log.round()
+        } else {
+            log
+        }
+    }
+
+    /// Returns the domain value of the tick, the inverse of [`Scale::tick`].
+    pub fn invert(&self, tick: f32) -> Option<f64> {
+        if self.range_diff == 0. {
+            return None;
+        }
+
+        let ratio = ((tick - self.range_start) / self.range_diff) as f64;
+        let (min, max) = (self.log(self.domain_min), self.log(self.domain_max));
+        Some(self.base.powf(min + (max - min) * ratio))
+    }
+
+    /// Returns the powers of the base in the domain.
+    pub fn ticks(&self) -> Vec<f64> {
+        let start = self.log(self.domain_min).ceil() as i32;
+        let end = self.log(self.domain_max).floor() as i32;
+        (start..=end).map(|exp| self.base.powi(exp)).collect()
+    }
+}
+
+impl Scale<f64> for ScaleLog {
+    fn tick(&self, value: &f64) -> Option<f32> {
+        if *value <= 0. || !value.is_finite() || self.domain_min == self.domain_max {
+            return None;
+        }
+
+        let (min, max) = (self.log(self.domain_min), self.log(self.domain_max));
+        let ratio = ((self.log(*value) - min) / (max - min)) as f32;
+        Some(ratio * self.range_diff + self.range_start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scale_log() {
+        let scale = ScaleLog::new(vec![1., 10., 100., 1000.], vec![0., 300.]);
+        assert_eq!(scale.tick(&1.), Some(0.));
+        assert_eq!(scale.tick(&100.), Some(200.));
+        assert_eq!(scale.tick(&1000.), Some(300.));
+        assert_eq!(scale.tick(&0.), None);
+        assert_eq!(scale.tick(&-1.), None);
+        assert_eq!(scale.invert(100.).map(f64::round), Some(10.));
+        assert_eq!(scale.ticks(), vec![1., 10., 100., 1000.]);
+
+        // The non-positive values are ignored in the domain.
+        let scale = ScaleLog::new(vec![0., 3., 420.], vec![300., 0.]).nice();
+        assert_eq!(scale.ticks(), vec![1., 10., 100., 1000.]);
+        assert_eq!(scale.tick(&1.), Some(300.));
+        assert_eq!(scale.tick(&1000.), Some(0.));
+
+        let scale = ScaleLog::new(vec![1., 8.], vec![0., 300.]).base(2.);
+        assert_eq!(scale.ticks(), vec![1., 2., 4., 8.]);
+        assert_eq!(scale.tick(&2.), Some(100.));
+
+        let scale = ScaleLog::new(vec![], vec![0., 300.]);
+        assert_eq!(scale.tick(&1.), None);
+    }
+}
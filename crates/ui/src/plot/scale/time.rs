@@ -0,0 +1,247 @@
+// @reference: https://d3js.org/d3-scale/time
+
+use chrono::{DateTime, Datelike as _, Duration, Months, NaiveDate, NaiveDateTime};
+
+use super::Scale;
+
+/// The interval between the ticks of the [`ScaleTime`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeInterval {
+    Second(u32),
+    Minute(u32),
+    Hour(u32),
+    Day(u32),
+    Week,
+    Month(u32),
+    Year(u32),
+}
+
+/// The candidates of the intervals from small to large, the years are extended by the span.
+const INTERVALS: [TimeInterval; 17] = [
+    TimeInterval::Second(1),
+    TimeInterval::Second(5),
+    TimeInterval::Second(15),
+    TimeInterval::Second(30),
+    TimeInterval::Minute(1),
+    TimeInterval::Minute(5),
+    TimeInterval::Minute(15),
+    TimeInterval::Minute(30),
+    TimeInterval::Hour(1),
+    TimeInterval::Hour(3),
+    TimeInterval::Hour(6),
+    TimeInterval::Hour(12),
+    TimeInterval::Day(1),
+    TimeInterval::Day(2),
+    TimeInterval::Week,
+    TimeInterval::Month(1),
+    TimeInterval::Month(3),
+];
+
+impl TimeInterval {
+    /// Returns the approximate seconds of the interval.
+    fn seconds(&self) -> i64 {
+        match *self {
+            Self::Second(n) => n as i64,
+            Self::Minute(n) => n as i64 * 60,
+            Self::Hour(n) => n as i64 * 3_600,
+            Self::Day(n) => n as i64 * 86_400,
+            Self::Week => 7 * 86_400,
+            Self::Month(n) => n as i64 * 30 * 86_400,
+            Self::Year(n) => n as i64 * 365 * 86_400,
+        }
+    }
+
+    /// Returns the default format of the ticks in the interval, in the syntax of [`chrono::format::strftime`].
+    pub fn format(&self) -> &'static str {
+        match self {
+            Self::Second(_) => "%H:%M:%S",
+            Self::Minute(_) | Self::Hour(_) => "%H:%M",
+            Self::Day(_) | Self::Week => "%b %d",
+            Self::Month(_) => "%b %Y",
+            Self::Year(_) => "%Y",
+        }
+    }
+
+    /// Returns the start of the interval that the time is in.
+    fn floor(&self, time: NaiveDateTime) -> NaiveDateTime {
+        let date = time.date();
+        let floor_by = |seconds: i64| {
+            let timestamp = time.and_utc().timestamp();
+            DateTime::from_timestamp(timestamp - timestamp.rem_euclid(seconds), 0)
+                .map_or(time, |time| time.naive_utc())
+        };
+        let midnight = |date: NaiveDate| date.and_hms_opt(0, 0, 0).unwrap_or(time);
+
+        match *self {
+            Self::Second(_) | Self::Minute(_) | Self::Hour(_) => floor_by(self.seconds()),
+            Self::Day(n) => {
+                let days = date.num_days_from_ce() as i64;
+                midnight(date - Duration::days(days.rem_euclid(n as i64)))
+            }
+            Self::Week => {
+                midnight(date - Duration::days(date.weekday().num_days_from_monday() as i64))
+            }
+            Self::Month(n) => NaiveDate::from_ymd_opt(date.year(), date.month0() / n * n + 1, 1)
+                .map_or(time, midnight),
+            Self::Year(n) => {
+                NaiveDate::from_ymd_opt(date.year() - date.year().rem_euclid(n as i32), 1, 1)
+                    .map_or(time, midnight)
+            }
+        }
+    }
+
+    /// Returns the next tick after the time.
+    fn next(&self, time: NaiveDateTime) -> Option<NaiveDateTime> {
+        match *self {
+            Self::Month(n) => time.checked_add_months(Months::new(n)),
+            Self::Year(n) => time.checked_add_months(Months::new(n * 12)),
+            _ => time.checked_add_signed(Duration::seconds(self.seconds())),
+        }
+    }
+}
+
+/// A linear scale of the time, with the ticks at the nice intervals, e.g. every 15 minutes.
+#[derive(Clone)]
+pub struct ScaleTime {
+    domain_start: NaiveDateTime,
+    domain_end: NaiveDateTime,
+    range_start: f32,
+    range_diff: f32,
+}
+
+impl ScaleTime {
+    pub fn new(domain: Vec<NaiveDateTime>, range: Vec<f32>) -> Self {
+        let domain_start = domain.iter().min().copied().unwrap_or_default();
+        let domain_end = domain.iter().max().copied().unwrap_or_default();
+        let range_start = range.first().copied().unwrap_or_default();
+        let range_end = range.last().copied().unwrap_or_default();
+
+        Self {
+            domain_start,
+            domain_end,
+            range_start,
+            range_diff: range_end - range_start,
+        }
+    }
+
+    fn span_millis(&self) -> i64 {
+        (self.domain_end - self.domain_start).num_milliseconds()
+    }
+
+    /// Returns the time of the tick, the inverse of [`Scale::tick`].
+    pub fn invert(&self, tick: f32) -> Option<NaiveDateTime> {
+        if self.range_diff == 0. {
+            return None;
+        }
+
+        let ratio = ((tick - self.range_start) / self.range_diff) as f64;
+        let millis = (self.span_millis() as f64 * ratio).round() as i64;
+        self.domain_start
+            .checked_add_signed(Duration::milliseconds(millis))
+    }
+
+    /// Returns the interval of about `count` ticks in the domain.
+    pub fn interval(&self, count: usize) -> TimeInterval {
+        let step = self.span_millis() / 1_000 / count.max(1) as i64;
+        INTERVALS
+            .into_iter()
+            .find(|interval| interval.seconds() >= step)
+            .unwrap_or_else(|| {
+                let years = (step as f64 / TimeInterval::Year(1).seconds() as f64).ceil();
+                // Round the years to 1, 2, 5, 10, 20, 50...
+                let magnitude = 10_f64.powf(years.log10().floor());
+                let nice = [1., 2., 5., 10.]
+                    .into_iter()
+                    .map(|n| n * magnitude)
+                    .find(|n| *n >= years)
+                    .unwrap_or(years);
+                TimeInterval::Year(nice as u32)
+            })
+    }
+
+    /// Returns about `count` ticks at the nice interval in the domain, and the interval.
+    pub fn ticks(&self, count: usize) -> (TimeInterval, Vec<NaiveDateTime>) {
+        let interval = self.interval(count);
+        let mut ticks = vec![];
+        let mut time = Some(interval.floor(self.domain_start));
+        while let Some(t) = time.filter(|t| *t <= self.domain_end) {
+            if t >= self.domain_start {
+                ticks.push(t);
+            }
+            time = interval.next(t);
+        }
+
+        (interval, ticks)
+    }
+}
+
+impl Scale<NaiveDateTime> for ScaleTime {
+    fn tick(&self, value: &NaiveDateTime) -> Option<f32> {
+        let span = self.span_millis();
+        if span == 0 {
+            return None;
+        }
+
+        let ratio = (*value - self.domain_start).num_milliseconds() as f64 / span as f64;
+        Some(ratio as f32 * self.range_diff + self.range_start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn time(y: i32, m: u32, d: u32, h: u32, min: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, min, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_scale_time() {
+        let scale = ScaleTime::new(
+            vec![time(2024, 1, 1, 0, 0), time(2024, 1, 1, 2, 0)],
+            vec![0., 120.],
+        );
+        assert_eq!(scale.tick(&time(2024, 1, 1, 1, 0)), Some(60.));
+        assert_eq!(scale.invert(30.), Some(time(2024, 1, 1, 0, 30)));
+
+        // 2 hours in about 6 ticks.
+        let (interval, ticks) = scale.ticks(6);
+        assert_eq!(interval, TimeInterval::Minute(30));
+        assert_eq!(interval.format(), "%H:%M");
+        assert_eq!(ticks.len(), 5);
+        assert_eq!(ticks[1], time(2024, 1, 1, 0, 30));
+
+        // The ticks start from the nice time.
+        let scale = ScaleTime::new(
+            vec![time(2024, 1, 1, 0, 7), time(2024, 1, 1, 1, 0)],
+            vec![0., 100.],
+        );
+        let (_, ticks) = scale.ticks(4);
+        assert_eq!(ticks[0], time(2024, 1, 1, 0, 15));
+
+        let scale = ScaleTime::new(
+            vec![time(2024, 2, 10, 0, 0), time(2024, 12, 1, 0, 0)],
+            vec![0., 100.],
+        );
+        let (interval, ticks) = scale.ticks(10);
+        assert_eq!(interval, TimeInterval::Month(1));
+        assert_eq!(ticks[0], time(2024, 3, 1, 0, 0));
+
+        let scale = ScaleTime::new(
+            vec![time(1990, 1, 1, 0, 0), time(2024, 1, 1, 0, 0)],
+            vec![0., 100.],
+        );
+        let (interval, ticks) = scale.ticks(4);
+        assert_eq!(interval, TimeInterval::Year(10));
+        assert_eq!(ticks[0], time(1990, 1, 1, 0, 0));
+        assert_eq!(ticks.len(), 4);
+
+        let scale = ScaleTime::new(vec![], vec![0., 100.]);
+        assert_eq!(scale.tick(&time(2024, 1, 1, 0, 0)), None);
+    }
+}
@@ -2,9 +2,9 @@
 
 use std::{f32::consts::PI, fmt::Debug};
 
-use gpui::{point, px, Bounds, Hsla, Path, PathBuilder, Pixels, Point, Window};
+use gpui::{point, px, Bounds, Hsla, Pixels, Point, Window};
 
-use crate::PixelsExt;
+use crate::{plot::PlotPath, PixelsExt};
 
 const EPSILON: f32 = 1e-12;
 const HALF_PI: f32 = PI / 2.;
@@ -75,7 +75,7 @@ impl Arc {
         inner_radius: Option<f32>,
         outer_radius: Option<f32>,
         bounds: &Bounds<Pixels>,
-    ) -> Option<Path<Pixels>> {
+    ) -> Option<PlotPath> {
         let start_angle = arc.start_angle - HALF_PI;
         let end_angle = arc.end_angle - HALF_PI;
         let da = end_angle - start_angle;
@@ -134,7 +134,7 @@ impl Arc {
         let x11 = center_x + r1 * a1_outer.cos();
         let y11 = center_y + r1 * a1_outer.sin();
 
-        let mut builder = PathBuilder::fill();
+        let mut builder = PlotPath::fill();
 
         // Move to the start point of the outer arc.
         builder.move_to(point(px(x01), px(y01)));
@@ -171,7 +171,7 @@ impl Arc {
             builder.line_to(point(px(center_x), px(center_y)));
         }
 
-        builder.build().ok()
+        Some(builder)
     }
 
     /// Paint the Arc.
@@ -186,7 +186,8 @@ impl Arc {
     ) {
         let path = self.path(arc, inner_radius, outer_radius, bounds);
         if let Some(path) = path {
-            window.paint_path(path, color.into());
+            let color: Hsla = color.into();
+            path.paint(color, window);
         }
     }
 }
@@ -1,8 +1,8 @@
 // @reference: https://d3js.org/d3-shape/area
 
-use gpui::{px, Background, Bounds, Path, PathBuilder, Pixels, Point, Window};
+use gpui::{px, Background, Bounds, Pixels, Point, Window};
 
-use crate::plot::{origin_point, StrokeStyle};
+use crate::plot::{origin_point, PlotPath, StrokeStyle};
 
 #[allow(clippy::type_complexity)]
 pub struct Area<T> {
@@ -12,7 +12,9 @@ pub struct Area<T> {
     y1: Box<dyn Fn(&T) -> Option<f32>>,
     fill: Background,
     stroke: Background,
+    stroke_width: Pixels,
     stroke_style: StrokeStyle,
+    dash_array: Option<Vec<Pixels>>,
 }
 
 impl<T> Default for Area<T> {
@@ -24,7 +26,9 @@ impl<T> Default for Area<T> {
             y1: Box::new(|_| None),
             fill: Default::default(),
             stroke: Default::default(),
+            stroke_width: px(1.),
             stroke_style: Default::default(),
+            dash_array: None,
         }
     }
 }
@@ -85,10 +89,25 @@ impl<T> Area<T> {
         self
     }
 
-    fn path(&self, bounds: &Bounds<Pixels>) -> (Option<Path<Pixels>>, Option<Path<Pixels>>) {
+    /// Set the stroke width of the Area, default: 1px.
+    pub fn stroke_width(mut self, stroke_width: impl Into<Pixels>) -> Self {
+        self.stroke_width = stroke_width.into();
+        self
+    }
+
+    /// Set the dash array of the stroke of the Area.
+    pub fn dash_array(mut self, dash_array: &[Pixels]) -> Self {
+        self.dash_array = Some(dash_array.to_vec());
+        self
+    }
+
+    fn path(&self, bounds: &Bounds<Pixels>) -> (Option<PlotPath>, Option<PlotPath>) {
         let origin = bounds.origin;
-        let mut area_builder = PathBuilder::fill();
-        let mut line_builder = PathBuilder::stroke(px(1.));
+        let mut area_builder = PlotPath::fill();
+        let mut line_builder = PlotPath::stroke(self.stroke_width);
+        if let Some(dash_array) = &self.dash_array {
+            line_builder = line_builder.dash_array(dash_array);
+        }
 
         let mut points = vec![];
 
@@ -110,7 +129,7 @@ impl<T> Area<T> {
         if points.len() == 1 {
             area_builder.move_to(points[0]);
             line_builder.move_to(points[0]);
-            return (area_builder.build().ok(), line_builder.build().ok());
+            return (Some(area_builder), Some(line_builder));
         }
 
         match self.stroke_style {
@@ -166,7 +185,7 @@ impl<T> Area<T> {
             }
         }
 
-        (area_builder.build().ok(), line_builder.build().ok())
+        (Some(area_builder), Some(line_builder))
     }
 
     /// Paint the Area.
@@ -174,10 +193,10 @@ impl<T> Area<T> {
         let (area, line) = self.path(bounds);
 
         if let Some(area) = area {
-            window.paint_path(area, self.fill);
+            area.paint(self.fill, window);
         }
         if let Some(line) = line {
-            window.paint_path(line, self.stroke);
+            line.paint(self.stroke, window);
         }
     }
 }
@@ -1,8 +1,11 @@
 use gpui::{fill, point, px, App, Bounds, Hsla, PaintQuad, Pixels, Point, Window};
 
-use crate::plot::{
-    label::{Label, Text, TEXT_GAP, TEXT_HEIGHT},
-    origin_point,
+use crate::{
+    export,
+    plot::{
+        label::{Label, Text, TEXT_GAP, TEXT_HEIGHT},
+        origin_point,
+    },
 };
 
 #[allow(clippy::type_complexity)]
@@ -143,7 +146,7 @@ impl<T> Bar<T> {
     pub fn paint(&self, bounds: &Bounds<Pixels>, window: &mut Window, cx: &mut App) {
         let (graph, labels) = self.path(bounds);
         for quad in graph {
-            window.paint_quad(quad);
+            export::paint_quad(window, quad);
         }
         labels.paint(bounds, window, cx);
     }
@@ -1,12 +1,12 @@
 // @reference: https://d3js.org/d3-shape/line
 
 use gpui::{
-    px, quad, size, Background, BorderStyle, Bounds, Hsla, PaintQuad, Path, PathBuilder, Pixels,
-    Point, Window,
+    px, quad, size, Background, BorderStyle, Bounds, Hsla, PaintQuad, Pixels, Point, Window,
 };
 
 use crate::{
-    plot::{origin_point, StrokeStyle},
+    export,
+    plot::{origin_point, Marker, PlotPath, StrokeStyle},
     PixelsExt,
 };
 
@@ -18,7 +18,9 @@ pub struct Line<T> {
     stroke: Background,
     stroke_width: Pixels,
     stroke_style: StrokeStyle,
+    dash_array: Option<Vec<Pixels>>,
     dot: bool,
+    dot_marker: Marker,
     dot_size: Pixels,
     dot_fill_color: Hsla,
     dot_stroke_color: Option<Hsla>,
@@ -33,7 +35,9 @@ impl<T> Default for Line<T> {
             stroke: Default::default(),
             stroke_width: px(1.),
             stroke_style: Default::default(),
+            dash_array: None,
             dot: false,
+            dot_marker: Marker::default(),
             dot_size: px(4.),
             dot_fill_color: gpui::transparent_black(),
             dot_stroke_color: None,
@@ -91,12 +95,24 @@ impl<T> Line<T> {
         self
     }
 
+    /// Set the dash array of the Line, e.g. `&[px(4.), px(2.)]`.
+    pub fn dash_array(mut self, dash_array: &[Pixels]) -> Self {
+        self.dash_array = Some(dash_array.to_vec());
+        self
+    }
+
     /// Show dots on the Line.
     pub fn dot(mut self) -> Self {
         self.dot = true;
         self
     }
 
+    /// Set the shape of the dots on the Line, default: [`Marker::Circle`].
+    pub fn dot_marker(mut self, marker: Marker) -> Self {
+        self.dot_marker = marker;
+        self
+    }
+
     /// Set the size of the dots on the Line.
     pub fn dot_size(mut self, dot_size: impl Into<Pixels>) -> Self {
         self.dot_size = dot_size.into();
@@ -117,9 +133,14 @@ impl<T> Line<T> {
 
     /// Paint the dots on the Line.
     fn paint_dot(&self, dot: Point<Pixels>) -> PaintQuad {
+        let corner_radius = match self.dot_marker {
+            Marker::Square => px(0.),
+            _ => self.dot_size / 2.,
+        };
+
         quad(
             gpui::bounds(dot, size(self.dot_size, self.dot_size)),
-            self.dot_size / 2.,
+            corner_radius,
             self.dot_fill_color,
             px(1.),
             self.dot_stroke_color.unwrap_or(self.dot_fill_color),
@@ -127,9 +148,24 @@ impl<T> Line<T> {
         )
     }
 
-    fn path(&self, bounds: &Bounds<Pixels>) -> (Option<Path<Pixels>>, Vec<PaintQuad>) {
+    /// The diamond of the dot at the `center`.
+    fn dot_diamond(&self, center: Point<Pixels>) -> PlotPath {
+        let r = self.dot_size / 2.;
+        let mut builder = PlotPath::fill();
+        builder.move_to(Point::new(center.x, center.y - r));
+        builder.line_to(Point::new(center.x + r, center.y));
+        builder.line_to(Point::new(center.x, center.y + r));
+        builder.line_to(Point::new(center.x - r, center.y));
+        builder.close();
+        builder
+    }
+
+    fn path(&self, bounds: &Bounds<Pixels>) -> (Option<PlotPath>, Vec<PaintQuad>) {
         let origin = bounds.origin;
-        let mut builder = PathBuilder::stroke(self.stroke_width);
+        let mut builder = PlotPath::stroke(self.stroke_width);
+        if let Some(dash_array) = &self.dash_array {
+            builder = builder.dash_array(dash_array);
+        }
         let mut dots = vec![];
         let mut paint_dots = vec![];
 
@@ -140,7 +176,7 @@ impl<T> Line<T> {
             if let (Some(x), Some(y)) = (x_tick, y_tick) {
                 let pos = origin_point(px(x), px(y), origin);
 
-                if self.dot {
+                if self.dot && self.dot_marker != Marker::Diamond {
                     let dot_radius = self.dot_size.as_f32() / 2.;
                     let dot_pos = origin_point(px(x - dot_radius), px(y - dot_radius), origin);
                     paint_dots.push(self.paint_dot(dot_pos));
@@ -156,7 +192,7 @@ impl<T> Line<T> {
 
         if dots.len() == 1 {
             builder.move_to(dots[0]);
-            return (builder.build().ok(), paint_dots);
+            return (Some(builder), paint_dots);
         }
 
         match self.stroke_style {
@@ -191,17 +227,26 @@ impl<T> Line<T> {
             }
         }
 
-        (builder.build().ok(), paint_dots)
+        (Some(builder), paint_dots)
     }
 
     /// Paint the Line.
     pub fn paint(&self, bounds: &Bounds<Pixels>, window: &mut Window) {
         let (path, dots) = self.path(bounds);
         if let Some(path) = path {
-            window.paint_path(path, self.stroke);
+            path.paint(self.stroke, window);
         }
         for dot in dots {
-            window.paint_quad(dot);
+            export::paint_quad(window, dot);
+        }
+
+        if self.dot && self.dot_marker == Marker::Diamond {
+            for v in self.data.iter() {
+                if let (Some(x), Some(y)) = ((self.x)(v), (self.y)(v)) {
+                    let center = origin_point(px(x), px(y), bounds.origin);
+                    self.dot_diamond(center).paint(self.dot_fill_color, window);
+                }
+            }
         }
     }
 }
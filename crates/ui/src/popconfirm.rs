@@ -0,0 +1,277 @@
+use std::rc::Rc;
+
+use gpui::{
+    div, prelude::FluentBuilder as _, px, App, AppContext as _, Context, Corner, DismissEvent,
+    ElementId, EventEmitter, FocusHandle, Focusable, InteractiveElement as _, IntoElement,
+    KeyBinding, ParentElement, Render, RenderOnce, SharedString, Styled, Window,
+};
+use rust_i18n::t;
+
+use crate::{
+    actions::{Cancel, Confirm},
+    button::{Button, ButtonVariant, ButtonVariants as _},
+    h_flex,
+    popover::Popover,
+    sound::{Sound, SoundFeedback},
+    v_flex, ActiveTheme as _, Icon, IconName, Selectable, Sizable as _, StyledExt as _,
+};
+
+const CONTEXT: &str = "Popconfirm";
+
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("enter", Confirm { secondary: false }, Some(CONTEXT)),
+        KeyBinding::new("escape", Cancel, Some(CONTEXT)),
+    ]);
+}
+
+type PopconfirmHandler = Rc<dyn Fn(&mut Window, &mut App)>;
+type TriggerFn = Box<dyn FnOnce(Popover<PopconfirmContent>) -> Popover<PopconfirmContent>>;
+
+/// The content of the [`Popconfirm`], with the message and the confirm/cancel buttons.
+pub struct PopconfirmContent {
+    focus_handle: FocusHandle,
+    message: SharedString,
+    description: Option<SharedString>,
+    icon: Option<Icon>,
+    confirm_text: SharedString,
+    cancel_text: SharedString,
+    confirm_variant: ButtonVariant,
+    on_confirm: Option<PopconfirmHandler>,
+    on_cancel: Option<PopconfirmHandler>,
+}
+
+impl PopconfirmContent {
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(on_confirm) = self.on_confirm.clone() {
+            on_confirm(window, cx);
+        }
+        SoundFeedback::play(Sound::Confirm, cx);
+        cx.emit(DismissEvent);
+    }
+
+    fn cancel(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(on_cancel) = self.on_cancel.clone() {
+            on_cancel(window, cx);
+        }
+        cx.emit(DismissEvent);
+    }
+}
+
+impl EventEmitter<DismissEvent> for PopconfirmContent {}
+
+impl Focusable for PopconfirmContent {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for PopconfirmContent {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context(CONTEXT)
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .p_3()
+            .gap_3()
+            .max_w(px(320.))
+            .child(
+                h_flex()
+                    .items_start()
+                    .gap_2()
+                    .when_some(self.icon.clone(), |this, icon| {
+                        this.child(
+                            div()
+                                .mt_0p5()
+                                .text_color(cx.theme().warning)
+                                .child(icon.small()),
+                        )
+                    })
+                    .child(
+                        v_flex()
+                            .gap_1()
+                            .child(div().text_sm().font_medium().child(self.message.clone()))
+                            .when_some(self.description.clone(), |this, description| {
+                                this.child(
+                                    div()
+                                        .text_xs()
+                                        .text_color(cx.theme().muted_foreground)
+                                        .child(description),
+                                )
+                            }),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .justify_end()
+                    .gap_2()
+                    .child(
+                        Button::new("cancel")
+                            .small()
+                            .outline()
+                            .label(self.cancel_text.clone())
+                            .on_click(
+                                cx.listener(|this, _, window, cx| this.cancel(&Cancel, window, cx)),
+                            ),
+                    )
+                    .child(
+                        Button::new("confirm")
+                            .small()
+                            .with_variant(self.confirm_variant)
+                            .label(self.confirm_text.clone())
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.confirm(&Confirm { secondary: false }, window, cx)
+                            })),
+                    ),
+            )
+    }
+}
+
+/// An inline confirmation popover anchored to the trigger, for the minor confirmations
+/// (e.g. delete a item) without a full [`Modal`](crate::modal::Modal).
+///
+/// - Click the trigger to open, press `enter` to confirm or `escape` to cancel.
+/// - The popover is focused when opened, and the focus is restored to the previous
+///   focused element when dismissed.
+///
+/// ```ignore
+/// Popconfirm::new("delete")
+///     .message("Delete this item?")
+///     .trigger(Button::new("delete").danger().label("Delete"))
+///     .on_confirm(|window, cx| delete_item(window, cx))
+/// ```
+#[derive(IntoElement)]
+pub struct Popconfirm {
+    id: ElementId,
+    message: SharedString,
+    description: Option<SharedString>,
+    icon: Option<Icon>,
+    confirm_text: Option<SharedString>,
+    cancel_text: Option<SharedString>,
+    confirm_variant: ButtonVariant,
+    anchor: Corner,
+    trigger: Option<TriggerFn>,
+    on_confirm: Option<PopconfirmHandler>,
+    on_cancel: Option<PopconfirmHandler>,
+}
+
+impl Popconfirm {
+    /// Create a new Popconfirm.
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            message: SharedString::default(),
+            description: None,
+            icon: Some(Icon::new(IconName::TriangleAlert)),
+            confirm_text: None,
+            cancel_text: None,
+            confirm_variant: ButtonVariant::Danger,
+            anchor: Corner::TopLeft,
+            trigger: None,
+            on_confirm: None,
+            on_cancel: None,
+        }
+    }
+
+    /// Set the message of the confirmation.
+    pub fn message(mut self, message: impl Into<SharedString>) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    /// Set the description below the message.
+    pub fn description(mut self, description: impl Into<SharedString>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the icon before the message, default is [`IconName::TriangleAlert`], `None` to hide it.
+    pub fn icon(mut self, icon: impl Into<Option<Icon>>) -> Self {
+        self.icon = icon.into();
+        self
+    }
+
+    /// Set the text of the confirm button, default: "OK"
+    pub fn confirm_text(mut self, confirm_text: impl Into<SharedString>) -> Self {
+        self.confirm_text = Some(confirm_text.into());
+        self
+    }
+
+    /// Set the text of the cancel button, default: "Cancel"
+    pub fn cancel_text(mut self, cancel_text: impl Into<SharedString>) -> Self {
+        self.cancel_text = Some(cancel_text.into());
+        self
+    }
+
+    /// Set the variant of the confirm button, default: [`ButtonVariant::Danger`]
+    pub fn confirm_variant(mut self, confirm_variant: ButtonVariant) -> Self {
+        self.confirm_variant = confirm_variant;
+        self
+    }
+
+    /// Set the anchor corner of the popover, default is `Corner::TopLeft`.
+    pub fn anchor(mut self, anchor: Corner) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Set the trigger element (e.g. a destructive [`Button`]) to open the popover.
+    ///
+    /// The trigger should not have the `on_click` for the action, use [`Self::on_confirm`] instead.
+    pub fn trigger<T>(mut self, trigger: T) -> Self
+    where
+        T: Selectable + IntoElement + 'static,
+    {
+        self.trigger = Some(Box::new(move |popover| popover.trigger(trigger)));
+        self
+    }
+
+    /// Set the callback when confirmed.
+    pub fn on_confirm(mut self, on_confirm: impl Fn(&mut Window, &mut App) + 'static) -> Self {
+        self.on_confirm = Some(Rc::new(on_confirm));
+        self
+    }
+
+    /// Set the callback when canceled.
+    pub fn on_cancel(mut self, on_cancel: impl Fn(&mut Window, &mut App) + 'static) -> Self {
+        self.on_cancel = Some(Rc::new(on_cancel));
+        self
+    }
+}
+
+impl RenderOnce for Popconfirm {
+    fn render(self, _: &mut Window, _: &mut App) -> impl IntoElement {
+        let message = self.message;
+        let description = self.description;
+        let icon = self.icon;
+        let confirm_text = self.confirm_text.unwrap_or_else(|| t!("Modal.ok").into());
+        let cancel_text = self
+            .cancel_text
+            .unwrap_or_else(|| t!("Modal.cancel").into());
+        let confirm_variant = self.confirm_variant;
+        let on_confirm = self.on_confirm;
+        let on_cancel = self.on_cancel;
+
+        let popover = Popover::new(self.id)
+            .anchor(self.anchor)
+            .content(move |_, cx| {
+                cx.new(|cx| PopconfirmContent {
+                    focus_handle: cx.focus_handle(),
+                    message: message.clone(),
+                    description: description.clone(),
+                    icon: icon.clone(),
+                    confirm_text: confirm_text.clone(),
+                    cancel_text: cancel_text.clone(),
+                    confirm_variant,
+                    on_confirm: on_confirm.clone(),
+                    on_cancel: on_cancel.clone(),
+                })
+            });
+
+        match self.trigger {
+            Some(trigger) => trigger(popover),
+            None => popover,
+        }
+    }
+}
@@ -7,7 +7,7 @@ use gpui::{
 };
 use std::{cell::RefCell, rc::Rc};
 
-use crate::{actions::Cancel, Selectable, StyledExt as _};
+use crate::{actions::Cancel, elevation::Layer, Selectable, StyledExt as _};
 
 const CONTEXT: &str = "Popover";
 
@@ -301,7 +301,7 @@ impl<M: ManagedView> Element for Popover<M> {
                                     }),
                             ),
                         )
-                        .with_priority(1)
+                        .with_priority(Layer::Popover.priority())
                         .into_any()
                     };
 
@@ -0,0 +1,102 @@
+use gpui::{
+    anchored, deferred, div, point, prelude::FluentBuilder as _, px, AnyElement, App, Corner,
+    IntoElement, ParentElement, Pixels, Point, RenderOnce, StyleRefinement, Styled, Window,
+};
+use smallvec::SmallVec;
+
+use crate::{elevation::Layer, StyledExt as _};
+
+/// Render the children in a window-level overlay layer, outside the clipping of the parents,
+/// e.g. a scroll container, Table cell or Sidebar.
+///
+/// The children are placed at the position of the portal in the layout, and they are still
+/// the elements of the logical parent, so the state and the actions are kept with the parent.
+///
+/// ```ignore
+/// div()
+///     .overflow_hidden()
+///     .child(Button::new("more").label("More"))
+///     .when(self.open, |this| {
+///         this.child(
+///             Portal::new()
+///                 .layer(Layer::Dropdown)
+///                 .popover_style(cx)
+///                 .child(menu),
+///         )
+///     })
+/// ```
+#[derive(IntoElement)]
+pub struct Portal {
+    layer: Layer,
+    anchor: Corner,
+    offset: Point<Pixels>,
+    snap_margin: Option<Pixels>,
+    style: StyleRefinement,
+    children: SmallVec<[AnyElement; 1]>,
+}
+
+impl Portal {
+    /// Create a new portal in the [`Layer::Popover`].
+    pub fn new() -> Self {
+        Self {
+            layer: Layer::Popover,
+            anchor: Corner::TopLeft,
+            offset: point(px(0.), px(0.)),
+            snap_margin: Some(px(8.)),
+            style: StyleRefinement::default(),
+            children: SmallVec::new(),
+        }
+    }
+
+    /// Set the layer to paint the children, default: [`Layer::Popover`]
+    pub fn layer(mut self, layer: Layer) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    /// Set the corner of the children placed at the position of the portal, default: [`Corner::TopLeft`]
+    pub fn anchor(mut self, anchor: Corner) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Set the offset from the position of the portal.
+    pub fn offset(mut self, offset: Point<Pixels>) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Set the margin to the window edges to keep the children inside the window,
+    /// `None` to allow overflowing the window, default: 8px
+    pub fn snap_to_window(mut self, margin: Option<Pixels>) -> Self {
+        self.snap_margin = margin;
+        self
+    }
+}
+
+impl ParentElement for Portal {
+    fn extend(&mut self, elements: impl IntoIterator<Item = AnyElement>) {
+        self.children.extend(elements);
+    }
+}
+
+impl Styled for Portal {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for Portal {
+    fn render(self, _: &mut Window, _: &mut App) -> impl IntoElement {
+        deferred(
+            anchored()
+                .anchor(self.anchor)
+                .offset(self.offset)
+                .when_some(self.snap_margin, |this, margin| {
+                    this.snap_to_window_with_margin(margin)
+                })
+                .child(div().refine_style(&self.style).children(self.children)),
+        )
+        .with_priority(self.layer.priority())
+    }
+}
@@ -0,0 +1,251 @@
+use std::{
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use gpui::{
+    div, px, AnyElement, App, Div, ElementId, Empty, IntoElement, ParentElement, Pixels,
+    RenderOnce, StyleRefinement, Styled, Window,
+};
+use smallvec::SmallVec;
+
+use crate::{
+    animation::{cubic_bezier, MotionSettings},
+    Placement, StyledExt as _,
+};
+
+/// The distance of the [`Transition::Slide`].
+const SLIDE_DISTANCE: Pixels = px(16.);
+
+/// The transition of the [`Presence`] to show or hide the element.
+#[derive(Clone)]
+pub enum Transition {
+    /// Fade in or out.
+    Fade,
+    /// Fade and slide in from the side, and slide out to the side.
+    Slide(Placement),
+    /// The custom transition, the `f32` is from `0.0` (hidden) to `1.0` (shown).
+    Custom(Rc<dyn Fn(Div, f32) -> Div>),
+}
+
+impl Transition {
+    /// Create a custom transition, the `f32` is from `0.0` (hidden) to `1.0` (shown).
+    pub fn custom(f: impl Fn(Div, f32) -> Div + 'static) -> Self {
+        Self::Custom(Rc::new(f))
+    }
+
+    fn apply(&self, this: Div, shown: f32) -> Div {
+        match self {
+            Self::Fade => this.opacity(shown),
+            Self::Slide(placement) => {
+                let offset = SLIDE_DISTANCE * (1. - shown);
+                let this = this.relative().opacity(shown);
+                match placement {
+                    Placement::Top => this.top(-offset),
+                    Placement::Bottom => this.top(offset),
+                    Placement::Left => this.left(-offset),
+                    Placement::Right => this.left(offset),
+                }
+            }
+            Self::Custom(f) => f(this, shown),
+        }
+    }
+}
+
+struct PresenceState {
+    visible: bool,
+    /// The start time of the running transition.
+    started_at: Option<Instant>,
+}
+
+/// Animate the children in and out when the `visible` is changed, and keep the children
+/// in the tree until the exit transition is finished.
+///
+/// The children are still required when it is hidden, to render the exit transition.
+///
+/// ```ignore
+/// v_flex()
+///     .children(self.items.iter().map(|item| {
+///         Presence::new(item.id, !item.removed)
+///             .transition(Transition::Slide(Placement::Left))
+///             .child(item_view(item))
+///     }))
+/// ```
+#[derive(IntoElement)]
+pub struct Presence {
+    id: ElementId,
+    visible: bool,
+    initial: bool,
+    transition: Transition,
+    exit_transition: Option<Transition>,
+    duration: Duration,
+    easing: Rc<dyn Fn(f32) -> f32>,
+    style: StyleRefinement,
+    children: SmallVec<[AnyElement; 1]>,
+}
+
+impl Presence {
+    /// Create a new presence, the `id` keeps the state of the transition.
+    pub fn new(id: impl Into<ElementId>, visible: bool) -> Self {
+        Self {
+            id: id.into(),
+            visible,
+            initial: true,
+            transition: Transition::Fade,
+            exit_transition: None,
+            duration: Duration::from_secs_f64(0.25),
+            easing: Rc::new(cubic_bezier(0.4, 0., 0.2, 1.)),
+            style: StyleRefinement::default(),
+            children: SmallVec::new(),
+        }
+    }
+
+    /// Set the transition to show and hide, default: [`Transition::Fade`]
+    pub fn transition(mut self, transition: Transition) -> Self {
+        self.transition = transition;
+        self
+    }
+
+    /// Set a different transition to hide, default is same as the [`Presence::transition`].
+    pub fn exit(mut self, transition: Transition) -> Self {
+        self.exit_transition = Some(transition);
+        self
+    }
+
+    /// Set the duration of the transition, default: 250ms
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Set the easing curve of the transition, e.g. [`cubic_bezier`] or [`gpui::ease_in_out`].
+    pub fn easing(mut self, easing: impl Fn(f32) -> f32 + 'static) -> Self {
+        self.easing = Rc::new(easing);
+        self
+    }
+
+    /// Set to animate in when it is first rendered as visible, default: true
+    pub fn initial(mut self, initial: bool) -> Self {
+        self.initial = initial;
+        self
+    }
+}
+
+impl ParentElement for Presence {
+    fn extend(&mut self, elements: impl IntoIterator<Item = AnyElement>) {
+        self.children.extend(elements);
+    }
+}
+
+impl Styled for Presence {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+/// Returns the linear progress of the transition.
+fn progress(elapsed: Duration, duration: Duration) -> f32 {
+    if duration.is_zero() {
+        return 1.;
+    }
+
+    (elapsed.as_secs_f32() / duration.as_secs_f32()).min(1.)
+}
+
+/// Returns the start time of a reversed transition, to continue from the current position.
+fn reversed_start(now: Instant, started_at: Option<Instant>, duration: Duration) -> Instant {
+    let Some(started_at) = started_at else {
+        return now;
+    };
+
+    let elapsed = now.duration_since(started_at).min(duration);
+    now - (duration - elapsed)
+}
+
+impl RenderOnce for Presence {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let now = Instant::now();
+        let visible = self.visible;
+        let initial = self.initial;
+        let motion = MotionSettings::global(cx);
+        let duration = motion.duration(self.duration);
+        let state = window.use_keyed_state(self.id, cx, |_, _| PresenceState {
+            visible,
+            started_at: (visible && initial).then_some(now),
+        });
+
+        let progress = state.update(cx, |state, _| {
+            if state.visible != visible {
+                state.visible = visible;
+                state.started_at = Some(reversed_start(now, state.started_at, duration));
+            }
+
+            let progress = state
+                .started_at
+                .map_or(1., |started_at| progress(now - started_at, duration));
+            if progress >= 1. {
+                state.started_at = None;
+            }
+            progress
+        });
+
+        if !visible && progress >= 1. {
+            return Empty.into_any_element();
+        }
+        if progress < 1. {
+            window.request_animation_frame();
+        }
+
+        let eased = (self.easing)(progress);
+        let (transition, shown) = if visible {
+            (&self.transition, eased)
+        } else {
+            (
+                self.exit_transition.as_ref().unwrap_or(&self.transition),
+                1. - eased,
+            )
+        };
+        // Fade instead of sliding in the reduced motion.
+        let transition = match transition {
+            Transition::Slide(_) if !motion.is_full() => &Transition::Fade,
+            transition => transition,
+        };
+
+        transition
+            .apply(div(), shown)
+            .refine_style(&self.style)
+            .children(self.children)
+            .into_any_element()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{progress, reversed_start};
+
+    #[test]
+    fn test_progress() {
+        let duration = Duration::from_millis(200);
+        assert_eq!(progress(Duration::ZERO, duration), 0.);
+        assert_eq!(progress(Duration::from_millis(100), duration), 0.5);
+        assert_eq!(progress(Duration::from_millis(300), duration), 1.);
+        assert_eq!(progress(Duration::ZERO, Duration::ZERO), 1.);
+    }
+
+    #[test]
+    fn test_reversed_start() {
+        let duration = Duration::from_millis(200);
+        let now = Instant::now() + Duration::from_secs(1);
+
+        assert_eq!(reversed_start(now, None, duration), now);
+        // Reversed at 3/4 of the transition, continue from 1/4.
+        let started_at = now - Duration::from_millis(150);
+        let start = reversed_start(now, Some(started_at), duration);
+        assert_eq!(now - start, Duration::from_millis(50));
+        // Reversed after finished, start from the beginning.
+        let started_at = now - Duration::from_millis(500);
+        assert_eq!(reversed_start(now, Some(started_at), duration), now);
+    }
+}
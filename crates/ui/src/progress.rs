@@ -1,6 +1,9 @@
-use crate::{ActiveTheme, StyledExt};
+use std::rc::Rc;
+
+use crate::{h_flex, tooltip::Tooltip, v_flex, ActiveTheme, StyledExt};
 use gpui::{
-    div, prelude::FluentBuilder, px, relative, App, Hsla, IntoElement, ParentElement, RenderOnce,
+    div, prelude::FluentBuilder, px, relative, App, ElementId, Hsla, InteractiveElement as _,
+    IntoElement, ParentElement, RenderOnce, SharedString, StatefulInteractiveElement as _,
     StyleRefinement, Styled, Window,
 };
 
@@ -77,3 +80,230 @@ impl RenderOnce for Progress {
             )
     }
 }
+
+/// A segment of the [`SegmentedBar`].
+#[derive(Clone)]
+pub struct BarSegment {
+    label: SharedString,
+    value: f64,
+    color: Option<Hsla>,
+}
+
+impl BarSegment {
+    /// Create a new segment with the label and value.
+    pub fn new(label: impl Into<SharedString>, value: f64) -> Self {
+        Self {
+            label: label.into(),
+            value: value.max(0.),
+            color: None,
+        }
+    }
+
+    /// Set the color of the segment, default is the `chart` colors of the theme in order.
+    pub fn color(mut self, color: impl Into<Hsla>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+}
+
+impl<L: Into<SharedString>> From<(L, f64)> for BarSegment {
+    fn from((label, value): (L, f64)) -> Self {
+        Self::new(label, value)
+    }
+}
+
+impl<L: Into<SharedString>, C: Into<Hsla>> From<(L, f64, C)> for BarSegment {
+    fn from((label, value, color): (L, f64, C)) -> Self {
+        Self::new(label, value).color(color)
+    }
+}
+
+/// Returns the ratio of each value in the total,
+/// the total is the sum of the values if it is less than the sum.
+fn segment_ratios(values: &[f64], total: Option<f64>) -> Vec<f32> {
+    let sum: f64 = values.iter().sum();
+    let total = total.map_or(sum, |total| total.max(sum));
+    if total <= 0. {
+        return vec![0.; values.len()];
+    }
+
+    values.iter().map(|value| (value / total) as f32).collect()
+}
+
+/// A multi-part bar to show the values of the categories in a total,
+/// e.g. the disk usage by category.
+///
+/// Hover a segment to show the label, value and percentage in a tooltip.
+///
+/// ```ignore
+/// SegmentedBar::new("storage")
+///     .total(512.)
+///     .segment(("Apps", 120.))
+///     .segment(("Photos", 86.5))
+///     .segment(("System", 40., cx.theme().muted_foreground))
+///     .format_value(|value| format!("{value} GB").into())
+/// ```
+#[derive(IntoElement)]
+pub struct SegmentedBar {
+    id: ElementId,
+    style: StyleRefinement,
+    segments: Vec<BarSegment>,
+    total: Option<f64>,
+    legend: bool,
+    format_value: Option<Rc<dyn Fn(f64) -> SharedString>>,
+}
+
+impl SegmentedBar {
+    /// Create a new SegmentedBar.
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            style: StyleRefinement::default().h(px(8.)).rounded(px(4.)),
+            segments: vec![],
+            total: None,
+            legend: true,
+            format_value: None,
+        }
+    }
+
+    /// Add a segment, a `(label, value)` or `(label, value, color)` tuple is also accepted.
+    pub fn segment(mut self, segment: impl Into<BarSegment>) -> Self {
+        self.segments.push(segment.into());
+        self
+    }
+
+    /// Add segments.
+    pub fn segments(mut self, segments: impl IntoIterator<Item = impl Into<BarSegment>>) -> Self {
+        self.segments.extend(segments.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set the total value, the rest of the segments is displayed as the empty track.
+    ///
+    /// Default is the sum of the segments.
+    pub fn total(mut self, total: f64) -> Self {
+        self.total = Some(total);
+        self
+    }
+
+    /// Set whether to show the legend below the bar, default: true
+    pub fn legend(mut self, legend: bool) -> Self {
+        self.legend = legend;
+        self
+    }
+
+    /// Set the format of the values in the tooltip and legend.
+    pub fn format_value(mut self, format: impl Fn(f64) -> SharedString + 'static) -> Self {
+        self.format_value = Some(Rc::new(format));
+        self
+    }
+}
+
+impl Styled for SegmentedBar {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for SegmentedBar {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let palette = [
+            cx.theme().chart_1,
+            cx.theme().chart_2,
+            cx.theme().chart_3,
+            cx.theme().chart_4,
+            cx.theme().chart_5,
+        ];
+        let values = self
+            .segments
+            .iter()
+            .map(|segment| segment.value)
+            .collect::<Vec<_>>();
+        let ratios = segment_ratios(&values, self.total);
+        let format_value = self.format_value.clone();
+        let format = move |value: f64| -> SharedString {
+            match &format_value {
+                Some(format_value) => format_value(value),
+                None => value.to_string().into(),
+            }
+        };
+
+        let segments = self
+            .segments
+            .into_iter()
+            .zip(ratios)
+            .enumerate()
+            .map(|(ix, (segment, ratio))| {
+                let color = segment.color.unwrap_or(palette[ix % palette.len()]);
+                (segment, ratio, color)
+            })
+            .collect::<Vec<_>>();
+
+        v_flex()
+            .id(self.id)
+            .w_full()
+            .gap_2()
+            .child(
+                h_flex()
+                    .w_full()
+                    .overflow_hidden()
+                    .bg(cx.theme().progress_bar.opacity(0.2))
+                    .refine_style(&self.style)
+                    .children(
+                        segments
+                            .iter()
+                            .enumerate()
+                            .map(|(ix, (segment, ratio, color))| {
+                                let tooltip = SharedString::from(format!(
+                                    "{}: {} ({:.1}%)",
+                                    segment.label,
+                                    format(segment.value),
+                                    ratio * 100.
+                                ));
+
+                                div()
+                                    .id(ix)
+                                    .h_full()
+                                    .flex_shrink_0()
+                                    .w(relative(*ratio))
+                                    .bg(*color)
+                                    .when(ix > 0, |this| {
+                                        this.border_l_1().border_color(cx.theme().background)
+                                    })
+                                    .tooltip(move |window, cx| {
+                                        Tooltip::new(tooltip.clone()).build(window, cx)
+                                    })
+                            }),
+                    ),
+            )
+            .when(self.legend, |this| {
+                this.child(h_flex().flex_wrap().gap_x_4().gap_y_1().text_xs().children(
+                    segments.iter().map(|(segment, _, color)| {
+                        h_flex()
+                            .gap_1p5()
+                            .child(div().size_2().rounded_full().bg(*color))
+                            .child(segment.label.clone())
+                            .child(
+                                div()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(format(segment.value)),
+                            )
+                    }),
+                ))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::segment_ratios;
+
+    #[test]
+    fn test_segment_ratios() {
+        assert_eq!(segment_ratios(&[1., 3.], None), vec![0.25, 0.75]);
+        assert_eq!(segment_ratios(&[1., 3.], Some(8.)), vec![0.125, 0.375]);
+        assert_eq!(segment_ratios(&[2., 2.], Some(2.)), vec![0.5, 0.5]);
+        assert_eq!(segment_ratios(&[0., 0.], None), vec![0., 0.]);
+        assert!(segment_ratios(&[], None).is_empty());
+    }
+}
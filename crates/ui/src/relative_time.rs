@@ -0,0 +1,196 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+use gpui::{
+    div, prelude::FluentBuilder as _, App, ElementId, InteractiveElement as _, IntoElement,
+    ParentElement, RenderOnce, SharedString, StatefulInteractiveElement as _, StyleRefinement,
+    Styled, Task, Window,
+};
+use rust_i18n::t;
+
+use crate::{tooltip::Tooltip, StyledExt as _};
+
+const MINUTE: i64 = 60;
+const HOUR: i64 = 60 * MINUTE;
+const DAY: i64 = 24 * HOUR;
+const MONTH: i64 = 30 * DAY;
+const YEAR: i64 = 365 * DAY;
+
+/// Returns the localized relative time of the timestamp to `now`,
+/// and the interval to refresh it.
+fn format_relative(timestamp: DateTime<Local>, now: DateTime<Local>) -> (SharedString, Duration) {
+    let seconds = now.signed_duration_since(timestamp).num_seconds();
+    let abs = seconds.abs();
+
+    let (count, unit, refresh) = if abs < 45 {
+        return (t!("RelativeTime.just_now").into(), Duration::from_secs(15));
+    } else if abs < 45 * MINUTE {
+        ((abs + 30) / MINUTE, "minute", Duration::from_secs(30))
+    } else if abs < 22 * HOUR {
+        (
+            (abs + 30 * MINUTE) / HOUR,
+            "hour",
+            Duration::from_secs(5 * 60),
+        )
+    } else if abs < 26 * DAY {
+        ((abs + 12 * HOUR) / DAY, "day", Duration::from_secs(60 * 60))
+    } else if abs < 320 * DAY {
+        (
+            (abs + 15 * DAY) / MONTH,
+            "month",
+            Duration::from_secs(60 * 60),
+        )
+    } else {
+        (
+            (abs + 182 * DAY) / YEAR,
+            "year",
+            Duration::from_secs(60 * 60),
+        )
+    };
+    let count = count.max(1);
+
+    let time = match (unit, count == 1) {
+        ("minute", true) => t!("RelativeTime.minute", count = count),
+        ("minute", false) => t!("RelativeTime.minutes", count = count),
+        ("hour", true) => t!("RelativeTime.hour", count = count),
+        ("hour", false) => t!("RelativeTime.hours", count = count),
+        ("day", true) => t!("RelativeTime.day", count = count),
+        ("day", false) => t!("RelativeTime.days", count = count),
+        ("month", true) => t!("RelativeTime.month", count = count),
+        ("month", false) => t!("RelativeTime.months", count = count),
+        (_, true) => t!("RelativeTime.year", count = count),
+        (_, false) => t!("RelativeTime.years", count = count),
+    };
+    let text = if seconds >= 0 {
+        t!("RelativeTime.ago", time = time)
+    } else {
+        t!("RelativeTime.in", time = time)
+    };
+    (text.into(), refresh)
+}
+
+struct RelativeTimeState {
+    interval: Duration,
+    _refresh_task: Option<Task<()>>,
+}
+
+/// A text of the relative time like "3 minutes ago", refreshed automatically,
+/// and the absolute time is shown in the tooltip.
+///
+/// ```ignore
+/// RelativeTime::new("updated-at", message.timestamp)
+///     .text_xs()
+///     .text_color(cx.theme().muted_foreground)
+/// ```
+#[derive(IntoElement)]
+pub struct RelativeTime {
+    id: ElementId,
+    timestamp: DateTime<Local>,
+    tooltip: bool,
+    tooltip_format: SharedString,
+    style: StyleRefinement,
+}
+
+impl RelativeTime {
+    /// Create a new relative time, the `id` keeps the refresh timer.
+    pub fn new(id: impl Into<ElementId>, timestamp: DateTime<Local>) -> Self {
+        Self {
+            id: id.into(),
+            timestamp,
+            tooltip: true,
+            tooltip_format: "%Y-%m-%d %H:%M:%S".into(),
+            style: StyleRefinement::default(),
+        }
+    }
+
+    /// Set to show the absolute time in the tooltip, default: true
+    pub fn tooltip(mut self, tooltip: bool) -> Self {
+        self.tooltip = tooltip;
+        self
+    }
+
+    /// Set the [`chrono` format](https://docs.rs/chrono/latest/chrono/format/strftime/index.html)
+    /// of the absolute time in the tooltip, default: `%Y-%m-%d %H:%M:%S`
+    pub fn tooltip_format(mut self, format: impl Into<SharedString>) -> Self {
+        self.tooltip_format = format.into();
+        self
+    }
+}
+
+impl Styled for RelativeTime {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for RelativeTime {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let (text, interval) = format_relative(self.timestamp, Local::now());
+
+        // Refresh the view on the cadence of the current unit.
+        let view_id = window.current_view();
+        let state = window.use_keyed_state(self.id.clone(), cx, |_, _| RelativeTimeState {
+            interval,
+            _refresh_task: None,
+        });
+        state.update(cx, |state, cx| {
+            if state._refresh_task.is_some() && state.interval == interval {
+                return;
+            }
+
+            state.interval = interval;
+            state._refresh_task = Some(cx.spawn(async move |_, cx| loop {
+                cx.background_executor().timer(interval).await;
+                if cx.update(|cx| cx.notify(view_id)).is_err() {
+                    break;
+                }
+            }));
+        });
+
+        let absolute: SharedString = self
+            .timestamp
+            .format(&self.tooltip_format)
+            .to_string()
+            .into();
+
+        div()
+            .id(self.id)
+            .child(text)
+            .when(self.tooltip, |this| {
+                this.tooltip(move |window, cx| Tooltip::new(absolute.clone()).build(window, cx))
+            })
+            .refine_style(&self.style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Local, TimeZone as _};
+
+    use super::format_relative;
+
+    #[test]
+    fn test_format_relative() {
+        let now = Local.with_ymd_and_hms(2024, 6, 15, 12, 0, 0).unwrap();
+        let text = |seconds: i64| format_relative(now - Duration::seconds(seconds), now).0;
+
+        assert_eq!(text(10), "just now");
+        assert_eq!(text(-10), "just now");
+        assert_eq!(text(50), "1 minute ago");
+        assert_eq!(text(3 * 60), "3 minutes ago");
+        assert_eq!(text(-3 * 60), "in 3 minutes");
+        assert_eq!(text(50 * 60), "1 hour ago");
+        assert_eq!(text(5 * 3600), "5 hours ago");
+        assert_eq!(text(23 * 3600), "1 day ago");
+        assert_eq!(text(3 * 86400), "3 days ago");
+        assert_eq!(text(40 * 86400), "1 month ago");
+        assert_eq!(text(-100 * 86400), "in 3 months");
+        assert_eq!(text(400 * 86400), "1 year ago");
+        assert_eq!(text(800 * 86400), "2 years ago");
+
+        assert_eq!(
+            format_relative(now - Duration::seconds(10), now).1,
+            std::time::Duration::from_secs(15)
+        );
+    }
+}
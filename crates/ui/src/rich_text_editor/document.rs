@@ -0,0 +1,434 @@
+use std::ops::Range;
+
+use gpui::SharedString;
+
+/// The inline marks of the text.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Marks {
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub link: Option<SharedString>,
+}
+
+impl Marks {
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    pub fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    pub fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+
+    pub fn link(mut self, url: impl Into<SharedString>) -> Self {
+        self.link = Some(url.into());
+        self
+    }
+}
+
+/// A run of text with the same marks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextSpan {
+    pub text: String,
+    pub marks: Marks,
+}
+
+/// The kind of a [`Block`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BlockKind {
+    #[default]
+    Paragraph,
+    /// Heading with level 1 to 3.
+    Heading(u8),
+    BulletItem,
+    NumberedItem,
+}
+
+impl BlockKind {
+    pub fn is_list_item(&self) -> bool {
+        matches!(self, Self::BulletItem | Self::NumberedItem)
+    }
+}
+
+/// A block of the [`RichTextDocument`], a paragraph, a heading or a list item.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Block {
+    pub kind: BlockKind,
+    pub spans: Vec<TextSpan>,
+}
+
+impl Block {
+    pub fn new(kind: BlockKind) -> Self {
+        Self {
+            kind,
+            spans: vec![],
+        }
+    }
+
+    /// Append a text span with the marks.
+    pub fn span(mut self, text: impl Into<String>, marks: Marks) -> Self {
+        self.spans.push(TextSpan {
+            text: text.into(),
+            marks,
+        });
+        self.normalize();
+        self
+    }
+
+    /// Returns the plain text.
+    pub fn text(&self) -> String {
+        self.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+
+    /// Returns the length of the text in bytes.
+    pub fn len(&self) -> usize {
+        self.spans.iter().map(|span| span.text.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the marks to continue typing at the offset, from the text before it.
+    pub(super) fn marks_at(&self, offset: usize) -> Marks {
+        let mut pos = 0;
+        for span in self.spans.iter() {
+            pos += span.text.len();
+            if offset <= pos && offset > pos - span.text.len() {
+                return span.marks.clone();
+            }
+        }
+
+        self.spans
+            .first()
+            .map(|span| span.marks.clone())
+            .unwrap_or_default()
+    }
+
+    /// Split the span at the offset, returns the index of the span starts at the offset.
+    fn split_at(&mut self, offset: usize) -> usize {
+        let mut pos = 0;
+        for ix in 0..self.spans.len() {
+            let len = self.spans[ix].text.len();
+            if offset == pos {
+                return ix;
+            }
+            if offset < pos + len {
+                let text = self.spans[ix].text.split_off(offset - pos);
+                let marks = self.spans[ix].marks.clone();
+                self.spans.insert(ix + 1, TextSpan { text, marks });
+                return ix + 1;
+            }
+            pos += len;
+        }
+        self.spans.len()
+    }
+
+    /// Merge the adjacent spans with the same marks, and remove the empty spans.
+    fn normalize(&mut self) {
+        let mut spans: Vec<TextSpan> = Vec::with_capacity(self.spans.len());
+        for span in self.spans.drain(..) {
+            if span.text.is_empty() {
+                continue;
+            }
+            match spans.last_mut() {
+                Some(last) if last.marks == span.marks => last.text.push_str(&span.text),
+                _ => spans.push(span),
+            }
+        }
+        self.spans = spans;
+    }
+
+    pub(super) fn insert(&mut self, offset: usize, text: &str, marks: Marks) {
+        let ix = self.split_at(offset);
+        self.spans.insert(
+            ix,
+            TextSpan {
+                text: text.to_string(),
+                marks,
+            },
+        );
+        self.normalize();
+    }
+
+    pub(super) fn remove(&mut self, range: Range<usize>) {
+        let start = self.split_at(range.start);
+        let end = self.split_at(range.end);
+        self.spans.drain(start..end);
+        self.normalize();
+    }
+
+    /// Split the block at the offset, returns the block after the offset with the same kind.
+    pub(super) fn split_off(&mut self, offset: usize) -> Block {
+        let ix = self.split_at(offset);
+        let spans = self.spans.split_off(ix);
+        self.normalize();
+        Block {
+            kind: self.kind,
+            spans,
+        }
+    }
+
+    pub(super) fn append(&mut self, other: Block) {
+        self.spans.extend(other.spans);
+        self.normalize();
+    }
+
+    pub(super) fn update_marks(&mut self, range: Range<usize>, f: &impl Fn(&mut Marks)) {
+        let start = self.split_at(range.start);
+        let end = self.split_at(range.end);
+        for span in self.spans[start..end].iter_mut() {
+            f(&mut span.marks);
+        }
+        self.normalize();
+    }
+
+    /// Returns the spans in the range.
+    pub(super) fn spans_in(&self, range: Range<usize>) -> impl Iterator<Item = &TextSpan> {
+        let mut pos = 0;
+        self.spans.iter().filter(move |span| {
+            let start = pos;
+            pos += span.text.len();
+            start < range.end && pos > range.start
+        })
+    }
+}
+
+/// The document of the [`RichTextEditor`](super::RichTextEditor), a list of blocks.
+///
+/// The offsets of the document are the byte offsets of the plain text,
+/// in which the blocks are joined with `\n`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RichTextDocument {
+    blocks: Vec<Block>,
+}
+
+impl Default for RichTextDocument {
+    fn default() -> Self {
+        Self {
+            blocks: vec![Block::default()],
+        }
+    }
+}
+
+impl RichTextDocument {
+    /// Create a document with the blocks, an empty paragraph is added if it is empty.
+    pub fn new(blocks: Vec<Block>) -> Self {
+        if blocks.is_empty() {
+            return Self::default();
+        }
+
+        Self { blocks }
+    }
+
+    pub fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+
+    /// Returns the plain text, the blocks are joined with `\n`.
+    pub fn text(&self) -> String {
+        self.blocks
+            .iter()
+            .map(Block::text)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Returns true if the document only has an empty paragraph.
+    pub fn is_empty(&self) -> bool {
+        self.blocks.len() == 1 && self.blocks[0].is_empty()
+    }
+
+    /// Returns the length of the plain text.
+    pub(super) fn len(&self) -> usize {
+        self.blocks
+            .iter()
+            .map(|block| block.len() + 1)
+            .sum::<usize>()
+            - 1
+    }
+
+    /// Returns the offset of the start of the block.
+    pub(super) fn block_start(&self, ix: usize) -> usize {
+        self.blocks[..ix].iter().map(|block| block.len() + 1).sum()
+    }
+
+    /// Returns the block index and the offset in the block of the offset.
+    pub(super) fn position(&self, offset: usize) -> (usize, usize) {
+        let mut start = 0;
+        for (ix, block) in self.blocks.iter().enumerate() {
+            if offset <= start + block.len() {
+                return (ix, offset.saturating_sub(start));
+            }
+            start += block.len() + 1;
+        }
+
+        let last = self.blocks.len() - 1;
+        (last, self.blocks[last].len())
+    }
+
+    /// Returns the range of the blocks in the range.
+    pub(super) fn block_range(&self, range: &Range<usize>) -> Range<usize> {
+        self.position(range.start).0..self.position(range.end).0 + 1
+    }
+
+    /// Returns the marks to continue typing at the offset.
+    pub(super) fn marks_at(&self, offset: usize) -> Marks {
+        let (ix, offset) = self.position(offset);
+        self.blocks[ix].marks_at(offset)
+    }
+
+    /// Replace the range with the text in the marks, returns the offset of the end of the new text.
+    ///
+    /// The `\n` in the text splits the block, the new blocks are paragraphs after a heading,
+    /// or the same kind for the list items.
+    pub(super) fn replace(&mut self, range: Range<usize>, text: &str, marks: &Marks) -> usize {
+        let (start_ix, start) = self.position(range.start);
+        let (end_ix, end) = self.position(range.end);
+        if start_ix == end_ix {
+            self.blocks[start_ix].remove(start..end);
+        } else {
+            let len = self.blocks[start_ix].len();
+            self.blocks[start_ix].remove(start..len);
+            let mut last = self.blocks.remove(end_ix);
+            last.remove(0..end);
+            self.blocks.drain(start_ix + 1..end_ix);
+            self.blocks[start_ix].append(last);
+        }
+
+        let (mut ix, mut offset) = (start_ix, start);
+        for (i, line) in text.split('\n').enumerate() {
+            if i > 0 {
+                let mut block = self.blocks[ix].split_off(offset);
+                if let BlockKind::Heading(_) = block.kind {
+                    block.kind = BlockKind::Paragraph;
+                }
+                ix += 1;
+                offset = 0;
+                self.blocks.insert(ix, block);
+            }
+            self.blocks[ix].insert(offset, line, marks.clone());
+            offset += line.len();
+        }
+
+        self.block_start(ix) + offset
+    }
+
+    /// Set the kind of the blocks in the range.
+    pub(super) fn set_block_kind(&mut self, blocks: Range<usize>, kind: BlockKind) {
+        for block in self.blocks[blocks].iter_mut() {
+            block.kind = kind;
+        }
+    }
+
+    /// Update the marks of the text in the range.
+    pub(super) fn update_marks(&mut self, range: Range<usize>, f: impl Fn(&mut Marks)) {
+        let (start_ix, start) = self.position(range.start);
+        let (end_ix, end) = self.position(range.end);
+        for ix in start_ix..=end_ix {
+            let block = &mut self.blocks[ix];
+            let block_start = if ix == start_ix { start } else { 0 };
+            let block_end = if ix == end_ix { end } else { block.len() };
+            block.update_marks(block_start..block_end, &f);
+        }
+    }
+
+    /// Returns true if all the text in the range matches the predicate.
+    pub(super) fn all_marks(&self, range: Range<usize>, f: impl Fn(&Marks) -> bool) -> bool {
+        if range.is_empty() {
+            return f(&self.marks_at(range.start));
+        }
+
+        let (start_ix, start) = self.position(range.start);
+        let (end_ix, end) = self.position(range.end);
+        (start_ix..=end_ix).all(|ix| {
+            let block = &self.blocks[ix];
+            let block_start = if ix == start_ix { start } else { 0 };
+            let block_end = if ix == end_ix { end } else { block.len() };
+            block
+                .spans_in(block_start..block_end)
+                .all(|span| f(&span.marks))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc() -> RichTextDocument {
+        RichTextDocument::new(vec![
+            Block::new(BlockKind::Heading(1)).span("Title", Marks::default()),
+            Block::new(BlockKind::Paragraph)
+                .span("Hello ", Marks::default())
+                .span("world", Marks::default().bold()),
+        ])
+    }
+
+    #[test]
+    fn test_position() {
+        let doc = doc();
+        assert_eq!(doc.text(), "Title\nHello world");
+        assert_eq!(doc.len(), 17);
+        assert_eq!(doc.position(5), (0, 5));
+        assert_eq!(doc.position(6), (1, 0));
+        assert_eq!(doc.position(17), (1, 11));
+        assert_eq!(doc.block_start(1), 6);
+        assert_eq!(doc.marks_at(12), Marks::default());
+        assert_eq!(doc.marks_at(13), Marks::default().bold());
+    }
+
+    #[test]
+    fn test_replace() {
+        let mut doc = doc();
+
+        // Split the heading, the new block is a paragraph.
+        assert_eq!(doc.replace(2..2, "\n", &Marks::default()), 3);
+        assert_eq!(doc.text(), "Ti\ntle\nHello world");
+        assert_eq!(doc.blocks()[1].kind, BlockKind::Paragraph);
+
+        // Merge the blocks, the first block keeps its kind.
+        assert_eq!(doc.replace(1..5, "", &Marks::default()), 1);
+        assert_eq!(doc.text(), "Te\nHello world");
+        assert_eq!(doc.blocks()[0].kind, BlockKind::Heading(1));
+
+        let end = doc.replace(9..14, "there", &Marks::default().italic());
+        assert_eq!(end, 14);
+        assert_eq!(
+            doc.blocks()[1].spans,
+            vec![
+                TextSpan {
+                    text: "Hello ".into(),
+                    marks: Marks::default()
+                },
+                TextSpan {
+                    text: "there".into(),
+                    marks: Marks::default().italic()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_marks() {
+        let mut doc = doc();
+        assert!(doc.all_marks(12..17, |m| m.bold));
+        assert!(!doc.all_marks(10..17, |m| m.bold));
+
+        doc.update_marks(2..15, |m| m.italic = true);
+        assert!(doc.all_marks(2..15, |m| m.italic));
+        assert!(!doc.all_marks(0..15, |m| m.italic));
+        assert_eq!(doc.blocks()[1].spans.len(), 3);
+
+        doc.update_marks(0..17, |m| m.italic = false);
+        assert_eq!(doc.blocks()[1].spans.len(), 2);
+        assert_eq!(doc.blocks()[0].spans.len(), 1);
+    }
+}
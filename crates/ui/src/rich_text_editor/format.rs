@@ -0,0 +1,476 @@
+use std::rc::Rc;
+
+use html5ever::{local_name, parse_document, tendril::TendrilSink as _, ParseOpts};
+use markdown::{mdast, ParseOptions};
+use markup5ever_rcdom::{Handle, NodeData, RcDom};
+
+use super::document::{Block, BlockKind, Marks, RichTextDocument, TextSpan};
+
+impl RichTextDocument {
+    /// Import the document from Markdown.
+    ///
+    /// The underline is imported from the `<u>` tags, the unsupported blocks are imported as paragraphs.
+    pub fn from_markdown(source: &str) -> Self {
+        let Ok(root) = markdown::to_mdast(source, &ParseOptions::gfm()) else {
+            return Self::from_text(source);
+        };
+
+        let mut blocks = vec![];
+        parse_markdown_blocks(&root, None, &mut blocks);
+        Self::new(blocks)
+    }
+
+    /// Export the document to Markdown, the underline is exported as `<u>` tags.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let mut number = 0;
+        let mut prev_kind: Option<BlockKind> = None;
+        for block in self.blocks() {
+            if let Some(prev_kind) = prev_kind {
+                out.push_str(if prev_kind.is_list_item() && prev_kind == block.kind {
+                    "\n"
+                } else {
+                    "\n\n"
+                });
+            }
+            number = match (prev_kind, block.kind) {
+                (Some(BlockKind::NumberedItem), BlockKind::NumberedItem) => number + 1,
+                _ => 1,
+            };
+            prev_kind = Some(block.kind);
+
+            match block.kind {
+                BlockKind::Paragraph => {}
+                BlockKind::Heading(level) => {
+                    out.push_str(&"#".repeat(level as usize));
+                    out.push(' ');
+                }
+                BlockKind::BulletItem => out.push_str("- "),
+                BlockKind::NumberedItem => out.push_str(&format!("{}. ", number)),
+            }
+            for span in block.spans.iter() {
+                push_markdown_span(&mut out, span);
+            }
+        }
+        out
+    }
+
+    /// Import the document from HTML.
+    ///
+    /// Supports `p`, `h1` to `h6`, `ul`, `ol`, `li`, `br`, `b`, `strong`, `i`, `em`, `u` and `a`,
+    /// the other tags are imported as their text.
+    pub fn from_html(source: &str) -> Self {
+        let Ok(dom) = parse_document(RcDom::default(), ParseOpts::default())
+            .from_utf8()
+            .read_from(&mut source.as_bytes())
+        else {
+            return Self::from_text(source);
+        };
+
+        let mut parser = HtmlParser::default();
+        parser.walk(&dom.document, &Marks::default());
+        parser.end_block();
+        Self::new(parser.blocks)
+    }
+
+    /// Export the document to HTML.
+    pub fn to_html(&self) -> String {
+        let mut out = String::new();
+        let mut list: Option<BlockKind> = None;
+        for block in self.blocks() {
+            let list_tag = |kind: BlockKind| match kind {
+                BlockKind::NumberedItem => "ol",
+                _ => "ul",
+            };
+            if list.is_some_and(|kind| kind != block.kind) {
+                out.push_str(&format!("</{}>", list_tag(list.take().unwrap())));
+            }
+            if block.kind.is_list_item() && list.is_none() {
+                out.push_str(&format!("<{}>", list_tag(block.kind)));
+                list = Some(block.kind);
+            }
+
+            let tag = match block.kind {
+                BlockKind::Paragraph => "p".to_string(),
+                BlockKind::Heading(level) => format!("h{}", level),
+                BlockKind::BulletItem | BlockKind::NumberedItem => "li".to_string(),
+            };
+            out.push_str(&format!("<{}>", tag));
+            for span in block.spans.iter() {
+                push_html_span(&mut out, span);
+            }
+            out.push_str(&format!("</{}>", tag));
+        }
+        if let Some(kind) = list {
+            out.push_str(if kind == BlockKind::NumberedItem {
+                "</ol>"
+            } else {
+                "</ul>"
+            });
+        }
+        out
+    }
+
+    /// Create a document of paragraphs from the plain text lines.
+    pub fn from_text(text: &str) -> Self {
+        Self::new(
+            text.lines()
+                .map(|line| Block::new(BlockKind::Paragraph).span(line, Marks::default()))
+                .collect(),
+        )
+    }
+}
+
+fn parse_markdown_blocks(node: &mdast::Node, list: Option<BlockKind>, blocks: &mut Vec<Block>) {
+    let kind = list.unwrap_or_default();
+    match node {
+        mdast::Node::Heading(heading) => {
+            let mut block = Block::new(BlockKind::Heading(heading.depth.clamp(1, 3)));
+            parse_markdown_inlines(&heading.children, &Marks::default(), &mut block);
+            blocks.push(block);
+        }
+        mdast::Node::Paragraph(paragraph) => {
+            let mut block = Block::new(kind);
+            parse_markdown_inlines(&paragraph.children, &Marks::default(), &mut block);
+            blocks.push(block);
+        }
+        mdast::Node::Code(code) => {
+            for line in code.value.lines() {
+                blocks.push(Block::new(kind).span(line, Marks::default()));
+            }
+        }
+        mdast::Node::List(list) => {
+            let kind = if list.ordered {
+                BlockKind::NumberedItem
+            } else {
+                BlockKind::BulletItem
+            };
+            for child in list.children.iter() {
+                parse_markdown_blocks(child, Some(kind), blocks);
+            }
+        }
+        _ => {
+            for child in node.children().into_iter().flatten() {
+                parse_markdown_blocks(child, list, blocks);
+            }
+        }
+    }
+}
+
+fn parse_markdown_inlines(nodes: &[mdast::Node], marks: &Marks, block: &mut Block) {
+    let mut marks = marks.clone();
+    for node in nodes {
+        match node {
+            mdast::Node::Text(text) => {
+                block.spans.push(TextSpan {
+                    text: text.value.replace('\n', " "),
+                    marks: marks.clone(),
+                });
+            }
+            mdast::Node::InlineCode(code) => {
+                block.spans.push(TextSpan {
+                    text: code.value.clone(),
+                    marks: marks.clone(),
+                });
+            }
+            mdast::Node::Break(_) => {
+                block.spans.push(TextSpan {
+                    text: " ".into(),
+                    marks: marks.clone(),
+                });
+            }
+            mdast::Node::Html(html) => match html.value.trim() {
+                "<u>" => marks.underline = true,
+                "</u>" => marks.underline = false,
+                _ => {}
+            },
+            mdast::Node::Strong(strong) => {
+                parse_markdown_inlines(&strong.children, &marks.clone().bold(), block)
+            }
+            mdast::Node::Emphasis(emphasis) => {
+                parse_markdown_inlines(&emphasis.children, &marks.clone().italic(), block)
+            }
+            mdast::Node::Link(link) => {
+                parse_markdown_inlines(&link.children, &marks.clone().link(link.url.clone()), block)
+            }
+            _ => {
+                if let Some(children) = node.children() {
+                    parse_markdown_inlines(children, &marks, block);
+                }
+            }
+        }
+    }
+    *block = std::mem::take(block).span("", Marks::default());
+}
+
+/// Escape the Markdown special characters.
+fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']' | '<' | '#') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn push_markdown_span(out: &mut String, span: &TextSpan) {
+    // The delimiters must be next to the non-whitespace characters.
+    let text = span.text.as_str();
+    let core = text.trim();
+    if core.is_empty() {
+        out.push_str(text);
+        return;
+    }
+    let leading = &text[..text.len() - text.trim_start().len()];
+    let trailing = &text[text.trim_end().len()..];
+
+    let marks = &span.marks;
+    let mut open = String::new();
+    let mut close = String::new();
+    if marks.link.is_some() {
+        open.push('[');
+    }
+    if marks.bold {
+        open.push_str("**");
+        close.insert_str(0, "**");
+    }
+    if marks.italic {
+        open.push('_');
+        close.insert(0, '_');
+    }
+    if marks.underline {
+        open.push_str("<u>");
+        close.insert_str(0, "</u>");
+    }
+    if let Some(link) = &marks.link {
+        close.push_str(&format!("]({})", link));
+    }
+
+    out.push_str(leading);
+    out.push_str(&open);
+    out.push_str(&escape_markdown(core));
+    out.push_str(&close);
+    out.push_str(trailing);
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn push_html_span(out: &mut String, span: &TextSpan) {
+    let marks = &span.marks;
+    if let Some(link) = &marks.link {
+        out.push_str(&format!("<a href=\"{}\">", escape_html(link)));
+    }
+    if marks.bold {
+        out.push_str("<strong>");
+    }
+    if marks.italic {
+        out.push_str("<em>");
+    }
+    if marks.underline {
+        out.push_str("<u>");
+    }
+    out.push_str(&escape_html(&span.text));
+    if marks.underline {
+        out.push_str("</u>");
+    }
+    if marks.italic {
+        out.push_str("</em>");
+    }
+    if marks.bold {
+        out.push_str("</strong>");
+    }
+    if marks.link.is_some() {
+        out.push_str("</a>");
+    }
+}
+
+#[derive(Default)]
+struct HtmlParser {
+    blocks: Vec<Block>,
+    current: Option<Block>,
+    lists: Vec<BlockKind>,
+}
+
+impl HtmlParser {
+    /// Start a new block, None to keep the current block if it is empty, e.g.: `<li><p>`.
+    fn start_block(&mut self, kind: Option<BlockKind>) {
+        if kind.is_none() && self.current.as_ref().is_some_and(|block| block.is_empty()) {
+            return;
+        }
+
+        let kind = kind.unwrap_or_default();
+        self.end_block();
+        self.current = Some(Block::new(kind));
+    }
+
+    fn end_block(&mut self) {
+        let Some(mut block) = self.current.take() else {
+            return;
+        };
+
+        if let Some(last) = block.spans.last_mut() {
+            last.text.truncate(last.text.trim_end().len());
+        }
+        block = block.span("", Marks::default());
+        if !block.is_empty() {
+            self.blocks.push(block);
+        }
+    }
+
+    fn push_text(&mut self, text: &str, marks: &Marks) {
+        // Collapse the whitespaces as the HTML rendering.
+        let mut collapsed = String::with_capacity(text.len());
+        for c in text.chars() {
+            if c.is_whitespace() {
+                if !collapsed.ends_with(' ') {
+                    collapsed.push(' ');
+                }
+            } else {
+                collapsed.push(c);
+            }
+        }
+
+        let block = self.current.get_or_insert_with(Block::default);
+        let at_line_start = block
+            .spans
+            .last()
+            .map_or(true, |span| span.text.ends_with(' '));
+        let text = if at_line_start {
+            collapsed.trim_start()
+        } else {
+            collapsed.as_str()
+        };
+        if !text.is_empty() {
+            block.spans.push(TextSpan {
+                text: text.to_string(),
+                marks: marks.clone(),
+            });
+        }
+    }
+
+    fn walk(&mut self, node: &Handle, marks: &Marks) {
+        let children = |this: &mut Self, marks: &Marks| {
+            for child in node.children.borrow().iter() {
+                this.walk(&Rc::clone(child), marks);
+            }
+        };
+
+        match &node.data {
+            NodeData::Text { contents } => self.push_text(&contents.borrow(), marks),
+            NodeData::Element { name, attrs, .. } => match name.local {
+                local_name!("head") | local_name!("script") | local_name!("style") => {}
+                local_name!("h1")
+                | local_name!("h2")
+                | local_name!("h3")
+                | local_name!("h4")
+                | local_name!("h5")
+                | local_name!("h6") => {
+                    let level = name.local.as_ref()[1..].parse::<u8>().unwrap_or(1);
+                    self.start_block(Some(BlockKind::Heading(level.min(3))));
+                    children(self, marks);
+                    self.end_block();
+                }
+                local_name!("p")
+                | local_name!("div")
+                | local_name!("blockquote")
+                | local_name!("pre") => {
+                    self.start_block(None);
+                    children(self, marks);
+                    self.end_block();
+                }
+                local_name!("ul") | local_name!("ol") => {
+                    self.end_block();
+                    self.lists.push(if name.local == local_name!("ol") {
+                        BlockKind::NumberedItem
+                    } else {
+                        BlockKind::BulletItem
+                    });
+                    children(self, marks);
+                    self.lists.pop();
+                }
+                local_name!("li") => {
+                    let kind = self.lists.last().copied().unwrap_or(BlockKind::BulletItem);
+                    self.start_block(Some(kind));
+                    children(self, marks);
+                    self.end_block();
+                }
+                local_name!("br") => {
+                    let kind = self.current.as_ref().map(|block| block.kind);
+                    self.end_block();
+                    self.current = Some(Block::new(kind.unwrap_or_default()));
+                }
+                local_name!("b") | local_name!("strong") => children(self, &marks.clone().bold()),
+                local_name!("i") | local_name!("em") => children(self, &marks.clone().italic()),
+                local_name!("u") => children(self, &marks.clone().underline()),
+                local_name!("a") => {
+                    let href = attrs
+                        .borrow()
+                        .iter()
+                        .find(|attr| attr.name.local == local_name!("href"))
+                        .map(|attr| attr.value.to_string());
+                    match href {
+                        Some(href) => children(self, &marks.clone().link(href)),
+                        None => children(self, marks),
+                    }
+                }
+                _ => children(self, marks),
+            },
+            _ => children(self, marks),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc() -> RichTextDocument {
+        RichTextDocument::new(vec![
+            Block::new(BlockKind::Heading(2)).span("Notes", Marks::default()),
+            Block::new(BlockKind::Paragraph)
+                .span("Hello ", Marks::default())
+                .span("bold", Marks::default().bold())
+                .span(" ", Marks::default())
+                .span("link", Marks::default().italic().link("https://a.com"))
+                .span(" and ", Marks::default())
+                .span("under", Marks::default().underline()),
+            Block::new(BlockKind::BulletItem).span("one", Marks::default()),
+            Block::new(BlockKind::BulletItem).span("two", Marks::default()),
+            Block::new(BlockKind::NumberedItem).span("first", Marks::default()),
+            Block::new(BlockKind::NumberedItem).span("1 * 2", Marks::default()),
+        ])
+    }
+
+    #[test]
+    fn test_markdown() {
+        let markdown = doc().to_markdown();
+        assert_eq!(
+            markdown,
+            "## Notes\n\nHello **bold** [_link_](https://a.com) and <u>under</u>\n\n- one\n- two\n\n1. first\n2. 1 \\* 2"
+        );
+        assert_eq!(RichTextDocument::from_markdown(&markdown), doc());
+    }
+
+    #[test]
+    fn test_html() {
+        let html = doc().to_html();
+        assert_eq!(
+            html,
+            "<h2>Notes</h2><p>Hello <strong>bold</strong> <a href=\"https://a.com\"><em>link</em></a> and <u>under</u></p><ul><li>one</li><li>two</li></ul><ol><li>first</li><li>1 * 2</li></ol>"
+        );
+        assert_eq!(RichTextDocument::from_html(&html), doc());
+
+        let doc = RichTextDocument::from_html(
+            "<div>\n  <p>Line 1<br>Line  2</p>\n  <ul><li><p>Item</p></li></ul>\n</div>",
+        );
+        assert_eq!(doc.text(), "Line 1\nLine 2\nItem");
+        assert_eq!(doc.blocks()[2].kind, BlockKind::BulletItem);
+    }
+}
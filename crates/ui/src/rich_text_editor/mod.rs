@@ -0,0 +1,1366 @@
+mod document;
+mod format;
+
+use std::{ops::Range, rc::Rc};
+
+use gpui::{
+    actions, canvas, div, fill, point, prelude::FluentBuilder as _, px, size, App, AppContext as _,
+    Bounds, ClipboardItem, Context, ElementId, ElementInputHandler, Entity, EntityInputHandler,
+    EventEmitter, FocusHandle, Focusable, FontStyle, FontWeight, HighlightStyle,
+    InteractiveElement as _, IntoElement, KeyBinding, MouseButton, MouseDownEvent, MouseMoveEvent,
+    MouseUpEvent, ParentElement, Pixels, Point, Render, RenderOnce, SharedString,
+    StatefulInteractiveElement as _, StyleRefinement, Styled, StyledText, Subscription, TextLayout,
+    UTF16Selection, UnderlineStyle, Window,
+};
+use rust_i18n::t;
+use unicode_segmentation::UnicodeSegmentation as _;
+
+use crate::{
+    actions::{SelectLeft, SelectRight},
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    input::{
+        Backspace, Copy, Cut, Delete, Enter, Input, InputEvent, InputState, MoveDown, MoveEnd,
+        MoveHome, MoveLeft, MoveRight, MoveUp, Paste, Redo, SelectAll, Undo,
+    },
+    menu::{ContextMenuExt as _, PopupMenuItem},
+    spellcheck::{self, SpellChecker},
+    v_flex, ActiveTheme, IconName, Selectable as _, Sizable as _, StyledExt as _,
+};
+
+pub use document::*;
+
+actions!(
+    rich_text_editor,
+    [ToggleBold, ToggleItalic, ToggleUnderline]
+);
+
+const CONTEXT: &str = "RichTextEditor";
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("backspace", Backspace, Some(CONTEXT)),
+        KeyBinding::new("delete", Delete, Some(CONTEXT)),
+        KeyBinding::new("enter", Enter { secondary: false }, Some(CONTEXT)),
+        KeyBinding::new("left", MoveLeft, Some(CONTEXT)),
+        KeyBinding::new("right", MoveRight, Some(CONTEXT)),
+        KeyBinding::new("up", MoveUp, Some(CONTEXT)),
+        KeyBinding::new("down", MoveDown, Some(CONTEXT)),
+        KeyBinding::new("shift-left", SelectLeft, Some(CONTEXT)),
+        KeyBinding::new("shift-right", SelectRight, Some(CONTEXT)),
+        KeyBinding::new("home", MoveHome, Some(CONTEXT)),
+        KeyBinding::new("end", MoveEnd, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-a", SelectAll, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-c", Copy, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-x", Cut, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-v", Paste, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-z", Undo, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-shift-z", Redo, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-b", ToggleBold, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-i", ToggleItalic, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-u", ToggleUnderline, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-a", SelectAll, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-c", Copy, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-x", Cut, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-v", Paste, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-z", Undo, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-y", Redo, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-b", ToggleBold, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-i", ToggleItalic, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-u", ToggleUnderline, Some(CONTEXT)),
+    ]);
+}
+
+const CURSOR_WIDTH: Pixels = px(1.5);
+
+/// Events emitted by the [`RichTextEditorState`].
+#[derive(Debug, Clone)]
+pub enum RichTextEditorEvent {
+    /// The document was changed by editing, undo or redo.
+    Change,
+}
+
+/// A snapshot of the document and the selection for undo and redo.
+struct Snapshot {
+    document: RichTextDocument,
+    anchor: usize,
+    head: usize,
+}
+
+/// State of the [`RichTextEditor`].
+pub struct RichTextEditorState {
+    focus_handle: FocusHandle,
+    document: RichTextDocument,
+    /// The selection is `anchor..head` in the offsets of the document, `head` is the cursor.
+    anchor: usize,
+    head: usize,
+    /// The marks toggled at the cursor without selection, to apply to the next typing.
+    pending_marks: Option<Marks>,
+    ime_marked_range: Option<Range<usize>>,
+    undo_stack: Vec<Snapshot>,
+    redo_stack: Vec<Snapshot>,
+    /// True if the last change was typing, to group the typing into one undo step.
+    typing: bool,
+    selecting: bool,
+    /// The text layouts of the blocks in the last render.
+    layouts: Vec<TextLayout>,
+    link_input: Entity<InputState>,
+    editing_link: bool,
+    /// The spell checker to underline the misspelled words.
+    spell_checker: Option<Rc<dyn SpellChecker>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl EventEmitter<RichTextEditorEvent> for RichTextEditorState {}
+
+impl Focusable for RichTextEditorState {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl RichTextEditorState {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let link_input = cx.new(|cx| {
+            InputState::new(window, cx).placeholder(t!("RichTextEditor.LinkPlaceholder"))
+        });
+        let _subscriptions = vec![cx.subscribe_in(
+            &link_input,
+            window,
+            |this, input, event: &InputEvent, window, cx| {
+                if let InputEvent::PressEnter { .. } = event {
+                    let url = input.read(cx).value();
+                    this.apply_link((!url.is_empty()).then_some(url), window, cx);
+                }
+            },
+        )];
+
+        Self {
+            focus_handle: cx.focus_handle(),
+            document: RichTextDocument::default(),
+            anchor: 0,
+            head: 0,
+            pending_marks: None,
+            ime_marked_range: None,
+            undo_stack: vec![],
+            redo_stack: vec![],
+            typing: false,
+            selecting: false,
+            layouts: vec![],
+            link_input,
+            editing_link: false,
+            spell_checker: None,
+            _subscriptions,
+        }
+    }
+
+    /// Set the initial document.
+    pub fn default_document(mut self, document: RichTextDocument) -> Self {
+        self.document = document;
+        self
+    }
+
+    /// Set the [`SpellChecker`] to underline the misspelled words, and show the suggestions
+    /// in the context menu.
+    pub fn spell_checker(mut self, spell_checker: impl SpellChecker) -> Self {
+        self.spell_checker = Some(Rc::new(spell_checker));
+        self
+    }
+
+    /// Set the [`SpellChecker`], `None` to disable the spell checking.
+    pub fn set_spell_checker(
+        &mut self,
+        spell_checker: Option<Rc<dyn SpellChecker>>,
+        cx: &mut Context<Self>,
+    ) {
+        self.spell_checker = spell_checker;
+        cx.notify();
+    }
+
+    /// Returns the document.
+    pub fn document(&self) -> &RichTextDocument {
+        &self.document
+    }
+
+    /// Replace the document, the undo history is cleared.
+    pub fn set_document(&mut self, document: RichTextDocument, cx: &mut Context<Self>) {
+        self.document = document;
+        self.anchor = 0;
+        self.head = 0;
+        self.pending_marks = None;
+        self.ime_marked_range = None;
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.typing = false;
+        cx.notify();
+    }
+
+    /// Replace the document with the Markdown.
+    pub fn set_markdown(&mut self, markdown: &str, cx: &mut Context<Self>) {
+        self.set_document(RichTextDocument::from_markdown(markdown), cx);
+    }
+
+    /// Replace the document with the HTML.
+    pub fn set_html(&mut self, html: &str, cx: &mut Context<Self>) {
+        self.set_document(RichTextDocument::from_html(html), cx);
+    }
+
+    /// Export the document to Markdown.
+    pub fn to_markdown(&self) -> String {
+        self.document.to_markdown()
+    }
+
+    /// Export the document to HTML.
+    pub fn to_html(&self) -> String {
+        self.document.to_html()
+    }
+
+    /// Returns the selected range in the offsets of the plain text.
+    pub fn selected_range(&self) -> Range<usize> {
+        self.anchor.min(self.head)..self.anchor.max(self.head)
+    }
+
+    /// Returns the marks of the selection, the mark is set if all the selected text has it.
+    ///
+    /// Without selection, returns the marks to continue typing at the cursor.
+    pub fn active_marks(&self) -> Marks {
+        let range = self.selected_range();
+        if range.is_empty() {
+            if let Some(marks) = &self.pending_marks {
+                return marks.clone();
+            }
+        }
+
+        Marks {
+            bold: self.document.all_marks(range.clone(), |m| m.bold),
+            italic: self.document.all_marks(range.clone(), |m| m.italic),
+            underline: self.document.all_marks(range.clone(), |m| m.underline),
+            link: self.document.marks_at(range.start).link,
+        }
+    }
+
+    /// Returns the kind of the block at the cursor.
+    pub fn block_kind(&self) -> BlockKind {
+        let (ix, _) = self.document.position(self.head);
+        self.document.blocks()[ix].kind
+    }
+
+    /// Toggle bold of the selection, or the next typing without selection.
+    pub fn toggle_bold(&mut self, cx: &mut Context<Self>) {
+        self.toggle_mark(|m| m.bold, |m, value| m.bold = value, cx);
+    }
+
+    /// Toggle italic of the selection, or the next typing without selection.
+    pub fn toggle_italic(&mut self, cx: &mut Context<Self>) {
+        self.toggle_mark(|m| m.italic, |m, value| m.italic = value, cx);
+    }
+
+    /// Toggle underline of the selection, or the next typing without selection.
+    pub fn toggle_underline(&mut self, cx: &mut Context<Self>) {
+        self.toggle_mark(|m| m.underline, |m, value| m.underline = value, cx);
+    }
+
+    fn toggle_mark(
+        &mut self,
+        get: impl Fn(&Marks) -> bool,
+        set: impl Fn(&mut Marks, bool),
+        cx: &mut Context<Self>,
+    ) {
+        let range = self.selected_range();
+        if range.is_empty() {
+            let mut marks = self.active_marks();
+            let value = !get(&marks);
+            set(&mut marks, value);
+            self.pending_marks = Some(marks);
+            cx.notify();
+            return;
+        }
+
+        let value = !self.document.all_marks(range.clone(), &get);
+        self.push_history(false);
+        self.document.update_marks(range, |m| set(m, value));
+        cx.emit(RichTextEditorEvent::Change);
+        cx.notify();
+    }
+
+    /// Set the kind of the blocks in the selection.
+    pub fn set_block_kind(&mut self, kind: BlockKind, cx: &mut Context<Self>) {
+        let blocks = self.document.block_range(&self.selected_range());
+        self.push_history(false);
+        self.document.set_block_kind(blocks, kind);
+        cx.emit(RichTextEditorEvent::Change);
+        cx.notify();
+    }
+
+    /// Set the kind of the blocks in the selection, or back to paragraph if they are already the kind.
+    pub fn toggle_block_kind(&mut self, kind: BlockKind, cx: &mut Context<Self>) {
+        let blocks = self.document.block_range(&self.selected_range());
+        if self.document.blocks()[blocks]
+            .iter()
+            .all(|b| b.kind == kind)
+        {
+            self.set_block_kind(BlockKind::Paragraph, cx);
+        } else {
+            self.set_block_kind(kind, cx);
+        }
+    }
+
+    /// Set or remove the link of the selection.
+    ///
+    /// Without selection, the url is inserted as a link at the cursor.
+    pub fn set_link(&mut self, url: Option<SharedString>, cx: &mut Context<Self>) {
+        let range = self.selected_range();
+        if range.is_empty() {
+            if let Some(url) = url {
+                let marks = self.active_marks().link(url.clone());
+                self.push_history(false);
+                let end = self.document.replace(range, &url, &marks);
+                self.anchor = end;
+                self.head = end;
+                self.pending_marks = Some(Marks {
+                    link: None,
+                    ..marks
+                });
+                cx.emit(RichTextEditorEvent::Change);
+                cx.notify();
+            }
+            return;
+        }
+
+        self.push_history(false);
+        self.document.update_marks(range, |m| m.link = url.clone());
+        cx.emit(RichTextEditorEvent::Change);
+        cx.notify();
+    }
+
+    /// Show the link bar to edit the link of the selection.
+    fn edit_link(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let url = self.active_marks().link.unwrap_or_default();
+        self.editing_link = true;
+        self.link_input.update(cx, |input, cx| {
+            input.set_value(url, window, cx);
+            input.focus(window, cx);
+        });
+        cx.notify();
+    }
+
+    fn apply_link(
+        &mut self,
+        url: Option<SharedString>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_link(url, cx);
+        self.editing_link = false;
+        self.focus_handle.focus(window);
+        cx.notify();
+    }
+
+    fn push_history(&mut self, typing: bool) {
+        if !(typing && self.typing) {
+            self.undo_stack.push(Snapshot {
+                document: self.document.clone(),
+                anchor: self.anchor,
+                head: self.head,
+            });
+        }
+        self.typing = typing;
+        self.redo_stack.clear();
+    }
+
+    /// Undo the last change.
+    pub fn undo(&mut self, cx: &mut Context<Self>) {
+        let Some(snapshot) = self.undo_stack.pop() else {
+            return;
+        };
+
+        let current = self.restore(snapshot);
+        self.redo_stack.push(current);
+        cx.emit(RichTextEditorEvent::Change);
+        cx.notify();
+    }
+
+    /// Redo the last undone change.
+    pub fn redo(&mut self, cx: &mut Context<Self>) {
+        let Some(snapshot) = self.redo_stack.pop() else {
+            return;
+        };
+
+        let current = self.restore(snapshot);
+        self.undo_stack.push(current);
+        cx.emit(RichTextEditorEvent::Change);
+        cx.notify();
+    }
+
+    /// Restore the snapshot, returns the current one.
+    fn restore(&mut self, snapshot: Snapshot) -> Snapshot {
+        self.typing = false;
+        self.pending_marks = None;
+        self.ime_marked_range = None;
+        Snapshot {
+            document: std::mem::replace(&mut self.document, snapshot.document),
+            anchor: std::mem::replace(&mut self.anchor, snapshot.anchor),
+            head: std::mem::replace(&mut self.head, snapshot.head),
+        }
+    }
+
+    /// Replace the range with the text, in the pending marks or the marks before the range.
+    fn replace(&mut self, range: Range<usize>, text: &str, typing: bool, cx: &mut Context<Self>) {
+        let marks = self
+            .pending_marks
+            .clone()
+            .unwrap_or_else(|| self.document.marks_at(range.start));
+        self.push_history(typing);
+        let end = self.document.replace(range, text, &marks);
+        self.anchor = end;
+        self.head = end;
+        cx.emit(RichTextEditorEvent::Change);
+        cx.notify();
+    }
+
+    fn move_to(&mut self, offset: usize, select: bool, cx: &mut Context<Self>) {
+        self.head = offset.min(self.document.len());
+        if !select {
+            self.anchor = self.head;
+        }
+        self.pending_marks = None;
+        self.typing = false;
+        cx.notify();
+    }
+
+    fn previous_boundary(&self, offset: usize) -> usize {
+        let (ix, block_offset) = self.document.position(offset);
+        if block_offset == 0 {
+            return offset.saturating_sub(1);
+        }
+
+        let text = self.document.blocks()[ix].text();
+        let prev = text[..block_offset]
+            .grapheme_indices(true)
+            .next_back()
+            .map_or(0, |(i, _)| i);
+        offset - block_offset + prev
+    }
+
+    fn next_boundary(&self, offset: usize) -> usize {
+        let (ix, block_offset) = self.document.position(offset);
+        let text = self.document.blocks()[ix].text();
+        match text[block_offset..].graphemes(true).next() {
+            Some(grapheme) => offset + grapheme.len(),
+            None => (offset + 1).min(self.document.len()),
+        }
+    }
+
+    /// Returns the offset at the position in the window, from the layouts of the last render.
+    fn offset_for_position(&self, position: Point<Pixels>) -> usize {
+        let Some(last) = self.layouts.len().checked_sub(1) else {
+            return 0;
+        };
+
+        for (ix, layout) in self.layouts.iter().enumerate() {
+            let bounds = layout.bounds();
+            if position.y >= bounds.bottom() && ix < last {
+                continue;
+            }
+
+            let y = position
+                .y
+                .clamp(bounds.top(), (bounds.bottom() - px(1.)).max(bounds.top()));
+            let offset = layout
+                .index_for_position(point(position.x, y))
+                .unwrap_or_else(|ix| ix);
+            return (self.document.block_start(ix) + offset).min(self.document.len());
+        }
+        0
+    }
+
+    /// Returns the origin of the cursor at the offset in the window, and the line height.
+    fn position_for_offset(&self, offset: usize) -> Option<(Point<Pixels>, Pixels)> {
+        let (ix, block_offset) = self.document.position(offset);
+        let layout = self.layouts.get(ix)?;
+        let position = layout.position_for_index(block_offset)?;
+        Some((position, layout.line_height()))
+    }
+
+    /// Returns the bounds of the cursor in the window from the last layout, `None` if not laid out.
+    ///
+    /// This is reported to the OS to position the IME candidate window.
+    pub fn cursor_bounds(&self) -> Option<Bounds<Pixels>> {
+        let (position, line_height) = self.position_for_offset(self.head)?;
+        Some(Bounds::new(position, size(px(1.), line_height)))
+    }
+
+    /// Returns the offset on the line above (`-1.`) or below (`1.`) the offset.
+    fn vertical_offset(&self, offset: usize, direction: f32) -> Option<usize> {
+        let (position, line_height) = self.position_for_offset(offset)?;
+        let y = position.y + line_height * (0.5 + direction);
+        Some(self.offset_for_position(point(position.x, y)))
+    }
+
+    fn offset_to_utf16(text: &str, offset: usize) -> usize {
+        text[..offset.min(text.len())].encode_utf16().count()
+    }
+
+    fn offset_from_utf16(text: &str, offset: usize) -> usize {
+        let mut utf16 = 0;
+        for (ix, c) in text.char_indices() {
+            if utf16 >= offset {
+                return ix;
+            }
+            utf16 += c.len_utf16();
+        }
+        text.len()
+    }
+
+    fn range_to_utf16(text: &str, range: &Range<usize>) -> Range<usize> {
+        Self::offset_to_utf16(text, range.start)..Self::offset_to_utf16(text, range.end)
+    }
+
+    fn range_from_utf16(text: &str, range: &Range<usize>) -> Range<usize> {
+        Self::offset_from_utf16(text, range.start)..Self::offset_from_utf16(text, range.end)
+    }
+
+    fn on_action_backspace(&mut self, _: &Backspace, _: &mut Window, cx: &mut Context<Self>) {
+        let range = self.selected_range();
+        if range.is_empty() {
+            // Convert the list item or heading to paragraph at the start of the block.
+            let (ix, offset) = self.document.position(range.start);
+            if offset == 0 && self.document.blocks()[ix].kind != BlockKind::Paragraph {
+                self.set_block_kind(BlockKind::Paragraph, cx);
+                return;
+            }
+            if range.start == 0 {
+                return;
+            }
+        }
+
+        let start = if range.is_empty() {
+            self.previous_boundary(range.start)
+        } else {
+            range.start
+        };
+        self.replace(start..range.end, "", false, cx);
+    }
+
+    fn on_action_delete(&mut self, _: &Delete, _: &mut Window, cx: &mut Context<Self>) {
+        let range = self.selected_range();
+        let end = if range.is_empty() {
+            self.next_boundary(range.end)
+        } else {
+            range.end
+        };
+        if end > range.start {
+            self.replace(range.start..end, "", false, cx);
+        }
+    }
+
+    fn on_action_enter(&mut self, _: &Enter, _: &mut Window, cx: &mut Context<Self>) {
+        let range = self.selected_range();
+        let (ix, _) = self.document.position(range.start);
+        let block = &self.document.blocks()[ix];
+        // Exit the list on an empty list item.
+        if range.is_empty() && block.kind.is_list_item() && block.is_empty() {
+            self.set_block_kind(BlockKind::Paragraph, cx);
+            return;
+        }
+
+        self.replace(range, "\n", false, cx);
+    }
+
+    fn on_action_move_left(&mut self, _: &MoveLeft, _: &mut Window, cx: &mut Context<Self>) {
+        let range = self.selected_range();
+        let offset = if range.is_empty() {
+            self.previous_boundary(range.start)
+        } else {
+            range.start
+        };
+        self.move_to(offset, false, cx);
+    }
+
+    fn on_action_move_right(&mut self, _: &MoveRight, _: &mut Window, cx: &mut Context<Self>) {
+        let range = self.selected_range();
+        let offset = if range.is_empty() {
+            self.next_boundary(range.end)
+        } else {
+            range.end
+        };
+        self.move_to(offset, false, cx);
+    }
+
+    fn on_action_select_left(&mut self, _: &SelectLeft, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_to(self.previous_boundary(self.head), true, cx);
+    }
+
+    fn on_action_select_right(&mut self, _: &SelectRight, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_to(self.next_boundary(self.head), true, cx);
+    }
+
+    fn on_action_move_up(&mut self, _: &MoveUp, _: &mut Window, cx: &mut Context<Self>) {
+        let offset = self.vertical_offset(self.head, -1.).unwrap_or(0);
+        self.move_to(offset, false, cx);
+    }
+
+    fn on_action_move_down(&mut self, _: &MoveDown, _: &mut Window, cx: &mut Context<Self>) {
+        let offset = self
+            .vertical_offset(self.head, 1.)
+            .unwrap_or(self.document.len());
+        self.move_to(offset, false, cx);
+    }
+
+    fn on_action_move_home(&mut self, _: &MoveHome, _: &mut Window, cx: &mut Context<Self>) {
+        let (ix, _) = self.document.position(self.head);
+        self.move_to(self.document.block_start(ix), false, cx);
+    }
+
+    fn on_action_move_end(&mut self, _: &MoveEnd, _: &mut Window, cx: &mut Context<Self>) {
+        let (ix, _) = self.document.position(self.head);
+        let end = self.document.block_start(ix) + self.document.blocks()[ix].len();
+        self.move_to(end, false, cx);
+    }
+
+    fn on_action_select_all(&mut self, _: &SelectAll, _: &mut Window, cx: &mut Context<Self>) {
+        self.anchor = 0;
+        self.move_to(self.document.len(), true, cx);
+    }
+
+    fn on_action_copy(&mut self, _: &Copy, _: &mut Window, cx: &mut Context<Self>) {
+        let range = self.selected_range();
+        if !range.is_empty() {
+            let text = self.document.text()[range].to_string();
+            cx.write_to_clipboard(ClipboardItem::new_string(text));
+        }
+    }
+
+    fn on_action_cut(&mut self, _: &Cut, window: &mut Window, cx: &mut Context<Self>) {
+        self.on_action_copy(&Copy, window, cx);
+        let range = self.selected_range();
+        if !range.is_empty() {
+            self.replace(range, "", false, cx);
+        }
+    }
+
+    fn on_action_paste(&mut self, _: &Paste, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(text) = cx.read_from_clipboard().and_then(|item| item.text()) {
+            let text = text.replace("\r\n", "\n");
+            self.replace(self.selected_range(), &text, false, cx);
+        }
+    }
+
+    fn on_action_undo(&mut self, _: &Undo, _: &mut Window, cx: &mut Context<Self>) {
+        self.undo(cx);
+    }
+
+    fn on_action_redo(&mut self, _: &Redo, _: &mut Window, cx: &mut Context<Self>) {
+        self.redo(cx);
+    }
+
+    fn on_action_toggle_bold(&mut self, _: &ToggleBold, _: &mut Window, cx: &mut Context<Self>) {
+        self.toggle_bold(cx);
+    }
+
+    fn on_action_toggle_italic(
+        &mut self,
+        _: &ToggleItalic,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.toggle_italic(cx);
+    }
+
+    fn on_action_toggle_underline(
+        &mut self,
+        _: &ToggleUnderline,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.toggle_underline(cx);
+    }
+
+    fn on_mouse_down(
+        &mut self,
+        event: &MouseDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.focus_handle.focus(window);
+        self.editing_link = false;
+        let offset = self.offset_for_position(event.position);
+
+        // Open the link with the platform modifier.
+        if event.modifiers.secondary() {
+            if let Some(link) = self.link_at(offset) {
+                cx.open_url(&link);
+                return;
+            }
+        }
+
+        self.selecting = true;
+        self.move_to(offset, event.modifiers.shift, cx);
+    }
+
+    fn on_mouse_move(&mut self, event: &MouseMoveEvent, _: &mut Window, cx: &mut Context<Self>) {
+        if self.selecting && event.pressed_button == Some(MouseButton::Left) {
+            self.move_to(self.offset_for_position(event.position), true, cx);
+        }
+    }
+
+    fn on_mouse_up(&mut self, _: &MouseUpEvent, _: &mut Window, _: &mut Context<Self>) {
+        self.selecting = false;
+    }
+
+    /// Returns the link of the text at the offset.
+    fn link_at(&self, offset: usize) -> Option<SharedString> {
+        let (ix, offset) = self.document.position(offset);
+        self.document.blocks()[ix]
+            .spans_in(offset..offset + 1)
+            .next()
+            .and_then(|span| span.marks.link.clone())
+    }
+
+    /// Returns the range, the word and the suggestions of the misspelled word at the offset.
+    fn misspelled_word_at(
+        &self,
+        offset: usize,
+    ) -> Option<(Range<usize>, String, Vec<SharedString>)> {
+        let spell_checker = self.spell_checker.as_ref()?;
+        let (ix, block_offset) = self.document.position(offset);
+        let text = self.document.blocks().get(ix)?.text();
+        let range = spellcheck::word_at(&text, block_offset)?;
+        let word = text[range.clone()].to_string();
+        if spell_checker.check(&word) {
+            return None;
+        }
+
+        let block_start = offset - block_offset;
+        let suggestions = spell_checker.suggestions(&word);
+        Some((
+            block_start + range.start..block_start + range.end,
+            word,
+            suggestions,
+        ))
+    }
+
+    /// Returns the highlights of the block, with the selection in the block range.
+    fn highlights(
+        block: &Block,
+        selection: Range<usize>,
+        cx: &App,
+    ) -> Vec<(Range<usize>, HighlightStyle)> {
+        let mut highlights = vec![];
+        let mut start = 0;
+        for span in block.spans.iter() {
+            let end = start + span.text.len();
+            let mut style = HighlightStyle::default();
+            if span.marks.bold {
+                style.font_weight = Some(FontWeight::BOLD);
+            }
+            if span.marks.italic {
+                style.font_style = Some(FontStyle::Italic);
+            }
+            if span.marks.link.is_some() {
+                style.color = Some(cx.theme().link);
+            }
+            if span.marks.underline || span.marks.link.is_some() {
+                style.underline = Some(UnderlineStyle {
+                    thickness: px(1.),
+                    color: style.color,
+                    wavy: false,
+                });
+            }
+
+            // Split the span at the selection boundaries.
+            let selected = selection.start.max(start)..selection.end.min(end);
+            let ranges = if selected.is_empty() {
+                vec![(start..end, false)]
+            } else {
+                vec![
+                    (start..selected.start, false),
+                    (selected.clone(), true),
+                    (selected.end..end, false),
+                ]
+            };
+            for (range, selected) in ranges {
+                if range.is_empty() {
+                    continue;
+                }
+                let mut style = style;
+                if selected {
+                    style.background_color = Some(cx.theme().selection);
+                }
+                highlights.push((range, style));
+            }
+            start = end;
+        }
+        highlights
+    }
+}
+
+impl EntityInputHandler for RichTextEditorState {
+    fn text_for_range(
+        &mut self,
+        range_utf16: Range<usize>,
+        adjusted_range: &mut Option<Range<usize>>,
+        _: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Option<String> {
+        let text = self.document.text();
+        let range = Self::range_from_utf16(&text, &range_utf16);
+        adjusted_range.replace(Self::range_to_utf16(&text, &range));
+        Some(text[range].to_string())
+    }
+
+    fn selected_text_range(
+        &mut self,
+        _: bool,
+        _: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Option<UTF16Selection> {
+        let text = self.document.text();
+        Some(UTF16Selection {
+            range: Self::range_to_utf16(&text, &self.selected_range()),
+            reversed: self.head < self.anchor,
+        })
+    }
+
+    fn marked_text_range(&self, _: &mut Window, _: &mut Context<Self>) -> Option<Range<usize>> {
+        let text = self.document.text();
+        self.ime_marked_range
+            .as_ref()
+            .map(|range| Self::range_to_utf16(&text, range))
+    }
+
+    fn unmark_text(&mut self, _: &mut Window, _: &mut Context<Self>) {
+        self.ime_marked_range = None;
+    }
+
+    fn replace_text_in_range(
+        &mut self,
+        range_utf16: Option<Range<usize>>,
+        new_text: &str,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let text = self.document.text();
+        let range = range_utf16
+            .map(|range| Self::range_from_utf16(&text, &range))
+            .or(self.ime_marked_range.take())
+            .unwrap_or(self.selected_range());
+        self.replace(range, new_text, true, cx);
+    }
+
+    fn replace_and_mark_text_in_range(
+        &mut self,
+        range_utf16: Option<Range<usize>>,
+        new_text: &str,
+        new_selected_range_utf16: Option<Range<usize>>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let text = self.document.text();
+        let range = range_utf16
+            .map(|range| Self::range_from_utf16(&text, &range))
+            .or(self.ime_marked_range.clone())
+            .unwrap_or(self.selected_range());
+        let pending_marks = self.pending_marks.clone();
+        self.replace(range.clone(), new_text, true, cx);
+        // Keep the pending marks until the composition is finished.
+        self.pending_marks = pending_marks;
+
+        if new_text.is_empty() {
+            self.ime_marked_range = None;
+        } else {
+            self.ime_marked_range = Some(range.start..range.start + new_text.len());
+            if let Some(selected) = new_selected_range_utf16 {
+                let selected = Self::range_from_utf16(new_text, &selected);
+                self.anchor = range.start + selected.start;
+                self.head = range.start + selected.end;
+            }
+        }
+    }
+
+    fn bounds_for_range(
+        &mut self,
+        range_utf16: Range<usize>,
+        _: Bounds<Pixels>,
+        _: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Option<Bounds<Pixels>> {
+        let text = self.document.text();
+        let range = Self::range_from_utf16(&text, &range_utf16);
+        let Some((start, line_height)) = self.position_for_offset(range.start) else {
+            return self.cursor_bounds();
+        };
+        let end = self
+            .position_for_offset(range.end)
+            .map_or(start, |(end, _)| end);
+        Some(Bounds::from_corners(
+            start,
+            point(end.x.max(start.x), start.y + line_height),
+        ))
+    }
+
+    fn character_index_for_point(
+        &mut self,
+        point: Point<Pixels>,
+        _: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Option<usize> {
+        let offset = self.offset_for_position(point);
+        Some(Self::offset_to_utf16(&self.document.text(), offset))
+    }
+}
+
+impl Render for RichTextEditorState {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let state = cx.entity();
+        let focus_handle = self.focus_handle.clone();
+        let focused = focus_handle.is_focused(window);
+        let selection = self.selected_range();
+        let cursor = self.head;
+
+        let mut layouts = vec![];
+        let mut number = 0;
+        let mut prev_kind = None;
+        let mut block_start = 0;
+        let blocks = self
+            .document
+            .blocks()
+            .iter()
+            .enumerate()
+            .map(|(ix, block)| {
+                number = match (prev_kind, block.kind) {
+                    (Some(BlockKind::NumberedItem), BlockKind::NumberedItem) => number + 1,
+                    _ => 1,
+                };
+                prev_kind = Some(block.kind);
+
+                let block_selection = selection.start.saturating_sub(block_start)
+                    ..selection.end.saturating_sub(block_start).min(block.len());
+                block_start += block.len() + 1;
+                let block_text = block.text();
+                let highlights = Self::highlights(block, block_selection, cx);
+                let highlights = match &self.spell_checker {
+                    Some(spell_checker) => {
+                        let underline = HighlightStyle {
+                            underline: Some(UnderlineStyle {
+                                thickness: px(1.),
+                                color: Some(cx.theme().danger),
+                                wavy: true,
+                            }),
+                            ..Default::default()
+                        };
+                        let misspelled = spell_checker
+                            .misspelled(&block_text)
+                            .into_iter()
+                            .map(|range| (range, underline));
+                        gpui::combine_highlights(highlights, misspelled).collect()
+                    }
+                    None => highlights,
+                };
+                let text = StyledText::new(block_text).with_highlights(highlights);
+                layouts.push(text.layout().clone());
+
+                let prefix = match block.kind {
+                    BlockKind::BulletItem => Some("•".to_string()),
+                    BlockKind::NumberedItem => Some(format!("{}.", number)),
+                    _ => None,
+                };
+
+                h_flex()
+                    .id(ix)
+                    .items_start()
+                    .w_full()
+                    .map(|this| match block.kind {
+                        BlockKind::Heading(1) => this.mt_2().text_2xl().font_semibold(),
+                        BlockKind::Heading(2) => this.mt_2().text_xl().font_semibold(),
+                        BlockKind::Heading(_) => this.mt_1().text_lg().font_semibold(),
+                        _ => this,
+                    })
+                    .when_some(prefix, |this, prefix| {
+                        this.child(
+                            div()
+                                .w_6()
+                                .flex_shrink_0()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(prefix),
+                        )
+                    })
+                    .child(div().flex_1().min_w_0().child(text))
+            })
+            .collect::<Vec<_>>();
+        self.layouts = layouts.clone();
+
+        v_flex()
+            .id("rich-text-editor")
+            .key_context(CONTEXT)
+            .track_focus(&focus_handle)
+            .on_action(cx.listener(Self::on_action_backspace))
+            .on_action(cx.listener(Self::on_action_delete))
+            .on_action(cx.listener(Self::on_action_enter))
+            .on_action(cx.listener(Self::on_action_move_left))
+            .on_action(cx.listener(Self::on_action_move_right))
+            .on_action(cx.listener(Self::on_action_move_up))
+            .on_action(cx.listener(Self::on_action_move_down))
+            .on_action(cx.listener(Self::on_action_select_left))
+            .on_action(cx.listener(Self::on_action_select_right))
+            .on_action(cx.listener(Self::on_action_move_home))
+            .on_action(cx.listener(Self::on_action_move_end))
+            .on_action(cx.listener(Self::on_action_select_all))
+            .on_action(cx.listener(Self::on_action_copy))
+            .on_action(cx.listener(Self::on_action_cut))
+            .on_action(cx.listener(Self::on_action_paste))
+            .on_action(cx.listener(Self::on_action_undo))
+            .on_action(cx.listener(Self::on_action_redo))
+            .on_action(cx.listener(Self::on_action_toggle_bold))
+            .on_action(cx.listener(Self::on_action_toggle_italic))
+            .on_action(cx.listener(Self::on_action_toggle_underline))
+            .relative()
+            .size_full()
+            .gap_1()
+            .p_3()
+            .cursor_text()
+            .overflow_y_scroll()
+            .on_mouse_down(MouseButton::Left, cx.listener(Self::on_mouse_down))
+            .on_mouse_move(cx.listener(Self::on_mouse_move))
+            .on_mouse_up(MouseButton::Left, cx.listener(Self::on_mouse_up))
+            .on_mouse_up_out(MouseButton::Left, cx.listener(Self::on_mouse_up))
+            .children(blocks)
+            .when(self.spell_checker.is_some(), |this| {
+                let state = state.clone();
+                let focus_handle = focus_handle.clone();
+                this.context_menu(move |mut menu, window, cx| {
+                    let offset = state.read(cx).offset_for_position(window.mouse_position());
+                    if let Some((range, word, suggestions)) =
+                        state.read(cx).misspelled_word_at(offset)
+                    {
+                        if suggestions.is_empty() {
+                            menu = menu.item(
+                                PopupMenuItem::new(t!("Input.No Suggestions")).disabled(true),
+                            );
+                        }
+                        for suggestion in suggestions {
+                            let state = state.clone();
+                            let range = range.clone();
+                            menu = menu.item(PopupMenuItem::new(suggestion.clone()).on_click(
+                                move |_, _, cx| {
+                                    state.update(cx, |state, cx| {
+                                        state.replace(range.clone(), &suggestion, false, cx);
+                                    });
+                                },
+                            ));
+                        }
+                        let state = state.clone();
+                        menu = menu
+                            .item(PopupMenuItem::new(t!("Input.Add to Dictionary")).on_click(
+                                move |_, _, cx| {
+                                    state.update(cx, |state, cx| {
+                                        if let Some(spell_checker) = &state.spell_checker {
+                                            spell_checker.add_word(&word);
+                                        }
+                                        cx.notify();
+                                    });
+                                },
+                            ))
+                            .separator();
+                    }
+
+                    menu.action_context(focus_handle.clone())
+                        .menu(t!("Input.Cut"), Box::new(Cut))
+                        .menu(t!("Input.Copy"), Box::new(Copy))
+                        .menu(t!("Input.Paste"), Box::new(Paste))
+                        .separator()
+                        .menu(t!("Input.Select All"), Box::new(SelectAll))
+                })
+            })
+            .child(
+                canvas(
+                    |_, _, _| {},
+                    move |bounds, _, window, cx| {
+                        window.handle_input(
+                            &focus_handle,
+                            ElementInputHandler::new(bounds, state.clone()),
+                            cx,
+                        );
+                        if !focused {
+                            return;
+                        }
+
+                        // Paint the cursor after the text layouts are prepainted.
+                        let (ix, offset) = state.read(cx).document.position(cursor);
+                        let Some(layout) = layouts.get(ix) else {
+                            return;
+                        };
+                        if let Some(position) = layout.position_for_index(offset) {
+                            window.paint_quad(fill(
+                                Bounds::new(position, size(CURSOR_WIDTH, layout.line_height())),
+                                cx.theme().caret,
+                            ));
+                        }
+                    },
+                )
+                .absolute()
+                .size_full(),
+            )
+    }
+}
+
+/// A rich text editor with bold, italic, underline, links, headings and lists.
+///
+/// The document can be imported and exported as Markdown or HTML.
+///
+/// ```ignore
+/// let state = cx.new(|cx| RichTextEditorState::new(window, cx));
+/// state.update(cx, |state, cx| state.set_markdown("# Hello\n\n**World**", cx));
+///
+/// RichTextEditor::new(&state).h(px(320.))
+///
+/// let html = state.read(cx).to_html();
+/// ```
+#[derive(IntoElement)]
+pub struct RichTextEditor {
+    id: ElementId,
+    state: Entity<RichTextEditorState>,
+    toolbar: bool,
+    placeholder: Option<SharedString>,
+    style: StyleRefinement,
+}
+
+impl RichTextEditor {
+    /// Create a new rich text editor with the given state.
+    pub fn new(state: &Entity<RichTextEditorState>) -> Self {
+        Self {
+            id: ElementId::Name(format!("rich-text-editor-{}", state.entity_id()).into()),
+            state: state.clone(),
+            toolbar: true,
+            placeholder: None,
+            style: StyleRefinement::default(),
+        }
+    }
+
+    /// Set to show the formatting toolbar, default: true
+    pub fn toolbar(mut self, toolbar: bool) -> Self {
+        self.toolbar = toolbar;
+        self
+    }
+
+    /// Set the placeholder shown when the document is empty.
+    pub fn placeholder(mut self, placeholder: impl Into<SharedString>) -> Self {
+        self.placeholder = Some(placeholder.into());
+        self
+    }
+
+    fn render_toolbar(&self, cx: &mut App) -> impl IntoElement {
+        let state = self.state.read(cx);
+        let marks = state.active_marks();
+        let kind = state.block_kind();
+
+        let mark_button =
+            |id: &'static str,
+             icon: IconName,
+             tooltip: SharedString,
+             action: &dyn gpui::Action,
+             selected: bool,
+             toggle: fn(&mut RichTextEditorState, &mut Context<RichTextEditorState>)| {
+                let state = self.state.clone();
+                Button::new(id)
+                    .icon(icon)
+                    .ghost()
+                    .xsmall()
+                    .selected(selected)
+                    .tooltip_with_action(tooltip, action, Some(CONTEXT))
+                    .on_click(move |_, window, cx| {
+                        state.update(cx, |state, cx| {
+                            toggle(state, cx);
+                            state.focus_handle.focus(window);
+                        })
+                    })
+            };
+        let block_button = |id: &'static str, icon: IconName, tooltip: SharedString, block_kind| {
+            let state = self.state.clone();
+            Button::new(id)
+                .icon(icon)
+                .ghost()
+                .xsmall()
+                .selected(kind == block_kind)
+                .tooltip(tooltip)
+                .on_click(move |_, window, cx| {
+                    state.update(cx, |state, cx| {
+                        state.toggle_block_kind(block_kind, cx);
+                        state.focus_handle.focus(window);
+                    })
+                })
+        };
+
+        h_flex()
+            .gap_0p5()
+            .px_1()
+            .py_1()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(mark_button(
+                "bold",
+                IconName::Bold,
+                t!("RichTextEditor.Bold").into(),
+                &ToggleBold,
+                marks.bold,
+                RichTextEditorState::toggle_bold,
+            ))
+            .child(mark_button(
+                "italic",
+                IconName::Italic,
+                t!("RichTextEditor.Italic").into(),
+                &ToggleItalic,
+                marks.italic,
+                RichTextEditorState::toggle_italic,
+            ))
+            .child(mark_button(
+                "underline",
+                IconName::Underline,
+                t!("RichTextEditor.Underline").into(),
+                &ToggleUnderline,
+                marks.underline,
+                RichTextEditorState::toggle_underline,
+            ))
+            .child(div().w_px().h_4().mx_1().bg(cx.theme().border))
+            .child(block_button(
+                "heading-1",
+                IconName::Heading1,
+                t!("RichTextEditor.Heading1").into(),
+                BlockKind::Heading(1),
+            ))
+            .child(block_button(
+                "heading-2",
+                IconName::Heading2,
+                t!("RichTextEditor.Heading2").into(),
+                BlockKind::Heading(2),
+            ))
+            .child(block_button(
+                "bullet-list",
+                IconName::List,
+                t!("RichTextEditor.BulletList").into(),
+                BlockKind::BulletItem,
+            ))
+            .child(block_button(
+                "numbered-list",
+                IconName::ListOrdered,
+                t!("RichTextEditor.NumberedList").into(),
+                BlockKind::NumberedItem,
+            ))
+            .child(div().w_px().h_4().mx_1().bg(cx.theme().border))
+            .child({
+                let state = self.state.clone();
+                Button::new("link")
+                    .icon(IconName::Link)
+                    .ghost()
+                    .xsmall()
+                    .selected(marks.link.is_some())
+                    .tooltip(t!("RichTextEditor.Link"))
+                    .on_click(move |_, window, cx| {
+                        state.update(cx, |state, cx| state.edit_link(window, cx))
+                    })
+            })
+    }
+
+    fn render_link_bar(&self, cx: &mut App) -> impl IntoElement {
+        let state = self.state.read(cx);
+        let has_link = state.active_marks().link.is_some();
+        let link_input = state.link_input.clone();
+
+        h_flex()
+            .gap_1()
+            .p_1()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(div().flex_1().child(Input::new(&link_input).xsmall()))
+            .child({
+                let state = self.state.clone();
+                Button::new("apply-link")
+                    .label(t!("RichTextEditor.Apply"))
+                    .primary()
+                    .xsmall()
+                    .on_click(move |_, window, cx| {
+                        state.update(cx, |state, cx| {
+                            let url = state.link_input.read(cx).value();
+                            state.apply_link((!url.is_empty()).then_some(url), window, cx);
+                        })
+                    })
+            })
+            .when(has_link, |this| {
+                let state = self.state.clone();
+                this.child(
+                    Button::new("remove-link")
+                        .label(t!("RichTextEditor.RemoveLink"))
+                        .ghost()
+                        .xsmall()
+                        .on_click(move |_, window, cx| {
+                            state.update(cx, |state, cx| state.apply_link(None, window, cx))
+                        }),
+                )
+            })
+    }
+}
+
+impl Styled for RichTextEditor {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for RichTextEditor {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let state = self.state.read(cx);
+        let focused = state.focus_handle.contains_focused(window, cx);
+        let is_empty = state.document.is_empty() && state.ime_marked_range.is_none();
+        let editing_link = state.editing_link;
+
+        v_flex()
+            .id(self.id.clone())
+            .size_full()
+            .bg(cx.theme().background)
+            .border_1()
+            .border_color(cx.theme().input)
+            .rounded(cx.theme().radius)
+            .overflow_hidden()
+            .when(focused, |this| this.focused_border(cx))
+            .when(self.toolbar, |this| this.child(self.render_toolbar(cx)))
+            .when(editing_link, |this| this.child(self.render_link_bar(cx)))
+            .child(
+                div()
+                    .relative()
+                    .flex_1()
+                    .min_h_0()
+                    .when_some(
+                        self.placeholder.clone().filter(|_| is_empty),
+                        |this, placeholder| {
+                            this.child(
+                                div()
+                                    .absolute()
+                                    .top_3()
+                                    .left_3()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(placeholder),
+                            )
+                        },
+                    )
+                    .child(self.state.clone()),
+            )
+            .refine_style(&self.style)
+    }
+}
@@ -1,14 +1,22 @@
 use crate::{
+    backdrop::{window_background_appearance, Material},
+    dpi::ScaleChanged,
     drawer::Drawer,
+    focus_region::{FocusNextRegion, FocusPrevRegion, FocusRegions},
     input::InputState,
     modal::Modal,
     notification::{Notification, NotificationList},
-    window_border, ActiveTheme, Placement,
+    sidebar::{
+        CloseSidebarOverlay, SidebarToggles, ToggleLeftSidebar, ToggleRightSidebar, ToggleSidebar,
+    },
+    window_border,
+    window_chrome::WindowChrome,
+    ActiveTheme, Placement, Side, StyledExt as _,
 };
 use gpui::{
     actions, canvas, div, prelude::FluentBuilder as _, AnyView, App, AppContext, Context,
-    DefiniteLength, Entity, FocusHandle, InteractiveElement, IntoElement, KeyBinding,
-    ParentElement as _, Render, Styled, Window,
+    DefiniteLength, Entity, EventEmitter, FocusHandle, InteractiveElement, IntoElement, KeyBinding,
+    ParentElement as _, Render, Styled, Subscription, Window, WindowBackgroundAppearance,
 };
 use std::{any::TypeId, rc::Rc};
 
@@ -219,7 +227,12 @@ pub struct Root {
     pub(super) focused_input: Option<Entity<InputState>>,
     pub notification: Entity<NotificationList>,
     drawer_size: Option<DefiniteLength>,
+    pub(crate) regions: FocusRegions,
+    pub(crate) sidebars: SidebarToggles,
+    background_appearance: WindowBackgroundAppearance,
+    scale_factor: f32,
     view: AnyView,
+    _subscriptions: Vec<Subscription>,
 }
 
 #[derive(Clone)]
@@ -237,6 +250,10 @@ pub(crate) struct ActiveModal {
 
 impl Root {
     pub fn new(view: AnyView, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let _subscriptions = vec![cx.observe_window_bounds(window, |this, window, cx| {
+            this.check_scale_factor(window, cx);
+        })];
+
         Self {
             previous_focus_handle: None,
             active_drawer: None,
@@ -244,10 +261,30 @@ impl Root {
             focused_input: None,
             notification: cx.new(|cx| NotificationList::new(window, cx)),
             drawer_size: None,
+            regions: FocusRegions::default(),
+            sidebars: SidebarToggles::default(),
+            background_appearance: WindowBackgroundAppearance::Opaque,
+            scale_factor: window.scale_factor(),
             view,
+            _subscriptions,
         }
     }
 
+    /// Emit the [`ScaleChanged`] if the scale factor of the window is changed.
+    fn check_scale_factor(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let scale_factor = window.scale_factor();
+        if scale_factor == self.scale_factor {
+            return;
+        }
+
+        let old = std::mem::replace(&mut self.scale_factor, scale_factor);
+        cx.emit(ScaleChanged {
+            old,
+            new: scale_factor,
+        });
+        cx.notify();
+    }
+
     pub fn update<F, R>(window: &mut Window, cx: &mut App, f: F) -> R
     where
         F: FnOnce(&mut Self, &mut Window, &mut Context<Self>) -> R,
@@ -384,12 +421,94 @@ impl Root {
     fn on_action_tab_prev(&mut self, _: &TabPrev, window: &mut Window, _: &mut Context<Self>) {
         window.focus_prev();
     }
+
+    fn cycle_region(&mut self, forward: bool, window: &mut Window, cx: &mut Context<Self>) {
+        // Keep the focus in the Modal or Drawer.
+        if !self.active_modals.is_empty() || self.active_drawer.is_some() {
+            cx.propagate();
+            return;
+        }
+
+        self.regions.cycle(forward, window, cx);
+        cx.notify();
+    }
+
+    fn on_action_focus_next_region(
+        &mut self,
+        _: &FocusNextRegion,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.cycle_region(true, window, cx);
+    }
+
+    fn on_action_focus_prev_region(
+        &mut self,
+        _: &FocusPrevRegion,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.cycle_region(false, window, cx);
+    }
+
+    fn toggle_sidebar(&mut self, side: Option<Side>, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.sidebars.toggle(side, window, cx) {
+            cx.propagate();
+        }
+    }
+
+    fn on_action_toggle_sidebar(
+        &mut self,
+        _: &ToggleSidebar,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.toggle_sidebar(None, window, cx);
+    }
+
+    fn on_action_toggle_left_sidebar(
+        &mut self,
+        _: &ToggleLeftSidebar,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.toggle_sidebar(Some(Side::Left), window, cx);
+    }
+
+    fn on_action_toggle_right_sidebar(
+        &mut self,
+        _: &ToggleRightSidebar,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.toggle_sidebar(Some(Side::Right), window, cx);
+    }
+
+    fn on_action_close_sidebar_overlay(
+        &mut self,
+        _: &CloseSidebarOverlay,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.sidebars.close_overlays(window, cx) {
+            cx.propagate();
+        }
+    }
 }
 
+impl EventEmitter<ScaleChanged> for Root {}
+
 impl Render for Root {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let base_font_size = cx.theme().font_size;
         window.set_rem_size(base_font_size);
+        self.regions.begin_frame();
+        self.sidebars.begin_frame();
+        let background_appearance = window_background_appearance(cx);
+        if self.background_appearance != background_appearance {
+            self.background_appearance = background_appearance;
+            window.set_background_appearance(background_appearance);
+        }
 
         window_border().child(
             div()
@@ -397,10 +516,23 @@ impl Render for Root {
                 .key_context(CONTEXT)
                 .on_action(cx.listener(Self::on_action_tab))
                 .on_action(cx.listener(Self::on_action_tab_prev))
+                .on_action(cx.listener(Self::on_action_focus_next_region))
+                .on_action(cx.listener(Self::on_action_focus_prev_region))
+                .on_action(cx.listener(Self::on_action_toggle_sidebar))
+                .on_action(cx.listener(Self::on_action_toggle_left_sidebar))
+                .on_action(cx.listener(Self::on_action_toggle_right_sidebar))
+                .on_action(cx.listener(Self::on_action_close_sidebar_overlay))
+                // Hide the focus ring of the region when using the mouse.
+                .capture_any_mouse_down(cx.listener(|this, _, _, cx| {
+                    if this.regions.clear_active() {
+                        cx.notify();
+                    }
+                }))
                 .relative()
                 .size_full()
                 .font_family(".SystemUIFont")
-                .bg(cx.theme().background)
+                .bg(Material::Window.color(cx))
+                .corner_radii(WindowChrome::corner_radii(window, cx))
                 .text_color(cx.theme().foreground)
                 .child(self.view.clone()),
         )
@@ -0,0 +1,218 @@
+use gpui::{Context, EventEmitter, SharedString};
+
+/// The id of a route, the segments are separated by `/`, e.g. `settings/team`.
+///
+/// A route is the parent of the routes starting with its segments, so the `settings`
+/// item is active while the `settings/team` is the current route.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Route(SharedString);
+
+impl Route {
+    /// Create a new route by the id.
+    pub fn new(id: impl Into<SharedString>) -> Self {
+        Self(id.into())
+    }
+
+    /// Returns the id of the route.
+    pub fn as_str(&self) -> &str {
+        self.0.as_ref()
+    }
+
+    /// Returns the parent route, `None` for the top level route.
+    pub fn parent(&self) -> Option<Route> {
+        self.as_str()
+            .rsplit_once('/')
+            .map(|(parent, _)| Route::new(parent.to_string()))
+    }
+
+    /// Returns true if the route is the `other` or one of its descendants.
+    pub fn starts_with(&self, other: &Route) -> bool {
+        match self.as_str().strip_prefix(other.as_str()) {
+            Some(rest) => rest.is_empty() || rest.starts_with('/'),
+            None => false,
+        }
+    }
+}
+
+impl From<&'static str> for Route {
+    fn from(id: &'static str) -> Self {
+        Self::new(id)
+    }
+}
+
+impl From<String> for Route {
+    fn from(id: String) -> Self {
+        Self::new(id)
+    }
+}
+
+impl From<SharedString> for Route {
+    fn from(id: SharedString) -> Self {
+        Self::new(id)
+    }
+}
+
+/// Events emitted by the [`Router`].
+#[derive(Debug, Clone)]
+pub enum RouterEvent {
+    /// The current route is changed.
+    Navigated(Route),
+}
+
+/// A lightweight router to share the current route and the back/forward history between
+/// the navigation components.
+///
+/// The [`SidebarMenuItem`](crate::sidebar::SidebarMenuItem),
+/// [`BreadcrumbItem`](crate::breadcrumb::BreadcrumbItem) and [`Tab`](crate::tab::Tab)
+/// can bind to a route by `route` method, they are active when the route is active,
+/// and navigate to it when clicked.
+///
+/// ```ignore
+/// let router = cx.new(|_| Router::new("inbox"));
+/// cx.observe(&router, |_, _, cx| cx.notify()).detach();
+///
+/// SidebarMenuItem::new("Inbox").route(&router, "inbox");
+/// ```
+pub struct Router {
+    current: Route,
+    back: Vec<Route>,
+    forward: Vec<Route>,
+}
+
+impl EventEmitter<RouterEvent> for Router {}
+
+impl Router {
+    /// Create a new router with the initial route.
+    pub fn new(initial: impl Into<Route>) -> Self {
+        Self {
+            current: initial.into(),
+            back: Vec::new(),
+            forward: Vec::new(),
+        }
+    }
+
+    /// Returns the current route.
+    pub fn current(&self) -> &Route {
+        &self.current
+    }
+
+    /// Returns true if the route is the current route or one of its parents.
+    pub fn is_active(&self, route: &Route) -> bool {
+        self.current.starts_with(route)
+    }
+
+    /// Navigate to the route and push the current route into the back history.
+    ///
+    /// The forward history is cleared, do nothing if the route is the current route.
+    pub fn navigate(&mut self, route: impl Into<Route>, cx: &mut Context<Self>) {
+        let route = route.into();
+        if route == self.current {
+            return;
+        }
+
+        let prev = std::mem::replace(&mut self.current, route);
+        self.back.push(prev);
+        self.forward.clear();
+        self.changed(cx);
+    }
+
+    /// Replace the current route without adding it to the history.
+    pub fn replace(&mut self, route: impl Into<Route>, cx: &mut Context<Self>) {
+        let route = route.into();
+        if route == self.current {
+            return;
+        }
+
+        self.current = route;
+        self.changed(cx);
+    }
+
+    /// Returns true if there is a route to go back.
+    pub fn can_go_back(&self) -> bool {
+        !self.back.is_empty()
+    }
+
+    /// Returns true if there is a route to go forward.
+    pub fn can_go_forward(&self) -> bool {
+        !self.forward.is_empty()
+    }
+
+    /// Go back to the previous route, returns false if the back history is empty.
+    pub fn back(&mut self, cx: &mut Context<Self>) -> bool {
+        let Some(route) = self.back.pop() else {
+            return false;
+        };
+
+        let next = std::mem::replace(&mut self.current, route);
+        self.forward.push(next);
+        self.changed(cx);
+        true
+    }
+
+    /// Go forward to the next route, returns false if the forward history is empty.
+    pub fn forward(&mut self, cx: &mut Context<Self>) -> bool {
+        let Some(route) = self.forward.pop() else {
+            return false;
+        };
+
+        let prev = std::mem::replace(&mut self.current, route);
+        self.back.push(prev);
+        self.changed(cx);
+        true
+    }
+
+    fn changed(&mut self, cx: &mut Context<Self>) {
+        cx.emit(RouterEvent::Navigated(self.current.clone()));
+        cx.notify();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{AppContext as _, TestAppContext};
+
+    use super::{Route, Router};
+
+    #[test]
+    fn test_route() {
+        let route = Route::new("settings/team");
+        assert_eq!(route.parent(), Some(Route::new("settings")));
+        assert_eq!(Route::new("settings").parent(), None);
+        assert!(route.starts_with(&"settings".into()));
+        assert!(route.starts_with(&"settings/team".into()));
+        assert!(!route.starts_with(&"set".into()));
+        assert!(!Route::new("settings").starts_with(&route));
+    }
+
+    #[gpui::test]
+    fn test_router_history(cx: &mut TestAppContext) {
+        let router = cx.new(|_| Router::new("inbox"));
+
+        router.update(cx, |router, cx| {
+            assert!(!router.can_go_back());
+            router.navigate("settings/team", cx);
+            router.navigate("settings/team", cx);
+            assert_eq!(router.current().as_str(), "settings/team");
+            assert!(router.is_active(&"settings".into()));
+            assert!(!router.is_active(&"inbox".into()));
+
+            router.navigate("drafts", cx);
+            assert!(router.back(cx));
+            assert_eq!(router.current().as_str(), "settings/team");
+            assert!(router.back(cx));
+            assert_eq!(router.current().as_str(), "inbox");
+            assert!(!router.back(cx));
+
+            assert!(router.forward(cx));
+            assert_eq!(router.current().as_str(), "settings/team");
+
+            // Navigate clears the forward history.
+            router.navigate("sent", cx);
+            assert!(!router.can_go_forward());
+
+            router.replace("archive", cx);
+            assert!(router.back(cx));
+            assert_eq!(router.current().as_str(), "settings/team");
+        });
+    }
+}
@@ -0,0 +1,141 @@
+use gpui::{App, FocusHandle, Window};
+
+/// Returns the index of the next or previous item, stops at the ends if not `wrap`.
+fn step(ix: usize, len: usize, forward: bool, wrap: bool) -> usize {
+    if len == 0 {
+        return 0;
+    }
+
+    match (forward, wrap) {
+        (true, true) => (ix + 1) % len,
+        (true, false) => (ix + 1).min(len - 1),
+        (false, true) => (ix + len - 1) % len,
+        (false, false) => ix.saturating_sub(1),
+    }
+}
+
+/// The roving tab stop of the items in a composite widget, like a toolbar or a menu bar.
+///
+/// Only the active item is a tab stop, so the widget takes a single Tab stop,
+/// and the arrow keys move the focus between the items.
+///
+/// ```ignore
+/// // In the render of the widget.
+/// h_flex()
+///     .on_action(cx.listener(|this, _: &SelectRight, window, _| this.roving.focus_next(window)))
+///     .on_action(cx.listener(|this, _: &SelectLeft, window, _| this.roving.focus_prev(window)))
+///     .children(self.items.iter().enumerate().map(|(ix, item)| {
+///         div().track_focus(self.roving.focus_handle(ix).unwrap()).child(item.clone())
+///     }))
+/// ```
+pub struct RovingFocus {
+    focus_handles: Vec<FocusHandle>,
+    active_ix: usize,
+    wrap: bool,
+}
+
+impl RovingFocus {
+    /// Create a new roving focus with `len` items.
+    pub fn new(len: usize, cx: &mut App) -> Self {
+        let mut this = Self {
+            focus_handles: vec![],
+            active_ix: 0,
+            wrap: true,
+        };
+        this.set_len(len, cx);
+        this
+    }
+
+    /// Set to wrap around at the ends when moving the focus, default: true
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Set the number of the items, the active item is kept if still exists.
+    pub fn set_len(&mut self, len: usize, cx: &mut App) {
+        self.focus_handles.truncate(len);
+        while self.focus_handles.len() < len {
+            self.focus_handles.push(cx.focus_handle());
+        }
+        self.set_active_ix(self.active_ix.min(len.saturating_sub(1)));
+    }
+
+    pub fn len(&self) -> usize {
+        self.focus_handles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.focus_handles.is_empty()
+    }
+
+    /// Returns the index of the active item, which is the tab stop.
+    pub fn active_ix(&self) -> usize {
+        self.active_ix
+    }
+
+    /// Returns the focus handle of the item to track the focus.
+    pub fn focus_handle(&self, ix: usize) -> Option<&FocusHandle> {
+        self.focus_handles.get(ix)
+    }
+
+    /// Returns the index of the focused item.
+    pub fn focused_ix(&self, window: &Window) -> Option<usize> {
+        self.focus_handles
+            .iter()
+            .position(|focus_handle| focus_handle.is_focused(window))
+    }
+
+    /// Set the active item without focusing it.
+    pub fn set_active_ix(&mut self, ix: usize) {
+        self.active_ix = ix;
+        for (ix, focus_handle) in self.focus_handles.iter().enumerate() {
+            _ = focus_handle.clone().tab_stop(ix == self.active_ix);
+        }
+    }
+
+    /// Focus the item and make it active.
+    pub fn focus(&mut self, ix: usize, window: &mut Window) {
+        if ix >= self.len() {
+            return;
+        }
+
+        self.set_active_ix(ix);
+        window.focus(&self.focus_handles[ix]);
+    }
+
+    /// Focus the next item.
+    pub fn focus_next(&mut self, window: &mut Window) {
+        self.focus(step(self.active_ix, self.len(), true, self.wrap), window);
+    }
+
+    /// Focus the previous item.
+    pub fn focus_prev(&mut self, window: &mut Window) {
+        self.focus(step(self.active_ix, self.len(), false, self.wrap), window);
+    }
+
+    /// Focus the first item.
+    pub fn focus_first(&mut self, window: &mut Window) {
+        self.focus(0, window);
+    }
+
+    /// Focus the last item.
+    pub fn focus_last(&mut self, window: &mut Window) {
+        self.focus(self.len().saturating_sub(1), window);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::step;
+
+    #[test]
+    fn test_step() {
+        assert_eq!(step(0, 0, true, true), 0);
+        assert_eq!(step(1, 3, true, true), 2);
+        assert_eq!(step(2, 3, true, true), 0);
+        assert_eq!(step(2, 3, true, false), 2);
+        assert_eq!(step(0, 3, false, true), 2);
+        assert_eq!(step(0, 3, false, false), 0);
+    }
+}
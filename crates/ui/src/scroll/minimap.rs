@@ -0,0 +1,213 @@
+use std::{ops::Range, rc::Rc};
+
+use gpui::{
+    canvas, div, fill, point, px, size, App, Bounds, ElementId, Hsla, InteractiveElement as _,
+    IntoElement, MouseButton, MouseDownEvent, ParentElement as _, Pixels, RenderOnce,
+    StyleRefinement, Styled, Window,
+};
+
+use crate::{scroll::Scrollbar, ActiveTheme as _, StyledExt as _};
+
+const MIN_MARKER_HEIGHT: Pixels = px(2.);
+
+/// A marker of the [`Minimap`], e.g.: a search match, an error or a selection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MinimapMarker {
+    /// The first row of the marker.
+    pub row: usize,
+    /// The number of rows of the marker.
+    pub rows: usize,
+    pub color: Hsla,
+}
+
+impl MinimapMarker {
+    /// Create a marker of a single row.
+    pub fn new(row: usize, color: Hsla) -> Self {
+        Self {
+            row,
+            rows: 1,
+            color,
+        }
+    }
+
+    /// Set the number of rows of the marker, default: 1
+    pub fn rows(mut self, rows: usize) -> Self {
+        self.rows = rows.max(1);
+        self
+    }
+}
+
+/// Returns the bounds of the `rows` in the track `bounds` of the `total` rows.
+fn rows_bounds(bounds: Bounds<Pixels>, total: usize, rows: Range<usize>) -> Bounds<Pixels> {
+    let total = total.max(1) as f32;
+    let top = bounds.size.height * (rows.start as f32 / total);
+    let height = (bounds.size.height * (rows.len() as f32 / total)).max(MIN_MARKER_HEIGHT);
+    let top = top.min(bounds.size.height - height).max(px(0.));
+
+    Bounds::new(
+        point(bounds.origin.x, bounds.origin.y + top),
+        size(bounds.size.width, height),
+    )
+}
+
+/// Returns the row at the `y` position in the track `bounds` of the `total` rows.
+fn row_at(bounds: Bounds<Pixels>, total: usize, y: Pixels) -> usize {
+    if total == 0 || bounds.size.height <= px(0.) {
+        return 0;
+    }
+
+    let ratio = ((y - bounds.origin.y) / bounds.size.height).clamp(0., 1.);
+    ((ratio * total as f32) as usize).min(total - 1)
+}
+
+/// A scroll position indicator to show the markers along the scrollbar track of
+/// a large scrollable component, like [`LogView`](crate::log_view::LogView) or [`Table`](crate::table::Table).
+///
+/// The positions are in rows of the `total` rows, click on it to jump to the row.
+///
+/// ```ignore
+/// div()
+///     .relative()
+///     .child(uniform_list(...))
+///     .child(
+///         Minimap::new("minimap", rows.len())
+///             .absolute()
+///             .top_0()
+///             .right_0()
+///             .markers(errors.iter().map(|ix| MinimapMarker::new(*ix, cx.theme().danger)))
+///             .on_click(|row, _, _| scroll_handle.scroll_to_item(row, ScrollStrategy::Center)),
+///     )
+/// ```
+#[derive(IntoElement)]
+pub struct Minimap {
+    id: ElementId,
+    style: StyleRefinement,
+    total: usize,
+    markers: Vec<MinimapMarker>,
+    viewport: Option<Range<usize>>,
+    on_click: Option<Rc<dyn Fn(usize, &mut Window, &mut App)>>,
+}
+
+impl Minimap {
+    /// Create a new minimap of the `total` rows.
+    pub fn new(id: impl Into<ElementId>, total: usize) -> Self {
+        Self {
+            id: id.into(),
+            style: StyleRefinement::default(),
+            total,
+            markers: vec![],
+            viewport: None,
+            on_click: None,
+        }
+    }
+
+    /// Add the markers.
+    pub fn markers(mut self, markers: impl IntoIterator<Item = MinimapMarker>) -> Self {
+        self.markers.extend(markers);
+        self
+    }
+
+    /// Set the visible rows to highlight.
+    pub fn viewport(mut self, rows: Range<usize>) -> Self {
+        self.viewport = Some(rows);
+        self
+    }
+
+    /// Set the click handler, the row at the click position is given.
+    pub fn on_click(mut self, handler: impl Fn(usize, &mut Window, &mut App) + 'static) -> Self {
+        self.on_click = Some(Rc::new(handler));
+        self
+    }
+}
+
+impl Styled for Minimap {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for Minimap {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let total = self.total;
+        let markers = self.markers;
+        let viewport = self.viewport;
+        let on_click = self.on_click;
+        let viewport_color = cx.theme().scrollbar_thumb.opacity(0.3);
+
+        div()
+            .id(self.id)
+            .h_full()
+            .w(Scrollbar::width())
+            .refine_style(&self.style)
+            .child(
+                canvas(
+                    |_, _, _| {},
+                    move |bounds, _, window, _| {
+                        if let Some(viewport) = viewport {
+                            window.paint_quad(fill(
+                                rows_bounds(bounds, total, viewport),
+                                viewport_color,
+                            ));
+                        }
+                        for marker in markers.iter() {
+                            window.paint_quad(fill(
+                                rows_bounds(bounds, total, marker.row..marker.row + marker.rows),
+                                marker.color,
+                            ));
+                        }
+
+                        if let Some(on_click) = on_click {
+                            window.on_mouse_event(
+                                move |event: &MouseDownEvent, phase, window, cx| {
+                                    if phase.bubble()
+                                        && event.button == MouseButton::Left
+                                        && bounds.contains(&event.position)
+                                        && total > 0
+                                    {
+                                        cx.stop_propagation();
+                                        on_click(
+                                            row_at(bounds, total, event.position.y),
+                                            window,
+                                            cx,
+                                        );
+                                    }
+                                },
+                            );
+                        }
+                    },
+                )
+                .size_full(),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{point, px, size, Bounds};
+
+    use super::{row_at, rows_bounds};
+
+    #[test]
+    fn test_rows_bounds() {
+        let bounds = Bounds::new(point(px(0.), px(100.)), size(px(10.), px(200.)));
+
+        let marker = rows_bounds(bounds, 100, 50..60);
+        assert_eq!(marker.origin.y, px(200.));
+        assert_eq!(marker.size.height, px(20.));
+
+        // Keep the min height, and inside the track.
+        let marker = rows_bounds(bounds, 10_000, 9_999..10_000);
+        assert_eq!(marker.size.height, px(2.));
+        assert_eq!(marker.origin.y, px(298.));
+    }
+
+    #[test]
+    fn test_row_at() {
+        let bounds = Bounds::new(point(px(0.), px(100.)), size(px(10.), px(200.)));
+        assert_eq!(row_at(bounds, 100, px(100.)), 0);
+        assert_eq!(row_at(bounds, 100, px(200.)), 50);
+        assert_eq!(row_at(bounds, 100, px(300.)), 99);
+        assert_eq!(row_at(bounds, 100, px(500.)), 99);
+        assert_eq!(row_at(bounds, 0, px(200.)), 0);
+    }
+}
@@ -1,7 +1,11 @@
+mod minimap;
+mod motion;
 mod scrollable;
 mod scrollable_mask;
 mod scrollbar;
 
+pub use minimap::*;
+pub use motion::*;
 pub use scrollable::*;
 pub use scrollable_mask::*;
 pub use scrollbar::*;
@@ -0,0 +1,155 @@
+use std::{
+    ops::Range,
+    time::{Duration, Instant},
+};
+
+use gpui::{point, px, App, ElementId, Pixels, Point, Window};
+
+use super::ScrollHandleOffsetable;
+
+/// The velocity is reset if the offset is not changed in this duration.
+const VELOCITY_TIMEOUT: Duration = Duration::from_millis(100);
+
+#[derive(Clone, Copy)]
+struct ScrollMotionState {
+    offset: Point<Pixels>,
+    velocity: Point<Pixels>,
+    updated_at: Instant,
+}
+
+/// The scroll position of a scroll handle in the current frame, to drive the styles by the scrolling,
+/// e.g. shrink the header as the list scrolls, fade in the shadow of a toolbar, or the parallax effects.
+///
+/// The view is re-rendered when the scroll container is scrolled, so it is created in the `render`,
+/// and there is no need to add any scroll listener.
+///
+/// ```ignore
+/// let motion = ScrollMotion::new("page", &self.scroll_handle, window, cx);
+///
+/// v_flex()
+///     .child(
+///         h_flex()
+///             .h(px(motion.interpolate(px(0.)..px(120.), 96., 48.)))
+///             .when(motion.progress(px(0.)..px(8.)) > 0., |this| this.shadow_md())
+///             .child(title),
+///     )
+///     .child(
+///         div()
+///             .id("page")
+///             .track_scroll(&self.scroll_handle)
+///             .overflow_y_scroll()
+///             .child(img("hero.png").mt(motion.parallax(0.5)))
+///             .child(content),
+///     )
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ScrollMotion {
+    offset: Point<Pixels>,
+    velocity: Point<Pixels>,
+}
+
+impl ScrollMotion {
+    /// Read the scroll position of the `handle`, the `id` keeps the previous position to measure the velocity.
+    pub fn new(
+        id: impl Into<ElementId>,
+        handle: &impl ScrollHandleOffsetable,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self {
+        // The offset of the scroll handle is negative when scrolled.
+        let offset = handle.offset();
+        let offset = point(-offset.x, -offset.y);
+        let now = Instant::now();
+        let state = window.use_keyed_state(id.into(), cx, |_, _| ScrollMotionState {
+            offset,
+            velocity: point(px(0.), px(0.)),
+            updated_at: now,
+        });
+
+        let velocity = state.update(cx, |state, _| {
+            let elapsed = now.duration_since(state.updated_at);
+            if state.offset != offset {
+                state.velocity = velocity(offset - state.offset, elapsed);
+                state.offset = offset;
+                state.updated_at = now;
+            } else if elapsed > VELOCITY_TIMEOUT {
+                state.velocity = point(px(0.), px(0.));
+            }
+            state.velocity
+        });
+
+        Self { offset, velocity }
+    }
+
+    /// Returns the scrolled distance from the start, it is positive when scrolled.
+    pub fn offset(&self) -> Point<Pixels> {
+        self.offset
+    }
+
+    /// Returns the scrolling velocity in pixels per second, it is zero when stopped.
+    pub fn velocity(&self) -> Point<Pixels> {
+        self.velocity
+    }
+
+    /// Returns the progress of the vertical scrolled distance in the `range`, clamped to `0.0..=1.0`.
+    pub fn progress(&self, range: Range<Pixels>) -> f32 {
+        progress(self.offset.y, range)
+    }
+
+    /// Returns the progress of the horizontal scrolled distance in the `range`, clamped to `0.0..=1.0`.
+    pub fn progress_x(&self, range: Range<Pixels>) -> f32 {
+        progress(self.offset.x, range)
+    }
+
+    /// Returns the value from `from` to `to` by the vertical progress in the `range`.
+    pub fn interpolate(&self, range: Range<Pixels>, from: f32, to: f32) -> f32 {
+        from + (to - from) * self.progress(range)
+    }
+
+    /// Returns the vertical scrolled distance multiplied by the `factor`, to move the
+    /// background slower (`0.0..1.0`) or faster (`> 1.0`) than the content.
+    pub fn parallax(&self, factor: f32) -> Pixels {
+        self.offset.y * factor
+    }
+}
+
+fn progress(value: Pixels, range: Range<Pixels>) -> f32 {
+    let length = range.end - range.start;
+    if length <= px(0.) {
+        return if value >= range.end { 1. } else { 0. };
+    }
+
+    ((value - range.start) / length).clamp(0., 1.)
+}
+
+fn velocity(delta: Point<Pixels>, elapsed: Duration) -> Point<Pixels> {
+    let secs = elapsed.as_secs_f32().max(0.001);
+    point(delta.x / secs, delta.y / secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use gpui::{point, px};
+
+    use super::{progress, velocity};
+
+    #[test]
+    fn test_progress() {
+        assert_eq!(progress(px(0.), px(0.)..px(100.)), 0.);
+        assert_eq!(progress(px(50.), px(0.)..px(100.)), 0.5);
+        assert_eq!(progress(px(150.), px(0.)..px(100.)), 1.);
+        assert_eq!(progress(px(10.), px(20.)..px(40.)), 0.);
+        assert_eq!(progress(px(30.), px(20.)..px(20.)), 1.);
+    }
+
+    #[test]
+    fn test_velocity() {
+        let v = velocity(point(px(0.), px(50.)), Duration::from_millis(125));
+        assert_eq!(v, point(px(0.), px(400.)));
+        // The elapsed time is at least 1ms.
+        let v = velocity(point(px(0.), px(1.)), Duration::ZERO);
+        assert!((f32::from(v.y) - 1000.).abs() < 0.1);
+    }
+}
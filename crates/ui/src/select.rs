@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use gpui::{
     anchored, canvas, deferred, div, prelude::FluentBuilder, px, rems, AnyElement, App, AppContext,
     Bounds, ClickEvent, Context, DismissEvent, Edges, ElementId, Entity, EventEmitter, FocusHandle,
@@ -9,6 +11,8 @@ use rust_i18n::t;
 
 use crate::{
     actions::{Cancel, Confirm, SelectDown, SelectUp},
+    elevation::Layer,
+    fuzzy::{fuzzy_filter, fuzzy_match, FuzzyIndex},
     h_flex,
     input::clear_button,
     list::{List, ListDelegate, ListState},
@@ -43,9 +47,9 @@ pub trait SelectItem: Clone {
     }
     /// Get the value of the item.
     fn value(&self) -> &Self::Value;
-    /// Check if the item matches the query for search, default is to match the title.
+    /// Check if the item matches the query for search, default is to fuzzy match the title.
     fn matches(&self, query: &str) -> bool {
-        self.title().to_lowercase().contains(&query.to_lowercase())
+        fuzzy_match(query, &self.title()).is_some()
     }
 }
 
@@ -353,17 +357,30 @@ pub struct Select<D: SelectDelegate + 'static> {
     options: SelectOptions,
 }
 
+/// The default number of the items to build the [`FuzzyIndex`] for the [`SearchableVec`].
+const INDEX_THRESHOLD: usize = 10_000;
+
 /// A built-in searchable vector for select items.
+///
+/// When the items are more than the [`SearchableVec::index_threshold`], a [`FuzzyIndex`]
+/// is built in the background at the first search, and the later searches use the index.
 #[derive(Debug, Clone)]
 pub struct SearchableVec<T> {
     items: Vec<T>,
     matched_items: Vec<T>,
+    index: Option<Arc<FuzzyIndex>>,
+    index_threshold: usize,
+    indexing: bool,
+    /// The last query and the indices of all its matches, to narrow the next search.
+    last_search: Option<(String, Vec<usize>)>,
 }
 
 impl<T: Clone> SearchableVec<T> {
     pub fn push(&mut self, item: T) {
         self.items.push(item.clone());
         self.matched_items.push(item);
+        self.index = None;
+        self.last_search = None;
     }
 }
 
@@ -373,20 +390,85 @@ impl<T: Clone> SearchableVec<T> {
         Self {
             items: items.clone(),
             matched_items: items,
+            index: None,
+            index_threshold: INDEX_THRESHOLD,
+            indexing: false,
+            last_search: None,
         }
     }
+
+    /// Set the number of the items to build the search index, default: 10000
+    pub fn index_threshold(mut self, threshold: usize) -> Self {
+        self.index_threshold = threshold;
+        self
+    }
+
+    /// Set a pre-built search index of the titles of the items, e.g. built by [`FuzzyIndex::build`].
+    ///
+    /// The index is ignored if it does not have the same number of items.
+    pub fn set_index(&mut self, index: Option<Arc<FuzzyIndex>>) {
+        self.index = index;
+        self.last_search = None;
+    }
+
+    /// Returns the search index if it has been built.
+    pub fn index(&self) -> Option<&Arc<FuzzyIndex>> {
+        self.index.as_ref()
+    }
 }
 
 impl<T: SelectItem> From<Vec<T>> for SearchableVec<T> {
     fn from(items: Vec<T>) -> Self {
-        Self {
-            items: items.clone(),
-            matched_items: items,
-        }
+        Self::new(items)
+    }
+}
+
+impl<I: SelectItem + 'static> SearchableVec<I> {
+    /// Build the index of the titles in the background, and set it when finished.
+    fn build_index(&mut self, cx: &mut Context<SelectState<Self>>) {
+        self.indexing = true;
+        let titles = self.items.iter().map(|item| item.title()).collect();
+        Self::spawn_build_index(titles, cx);
+    }
+
+    fn spawn_build_index(titles: Vec<SharedString>, cx: &mut Context<SelectState<Self>>) {
+        let build = FuzzyIndex::build(titles, cx);
+        cx.spawn(async move |state, cx| {
+            let index = Arc::new(build.await);
+            _ = state.update(cx, |state, cx| {
+                state.list.update(cx, |list, _| {
+                    let delegate = &mut list.delegate_mut().delegate;
+                    delegate.indexing = false;
+                    delegate.set_index(Some(index));
+                });
+            });
+        })
+        .detach();
+    }
+
+    fn search_with_index(&mut self, query: &str, index: &FuzzyIndex) {
+        let query = query
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>();
+        let within = self
+            .last_search
+            .as_ref()
+            .filter(|(last, _)| !last.is_empty() && query.starts_with(last.as_str()))
+            .map(|(_, matches)| matches.as_slice());
+
+        let results = index.search(&query, within);
+        self.matched_items = results
+            .iter()
+            .filter_map(|(ix, _)| self.items.get(*ix).cloned())
+            .collect();
+        let mut matches = results.into_iter().map(|(ix, _)| ix).collect::<Vec<_>>();
+        matches.sort_unstable();
+        self.last_search = Some((query, matches));
     }
 }
 
-impl<I: SelectItem> SelectDelegate for SearchableVec<I> {
+impl<I: SelectItem + 'static> SelectDelegate for SearchableVec<I> {
     type Item = I;
 
     fn items_count(&self, _: usize) -> usize {
@@ -415,14 +497,30 @@ impl<I: SelectItem> SelectDelegate for SearchableVec<I> {
         &mut self,
         query: &str,
         _window: &mut Window,
-        _: &mut Context<SelectState<Self>>,
+        cx: &mut Context<SelectState<Self>>,
     ) -> Task<()> {
-        self.matched_items = self
-            .items
-            .iter()
-            .filter(|item| item.title().to_lowercase().contains(&query.to_lowercase()))
-            .cloned()
-            .collect();
+        if let Some(index) = self
+            .index
+            .clone()
+            .filter(|index| index.len() == self.items.len())
+        {
+            self.search_with_index(query, &index);
+            return Task::ready(());
+        }
+
+        if self.items.len() >= self.index_threshold && !self.indexing {
+            self.build_index(cx);
+        }
+
+        // Ranked by the fuzzy score of the title, an empty query keeps the original order.
+        self.matched_items = fuzzy_filter(
+            query,
+            self.items.iter().map(|item| (item.title(), item)),
+            |(title, _)| title,
+        )
+        .into_iter()
+        .map(|((_, item), _)| item.clone())
+        .collect();
 
         Task::ready(())
     }
@@ -526,7 +624,7 @@ where
     }
 
     fn matches(&self, query: &str) -> bool {
-        self.title.to_lowercase().contains(&query.to_lowercase())
+        fuzzy_match(query, &self.title).is_some()
             || self.items.iter().any(|item| item.matches(query))
     }
 }
@@ -768,6 +866,20 @@ where
     }
 }
 
+impl<I: SelectItem + 'static> SelectState<SearchableVec<I>> {
+    /// Rebuild the search index of the items in the background, e.g. after the titles are changed.
+    ///
+    /// The searches keep using the previous index until the new index is ready.
+    pub fn refresh_search_index(&mut self, cx: &mut Context<Self>) {
+        let titles = self.list.update(cx, |list, _| {
+            let delegate = &mut list.delegate_mut().delegate;
+            delegate.indexing = true;
+            delegate.items.iter().map(|item| item.title()).collect()
+        });
+        SearchableVec::spawn_build_index(titles, cx);
+    }
+}
+
 impl<D> Render for SelectState<D>
 where
     D: SelectDelegate + 'static,
@@ -890,7 +1002,7 @@ where
                                         .border_1()
                                         .border_color(cx.theme().border)
                                         .rounded(popup_radius)
-                                        .shadow_md()
+                                        .elevation(Layer::Dropdown.elevation(), cx)
                                         .child(
                                             List::new(&self.list)
                                                 .when_some(
@@ -909,7 +1021,7 @@ where
                                 })),
                         ),
                     )
-                    .with_priority(1),
+                    .with_priority(Layer::Dropdown.priority()),
                 )
             })
     }
@@ -0,0 +1,537 @@
+use std::{collections::BTreeSet, ops::Range, rc::Rc};
+
+use gpui::{
+    actions, div, prelude::FluentBuilder as _, uniform_list, AnyElement, App, Context, ElementId,
+    Entity, EventEmitter, FocusHandle, InteractiveElement as _, IntoElement, KeyBinding,
+    ListSizingBehavior, MouseButton, MouseDownEvent, ParentElement, Render, RenderOnce,
+    ScrollStrategy, StyleRefinement, Styled, UniformListScrollHandle, Window,
+};
+use rust_i18n::t;
+
+use crate::{
+    actions::{Cancel, SelectDown, SelectUp},
+    button::{Button, ButtonVariants as _},
+    checkbox::Checkbox,
+    h_flex, input,
+    scroll::{Scrollbar, ScrollbarState},
+    v_flex, ActiveTheme, Sizable as _, StyledExt as _,
+};
+
+actions!(selectable_list, [ExtendUp, ExtendDown, ToggleSelected]);
+
+const CONTEXT: &str = "SelectableList";
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("up", SelectUp, Some(CONTEXT)),
+        KeyBinding::new("down", SelectDown, Some(CONTEXT)),
+        KeyBinding::new("shift-up", ExtendUp, Some(CONTEXT)),
+        KeyBinding::new("shift-down", ExtendDown, Some(CONTEXT)),
+        KeyBinding::new("space", ToggleSelected, Some(CONTEXT)),
+        KeyBinding::new("escape", Cancel, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-a", input::SelectAll, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-a", input::SelectAll, Some(CONTEXT)),
+    ]);
+}
+
+/// Events emitted by the [`SelectableListState`].
+#[derive(Debug, Clone)]
+pub enum SelectableListEvent {
+    /// The selected indices was changed.
+    Change(Vec<usize>),
+}
+
+/// The selected indices with the anchor for the range selection.
+#[derive(Debug, Default)]
+struct Selection {
+    selected: BTreeSet<usize>,
+    anchor: Option<usize>,
+}
+
+impl Selection {
+    fn toggle(&mut self, ix: usize) {
+        if !self.selected.remove(&ix) {
+            self.selected.insert(ix);
+        }
+        self.anchor = Some(ix);
+    }
+
+    /// Select the range from the anchor to `ix`, or only `ix` if there is no anchor.
+    fn extend_to(&mut self, ix: usize) {
+        let anchor = *self.anchor.get_or_insert(ix);
+        self.selected.extend(anchor.min(ix)..=anchor.max(ix));
+    }
+
+    fn select_all(&mut self, count: usize) {
+        self.selected.extend(0..count);
+    }
+
+    fn clear(&mut self) {
+        self.selected.clear();
+        self.anchor = None;
+    }
+
+    /// Drop the indices out of the `count`.
+    fn truncate(&mut self, count: usize) {
+        self.selected.retain(|ix| *ix < count);
+        if self.anchor.map_or(false, |anchor| anchor >= count) {
+            self.anchor = None;
+        }
+    }
+}
+
+/// State of the [`SelectableList`].
+pub struct SelectableListState {
+    focus_handle: FocusHandle,
+    items_count: usize,
+    selection: Selection,
+    /// The item of the keyboard cursor.
+    active_ix: Option<usize>,
+    scroll_handle: UniformListScrollHandle,
+    scrollbar_state: ScrollbarState,
+    render_item: Option<Rc<dyn Fn(usize, &mut Window, &mut App) -> AnyElement>>,
+    render_bulk_actions: Option<Rc<dyn Fn(&[usize], &mut Window, &mut App) -> AnyElement>>,
+}
+
+impl SelectableListState {
+    /// Create a new selectable list state.
+    pub fn new(_: &mut Window, cx: &mut Context<Self>) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            items_count: 0,
+            selection: Selection::default(),
+            active_ix: None,
+            scroll_handle: UniformListScrollHandle::new(),
+            scrollbar_state: ScrollbarState::default(),
+            render_item: None,
+            render_bulk_actions: None,
+        }
+    }
+
+    /// Set the number of items.
+    pub fn items_count(mut self, count: usize) -> Self {
+        self.items_count = count;
+        self
+    }
+
+    /// Set the number of items, the selected indices out of the count will be removed.
+    pub fn set_items_count(&mut self, count: usize, cx: &mut Context<Self>) {
+        self.items_count = count;
+        let len = self.selection.selected.len();
+        self.selection.truncate(count);
+        if self.active_ix.map_or(false, |ix| ix >= count) {
+            self.active_ix = None;
+        }
+        if self.selection.selected.len() != len {
+            self.emit_change(cx);
+        }
+        cx.notify();
+    }
+
+    /// Returns the selected indices in ascending order.
+    pub fn selected_indices(&self) -> Vec<usize> {
+        self.selection.selected.iter().copied().collect()
+    }
+
+    /// Returns true if the item at `ix` is selected.
+    pub fn is_selected(&self, ix: usize) -> bool {
+        self.selection.selected.contains(&ix)
+    }
+
+    /// Toggle the selection of the item at `ix`.
+    pub fn toggle(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if ix >= self.items_count {
+            return;
+        }
+
+        self.selection.toggle(ix);
+        self.active_ix = Some(ix);
+        self.emit_change(cx);
+    }
+
+    /// Select the items from the last toggled item to `ix`, like `Shift` + click.
+    pub fn select_range_to(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if ix >= self.items_count {
+            return;
+        }
+
+        self.selection.extend_to(ix);
+        self.active_ix = Some(ix);
+        self.emit_change(cx);
+    }
+
+    /// Select all the items.
+    pub fn select_all(&mut self, cx: &mut Context<Self>) {
+        self.selection.select_all(self.items_count);
+        self.emit_change(cx);
+    }
+
+    /// Clear the selection.
+    pub fn clear_selection(&mut self, cx: &mut Context<Self>) {
+        if self.selection.selected.is_empty() {
+            return;
+        }
+
+        self.selection.clear();
+        self.emit_change(cx);
+    }
+
+    fn emit_change(&mut self, cx: &mut Context<Self>) {
+        cx.emit(SelectableListEvent::Change(self.selected_indices()));
+        cx.notify();
+    }
+
+    fn move_active(&mut self, down: bool, extend: bool, cx: &mut Context<Self>) {
+        if self.items_count == 0 {
+            return;
+        }
+
+        if extend && self.selection.anchor.is_none() {
+            self.selection.anchor = self.active_ix;
+        }
+        let ix = match self.active_ix {
+            Some(ix) if down => (ix + 1).min(self.items_count - 1),
+            Some(ix) => ix.saturating_sub(1),
+            None => 0,
+        };
+        self.active_ix = Some(ix);
+        self.scroll_handle.scroll_to_item(ix, ScrollStrategy::Top);
+        if extend {
+            self.select_range_to(ix, cx);
+        } else {
+            cx.notify();
+        }
+    }
+
+    fn on_action_select_up(&mut self, _: &SelectUp, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_active(false, false, cx);
+    }
+
+    fn on_action_select_down(&mut self, _: &SelectDown, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_active(true, false, cx);
+    }
+
+    fn on_action_extend_up(&mut self, _: &ExtendUp, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_active(false, true, cx);
+    }
+
+    fn on_action_extend_down(&mut self, _: &ExtendDown, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_active(true, true, cx);
+    }
+
+    fn on_action_toggle(&mut self, _: &ToggleSelected, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.active_ix {
+            self.toggle(ix, cx);
+        }
+    }
+
+    fn on_action_select_all(
+        &mut self,
+        _: &input::SelectAll,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.select_all(cx);
+    }
+
+    fn on_action_cancel(&mut self, _: &Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        self.clear_selection(cx);
+    }
+
+    fn on_item_mouse_down(
+        &mut self,
+        ix: usize,
+        event: &MouseDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.focus_handle.focus(window);
+        if event.modifiers.shift {
+            self.select_range_to(ix, cx);
+        } else {
+            self.toggle(ix, cx);
+        }
+    }
+
+    fn render_header(&self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let selected = self.selected_indices();
+        let all_selected = self.items_count > 0 && selected.len() == self.items_count;
+        let bulk_actions = self
+            .render_bulk_actions
+            .clone()
+            .filter(|_| !selected.is_empty())
+            .map(|render| render(&selected, window, cx));
+
+        h_flex()
+            .h_10()
+            .px_3()
+            .gap_3()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .text_sm()
+            .child(
+                Checkbox::new("select-all")
+                    .checked(all_selected)
+                    .tab_stop(false)
+                    .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                        if *checked {
+                            this.select_all(cx);
+                        } else {
+                            this.clear_selection(cx);
+                        }
+                    })),
+            )
+            .map(|this| {
+                if selected.is_empty() {
+                    this.child(
+                        div().text_color(cx.theme().muted_foreground).child(
+                            t!("SelectableList.items", count = self.items_count).to_string(),
+                        ),
+                    )
+                } else {
+                    this.child(t!("SelectableList.selected", count = selected.len()).to_string())
+                        .child(div().text_color(cx.theme().muted_foreground).child("·"))
+                        .child(
+                            Button::new("clear-selection")
+                                .link()
+                                .small()
+                                .label(t!("SelectableList.Clear"))
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.clear_selection(cx);
+                                })),
+                        )
+                }
+            })
+            .child(
+                h_flex()
+                    .flex_1()
+                    .justify_end()
+                    .gap_2()
+                    .children(bulk_actions),
+            )
+    }
+
+    fn render_items(
+        &mut self,
+        visible_range: Range<usize>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        let Some(render_item) = self.render_item.clone() else {
+            return vec![];
+        };
+
+        visible_range
+            .map(|ix| {
+                let selected = self.is_selected(ix);
+                let active = self.active_ix == Some(ix);
+
+                h_flex()
+                    .id(ix)
+                    .w_full()
+                    .px_3()
+                    .gap_3()
+                    .border_1()
+                    .border_color(cx.theme().transparent)
+                    .when(selected, |this| this.bg(cx.theme().accent))
+                    .when(!selected, |this| {
+                        this.hover(|this| this.bg(cx.theme().accent.opacity(0.5)))
+                    })
+                    .when(active && self.focus_handle.is_focused(window), |this| {
+                        this.border_color(cx.theme().ring)
+                    })
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |this, event, window, cx| {
+                            this.on_item_mouse_down(ix, event, window, cx);
+                        }),
+                    )
+                    .child(
+                        Checkbox::new(("item", ix))
+                            .checked(selected)
+                            .tab_stop(false),
+                    )
+                    .child(
+                        div()
+                            .flex_1()
+                            .overflow_hidden()
+                            .child(render_item(ix, window, cx)),
+                    )
+                    .into_any_element()
+            })
+            .collect()
+    }
+}
+
+impl EventEmitter<SelectableListEvent> for SelectableListState {}
+
+impl Render for SelectableListState {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .size_full()
+            .child(self.render_header(window, cx))
+            .child(
+                div()
+                    .relative()
+                    .flex_1()
+                    .w_full()
+                    .child(
+                        uniform_list("items", self.items_count, cx.processor(Self::render_items))
+                            .size_full()
+                            .track_scroll(self.scroll_handle.clone())
+                            .with_sizing_behavior(ListSizingBehavior::Auto),
+                    )
+                    .child(
+                        div()
+                            .absolute()
+                            .top_0()
+                            .right_0()
+                            .bottom_0()
+                            .w(Scrollbar::width())
+                            .child(Scrollbar::vertical(
+                                &self.scrollbar_state,
+                                &self.scroll_handle,
+                            )),
+                    ),
+            )
+    }
+}
+
+/// A virtualized list with a checkbox column for multiple selection.
+///
+/// Click to toggle an item, `Shift` + click to select a range. The header shows
+/// the selection summary, and the bulk actions when there are selected items.
+///
+/// ```ignore
+/// let state = cx.new(|cx| SelectableListState::new(window, cx).items_count(items.len()));
+///
+/// SelectableList::new(&state)
+///     .item(move |ix, _, _| Label::new(items[ix].clone()))
+///     .bulk_actions(|selected, _, _| {
+///         Button::new("delete").small().label(format!("Delete {}", selected.len()))
+///     })
+/// ```
+#[derive(IntoElement)]
+pub struct SelectableList {
+    id: ElementId,
+    state: Entity<SelectableListState>,
+    style: StyleRefinement,
+    render_item: Option<Rc<dyn Fn(usize, &mut Window, &mut App) -> AnyElement>>,
+    render_bulk_actions: Option<Rc<dyn Fn(&[usize], &mut Window, &mut App) -> AnyElement>>,
+}
+
+impl SelectableList {
+    /// Create a new selectable list with the given state.
+    pub fn new(state: &Entity<SelectableListState>) -> Self {
+        Self {
+            id: ElementId::Name(format!("selectable-list-{}", state.entity_id()).into()),
+            state: state.clone(),
+            style: StyleRefinement::default(),
+            render_item: None,
+            render_bulk_actions: None,
+        }
+    }
+
+    /// Set the item to render at the index.
+    ///
+    /// NOTE: Every item should have same height.
+    pub fn item<F, E>(mut self, f: F) -> Self
+    where
+        F: Fn(usize, &mut Window, &mut App) -> E + 'static,
+        E: IntoElement,
+    {
+        self.render_item = Some(Rc::new(move |ix, window, cx| {
+            f(ix, window, cx).into_any_element()
+        }));
+        self
+    }
+
+    /// Set the bulk actions to show in the header when there are selected items,
+    /// the selected indices are given.
+    pub fn bulk_actions<F, E>(mut self, f: F) -> Self
+    where
+        F: Fn(&[usize], &mut Window, &mut App) -> E + 'static,
+        E: IntoElement,
+    {
+        self.render_bulk_actions = Some(Rc::new(move |selected, window, cx| {
+            f(selected, window, cx).into_any_element()
+        }));
+        self
+    }
+}
+
+impl Styled for SelectableList {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for SelectableList {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let focus_handle = self.state.update(cx, |state, _| {
+            state.render_item = self.render_item;
+            state.render_bulk_actions = self.render_bulk_actions;
+            state.focus_handle.clone()
+        });
+
+        div()
+            .id(self.id)
+            .key_context(CONTEXT)
+            .track_focus(&focus_handle)
+            .on_action(window.listener_for(&self.state, SelectableListState::on_action_select_up))
+            .on_action(window.listener_for(&self.state, SelectableListState::on_action_select_down))
+            .on_action(window.listener_for(&self.state, SelectableListState::on_action_extend_up))
+            .on_action(window.listener_for(&self.state, SelectableListState::on_action_extend_down))
+            .on_action(window.listener_for(&self.state, SelectableListState::on_action_toggle))
+            .on_action(window.listener_for(&self.state, SelectableListState::on_action_select_all))
+            .on_action(window.listener_for(&self.state, SelectableListState::on_action_cancel))
+            .size_full()
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded(cx.theme().radius)
+            .overflow_hidden()
+            .child(self.state)
+            .refine_style(&self.style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Selection;
+
+    #[test]
+    fn test_selection() {
+        let mut selection = Selection::default();
+        selection.toggle(2);
+        selection.extend_to(5);
+        assert_eq!(
+            selection.selected.iter().copied().collect::<Vec<_>>(),
+            vec![2, 3, 4, 5]
+        );
+
+        // Toggle off keeps the other items, and moves the anchor.
+        selection.toggle(4);
+        selection.extend_to(0);
+        assert_eq!(
+            selection.selected.iter().copied().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4, 5]
+        );
+
+        selection.truncate(3);
+        assert_eq!(
+            selection.selected.iter().copied().collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+        assert_eq!(selection.anchor, None);
+
+        selection.clear();
+        selection.extend_to(1);
+        assert_eq!(
+            selection.selected.iter().copied().collect::<Vec<_>>(),
+            vec![1]
+        );
+        selection.select_all(3);
+        assert_eq!(selection.selected.len(), 3);
+    }
+}
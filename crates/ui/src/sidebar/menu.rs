@@ -1,6 +1,10 @@
-use crate::{h_flex, v_flex, ActiveTheme as _, Collapsible, Icon, IconName, StyledExt};
+use crate::{
+    h_flex,
+    router::{Route, Router},
+    v_flex, ActiveTheme as _, Collapsible, Icon, IconName, StyledExt,
+};
 use gpui::{
-    div, percentage, prelude::FluentBuilder as _, AnyElement, App, ClickEvent, ElementId,
+    div, percentage, prelude::FluentBuilder as _, AnyElement, App, ClickEvent, ElementId, Entity,
     InteractiveElement as _, IntoElement, ParentElement as _, RenderOnce, SharedString,
     StatefulInteractiveElement as _, Styled as _, Window,
 };
@@ -73,6 +77,7 @@ pub struct SidebarMenuItem {
     collapsed: bool,
     children: Vec<Self>,
     suffix: Option<AnyElement>,
+    route: Option<(Entity<Router>, Route)>,
 }
 
 impl SidebarMenuItem {
@@ -87,6 +92,7 @@ impl SidebarMenuItem {
             collapsed: false,
             children: Vec::new(),
             suffix: None,
+            route: None,
         }
     }
 
@@ -102,6 +108,15 @@ impl SidebarMenuItem {
         self
     }
 
+    /// Bind the menu item to a route of the [`Router`].
+    ///
+    /// The item is active when the route is active, and navigates to the route when clicked,
+    /// the click handler is still called after the navigation.
+    pub fn route(mut self, router: &Entity<Router>, route: impl Into<Route>) -> Self {
+        self.route = Some((router.clone(), route.into()));
+        self
+    }
+
     /// Add a click handler to the menu item
     pub fn on_click(
         mut self,
@@ -148,15 +163,20 @@ impl SidebarMenuItem {
 }
 
 impl RenderOnce for SidebarMenuItem {
-    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
-        let handler = self.handler.clone();
+    fn render(mut self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        if let Some((router, route)) = &self.route {
+            self.active |= router.read(cx).is_active(route);
+        }
+
         let is_collapsed = self.collapsed;
         let is_active = self.active;
         let is_open = self.is_open();
         let is_submenu = self.is_submenu();
+        let handler = self.handler;
+        let route = self.route;
 
         div()
-            .id(self.id.clone())
+            .id(self.id)
             .w_full()
             .child(
                 h_flex()
@@ -181,7 +201,7 @@ impl RenderOnce for SidebarMenuItem {
                             .bg(cx.theme().sidebar_accent)
                             .text_color(cx.theme().sidebar_accent_foreground)
                     })
-                    .when_some(self.icon.clone(), |this, icon| this.child(icon))
+                    .when_some(self.icon, |this, icon| this.child(icon))
                     .when(is_collapsed, |this| {
                         this.justify_center().when(is_active, |this| {
                             this.bg(cx.theme().sidebar_accent)
@@ -196,12 +216,7 @@ impl RenderOnce for SidebarMenuItem {
                                     .gap_x_2()
                                     .justify_between()
                                     .overflow_x_hidden()
-                                    .child(
-                                        h_flex()
-                                            .flex_1()
-                                            .overflow_x_hidden()
-                                            .child(self.label.clone()),
-                                    )
+                                    .child(h_flex().flex_1().overflow_x_hidden().child(self.label))
                                     .when_some(self.suffix, |this, suffix| this.child(suffix)),
                             )
                             .when(is_submenu, |this| {
@@ -212,7 +227,12 @@ impl RenderOnce for SidebarMenuItem {
                                 )
                             })
                     })
-                    .on_click(move |ev, window, cx| handler(ev, window, cx)),
+                    .on_click(move |ev, window, cx| {
+                        if let Some((router, route)) = &route {
+                            router.update(cx, |router, cx| router.navigate(route.clone(), cx));
+                        }
+                        handler(ev, window, cx)
+                    }),
             )
             .when(is_submenu && is_open && !is_collapsed, |this| {
                 this.child(
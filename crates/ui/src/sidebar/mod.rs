@@ -1,12 +1,16 @@
 use crate::{
+    backdrop::Material,
     button::{Button, ButtonVariants},
+    elevation::Elevation,
     h_flex,
+    modal::overlay_color,
     scroll::ScrollbarAxis,
-    v_flex, ActiveTheme, Collapsible, Icon, IconName, Side, Sizable, StyledExt,
+    v_flex, ActiveTheme, Collapsible, Icon, IconName, Root, Side, Sizable, StyledExt,
 };
 use gpui::{
-    div, prelude::FluentBuilder, px, AnyElement, App, ClickEvent, DefiniteLength,
-    InteractiveElement as _, IntoElement, ParentElement, Pixels, RenderOnce, Styled, Window,
+    actions, anchored, deferred, div, point, prelude::FluentBuilder, px, Action, AnyElement, App,
+    ClickEvent, DefiniteLength, InteractiveElement as _, IntoElement, KeyBinding, MouseButton,
+    ParentElement, Pixels, RenderOnce, Styled, Window,
 };
 use std::rc::Rc;
 
@@ -19,6 +23,74 @@ pub use group::*;
 pub use header::*;
 pub use menu::*;
 
+actions!(
+    sidebar,
+    [
+        ToggleSidebar,
+        ToggleLeftSidebar,
+        ToggleRightSidebar,
+        CloseSidebarOverlay
+    ]
+);
+
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("secondary-b", ToggleSidebar, Some("Root")),
+        KeyBinding::new("escape", CloseSidebarOverlay, Some("Root")),
+    ]);
+}
+
+struct SidebarToggle {
+    side: Side,
+    collapsed: bool,
+    /// Whether the sidebar is shown as an overlay, see [`Sidebar::overlay_below`].
+    overlay: bool,
+    on_toggle: Rc<dyn Fn(&bool, &mut Window, &mut App)>,
+}
+
+/// The toggle handlers of the [`Sidebar`]s rendered in a window, kept by the [`Root`]
+/// to handle the [`ToggleSidebar`] actions.
+#[derive(Default)]
+pub(crate) struct SidebarToggles {
+    entries: Vec<SidebarToggle>,
+}
+
+impl SidebarToggles {
+    /// Start to collect the sidebars rendered in the frame.
+    pub(crate) fn begin_frame(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Toggle the first sidebar on the `side`, or the first sidebar if `None`,
+    /// returns false if there is no sidebar to toggle.
+    pub(crate) fn toggle(&self, side: Option<Side>, window: &mut Window, cx: &mut App) -> bool {
+        let Some(entry) = self
+            .entries
+            .iter()
+            .find(|entry| side.is_none_or(|side| side == entry.side))
+        else {
+            return false;
+        };
+
+        (entry.on_toggle)(&!entry.collapsed, window, cx);
+        true
+    }
+
+    /// Collapse the opened overlay sidebars, returns false if there is none.
+    pub(crate) fn close_overlays(&self, window: &mut Window, cx: &mut App) -> bool {
+        let mut closed = false;
+        for entry in self
+            .entries
+            .iter()
+            .filter(|entry| entry.overlay && !entry.collapsed)
+        {
+            (entry.on_toggle)(&true, window, cx);
+            closed = true;
+        }
+        closed
+    }
+}
+
 const DEFAULT_WIDTH: Pixels = px(255.);
 const COLLAPSED_WIDTH: Pixels = px(48.);
 
@@ -36,6 +108,8 @@ pub struct Sidebar<E: Collapsible + IntoElement + 'static> {
     width: DefiniteLength,
     border_width: Pixels,
     collapsed: bool,
+    overlay_below: Option<Pixels>,
+    on_toggle: Option<Rc<dyn Fn(&bool, &mut Window, &mut App)>>,
 }
 
 impl<E: Collapsible + IntoElement> Sidebar<E> {
@@ -50,6 +124,8 @@ impl<E: Collapsible + IntoElement> Sidebar<E> {
             width: DEFAULT_WIDTH.into(),
             border_width: px(1.),
             collapsed: false,
+            overlay_below: None,
+            on_toggle: None,
         }
     }
 
@@ -87,6 +163,35 @@ impl<E: Collapsible + IntoElement> Sidebar<E> {
         self
     }
 
+    /// Set the handler to toggle the collapsed state, the `&bool` is the new collapsed state.
+    ///
+    /// The handler is called by the [`ToggleSidebar`] action (`cmd-b` / `ctrl-b`) and the
+    /// [`ToggleLeftSidebar`] or [`ToggleRightSidebar`] action of the side, e.g. dispatched
+    /// by the [`SidebarToggleButton`].
+    ///
+    /// The sidebar must be rendered in every frame to handle the actions, so it can't be in a cached view.
+    pub fn on_toggle(mut self, on_toggle: impl Fn(&bool, &mut Window, &mut App) + 'static) -> Self {
+        self.on_toggle = Some(Rc::new(on_toggle));
+        self
+    }
+
+    /// Show the sidebar as an overlay drawer when the window is narrower than the `width`,
+    /// instead of taking the width of the layout, default is None.
+    ///
+    /// In the overlay mode, the sidebar is hidden when collapsed, and opened over the content when
+    /// expanded, e.g. by the [`SidebarToggleButton`]. Click the scrim or press `escape` to collapse
+    /// it by the [`Sidebar::on_toggle`].
+    pub fn overlay_below(mut self, width: impl Into<Pixels>) -> Self {
+        self.overlay_below = Some(width.into());
+        self
+    }
+
+    /// Returns true if the sidebar is shown as an overlay in the window, see [`Sidebar::overlay_below`].
+    pub fn is_overlay(&self, window: &Window) -> bool {
+        self.overlay_below
+            .is_some_and(|width| window.viewport_size().width < width)
+    }
+
     /// Set the header of the sidebar.
     pub fn header(mut self, header: impl IntoElement) -> Self {
         self.header = Some(header.into_any_element());
@@ -154,6 +259,9 @@ impl SidebarToggleButton {
     }
 
     /// Add a click handler to the toggle button.
+    ///
+    /// Default to dispatch the [`ToggleLeftSidebar`] or [`ToggleRightSidebar`] action of the side,
+    /// see [`Sidebar::on_toggle`].
     pub fn on_click(
         mut self,
         on_click: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
@@ -182,27 +290,53 @@ impl RenderOnce for SidebarToggleButton {
             }
         };
 
+        let action: Box<dyn Action> = if self.side.is_left() {
+            Box::new(ToggleLeftSidebar)
+        } else {
+            Box::new(ToggleRightSidebar)
+        };
+
         self.btn
-            .when_some(on_click, |this, on_click| {
-                this.on_click(move |ev, window, cx| {
-                    on_click(ev, window, cx);
-                })
+            .on_click(move |ev, window, cx| match &on_click {
+                Some(on_click) => on_click(ev, window, cx),
+                None => window.dispatch_action(action.boxed_clone(), cx),
             })
             .icon(Icon::new(icon).size_4())
     }
 }
 
 impl<E: Collapsible + IntoElement> RenderOnce for Sidebar<E> {
-    fn render(mut self, _: &mut Window, cx: &mut App) -> impl IntoElement {
-        v_flex()
+    fn render(mut self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let overlay = self.is_overlay(window);
+        let on_toggle = self.on_toggle.take().filter(|_| self.collapsible);
+        if let Some(on_toggle) = on_toggle.clone() {
+            if let Some(Some(root)) = window.root::<Root>() {
+                root.update(cx, |root, _| {
+                    root.sidebars.entries.push(SidebarToggle {
+                        side: self.side,
+                        collapsed: self.collapsed,
+                        overlay,
+                        on_toggle,
+                    });
+                });
+            }
+        }
+
+        if overlay && self.collapsed {
+            return div().into_any_element();
+        }
+
+        // The overlay sidebar is always expanded when opened.
+        let collapsed = self.collapsed && !overlay;
+        let sidebar = v_flex()
             .id("sidebar")
             .w(self.width)
-            .when(self.collapsed, |this| this.w(COLLAPSED_WIDTH))
+            .when(collapsed, |this| this.w(COLLAPSED_WIDTH))
             .flex_shrink_0()
             .h_full()
             .overflow_hidden()
             .relative()
-            .bg(cx.theme().sidebar)
+            .bg(Material::Sidebar.color(cx))
             .text_color(cx.theme().sidebar_foreground)
             .border_color(cx.theme().sidebar_border)
             .map(|this| match self.side {
@@ -219,7 +353,7 @@ impl<E: Collapsible + IntoElement> RenderOnce for Sidebar<E> {
                             self.content
                                 .into_iter()
                                 .enumerate()
-                                .map(|(ix, c)| div().id(ix).child(c.collapsed(self.collapsed))),
+                                .map(|(ix, c)| div().id(ix).child(c.collapsed(collapsed))),
                         )
                         .gap_2()
                         .scrollable(ScrollbarAxis::Vertical),
@@ -227,6 +361,50 @@ impl<E: Collapsible + IntoElement> RenderOnce for Sidebar<E> {
             )
             .when_some(self.footer.take(), |this, footer| {
                 this.child(h_flex().id("footer").gap_2().p_2().child(footer))
-            })
+            });
+
+        if !overlay {
+            return sidebar.into_any_element();
+        }
+
+        let window_paddings = crate::window_border::window_paddings(window);
+        let size = window.viewport_size()
+            - gpui::size(
+                window_paddings.left + window_paddings.right,
+                window_paddings.top + window_paddings.bottom,
+            );
+
+        deferred(
+            anchored()
+                .position(point(window_paddings.left, window_paddings.top))
+                .snap_to_window()
+                .child(
+                    div()
+                        .occlude()
+                        .w(size.width)
+                        .h(size.height)
+                        .bg(overlay_color(true, cx))
+                        .on_mouse_down(MouseButton::Left, move |_, window, cx| {
+                            cx.stop_propagation();
+                            if let Some(on_toggle) = on_toggle.as_ref() {
+                                on_toggle(&true, window, cx);
+                            }
+                        })
+                        .child(
+                            sidebar
+                                .absolute()
+                                .top_0()
+                                .bottom_0()
+                                .map(|this| match self.side {
+                                    Side::Left => this.left_0(),
+                                    Side::Right => this.right_0(),
+                                })
+                                .occlude()
+                                .elevation(Elevation::Modal, cx),
+                        ),
+                ),
+        )
+        .with_priority(1)
+        .into_any_element()
     }
 }
@@ -1,7 +1,7 @@
-use crate::{ActiveTheme, StyledExt};
+use crate::{animation::MotionSettings, ActiveTheme, StyledExt};
 use gpui::{
-    bounce, div, ease_in_out, Animation, AnimationExt, IntoElement, RenderOnce, StyleRefinement,
-    Styled,
+    bounce, div, ease_in_out, prelude::FluentBuilder as _, Animation, AnimationExt, IntoElement,
+    RenderOnce, StyleRefinement, Styled,
 };
 use std::time::Duration;
 
@@ -45,15 +45,23 @@ impl RenderOnce for Skeleton {
                 cx.theme().skeleton
             })
             .refine_style(&self.style)
-            .with_animation(
-                "skeleton",
-                Animation::new(Duration::from_secs(2))
-                    .repeat()
-                    .with_easing(bounce(ease_in_out)),
-                move |this, delta| {
-                    let v = 1.0 - delta * 0.5;
-                    this.opacity(v)
-                },
-            )
+            .map(|this| {
+                // The looping pulse is only in the full motion.
+                if !MotionSettings::global(cx).is_full() {
+                    return this.into_any_element();
+                }
+
+                this.with_animation(
+                    "skeleton",
+                    Animation::new(Duration::from_secs(2))
+                        .repeat()
+                        .with_easing(bounce(ease_in_out)),
+                    move |this, delta| {
+                        let v = 1.0 - delta * 0.5;
+                        this.opacity(v)
+                    },
+                )
+                .into_any_element()
+            })
     }
 }
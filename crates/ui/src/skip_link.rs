@@ -0,0 +1,102 @@
+use gpui::{
+    div, prelude::FluentBuilder as _, App, ElementId, FocusHandle, InteractiveElement as _,
+    IntoElement, KeyBinding, ParentElement, RenderOnce, SharedString,
+    StatefulInteractiveElement as _, StyleRefinement, Styled, Window,
+};
+
+use crate::{actions::Confirm, ActiveTheme, StyledExt as _};
+
+const CONTEXT: &str = "SkipLink";
+
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("enter", Confirm { secondary: false }, Some(CONTEXT)),
+        KeyBinding::new("space", Confirm { secondary: false }, Some(CONTEXT)),
+    ]);
+}
+
+/// A "Skip to content" link, it is invisible until focused by the Tab key,
+/// and moves the focus to the target to skip the dense UI before it, e.g. the toolbar.
+///
+/// It is before the other elements in the tab order, render it as the last child
+/// of the window to show above the content.
+///
+/// ```ignore
+/// div()
+///     .size_full()
+///     .child(toolbar)
+///     .child(div().track_focus(&self.content_focus).child(content))
+///     .child(SkipLink::new("skip-to-content", "Skip to content", &self.content_focus))
+/// ```
+#[derive(IntoElement)]
+pub struct SkipLink {
+    id: ElementId,
+    label: SharedString,
+    target: FocusHandle,
+    style: StyleRefinement,
+}
+
+impl SkipLink {
+    /// Create a new skip link to focus the `target`.
+    pub fn new(
+        id: impl Into<ElementId>,
+        label: impl Into<SharedString>,
+        target: &FocusHandle,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+            target: target.clone(),
+            style: StyleRefinement::default(),
+        }
+    }
+}
+
+impl Styled for SkipLink {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for SkipLink {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let focus_handle = window
+            .use_keyed_state(self.id.clone(), cx, |_, cx| cx.focus_handle())
+            .read(cx)
+            .clone();
+        let is_focused = focus_handle.is_focused(window);
+        let target = self.target.clone();
+
+        div()
+            .id(self.id)
+            .key_context(CONTEXT)
+            .track_focus(&focus_handle.tab_index(-1).tab_stop(true))
+            .absolute()
+            .top_2()
+            .left_2()
+            .map(|this| {
+                if is_focused {
+                    this.px_3()
+                        .py_1p5()
+                        .text_sm()
+                        .bg(cx.theme().popover)
+                        .text_color(cx.theme().link)
+                        .border_2()
+                        .border_color(cx.theme().ring)
+                        .rounded(cx.theme().radius)
+                        .shadow_md()
+                        .cursor_pointer()
+                } else {
+                    // Keep it in the tab order without taking any space.
+                    this.size_0().overflow_hidden()
+                }
+            })
+            .refine_style(&self.style)
+            .on_action({
+                let target = target.clone();
+                move |_: &Confirm, window, _| target.focus(window)
+            })
+            .on_click(move |_, window, _| target.focus(window))
+            .child(self.label)
+    }
+}
@@ -0,0 +1,238 @@
+use std::{collections::HashMap, path::PathBuf, rc::Rc, sync::Arc};
+
+use gpui::{App, Global, SharedString};
+
+/// The named sound of the audio feedback, played by the [`SoundFeedback`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Sound {
+    /// A notification is pushed, e.g.: info, success or warning.
+    Toast,
+    /// An error notification is pushed.
+    Error,
+    /// A modal or popconfirm is confirmed.
+    Confirm,
+    /// A custom sound registered by the app.
+    Custom(SharedString),
+}
+
+impl From<&'static str> for Sound {
+    fn from(name: &'static str) -> Self {
+        Self::Custom(name.into())
+    }
+}
+
+impl From<SharedString> for Sound {
+    fn from(name: SharedString) -> Self {
+        Self::Custom(name)
+    }
+}
+
+/// The audio data of a [`Sound`], decoded by the [`SoundBackend`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SoundSource {
+    /// An audio file, e.g.: `sounds/error.wav`.
+    Path(PathBuf),
+    /// The encoded bytes of an audio file, e.g.: from `include_bytes!`.
+    Bytes(Arc<[u8]>),
+}
+
+impl From<PathBuf> for SoundSource {
+    fn from(path: PathBuf) -> Self {
+        Self::Path(path)
+    }
+}
+
+impl From<&'static [u8]> for SoundSource {
+    fn from(bytes: &'static [u8]) -> Self {
+        Self::Bytes(bytes.into())
+    }
+}
+
+/// The backend to play the sounds, e.g.: rodio or the system audio API.
+///
+/// The method is called on the main thread, so the backend should play the sound
+/// without blocking.
+pub trait SoundBackend: 'static {
+    /// Play the `source` at the `volume`, in `0.0..=1.0`.
+    fn play(&self, source: &SoundSource, volume: f32, cx: &mut App);
+}
+
+/// The global audio feedback of the components, the [`Sound::Toast`] and [`Sound::Error`] are
+/// played by the notifications, and the [`Sound::Confirm`] by the modals and popconfirms.
+///
+/// It's silent until a [`SoundBackend`] is set and the sounds are registered.
+///
+/// ```ignore
+/// SoundFeedback::global_mut(cx)
+///     .set_backend(MyBackend::new())
+///     .register(Sound::Error, include_bytes!("../sounds/error.wav").as_slice())
+///     .register("done", PathBuf::from("sounds/done.wav"));
+///
+/// // Expose the mute in the settings of the app.
+/// SoundFeedback::global_mut(cx).set_muted(true);
+/// ```
+#[derive(Clone)]
+pub struct SoundFeedback {
+    backend: Option<Rc<dyn SoundBackend>>,
+    sounds: HashMap<Sound, SoundSource>,
+    volume: f32,
+    muted: bool,
+}
+
+impl Default for SoundFeedback {
+    fn default() -> Self {
+        Self {
+            backend: None,
+            sounds: HashMap::new(),
+            volume: 1.,
+            muted: false,
+        }
+    }
+}
+
+impl Global for SoundFeedback {}
+
+impl SoundFeedback {
+    /// Returns the global sound feedback.
+    pub fn global(cx: &App) -> &Self {
+        cx.global::<Self>()
+    }
+
+    /// Returns the mutable global sound feedback.
+    pub fn global_mut(cx: &mut App) -> &mut Self {
+        cx.global_mut::<Self>()
+    }
+
+    /// Set the backend to play the sounds.
+    pub fn set_backend(&mut self, backend: impl SoundBackend) -> &mut Self {
+        self.backend = Some(Rc::new(backend));
+        self
+    }
+
+    /// Register the `source` of the `sound`, the previous source is replaced.
+    pub fn register(
+        &mut self,
+        sound: impl Into<Sound>,
+        source: impl Into<SoundSource>,
+    ) -> &mut Self {
+        self.sounds.insert(sound.into(), source.into());
+        self
+    }
+
+    /// Remove the source of the `sound`, the sound will not be played.
+    pub fn unregister(&mut self, sound: &Sound) -> &mut Self {
+        self.sounds.remove(sound);
+        self
+    }
+
+    /// Returns the source of the `sound`, `None` if not registered.
+    pub fn source(&self, sound: &Sound) -> Option<&SoundSource> {
+        self.sounds.get(sound)
+    }
+
+    /// Returns the volume, in `0.0..=1.0`, default is `1.0`.
+    pub fn volume(&self) -> f32 {
+        self.volume
+    }
+
+    /// Set the volume, clamped to `0.0..=1.0`.
+    pub fn set_volume(&mut self, volume: f32) -> &mut Self {
+        self.volume = volume.clamp(0., 1.);
+        self
+    }
+
+    /// Returns true if the sounds are muted.
+    pub fn muted(&self) -> bool {
+        self.muted
+    }
+
+    /// Mute or unmute all the sounds.
+    pub fn set_muted(&mut self, muted: bool) -> &mut Self {
+        self.muted = muted;
+        self
+    }
+
+    /// Play the `sound` by the backend, returns false if the sound is not played, e.g.:
+    /// muted, no backend or the sound is not registered.
+    pub fn play(sound: impl Into<Sound>, cx: &mut App) -> bool {
+        let Some(this) = cx.try_global::<Self>() else {
+            return false;
+        };
+        if this.muted || this.volume <= 0. {
+            return false;
+        }
+        let Some((backend, source)) = this
+            .backend
+            .clone()
+            .zip(this.source(&sound.into()).cloned())
+        else {
+            return false;
+        };
+
+        let volume = this.volume;
+        backend.play(&source, volume, cx);
+        true
+    }
+}
+
+pub(crate) fn init(cx: &mut App) {
+    cx.set_global(SoundFeedback::default());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use gpui::App;
+
+    use super::{Sound, SoundBackend, SoundFeedback, SoundSource};
+
+    struct TestBackend(Rc<RefCell<Vec<(SoundSource, f32)>>>);
+
+    impl SoundBackend for TestBackend {
+        fn play(&self, source: &SoundSource, volume: f32, _: &mut App) {
+            self.0.borrow_mut().push((source.clone(), volume));
+        }
+    }
+
+    #[gpui::test]
+    fn test_sound_feedback(cx: &mut gpui::TestAppContext) {
+        let played = Rc::new(RefCell::new(vec![]));
+        cx.update(|cx| {
+            super::init(cx);
+            // No backend
+            assert!(!SoundFeedback::play(Sound::Error, cx));
+
+            SoundFeedback::global_mut(cx)
+                .set_backend(TestBackend(played.clone()))
+                .register(Sound::Error, b"error".as_slice())
+                .register("done", b"done".as_slice())
+                .set_volume(1.5);
+            assert_eq!(SoundFeedback::global(cx).volume(), 1.);
+
+            assert!(SoundFeedback::play(Sound::Error, cx));
+            assert!(SoundFeedback::play("done", cx));
+            // Not registered
+            assert!(!SoundFeedback::play(Sound::Toast, cx));
+
+            SoundFeedback::global_mut(cx)
+                .set_volume(0.5)
+                .set_muted(true);
+            assert!(!SoundFeedback::play(Sound::Error, cx));
+            SoundFeedback::global_mut(cx).set_muted(false);
+            assert!(SoundFeedback::play(Sound::Error, cx));
+
+            SoundFeedback::global_mut(cx).unregister(&Sound::Error);
+            assert!(!SoundFeedback::play(Sound::Error, cx));
+        });
+
+        assert_eq!(
+            *played.borrow(),
+            vec![
+                (SoundSource::Bytes(b"error".as_slice().into()), 1.),
+                (SoundSource::Bytes(b"done".as_slice().into()), 1.),
+                (SoundSource::Bytes(b"error".as_slice().into()), 0.5),
+            ]
+        );
+    }
+}
@@ -0,0 +1,217 @@
+//! The spell checking for the text inputs, see [`SpellChecker`].
+
+use std::ops::Range;
+
+use gpui::SharedString;
+use unicode_segmentation::UnicodeSegmentation as _;
+
+/// A pluggable spell checker for the [`InputState`](crate::input::InputState) and the
+/// [`RichTextEditorState`](crate::rich_text_editor::RichTextEditorState).
+///
+/// The misspelled words are rendered with the squiggly underlines, and the suggestions
+/// are shown in the context menu when right click on the word.
+pub trait SpellChecker: 'static {
+    /// Returns true if the `word` is spelled correctly.
+    fn check(&self, word: &str) -> bool;
+
+    /// Returns the suggestions for the misspelled `word`, the best first.
+    fn suggestions(&self, word: &str) -> Vec<SharedString>;
+
+    /// Add the `word` to the dictionary of the user, e.g. by the "Add to Dictionary" menu.
+    fn add_word(&self, _word: &str) {}
+
+    /// Returns the byte ranges of the misspelled words in the `text`.
+    ///
+    /// Only the words of letters (and the apostrophes) are checked, the words with the
+    /// digits or the symbols like the urls are skipped.
+    fn misspelled(&self, text: &str) -> Vec<Range<usize>> {
+        words(text)
+            .filter(|(_, word)| !self.check(word))
+            .map(|(start, word)| start..start + word.len())
+            .collect()
+    }
+}
+
+/// Returns the words with the byte offsets in the `text` to check the spelling.
+pub(crate) fn words(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split_word_bound_indices().filter(|(_, word)| {
+        word.chars().any(char::is_alphabetic)
+            && word
+                .chars()
+                .all(|c| c.is_alphabetic() || c == '\'' || c == '’')
+    })
+}
+
+/// Returns the range of the word contains the `offset` in the `text`.
+pub(crate) fn word_at(text: &str, offset: usize) -> Option<Range<usize>> {
+    words(text)
+        .map(|(start, word)| start..start + word.len())
+        .find(|range| range.start <= offset && offset <= range.end)
+}
+
+#[cfg(feature = "spellcheck")]
+pub use dictionary::DictionarySpellChecker;
+
+#[cfg(feature = "spellcheck")]
+mod dictionary {
+    use std::{cell::RefCell, collections::HashSet};
+
+    use gpui::SharedString;
+
+    use super::SpellChecker;
+
+    /// The max number of the suggestions.
+    const MAX_SUGGESTIONS: usize = 5;
+
+    /// A [`SpellChecker`] with the word list of a Hunspell dictionary (the `.dic` file).
+    ///
+    /// The words are matched case-insensitively, and the suggestions are the words within
+    /// one edit (insert, delete, replace or swap a letter) of the misspelled word.
+    #[derive(Debug, Default)]
+    pub struct DictionarySpellChecker {
+        words: HashSet<String>,
+        user_words: RefCell<HashSet<String>>,
+    }
+
+    impl DictionarySpellChecker {
+        /// Create a spell checker with the content of a Hunspell `.dic` file.
+        ///
+        /// The first line of the word count is optional, and the affix flags after `/` are ignored.
+        pub fn from_dic(content: &str) -> Self {
+            let words = content
+                .lines()
+                .enumerate()
+                .filter(|(ix, line)| !(*ix == 0 && line.trim().parse::<usize>().is_ok()))
+                .filter_map(|(_, line)| {
+                    let word = line.split('/').next()?.trim();
+                    (!word.is_empty()).then(|| word.to_lowercase())
+                })
+                .collect();
+
+            Self {
+                words,
+                user_words: RefCell::default(),
+            }
+        }
+
+        /// Create a spell checker with the words.
+        pub fn new(words: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+            Self {
+                words: words
+                    .into_iter()
+                    .map(|word| word.as_ref().to_lowercase())
+                    .collect(),
+                user_words: RefCell::default(),
+            }
+        }
+
+        fn contains(&self, word: &str) -> bool {
+            self.words.contains(word) || self.user_words.borrow().contains(word)
+        }
+    }
+
+    impl SpellChecker for DictionarySpellChecker {
+        fn check(&self, word: &str) -> bool {
+            let word = word.to_lowercase().replace('’', "'");
+            self.contains(&word) || self.contains(word.trim_end_matches("'s"))
+        }
+
+        fn suggestions(&self, word: &str) -> Vec<SharedString> {
+            let lower = word.to_lowercase();
+            let chars = lower.chars().collect::<Vec<_>>();
+            let letters = ('a'..='z').chain(chars.iter().copied()).collect::<Vec<_>>();
+
+            let mut candidates = vec![];
+            for i in 0..chars.len() {
+                // Delete
+                candidates.push([&chars[..i], &chars[i + 1..]].concat());
+                // Swap
+                if i + 1 < chars.len() {
+                    let mut swapped = chars.clone();
+                    swapped.swap(i, i + 1);
+                    candidates.push(swapped);
+                }
+            }
+            for i in 0..=chars.len() {
+                for c in &letters {
+                    // Insert
+                    candidates.push([&chars[..i], &[*c], &chars[i..]].concat());
+                    // Replace
+                    if i < chars.len() && chars[i] != *c {
+                        candidates.push([&chars[..i], &[*c], &chars[i + 1..]].concat());
+                    }
+                }
+            }
+
+            let is_capitalized = word.chars().next().is_some_and(char::is_uppercase);
+            let mut suggestions: Vec<SharedString> = vec![];
+            for candidate in candidates {
+                let candidate = candidate.into_iter().collect::<String>();
+                if !self.contains(&candidate) {
+                    continue;
+                }
+
+                let suggestion: SharedString = if is_capitalized {
+                    let mut chars = candidate.chars();
+                    chars
+                        .next()
+                        .map(|c| c.to_uppercase().chain(chars).collect::<String>())
+                        .unwrap_or_default()
+                        .into()
+                } else {
+                    candidate.into()
+                };
+                if !suggestions.contains(&suggestion) {
+                    suggestions.push(suggestion);
+                }
+                if suggestions.len() >= MAX_SUGGESTIONS {
+                    break;
+                }
+            }
+            suggestions
+        }
+
+        fn add_word(&self, word: &str) {
+            self.user_words.borrow_mut().insert(word.to_lowercase());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{word_at, words};
+
+    #[test]
+    fn test_words() {
+        let text = "Hello wrld, it's 2 o’clock at https://example.com v2";
+        let words = words(text).map(|(_, word)| word).collect::<Vec<_>>();
+        assert_eq!(
+            words,
+            vec!["Hello", "wrld", "it's", "o’clock", "at", "https"]
+        );
+
+        assert_eq!(word_at("Hello wrld", 7), Some(6..10));
+        assert_eq!(word_at("Hello wrld", 10), Some(6..10));
+        assert_eq!(word_at("Hello, wrld", 5), Some(0..5));
+        assert_eq!(word_at("1 + 2", 1), None);
+    }
+
+    #[cfg(feature = "spellcheck")]
+    #[test]
+    fn test_dictionary_spell_checker() {
+        use super::{DictionarySpellChecker, SpellChecker as _};
+
+        let checker = DictionarySpellChecker::from_dic("4\nhello/S\nworld\nword/SM\nit\n");
+        assert!(checker.check("Hello"));
+        assert!(checker.check("world's"));
+        assert!(!checker.check("wrld"));
+        assert_eq!(checker.misspelled("Hello wrld, it"), vec![6..10]);
+
+        assert_eq!(checker.suggestions("wrld"), vec!["world"]);
+        assert_eq!(checker.suggestions("wod"), vec!["word"]);
+        assert_eq!(checker.suggestions("Wrold"), vec!["World"]);
+
+        checker.add_word("GPUI");
+        assert!(checker.check("gpui"));
+    }
+}
@@ -0,0 +1,218 @@
+use gpui::{
+    point, px, AnyElement, App, Bounds, Display, Element, ElementId, FlexDirection,
+    GlobalElementId, InspectorElementId, IntoElement, LayoutId, ParentElement, Pixels, Refineable,
+    Style, StyleRefinement, Styled, Window,
+};
+use smallvec::SmallVec;
+
+/// Create a [`Sticky`] section with the `element` pinned to the top.
+pub fn sticky(element: impl IntoElement) -> Sticky {
+    Sticky::new(element)
+}
+
+/// A section with an element pinned to the top (or bottom) of the scroll container
+/// while the section is in view, e.g. the section headers of a list, the group headers
+/// of a table and the section labels of a form.
+///
+/// The pinned element is pushed away by the end of the section, so the sections in
+/// the same scroll container are replaced one by one when scrolling.
+///
+/// The pinned element is painted above the content of the section, so it should have a background.
+///
+/// ```ignore
+/// v_flex()
+///     .id("contacts")
+///     .overflow_y_scroll()
+///     .children(groups.iter().map(|group| {
+///         sticky(div().bg(cx.theme().background).child(group.name.clone()))
+///             .gap_1()
+///             .children(group.contacts.iter().map(|contact| contact_item(contact)))
+///     }))
+/// ```
+pub struct Sticky {
+    element: AnyElement,
+    at_bottom: bool,
+    style: StyleRefinement,
+    children: SmallVec<[AnyElement; 2]>,
+}
+
+impl Sticky {
+    /// Create a new section with the `element` pinned to the top.
+    pub fn new(element: impl IntoElement) -> Self {
+        Self {
+            element: element.into_any_element(),
+            at_bottom: false,
+            style: StyleRefinement::default(),
+            children: SmallVec::new(),
+        }
+    }
+
+    /// Place the element at the end of the section, and pin it to the bottom of the scroll container.
+    pub fn at_bottom(mut self) -> Self {
+        self.at_bottom = true;
+        self
+    }
+}
+
+/// Returns the vertical offset to keep the element of the `element_height` inside
+/// both of the `section` and the visible `viewport` of the scroll container.
+fn sticky_offset(
+    section: Bounds<Pixels>,
+    element_height: Pixels,
+    viewport: Bounds<Pixels>,
+    at_bottom: bool,
+) -> Pixels {
+    let max_offset = (section.size.height - element_height).max(px(0.));
+    if at_bottom {
+        -(section.bottom() - viewport.bottom()).clamp(px(0.), max_offset)
+    } else {
+        (viewport.top() - section.top()).clamp(px(0.), max_offset)
+    }
+}
+
+impl ParentElement for Sticky {
+    fn extend(&mut self, elements: impl IntoIterator<Item = AnyElement>) {
+        self.children.extend(elements);
+    }
+}
+
+impl Styled for Sticky {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl IntoElement for Sticky {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Element for Sticky {
+    type RequestLayoutState = LayoutId;
+    type PrepaintState = ();
+
+    fn id(&self) -> Option<ElementId> {
+        None
+    }
+
+    fn source_location(&self) -> Option<&'static std::panic::Location<'static>> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        _: Option<&InspectorElementId>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let mut style = Style {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        };
+        style.refine(&self.style);
+
+        let element_layout_id = self.element.request_layout(window, cx);
+        let mut layout_ids = self
+            .children
+            .iter_mut()
+            .map(|child| child.request_layout(window, cx))
+            .collect::<Vec<_>>();
+        if self.at_bottom {
+            layout_ids.push(element_layout_id);
+        } else {
+            layout_ids.insert(0, element_layout_id);
+        }
+
+        (
+            window.request_layout(style, layout_ids, cx),
+            element_layout_id,
+        )
+    }
+
+    fn prepaint(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        _: Option<&InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        element_layout_id: &mut Self::RequestLayoutState,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self::PrepaintState {
+        for child in &mut self.children {
+            child.prepaint(window, cx);
+        }
+
+        // The content mask is the visible bounds of the scroll container.
+        let element_height = window.layout_bounds(*element_layout_id).size.height;
+        let viewport = window.content_mask().bounds;
+        let offset = sticky_offset(bounds, element_height, viewport, self.at_bottom);
+        window.with_element_offset(point(px(0.), offset), |window| {
+            self.element.prepaint(window, cx);
+        });
+    }
+
+    fn paint(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        _: Option<&InspectorElementId>,
+        _: Bounds<Pixels>,
+        _: &mut Self::RequestLayoutState,
+        _: &mut Self::PrepaintState,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        for child in &mut self.children {
+            child.paint(window, cx);
+        }
+        // Paint the pinned element above the content.
+        self.element.paint(window, cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{point, px, size, Bounds};
+
+    use super::sticky_offset;
+
+    #[test]
+    fn test_sticky_offset() {
+        let viewport = Bounds::new(point(px(0.), px(100.)), size(px(100.), px(200.)));
+        let section = |top: f32| Bounds::new(point(px(0.), px(top)), size(px(100.), px(150.)));
+
+        // In view, not moved.
+        assert_eq!(
+            sticky_offset(section(120.), px(20.), viewport, false),
+            px(0.)
+        );
+        // Scrolled above the viewport, pinned to the top.
+        assert_eq!(
+            sticky_offset(section(60.), px(20.), viewport, false),
+            px(40.)
+        );
+        // Pushed away by the end of the section.
+        assert_eq!(
+            sticky_offset(section(-40.), px(20.), viewport, false),
+            px(130.)
+        );
+
+        // Below the viewport, pinned to the bottom.
+        assert_eq!(
+            sticky_offset(section(200.), px(20.), viewport, true),
+            px(-50.)
+        );
+        assert_eq!(
+            sticky_offset(section(120.), px(20.), viewport, true),
+            px(0.)
+        );
+        assert_eq!(
+            sticky_offset(section(400.), px(20.), viewport, true),
+            px(-130.)
+        );
+    }
+}
@@ -1,6 +1,8 @@
 use std::fmt::{self, Display, Formatter};
 
 use crate::{
+    backdrop::Material,
+    elevation::Elevation,
     scroll::{Scrollable, ScrollbarAxis},
     ActiveTheme,
 };
@@ -9,6 +11,7 @@ use gpui::{
     Element, FocusHandle, Hsla, Length, ParentElement, Pixels, Refineable, StyleRefinement, Styled,
     Window,
 };
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Returns a `Div` as horizontal flex layout.
@@ -192,14 +195,21 @@ pub trait StyledExt: Styled + Sized {
     /// Set as Popover style
     #[inline]
     fn popover_style(self, cx: &App) -> Self {
-        self.bg(cx.theme().popover)
+        self.bg(Material::Popover.color(cx))
             .text_color(cx.theme().popover_foreground)
             .border_1()
             .border_color(cx.theme().border)
-            .shadow_lg()
+            .elevation(Elevation::Popup, cx)
             .rounded(cx.theme().radius)
     }
 
+    /// Set the shadow of the elevation.
+    #[inline]
+    fn elevation(mut self, elevation: Elevation, cx: &App) -> Self {
+        self.style().box_shadow = Some(elevation.shadow(cx));
+        self
+    }
+
     /// Set corner radii for the element.
     fn corner_radii(self, radius: Corners<Pixels>) -> Self {
         self.rounded_tl(radius.top_left)
@@ -346,6 +356,30 @@ impl Size {
     }
 }
 
+/// The spacing density of the dense components, e.g. the [`Table`](crate::table::Table),
+/// it adjusts the paddings and heights by one step of the [`Size`].
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Density {
+    /// One step smaller, to show more rows.
+    Compact,
+    #[default]
+    Normal,
+    /// One step larger, for the touch or the readability.
+    Comfortable,
+}
+
+impl Density {
+    /// Returns the size adjusted by the density, the [`Size::Size`] is not changed.
+    pub fn size(&self, size: Size) -> Size {
+        match (self, size) {
+            (_, Size::Size(_)) | (Density::Normal, _) => size,
+            (Density::Compact, size) => size.smaller(),
+            (Density::Comfortable, size) => size.larger(),
+        }
+    }
+}
+
 impl From<Pixels> for Size {
     fn from(size: Pixels) -> Self {
         Size::Size(size)
@@ -735,7 +769,7 @@ impl LengthExt for Length {
 mod tests {
     use gpui::px;
 
-    use crate::Size;
+    use crate::{Density, Size};
 
     #[test]
     fn test_size_max_min() {
@@ -762,4 +796,17 @@ mod tests {
             Size::Size(px(10.))
         );
     }
+
+    #[test]
+    fn test_density_size() {
+        assert_eq!(Density::Normal.size(Size::Medium), Size::Medium);
+        assert_eq!(Density::Compact.size(Size::Medium), Size::Small);
+        assert_eq!(Density::Compact.size(Size::XSmall), Size::XSmall);
+        assert_eq!(Density::Comfortable.size(Size::Medium), Size::Large);
+        assert_eq!(Density::Comfortable.size(Size::Large), Size::Large);
+        assert_eq!(
+            Density::Compact.size(Size::Size(px(30.))),
+            Size::Size(px(30.))
+        );
+    }
 }
@@ -1,5 +1,6 @@
 use crate::{
-    h_flex, text::Text, tooltip::Tooltip, ActiveTheme, Disableable, Side, Sizable, Size, StyledExt,
+    animation::MotionSettings, h_flex, text::Text, tooltip::Tooltip, ActiveTheme, Disableable,
+    Side, Sizable, Size, StyledExt,
 };
 use gpui::{
     div, prelude::FluentBuilder as _, px, Animation, AnimationExt as _, App, ElementId,
@@ -155,7 +156,9 @@ impl RenderOnce for Switch {
                                 .map(|this| {
                                     let prev_checked = toggle_state.read(cx);
                                     if !self.disabled && *prev_checked != checked {
-                                        let duration = Duration::from_secs_f64(0.15);
+                                        let motion = MotionSettings::global(cx);
+                                        let duration =
+                                            motion.duration(Duration::from_secs_f64(0.15));
                                         cx.spawn({
                                             let toggle_state = toggle_state.clone();
                                             async move |cx| {
@@ -168,8 +171,11 @@ impl RenderOnce for Switch {
 
                                         this.with_animation(
                                             ElementId::NamedInteger("move".into(), checked as u64),
-                                            Animation::new(duration),
+                                            motion.animation(Animation::new(
+                                                Duration::from_secs_f64(0.15),
+                                            )),
                                             move |this, delta| {
+                                                let delta = motion.motion_delta(delta);
                                                 let max_x = bg_width - bar_width - inset * 2;
                                                 let x = if checked {
                                                     max_x * delta
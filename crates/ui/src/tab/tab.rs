@@ -1,9 +1,13 @@
 use std::rc::Rc;
 
-use crate::{h_flex, ActiveTheme, Icon, IconName, Selectable, Sizable, Size, StyledExt};
+use crate::{
+    h_flex,
+    router::{Route, Router},
+    ActiveTheme, Icon, IconName, Selectable, Sizable, Size, StyledExt,
+};
 use gpui::prelude::FluentBuilder as _;
 use gpui::{
-    div, px, relative, AnyElement, App, ClickEvent, Div, Edges, ElementId, Hsla,
+    div, px, relative, AnyElement, App, ClickEvent, Div, Edges, ElementId, Entity, Hsla,
     InteractiveElement, IntoElement, ParentElement, Pixels, RenderOnce, SharedString,
     StatefulInteractiveElement, Styled, Window,
 };
@@ -394,6 +398,7 @@ pub struct Tab {
     pub(super) disabled: bool,
     pub(super) selected: bool,
     on_click: Option<Rc<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>>,
+    route: Option<(Entity<Router>, Route)>,
 }
 
 impl From<&'static str> for Tab {
@@ -443,6 +448,7 @@ impl Default for Tab {
             variant: TabVariant::default(),
             size: Size::default(),
             on_click: None,
+            route: None,
         }
     }
 }
@@ -518,6 +524,15 @@ impl Tab {
         self
     }
 
+    /// Bind the tab to a route of the [`Router`].
+    ///
+    /// The tab is selected when the route is active, this takes precedence over the
+    /// `selected_index` of the [`TabBar`](super::TabBar), and navigates to the route when clicked.
+    pub fn route(mut self, router: &Entity<Router>, route: impl Into<Route>) -> Self {
+        self.route = Some((router.clone(), route.into()));
+        self
+    }
+
     /// Set id to the tab.
     pub(super) fn id(mut self, id: impl Into<ElementId>) -> Self {
         self.id = id.into();
@@ -564,7 +579,11 @@ impl Sizable for Tab {
 }
 
 impl RenderOnce for Tab {
-    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+    fn render(mut self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        if let Some((router, route)) = &self.route {
+            self.selected = router.read(cx).is_active(route);
+        }
+
         let mut tab_style = if self.selected {
             self.variant.selected(cx)
         } else {
@@ -653,7 +672,12 @@ impl RenderOnce for Tab {
             )
             .when_some(self.suffix, |this, suffix| this.child(suffix))
             .when(!self.disabled, |this| {
-                this.when_some(self.on_click.clone(), |this, on_click| {
+                this.when_some(self.route, |this, (router, route)| {
+                    this.on_click(move |_, _, cx| {
+                        router.update(cx, |router, cx| router.navigate(route.clone(), cx));
+                    })
+                })
+                .when_some(self.on_click.clone(), |this, on_click| {
                     this.on_click(move |event, window, cx| on_click(event, window, cx))
                 })
             })
@@ -0,0 +1,154 @@
+use std::{fmt, ops::Range, rc::Rc};
+
+/// The aggregation of a column, shown in the footer of the [`Table`](super::Table).
+///
+/// The values are from the [`TableDelegate::column_value`](super::TableDelegate::column_value),
+/// the rows without value (`None`) are skipped.
+#[derive(Clone)]
+pub enum Aggregator {
+    /// The sum of the values.
+    Sum,
+    /// The average of the values.
+    Avg,
+    /// The minimum value.
+    Min,
+    /// The maximum value.
+    Max,
+    /// The number of the rows with value.
+    Count,
+    /// A custom aggregation of the values, in the order of the rows.
+    Custom(Rc<dyn Fn(&[f64]) -> Option<f64>>),
+}
+
+impl Aggregator {
+    /// Create a custom aggregator with the function of the values.
+    pub fn custom(f: impl Fn(&[f64]) -> Option<f64> + 'static) -> Self {
+        Self::Custom(Rc::new(f))
+    }
+
+    /// Returns the aggregation of the values.
+    pub fn aggregate(&self, values: impl IntoIterator<Item = f64>) -> Option<f64> {
+        let mut state = AggregateState::default();
+        state.splice(0..0, values.into_iter().map(Some).collect());
+        state.value(self)
+    }
+}
+
+impl fmt::Debug for Aggregator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sum => write!(f, "Sum"),
+            Self::Avg => write!(f, "Avg"),
+            Self::Min => write!(f, "Min"),
+            Self::Max => write!(f, "Max"),
+            Self::Count => write!(f, "Count"),
+            Self::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+/// The running aggregation of a column, updated incrementally when the rows change.
+///
+/// The sum and count are updated by the changed rows only, the min and max are
+/// recalculated from all the rows only when the current min or max row is removed.
+#[derive(Debug, Default, Clone)]
+pub(super) struct AggregateState {
+    values: Vec<Option<f64>>,
+    sum: f64,
+    count: usize,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl AggregateState {
+    /// Returns the number of the rows.
+    pub(super) fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Replace the values of the rows in the `range` by the `new_values`.
+    pub(super) fn splice(&mut self, range: Range<usize>, new_values: Vec<Option<f64>>) {
+        let range = range.start.min(self.values.len())..range.end.min(self.values.len());
+        let mut extremes_removed = false;
+        for value in self.values[range.clone()].iter().flatten() {
+            self.sum -= value;
+            self.count -= 1;
+            extremes_removed |= Some(*value) == self.min || Some(*value) == self.max;
+        }
+
+        for value in new_values.iter().flatten() {
+            self.sum += value;
+            self.count += 1;
+            self.min = Some(self.min.map_or(*value, |min| min.min(*value)));
+            self.max = Some(self.max.map_or(*value, |max| max.max(*value)));
+        }
+        self.values.splice(range, new_values);
+
+        if self.count == 0 {
+            self.sum = 0.;
+            self.min = None;
+            self.max = None;
+        } else if extremes_removed {
+            let values = self.values.iter().flatten();
+            self.min = values.clone().copied().reduce(f64::min);
+            self.max = values.copied().reduce(f64::max);
+        }
+    }
+
+    /// Returns the aggregation by the `aggregator`, `None` if there is no value.
+    pub(super) fn value(&self, aggregator: &Aggregator) -> Option<f64> {
+        match aggregator {
+            Aggregator::Sum => (self.count > 0).then_some(self.sum),
+            Aggregator::Avg => (self.count > 0).then(|| self.sum / self.count as f64),
+            Aggregator::Min => self.min,
+            Aggregator::Max => self.max,
+            Aggregator::Count => Some(self.count as f64),
+            Aggregator::Custom(f) => {
+                let values = self.values.iter().flatten().copied().collect::<Vec<_>>();
+                f(&values)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AggregateState, Aggregator};
+
+    #[test]
+    fn test_aggregate_state() {
+        let mut state = AggregateState::default();
+        state.splice(0..0, vec![Some(3.), None, Some(1.), Some(5.)]);
+        assert_eq!(state.len(), 4);
+        assert_eq!(state.value(&Aggregator::Sum), Some(9.));
+        assert_eq!(state.value(&Aggregator::Avg), Some(3.));
+        assert_eq!(state.value(&Aggregator::Min), Some(1.));
+        assert_eq!(state.value(&Aggregator::Max), Some(5.));
+        assert_eq!(state.value(&Aggregator::Count), Some(3.));
+
+        // Remove the max, and update a row.
+        state.splice(3..4, vec![]);
+        state.splice(1..2, vec![Some(2.)]);
+        assert_eq!(state.value(&Aggregator::Sum), Some(6.));
+        assert_eq!(state.value(&Aggregator::Max), Some(3.));
+        assert_eq!(state.value(&Aggregator::Min), Some(1.));
+
+        let median = Aggregator::custom(|values| {
+            let mut values = values.to_vec();
+            values.sort_by(f64::total_cmp);
+            values.get(values.len() / 2).copied()
+        });
+        assert_eq!(state.value(&median), Some(2.));
+
+        state.splice(0..3, vec![None]);
+        assert_eq!(state.value(&Aggregator::Sum), None);
+        assert_eq!(state.value(&Aggregator::Min), None);
+        assert_eq!(state.value(&Aggregator::Count), Some(0.));
+    }
+
+    #[test]
+    fn test_aggregate() {
+        assert_eq!(Aggregator::Avg.aggregate([1., 2., 6.]), Some(3.));
+        assert_eq!(Aggregator::Max.aggregate([]), None);
+    }
+}
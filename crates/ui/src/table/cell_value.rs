@@ -0,0 +1,351 @@
+use std::{cmp::Ordering, fmt, rc::Rc};
+
+use chrono::{NaiveDate, NaiveDateTime};
+use gpui::SharedString;
+
+use crate::table::ColumnSort;
+
+/// The typed value of a table cell, for the automatic sorting and filtering.
+///
+/// See [`TableDelegate::cell_value`](super::TableDelegate::cell_value).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum CellValue {
+    /// No value, always sorted at the end.
+    #[default]
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    /// A date or date time.
+    Date(NaiveDateTime),
+    Text(SharedString),
+}
+
+impl CellValue {
+    /// Returns the numeric value of `Int`, `Float` and `Bool`, for the [`Aggregator`](super::Aggregator).
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Int(value) => Some(*value as f64),
+            Self::Float(value) => Some(*value),
+            Self::Bool(value) => Some(if *value { 1. } else { 0. }),
+            _ => None,
+        }
+    }
+
+    /// The order of the different types.
+    fn rank(&self) -> u8 {
+        match self {
+            Self::Bool(_) => 0,
+            Self::Int(_) | Self::Float(_) => 1,
+            Self::Date(_) => 2,
+            Self::Text(_) => 3,
+            Self::Null => 4,
+        }
+    }
+
+    /// Compare with the other value.
+    ///
+    /// - The numbers are compared by the value, `Int` and `Float` are comparable.
+    /// - The texts are compared case-insensitively, then case-sensitively.
+    /// - The `Null` is greater than any value, the different types are ordered by
+    ///   `Bool`, numbers, `Date`, `Text`.
+    pub fn compare(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Bool(a), Self::Bool(b)) => a.cmp(b),
+            (Self::Int(a), Self::Int(b)) => a.cmp(b),
+            (Self::Date(a), Self::Date(b)) => a.cmp(b),
+            (Self::Text(a), Self::Text(b)) => a
+                .to_lowercase()
+                .cmp(&b.to_lowercase())
+                .then_with(|| a.cmp(b)),
+            (a, b) if a.rank() != b.rank() => a.rank().cmp(&b.rank()),
+            (a, b) => match (a.as_f64(), b.as_f64()) {
+                (Some(a), Some(b)) => a.total_cmp(&b),
+                _ => Ordering::Equal,
+            },
+        }
+    }
+
+    /// Returns true if the value matches the `filter`.
+    ///
+    /// The filter can start with a comparison operator (`>`, `>=`, `<`, `<=`, `=`, `!=`)
+    /// to compare with the value parsed in the same type, e.g. `>= 100` or `< 2024-01-01`.
+    /// Otherwise the text of the value contains the filter case-insensitively.
+    pub fn matches(&self, filter: &str) -> bool {
+        let filter = filter.trim();
+        if filter.is_empty() {
+            return true;
+        }
+
+        let operators = [">=", "<=", "!=", ">", "<", "="];
+        if let Some((op, rest)) = operators
+            .iter()
+            .find_map(|op| filter.strip_prefix(op).map(|rest| (*op, rest.trim())))
+        {
+            let Some(other) = self.parse_same_type(rest) else {
+                return false;
+            };
+            let ordering = self.compare(&other);
+            return match op {
+                ">=" => ordering.is_ge(),
+                "<=" => ordering.is_le(),
+                "!=" => ordering.is_ne(),
+                ">" => ordering.is_gt(),
+                "<" => ordering.is_lt(),
+                _ => ordering.is_eq(),
+            };
+        }
+
+        self.to_string()
+            .to_lowercase()
+            .contains(&filter.to_lowercase())
+    }
+
+    /// Parse the `text` in the type of this value.
+    fn parse_same_type(&self, text: &str) -> Option<Self> {
+        match self {
+            Self::Null => None,
+            Self::Bool(_) => text.parse().ok().map(Self::Bool),
+            Self::Int(_) | Self::Float(_) => text.parse().ok().map(Self::Float),
+            Self::Date(_) => NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S")
+                .ok()
+                .or_else(|| {
+                    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                        .ok()
+                        .map(|date| date.and_time(Default::default()))
+                })
+                .map(Self::Date),
+            Self::Text(_) => Some(Self::Text(text.to_string().into())),
+        }
+    }
+}
+
+impl fmt::Display for CellValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Null => Ok(()),
+            Self::Bool(value) => write!(f, "{}", value),
+            Self::Int(value) => write!(f, "{}", value),
+            Self::Float(value) => write!(f, "{}", value),
+            Self::Date(value) => write!(f, "{}", value),
+            Self::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+macro_rules! impl_from {
+    ($variant:ident, $ty:ty, $($from:ty),+) => {
+        $(
+            impl From<$from> for CellValue {
+                fn from(value: $from) -> Self {
+                    Self::$variant(value as $ty)
+                }
+            }
+        )+
+    };
+}
+
+impl_from!(Int, i64, i8, i16, i32, i64, u8, u16, u32, usize, isize);
+impl_from!(Float, f64, f32, f64);
+
+impl From<bool> for CellValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<NaiveDateTime> for CellValue {
+    fn from(value: NaiveDateTime) -> Self {
+        Self::Date(value)
+    }
+}
+
+impl From<NaiveDate> for CellValue {
+    fn from(value: NaiveDate) -> Self {
+        Self::Date(value.and_time(Default::default()))
+    }
+}
+
+impl From<SharedString> for CellValue {
+    fn from(value: SharedString) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for CellValue {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string().into())
+    }
+}
+
+impl From<String> for CellValue {
+    fn from(value: String) -> Self {
+        Self::Text(value.into())
+    }
+}
+
+impl<T: Into<CellValue>> From<Option<T>> for CellValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Null, Into::into)
+    }
+}
+
+/// A custom comparator of the [`CellValue`] for a column, see [`Column::comparator`](super::Column::comparator).
+#[derive(Clone)]
+pub struct CellComparator(Rc<dyn Fn(&CellValue, &CellValue) -> Ordering>);
+
+impl CellComparator {
+    /// Create a comparator with the function.
+    pub fn new(f: impl Fn(&CellValue, &CellValue) -> Ordering + 'static) -> Self {
+        Self(Rc::new(f))
+    }
+
+    /// Compare the values.
+    pub fn compare(&self, a: &CellValue, b: &CellValue) -> Ordering {
+        (self.0)(a, b)
+    }
+}
+
+impl fmt::Debug for CellComparator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CellComparator").finish()
+    }
+}
+
+/// The values of a column to sort the rows, see [`sort_order`].
+pub(super) struct SortKey {
+    pub(super) values: Vec<CellValue>,
+    pub(super) sort: ColumnSort,
+    pub(super) comparator: Option<CellComparator>,
+}
+
+/// Returns the order of the rows sorted by the `keys`, the later keys are used when the
+/// earlier keys are equal. The `Null` values are always at the end.
+///
+/// The sort is stable, and the keys of `ColumnSort::Default` are skipped.
+pub(super) fn sort_order(keys: &[SortKey]) -> Vec<usize> {
+    let rows_count = keys.iter().map(|key| key.values.len()).min().unwrap_or(0);
+    let mut order = (0..rows_count).collect::<Vec<_>>();
+    let keys = keys
+        .iter()
+        .filter(|key| key.sort != ColumnSort::Default)
+        .collect::<Vec<_>>();
+    if keys.is_empty() {
+        return order;
+    }
+
+    order.sort_by(|a, b| {
+        keys.iter()
+            .map(|key| {
+                let (a, b) = (&key.values[*a], &key.values[*b]);
+                match (a, b) {
+                    (CellValue::Null, CellValue::Null) => Ordering::Equal,
+                    (CellValue::Null, _) => Ordering::Greater,
+                    (_, CellValue::Null) => Ordering::Less,
+                    _ => {
+                        let ordering = match &key.comparator {
+                            Some(comparator) => comparator.compare(a, b),
+                            None => a.compare(b),
+                        };
+                        if key.sort == ColumnSort::Descending {
+                            ordering.reverse()
+                        } else {
+                            ordering
+                        }
+                    }
+                }
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    });
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::{sort_order, CellComparator, CellValue, SortKey};
+    use crate::table::ColumnSort;
+
+    #[test]
+    fn test_compare() {
+        assert!(CellValue::Int(2).compare(&CellValue::Float(10.5)).is_lt());
+        assert!(CellValue::from("apple").compare(&"Banana".into()).is_lt());
+        assert!(CellValue::from("10").compare(&"9".into()).is_lt());
+        assert!(CellValue::Int(10).compare(&CellValue::Int(9)).is_gt());
+        assert!(CellValue::from(1).compare(&"a".into()).is_lt());
+        assert!(CellValue::Null.compare(&"a".into()).is_gt());
+
+        let date = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        assert!(CellValue::from(date)
+            .compare(&NaiveDate::from_ymd_opt(2023, 12, 31).unwrap().into())
+            .is_gt());
+    }
+
+    #[test]
+    fn test_matches() {
+        assert!(CellValue::Int(120).matches(">= 100"));
+        assert!(!CellValue::Int(120).matches("< 100"));
+        assert!(CellValue::Float(1.5).matches("!=2"));
+        assert!(CellValue::Int(120).matches("12"));
+        assert!(CellValue::from("Hello World").matches("world"));
+        assert!(!CellValue::from("Hello").matches("< abc"));
+
+        let date = CellValue::from(NaiveDate::from_ymd_opt(2024, 5, 1).unwrap());
+        assert!(date.matches("> 2024-01-01"));
+        assert!(!date.matches("> 2025-01-01"));
+        assert!(!date.matches("> invalid"));
+        assert!(!CellValue::Null.matches("> 1"));
+        assert!(CellValue::Null.matches(""));
+    }
+
+    #[test]
+    fn test_sort_order() {
+        let key = |values: &[CellValue], sort, comparator| SortKey {
+            values: values.to_vec(),
+            sort,
+            comparator,
+        };
+
+        let values = vec![
+            CellValue::Int(3),
+            CellValue::Null,
+            CellValue::Float(1.5),
+            CellValue::Int(3),
+            CellValue::Int(-1),
+        ];
+        assert_eq!(
+            sort_order(&[key(&values, ColumnSort::Ascending, None)]),
+            vec![4, 2, 0, 3, 1]
+        );
+        assert_eq!(
+            sort_order(&[key(&values, ColumnSort::Descending, None)]),
+            vec![0, 3, 2, 4, 1]
+        );
+        assert_eq!(
+            sort_order(&[key(&values, ColumnSort::Default, None)]),
+            vec![0, 1, 2, 3, 4]
+        );
+
+        // By the absolute value.
+        let comparator = CellComparator::new(|a, b| {
+            let (a, b) = (a.as_f64().unwrap().abs(), b.as_f64().unwrap().abs());
+            a.total_cmp(&b)
+        });
+        assert_eq!(
+            sort_order(&[key(&values, ColumnSort::Ascending, Some(comparator))]),
+            vec![4, 2, 0, 3, 1]
+        );
+
+        // By the group, then the values in descending order.
+        let groups = ["b", "a", "b", "a", "a"].map(CellValue::from);
+        assert_eq!(
+            sort_order(&[
+                key(&groups, ColumnSort::Ascending, None),
+                key(&values, ColumnSort::Descending, None),
+            ]),
+            vec![3, 4, 1, 0, 2]
+        );
+    }
+}
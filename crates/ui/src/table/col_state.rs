@@ -0,0 +1,163 @@
+use gpui::{px, Context, SharedString, Window};
+use serde::{Deserialize, Serialize};
+
+use crate::table::{ColumnSort, TableDelegate, TableEvent, TableState};
+
+/// The layout of a column in the [`TableColState`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColState {
+    /// The [`Column::key`](super::Column::key) to match the column.
+    pub key: SharedString,
+    /// The width of the column in pixels.
+    pub width: f32,
+    /// Whether the column is visible, see [`TableState::set_col_visible`].
+    #[serde(default = "default_visible")]
+    pub visible: bool,
+    /// The sort of the column, `None` if the column is not sorted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort: Option<ColumnSort>,
+    /// The priority of the column in the multi-column sort, the lower first.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_priority: Option<usize>,
+}
+
+fn default_visible() -> bool {
+    true
+}
+
+/// The widths, order, visibility and sorts of the columns, to save the layout of the table
+/// and restore it on the next launch.
+///
+/// ```ignore
+/// let json = serde_json::to_string(&table.read(cx).col_state())?;
+///
+/// let col_state = serde_json::from_str::<TableColState>(&json)?;
+/// table.update(cx, |table, cx| table.set_col_state(&col_state, window, cx));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TableColState {
+    /// The columns in the display order.
+    pub columns: Vec<ColState>,
+}
+
+impl<D> TableState<D>
+where
+    D: TableDelegate,
+{
+    /// Returns the current layout of the columns, see [`TableColState`].
+    pub fn col_state(&self) -> TableColState {
+        TableColState {
+            columns: self
+                .col_groups
+                .iter()
+                .map(|col_group| ColState {
+                    key: col_group.column.key.clone(),
+                    width: col_group.width.into(),
+                    visible: col_group.visible,
+                    sort: col_group.sort_priority.and(col_group.column.sort),
+                    sort_priority: col_group.sort_priority,
+                })
+                .collect(),
+        }
+    }
+
+    /// Restore the layout of the columns from the [`Self::col_state`], the columns are matched
+    /// by the key, and the columns not in the state are kept after them.
+    ///
+    /// The columns are moved, shown or hidden by the [`TableDelegate::move_column`] and
+    /// [`TableDelegate::set_col_visible`], and the rows are sorted by the
+    /// [`TableDelegate::perform_multi_sort`] if the sorts are changed.
+    pub fn set_col_state(
+        &mut self,
+        state: &TableColState,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let col_ix = |this: &Self, key: &SharedString| {
+            this.col_groups
+                .iter()
+                .position(|col_group| col_group.column.key == *key)
+        };
+
+        // Order
+        let mut to_ix = 0;
+        for col_state in &state.columns {
+            if let Some(ix) = col_ix(self, &col_state.key) {
+                self.move_column(ix, to_ix, window, cx);
+                to_ix += 1;
+            }
+        }
+
+        // Widths
+        let mut widths_changed = false;
+        for col_state in &state.columns {
+            let Some(ix) = col_ix(self, &col_state.key) else {
+                continue;
+            };
+            let col_group = &mut self.col_groups[ix];
+            let width = px(col_state.width)
+                .min(col_group.column.max_width)
+                .max(col_group.column.min_width);
+            if col_group.width != width {
+                col_group.width = width;
+                widths_changed = true;
+            }
+        }
+        if widths_changed {
+            let widths = self.col_groups.iter().map(|g| g.width).collect();
+            cx.emit(TableEvent::ColumnWidthsChanged(widths));
+        }
+
+        // Visibility, show the columns first to keep at least one visible column.
+        for visible in [true, false] {
+            for col_state in state.columns.iter().filter(|c| c.visible == visible) {
+                if let Some(ix) = col_ix(self, &col_state.key) {
+                    self.set_col_visible(ix, visible, window, cx);
+                }
+            }
+        }
+
+        // Sorts
+        let prev_sorts = self.sorts();
+        for col_group in self.col_groups.iter_mut() {
+            if col_group.column.sort.is_none() {
+                continue;
+            }
+
+            let col_state = state
+                .columns
+                .iter()
+                .find(|col_state| col_state.key == col_group.column.key);
+            match col_state.and_then(|c| c.sort.filter(|sort| *sort != ColumnSort::Default)) {
+                Some(sort) => {
+                    col_group.column.sort = Some(sort);
+                    col_group.sort_priority = Some(
+                        col_state
+                            .and_then(|c| c.sort_priority)
+                            .unwrap_or(usize::MAX),
+                    );
+                }
+                None => {
+                    col_group.column.sort = Some(ColumnSort::Default);
+                    col_group.sort_priority = None;
+                }
+            }
+        }
+        let sorts = self.sorts();
+        for (priority, (ix, _)) in sorts.iter().enumerate() {
+            self.col_groups[*ix].sort_priority = Some(priority);
+        }
+        if sorts != prev_sorts && self.sortable && !sorts.is_empty() {
+            self.capture_scroll_anchor(cx);
+            self.sort_generation += 1;
+            self.delegate_mut()
+                .perform_multi_sort(sorts.clone(), window, cx);
+            self.sort_performed(sorts.iter().map(|(ix, _)| *ix).collect(), window, cx);
+            for (ix, sort) in sorts {
+                cx.emit(TableEvent::SortChanged(ix, sort));
+            }
+        }
+
+        self.notify(cx);
+    }
+}
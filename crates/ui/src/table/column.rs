@@ -1,9 +1,15 @@
+use std::cmp::Ordering;
+
 use gpui::{
     div, prelude::FluentBuilder, px, Bounds, Context, Edges, Empty, EntityId, IntoElement,
     ParentElement as _, Pixels, Render, SharedString, Styled as _, TextAlign, Window,
 };
+use serde::{Deserialize, Serialize};
 
-use crate::ActiveTheme as _;
+use crate::{
+    table::{Aggregator, CellComparator, CellValue, ColumnFilter, FilterValue},
+    ActiveTheme as _,
+};
 
 /// Represents a column in a table, used for initializing table columns.
 #[derive(Debug, Clone)]
@@ -26,7 +32,17 @@ pub struct Column {
     pub paddings: Option<Edges<Pixels>>,
     /// The width of the column.
     pub width: Pixels,
-    /// Whether the column is fixed, the fixed column will pin at the left side when scrolling horizontally.
+    /// The min width of the column when resizing, default is 10px.
+    pub min_width: Pixels,
+    /// The max width of the column when resizing, default is 1200px.
+    pub max_width: Pixels,
+    /// The max lines to wrap the text of the cells, if any, default to a single line with ellipsis.
+    pub wrap: Option<usize>,
+    /// The priority to keep the column visible when the table is too narrow, if any.
+    ///
+    /// The columns with the lower priority are hidden first, the columns without priority are always visible.
+    pub priority: Option<usize>,
+    /// Whether the column is fixed, the fixed column will pin at the left or right side when scrolling horizontally.
     pub fixed: Option<ColumnFixed>,
     /// Whether the column is resizable.
     pub resizable: bool,
@@ -34,6 +50,12 @@ pub struct Column {
     pub movable: bool,
     /// Whether the column is selectable, if true this column's cells can be selected in column selection mode.
     pub selectable: bool,
+    /// The aggregation of the column to show in the footer, if any.
+    pub aggregator: Option<Aggregator>,
+    /// The custom comparator of the [`CellValue`] to sort the column, if any.
+    pub comparator: Option<CellComparator>,
+    /// The filter type of the column to show a filter popover in the header, if any.
+    pub filter: Option<ColumnFilter>,
 }
 
 impl Default for Column {
@@ -45,10 +67,17 @@ impl Default for Column {
             sort: None,
             paddings: None,
             width: px(100.),
+            min_width: px(10.),
+            max_width: px(1200.),
+            priority: None,
+            wrap: None,
             fixed: None,
             resizable: true,
             movable: true,
             selectable: true,
+            aggregator: None,
+            comparator: None,
+            filter: None,
         }
     }
 }
@@ -117,6 +146,35 @@ impl Column {
         self
     }
 
+    /// Set the min width of the column when resizing, default is 10px.
+    pub fn min_width(mut self, min_width: impl Into<Pixels>) -> Self {
+        self.min_width = min_width.into();
+        self
+    }
+
+    /// Set the max width of the column when resizing, default is 1200px.
+    pub fn max_width(mut self, max_width: impl Into<Pixels>) -> Self {
+        self.max_width = max_width.into();
+        self
+    }
+
+    /// Set the priority to keep the column visible, the columns with the lower priority are
+    /// hidden first when the table is too narrow, and shown in the "+N" row detail.
+    ///
+    /// Default is None, the column is always visible.
+    pub fn priority(mut self, priority: usize) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Wrap the text of the cells to the max `lines`, the height of the rows is adjusted to fit the lines.
+    ///
+    /// Default is None, the text is truncated in a single line with the ellipsis.
+    pub fn wrap(mut self, lines: usize) -> Self {
+        self.wrap = Some(lines.max(1));
+        self
+    }
+
     /// Set whether the column is fixed, default is false.
     pub fn fixed(mut self, fixed: impl Into<ColumnFixed>) -> Self {
         self.fixed = Some(fixed.into());
@@ -129,6 +187,14 @@ impl Column {
         self
     }
 
+    /// Set whether the column is fixed on right side, default is false.
+    ///
+    /// The right fixed columns must be the last columns of the table.
+    pub fn fixed_right(mut self) -> Self {
+        self.fixed = Some(ColumnFixed::Right);
+        self
+    }
+
     /// Set whether the column is resizable, default is true.
     pub fn resizable(mut self, resizable: bool) -> Self {
         self.resizable = resizable;
@@ -146,6 +212,33 @@ impl Column {
         self.selectable = selectable;
         self
     }
+
+    /// Set the [`Aggregator`] of the column, the table shows a footer with the aggregated value.
+    ///
+    /// The values are from the [`TableDelegate::column_value`](super::TableDelegate::column_value).
+    pub fn aggregate(mut self, aggregator: Aggregator) -> Self {
+        self.aggregator = Some(aggregator);
+        self
+    }
+
+    /// Set a custom comparator of the [`CellValue`] for the default sorting, default to [`CellValue::compare`].
+    ///
+    /// The `Null` values are always sorted at the end, so the comparator is not called with them.
+    pub fn comparator(
+        mut self,
+        comparator: impl Fn(&CellValue, &CellValue) -> Ordering + 'static,
+    ) -> Self {
+        self.comparator = Some(CellComparator::new(comparator));
+        self
+    }
+
+    /// Set the [`ColumnFilter`] of the column, the header shows a filter button to edit the filter.
+    ///
+    /// See [`TableDelegate::apply_filter`](super::TableDelegate::apply_filter).
+    pub fn filter(mut self, filter: ColumnFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
 }
 
 impl FluentBuilder for Column {}
@@ -153,6 +246,7 @@ impl FluentBuilder for Column {}
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ColumnFixed {
     Left,
+    Right,
 }
 
 /// Used to sort the column runtime info in Table internal.
@@ -165,9 +259,29 @@ pub(crate) struct ColGroup {
     pub(crate) width: Pixels,
     /// The bounds of the column in the table after it renders.
     pub(crate) bounds: Bounds<Pixels>,
+    /// The filter value of the column, see [`TableState::set_filter`](super::TableState::set_filter).
+    pub(crate) filter_value: Option<FilterValue>,
+    /// The priority of the column in the multi-column sort, the lower first.
+    pub(crate) sort_priority: Option<usize>,
+    /// Whether the column is shown by the user, see [`TableState::set_col_visible`](super::TableState::set_col_visible).
+    pub(crate) visible: bool,
+    /// Whether the column is not rendered, because it's hidden by the user or the table is
+    /// too narrow, see [`Column::priority`].
+    pub(crate) hidden: bool,
+    /// The group of the column, see [`TableDelegate::col_group`](super::TableDelegate::col_group).
+    pub(crate) group: Option<SharedString>,
 }
 
 impl ColGroup {
+    /// Returns the rendered width of the column, 0 if the column is hidden.
+    pub(crate) fn visible_width(&self) -> Pixels {
+        if self.hidden {
+            px(0.)
+        } else {
+            self.width
+        }
+    }
+
     pub(crate) fn is_resizable(&self) -> bool {
         self.column.resizable
     }
@@ -179,10 +293,13 @@ pub(crate) struct DragColumn {
     pub(crate) name: SharedString,
     pub(crate) width: Pixels,
     pub(crate) col_ix: usize,
+    /// The number of the columns from the `col_ix`, more than 1 when dragging a column group.
+    pub(crate) len: usize,
 }
 
 /// The sorting behavior of a column.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ColumnSort {
     /// No sorting.
     #[default]
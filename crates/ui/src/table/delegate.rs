@@ -1,17 +1,72 @@
 use std::ops::Range;
 
 use gpui::{
-    div, App, Context, Div, InteractiveElement as _, IntoElement, ParentElement as _, Stateful,
-    Styled as _, Window,
+    div, prelude::FluentBuilder as _, App, Context, Div, InteractiveElement as _, IntoElement,
+    ParentElement as _, SharedString, Stateful, Styled as _, Task, Window,
 };
 
 use crate::{
+    format::format_number,
     h_flex,
+    indicator::Indicator,
     menu::PopupMenu,
-    table::{loading::Loading, Column, ColumnSort, TableState},
-    ActiveTheme as _, Icon, IconName, Size,
+    table::{
+        cell_value::{sort_order, SortKey},
+        loading::Loading,
+        CellValue, Column, ColumnSort, FilterValue, TableState,
+    },
+    ActiveTheme as _, Icon, IconName, Sizable as _, Size,
 };
 
+/// The stable identity of a row, see [`TableDelegate::row_id`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RowId(SharedString);
+
+impl RowId {
+    /// Returns the id as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for RowId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+macro_rules! impl_row_id_from_number {
+    ($($ty:ty),+) => {
+        $(
+            impl From<$ty> for RowId {
+                fn from(id: $ty) -> Self {
+                    Self(id.to_string().into())
+                }
+            }
+        )+
+    };
+}
+
+impl_row_id_from_number!(usize, u32, u64, i32, i64);
+
+impl From<SharedString> for RowId {
+    fn from(id: SharedString) -> Self {
+        Self(id)
+    }
+}
+
+impl From<String> for RowId {
+    fn from(id: String) -> Self {
+        Self(id.into())
+    }
+}
+
+impl From<&'static str> for RowId {
+    fn from(id: &'static str) -> Self {
+        Self(id.into())
+    }
+}
+
 /// A delegate trait for providing data and rendering for a table.
 #[allow(unused)]
 pub trait TableDelegate: Sized + 'static {
@@ -26,20 +81,163 @@ pub trait TableDelegate: Sized + 'static {
     /// This only call on Table prepare or refresh.
     fn column(&self, col_ix: usize, cx: &App) -> &Column;
 
+    /// Returns the group name of the column at the given index, the adjacent columns in the
+    /// same group are merged into one cell in a second header row above the columns,
+    /// e.g. "Name" spanning "First Name" and "Last Name".
+    ///
+    /// This only call on Table prepare or refresh, then the group moves with the column.
+    fn col_group(&self, col_ix: usize, cx: &App) -> Option<SharedString> {
+        None
+    }
+
+    /// Returns whether the column at the given index is visible, default to true.
+    ///
+    /// This only call on Table prepare or refresh, then the columns are shown or hidden by
+    /// the context menu of the header, see [`TableState::set_col_visible`].
+    fn col_visible(&self, col_ix: usize, cx: &App) -> bool {
+        true
+    }
+
+    /// Called when the column at the given index is shown or hidden, to keep the visibility
+    /// on refresh, e.g. save it in the delegate and return it in the [`Self::col_visible`].
+    fn set_col_visible(
+        &mut self,
+        col_ix: usize,
+        visible: bool,
+        window: &mut Window,
+        cx: &mut Context<TableState<Self>>,
+    ) {
+    }
+
     /// Perform sort on the column at the given index.
+    ///
+    /// Default to sort the rows by the [`cell_value`](Self::cell_value) with the
+    /// [`Column::comparator`], then call [`reorder_rows`](Self::reorder_rows) with the new order.
+    /// The `ColumnSort::Default` keeps the current order.
     fn perform_sort(
         &mut self,
         col_ix: usize,
         sort: ColumnSort,
         window: &mut Window,
         cx: &mut Context<TableState<Self>>,
+    ) {
+        sort_rows_by_cell_values(self, &[(col_ix, sort)], window, cx);
+    }
+
+    /// Perform sort on multiple columns, the `sorts` are the columns and the sort orders
+    /// by the priority, when Shift-click the headers.
+    ///
+    /// Default to call [`perform_sort`](Self::perform_sort) for a single column,
+    /// or sort the rows by the [`cell_value`](Self::cell_value) of the columns, then call
+    /// [`reorder_rows`](Self::reorder_rows) with the new order.
+    fn perform_multi_sort(
+        &mut self,
+        sorts: Vec<(usize, ColumnSort)>,
+        window: &mut Window,
+        cx: &mut Context<TableState<Self>>,
+    ) {
+        match sorts.as_slice() {
+            [] => {}
+            [(col_ix, sort)] => self.perform_sort(*col_ix, *sort, window, cx),
+            sorts => sort_rows_by_cell_values(self, sorts, window, cx),
+        }
+    }
+
+    /// Apply the filter on the column at the given index, `None` to clear the filter of the column.
+    ///
+    /// The [`FilterValue::matches`] can be used to match the [`cell_value`](Self::cell_value) with the filter,
+    /// the filters of the other columns should be kept.
+    fn apply_filter(
+        &mut self,
+        col_ix: usize,
+        filter: Option<FilterValue>,
+        window: &mut Window,
+        cx: &mut Context<TableState<Self>>,
+    ) {
+    }
+
+    /// Returns the stable id of the row, default: None to track the rows by the index.
+    ///
+    /// The selection is tracked by the id to survive the sorts, filters and incremental updates,
+    /// and the viewport is anchored to the row with [`TableState::preserve_scroll_position`].
+    fn row_id(&self, row_ix: usize, cx: &App) -> Option<RowId> {
+        None
+    }
+
+    /// Returns the typed value of the cell for the automatic sorting, default: `CellValue::Null`
+    fn cell_value(&self, row_ix: usize, col_ix: usize, cx: &App) -> CellValue {
+        CellValue::Null
+    }
+
+    /// Returns the full text of the cell to show in a tooltip when the cell is truncated,
+    /// default to the text of the [`cell_value`](Self::cell_value).
+    fn cell_text(&self, row_ix: usize, col_ix: usize, cx: &App) -> Option<SharedString> {
+        match self.cell_value(row_ix, col_ix, cx) {
+            CellValue::Null => None,
+            value => Some(value.to_string().into()),
+        }
+    }
+
+    /// Returns the text of the cell to export or copy, see [`TableState::export`],
+    /// default to the [`cell_text`](Self::cell_text).
+    fn export_cell_text(&self, row_ix: usize, col_ix: usize, cx: &App) -> SharedString {
+        self.cell_text(row_ix, col_ix, cx).unwrap_or_default()
+    }
+
+    /// Reorder the rows of the data source by the default [`perform_sort`](Self::perform_sort).
+    ///
+    /// The `order` is the old row indices in the new order, e.g. `rows = order.iter().map(|ix| rows[*ix].clone())`.
+    fn reorder_rows(
+        &mut self,
+        order: &[usize],
+        window: &mut Window,
+        cx: &mut Context<TableState<Self>>,
     ) {
     }
 
-    /// Render the header cell at the given column index, default to the column name.
+    /// Returns the numeric value of the cell for the [`Aggregator`](super::Aggregator) of the column,
+    /// default to the number of the [`cell_value`](Self::cell_value).
+    ///
+    /// This only call when the rows changed, see [`TableState::rows_changed`].
+    fn column_value(&self, row_ix: usize, col_ix: usize, cx: &App) -> Option<f64> {
+        self.cell_value(row_ix, col_ix, cx).as_f64()
+    }
+
+    /// Render the footer cell of the column without an [`Aggregator`](super::Aggregator),
+    /// e.g. the "Total" label, default: empty
+    ///
+    /// The footer is shown with [`Table::footer`](super::Table::footer) or any aggregated column.
+    fn render_tfoot_td(
+        &self,
+        col_ix: usize,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> impl IntoElement {
+        div()
+    }
+
+    /// Render the footer cell at the given column index with the aggregated `value`,
+    /// default to the formatted value.
+    fn render_footer(
+        &self,
+        col_ix: usize,
+        value: Option<f64>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> impl IntoElement {
+        div()
+            .size_full()
+            .when_some(value, |this, value| this.child(format_number(value, 2)))
+    }
+
+    /// Render the header cell at the given column index, default to the column name
+    /// truncated with the ellipsis, the full name is shown in a tooltip when truncated.
     fn render_th(&self, col_ix: usize, window: &mut Window, cx: &mut App) -> impl IntoElement {
         div()
             .size_full()
+            .overflow_hidden()
+            .whitespace_nowrap()
+            .text_ellipsis()
             .child(self.column(col_ix, cx).name.clone())
     }
 
@@ -68,6 +266,134 @@ pub trait TableDelegate: Sized + 'static {
         cx: &mut App,
     ) -> impl IntoElement;
 
+    /// Return true if the cell at the given row and column can be edited, default: false
+    ///
+    /// The editable cell is edited by double click, or [`TableState::edit_cell`].
+    fn can_edit_cell(&self, row_ix: usize, col_ix: usize, cx: &App) -> bool {
+        false
+    }
+
+    /// Called when the cell starts editing, e.g. to create the editor with the value of the cell
+    /// and focus it.
+    fn begin_edit_cell(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<TableState<Self>>,
+    ) {
+    }
+
+    /// Render the cell in editing, default to [`render_td`](Self::render_td).
+    ///
+    /// In the editor, `enter` commits the edit, `escape` cancels it,
+    /// and `tab` / `shift-tab` move to the next / previous editable cell.
+    fn render_editing_td(
+        &self,
+        row_ix: usize,
+        col_ix: usize,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> impl IntoElement {
+        self.render_td(row_ix, col_ix, window, cx)
+    }
+
+    /// Save the value of the editor to the cell, return false to reject the value and keep editing.
+    ///
+    /// Default: true
+    fn commit_edit_cell(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<TableState<Self>>,
+    ) -> bool {
+        true
+    }
+
+    /// Called when the edit of the cell is canceled.
+    fn cancel_edit_cell(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<TableState<Self>>,
+    ) {
+    }
+
+    /// Load the rows of the page in the paged mode, see [`Table::paginated`](super::Table::paginated).
+    ///
+    /// The delegate holds the rows of the current page, replace the rows here, or in a task for
+    /// the async data source and call the [`TableState::refresh`] after loaded.
+    fn load_page(
+        &mut self,
+        page: usize,
+        page_size: usize,
+        window: &mut Window,
+        cx: &mut Context<TableState<Self>>,
+    ) {
+    }
+
+    /// Returns the number of the rows of all the pages in the paged mode, default to the
+    /// [`rows_count`](Self::rows_count).
+    fn total_rows_count(&self, cx: &App) -> usize {
+        self.rows_count(cx)
+    }
+
+    /// Return true if the row can expand to show the [`render_row_detail`](Self::render_row_detail),
+    /// a chevron toggle is shown in the first cell of the row, default: false
+    fn can_expand_row(&self, row_ix: usize, cx: &App) -> bool {
+        false
+    }
+
+    /// Render the detail panel beneath the expanded row, e.g. the line items of an order.
+    ///
+    /// The panel spans the width of the table, its height is measured to fit the content.
+    fn render_row_detail(
+        &self,
+        row_ix: usize,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> impl IntoElement {
+        div()
+    }
+
+    /// Return the depth of the row in the tree mode, the root rows are 0, see [`TableState::tree`].
+    fn row_depth(&self, row_ix: usize, cx: &App) -> usize {
+        0
+    }
+
+    /// Return true if the row has children to expand in the tree mode, default: false
+    fn has_row_children(&self, row_ix: usize, cx: &App) -> bool {
+        false
+    }
+
+    /// Insert the children of the expanded row after it, with the depth of the row + 1.
+    ///
+    /// The rows of the delegate are the flattened tree of the expanded rows, the row is
+    /// identified by the [`Self::row_id`] because the indices are changed by expanding.
+    ///
+    /// Return a task to load the children lazily, e.g. read a directory, the row shows a loading
+    /// indicator until the task is done.
+    fn row_children(
+        &mut self,
+        row_id: &RowId,
+        window: &mut Window,
+        cx: &mut Context<TableState<Self>>,
+    ) -> Option<Task<()>> {
+        None
+    }
+
+    /// Remove the descendants of the collapsed row, they are the `rows` after the row.
+    fn remove_row_children(
+        &mut self,
+        row_id: &RowId,
+        rows: Range<usize>,
+        window: &mut Window,
+        cx: &mut Context<TableState<Self>>,
+    ) {
+    }
+
     /// Move the column at the given `col_ix` to insert before the column at the given `to_ix`.
     fn move_column(
         &mut self,
@@ -78,6 +404,22 @@ pub trait TableDelegate: Sized + 'static {
     ) {
     }
 
+    /// Return true if the row can be dragged to move, see [`TableState::row_movable`], default: false
+    fn can_move_row(&self, row_ix: usize, cx: &App) -> bool {
+        false
+    }
+
+    /// Move the row at the given `row_ix` to the given `to_ix`, like the `Vec::remove` then
+    /// `Vec::insert` of the rows.
+    fn move_row(
+        &mut self,
+        row_ix: usize,
+        to_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<TableState<Self>>,
+    ) {
+    }
+
     /// Return a Element to show when table is empty.
     fn render_empty(&self, window: &mut Window, cx: &mut App) -> impl IntoElement {
         h_flex()
@@ -107,6 +449,14 @@ pub trait TableDelegate: Sized + 'static {
         true
     }
 
+    /// Return true if there are no more rows to load, to stop the [`Self::load_more`] when
+    /// scrolling to the bottom.
+    ///
+    /// Default: false
+    fn has_no_more_rows(&self, cx: &App) -> bool {
+        false
+    }
+
     /// Returns a threshold value (n rows), of course, when scrolling to the bottom,
     /// the remaining number of rows triggers `load_more`.
     /// This should smaller than the total number of first load rows.
@@ -116,6 +466,21 @@ pub trait TableDelegate: Sized + 'static {
         20
     }
 
+    /// Return true to show the loading row below the rows, while loading more data.
+    fn loading_more(&self, cx: &App) -> bool {
+        false
+    }
+
+    /// Return a Element of the loading row below the rows, default is a row with the [`Indicator`].
+    fn render_loading_more(&self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .flex_shrink_0()
+            .justify_center()
+            .py_2()
+            .child(Indicator::new().small().color(cx.theme().muted_foreground))
+    }
+
     /// Load more data when the table is scrolled to the bottom.
     ///
     /// This will performed in a background task.
@@ -157,3 +522,32 @@ pub trait TableDelegate: Sized + 'static {
     ) {
     }
 }
+
+/// Sort the rows of the delegate by the [`TableDelegate::cell_value`] of the columns.
+fn sort_rows_by_cell_values<D: TableDelegate>(
+    delegate: &mut D,
+    sorts: &[(usize, ColumnSort)],
+    window: &mut Window,
+    cx: &mut Context<TableState<D>>,
+) {
+    let rows_count = delegate.rows_count(cx);
+    let keys = sorts
+        .iter()
+        .map(|(col_ix, sort)| SortKey {
+            values: (0..rows_count)
+                .map(|row_ix| delegate.cell_value(row_ix, *col_ix, cx))
+                .collect(),
+            sort: *sort,
+            comparator: delegate.column(*col_ix, cx).comparator.clone(),
+        })
+        .filter(|key| key.values.iter().any(|value| *value != CellValue::Null))
+        .collect::<Vec<_>>();
+    if keys.is_empty() {
+        return;
+    }
+
+    let order = sort_order(&keys);
+    if order.iter().enumerate().any(|(ix, row_ix)| ix != *row_ix) {
+        delegate.reorder_rows(&order, window, cx);
+    }
+}
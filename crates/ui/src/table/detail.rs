@@ -0,0 +1,254 @@
+use std::{collections::BTreeMap, ops::Range, rc::Rc};
+
+use gpui::{
+    div, px, size, AnyElement, App, AvailableSpace, Context, InteractiveElement as _, IntoElement,
+    ParentElement as _, Pixels, ScrollStrategy, Size, Styled as _, Window,
+};
+
+use crate::{
+    button::{Button, ButtonVariants as _},
+    table::{TableDelegate, TableEvent, TableState},
+    ActiveTheme as _, IconName, Sizable as _,
+};
+
+impl<D> TableState<D>
+where
+    D: TableDelegate,
+{
+    /// Returns true if the row is expanded to show the [`TableDelegate::render_row_detail`].
+    pub fn is_row_expanded(&self, row_ix: usize) -> bool {
+        self.expanded_rows.contains_key(&row_ix)
+    }
+
+    /// Returns the indices of the expanded rows, in ascending order.
+    pub fn expanded_rows(&self) -> Vec<usize> {
+        self.expanded_rows.keys().copied().collect()
+    }
+
+    /// Expand or collapse the row, and emit the [`TableEvent::RowExpanded`].
+    ///
+    /// Returns false if the row can't expand, see [`TableDelegate::can_expand_row`].
+    pub fn set_row_expanded(
+        &mut self,
+        row_ix: usize,
+        expanded: bool,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        if expanded == self.is_row_expanded(row_ix) {
+            return true;
+        }
+
+        if expanded {
+            if row_ix >= self.delegate.rows_count(cx) || !self.delegate.can_expand_row(row_ix, cx) {
+                return false;
+            }
+            self.expanded_rows.insert(row_ix, None);
+            if let Some(row_id) = self.delegate.row_id(row_ix, cx) {
+                self.expanded_row_ids.insert(row_id);
+            }
+        } else {
+            self.expanded_rows.remove(&row_ix);
+            if let Some(row_id) = self.delegate.row_id(row_ix, cx) {
+                self.expanded_row_ids.remove(&row_id);
+            }
+        }
+
+        cx.emit(TableEvent::RowExpanded {
+            row: row_ix,
+            expanded,
+        });
+        self.notify(cx);
+        true
+    }
+
+    /// Toggle the row between expanded and collapsed, see [`Self::set_row_expanded`].
+    pub fn toggle_row_expanded(&mut self, row_ix: usize, cx: &mut Context<Self>) -> bool {
+        let expanded = !self.is_row_expanded(row_ix);
+        self.set_row_expanded(row_ix, expanded, cx)
+    }
+
+    /// Collapse all the expanded rows.
+    ///
+    /// The rows are tracked by the [`TableDelegate::row_id`] to keep them expanded after
+    /// sorted, filtered or refreshed, the rows without id are collapsed after sorted or filtered.
+    pub fn collapse_all_rows(&mut self, cx: &mut Context<Self>) {
+        if self.expanded_rows.is_empty() && self.expanded_row_ids.is_empty() {
+            return;
+        }
+
+        self.expanded_rows.clear();
+        self.expanded_row_ids.clear();
+        self.notify(cx);
+    }
+
+    /// Move the expanded rows to the new indices of the expanded row ids after the rows changed.
+    ///
+    /// The rows without id are kept at the same indices, or collapsed if `reordered`.
+    /// The ids not in the rows are kept, to expand the rows again when they are shown,
+    /// e.g. the filter is cleared.
+    pub(super) fn sync_expanded_rows(&mut self, reordered: bool, cx: &App) {
+        if self.expanded_row_ids.is_empty() {
+            if reordered {
+                self.expanded_rows.clear();
+            }
+            return;
+        }
+
+        let rows_count = self.delegate.rows_count(cx);
+        let mut expanded_rows = BTreeMap::new();
+        for row_ix in 0..rows_count {
+            let size = self.expanded_rows.get(&row_ix).copied().flatten();
+            match self.delegate.row_id(row_ix, cx) {
+                Some(row_id) => {
+                    if self.expanded_row_ids.contains(&row_id) {
+                        // Measure again if the row is moved.
+                        expanded_rows.insert(row_ix, size.filter(|_| !reordered));
+                    }
+                }
+                None => {
+                    if !reordered && self.expanded_rows.contains_key(&row_ix) {
+                        expanded_rows.insert(row_ix, size);
+                    }
+                }
+            }
+        }
+        self.expanded_rows = expanded_rows;
+    }
+
+    /// Measure the detail panels again on the next render, e.g. the rows changed.
+    pub(super) fn invalidate_row_details(&mut self) {
+        self.expanded_rows
+            .values_mut()
+            .for_each(|size| *size = None);
+    }
+
+    /// Move the expanded rows after the rows in the `range` are replaced by `new_len` rows,
+    /// the replaced rows are kept expanded if they are still in the new rows.
+    pub(super) fn splice_expanded_rows(&mut self, range: Range<usize>, new_len: usize) {
+        if self.expanded_rows.is_empty() {
+            return;
+        }
+
+        let removed_end = range.start + new_len.min(range.len());
+        self.expanded_rows = std::mem::take(&mut self.expanded_rows)
+            .into_iter()
+            .filter_map(|(row_ix, size)| {
+                if row_ix < range.start {
+                    Some((row_ix, size))
+                } else if row_ix < removed_end {
+                    Some((row_ix, None))
+                } else if row_ix >= range.end {
+                    Some((row_ix + new_len - range.len(), size))
+                } else {
+                    None
+                }
+            })
+            .collect();
+    }
+
+    /// Measure the detail panels not measured yet or in another width, and remove the rows
+    /// out of bounds.
+    pub(super) fn measure_row_details(
+        &mut self,
+        rows_count: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.expanded_rows.retain(|row_ix, _| *row_ix < rows_count);
+
+        let width = self.bounds.size.width;
+        let available_space = size(
+            if width > px(0.) {
+                AvailableSpace::Definite(width)
+            } else {
+                AvailableSpace::MinContent
+            },
+            AvailableSpace::MinContent,
+        );
+
+        let rows = self
+            .expanded_rows
+            .iter()
+            .filter(|(_, size)| size.is_none_or(|size| size.width != width))
+            .map(|(row_ix, _)| *row_ix)
+            .collect::<Vec<_>>();
+        for row_ix in rows {
+            let mut el = self.render_row_detail_panel(row_ix, window, cx);
+            let size = el.layout_as_root(available_space, window, cx);
+            self.expanded_rows.insert(row_ix, Some(size));
+        }
+    }
+
+    /// Returns the total height of the detail panels of the expanded rows before the `row_ix`.
+    pub(super) fn row_details_height_before(&self, row_ix: usize) -> Pixels {
+        self.expanded_rows
+            .range(..row_ix)
+            .fold(px(0.), |sum, (_, size)| sum + detail_height(size))
+    }
+
+    /// Returns the sizes of the rows with the detail panels for the virtual list.
+    pub(super) fn row_item_sizes(&self, count: usize, row_height: Pixels) -> Rc<Vec<Size<Pixels>>> {
+        let width = self.bounds.size.width;
+        let mut sizes = vec![size(width, row_height); count];
+        for (row_ix, size) in self.expanded_rows.range(..count) {
+            sizes[*row_ix].height += detail_height(size);
+        }
+        Rc::new(sizes)
+    }
+
+    /// Scroll to the row by the scroll handle of the rows list in use.
+    pub(super) fn scroll_to_row_item(&self, row_ix: usize, strategy: ScrollStrategy) {
+        if self.expanded_rows.is_empty() {
+            self.vertical_scroll_handle.scroll_to_item(row_ix, strategy);
+        } else {
+            self.detail_scroll_handle.scroll_to_item(row_ix, strategy);
+        }
+    }
+
+    pub(super) fn render_row_detail_panel(
+        &mut self,
+        row_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        div()
+            .id(("row-detail", row_ix))
+            .w_full()
+            .border_b_1()
+            .border_color(cx.theme().table_row_border)
+            .bg(cx.theme().table_head.opacity(0.5))
+            .child(self.delegate.render_row_detail(row_ix, window, cx))
+            .into_any_element()
+    }
+
+    /// Render the chevron toggle in the first cell of the expandable row.
+    pub(super) fn render_expand_toggle(
+        &self,
+        row_ix: usize,
+        cx: &mut Context<Self>,
+    ) -> Option<impl IntoElement> {
+        if !self.delegate.can_expand_row(row_ix, cx) {
+            return None;
+        }
+
+        Some(
+            Button::new(("expand-row", row_ix))
+                .ghost()
+                .xsmall()
+                .flex_shrink_0()
+                .icon(if self.is_row_expanded(row_ix) {
+                    IconName::ChevronDown
+                } else {
+                    IconName::ChevronRight
+                })
+                .on_click(cx.listener(move |this, _, _, cx| {
+                    cx.stop_propagation();
+                    this.toggle_row_expanded(row_ix, cx);
+                })),
+        )
+    }
+}
+
+fn detail_height(size: &Option<Size<Pixels>>) -> Pixels {
+    size.map_or(px(0.), |size| size.height)
+}
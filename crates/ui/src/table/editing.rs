@@ -0,0 +1,127 @@
+use gpui::{App, Context, Window};
+
+use crate::table::{TableDelegate, TableEvent, TableState};
+
+impl<D> TableState<D>
+where
+    D: TableDelegate,
+{
+    /// Returns the `(row_ix, col_ix)` of the cell in editing, if any.
+    pub fn editing_cell(&self) -> Option<(usize, usize)> {
+        self.editing_cell
+    }
+
+    /// Start editing the cell by the [`TableDelegate::render_editing_td`], returns false if
+    /// the cell is not editable, see [`TableDelegate::can_edit_cell`].
+    ///
+    /// The cell in editing is committed first, do nothing if it's failed to commit.
+    pub fn edit_cell(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        if self.editing_cell == Some((row_ix, col_ix)) {
+            return true;
+        }
+        if !self.is_cell_editable(row_ix, col_ix, cx) {
+            return false;
+        }
+        if self.editing_cell.is_some() && !self.commit_editing(window, cx) {
+            return false;
+        }
+
+        self.editing_cell = Some((row_ix, col_ix));
+        self.delegate.begin_edit_cell(row_ix, col_ix, window, cx);
+        self.scroll_to_row(row_ix, cx);
+        self.scroll_to_col(col_ix, cx);
+        cx.notify();
+        true
+    }
+
+    /// Commit the cell in editing by [`TableDelegate::commit_edit_cell`], and emit the
+    /// [`TableEvent::CellEdited`].
+    ///
+    /// Returns false if the delegate rejected the value, the cell is kept in editing.
+    pub fn commit_editing(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        let Some((row_ix, col_ix)) = self.editing_cell else {
+            return true;
+        };
+        if !self.delegate.commit_edit_cell(row_ix, col_ix, window, cx) {
+            return false;
+        }
+
+        self.editing_cell = None;
+        self.focus_handle.focus(window);
+        self.rows_changed(row_ix..row_ix + 1, 1, cx);
+        cx.emit(TableEvent::CellEdited {
+            row: row_ix,
+            col: col_ix,
+        });
+        true
+    }
+
+    /// Cancel the cell in editing by [`TableDelegate::cancel_edit_cell`].
+    pub fn cancel_editing(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some((row_ix, col_ix)) = self.editing_cell.take() else {
+            return;
+        };
+
+        self.delegate.cancel_edit_cell(row_ix, col_ix, window, cx);
+        self.focus_handle.focus(window);
+        cx.notify();
+    }
+
+    /// Commit the cell in editing and edit the next (or previous) editable cell,
+    /// the cells are visited row by row.
+    pub(super) fn edit_sibling_cell(
+        &mut self,
+        forward: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((row_ix, col_ix)) = self.editing_cell else {
+            return;
+        };
+
+        match self.sibling_editable_cell(row_ix, col_ix, forward, cx) {
+            Some((row_ix, col_ix)) => {
+                self.edit_cell(row_ix, col_ix, window, cx);
+            }
+            None => {
+                self.commit_editing(window, cx);
+            }
+        }
+    }
+
+    fn is_cell_editable(&self, row_ix: usize, col_ix: usize, cx: &App) -> bool {
+        row_ix < self.delegate.rows_count(cx)
+            && self
+                .col_groups
+                .get(col_ix)
+                .is_some_and(|col_group| !col_group.hidden)
+            && self.delegate.can_edit_cell(row_ix, col_ix, cx)
+    }
+
+    fn sibling_editable_cell(
+        &self,
+        row_ix: usize,
+        col_ix: usize,
+        forward: bool,
+        cx: &App,
+    ) -> Option<(usize, usize)> {
+        let cols_count = self.col_groups.len();
+        let cells_count = self.delegate.rows_count(cx) * cols_count;
+        let ix = row_ix * cols_count + col_ix;
+
+        let mut cells: Box<dyn Iterator<Item = usize>> = if forward {
+            Box::new(ix + 1..cells_count)
+        } else {
+            Box::new((0..ix).rev())
+        };
+        cells
+            .find(|ix| self.is_cell_editable(ix / cols_count, ix % cols_count, cx))
+            .map(|ix| (ix / cols_count, ix % cols_count))
+    }
+}
@@ -0,0 +1,301 @@
+use std::ops::Range;
+
+use gpui::{
+    point, px, rems, size, App, Bounds, ClipboardItem, Context, FontWeight, Hsla, Pixels,
+    TextAlign, Window,
+};
+
+use crate::{
+    export::{self, SvgDocument},
+    input,
+    table::{SelectionState, TableDelegate, TableState},
+    ActiveTheme as _, Size,
+};
+
+/// The text format to export the table, see [`TableState::export`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    /// Comma-separated values.
+    #[default]
+    Csv,
+    /// Tab-separated values, pasted as the cells in the spreadsheets.
+    Tsv,
+}
+
+impl ExportFormat {
+    fn delimiter(&self) -> char {
+        match self {
+            Self::Csv => ',',
+            Self::Tsv => '\t',
+        }
+    }
+
+    /// Quote the field if it contains the delimiter, a quote or a line break.
+    fn escape(&self, field: &str) -> String {
+        if field.contains([self.delimiter(), '"', '\n', '\r']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        }
+    }
+
+    fn write_line(&self, out: &mut String, fields: impl IntoIterator<Item = impl AsRef<str>>) {
+        for (ix, field) in fields.into_iter().enumerate() {
+            if ix > 0 {
+                out.push(self.delimiter());
+            }
+            out.push_str(&self.escape(field.as_ref()));
+        }
+        out.push('\n');
+    }
+}
+
+/// The part of the table to export as an image, see [`TableState::export_svg`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportScope {
+    /// The rows and the columns in the current viewport.
+    #[default]
+    Viewport,
+    /// All the rows and the columns, including the columns hidden by the narrow table,
+    /// excluding the columns hidden by the user.
+    All,
+}
+
+impl<D> TableState<D>
+where
+    D: TableDelegate,
+{
+    /// Export the rows in the current sort and filter as text, with a header line of the
+    /// column names, the cells are the [`TableDelegate::export_cell_text`].
+    pub fn export(&self, format: ExportFormat, cx: &App) -> String {
+        self.export_rows(0..self.delegate.rows_count(cx), true, format, cx)
+    }
+
+    /// Export the rows as text, with a header line of the column names if `header` is true.
+    pub fn export_rows(
+        &self,
+        rows: impl IntoIterator<Item = usize>,
+        header: bool,
+        format: ExportFormat,
+        cx: &App,
+    ) -> String {
+        let columns_count = self.delegate.columns_count(cx);
+        let mut out = String::new();
+        if header {
+            format.write_line(
+                &mut out,
+                (0..columns_count).map(|col_ix| self.delegate.column(col_ix, cx).name.clone()),
+            );
+        }
+        for row_ix in rows {
+            format.write_line(
+                &mut out,
+                (0..columns_count).map(|col_ix| self.delegate.export_cell_text(row_ix, col_ix, cx)),
+            );
+        }
+        out
+    }
+
+    /// Export the header and the rows in the `scope` as SVG in the light theme,
+    /// the cells are the [`TableDelegate::export_cell_text`].
+    pub fn export_svg(&self, scope: ExportScope, cx: &mut App) -> String {
+        self.export_document(scope, cx).to_svg()
+    }
+
+    /// Export the header and the rows in the `scope` as PNG in the light theme,
+    /// the size is multiplied by the `scale`, e.g. `2.` for the high DPI.
+    pub fn export_png(
+        &self,
+        scope: ExportScope,
+        scale: f32,
+        cx: &mut App,
+    ) -> anyhow::Result<Vec<u8>> {
+        self.export_document(scope, cx).to_png(scale)
+    }
+
+    /// Returns the rows and the columns (in the display order) to export in the `scope`.
+    fn export_range(&self, scope: ExportScope, cx: &App) -> (Range<usize>, Vec<usize>) {
+        let rows_count = self.delegate.rows_count(cx);
+        match scope {
+            ExportScope::All => (
+                0..rows_count,
+                (0..self.col_groups.len())
+                    .filter(|col_ix| self.col_groups[*col_ix].visible)
+                    .collect(),
+            ),
+            ExportScope::Viewport => {
+                let left_cols_count = self.fixed_left_cols_count();
+                let right_cols = self.fixed_right_cols();
+                let cols = &self.visible_range.cols;
+                let scroll_cols = (cols.start + left_cols_count)
+                    ..(cols.end + left_cols_count).min(right_cols.start);
+                let col_ixs = (0..left_cols_count)
+                    .chain(scroll_cols)
+                    .chain(right_cols)
+                    .filter(|col_ix| !self.col_groups[*col_ix].hidden)
+                    .collect();
+                let rows = &self.visible_range.rows;
+                (
+                    rows.start.min(rows_count)..rows.end.min(rows_count),
+                    col_ixs,
+                )
+            }
+        }
+    }
+
+    fn export_document(&self, scope: ExportScope, cx: &mut App) -> SvgDocument {
+        let (rows, col_ixs) = self.export_range(scope, cx);
+        let size_ = self.options.size;
+        let row_height = size_.table_row_height();
+        let font_size = match size_ {
+            Size::XSmall | Size::Small => rems(0.875).to_pixels(px(16.)),
+            _ => cx.theme().font_size,
+        };
+        let width = col_ixs
+            .iter()
+            .map(|col_ix| self.col_groups[*col_ix].width)
+            .fold(px(0.), |sum, width| sum + width);
+        let height = row_height * (rows.len() + 1) as f32;
+
+        export::with_light_theme(cx, |cx| {
+            let theme = cx.theme();
+            let mut document =
+                SvgDocument::new(size(width, height)).font_family(theme.font_family.clone());
+            let full_width = |y: Pixels| Bounds::new(point(px(0.), y), size(width, row_height));
+            document.rect(
+                Bounds::new(point(px(0.), px(0.)), size(width, height)),
+                px(0.),
+                theme.table,
+                px(1.),
+                theme.border,
+            );
+            document.rect(
+                full_width(px(0.)),
+                px(0.),
+                theme.table_head,
+                px(0.),
+                theme.border,
+            );
+
+            let paint_row =
+                |document: &mut SvgDocument,
+                 y: Pixels,
+                 cells: &mut dyn FnMut(usize) -> (String, FontWeight, Hsla)| {
+                    let mut x = px(0.);
+                    for col_ix in &col_ixs {
+                        let col_group = &self.col_groups[*col_ix];
+                        let column = &col_group.column;
+                        let paddings = column
+                            .paddings
+                            .unwrap_or_else(|| size_.table_cell_padding());
+                        let bounds = Bounds::new(point(x, y), size(col_group.width, row_height));
+                        let text_x = match column.align {
+                            TextAlign::Left => x + paddings.left,
+                            TextAlign::Center => x + col_group.width / 2.,
+                            TextAlign::Right => x + col_group.width - paddings.right,
+                        };
+                        let (text, font_weight, color) = cells(*col_ix);
+                        document.push_clip(bounds);
+                        document.text(
+                            &text,
+                            point(text_x, y + (row_height - font_size) / 2.),
+                            font_size,
+                            font_weight,
+                            color,
+                            column.align,
+                        );
+                        document.pop_clip();
+                        x += col_group.width;
+                    }
+                };
+
+            paint_row(&mut document, px(0.), &mut |col_ix| {
+                (
+                    self.col_groups[col_ix].column.name.to_string(),
+                    FontWeight::MEDIUM,
+                    theme.table_head_foreground,
+                )
+            });
+            for (ix, row_ix) in rows.enumerate() {
+                let y = row_height * (ix + 1) as f32;
+                if self.options.stripe && row_ix % 2 != 0 {
+                    document.rect(
+                        full_width(y),
+                        px(0.),
+                        theme.table_even,
+                        px(0.),
+                        theme.border,
+                    );
+                }
+                document.stroke_path(
+                    &format!("M0 {}H{}", f32::from(y), f32::from(width)),
+                    theme.table_row_border,
+                    px(1.),
+                    None,
+                );
+                paint_row(&mut document, y, &mut |col_ix| {
+                    (
+                        self.delegate
+                            .export_cell_text(row_ix, col_ix, cx)
+                            .to_string(),
+                        FontWeight::NORMAL,
+                        theme.foreground,
+                    )
+                });
+            }
+
+            document
+        })
+    }
+
+    /// Returns the selection as the tab-separated text: the cells of the selected row,
+    /// the column name and the cells of the selected column, or the text of the active cell.
+    pub fn selection_text(&self, cx: &App) -> Option<String> {
+        let format = ExportFormat::Tsv;
+        match self.selection_state {
+            SelectionState::Row => {
+                let row_ix = self.selected_row?;
+                Some(self.export_rows([row_ix], false, format, cx))
+            }
+            SelectionState::Column => {
+                let col_ix = self.selected_col?;
+                let mut out = String::new();
+                format.write_line(&mut out, [self.delegate.column(col_ix, cx).name.clone()]);
+                for row_ix in 0..self.delegate.rows_count(cx) {
+                    format.write_line(
+                        &mut out,
+                        [self.delegate.export_cell_text(row_ix, col_ix, cx)],
+                    );
+                }
+                Some(out)
+            }
+            SelectionState::Cell => {
+                let (row_ix, col_ix) = self.active_cell()?;
+                Some(
+                    self.delegate
+                        .export_cell_text(row_ix, col_ix, cx)
+                        .to_string(),
+                )
+            }
+        }
+    }
+
+    /// Copy the [`selection_text`](Self::selection_text) to the clipboard,
+    /// returns false if nothing is selected.
+    ///
+    /// This is bound to `cmd-c` (`ctrl-c` on Linux and Windows) when the table is focused.
+    pub fn copy_selection_to_clipboard(&self, cx: &mut App) -> bool {
+        let Some(text) = self.selection_text(cx) else {
+            return false;
+        };
+
+        cx.write_to_clipboard(ClipboardItem::new_string(text));
+        true
+    }
+
+    pub(super) fn action_copy(&mut self, _: &input::Copy, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.copy_selection_to_clipboard(cx) {
+            cx.propagate();
+        }
+    }
+}
@@ -0,0 +1,298 @@
+use gpui::{
+    prelude::FluentBuilder as _, px, App, AppContext as _, Context, DismissEvent, Entity,
+    EventEmitter, FocusHandle, Focusable, InteractiveElement as _, IntoElement, ParentElement as _,
+    Render, SharedString, Styled as _, Subscription, Window,
+};
+use rust_i18n::t;
+
+use crate::{
+    button::{Button, ButtonVariants as _},
+    checkbox::Checkbox,
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    table::{CellValue, TableDelegate, TableState},
+    v_flex, Sizable as _,
+};
+
+/// The filter type of a column, see [`Column::filter`](super::Column::filter).
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnFilter {
+    /// Filter the cells by a text, see [`CellValue::matches`].
+    Text,
+    /// Filter the numeric cells in a range.
+    Range,
+    /// Filter the cells in a set of the options.
+    Set(Vec<SharedString>),
+}
+
+/// The value of a column filter, see [`TableState::set_filter`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterValue {
+    /// The text to match, see [`CellValue::matches`].
+    Text(SharedString),
+    /// The inclusive range of the numbers, `None` for no limit.
+    Range { min: Option<f64>, max: Option<f64> },
+    /// The selected options.
+    Set(Vec<SharedString>),
+}
+
+impl FilterValue {
+    /// Returns true if the filter matches all the values.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Text(text) => text.trim().is_empty(),
+            Self::Range { min, max } => min.is_none() && max.is_none(),
+            Self::Set(options) => options.is_empty(),
+        }
+    }
+
+    /// Returns true if the cell value matches the filter.
+    ///
+    /// - `Text` matches by [`CellValue::matches`].
+    /// - `Range` matches the numbers in the range, the other values are not matched.
+    /// - `Set` matches the text of the value in the options.
+    pub fn matches(&self, value: &CellValue) -> bool {
+        match self {
+            Self::Text(text) => value.matches(text),
+            Self::Range { min, max } => value.as_f64().is_some_and(|value| {
+                min.is_none_or(|min| value >= min) && max.is_none_or(|max| value <= max)
+            }),
+            Self::Set(options) => {
+                let text = value.to_string();
+                options.iter().any(|option| option.as_ref() == text)
+            }
+        }
+    }
+}
+
+impl From<SharedString> for FilterValue {
+    fn from(text: SharedString) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&str> for FilterValue {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string().into())
+    }
+}
+
+impl From<String> for FilterValue {
+    fn from(text: String) -> Self {
+        Self::Text(text.into())
+    }
+}
+
+/// The popover content to edit the filter of a column.
+pub(super) struct FilterPanel<D: TableDelegate> {
+    table: Entity<TableState<D>>,
+    col_ix: usize,
+    filter: ColumnFilter,
+    /// The text input, or the min and max inputs of the range.
+    inputs: Vec<Entity<InputState>>,
+    focus_handle: FocusHandle,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl<D: TableDelegate> FilterPanel<D> {
+    pub(super) fn new(
+        table: Entity<TableState<D>>,
+        col_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let state = table.read(cx);
+        let filter = state.col_groups[col_ix]
+            .column
+            .filter
+            .clone()
+            .unwrap_or(ColumnFilter::Text);
+        let value = state.filter(col_ix).cloned();
+
+        let defaults: Vec<(SharedString, String)> = match (&filter, value) {
+            (ColumnFilter::Text, value) => {
+                let text = match value {
+                    Some(FilterValue::Text(text)) => text.to_string(),
+                    _ => String::new(),
+                };
+                vec![(t!("Table.Filter").into(), text)]
+            }
+            (ColumnFilter::Range, value) => {
+                let (min, max) = match value {
+                    Some(FilterValue::Range { min, max }) => (min, max),
+                    _ => (None, None),
+                };
+                let text = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+                vec![
+                    (t!("Table.Min").into(), text(min)),
+                    (t!("Table.Max").into(), text(max)),
+                ]
+            }
+            (ColumnFilter::Set(_), _) => vec![],
+        };
+
+        let mut inputs = Vec::with_capacity(defaults.len());
+        let mut _subscriptions = Vec::with_capacity(defaults.len());
+        for (placeholder, text) in defaults {
+            let input = cx.new(|cx| {
+                InputState::new(window, cx)
+                    .placeholder(placeholder)
+                    .default_value(text)
+            });
+            _subscriptions.push(cx.subscribe_in(
+                &input,
+                window,
+                |this, _, event: &InputEvent, window, cx| {
+                    if let InputEvent::Change = event {
+                        this.apply_inputs(window, cx);
+                    }
+                },
+            ));
+            inputs.push(input);
+        }
+
+        Self {
+            table,
+            col_ix,
+            filter,
+            inputs,
+            focus_handle: cx.focus_handle(),
+            _subscriptions,
+        }
+    }
+
+    fn set_filter(&self, value: Option<FilterValue>, window: &mut Window, cx: &mut App) {
+        let col_ix = self.col_ix;
+        self.table.update(cx, |table, cx| {
+            table.set_filter(col_ix, value, window, cx);
+        });
+    }
+
+    fn apply_inputs(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let texts = self
+            .inputs
+            .iter()
+            .map(|input| input.read(cx).value())
+            .collect::<Vec<_>>();
+        let value = match self.filter {
+            ColumnFilter::Text => FilterValue::Text(texts[0].clone()),
+            ColumnFilter::Range => {
+                let parse = |text: &SharedString| text.trim().parse::<f64>().ok();
+                FilterValue::Range {
+                    min: parse(&texts[0]),
+                    max: parse(&texts[1]),
+                }
+            }
+            ColumnFilter::Set(_) => return,
+        };
+        self.set_filter(Some(value), window, cx);
+    }
+
+    fn toggle_option(&mut self, option: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let mut selected = match self.table.read(cx).filter(self.col_ix) {
+            Some(FilterValue::Set(selected)) => selected.clone(),
+            _ => vec![],
+        };
+        if let Some(ix) = selected.iter().position(|s| *s == option) {
+            selected.remove(ix);
+        } else {
+            selected.push(option);
+        }
+        self.set_filter(Some(FilterValue::Set(selected)), window, cx);
+        cx.notify();
+    }
+
+    fn clear(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_filter(None, window, cx);
+        cx.emit(DismissEvent);
+    }
+}
+
+impl<D: TableDelegate> EventEmitter<DismissEvent> for FilterPanel<D> {}
+
+impl<D: TableDelegate> Focusable for FilterPanel<D> {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl<D: TableDelegate> Render for FilterPanel<D> {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let selected = match self.table.read(cx).filter(self.col_ix) {
+            Some(FilterValue::Set(selected)) => selected.clone(),
+            _ => vec![],
+        };
+
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .gap_2()
+            .min_w(px(180.))
+            .map(|this| match &self.filter {
+                ColumnFilter::Text => this.child(Input::new(&self.inputs[0]).small()),
+                ColumnFilter::Range => this.child(
+                    h_flex()
+                        .gap_1()
+                        .child(Input::new(&self.inputs[0]).small())
+                        .child("-")
+                        .child(Input::new(&self.inputs[1]).small()),
+                ),
+                ColumnFilter::Set(options) => this.child(v_flex().gap_1().children(
+                    options.iter().enumerate().map(|(ix, option)| {
+                        let checked = selected.contains(option);
+                        let option = option.clone();
+                        Checkbox::new(("filter-option", ix))
+                            .small()
+                            .label(option.clone())
+                            .checked(checked)
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.toggle_option(option.clone(), window, cx);
+                            }))
+                    }),
+                )),
+            })
+            .child(
+                h_flex().justify_end().child(
+                    Button::new("clear-filter")
+                        .ghost()
+                        .xsmall()
+                        .label(t!("Table.Clear"))
+                        .on_click(cx.listener(|this, _, window, cx| this.clear(window, cx))),
+                ),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FilterValue;
+    use crate::table::CellValue;
+
+    #[test]
+    fn test_filter_matches() {
+        let text = FilterValue::from("app");
+        assert!(text.matches(&"Apple".into()));
+        assert!(!text.matches(&"Banana".into()));
+        assert!(FilterValue::from(">= 10").matches(&CellValue::Int(12)));
+
+        let range = FilterValue::Range {
+            min: Some(10.),
+            max: None,
+        };
+        assert!(range.matches(&CellValue::Int(10)));
+        assert!(range.matches(&CellValue::Float(99.5)));
+        assert!(!range.matches(&CellValue::Int(9)));
+        assert!(!range.matches(&CellValue::Null));
+        assert!(!range.is_empty());
+        assert!(FilterValue::Range {
+            min: None,
+            max: None
+        }
+        .is_empty());
+
+        let set = FilterValue::Set(vec!["Active".into(), "Pending".into()]);
+        assert!(set.matches(&"Active".into()));
+        assert!(!set.matches(&"Closed".into()));
+        assert!(FilterValue::Set(vec![]).is_empty());
+        assert!(FilterValue::from(" ").is_empty());
+    }
+}
@@ -1,31 +1,89 @@
-use std::{ops::Range, rc::Rc, time::Duration};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap, HashSet},
+    hash::{DefaultHasher, Hash as _, Hasher as _},
+    ops::Range,
+    rc::Rc,
+    time::Duration,
+};
 
 use crate::{
-    actions::{Cancel, SelectDown, SelectUp},
+    actions::{Cancel, Confirm, SelectDown, SelectUp},
+    button::{Button, ButtonVariants as _},
     h_flex,
+    indicator::Indicator,
+    input,
     menu::{ContextMenuExt, PopupMenu},
+    popover::{Popover, PopoverContent},
     scroll::{ScrollableMask, Scrollbar, ScrollbarState},
-    v_flex, ActiveTheme, Icon, IconName, Sizable, Size, StyleSized as _, StyledExt,
-    VirtualListScrollHandle,
+    tooltip::Tooltip,
+    v_flex, ActiveTheme, Density, Icon, IconName, Selectable as _, Sizable, Size, StyleSized as _,
+    StyledExt, VirtualListScrollHandle,
 };
 use gpui::{
-    actions, canvas, div, prelude::FluentBuilder, px, uniform_list, App, AppContext, Axis, Bounds,
-    Context, Div, DragMoveEvent, Edges, Entity, EventEmitter, FocusHandle, Focusable,
-    InteractiveElement, IntoElement, KeyBinding, ListSizingBehavior, MouseButton, MouseDownEvent,
-    ParentElement, Pixels, Point, Render, RenderOnce, ScrollStrategy, SharedString,
-    StatefulInteractiveElement as _, Styled, Task, UniformListScrollHandle, Window,
+    actions, canvas, div, prelude::FluentBuilder, px, rems, size, uniform_list, AnyElement, App,
+    AppContext, Axis, Bounds, ClickEvent, Context, Corner, Div, DragMoveEvent, Edges, Entity,
+    EventEmitter, FocusHandle, Focusable, InteractiveElement, IntoElement, KeyBinding,
+    ListSizingBehavior, MouseButton, MouseDownEvent, ParentElement, Pixels, Point, Render,
+    RenderOnce, ScrollStrategy, SharedString, StatefulInteractiveElement as _, Styled, Task,
+    TextAlign, UniformListScrollHandle, Window,
 };
 
+mod aggregate;
+mod cell_value;
+mod col_state;
 mod column;
 mod delegate;
+mod detail;
+mod editing;
+mod export;
+mod filter;
 mod loading;
-
+mod navigation;
+mod optimistic;
+mod paging;
+mod row_drag;
+mod tree;
+mod visibility;
+
+use aggregate::AggregateState;
+pub use aggregate::Aggregator;
+pub use cell_value::{CellComparator, CellValue};
+pub use col_state::{ColState, TableColState};
 pub use column::*;
 pub use delegate::*;
-
-actions!(table, [SelectPrevColumn, SelectNextColumn]);
+pub use export::{ExportFormat, ExportScope};
+use filter::FilterPanel;
+pub use filter::{ColumnFilter, FilterValue};
+use navigation::Movement;
+pub use optimistic::OptimisticEdit;
+use optimistic::PendingEdit;
+use row_drag::DragRow;
+
+actions!(
+    table,
+    [
+        SelectPrevColumn,
+        SelectNextColumn,
+        EditNextCell,
+        EditPrevCell,
+        SelectHome,
+        SelectEnd,
+        SelectFirstRow,
+        SelectLastRow,
+        SelectPageUp,
+        SelectPageDown
+    ]
+);
 
 const CONTEXT: &'static str = "Table";
+/// The key context of the cell in editing, see [`TableDelegate::render_editing_td`].
+const EDITOR_CONTEXT: &'static str = "TableCellEditor";
+/// The width of the "+N" column to show the hidden columns, see [`Column::priority`].
+const MORE_COLS_WIDTH: Pixels = px(40.);
+/// The width of the sort icon and the filter button on the right of the column name.
+const SORT_ICON_WIDTH: Pixels = px(16.);
+const FILTER_BUTTON_WIDTH: Pixels = px(20.);
 pub(crate) fn init(cx: &mut App) {
     cx.bind_keys([
         KeyBinding::new("escape", Cancel, Some(CONTEXT)),
@@ -33,6 +91,27 @@ pub(crate) fn init(cx: &mut App) {
         KeyBinding::new("down", SelectDown, Some(CONTEXT)),
         KeyBinding::new("left", SelectPrevColumn, Some(CONTEXT)),
         KeyBinding::new("right", SelectNextColumn, Some(CONTEXT)),
+        KeyBinding::new("home", SelectHome, Some(CONTEXT)),
+        KeyBinding::new("end", SelectEnd, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-up", SelectFirstRow, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-down", SelectLastRow, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-home", SelectFirstRow, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-end", SelectLastRow, Some(CONTEXT)),
+        KeyBinding::new("pageup", SelectPageUp, Some(CONTEXT)),
+        KeyBinding::new("pagedown", SelectPageDown, Some(CONTEXT)),
+        KeyBinding::new("enter", Confirm { secondary: false }, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-c", input::Copy, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-c", input::Copy, Some(CONTEXT)),
+        KeyBinding::new("enter", Confirm { secondary: false }, Some(EDITOR_CONTEXT)),
+        KeyBinding::new("escape", Cancel, Some(EDITOR_CONTEXT)),
+        KeyBinding::new("tab", EditNextCell, Some(EDITOR_CONTEXT)),
+        KeyBinding::new("shift-tab", EditPrevCell, Some(EDITOR_CONTEXT)),
     ]);
 }
 
@@ -40,10 +119,12 @@ pub(crate) fn init(cx: &mut App) {
 enum SelectionState {
     Column,
     Row,
+    /// The active cell in the selected row, see [`TableState::cell_selectable`].
+    Cell,
 }
 
 /// The Table event.
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub enum TableEvent {
     /// Single click or move to selected row.
     SelectRow(usize),
@@ -60,6 +141,69 @@ pub enum TableEvent {
     /// The first `usize` is the original index of the column,
     /// and the second `usize` is the new index of the column.
     MoveColumn(usize, usize),
+    /// A row has been moved by dragging, see [`TableState::move_row`].
+    ///
+    /// The first `usize` is the original index of the row,
+    /// and the second `usize` is the new index of the row.
+    MoveRow(usize, usize),
+    /// The sort of a column has changed by clicking the header.
+    ///
+    /// The `usize` is the index of the clicked column. A click sorts by this column only, the
+    /// other columns are reset to `ColumnSort::Default`. A Shift-click adds it to the multi-column
+    /// sort and keeps the other sorted columns, see [`TableState::sorts`] for all the sorted
+    /// columns by the priority, also saved as the [`ColState::sort_priority`].
+    SortChanged(usize, ColumnSort),
+    /// The filter of a column has changed, see [`TableState::set_filter`].
+    FilterChanged(usize, Option<FilterValue>),
+    /// The cell has been edited, see [`TableState::commit_editing`].
+    CellEdited { row: usize, col: usize },
+    /// The row has been expanded or collapsed, see [`TableState::set_row_expanded`].
+    RowExpanded { row: usize, expanded: bool },
+    /// The children of the row have been shown or hidden in the tree mode,
+    /// see [`TableState::set_tree_expanded`].
+    TreeExpanded { row: usize, expanded: bool },
+    /// The page has changed in the paged mode, see [`TableState::set_page`].
+    PageChanged(usize),
+    /// The table is scrolled within the [`TableDelegate::load_more_threshold`] rows of the end,
+    /// the [`TableDelegate::is_eof`] returns true, and not [`TableDelegate::has_no_more_rows`]
+    /// or [`TableDelegate::loading_more`].
+    ///
+    /// Emitted once for each number of the rows, along with the [`TableDelegate::load_more`],
+    /// e.g. to fetch the next page of a remote API.
+    LoadMore,
+    /// The visible range of the rows has changed, e.g. to prefetch the data of the rows.
+    VisibleRangeChanged(Range<usize>),
+    /// The active cell has changed by the keyboard or mouse, see [`TableState::cell_selectable`].
+    ActiveCellChanged { row: usize, col: usize },
+    /// The `enter` key is pressed on the selected row.
+    ActivateRow(usize),
+    /// The column has been shown or hidden, see [`TableState::set_col_visible`].
+    ColumnVisibilityChanged { col: usize, visible: bool },
+}
+
+/// The row to keep at the same position in the viewport when the rows are refreshed or sorted,
+/// see [`TableState::preserve_scroll_position`].
+#[derive(Debug, Clone)]
+struct ScrollAnchor {
+    /// The id of the anchor row.
+    row_id: RowId,
+    /// The top of the anchor row relative to the top of the viewport.
+    offset: Pixels,
+}
+
+/// The scroll offset, the visible rows, the selected row, the rows count and the expanded rows
+/// count when the [`ScrollAnchor`] is captured, to capture it again only when changed.
+type ScrollAnchorKey = (Pixels, Range<usize>, Option<usize>, usize, usize);
+
+/// The widths of the shaped text of the cells and the headers, to not shape the text on each frame.
+#[derive(Default)]
+struct TextWidths {
+    /// The font of the shaped text, the widths are cleared when changed.
+    font: (SharedString, Pixels),
+    /// The visible rows when the widths of the other rows are removed.
+    rows: Range<usize>,
+    /// The text and the width by the `(row_ix, col_ix)`, the `row_ix` is `None` for the headers.
+    widths: HashMap<(Option<usize>, usize), (SharedString, Pixels)>,
 }
 
 /// The visible range of the rows and columns.
@@ -89,8 +233,16 @@ struct TableOptions {
     stripe: bool,
     /// Set to use border style of the table.
     bordered: bool,
-    /// The cell size of the table.
+    /// The cell size of the table, adjusted by the `density`.
     size: Size,
+    /// The density of the table, `None` to use the [`Theme::table_density`](crate::Theme::table_density).
+    density: Option<Density>,
+    /// The fixed height of the rows, `None` to use the height of the `size`.
+    row_height: Option<Pixels>,
+    /// Set to show the footer row pinned to the bottom.
+    footer: bool,
+    /// The number of the rows per page in the paged mode.
+    page_size: Option<usize>,
 }
 
 impl Default for TableOptions {
@@ -100,10 +252,22 @@ impl Default for TableOptions {
             stripe: false,
             bordered: true,
             size: Size::default(),
+            density: None,
+            row_height: None,
+            footer: false,
+            page_size: None,
         }
     }
 }
 
+impl TableOptions {
+    /// Returns the height of the header and the rows, without the wrapped lines.
+    fn row_height(&self) -> Pixels {
+        self.row_height
+            .unwrap_or_else(|| self.size.table_row_height())
+    }
+}
+
 /// The state for [`Table`].
 pub struct TableState<D: TableDelegate> {
     focus_handle: FocusHandle,
@@ -115,6 +279,8 @@ pub struct TableState<D: TableDelegate> {
     fixed_head_cols_bounds: Bounds<Pixels>,
 
     col_groups: Vec<ColGroup>,
+    /// The running aggregations of the columns with [`Aggregator`], by the column index.
+    aggregates: Vec<Option<AggregateState>>,
 
     /// Whether the table can loop selection, default is true.
     ///
@@ -124,33 +290,100 @@ pub struct TableState<D: TableDelegate> {
     pub col_selectable: bool,
     /// Whether the table can select row.
     pub row_selectable: bool,
+    /// Whether the table can select cell, see [`TableState::cell_selectable`].
+    pub cell_selectable: bool,
     /// Whether the table can sort.
     pub sortable: bool,
+    /// Whether the rows are sorted asynchronously by the delegate, see [`TableState::async_sort`].
+    async_sort: bool,
     /// Whether the table can resize columns.
     pub col_resizable: bool,
     /// Whether the table can move columns.
     pub col_movable: bool,
+    /// Whether the columns can be hidden by the context menu of the header.
+    pub col_hideable: bool,
     /// Enable/disable fixed columns feature.
     pub col_fixed: bool,
+    /// Whether the table can move rows by dragging, see [`TableDelegate::can_move_row`].
+    pub row_movable: bool,
+    /// Whether to drag the rows by the grip in the first cell, instead of the whole row.
+    row_drag_handle: bool,
+    /// Whether to show the rows as a tree by the [`TableDelegate::row_depth`].
+    tree: bool,
 
     pub vertical_scroll_handle: UniformListScrollHandle,
     pub vertical_scroll_state: ScrollbarState,
     pub horizontal_scroll_handle: VirtualListScrollHandle,
     pub horizontal_scroll_state: ScrollbarState,
+    /// The scroll handle of the rows list with the detail panels, it shares the base handle
+    /// with the `vertical_scroll_handle` to keep the scroll position.
+    detail_scroll_handle: VirtualListScrollHandle,
 
     selected_row: Option<usize>,
+    /// The id of the selected row, to track the selection when the rows changed.
+    selected_row_id: Option<RowId>,
     selection_state: SelectionState,
     right_clicked_row: Option<usize>,
+    /// Whether the header is right clicked to show the column visibility menu.
+    right_clicked_head: bool,
     selected_col: Option<usize>,
+    /// The column of the active cell in the selected row.
+    active_col: Option<usize>,
 
     /// The column index that is being resized.
     resizing_col: Option<usize>,
+    /// The keys of the columns waiting for the [`TableState::sort_completed`].
+    sorting_cols: Vec<SharedString>,
+    /// The generation of the latest sort, see [`TableState::sort_generation`].
+    sort_generation: usize,
+    /// The index to insert the dragging row before, `rows_count` for after the last row.
+    drop_row: Option<usize>,
 
     /// The visible range of the rows and columns.
     visible_range: TableVisibleRange,
+    /// Whether to keep the anchor row in the viewport when the rows are refreshed or sorted.
+    preserve_scroll_position: bool,
+    scroll_anchor: Option<ScrollAnchor>,
+    scroll_anchor_key: Option<ScrollAnchorKey>,
+    /// The hash of the inputs of the last [`TableState::update_hidden_cols`].
+    hidden_cols_key: Option<u64>,
+    /// The rows count of the last render, to sync the selected row when changed.
+    rendered_rows_count: usize,
+    text_widths: RefCell<TextWidths>,
+
+    /// Whether to show the loading overlay over the rows, see [`TableState::set_loading`].
+    overlay_loading: bool,
+    /// The optimistic edits not committed or rolled back yet.
+    pending_edits: Vec<PendingEdit<D>>,
+    next_edit_id: usize,
+    /// The `(row_ix, col_ix)` of the cell in editing, see [`TableState::edit_cell`].
+    editing_cell: Option<(usize, usize)>,
+    /// The expanded rows with the measured size of the detail panels, `None` if not measured yet,
+    /// see [`TableState::set_row_expanded`].
+    expanded_rows: BTreeMap<usize, Option<gpui::Size<Pixels>>>,
+    /// The ids of the expanded rows, to track the expanded rows by the identity.
+    expanded_row_ids: HashSet<RowId>,
+    /// The ids of the rows with the children shown in the tree mode.
+    tree_expanded: HashSet<RowId>,
+    /// The tasks to load the children of the expanding rows, see [`TableDelegate::row_children`].
+    tree_loading: HashMap<RowId, Task<()>>,
+
+    /// The depth of the nested [`TableState::begin_update`] calls.
+    update_depth: usize,
+    /// The refresh or notify deferred to the [`TableState::end_update`].
+    pending_refresh: bool,
+    pending_notify: bool,
+
+    /// The current page in the paged mode.
+    page: usize,
+    /// Whether the current page is loaded by the [`TableDelegate::load_page`].
+    page_loaded: bool,
 
     _measure: Vec<Duration>,
     _load_more_task: Task<()>,
+    /// The number of the rows when the last [`TableEvent::LoadMore`] is emitted.
+    load_more_rows_count: Option<usize>,
+    _load_page_task: Task<()>,
 }
 
 impl<D> TableState<D>
@@ -159,31 +392,71 @@ where
 {
     /// Create a new TableState with the given delegate.
     pub fn new(delegate: D, _: &mut Window, cx: &mut Context<Self>) -> Self {
+        let vertical_scroll_handle = UniformListScrollHandle::new();
+        let detail_scroll_handle =
+            VirtualListScrollHandle::from(vertical_scroll_handle.0.borrow().base_handle.clone());
+
         let mut this = Self {
-            focus_handle: cx.focus_handle(),
+            // A single tab stop, the arrow keys move the selection in the table.
+            focus_handle: cx.focus_handle().tab_stop(true),
             options: TableOptions::default(),
             delegate,
             col_groups: Vec::new(),
+            aggregates: Vec::new(),
             horizontal_scroll_handle: VirtualListScrollHandle::new(),
-            vertical_scroll_handle: UniformListScrollHandle::new(),
+            vertical_scroll_handle,
             vertical_scroll_state: ScrollbarState::default(),
             horizontal_scroll_state: ScrollbarState::default(),
+            detail_scroll_handle,
             selection_state: SelectionState::Row,
             selected_row: None,
+            selected_row_id: None,
             right_clicked_row: None,
+            right_clicked_head: false,
             selected_col: None,
+            active_col: None,
             resizing_col: None,
+            drop_row: None,
             bounds: Bounds::default(),
             fixed_head_cols_bounds: Bounds::default(),
             visible_range: TableVisibleRange::default(),
+            preserve_scroll_position: false,
+            scroll_anchor: None,
+            scroll_anchor_key: None,
+            hidden_cols_key: None,
+            rendered_rows_count: 0,
+            text_widths: RefCell::new(TextWidths::default()),
+            overlay_loading: false,
+            pending_edits: Vec::new(),
+            next_edit_id: 0,
+            editing_cell: None,
+            expanded_rows: BTreeMap::new(),
+            expanded_row_ids: HashSet::new(),
+            tree_expanded: HashSet::new(),
+            tree_loading: HashMap::new(),
             loop_selection: true,
             col_selectable: true,
             row_selectable: true,
+            cell_selectable: false,
             sortable: true,
+            async_sort: false,
+            sorting_cols: Vec::new(),
+            sort_generation: 0,
             col_movable: true,
+            col_hideable: true,
             col_resizable: true,
             col_fixed: true,
+            row_movable: true,
+            row_drag_handle: false,
+            tree: false,
+            update_depth: 0,
+            pending_refresh: false,
+            pending_notify: false,
+            page: 0,
+            page_loaded: false,
             _load_more_task: Task::ready(()),
+            load_more_rows_count: None,
+            _load_page_task: Task::ready(()),
             _measure: Vec::new(),
         };
 
@@ -213,6 +486,37 @@ where
         self
     }
 
+    /// Set to enable/disable the context menu of the header to show or hide the columns, default to true.
+    ///
+    /// See [`TableState::set_col_visible`].
+    pub fn col_hideable(mut self, col_hideable: bool) -> Self {
+        self.col_hideable = col_hideable;
+        self
+    }
+
+    /// Set to enable/disable row movable by dragging, default to true.
+    ///
+    /// The rows are movable only if the [`TableDelegate::can_move_row`] returns true.
+    pub fn row_movable(mut self, row_movable: bool) -> Self {
+        self.row_movable = row_movable;
+        self
+    }
+
+    /// Set to drag the rows by a grip in the first cell instead of the whole row, default to false.
+    pub fn row_drag_handle(mut self, row_drag_handle: bool) -> Self {
+        self.row_drag_handle = row_drag_handle;
+        self
+    }
+
+    /// Set to show the rows as a tree with the indent guides and the chevrons to expand
+    /// the children, default to false.
+    ///
+    /// See [`TableDelegate::row_depth`] and [`TableDelegate::row_children`].
+    pub fn tree(mut self, tree: bool) -> Self {
+        self.tree = tree;
+        self
+    }
+
     /// Set to enable/disable column resizable, default to true.
     pub fn col_resizable(mut self, col_resizable: bool) -> Self {
         self.col_resizable = col_resizable;
@@ -225,6 +529,26 @@ where
         self
     }
 
+    /// Set to sort the rows asynchronously, e.g. by a server, default to false.
+    ///
+    /// The [`TableDelegate::perform_sort`] can start a task to sort the rows, the headers of
+    /// the sorted columns show a loading indicator until the [`TableState::sort_completed`] is called
+    /// with the [`TableState::sort_generation`] of the sort.
+    pub fn async_sort(mut self, async_sort: bool) -> Self {
+        self.async_sort = async_sort;
+        self
+    }
+
+    /// Set to keep the viewport anchored to the selected (or the first visible) row when
+    /// the rows are refreshed or sorted, instead of keeping the scroll offset, default false.
+    ///
+    /// The rows are matched by the [`TableDelegate::row_id`], the selected row is also
+    /// moved to the new index of the row.
+    pub fn preserve_scroll_position(mut self, preserve: bool) -> Self {
+        self.preserve_scroll_position = preserve;
+        self
+    }
+
     /// Set to enable/disable row selectable, default true
     pub fn row_selectable(mut self, row_selectable: bool) -> Self {
         self.row_selectable = row_selectable;
@@ -237,25 +561,105 @@ where
         self
     }
 
+    /// Set to enable/disable cell selectable, default false
+    ///
+    /// When enabled, the arrow keys move the active cell instead of selecting the rows and columns,
+    /// and the active cell is shown with a focus ring, see [`TableEvent::ActiveCellChanged`].
+    pub fn cell_selectable(mut self, cell_selectable: bool) -> Self {
+        self.cell_selectable = cell_selectable;
+        self
+    }
+
     /// When we update columns or rows, we need to refresh the table.
+    ///
+    /// In a batch update, the refresh is deferred to the [`Self::end_update`].
     pub fn refresh(&mut self, cx: &mut Context<Self>) {
+        if self.update_depth > 0 {
+            self.pending_refresh = true;
+            return;
+        }
+
         self.prepare_col_groups(cx);
     }
 
+    /// Begin a batch update, the [`Self::refresh`] and the notify of the table are deferred
+    /// until the [`Self::end_update`], e.g. to stream many rows into the table with a single re-render.
+    ///
+    /// The calls can be nested, the table is refreshed once at the outermost `end_update`.
+    pub fn begin_update(&mut self) {
+        self.update_depth += 1;
+    }
+
+    /// End a batch update started by [`Self::begin_update`], and refresh the table if needed.
+    pub fn end_update(&mut self, cx: &mut Context<Self>) {
+        self.update_depth = self.update_depth.saturating_sub(1);
+        if self.update_depth > 0 {
+            return;
+        }
+
+        if std::mem::take(&mut self.pending_refresh) {
+            self.pending_notify = false;
+            self.prepare_col_groups(cx);
+        } else if std::mem::take(&mut self.pending_notify) {
+            cx.notify();
+        }
+    }
+
+    /// Run the `f` in a batch update, see [`Self::begin_update`].
+    ///
+    /// ```ignore
+    /// table.update(cx, |table, cx| {
+    ///     table.batch_update(cx, |table, cx| {
+    ///         for row in rows {
+    ///             table.delegate_mut().push(row);
+    ///             table.refresh(cx);
+    ///         }
+    ///     });
+    /// });
+    /// ```
+    pub fn batch_update<R>(
+        &mut self,
+        cx: &mut Context<Self>,
+        f: impl FnOnce(&mut Self, &mut Context<Self>) -> R,
+    ) -> R {
+        self.begin_update();
+        let result = f(self, cx);
+        self.end_update(cx);
+        result
+    }
+
+    /// Returns true if the table is in a batch update.
+    pub fn is_updating(&self) -> bool {
+        self.update_depth > 0
+    }
+
+    /// Notify the table to re-render, or defer it to the end of the batch update.
+    fn notify(&mut self, cx: &mut Context<Self>) {
+        if self.update_depth > 0 {
+            self.pending_notify = true;
+            return;
+        }
+
+        cx.notify();
+    }
+
     /// Scroll to the row at the given index.
     pub fn scroll_to_row(&mut self, row_ix: usize, cx: &mut Context<Self>) {
-        self.vertical_scroll_handle
-            .scroll_to_item(row_ix, ScrollStrategy::Top);
-        cx.notify();
+        self.scroll_to_row_item(row_ix, ScrollStrategy::Top);
+        self.notify(cx);
     }
 
     // Scroll to the column at the given index.
     pub fn scroll_to_col(&mut self, col_ix: usize, cx: &mut Context<Self>) {
+        // The right fixed columns are always visible.
+        if self.fixed_right_cols().contains(&col_ix) {
+            return;
+        }
         let col_ix = col_ix.saturating_sub(self.fixed_left_cols_count());
 
         self.horizontal_scroll_handle
             .scroll_to_item(col_ix, ScrollStrategy::Top);
-        cx.notify();
+        self.notify(cx);
     }
 
     /// Returns the selected row index.
@@ -263,6 +667,20 @@ where
         self.selected_row
     }
 
+    /// Returns the id of the selected row, if the delegate provides the [`TableDelegate::row_id`].
+    pub fn selected_row_id(&self) -> Option<&RowId> {
+        self.selected_row_id.as_ref()
+    }
+
+    /// Sets the selected row by the id, returns false if the row is not found.
+    pub fn set_selected_row_id(&mut self, row_id: &RowId, cx: &mut Context<Self>) -> bool {
+        let Some(row_ix) = self.row_ix(row_id, cx) else {
+            return false;
+        };
+        self.set_selected_row(row_ix, cx);
+        true
+    }
+
     /// Sets the selected row to the given index.
     pub fn set_selected_row(&mut self, row_ix: usize, cx: &mut Context<Self>) {
         let is_down = match self.selected_row {
@@ -273,8 +691,9 @@ where
         self.selection_state = SelectionState::Row;
         self.right_clicked_row = None;
         self.selected_row = Some(row_ix);
+        self.selected_row_id = self.delegate.row_id(row_ix, cx);
         if let Some(row_ix) = self.selected_row {
-            self.vertical_scroll_handle.scroll_to_item(
+            self.scroll_to_row_item(
                 row_ix,
                 if is_down {
                     ScrollStrategy::Bottom
@@ -284,7 +703,7 @@ where
             );
         }
         cx.emit(TableEvent::SelectRow(row_ix));
-        cx.notify();
+        self.notify(cx);
     }
 
     /// Returns the selected column index.
@@ -300,15 +719,34 @@ where
             self.scroll_to_col(col_ix, cx);
         }
         cx.emit(TableEvent::SelectColumn(col_ix));
-        cx.notify();
+        self.notify(cx);
     }
 
     /// Clear the selection of the table.
     pub fn clear_selection(&mut self, cx: &mut Context<Self>) {
         self.selection_state = SelectionState::Row;
         self.selected_row = None;
+        self.selected_row_id = None;
         self.selected_col = None;
-        cx.notify();
+        self.active_col = None;
+        self.notify(cx);
+    }
+
+    /// Set to show a loading overlay with a spinner over the current rows, e.g. when reloading the data.
+    ///
+    /// Unlike the [`TableDelegate::loading`], the rows are kept visible under the overlay.
+    pub fn set_loading(&mut self, loading: bool, cx: &mut Context<Self>) {
+        if self.overlay_loading == loading {
+            return;
+        }
+
+        self.overlay_loading = loading;
+        self.notify(cx);
+    }
+
+    /// Returns true if the loading overlay is shown, see [`Self::set_loading`].
+    pub fn is_loading(&self) -> bool {
+        self.overlay_loading
     }
 
     /// Returns the visible range of the rows and columns.
@@ -322,16 +760,270 @@ where
         self.col_groups = (0..self.delegate.columns_count(cx))
             .map(|col_ix| {
                 let column = self.delegate().column(col_ix, cx);
+                let visible = self.delegate.col_visible(col_ix, cx);
                 ColGroup {
-                    width: column.width,
+                    width: column.width.min(column.max_width).max(column.min_width),
                     bounds: Bounds::default(),
                     column: column.clone(),
+                    filter_value: None,
+                    sort_priority: None,
+                    visible,
+                    hidden: !visible,
+                    group: self.delegate.col_group(col_ix, cx),
                 }
             })
             .collect();
+        let mut priority = 0;
+        for col_group in self.col_groups.iter_mut() {
+            if matches!(
+                col_group.column.sort,
+                Some(ColumnSort::Ascending | ColumnSort::Descending)
+            ) {
+                col_group.sort_priority = Some(priority);
+                priority += 1;
+            }
+        }
+        self.prepare_aggregates(cx);
+        self.sync_selected_row(cx);
+        self.sync_expanded_rows(false, cx);
+        self.restore_scroll_anchor(cx);
+        self.invalidate_row_details();
+        self.hidden_cols_key = None;
+        cx.notify();
+    }
+
+    /// Returns true if any column has a [`TableDelegate::col_group`].
+    fn has_col_group(&self) -> bool {
+        self.col_groups
+            .iter()
+            .any(|col_group| col_group.group.is_some())
+    }
+
+    /// Returns the height of the table head, two rows if there are column groups.
+    fn head_height(&self) -> Pixels {
+        let row_height = self.options.row_height();
+        if self.has_col_group() {
+            row_height * 2.
+        } else {
+            row_height
+        }
+    }
+
+    /// Returns the height of the rows, measured in the last layout.
+    fn row_height(&self) -> Pixels {
+        self.vertical_scroll_handle
+            .0
+            .borrow()
+            .last_item_size
+            .map(|size| size.item.height)
+            .filter(|height| *height > px(0.))
+            .unwrap_or(self.options.row_height())
+    }
+
+    /// Save the anchor row of the current rows to restore the scroll position after the
+    /// rows are refreshed or sorted.
+    fn capture_scroll_anchor(&mut self, cx: &App) {
+        if !self.preserve_scroll_position {
+            return;
+        }
+        self.scroll_anchor_key = Some(self.scroll_anchor_key(cx));
+
+        let rows_count = self.delegate.rows_count(cx);
+        let visible_rows = self.visible_range.rows.clone();
+        let anchor_ix = self
+            .selected_row
+            .filter(|ix| visible_rows.contains(ix))
+            .or(Some(visible_rows.start))
+            .filter(|ix| *ix < rows_count);
+        let Some(anchor_ix) = anchor_ix else {
+            self.scroll_anchor = None;
+            return;
+        };
+
+        let scroll_y = self
+            .vertical_scroll_handle
+            .0
+            .borrow()
+            .base_handle
+            .offset()
+            .y;
+        self.scroll_anchor = self
+            .delegate
+            .row_id(anchor_ix, cx)
+            .map(|row_id| ScrollAnchor {
+                row_id,
+                offset: self.row_height() * anchor_ix as f32
+                    + self.row_details_height_before(anchor_ix)
+                    + scroll_y,
+            });
+    }
+
+    fn scroll_anchor_key(&self, cx: &App) -> ScrollAnchorKey {
+        let scroll_y = self
+            .vertical_scroll_handle
+            .0
+            .borrow()
+            .base_handle
+            .offset()
+            .y;
+        (
+            scroll_y,
+            self.visible_range.rows.clone(),
+            self.selected_row,
+            self.delegate.rows_count(cx),
+            self.expanded_rows.len(),
+        )
+    }
+
+    /// Scroll to keep the anchor row at the same position.
+    fn restore_scroll_anchor(&mut self, cx: &App) {
+        let Some(anchor) = self.scroll_anchor.take() else {
+            return;
+        };
+        self.scroll_anchor_key = None;
+
+        if let Some(anchor_ix) = self.row_ix(&anchor.row_id, cx) {
+            let scroll_handle = self.vertical_scroll_handle.0.borrow();
+            let mut offset = scroll_handle.base_handle.offset();
+            offset.y = (anchor.offset
+                - self.row_height() * anchor_ix as f32
+                - self.row_details_height_before(anchor_ix))
+            .min(px(0.));
+            scroll_handle.base_handle.set_offset(offset);
+        }
+    }
+
+    /// Returns the index of the row with the `row_id`, see [`TableDelegate::row_id`].
+    pub fn row_ix(&self, row_id: &RowId, cx: &App) -> Option<usize> {
+        (0..self.delegate.rows_count(cx))
+            .find(|row_ix| self.delegate.row_id(*row_ix, cx).as_ref() == Some(row_id))
+    }
+
+    /// Move the selected row to the new index of the selected row id after the rows changed,
+    /// or clear the selection if the row is removed.
+    fn sync_selected_row(&mut self, cx: &App) {
+        let Some(row_id) = self.selected_row_id.as_ref() else {
+            return;
+        };
+        let rows_count = self.delegate.rows_count(cx);
+        if self
+            .selected_row
+            .filter(|ix| *ix < rows_count)
+            .and_then(|ix| self.delegate.row_id(ix, cx))
+            .as_ref()
+            == Some(row_id)
+        {
+            return;
+        }
+
+        self.selected_row = self.row_ix(row_id, cx);
+        if self.selected_row.is_none() {
+            self.selected_row_id = None;
+        }
+        self.right_clicked_row = None;
+    }
+
+    /// Recalculate the aggregations of all the rows.
+    fn prepare_aggregates(&mut self, cx: &App) {
+        let rows_count = self.delegate.rows_count(cx);
+        self.aggregates = self
+            .col_groups
+            .iter()
+            .enumerate()
+            .map(|(col_ix, col_group)| {
+                col_group.column.aggregator.as_ref()?;
+                let mut state = AggregateState::default();
+                state.splice(
+                    0..0,
+                    (0..rows_count)
+                        .map(|row_ix| self.delegate.column_value(row_ix, col_ix, cx))
+                        .collect(),
+                );
+                Some(state)
+            })
+            .collect();
+    }
+
+    /// Update the aggregations after the rows in the `range` are replaced by `new_len` rows,
+    /// only the values of the new rows are read by [`TableDelegate::column_value`].
+    ///
+    /// For example, `rows_changed(10..10, 5, cx)` after inserting 5 rows at 10,
+    /// `rows_changed(3..4, 0, cx)` after removing the row 3, `rows_changed(3..4, 1, cx)` after updating the row 3.
+    ///
+    /// The aggregations are recalculated on render if the rows count is changed without this.
+    pub fn rows_changed(&mut self, range: Range<usize>, new_len: usize, cx: &mut Context<Self>) {
+        for (col_ix, state) in self.aggregates.iter_mut().enumerate() {
+            let Some(state) = state else {
+                continue;
+            };
+            let values = (range.start..range.start + new_len)
+                .map(|row_ix| self.delegate.column_value(row_ix, col_ix, cx))
+                .collect();
+            state.splice(range.clone(), values);
+        }
+        self.splice_expanded_rows(range, new_len);
+        self.sync_expanded_rows(false, cx);
+        self.sync_selected_row(cx);
+        self.notify(cx);
+    }
+
+    /// Returns the filter of the column, if any.
+    pub fn filter(&self, col_ix: usize) -> Option<&FilterValue> {
+        self.col_groups.get(col_ix)?.filter_value.as_ref()
+    }
+
+    /// Set the filter of the column, `None` or an empty filter to clear the filter.
+    ///
+    /// The [`TableDelegate::apply_filter`] is called to filter the rows,
+    /// and a [`TableEvent::FilterChanged`] is emitted.
+    pub fn set_filter(
+        &mut self,
+        col_ix: usize,
+        filter: Option<impl Into<FilterValue>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let filter = filter.map(Into::into).filter(|filter| !filter.is_empty());
+        let Some(col_group) = self.col_groups.get_mut(col_ix) else {
+            return;
+        };
+        if col_group.filter_value == filter {
+            return;
+        }
+
+        col_group.filter_value = filter.clone();
+        self.delegate_mut()
+            .apply_filter(col_ix, filter.clone(), window, cx);
+        // The rows are changed.
+        self.prepare_aggregates(cx);
+        self.sync_selected_row(cx);
+        self.sync_expanded_rows(true, cx);
+        self.reset_page(window, cx);
+
+        cx.emit(TableEvent::FilterChanged(col_ix, filter));
         cx.notify();
     }
 
+    /// Returns the aggregated value of the column by the [`Aggregator`] of the column.
+    pub fn aggregate(&self, col_ix: usize) -> Option<f64> {
+        let aggregator = self.col_groups.get(col_ix)?.column.aggregator.as_ref()?;
+        self.aggregates.get(col_ix)?.as_ref()?.value(aggregator)
+    }
+
+    /// Returns the aggregated value of the column in the `rows`, e.g. to show in a group header row.
+    pub fn aggregate_rows(
+        &self,
+        col_ix: usize,
+        aggregator: &Aggregator,
+        rows: impl IntoIterator<Item = usize>,
+        cx: &App,
+    ) -> Option<f64> {
+        aggregator.aggregate(
+            rows.into_iter()
+                .filter_map(|row_ix| self.delegate.column_value(row_ix, col_ix, cx)),
+        )
+    }
+
     fn fixed_left_cols_count(&self) -> usize {
         if !self.col_fixed {
             return 0;
@@ -343,15 +1035,46 @@ where
             .count()
     }
 
+    /// Returns the range of the right fixed columns, they are the last columns of the table.
+    fn fixed_right_cols(&self) -> Range<usize> {
+        let len = self.col_groups.len();
+        if !self.col_fixed {
+            return len..len;
+        }
+
+        let count = self
+            .col_groups
+            .iter()
+            .rev()
+            .take_while(|col| col.column.fixed == Some(ColumnFixed::Right))
+            .count();
+        len - count..len
+    }
+
+    /// Returns the total width of the right fixed columns.
+    fn fixed_right_cols_width(&self) -> Pixels {
+        self.fixed_right_cols()
+            .map(|col_ix| self.col_groups[col_ix].visible_width())
+            .fold(px(0.), |sum, width| sum + width)
+    }
+
     fn on_row_click(
         &mut self,
         ev: &MouseDownEvent,
         row_ix: usize,
-        _: &mut Window,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if self
+            .editing_cell
+            .is_some_and(|(editing_row, _)| editing_row != row_ix)
+        {
+            self.commit_editing(window, cx);
+        }
+
         if ev.button == MouseButton::Right {
             self.right_clicked_row = Some(row_ix);
+            self.right_clicked_head = false;
         } else {
             self.set_selected_row(row_ix, cx);
 
@@ -390,6 +1113,11 @@ where
     }
 
     fn action_select_prev(&mut self, _: &SelectUp, _: &mut Window, cx: &mut Context<Self>) {
+        if self.cell_selectable {
+            self.move_selection(Movement::Up, cx);
+            return;
+        }
+
         let rows_count = self.delegate.rows_count(cx);
         if rows_count < 1 {
             return;
@@ -408,6 +1136,11 @@ where
     }
 
     fn action_select_next(&mut self, _: &SelectDown, _: &mut Window, cx: &mut Context<Self>) {
+        if self.cell_selectable {
+            self.move_selection(Movement::Down, cx);
+            return;
+        }
+
         let rows_count = self.delegate.rows_count(cx);
         if rows_count < 1 {
             return;
@@ -434,6 +1167,11 @@ where
         _: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if self.cell_selectable {
+            self.move_selection(Movement::Left, cx);
+            return;
+        }
+
         let mut selected_col = self.selected_col.unwrap_or(0);
         let columns_count = self.delegate.columns_count(cx);
         if selected_col > 0 {
@@ -452,6 +1190,11 @@ where
         _: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if self.cell_selectable {
+            self.move_selection(Movement::Right, cx);
+            return;
+        }
+
         let mut selected_col = self.selected_col.unwrap_or(0);
         if selected_col < self.delegate.columns_count(cx).saturating_sub(1) {
             selected_col += 1;
@@ -498,8 +1241,6 @@ where
             return;
         }
 
-        const MIN_WIDTH: Pixels = px(10.0);
-        const MAX_WIDTH: Pixels = px(1200.0);
         let Some(col_group) = self.col_groups.get_mut(ix) else {
             return;
         };
@@ -510,21 +1251,43 @@ where
         let size = size.floor();
 
         let old_width = col_group.width;
-        let new_width = size;
-        if new_width < MIN_WIDTH {
-            return;
-        }
+        let new_width = size
+            .min(col_group.column.max_width)
+            .max(col_group.column.min_width);
         let changed_width = new_width - old_width;
         // If change size is less than 1px, do nothing.
         if changed_width > px(-1.0) && changed_width < px(1.0) {
             return;
         }
-        col_group.width = new_width.min(MAX_WIDTH);
+        col_group.width = new_width;
 
         cx.notify();
     }
 
-    fn perform_sort(&mut self, col_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+    /// Returns the sorted columns and the sort orders by the priority.
+    pub fn sorts(&self) -> Vec<(usize, ColumnSort)> {
+        let mut sorts = self
+            .col_groups
+            .iter()
+            .enumerate()
+            .filter_map(|(ix, col_group)| {
+                let priority = col_group.sort_priority?;
+                Some((priority, ix, col_group.column.sort?))
+            })
+            .collect::<Vec<_>>();
+        sorts.sort_by_key(|(priority, _, _)| *priority);
+        sorts.into_iter().map(|(_, ix, sort)| (ix, sort)).collect()
+    }
+
+    /// Toggle the sort of the column, if `multiple` (Shift-click) the column is added to
+    /// the multi-column sort, otherwise the other columns are reset.
+    fn perform_sort(
+        &mut self,
+        col_ix: usize,
+        multiple: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
         if !self.sortable {
             return;
         }
@@ -541,21 +1304,119 @@ where
             ColumnSort::Default => ColumnSort::Descending,
         };
 
+        let next_priority = self
+            .col_groups
+            .iter()
+            .filter_map(|g| g.sort_priority)
+            .max()
+            .map_or(0, |priority| priority + 1);
         for (ix, col_group) in self.col_groups.iter_mut().enumerate() {
             if ix == col_ix {
                 col_group.column.sort = Some(sort);
-            } else {
+                if sort == ColumnSort::Default {
+                    col_group.sort_priority = None;
+                } else if !multiple {
+                    col_group.sort_priority = Some(0);
+                } else if col_group.sort_priority.is_none() {
+                    col_group.sort_priority = Some(next_priority);
+                }
+            } else if !multiple {
+                col_group.sort_priority = None;
                 if col_group.column.sort.is_some() {
                     col_group.column.sort = Some(ColumnSort::Default);
                 }
             }
         }
 
-        self.delegate_mut().perform_sort(col_ix, sort, window, cx);
+        let sorts = self.sorts();
+        // Compact the priorities after a column is removed from the sort.
+        for (priority, (ix, _)) in sorts.iter().enumerate() {
+            self.col_groups[*ix].sort_priority = Some(priority);
+        }
+
+        let mut sorted_cols = sorts.iter().map(|(ix, _)| *ix).collect::<Vec<_>>();
+        if !sorted_cols.contains(&col_ix) {
+            sorted_cols.push(col_ix);
+        }
+
+        self.capture_scroll_anchor(cx);
+        self.sort_generation += 1;
+        if multiple && !sorts.is_empty() {
+            self.delegate_mut().perform_multi_sort(sorts, window, cx);
+        } else {
+            self.delegate_mut().perform_sort(col_ix, sort, window, cx);
+        }
+        self.sort_performed(sorted_cols, window, cx);
 
+        cx.emit(TableEvent::SortChanged(col_ix, sort));
         cx.notify();
     }
 
+    /// Update the rows after the delegate sorted them, or wait for the [`Self::sort_completed`]
+    /// in the [`Self::async_sort`] mode, with the loading indicator on the `col_ixs`.
+    fn sort_performed(&mut self, col_ixs: Vec<usize>, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.async_sort {
+            self.rows_reordered(window, cx);
+            return;
+        }
+
+        self.sorting_cols = col_ixs
+            .into_iter()
+            .filter_map(|ix| self.col_groups.get(ix))
+            .map(|col_group| col_group.column.key.clone())
+            .collect();
+    }
+
+    /// Returns the generation of the latest sort, increased before each [`TableDelegate::perform_sort`]
+    /// and [`TableDelegate::perform_multi_sort`].
+    ///
+    /// Read it in the task started by the [`TableDelegate::perform_sort`], and pass it to
+    /// the [`Self::sort_completed`].
+    pub fn sort_generation(&self) -> usize {
+        self.sort_generation
+    }
+
+    /// Call when the rows are sorted by the delegate in the [`Self::async_sort`] mode,
+    /// to hide the loading indicator of the headers and update the rows.
+    ///
+    /// The `generation` is the [`Self::sort_generation`] of the sort, the completion of
+    /// a stale sort is ignored when the header is clicked again before it's completed.
+    pub fn sort_completed(
+        &mut self,
+        generation: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.sorting_cols.is_empty() || generation != self.sort_generation {
+            return;
+        }
+
+        self.sorting_cols.clear();
+        self.rows_reordered(window, cx);
+        self.notify(cx);
+    }
+
+    /// Returns true if waiting for the [`Self::sort_completed`] in the [`Self::async_sort`] mode.
+    pub fn is_sorting(&self) -> bool {
+        !self.sorting_cols.is_empty()
+    }
+
+    /// Returns true if the column is waiting for the [`Self::sort_completed`].
+    pub fn is_col_sorting(&self, col_ix: usize) -> bool {
+        self.col_groups
+            .get(col_ix)
+            .is_some_and(|col_group| self.sorting_cols.contains(&col_group.column.key))
+    }
+
+    /// Update the states of the rows after the rows are reordered by sorting.
+    fn rows_reordered(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.prepare_aggregates(cx);
+        self.sync_selected_row(cx);
+        self.sync_expanded_rows(true, cx);
+        self.restore_scroll_anchor(cx);
+        self.reset_page(window, cx);
+    }
+
     fn move_column(
         &mut self,
         col_ix: usize,
@@ -570,11 +1431,38 @@ where
         self.delegate.move_column(col_ix, to_ix, window, cx);
         let col_group = self.col_groups.remove(col_ix);
         self.col_groups.insert(to_ix, col_group);
+        if col_ix < self.aggregates.len() && to_ix < self.aggregates.len() {
+            let aggregate = self.aggregates.remove(col_ix);
+            self.aggregates.insert(to_ix, aggregate);
+        }
 
         cx.emit(TableEvent::MoveColumn(col_ix, to_ix));
         cx.notify();
     }
 
+    /// Move the `len` columns from the `col_ix` to the `to_ix` one by one, for dragging a
+    /// column group, do nothing if the `to_ix` is in the columns.
+    fn move_columns(
+        &mut self,
+        col_ix: usize,
+        len: usize,
+        to_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if (col_ix..col_ix + len).contains(&to_ix) {
+            return;
+        }
+
+        for i in 0..len {
+            if to_ix < col_ix {
+                self.move_column(col_ix + i, to_ix + i, window, cx);
+            } else {
+                self.move_column(col_ix, to_ix, window, cx);
+            }
+        }
+    }
+
     /// Dispatch delegate's `load_more` method when the visible range is near the end.
     fn load_more_if_need(
         &mut self,
@@ -583,13 +1471,25 @@ where
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if self.page_size().is_some() {
+            return;
+        }
+
         let threshold = self.delegate.load_more_threshold();
         // Securely handle subtract logic to prevent attempt to subtract with overflow
         if visible_end >= rows_count.saturating_sub(threshold) {
-            if !self.delegate.is_eof(cx) {
+            if !self.delegate.is_eof(cx)
+                || self.delegate.has_no_more_rows(cx)
+                || self.delegate.loading_more(cx)
+            {
                 return;
             }
 
+            if self.load_more_rows_count != Some(rows_count) {
+                self.load_more_rows_count = Some(rows_count);
+                cx.emit(TableEvent::LoadMore);
+            }
+
             self._load_more_task = cx.spawn_in(window, async move |view, window| {
                 _ = view.update_in(window, |view, window, cx| {
                     view.delegate.load_more(window, cx);
@@ -617,7 +1517,8 @@ where
             }
             self.delegate_mut()
                 .visible_rows_changed(visible_range.clone(), window, cx);
-            self.visible_range.rows = visible_range;
+            self.visible_range.rows = visible_range.clone();
+            cx.emit(TableEvent::VisibleRangeChanged(visible_range));
         } else {
             if self.visible_range.cols == visible_range {
                 return;
@@ -628,6 +1529,155 @@ where
         }
     }
 
+    /// Returns the range of the visible scrollable columns (between the left and right fixed columns),
+    /// and the width of the columns before and after the range.
+    ///
+    /// The head and the filler rows only render the visible columns, so the wide table
+    /// with hundreds of columns is not materialized off-screen.
+    fn visible_scrollable_cols(
+        &self,
+        left_columns_count: usize,
+        window: &Window,
+    ) -> (Range<usize>, Pixels, Pixels) {
+        let right_start = self.fixed_right_cols().start;
+        let widths = self
+            .col_groups
+            .iter()
+            .take(right_start)
+            .skip(left_columns_count)
+            .map(|col| col.visible_width())
+            .collect::<Vec<_>>();
+        let mut viewport_width = self.horizontal_scroll_handle.bounds().size.width;
+        if viewport_width <= px(0.) {
+            viewport_width = window.viewport_size().width;
+        }
+
+        let range = visible_col_range(
+            &widths,
+            self.horizontal_scroll_handle.offset().x,
+            viewport_width,
+        );
+        let before = widths[..range.start].iter().fold(px(0.), |sum, w| sum + *w);
+        let after = widths[range.end..].iter().fold(px(0.), |sum, w| sum + *w);
+        (range, before, after)
+    }
+
+    /// Returns the indices of the columns hidden because the table is too narrow.
+    pub fn hidden_cols(&self) -> Vec<usize> {
+        self.col_groups
+            .iter()
+            .enumerate()
+            .filter(|(_, col)| col.hidden && col.visible)
+            .map(|(ix, _)| ix)
+            .collect()
+    }
+
+    /// Hide the columns with the lowest [`Column::priority`] until the columns fit in the table,
+    /// and the columns hidden by the user.
+    ///
+    /// Skipped if the widths, the visibility and the priorities of the columns and the width of
+    /// the table are not changed since the last update.
+    pub(super) fn update_hidden_cols(&mut self) {
+        let key = self.hidden_cols_key();
+        if self.hidden_cols_key == Some(key) {
+            return;
+        }
+
+        let widths = self
+            .col_groups
+            .iter()
+            .map(|col| if col.visible { col.width } else { px(0.) })
+            .collect::<Vec<_>>();
+        let priorities = self
+            .col_groups
+            .iter()
+            .map(|col| col.column.priority.filter(|_| col.visible))
+            .collect::<Vec<_>>();
+        // Leave the space of the last empty column and the vertical scrollbar.
+        let available_width = self.bounds.size.width - px(16.);
+        let hidden = hidden_cols(&widths, &priorities, available_width, MORE_COLS_WIDTH);
+        for (col_group, hidden) in self.col_groups.iter_mut().zip(hidden) {
+            col_group.hidden = hidden || !col_group.visible;
+        }
+        self.hidden_cols_key = Some(self.hidden_cols_key());
+    }
+
+    /// Returns the hash of the inputs and the result of the [`Self::update_hidden_cols`],
+    /// the hidden states are included to update again if they are changed by others.
+    fn hidden_cols_key(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.bounds.size.width.hash(&mut hasher);
+        for col_group in &self.col_groups {
+            col_group.width.hash(&mut hasher);
+            col_group.visible.hash(&mut hasher);
+            col_group.hidden.hash(&mut hasher);
+            col_group.column.priority.hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// Render the "+N" button of the row to show the hidden columns in a popover.
+    fn render_more_cols(
+        &self,
+        row_ix: usize,
+        hidden_cols: Vec<usize>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let view = cx.entity();
+        let hidden_cols = Rc::new(hidden_cols);
+
+        h_flex()
+            .w(MORE_COLS_WIDTH)
+            .h_full()
+            .flex_shrink_0()
+            .justify_center()
+            .child(
+                Popover::new(("table-more-cols", row_ix))
+                    .anchor(Corner::TopRight)
+                    .trigger(
+                        Button::new(("more-cols", row_ix))
+                            .ghost()
+                            .xsmall()
+                            .label(format!("+{}", hidden_cols.len())),
+                    )
+                    .content(move |window, cx| {
+                        let view = view.clone();
+                        let hidden_cols = hidden_cols.clone();
+                        cx.new(|cx| {
+                            PopoverContent::new(window, cx, move |window, cx| {
+                                view.update(cx, |table, cx| {
+                                    let mut items = Vec::with_capacity(hidden_cols.len());
+                                    for col_ix in hidden_cols.iter().copied() {
+                                        let name = table.col_groups[col_ix].column.name.clone();
+                                        items.push(
+                                            h_flex()
+                                                .gap_4()
+                                                .justify_between()
+                                                .child(
+                                                    div()
+                                                        .text_color(cx.theme().muted_foreground)
+                                                        .child(name),
+                                                )
+                                                .child(
+                                                    table
+                                                        .delegate
+                                                        .render_td(row_ix, col_ix, window, cx),
+                                                ),
+                                        );
+                                    }
+                                    v_flex()
+                                        .gap_1()
+                                        .min_w(px(200.))
+                                        .children(items)
+                                        .into_any_element()
+                                })
+                            })
+                        })
+                    }),
+            )
+    }
+
     fn render_cell(&self, col_ix: usize, _window: &mut Window, _cx: &mut Context<Self>) -> Div {
         let Some(col_group) = self.col_groups.get(col_ix) else {
             return div();
@@ -642,6 +1692,7 @@ where
             .flex_shrink_0()
             .overflow_hidden()
             .whitespace_nowrap()
+            .text_ellipsis()
             .table_cell_size(self.options.size)
             .map(|this| match col_padding {
                 Some(padding) => this
@@ -653,6 +1704,225 @@ where
             })
     }
 
+    /// Render the cell of the row, wrapped by the [`Column::wrap`], and shows the full text
+    /// of the [`TableDelegate::cell_text`] in a tooltip when the text is truncated.
+    fn render_td_cell(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let Some(col_group) = self.col_groups.get(col_ix) else {
+            return div().into_any_element();
+        };
+
+        if self.editing_cell == Some((row_ix, col_ix)) {
+            return self
+                .render_col_wrap(col_ix, window, cx)
+                .child(
+                    self.render_cell(col_ix, window, cx)
+                        .key_context(EDITOR_CONTEXT)
+                        .on_action(cx.listener(|this, _: &Confirm, window, cx| {
+                            this.commit_editing(window, cx);
+                        }))
+                        .on_action(cx.listener(|this, _: &Cancel, window, cx| {
+                            this.cancel_editing(window, cx);
+                        }))
+                        .on_action(cx.listener(|this, _: &EditNextCell, window, cx| {
+                            this.edit_sibling_cell(true, window, cx);
+                        }))
+                        .on_action(cx.listener(|this, _: &EditPrevCell, window, cx| {
+                            this.edit_sibling_cell(false, window, cx);
+                        }))
+                        .child(self.delegate.render_editing_td(row_ix, col_ix, window, cx)),
+                )
+                .into_any_element();
+        }
+
+        let editable = self.delegate.can_edit_cell(row_ix, col_ix, cx);
+        let wrap = col_group.column.wrap;
+        let tooltip = self.td_tooltip(row_ix, col_ix, window, cx);
+
+        // The drag handle, the tree indents and the chevron toggle of the expandable row are
+        // in the first visible cell.
+        let (drag_handle, tree_toggle, expand_toggle) =
+            if self.col_groups.iter().position(|col| !col.hidden) == Some(col_ix) {
+                (
+                    self.render_drag_handle(row_ix, cx),
+                    self.render_tree_toggle(row_ix, cx),
+                    self.render_expand_toggle(row_ix, cx),
+                )
+            } else {
+                (None, None, None)
+            };
+
+        let el = self.render_col_wrap(col_ix, window, cx).child(
+            self.render_cell(col_ix, window, cx)
+                .when_some(wrap, |this, lines| {
+                    this.whitespace_normal().line_clamp(lines)
+                })
+                .when(
+                    drag_handle.is_some() || tree_toggle.is_some() || expand_toggle.is_some(),
+                    |this| this.flex().items_center().gap_1(),
+                )
+                .children(drag_handle)
+                .children(tree_toggle)
+                .children(expand_toggle)
+                .child(self.measure_render_td(row_ix, col_ix, window, cx)),
+        );
+
+        let el = el.when(editable, |this| {
+            this.on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, ev: &MouseDownEvent, window, cx| {
+                    if ev.click_count == 2 {
+                        this.edit_cell(row_ix, col_ix, window, cx);
+                    }
+                }),
+            )
+        });
+
+        let el = el.when(self.cell_selectable, |this| {
+            this.on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, ev: &MouseDownEvent, window, cx| {
+                    this.on_cell_click(ev, row_ix, col_ix, window, cx);
+                }),
+            )
+        });
+
+        // The focus ring of the active cell.
+        let el = el.when(self.active_cell() == Some((row_ix, col_ix)), |this| {
+            let ring = if self.focus_handle.is_focused(window) {
+                cx.theme().ring
+            } else {
+                cx.theme().table_active_border
+            };
+            this.relative()
+                .child(div().absolute().inset_0().border_2().border_color(ring))
+        });
+
+        match tooltip {
+            Some(text) => el
+                .id(("table-cell", col_ix))
+                .tooltip(move |window, cx| Tooltip::new(text.clone()).build(window, cx))
+                .into_any_element(),
+            None => el.into_any_element(),
+        }
+    }
+
+    /// Returns the width of the text in the cells of the column, without the paddings.
+    fn col_content_width(&self, col_ix: usize) -> Pixels {
+        let Some(col_group) = self.col_groups.get(col_ix) else {
+            return px(0.);
+        };
+
+        let paddings = col_group
+            .column
+            .paddings
+            .unwrap_or(self.options.size.table_cell_padding());
+        col_group.width - paddings.left - paddings.right
+    }
+
+    /// Returns the full text of the [`TableDelegate::cell_text`] to show in a tooltip,
+    /// if it's wider than the lines of the cell.
+    fn td_tooltip(
+        &self,
+        row_ix: usize,
+        col_ix: usize,
+        window: &mut Window,
+        cx: &App,
+    ) -> Option<SharedString> {
+        let lines = self.col_groups.get(col_ix)?.column.wrap.unwrap_or(1);
+        let content_width = self.col_content_width(col_ix) * lines as f32;
+        self.delegate
+            .cell_text(row_ix, col_ix, cx)
+            .filter(|text| self.text_width((Some(row_ix), col_ix), text, window) > content_width)
+    }
+
+    /// Returns the column name to show in a tooltip, if it's wider than the header cell
+    /// without the sort icon and the filter button.
+    fn th_tooltip(&self, col_ix: usize, window: &mut Window) -> Option<SharedString> {
+        let col_group = self.col_groups.get(col_ix)?;
+        let mut content_width = self.col_content_width(col_ix);
+        if self.sortable && col_group.column.sort.is_some() {
+            content_width = content_width - SORT_ICON_WIDTH;
+        }
+        if col_group.column.filter.is_some() {
+            content_width = content_width - FILTER_BUTTON_WIDTH;
+        }
+
+        Some(col_group.column.name.clone())
+            .filter(|name| self.text_width((None, col_ix), name, window) > content_width)
+    }
+
+    /// Returns the width of the single line `text` in the font size of the cells.
+    ///
+    /// The width is cached by the `(row_ix, col_ix)` of the cell, and shaped again only
+    /// when the text or the font is changed.
+    fn text_width(
+        &self,
+        key: (Option<usize>, usize),
+        text: &SharedString,
+        window: &mut Window,
+    ) -> Pixels {
+        if text.contains('\n') {
+            return Pixels::MAX;
+        }
+
+        let text_style = window.text_style();
+        let font_size = match self.options.size {
+            Size::XSmall | Size::Small => rems(0.875).to_pixels(window.rem_size()),
+            _ => text_style.font_size.to_pixels(window.rem_size()),
+        };
+
+        let mut text_widths = self.text_widths.borrow_mut();
+        if text_widths.font.0 != text_style.font_family || text_widths.font.1 != font_size {
+            text_widths.font = (text_style.font_family.clone(), font_size);
+            text_widths.widths.clear();
+        }
+        if let Some((cached_text, width)) = text_widths.widths.get(&key) {
+            if cached_text == text {
+                return *width;
+            }
+        }
+
+        let run = text_style.to_run(text.len());
+        let width = window
+            .text_system()
+            .shape_line(text.clone(), font_size, &[run], None)
+            .width;
+        text_widths.widths.insert(key, (text.clone(), width));
+        width
+    }
+
+    /// Remove the cached text widths of the rows out of the visible rows.
+    fn prune_text_widths(&mut self) {
+        let rows = &self.visible_range.rows;
+        let text_widths = self.text_widths.get_mut();
+        if text_widths.rows == *rows {
+            return;
+        }
+
+        text_widths
+            .widths
+            .retain(|(row_ix, _), _| row_ix.is_none_or(|row_ix| rows.contains(&row_ix)));
+        text_widths.rows = rows.clone();
+    }
+
+    /// Returns the height of the rows to fit the max lines of the [`Column::wrap`].
+    fn content_row_height(&self, window: &Window) -> Pixels {
+        let lines = self
+            .col_groups
+            .iter()
+            .filter(|col| !col.hidden)
+            .filter_map(|col| col.column.wrap)
+            .max()
+            .unwrap_or(1);
+        self.options.row_height() + window.line_height() * lines.saturating_sub(1) as f32
+    }
+
     /// Show Column selection style, when the column is selected and the selection state is Column.
     fn render_col_wrap(&self, col_ix: usize, _: &mut Window, cx: &mut Context<Self>) -> Div {
         let el = h_flex().h_full();
@@ -691,7 +1961,8 @@ where
             return div().into_any_element();
         }
 
-        let group_id = SharedString::from(format!("resizable-handle:{}", ix));
+        // The `group_hover` matches the nearest group, so the same name is fine for all handles.
+        let group_id = SharedString::new_static("resizable-handle");
 
         h_flex()
             .id(("resizable-handle", ix))
@@ -774,7 +2045,7 @@ where
         col_group: &ColGroup,
         _: &mut Window,
         cx: &mut Context<Self>,
-    ) -> Option<impl IntoElement> {
+    ) -> Option<AnyElement> {
         if !self.sortable {
             return None;
         }
@@ -783,11 +2054,33 @@ where
             return None;
         };
 
+        if self.sorting_cols.contains(&col_group.column.key) {
+            return Some(
+                div()
+                    .id(("icon-sort", col_ix))
+                    .p(px(2.))
+                    .child(
+                        Indicator::new()
+                            .xsmall()
+                            .color(cx.theme().secondary_foreground),
+                    )
+                    .into_any_element(),
+            );
+        }
+
         let (icon, is_on) = match sort {
             ColumnSort::Ascending => (IconName::SortAscending, true),
             ColumnSort::Descending => (IconName::SortDescending, true),
             ColumnSort::Default => (IconName::ChevronsUpDown, false),
         };
+        // Show the priority numbers only when sorting by multiple columns.
+        let priority = col_group.sort_priority.filter(|_| {
+            self.col_groups
+                .iter()
+                .filter(|g| g.sort_priority.is_some())
+                .count()
+                > 1
+        });
 
         Some(
             div()
@@ -800,14 +2093,26 @@ where
                 })
                 .hover(|this| this.bg(cx.theme().secondary).opacity(7.))
                 .active(|this| this.bg(cx.theme().secondary_active).opacity(1.))
-                .on_click(
-                    cx.listener(move |table, _, window, cx| table.perform_sort(col_ix, window, cx)),
-                )
+                .on_click(cx.listener(move |table, ev: &ClickEvent, window, cx| {
+                    table.perform_sort(col_ix, ev.modifiers().shift, window, cx)
+                }))
+                .flex()
+                .items_center()
                 .child(
                     Icon::new(icon)
                         .size_3()
                         .text_color(cx.theme().secondary_foreground),
-                ),
+                )
+                .when_some(priority, |this, priority| {
+                    this.child(
+                        div()
+                            .text_size(px(9.))
+                            .line_height(px(9.))
+                            .text_color(cx.theme().secondary_foreground)
+                            .child((priority + 1).to_string()),
+                    )
+                })
+                .into_any_element(),
         )
     }
 
@@ -815,6 +2120,39 @@ where
     /// The children must be one by one items.
     /// Because the horizontal scroll handle will use the child_item_bounds to
     /// calculate the item position for itself's `scroll_to_item` method.
+    fn render_filter_button(
+        &self,
+        col_ix: usize,
+        col_group: &ColGroup,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<impl IntoElement> {
+        col_group.column.filter.as_ref()?;
+
+        let view = cx.entity();
+        let is_on = col_group.filter_value.is_some();
+
+        Some(
+            Popover::new(("table-filter", col_ix))
+                .anchor(Corner::TopRight)
+                .trigger(
+                    Button::new(("icon-filter", col_ix))
+                        .ghost()
+                        .xsmall()
+                        .icon(
+                            Icon::new(IconName::ListFilter)
+                                .size_3()
+                                .when(!is_on, |this| this.text_color(cx.theme().muted_foreground)),
+                        )
+                        .selected(is_on),
+                )
+                .content(move |window, cx| {
+                    let view = view.clone();
+                    cx.new(|cx| FilterPanel::new(view, col_ix, window, cx))
+                }),
+        )
+    }
+
     fn render_th(
         &self,
         col_ix: usize,
@@ -827,12 +2165,16 @@ where
         let movable = self.col_movable && col_group.column.movable;
         let paddings = col_group.column.paddings;
         let name = col_group.column.name.clone();
+        let tooltip = self.th_tooltip(col_ix, window);
 
         h_flex()
             .h_full()
             .child(
                 self.render_cell(col_ix, window, cx)
                     .id(("col-header", col_ix))
+                    .when_some(tooltip, |this, name| {
+                        this.tooltip(move |window, cx| Tooltip::new(name.clone()).build(window, cx))
+                    })
                     .on_mouse_down(
                         MouseButton::Left,
                         cx.listener(move |this, _, window, cx| {
@@ -851,13 +2193,15 @@ where
                                     self.options.size.table_cell_padding().right - paddings.right;
                                 this.pr(offset_pr.max(px(0.)))
                             })
-                            .children(self.render_sort_icon(col_ix, &col_group, window, cx)),
+                            .children(self.render_sort_icon(col_ix, &col_group, window, cx))
+                            .children(self.render_filter_button(col_ix, &col_group, window, cx)),
                     )
                     .when(movable, |this| {
                         this.on_drag(
                             DragColumn {
                                 entity_id,
                                 col_ix,
+                                len: 1,
                                 name,
                                 width: col_group.width,
                             },
@@ -879,7 +2223,7 @@ where
                                     return;
                                 }
 
-                                table.move_column(drag.col_ix, col_ix, window, cx);
+                                table.move_columns(drag.col_ix, drag.len, col_ix, window, cx);
                             },
                         ))
                     }),
@@ -900,6 +2244,109 @@ where
             })
     }
 
+    /// Render the group row above the `cols` of the table head, the adjacent columns in the
+    /// same [`TableDelegate::col_group`] are merged into one cell, it can be dragged to move
+    /// the columns together, and resized by the last column.
+    fn render_th_groups(
+        &self,
+        cols: &[usize],
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let entity_id = cx.entity_id();
+        let groups = self
+            .col_groups
+            .iter()
+            .map(|col_group| col_group.group.as_ref())
+            .collect::<Vec<_>>();
+
+        h_flex()
+            .id("table-head-groups")
+            .h(self.options.row_height())
+            .children(col_group_spans(cols, &groups).into_iter().map(|span| {
+                let (first, last) = (cols[span.start], cols[span.end - 1]);
+                let width = cols[span].iter().fold(px(0.), |width, col_ix| {
+                    width + self.col_groups[*col_ix].width
+                });
+                let group = self.col_groups[first].group.clone();
+                let movable = group.is_some()
+                    && self.col_movable
+                    && self.col_groups[first..=last]
+                        .iter()
+                        .all(|col_group| col_group.column.movable);
+
+                h_flex()
+                    .h_full()
+                    .child(
+                        h_flex()
+                            .id(("col-group", first))
+                            .w(width)
+                            .h_full()
+                            .flex_shrink_0()
+                            .justify_center()
+                            .overflow_hidden()
+                            .whitespace_nowrap()
+                            .table_cell_size(self.options.size)
+                            .when_some(group.clone(), |this, group| {
+                                this.border_b_1()
+                                    .border_color(cx.theme().border)
+                                    .child(group)
+                            })
+                            .when(movable, |this| {
+                                this.on_drag(
+                                    DragColumn {
+                                        entity_id,
+                                        col_ix: first,
+                                        len: last + 1 - first,
+                                        name: group.clone().unwrap_or_default(),
+                                        width,
+                                    },
+                                    |drag, _, _, cx| {
+                                        cx.stop_propagation();
+                                        cx.new(|_| drag.clone())
+                                    },
+                                )
+                                .drag_over::<DragColumn>(|this, _, _, cx| {
+                                    this.rounded_l_none()
+                                        .border_l_2()
+                                        .border_r_0()
+                                        .border_color(cx.theme().drag_border)
+                                })
+                                .on_drop(cx.listener(
+                                    move |table, drag: &DragColumn, window, cx| {
+                                        if drag.entity_id != cx.entity_id() {
+                                            return;
+                                        }
+
+                                        table.move_columns(
+                                            drag.col_ix,
+                                            drag.len,
+                                            first,
+                                            window,
+                                            cx,
+                                        );
+                                    },
+                                ))
+                            }),
+                    )
+                    .when(group.is_some(), |this| {
+                        this.child(self.render_resize_handle(last, window, cx))
+                    })
+            }))
+    }
+
+    /// The border at the left of the right fixed columns.
+    fn render_fixed_right_border(cx: &App) -> Div {
+        div()
+            .absolute()
+            .top_0()
+            .left_0()
+            .bottom_0()
+            .w_0()
+            .border_l_1()
+            .border_color(cx.theme().border)
+    }
+
     fn render_table_head(
         &mut self,
         left_columns_count: usize,
@@ -908,6 +2355,27 @@ where
     ) -> impl IntoElement {
         let view = cx.entity().clone();
         let horizontal_scroll_handle = self.horizontal_scroll_handle.clone();
+        let (visible_cols, cols_before, cols_after) =
+            self.visible_scrollable_cols(left_columns_count, window);
+        let right_cols = self.fixed_right_cols();
+        let hidden_cols_count = self.hidden_cols().len();
+        let has_col_group = self.has_col_group();
+        let row_height = self.options.row_height();
+        let left_cols = self
+            .col_groups
+            .iter()
+            .filter(|col| col.column.fixed == Some(ColumnFixed::Left))
+            .enumerate()
+            .filter(|(_, col)| !col.hidden)
+            .map(|(col_ix, _)| col_ix)
+            .collect::<Vec<_>>();
+        let visible_cols = visible_cols
+            .map(|col_ix| left_columns_count + col_ix)
+            .filter(|col_ix| !self.col_groups[*col_ix].hidden)
+            .collect::<Vec<_>>();
+        let right_cols = right_cols
+            .filter(|col_ix| !self.col_groups[*col_ix].hidden)
+            .collect::<Vec<_>>();
 
         // Reset fixed head columns bounds, if no fixed columns are present
         if left_columns_count == 0 {
@@ -916,25 +2384,35 @@ where
 
         h_flex()
             .w_full()
-            .h(self.options.size.table_row_height())
+            .h(self.head_height())
             .flex_shrink_0()
             .border_b_1()
             .border_color(cx.theme().border)
             .text_color(cx.theme().table_head_foreground)
+            .on_mouse_down(
+                MouseButton::Right,
+                cx.listener(|this, _, _, _| {
+                    this.right_clicked_row = None;
+                    this.right_clicked_head = true;
+                }),
+            )
             .when(left_columns_count > 0, |this| {
                 let view = view.clone();
                 // Render left fixed columns
                 this.child(
-                    h_flex()
+                    v_flex()
                         .relative()
                         .h_full()
                         .bg(cx.theme().table_head)
-                        .children(
-                            self.col_groups
-                                .iter()
-                                .filter(|col| col.column.fixed == Some(ColumnFixed::Left))
-                                .enumerate()
-                                .map(|(col_ix, _)| self.render_th(col_ix, window, cx)),
+                        .when(has_col_group, |this| {
+                            this.child(self.render_th_groups(&left_cols, window, cx))
+                        })
+                        .child(
+                            h_flex().h(row_height).children(
+                                left_cols
+                                    .iter()
+                                    .map(|col_ix| self.render_th(*col_ix, window, cx)),
+                            ),
                         )
                         .child(
                             // Fixed columns border
@@ -970,30 +2448,173 @@ where
                     .track_scroll(&horizontal_scroll_handle)
                     .bg(cx.theme().table_head)
                     .child(
-                        h_flex()
+                        v_flex()
                             .relative()
-                            .children(
-                                self.col_groups
-                                    .iter()
-                                    .skip(left_columns_count)
-                                    .enumerate()
-                                    .map(|(col_ix, _)| {
-                                        self.render_th(left_columns_count + col_ix, window, cx)
-                                    }),
-                            )
-                            .child(self.delegate.render_last_empty_col(window, cx)),
+                            .h_full()
+                            .when(has_col_group, |this| {
+                                this.child(
+                                    h_flex()
+                                        .child(div().w(cols_before).h_full().flex_shrink_0())
+                                        .child(self.render_th_groups(&visible_cols, window, cx)),
+                                )
+                            })
+                            .child(
+                                h_flex()
+                                    .h(row_height)
+                                    .child(div().w(cols_before).h_full().flex_shrink_0())
+                                    .children(
+                                        visible_cols
+                                            .iter()
+                                            .map(|col_ix| self.render_th(*col_ix, window, cx)),
+                                    )
+                                    .child(div().w(cols_after).h_full().flex_shrink_0())
+                                    .child(self.delegate.render_last_empty_col(window, cx)),
+                            ),
                     ),
             )
+            .when(!right_cols.is_empty(), |this| {
+                // Render right fixed columns
+                this.child(
+                    v_flex()
+                        .relative()
+                        .h_full()
+                        .flex_shrink_0()
+                        .bg(cx.theme().table_head)
+                        .when(has_col_group, |this| {
+                            this.child(self.render_th_groups(&right_cols, window, cx))
+                        })
+                        .child(
+                            h_flex().h(row_height).children(
+                                right_cols
+                                    .iter()
+                                    .map(|col_ix| self.render_th(*col_ix, window, cx)),
+                            ),
+                        )
+                        .child(Self::render_fixed_right_border(cx)),
+                )
+            })
+            .when(hidden_cols_count > 0, |this| {
+                this.child(
+                    h_flex()
+                        .w(MORE_COLS_WIDTH)
+                        .h_full()
+                        .flex_shrink_0()
+                        .justify_center()
+                        .bg(cx.theme().table_head)
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(format!("+{}", hidden_cols_count)),
+                )
+            })
     }
 
-    #[allow(clippy::too_many_arguments)]
-    fn render_table_row(
+    fn render_td_foot(
+        &self,
+        col_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let align = self.col_groups[col_ix].column.align;
+        let aggregated = self.aggregates.get(col_ix).is_some_and(Option::is_some);
+        self.render_cell(col_ix, window, cx)
+            .flex()
+            .items_center()
+            .font_medium()
+            .when(align == TextAlign::Right, |this| this.justify_end())
+            .map(|this| {
+                if aggregated {
+                    this.child(self.delegate.render_footer(
+                        col_ix,
+                        self.aggregate(col_ix),
+                        window,
+                        cx,
+                    ))
+                } else {
+                    this.child(self.delegate.render_tfoot_td(col_ix, window, cx))
+                }
+            })
+    }
+
+    /// Render the footer with the aggregated values of the columns and the
+    /// [`TableDelegate::render_tfoot_td`], aligned with the columns and scrolled with the body.
+    fn render_table_foot(
+        &mut self,
+        left_columns_count: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let (visible_cols, cols_before, cols_after) =
+            self.visible_scrollable_cols(left_columns_count, window);
+        let right_cols = self.fixed_right_cols();
+        let hidden_cols_count = self.hidden_cols().len();
+
+        h_flex()
+            .w_full()
+            .h(self.options.row_height())
+            .flex_shrink_0()
+            .border_t_1()
+            .border_color(cx.theme().border)
+            .bg(cx.theme().table_head)
+            .text_color(cx.theme().table_head_foreground)
+            .when(left_columns_count > 0, |this| {
+                this.child(
+                    h_flex()
+                        .h_full()
+                        .border_r_1()
+                        .border_color(cx.theme().border)
+                        .children(
+                            (0..left_columns_count)
+                                .filter(|col_ix| !self.col_groups[*col_ix].hidden)
+                                .map(|col_ix| self.render_td_foot(col_ix, window, cx)),
+                        ),
+                )
+            })
+            .child(
+                h_flex()
+                    .id("table-foot")
+                    .size_full()
+                    .overflow_scroll()
+                    .track_scroll(&self.horizontal_scroll_handle)
+                    .child(
+                        h_flex()
+                            .h_full()
+                            .child(div().w(cols_before).h_full().flex_shrink_0())
+                            .children(
+                                visible_cols
+                                    .map(|col_ix| left_columns_count + col_ix)
+                                    .filter(|col_ix| !self.col_groups[*col_ix].hidden)
+                                    .map(|col_ix| self.render_td_foot(col_ix, window, cx)),
+                            )
+                            .child(div().w(cols_after).h_full().flex_shrink_0())
+                            .child(self.delegate.render_last_empty_col(window, cx)),
+                    ),
+            )
+            .when(!right_cols.is_empty(), |this| {
+                this.child(
+                    h_flex()
+                        .h_full()
+                        .flex_shrink_0()
+                        .border_l_1()
+                        .border_color(cx.theme().border)
+                        .children(
+                            right_cols
+                                .filter(|col_ix| !self.col_groups[*col_ix].hidden)
+                                .map(|col_ix| self.render_td_foot(col_ix, window, cx)),
+                        ),
+                )
+            })
+            .when(hidden_cols_count > 0, |this| {
+                this.child(div().w(MORE_COLS_WIDTH).h_full().flex_shrink_0())
+            })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn render_table_row(
         &mut self,
         row_ix: usize,
         rows_count: usize,
         left_columns_count: usize,
         col_sizes: Rc<Vec<gpui::Size<Pixels>>>,
-        columns_count: usize,
         extra_rows_count: usize,
         window: &mut Window,
         cx: &mut Context<Self>,
@@ -1001,9 +2622,13 @@ where
         let horizontal_scroll_handle = self.horizontal_scroll_handle.clone();
         let is_stripe_row = self.options.stripe && row_ix % 2 != 0;
         let is_selected = self.selected_row == Some(row_ix);
+        let is_pending = self.is_row_pending(row_ix, cx);
         let view = cx.entity().clone();
+        let hidden_cols = self.hidden_cols();
+        let right_cols = self.fixed_right_cols();
 
         if row_ix < rows_count {
+            let is_draggable = self.is_row_draggable(row_ix, cx);
             let is_last_row = row_ix == rows_count - 1;
             let table_is_filled = extra_rows_count == 0;
             let need_render_border = if is_last_row {
@@ -1023,11 +2648,12 @@ where
 
             tr.h_flex()
                 .w_full()
-                .h(self.options.size.table_row_height())
+                .h(self.content_row_height(window))
                 .when(need_render_border, |this| {
                     this.border_b_1().border_color(cx.theme().table_row_border)
                 })
                 .when(is_stripe_row, |this| this.bg(cx.theme().table_even))
+                .when(is_pending, |this| this.opacity(0.6))
                 .refine_style(&style)
                 .hover(|this| {
                     if is_selected || self.right_clicked_row == Some(row_ix) {
@@ -1045,13 +2671,11 @@ where
                             .children({
                                 let mut items = Vec::with_capacity(left_columns_count);
 
-                                (0..left_columns_count).for_each(|col_ix| {
-                                    items.push(self.render_col_wrap(col_ix, window, cx).child(
-                                        self.render_cell(col_ix, window, cx).child(
-                                            self.measure_render_td(row_ix, col_ix, window, cx),
-                                        ),
-                                    ));
-                                });
+                                (0..left_columns_count)
+                                    .filter(|col_ix| !hidden_cols.contains(col_ix))
+                                    .for_each(|col_ix| {
+                                        items.push(self.render_td_cell(row_ix, col_ix, window, cx));
+                                    });
 
                                 items
                             })
@@ -1096,16 +2720,14 @@ where
 
                                         visible_range.for_each(|col_ix| {
                                             let col_ix = col_ix + left_columns_count;
-                                            let el =
-                                                table.render_col_wrap(col_ix, window, cx).child(
-                                                    table.render_cell(col_ix, window, cx).child(
-                                                        table.measure_render_td(
-                                                            row_ix, col_ix, window, cx,
-                                                        ),
-                                                    ),
-                                                );
-
-                                            items.push(el);
+                                            if table.col_groups[col_ix].hidden {
+                                                items.push(div().into_any_element());
+                                                return;
+                                            }
+
+                                            items.push(
+                                                table.render_td_cell(row_ix, col_ix, window, cx),
+                                            );
                                         });
 
                                         items
@@ -1116,6 +2738,25 @@ where
                         )
                         .child(self.delegate.render_last_empty_col(window, cx)),
                 )
+                .when(!right_cols.is_empty(), |this| {
+                    // Right fixed columns
+                    this.child(
+                        h_flex()
+                            .relative()
+                            .h_full()
+                            .flex_shrink_0()
+                            .children(
+                                right_cols
+                                    .filter(|col_ix| !hidden_cols.contains(col_ix))
+                                    .map(|col_ix| self.render_td_cell(row_ix, col_ix, window, cx))
+                                    .collect::<Vec<_>>(),
+                            )
+                            .child(Self::render_fixed_right_border(cx)),
+                    )
+                })
+                .when(!hidden_cols.is_empty(), |this| {
+                    this.child(self.render_more_cols(row_ix, hidden_cols, window, cx))
+                })
                 // Row selected style
                 .when_some(self.selected_row, |this, _| {
                     this.when(
@@ -1135,6 +2776,11 @@ where
                         },
                     )
                 })
+                // The row of the active cell
+                .when(
+                    is_selected && self.selection_state == SelectionState::Cell,
+                    |this| this.bg(cx.theme().table_active),
+                )
                 // Row right click row style
                 .when(self.right_clicked_row == Some(row_ix), |this| {
                     this.border_color(gpui::transparent_white()).child(
@@ -1160,7 +2806,24 @@ where
                         this.on_row_click(ev, row_ix, window, cx);
                     }),
                 )
+                .when(is_draggable && !self.row_drag_handle, |this| {
+                    this.on_drag(self.drag_row(row_ix, cx), |drag, _, _, cx| {
+                        cx.stop_propagation();
+                        cx.new(|_| drag.clone())
+                    })
+                })
+                .when(self.row_movable, |this| {
+                    this.on_drag_move(cx.listener(
+                        move |this, e: &DragMoveEvent<DragRow>, _, cx| {
+                            this.on_drag_row_over(e, row_ix, cx);
+                        },
+                    ))
+                })
+                .children(self.render_drop_indicator(row_ix, rows_count, cx))
         } else {
+            let (visible_cols, cols_before, _) =
+                self.visible_scrollable_cols(left_columns_count, window);
+
             // Render fake rows to fill the rest table space
             self.delegate
                 .render_tr(row_ix, window, cx)
@@ -1170,20 +2833,152 @@ where
                 .border_t_1()
                 .border_color(cx.theme().table_row_border)
                 .when(is_stripe_row, |this| this.bg(cx.theme().table_even))
-                .children((0..columns_count).map(|col_ix| {
-                    h_flex()
-                        .left(horizontal_scroll_handle.offset().x)
-                        .child(self.render_cell(col_ix, window, cx))
-                }))
+                .children(
+                    (0..left_columns_count)
+                        .chain(
+                            (left_columns_count + visible_cols.start)
+                                ..(left_columns_count + visible_cols.end),
+                        )
+                        .filter(|col_ix| !self.col_groups[*col_ix].hidden)
+                        .map(|col_ix| {
+                            h_flex()
+                                .left(horizontal_scroll_handle.offset().x)
+                                .when(col_ix == left_columns_count + visible_cols.start, |this| {
+                                    this.pl(cols_before)
+                                })
+                                .child(self.render_cell(col_ix, window, cx))
+                        }),
+                )
                 .child(self.delegate.render_last_empty_col(window, cx))
         }
     }
 
+    /// Render the list of the rows, the rows are in the same height unless some rows are expanded
+    /// to show the detail panels, see [`TableDelegate::render_row_detail`].
+    fn render_rows_list(
+        &mut self,
+        rows_count: usize,
+        render_rows_count: usize,
+        left_columns_count: usize,
+        extra_rows_count: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        if self.expanded_rows.is_empty() {
+            return uniform_list(
+                "table-uniform-list",
+                render_rows_count,
+                cx.processor(move |table, visible_range: Range<usize>, window, cx| {
+                    table.render_rows(
+                        visible_range,
+                        rows_count,
+                        left_columns_count,
+                        extra_rows_count,
+                        window,
+                        cx,
+                    )
+                }),
+            )
+            .flex_grow()
+            .size_full()
+            .with_sizing_behavior(ListSizingBehavior::Auto)
+            .track_scroll(self.vertical_scroll_handle.clone())
+            .into_any_element();
+        }
+
+        self.measure_row_details(rows_count, window, cx);
+        let item_sizes = self.row_item_sizes(render_rows_count, self.content_row_height(window));
+        crate::virtual_list::v_virtual_list(
+            cx.entity(),
+            "table-rows",
+            item_sizes,
+            move |table, visible_range: Range<usize>, window, cx| {
+                table.render_rows(
+                    visible_range,
+                    rows_count,
+                    left_columns_count,
+                    extra_rows_count,
+                    window,
+                    cx,
+                )
+            },
+        )
+        .flex_grow()
+        .size_full()
+        .with_sizing_behavior(ListSizingBehavior::Auto)
+        .track_scroll(&self.detail_scroll_handle)
+        .into_any_element()
+    }
+
+    fn render_rows(
+        &mut self,
+        visible_range: Range<usize>,
+        rows_count: usize,
+        left_columns_count: usize,
+        extra_rows_count: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        // We must calculate the col sizes here, because the col sizes
+        // need render_th first, then that method will set the bounds of each col.
+        let right_start = self.fixed_right_cols().start;
+        let col_sizes: Rc<Vec<gpui::Size<Pixels>>> = Rc::new(
+            self.col_groups
+                .iter()
+                .take(right_start)
+                .skip(left_columns_count)
+                .map(|col| size(col.visible_width(), self.options.row_height()))
+                .collect(),
+        );
+
+        self.load_more_if_need(rows_count, visible_range.end, window, cx);
+        self.update_visible_range_if_need(visible_range.clone(), Axis::Vertical, window, cx);
+
+        if visible_range.end > rows_count {
+            self.scroll_to_row(
+                std::cmp::min(visible_range.start, rows_count.saturating_sub(1)),
+                cx,
+            );
+        }
+
+        let mut items = Vec::with_capacity(visible_range.end.saturating_sub(visible_range.start));
+
+        // Render fake rows to fill the table
+        visible_range.for_each(|row_ix| {
+            // Render real rows for available data
+            let row = self
+                .render_table_row(
+                    row_ix,
+                    rows_count,
+                    left_columns_count,
+                    col_sizes.clone(),
+                    extra_rows_count,
+                    window,
+                    cx,
+                )
+                .into_any_element();
+
+            if self.is_row_expanded(row_ix) {
+                items.push(
+                    v_flex()
+                        .w_full()
+                        .child(row)
+                        .child(self.render_row_detail_panel(row_ix, window, cx))
+                        .into_any_element(),
+                );
+            } else {
+                items.push(row);
+            }
+        });
+
+        items
+    }
+
     /// Calculate the extra rows needed to fill the table empty space when `stripe` is true.
-    fn calculate_extra_rows_needed(&self, rows_count: usize) -> usize {
+    fn calculate_extra_rows_needed(&self, rows_count: usize, window: &Window) -> usize {
         let mut extra_rows_needed = 0;
 
-        let row_height = self.options.size.table_row_height();
+        let row_height = self.content_row_height(window);
         let total_height = self
             .vertical_scroll_handle
             .0
@@ -1193,7 +2988,8 @@ where
             .size
             .height;
 
-        let actual_height = row_height * rows_count as f32;
+        let actual_height =
+            row_height * rows_count as f32 + self.row_details_height_before(rows_count);
         let remaining_height = total_height - actual_height;
 
         if remaining_height > px(0.) {
@@ -1256,7 +3052,7 @@ where
             div()
                 .occlude()
                 .absolute()
-                .top(self.options.size.table_row_height())
+                .top(self.head_height())
                 .right_0()
                 .bottom_0()
                 .w(Scrollbar::width())
@@ -1279,7 +3075,7 @@ where
             .occlude()
             .absolute()
             .left(self.fixed_head_cols_bounds.size.width)
-            .right_0()
+            .right(self.fixed_right_cols_width())
             .bottom_0()
             .h(Scrollbar::width())
             .child(Scrollbar::horizontal(
@@ -1305,16 +3101,41 @@ where
 {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         self.measure(window, cx);
+        self.update_hidden_cols();
+        self.load_page_if_need(window, cx);
+        // The rows may be changed without the `rows_changed` or `refresh`.
+        let rows_count = self.delegate.rows_count(cx);
+        if self.rendered_rows_count != rows_count {
+            self.rendered_rows_count = rows_count;
+            self.sync_selected_row(cx);
+        }
+        // Anchor to the rows in this frame, to restore after the rows are refreshed.
+        if self.preserve_scroll_position
+            && self.scroll_anchor_key.as_ref() != Some(&self.scroll_anchor_key(cx))
+        {
+            self.capture_scroll_anchor(cx);
+        }
+        self.prune_text_widths();
+        if !cx.has_active_drag() {
+            self.drop_row = None;
+        }
 
-        let columns_count = self.delegate.columns_count(cx);
         let left_columns_count = self
             .col_groups
             .iter()
             .filter(|col| self.col_fixed && col.column.fixed == Some(ColumnFixed::Left))
             .count();
-        let rows_count = self.delegate.rows_count(cx);
+        if self
+            .aggregates
+            .iter()
+            .flatten()
+            .any(|state| state.len() != rows_count)
+        {
+            self.prepare_aggregates(cx);
+        }
+        let has_footer = self.options.footer || self.aggregates.iter().any(Option::is_some);
         let loading = self.delegate.loading(cx);
-        let extra_rows_count = self.calculate_extra_rows_needed(rows_count);
+        let extra_rows_count = self.calculate_extra_rows_needed(rows_count, window);
         let render_rows_count = if self.options.stripe {
             rows_count + extra_rows_count
         } else {
@@ -1351,10 +3172,13 @@ where
             .context_menu({
                 let view = cx.entity().clone();
                 move |this, window: &mut Window, cx: &mut Context<PopupMenu>| {
-                    if let Some(row_ix) = view.read(cx).right_clicked_row {
+                    let table = view.read(cx);
+                    if let Some(row_ix) = table.right_clicked_row {
                         view.update(cx, |menu, cx| {
                             menu.delegate().context_menu(row_ix, this, window, cx)
                         })
+                    } else if table.right_clicked_head && table.col_hideable {
+                        view.update(cx, |table, cx| table.col_visibility_menu(this, cx))
                     } else {
                         this
                     }
@@ -1365,77 +3189,33 @@ where
                     this.children(empty_view)
                 } else {
                     this.child(
-                        h_flex().id("table-body").flex_grow().size_full().child(
-                            uniform_list(
-                                "table-uniform-list",
-                                render_rows_count,
-                                cx.processor(
-                                    move |table, visible_range: Range<usize>, window, cx| {
-                                        // We must calculate the col sizes here, because the col sizes
-                                        // need render_th first, then that method will set the bounds of each col.
-                                        let col_sizes: Rc<Vec<gpui::Size<Pixels>>> = Rc::new(
-                                            table
-                                                .col_groups
-                                                .iter()
-                                                .skip(left_columns_count)
-                                                .map(|col| col.bounds.size)
-                                                .collect(),
-                                        );
-
-                                        table.load_more_if_need(
-                                            rows_count,
-                                            visible_range.end,
-                                            window,
-                                            cx,
-                                        );
-                                        table.update_visible_range_if_need(
-                                            visible_range.clone(),
-                                            Axis::Vertical,
-                                            window,
-                                            cx,
-                                        );
-
-                                        if visible_range.end > rows_count {
-                                            table.scroll_to_row(
-                                                std::cmp::min(
-                                                    visible_range.start,
-                                                    rows_count.saturating_sub(1),
-                                                ),
-                                                cx,
-                                            );
-                                        }
-
-                                        let mut items = Vec::with_capacity(
-                                            visible_range.end.saturating_sub(visible_range.start),
-                                        );
-
-                                        // Render fake rows to fill the table
-                                        visible_range.for_each(|row_ix| {
-                                            // Render real rows for available data
-                                            items.push(table.render_table_row(
-                                                row_ix,
-                                                rows_count,
-                                                left_columns_count,
-                                                col_sizes.clone(),
-                                                columns_count,
-                                                extra_rows_count,
-                                                window,
-                                                cx,
-                                            ));
-                                        });
-
-                                        items
-                                    },
-                                ),
-                            )
+                        h_flex()
+                            .id("table-body")
                             .flex_grow()
                             .size_full()
-                            .with_sizing_behavior(ListSizingBehavior::Auto)
-                            .track_scroll(self.vertical_scroll_handle.clone())
-                            .into_any_element(),
-                        ),
+                            .when(self.row_movable, |this| {
+                                this.on_drag_move(cx.listener(Self::on_drag_row_move))
+                                    .on_drop(cx.listener(Self::on_drop_row))
+                            })
+                            .child(self.render_rows_list(
+                                rows_count,
+                                render_rows_count,
+                                left_columns_count,
+                                extra_rows_count,
+                                window,
+                                cx,
+                            )),
                     )
                 }
+            })
+            .when(rows_count > 0 && self.delegate.loading_more(cx), |this| {
+                this.child(self.delegate.render_loading_more(window, cx))
+            })
+            .when(has_footer, |this| {
+                this.child(self.render_table_foot(left_columns_count, window, cx))
+            })
+            .when_some(self.options.page_size, |this, page_size| {
+                this.child(self.render_pagination(page_size, cx))
             });
 
         div()
@@ -1453,11 +3233,42 @@ where
                             cx.notify();
                         }))
                     })
+                    .when(self.overlay_loading, |this| {
+                        this.child(
+                            div()
+                                .id("table-loading-overlay")
+                                .absolute()
+                                .top_0()
+                                .left_0()
+                                .size_full()
+                                .occlude()
+                                .flex()
+                                .items_center()
+                                .justify_center()
+                                .bg(cx.theme().background.opacity(0.5))
+                                .child(Indicator::new().with_size(self.options.size)),
+                        )
+                    })
             })
             .child(canvas(
                 {
                     let state = cx.entity();
-                    move |bounds, _, cx| state.update(cx, |state, _| state.bounds = bounds)
+                    move |bounds, _, cx| {
+                        state.update(cx, |state, cx| {
+                            // Re-render to hide or show the columns by the priority,
+                            // or to measure the detail panels in the new width.
+                            if state.bounds.size.width != bounds.size.width
+                                && (!state.expanded_rows.is_empty()
+                                    || state
+                                        .col_groups
+                                        .iter()
+                                        .any(|col| col.column.priority.is_some()))
+                            {
+                                cx.notify();
+                            }
+                            state.bounds = bounds
+                        })
+                    }
                 },
                 |_, _, _, _| {},
             ))
@@ -1504,12 +3315,47 @@ where
         self
     }
 
+    /// Set the height of the header and the rows, default to the height of the size.
+    ///
+    /// The rows with the [`Column::wrap`] are higher to fit the lines.
+    pub fn row_height(mut self, row_height: impl Into<Pixels>) -> Self {
+        self.options.row_height = Some(row_height.into());
+        self
+    }
+
+    /// Set the density to adjust the paddings and the heights of the header and the cells,
+    /// default to the [`Theme::table_density`](crate::Theme::table_density).
+    pub fn density(mut self, density: Density) -> Self {
+        self.options.density = Some(density);
+        self
+    }
+
     /// Set to use border style of the table, default to true.
     pub fn bordered(mut self, bordered: bool) -> Self {
         self.options.bordered = bordered;
         self
     }
 
+    /// Set to show the footer row pinned to the bottom of the table, default to false.
+    ///
+    /// The footer cells are the [`TableDelegate::render_tfoot_td`], or the aggregated values
+    /// of the columns with an [`Aggregator`], the footer is always shown with any aggregated column.
+    pub fn footer(mut self, footer: bool) -> Self {
+        self.options.footer = footer;
+        self
+    }
+
+    /// Set to show the rows in pages of the `page_size`, with a [`Pagination`](crate::pagination::Pagination)
+    /// below the table.
+    ///
+    /// The delegate holds the rows of the current page, loaded by the [`TableDelegate::load_page`],
+    /// and the pages count is by the [`TableDelegate::total_rows_count`]. The infinite loading is
+    /// disabled in the paged mode.
+    pub fn paginated(mut self, page_size: usize) -> Self {
+        self.options.page_size = Some(page_size.max(1));
+        self
+    }
+
     /// Set scrollbar visibility.
     pub fn scrollbar_visible(mut self, vertical: bool, horizontal: bool) -> Self {
         self.options.scrollbar_visible = Edges {
@@ -1538,8 +3384,13 @@ where
     fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
         let bordered = self.options.bordered;
         let focus_handle = self.state.focus_handle(cx);
+        let mut options = self.options;
+        options.size = options
+            .density
+            .unwrap_or(cx.theme().table_density)
+            .size(options.size);
         self.state.update(cx, |state, _| {
-            state.options = self.options;
+            state.options = options;
         });
 
         div()
@@ -1552,6 +3403,14 @@ where
             .on_action(window.listener_for(&self.state, TableState::action_select_prev))
             .on_action(window.listener_for(&self.state, TableState::action_select_next_col))
             .on_action(window.listener_for(&self.state, TableState::action_select_prev_col))
+            .on_action(window.listener_for(&self.state, TableState::action_select_home))
+            .on_action(window.listener_for(&self.state, TableState::action_select_end))
+            .on_action(window.listener_for(&self.state, TableState::action_select_first_row))
+            .on_action(window.listener_for(&self.state, TableState::action_select_last_row))
+            .on_action(window.listener_for(&self.state, TableState::action_select_page_up))
+            .on_action(window.listener_for(&self.state, TableState::action_select_page_down))
+            .on_action(window.listener_for(&self.state, TableState::action_confirm))
+            .on_action(window.listener_for(&self.state, TableState::action_copy))
             .bg(cx.theme().table)
             .when(bordered, |this| {
                 this.rounded(cx.theme().radius)
@@ -1561,3 +3420,1451 @@ where
             .child(self.state)
     }
 }
+
+/// Returns whether the columns are hidden to fit the `widths` in the `available_width`,
+/// the columns with the lower priority (and then the right most) are hidden first.
+///
+/// The `reserved_width` is the width of the "+N" column, only reserved when some columns are hidden.
+fn hidden_cols(
+    widths: &[Pixels],
+    priorities: &[Option<usize>],
+    available_width: Pixels,
+    reserved_width: Pixels,
+) -> Vec<bool> {
+    let mut hidden = vec![false; widths.len()];
+    let mut total_width = widths.iter().fold(px(0.), |sum, w| sum + *w);
+    if available_width <= px(0.) || total_width <= available_width {
+        return hidden;
+    }
+
+    let mut candidates = priorities
+        .iter()
+        .enumerate()
+        .filter_map(|(ix, priority)| priority.map(|priority| (priority, ix)))
+        .collect::<Vec<_>>();
+    candidates.sort_by_key(|(priority, ix)| (*priority, std::cmp::Reverse(*ix)));
+    for (_, ix) in candidates {
+        hidden[ix] = true;
+        total_width -= widths[ix];
+        if total_width + reserved_width <= available_width {
+            break;
+        }
+    }
+    hidden
+}
+
+/// Returns the range of the columns with the `widths` in the viewport, with one more column
+/// on each side, the `scroll_x` is the (negative) horizontal scroll offset.
+fn visible_col_range(widths: &[Pixels], scroll_x: Pixels, viewport_width: Pixels) -> Range<usize> {
+    let left = -scroll_x.min(px(0.));
+    let right = left + viewport_width;
+
+    let mut start = widths.len();
+    let mut end = widths.len();
+    let mut x = px(0.);
+    for (ix, width) in widths.iter().enumerate() {
+        let col_right = x + *width;
+        if start == widths.len() && col_right > left {
+            start = ix;
+        }
+        if x >= right {
+            end = ix;
+            break;
+        }
+        x = col_right;
+    }
+
+    start.saturating_sub(1)..(end + 1).min(widths.len())
+}
+
+/// Returns the spans of the `cols` in the table head, the adjacent columns in the same group
+/// are merged into one span, the ranges are the positions in the `cols`.
+fn col_group_spans(cols: &[usize], groups: &[Option<&SharedString>]) -> Vec<Range<usize>> {
+    let mut spans: Vec<Range<usize>> = vec![];
+    for (ix, col_ix) in cols.iter().enumerate() {
+        let group = groups.get(*col_ix).copied().flatten();
+        match spans.last_mut() {
+            Some(span) if group.is_some() && groups[cols[span.start]] == group => span.end = ix + 1,
+            _ => spans.push(ix..ix + 1),
+        }
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, ops::Range, rc::Rc};
+
+    use gpui::{div, point, px, size, App, IntoElement, SharedString, Styled as _, Task, Window};
+
+    use super::{
+        col_group_spans, hidden_cols, visible_col_range, CellValue, Column, ColumnSort, Confirm,
+        ExportFormat, ExportScope, FilterValue, RowId, Table, TableColState, TableDelegate,
+        TableEvent, TableState,
+    };
+
+    #[derive(Default)]
+    struct TestDelegate {
+        columns: Vec<Column>,
+        groups: Vec<Option<SharedString>>,
+        rows: Vec<usize>,
+        /// The number of the rendered cells.
+        rendered_tds: Cell<usize>,
+        /// The number of the rendered footer cells.
+        rendered_tfoot_tds: Cell<usize>,
+        /// The number of the rows of all the pages in the paged mode.
+        total_rows: usize,
+        /// The keys of the columns hidden by the user.
+        hidden_keys: Vec<SharedString>,
+        /// The depths of the rows in the tree mode, the row `n` in 1..10 has the children
+        /// `n * 10 + 1` and `n * 10 + 2`, the children of the row 2 are loaded lazily.
+        depths: Vec<usize>,
+        /// Whether there are no more rows to load.
+        eof: bool,
+    }
+
+    impl TestDelegate {
+        fn insert_row_children(&mut self, row_id: &RowId) {
+            let id: usize = row_id.as_str().parse().unwrap();
+            let row_ix = self.rows.iter().position(|row| *row == id).unwrap();
+            let depth = self.depths[row_ix] + 1;
+            for (i, child) in [id * 10 + 1, id * 10 + 2].into_iter().enumerate() {
+                self.rows.insert(row_ix + 1 + i, child);
+                self.depths.insert(row_ix + 1 + i, depth);
+            }
+        }
+    }
+
+    impl TableDelegate for TestDelegate {
+        fn columns_count(&self, _: &App) -> usize {
+            self.columns.len()
+        }
+
+        fn rows_count(&self, _: &App) -> usize {
+            self.rows.len()
+        }
+
+        fn row_id(&self, row_ix: usize, _: &App) -> Option<RowId> {
+            Some(self.rows[row_ix].into())
+        }
+
+        fn column(&self, col_ix: usize, _: &App) -> &Column {
+            &self.columns[col_ix]
+        }
+
+        fn col_group(&self, col_ix: usize, _: &App) -> Option<SharedString> {
+            self.groups.get(col_ix).cloned().flatten()
+        }
+
+        fn move_column(
+            &mut self,
+            col_ix: usize,
+            to_ix: usize,
+            _: &mut Window,
+            _: &mut gpui::Context<TableState<Self>>,
+        ) {
+            let column = self.columns.remove(col_ix);
+            self.columns.insert(to_ix, column);
+        }
+
+        fn col_visible(&self, col_ix: usize, _: &App) -> bool {
+            !self.hidden_keys.contains(&self.columns[col_ix].key)
+        }
+
+        fn set_col_visible(
+            &mut self,
+            col_ix: usize,
+            visible: bool,
+            _: &mut Window,
+            _: &mut gpui::Context<TableState<Self>>,
+        ) {
+            let key = self.columns[col_ix].key.clone();
+            self.hidden_keys.retain(|k| *k != key);
+            if !visible {
+                self.hidden_keys.push(key);
+            }
+        }
+
+        fn render_td(&self, _: usize, _: usize, _: &mut Window, _: &mut App) -> impl IntoElement {
+            self.rendered_tds.set(self.rendered_tds.get() + 1);
+            div()
+        }
+
+        fn render_tfoot_td(&self, _: usize, _: &mut Window, _: &mut App) -> impl IntoElement {
+            self.rendered_tfoot_tds
+                .set(self.rendered_tfoot_tds.get() + 1);
+            div()
+        }
+
+        fn can_edit_cell(&self, _: usize, col_ix: usize, _: &App) -> bool {
+            col_ix % 2 == 1
+        }
+
+        fn can_expand_row(&self, row_ix: usize, _: &App) -> bool {
+            row_ix % 2 == 0
+        }
+
+        fn can_move_row(&self, row_ix: usize, _: &App) -> bool {
+            row_ix > 0
+        }
+
+        fn move_row(
+            &mut self,
+            row_ix: usize,
+            to_ix: usize,
+            _: &mut Window,
+            _: &mut gpui::Context<TableState<Self>>,
+        ) {
+            let row = self.rows.remove(row_ix);
+            self.rows.insert(to_ix, row);
+        }
+
+        fn has_no_more_rows(&self, _: &App) -> bool {
+            self.eof
+        }
+
+        /// Sort the rows by the id in the column with the `value` key.
+        fn cell_value(&self, row_ix: usize, col_ix: usize, _: &App) -> CellValue {
+            match self.columns[col_ix].key.as_ref() {
+                "value" => self.rows[row_ix].into(),
+                _ => CellValue::Null,
+            }
+        }
+
+        fn reorder_rows(
+            &mut self,
+            order: &[usize],
+            _: &mut Window,
+            _: &mut gpui::Context<TableState<Self>>,
+        ) {
+            self.rows = order.iter().map(|ix| self.rows[*ix]).collect();
+        }
+
+        fn row_depth(&self, row_ix: usize, _: &App) -> usize {
+            self.depths.get(row_ix).copied().unwrap_or(0)
+        }
+
+        fn has_row_children(&self, row_ix: usize, _: &App) -> bool {
+            (1..10).contains(&self.rows[row_ix])
+        }
+
+        fn row_children(
+            &mut self,
+            row_id: &RowId,
+            _: &mut Window,
+            cx: &mut gpui::Context<TableState<Self>>,
+        ) -> Option<Task<()>> {
+            if row_id.as_str() == "2" {
+                let row_id = row_id.clone();
+                return Some(cx.spawn(async move |this, cx| {
+                    _ = this.update(cx, |this, _| {
+                        this.delegate_mut().insert_row_children(&row_id);
+                    });
+                }));
+            }
+
+            self.insert_row_children(row_id);
+            None
+        }
+
+        fn remove_row_children(
+            &mut self,
+            _: &RowId,
+            rows: Range<usize>,
+            _: &mut Window,
+            _: &mut gpui::Context<TableState<Self>>,
+        ) {
+            self.rows.drain(rows.clone());
+            self.depths.drain(rows);
+        }
+
+        fn load_page(
+            &mut self,
+            page: usize,
+            page_size: usize,
+            _: &mut Window,
+            _: &mut gpui::Context<TableState<Self>>,
+        ) {
+            let start = page * page_size;
+            self.rows = (start..(start + page_size).min(self.total_rows)).collect();
+        }
+
+        fn total_rows_count(&self, _: &App) -> usize {
+            self.total_rows.max(self.rows.len())
+        }
+
+        fn render_row_detail(&self, _: usize, _: &mut Window, _: &mut App) -> impl IntoElement {
+            div().h(px(100.))
+        }
+
+        fn export_cell_text(&self, row_ix: usize, col_ix: usize, _: &App) -> SharedString {
+            match col_ix {
+                0 => format!("{}", self.rows[row_ix]).into(),
+                _ => format!("Row \"{}\", tab\t", self.rows[row_ix]).into(),
+            }
+        }
+    }
+
+    #[gpui::test]
+    fn test_batch_update(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let (state, cx) =
+            cx.add_window_view(|window, cx| TableState::new(TestDelegate::default(), window, cx));
+
+        let notified = Rc::new(Cell::new(0));
+        let _subscription = cx.update({
+            let notified = notified.clone();
+            |_, cx| cx.observe(&state, move |_, _| notified.set(notified.get() + 1))
+        });
+
+        state.update(cx, |state, cx| {
+            state.batch_update(cx, |state, cx| {
+                for ix in 0..100 {
+                    state
+                        .delegate_mut()
+                        .columns
+                        .push(Column::new(format!("col-{}", ix), "Column"));
+                    state.refresh(cx);
+                    assert!(state.is_updating());
+                }
+                assert_eq!(state.col_groups.len(), 0);
+            });
+            assert!(!state.is_updating());
+            assert_eq!(state.col_groups.len(), 100);
+        });
+        cx.run_until_parked();
+        assert_eq!(notified.get(), 1);
+    }
+
+    #[gpui::test]
+    fn test_async_sort(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let (state, cx) = cx.add_window_view(|window, cx| {
+            let delegate = TestDelegate {
+                columns: vec![
+                    Column::new("name", "Name").sortable(),
+                    Column::new("age", "Age").sortable(),
+                ],
+                rows: (0..3).collect(),
+                ..Default::default()
+            };
+            TableState::new(delegate, window, cx).async_sort(true)
+        });
+
+        state.update_in(cx, |state, window, cx| {
+            state.set_selected_row(0, cx);
+            state.perform_sort(1, false, window, cx);
+            assert!(state.is_sorting());
+            assert!(state.is_col_sorting(1));
+            assert!(!state.is_col_sorting(0));
+
+            let generation = state.sort_generation();
+
+            // Click again before the first sort is completed.
+            state.perform_sort(1, false, window, cx);
+            assert_eq!(state.sort_generation(), generation + 1);
+            state.sort_completed(generation, window, cx);
+            assert!(state.is_sorting());
+
+            // The rows are sorted by the server.
+            state.delegate_mut().rows = vec![2, 1, 0];
+            assert_eq!(state.selected_row, Some(0));
+            state.sort_completed(generation + 1, window, cx);
+            assert!(!state.is_sorting());
+            assert_eq!(state.selected_row, Some(2));
+        });
+    }
+
+    #[gpui::test]
+    fn test_events(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let (state, cx) = cx.add_window_view(|window, cx| {
+            let columns = vec![
+                Column::new("name", "Name").sortable(),
+                Column::new("age", "Age").sortable(),
+            ];
+            TableState::new(
+                TestDelegate {
+                    columns,
+                    ..Default::default()
+                },
+                window,
+                cx,
+            )
+        });
+
+        let events = Rc::new(std::cell::RefCell::new(vec![]));
+        let _subscription = cx.update({
+            let events = events.clone();
+            |_, cx| {
+                cx.subscribe(&state, move |_, event: &TableEvent, _| {
+                    events.borrow_mut().push(format!("{:?}", event))
+                })
+            }
+        });
+
+        state.update_in(cx, |state, window, cx| {
+            state.perform_sort(1, false, window, cx);
+            state.perform_sort(0, true, window, cx);
+            assert_eq!(
+                state.sorts(),
+                vec![(1, ColumnSort::Descending), (0, ColumnSort::Descending)]
+            );
+            state.perform_sort(1, true, window, cx);
+            state.perform_sort(1, true, window, cx);
+            assert_eq!(state.sorts(), vec![(0, ColumnSort::Descending)]);
+            assert_eq!(state.col_groups[0].sort_priority, Some(0));
+            state.perform_sort(1, false, window, cx);
+            assert_eq!(state.sorts(), vec![(1, ColumnSort::Descending)]);
+            state.set_filter(0, Some(">= 18"), window, cx);
+            // Not changed.
+            state.set_filter(0, Some(">= 18"), window, cx);
+            assert_eq!(state.filter(0), Some(&FilterValue::from(">= 18")));
+            state.set_filter(0, None::<&str>, window, cx);
+            assert_eq!(state.filter(0), None);
+            assert_eq!(
+                state.col_groups[1].column.sort,
+                Some(ColumnSort::Descending)
+            );
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            *events.borrow(),
+            vec![
+                "SortChanged(1, Descending)",
+                "SortChanged(0, Descending)",
+                "SortChanged(1, Ascending)",
+                "SortChanged(1, Default)",
+                "SortChanged(1, Descending)",
+                "FilterChanged(0, Some(Text(\">= 18\")))",
+                "FilterChanged(0, None)",
+            ]
+        );
+    }
+
+    #[gpui::test]
+    fn test_virtualized_rows(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let (state, cx) = cx.add_window_view(|window, cx| {
+            let delegate = TestDelegate {
+                columns: vec![Column::new("id", "ID")],
+                rows: (0..100_000).collect(),
+                ..Default::default()
+            };
+            TableState::new(delegate, window, cx)
+        });
+
+        let draw = |cx: &mut gpui::VisualTestContext| {
+            let state = state.clone();
+            cx.draw(
+                point(px(0.), px(0.)),
+                size(px(400.), px(600.)),
+                move |_, _| Table::new(&state).into_any_element(),
+            );
+        };
+
+        draw(cx);
+        state.update(cx, |state, cx| {
+            // Only the rows in the viewport are rendered.
+            assert!(state.delegate().rendered_tds.get() < 1_000);
+            assert!(state.visible_range().rows().end < 100);
+            state.delegate().rendered_tds.set(0);
+            state.scroll_to_row(99_990, cx);
+        });
+
+        draw(cx);
+        state.update(cx, |state, _| {
+            assert!(state.delegate().rendered_tds.get() < 1_000);
+            assert!(state.visible_range().rows().contains(&99_990));
+        });
+    }
+
+    #[gpui::test]
+    fn test_expand_row(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let (state, cx) = cx.add_window_view(|window, cx| {
+            let delegate = TestDelegate {
+                columns: vec![Column::new("id", "ID")],
+                rows: (0..1000).collect(),
+                ..Default::default()
+            };
+            TableState::new(delegate, window, cx)
+        });
+
+        let draw = |cx: &mut gpui::VisualTestContext| {
+            let state = state.clone();
+            cx.draw(
+                point(px(0.), px(0.)),
+                size(px(400.), px(600.)),
+                move |_, _| Table::new(&state).into_any_element(),
+            );
+        };
+
+        draw(cx);
+        state.update(cx, |state, cx| {
+            assert!(!state.set_row_expanded(1, true, cx));
+            assert!(state.set_row_expanded(2, true, cx));
+            assert!(state.toggle_row_expanded(4, cx));
+            assert_eq!(state.expanded_rows(), vec![2, 4]);
+        });
+
+        draw(cx);
+        state.update(cx, |state, cx| {
+            // The detail panel with the bottom border.
+            assert_eq!(
+                state.expanded_rows[&2].map(|size| size.height),
+                Some(px(101.))
+            );
+            assert_eq!(state.row_details_height_before(3), px(101.));
+            assert_eq!(state.row_details_height_before(5), px(202.));
+
+            state.delegate_mut().rows.insert(0, 1000);
+            state.rows_changed(0..0, 1, cx);
+            assert_eq!(state.expanded_rows(), vec![3, 5]);
+            state.delegate_mut().rows.remove(3);
+            state.rows_changed(3..4, 0, cx);
+            assert_eq!(state.expanded_rows(), vec![4]);
+            state.scroll_to_row(900, cx);
+        });
+
+        draw(cx);
+        state.update(cx, |state, cx| {
+            assert!(state.visible_range().rows().contains(&900));
+            state.collapse_all_rows(cx);
+            assert!(state.expanded_rows().is_empty());
+        });
+    }
+
+    #[gpui::test]
+    fn test_expanded_rows_by_id(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let (state, cx) = cx.add_window_view(|window, cx| {
+            let delegate = TestDelegate {
+                columns: vec![Column::new("value", "Value").sortable()],
+                rows: (0..5).collect(),
+                ..Default::default()
+            };
+            TableState::new(delegate, window, cx)
+        });
+
+        state.update_in(cx, |state, window, cx| {
+            assert!(state.set_row_expanded(2, true, cx));
+            assert!(state.set_row_expanded(4, true, cx));
+
+            // Sort descending
+            state.perform_sort(0, false, window, cx);
+            assert_eq!(state.delegate().rows, vec![4, 3, 2, 1, 0]);
+            assert_eq!(state.expanded_rows(), vec![0, 2]);
+
+            // Filtered out, and expanded again when shown.
+            state.delegate_mut().rows = vec![3, 2, 1];
+            state.set_filter(0, Some("< 4"), window, cx);
+            assert_eq!(state.expanded_rows(), vec![1]);
+            state.delegate_mut().rows = vec![4, 3, 2, 1, 0];
+            state.set_filter(0, None::<&str>, window, cx);
+            assert_eq!(state.expanded_rows(), vec![0, 2]);
+
+            // Refresh with a new row at the start.
+            state.delegate_mut().rows.insert(0, 5);
+            state.refresh(cx);
+            assert_eq!(state.expanded_rows(), vec![1, 3]);
+            assert!(state.is_row_expanded(3));
+
+            state.set_row_expanded(3, false, cx);
+            state.perform_sort(0, false, window, cx);
+            assert_eq!(state.delegate().rows, vec![5, 4, 3, 2, 1, 0]);
+            assert_eq!(state.expanded_rows(), vec![1]);
+        });
+    }
+
+    #[gpui::test]
+    fn test_wrap_and_tooltip(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let (state, cx) = cx.add_window_view(|window, cx| {
+            let delegate = TestDelegate {
+                columns: vec![
+                    Column::new("value", "Value").width(90.).wrap(2),
+                    Column::new("name", "A very long column name").width(90.),
+                ],
+                rows: vec![1, 123456789012, 123456789012345678],
+                ..Default::default()
+            };
+            TableState::new(delegate, window, cx)
+        });
+
+        state.update_in(cx, |state, window, _| {
+            assert_eq!(
+                state.content_row_height(window),
+                state.options.row_height() + window.line_height()
+            );
+            state.col_groups[0].column.wrap = None;
+            assert_eq!(state.content_row_height(window), state.options.row_height());
+        });
+
+        state.update_in(cx, |state, window, cx| {
+            assert_eq!(state.th_tooltip(0, window), None);
+            assert_eq!(
+                state.th_tooltip(1, window),
+                Some("A very long column name".into())
+            );
+
+            assert_eq!(state.td_tooltip(0, 0, window, cx), None);
+            assert_eq!(
+                state.td_tooltip(1, 0, window, cx),
+                Some("123456789012".into())
+            );
+            assert_eq!(state.td_tooltip(1, 1, window, cx), None);
+
+            // The text fits in 2 lines.
+            state.col_groups[0].column.wrap = Some(2);
+            assert_eq!(state.td_tooltip(1, 0, window, cx), None);
+            assert_eq!(
+                state.td_tooltip(2, 0, window, cx),
+                Some("123456789012345678".into())
+            );
+        });
+    }
+
+    #[gpui::test]
+    fn test_render_caches(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let (state, cx) = cx.add_window_view(|window, cx| {
+            let delegate = TestDelegate {
+                columns: vec![
+                    Column::new("value", "Value").width(90.),
+                    Column::new("name", "Name").width(90.).priority(1),
+                ],
+                rows: vec![1, 123456789012, 2],
+                ..Default::default()
+            };
+            TableState::new(delegate, window, cx)
+        });
+
+        state.update_in(cx, |state, window, cx| {
+            let cached_text = |state: &TableState<TestDelegate>, key| {
+                state
+                    .text_widths
+                    .borrow()
+                    .widths
+                    .get(&key)
+                    .map(|(text, _)| text.clone())
+            };
+
+            assert!(state.td_tooltip(1, 0, window, cx).is_some());
+            assert_eq!(
+                cached_text(state, (Some(1), 0)),
+                Some("123456789012".into())
+            );
+            assert_eq!(state.th_tooltip(0, window), None);
+            assert_eq!(cached_text(state, (None, 0)), Some("Value".into()));
+
+            // Shaped again after the text is changed.
+            state.delegate_mut().rows[1] = 5;
+            assert_eq!(state.td_tooltip(1, 0, window, cx), None);
+            assert_eq!(cached_text(state, (Some(1), 0)), Some("5".into()));
+
+            // The rows out of the viewport are removed, the headers are kept.
+            state.visible_range.rows = 2..3;
+            state.prune_text_widths();
+            assert_eq!(cached_text(state, (Some(1), 0)), None);
+            assert_eq!(cached_text(state, (None, 0)), Some("Value".into()));
+
+            // The hidden columns are updated only when changed.
+            state.bounds.size.width = px(150.);
+            state.update_hidden_cols();
+            let key = state.hidden_cols_key;
+            assert!(key.is_some());
+            assert!(state.col_groups[1].hidden);
+            state.update_hidden_cols();
+            assert_eq!(state.hidden_cols_key, key);
+            state.col_groups[0].width = px(10.);
+            state.update_hidden_cols();
+            assert_ne!(state.hidden_cols_key, key);
+            state.col_groups[0].hidden = true;
+            state.update_hidden_cols();
+            assert!(!state.col_groups[0].hidden);
+        });
+    }
+
+    #[gpui::test]
+    fn test_edit_cell(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let (state, cx) = cx.add_window_view(|window, cx| {
+            let delegate = TestDelegate {
+                columns: (0..4)
+                    .map(|ix| Column::new(format!("col-{}", ix), "Column"))
+                    .collect(),
+                rows: (0..3).collect(),
+                ..Default::default()
+            };
+            TableState::new(delegate, window, cx)
+        });
+
+        let events = Rc::new(std::cell::RefCell::new(vec![]));
+        let _subscription = cx.update({
+            let events = events.clone();
+            |_, cx| {
+                cx.subscribe(&state, move |_, event: &TableEvent, _| {
+                    events.borrow_mut().push(format!("{:?}", event))
+                })
+            }
+        });
+
+        state.update_in(cx, |state, window, cx| {
+            assert!(!state.edit_cell(0, 0, window, cx));
+            assert!(!state.edit_cell(3, 1, window, cx));
+            assert!(state.edit_cell(0, 1, window, cx));
+            assert_eq!(state.editing_cell(), Some((0, 1)));
+
+            state.edit_sibling_cell(true, window, cx);
+            assert_eq!(state.editing_cell(), Some((0, 3)));
+            state.edit_sibling_cell(true, window, cx);
+            assert_eq!(state.editing_cell(), Some((1, 1)));
+            state.edit_sibling_cell(false, window, cx);
+            assert_eq!(state.editing_cell(), Some((0, 3)));
+
+            state.cancel_editing(window, cx);
+            assert_eq!(state.editing_cell(), None);
+
+            // The last editable cell is committed.
+            state.edit_cell(2, 3, window, cx);
+            state.edit_sibling_cell(true, window, cx);
+            assert_eq!(state.editing_cell(), None);
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            *events.borrow(),
+            vec![
+                "CellEdited { row: 0, col: 1 }",
+                "CellEdited { row: 0, col: 3 }",
+                "CellEdited { row: 1, col: 1 }",
+                "CellEdited { row: 2, col: 3 }",
+            ]
+        );
+    }
+
+    #[gpui::test]
+    fn test_preserve_scroll_position(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let (state, cx) = cx.add_window_view(|window, cx| {
+            let delegate = TestDelegate {
+                columns: vec![Column::new("id", "ID")],
+                rows: (0..100).collect(),
+                ..Default::default()
+            };
+            TableState::new(delegate, window, cx).preserve_scroll_position(true)
+        });
+
+        state.update(cx, |state, cx| {
+            let row_height = state.row_height();
+            state.visible_range.rows = 10..20;
+            state.set_selected_row(12, cx);
+            assert_eq!(state.selected_row_id(), Some(&RowId::from(12)));
+            state
+                .vertical_scroll_handle
+                .0
+                .borrow()
+                .base_handle
+                .set_offset(point(px(0.), -row_height * 10.));
+            state.capture_scroll_anchor(cx);
+
+            state.delegate_mut().rows.reverse();
+            state.refresh(cx);
+            // The row 12 is at the index 87 now, and still the 3rd row in the viewport.
+            assert_eq!(state.selected_row, Some(87));
+            let offset = state.vertical_scroll_handle.0.borrow().base_handle.offset();
+            assert_eq!(offset.y, -row_height * 85.);
+
+            // The selected row is removed.
+            state.capture_scroll_anchor(cx);
+            state.delegate_mut().rows.retain(|row| *row != 12);
+            state.refresh(cx);
+            assert_eq!(state.selected_row, None);
+            assert_eq!(state.selected_row_id(), None);
+
+            // Track the selection by the id on the incremental updates.
+            assert!(state.set_selected_row_id(&RowId::from(50), cx));
+            assert_eq!(state.selected_row, Some(49));
+            state.delegate_mut().rows.insert(0, 100);
+            state.rows_changed(0..0, 1, cx);
+            assert_eq!(state.selected_row, Some(50));
+        });
+    }
+
+    #[gpui::test]
+    fn test_fixed_right_cols(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let (state, cx) = cx.add_window_view(|window, cx| {
+            let delegate = TestDelegate {
+                columns: vec![
+                    Column::new("id", "ID").fixed_left(),
+                    Column::new("a", "A"),
+                    Column::new("b", "B"),
+                    Column::new("c", "C").fixed_right().width(80.),
+                    Column::new("d", "D").fixed_right().width(50.),
+                ],
+                rows: (0..10).collect(),
+                ..Default::default()
+            };
+            TableState::new(delegate, window, cx)
+        });
+
+        state.update(cx, |state, _| {
+            assert_eq!(state.fixed_right_cols(), 3..5);
+            assert_eq!(state.fixed_right_cols_width(), px(130.));
+
+            state.col_fixed = false;
+            assert_eq!(state.fixed_right_cols(), 5..5);
+            assert_eq!(state.fixed_right_cols_width(), px(0.));
+        });
+    }
+
+    #[gpui::test]
+    fn test_col_group(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let (state, cx) = cx.add_window_view(|window, cx| {
+            let delegate = TestDelegate {
+                columns: vec![
+                    Column::new("id", "ID"),
+                    Column::new("first_name", "First Name"),
+                    Column::new("last_name", "Last Name"),
+                    Column::new("email", "Email"),
+                ],
+                groups: vec![None, Some("Name".into()), Some("Name".into()), None],
+                rows: (0..10).collect(),
+                ..Default::default()
+            };
+            TableState::new(delegate, window, cx)
+        });
+
+        state.update(cx, |state, _| {
+            assert!(state.has_col_group());
+            assert_eq!(state.head_height(), state.options.row_height() * 2.);
+        });
+
+        // Drag the "Name" group to the "Email" column, the columns move together.
+        state.update_in(cx, |state, window, cx| {
+            state.move_columns(1, 2, 3, window, cx);
+            let keys = state
+                .delegate()
+                .columns
+                .iter()
+                .map(|column| column.key.clone())
+                .collect::<Vec<_>>();
+            assert_eq!(keys, vec!["id", "email", "first_name", "last_name"]);
+            let groups = state
+                .col_groups
+                .iter()
+                .map(|col_group| col_group.group.clone())
+                .collect::<Vec<_>>();
+            assert_eq!(
+                groups,
+                vec![None, None, Some("Name".into()), Some("Name".into())]
+            );
+
+            // Drop in the group itself does nothing.
+            state.move_columns(2, 2, 3, window, cx);
+            assert_eq!(state.col_groups[2].column.key, "first_name");
+
+            state.move_columns(2, 2, 0, window, cx);
+            assert_eq!(state.col_groups[0].column.key, "first_name");
+            assert_eq!(state.col_groups[1].column.key, "last_name");
+            assert_eq!(state.col_groups[2].column.key, "id");
+        });
+    }
+
+    #[test]
+    fn test_col_group_spans() {
+        let name = SharedString::from("Name");
+        let address = SharedString::from("Address");
+        let groups = vec![
+            None,
+            None,
+            Some(&name),
+            Some(&name),
+            Some(&address),
+            Some(&address),
+            Some(&name),
+        ];
+        assert_eq!(
+            col_group_spans(&[0, 1, 2, 3, 4, 5, 6], &groups),
+            vec![0..1, 1..2, 2..4, 4..6, 6..7]
+        );
+        // The hidden column is skipped.
+        assert_eq!(col_group_spans(&[2, 4, 5], &groups), vec![0..1, 1..3]);
+        assert!(col_group_spans(&[], &groups).is_empty());
+    }
+
+    #[gpui::test]
+    fn test_optimistic_update(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let (state, cx) = cx.add_window_view(|window, cx| {
+            let delegate = TestDelegate {
+                columns: vec![Column::new("id", "ID")],
+                rows: (0..10).collect(),
+                ..Default::default()
+            };
+            TableState::new(delegate, window, cx)
+        });
+
+        state.update(cx, |state, cx| {
+            let edit = state.optimistic_update(
+                3,
+                |delegate| delegate.rows[3] = 30,
+                |delegate| {
+                    let ix = delegate.rows.iter().position(|row| *row == 30).unwrap();
+                    delegate.rows[ix] = 3;
+                },
+                cx,
+            );
+            assert_eq!(state.delegate().rows[3], 30);
+            assert!(state.is_row_pending(3, cx));
+            assert!(!state.is_row_pending(4, cx));
+
+            // The pending row is tracked by the id after the rows are moved.
+            state.delegate_mut().rows.insert(0, 100);
+            assert!(state.is_row_pending(4, cx));
+
+            assert!(state.rollback_edit(edit, cx));
+            assert_eq!(state.delegate().rows[4], 3);
+            assert!(!state.is_row_pending(4, cx));
+            assert!(!state.rollback_edit(edit, cx));
+
+            let edit = state.optimistic_update(0, |delegate| delegate.rows[0] = 0, |_| {}, cx);
+            assert!(state.commit_edit(edit, cx));
+            assert!(!state.is_row_pending(0, cx));
+        });
+    }
+
+    #[gpui::test]
+    fn test_optimistic_save(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let (state, cx) = cx.add_window_view(|window, cx| {
+            let delegate = TestDelegate {
+                columns: vec![Column::new("id", "ID")],
+                rows: (0..10).collect(),
+                ..Default::default()
+            };
+            TableState::new(delegate, window, cx)
+        });
+
+        state.update_in(cx, |state, window, cx| {
+            state.optimistic_save(
+                1,
+                |delegate| delegate.rows[1] = 10,
+                |delegate| delegate.rows[1] = 1,
+                async { Err(anyhow::anyhow!("failed")) },
+                window,
+                cx,
+            );
+            state.optimistic_save(
+                2,
+                |delegate| delegate.rows[2] = 20,
+                |delegate| delegate.rows[2] = 2,
+                async { Ok(()) },
+                window,
+                cx,
+            );
+            state.set_loading(true, cx);
+            assert!(state.is_loading());
+        });
+        cx.run_until_parked();
+        state.update(cx, |state, cx| {
+            assert_eq!(state.delegate().rows[1], 1);
+            assert_eq!(state.delegate().rows[2], 20);
+            assert!(!state.is_row_pending(1, cx));
+            assert!(!state.is_row_pending(2, cx));
+        });
+    }
+
+    #[test]
+    fn test_hidden_cols() {
+        let widths = vec![px(100.); 5];
+        let priorities = vec![None, Some(2), Some(1), None, Some(1)];
+        assert_eq!(
+            hidden_cols(&widths, &priorities, px(500.), px(40.)),
+            vec![false; 5]
+        );
+        // The right most column of the lowest priority first.
+        assert_eq!(
+            hidden_cols(&widths, &priorities, px(450.), px(40.)),
+            vec![false, false, false, false, true]
+        );
+        assert_eq!(
+            hidden_cols(&widths, &priorities, px(350.), px(40.)),
+            vec![false, false, true, false, true]
+        );
+        // The columns without priority are always visible.
+        assert_eq!(
+            hidden_cols(&widths, &priorities, px(100.), px(40.)),
+            vec![false, true, true, false, true]
+        );
+        assert_eq!(
+            hidden_cols(&widths, &priorities, px(0.), px(40.)),
+            vec![false; 5]
+        );
+    }
+
+    #[test]
+    fn test_visible_col_range() {
+        let widths = vec![px(100.); 10];
+        assert_eq!(visible_col_range(&widths, px(0.), px(250.)), 0..4);
+        assert_eq!(visible_col_range(&widths, px(-350.), px(250.)), 2..7);
+        assert_eq!(visible_col_range(&widths, px(-800.), px(250.)), 7..10);
+        assert_eq!(visible_col_range(&widths, px(0.), px(2000.)), 0..10);
+        assert_eq!(visible_col_range(&[], px(0.), px(250.)), 0..0);
+    }
+
+    #[gpui::test]
+    fn test_export(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let (state, cx) = cx.add_window_view(|window, cx| {
+            let delegate = TestDelegate {
+                columns: vec![Column::new("id", "ID"), Column::new("name", "Name, Full")],
+                rows: vec![1, 2],
+                ..Default::default()
+            };
+            TableState::new(delegate, window, cx)
+        });
+
+        state.update(cx, |state, cx| {
+            assert_eq!(
+                state.export(ExportFormat::Csv, cx),
+                "ID,\"Name, Full\"\n1,\"Row \"\"1\"\", tab\t\"\n2,\"Row \"\"2\"\", tab\t\"\n"
+            );
+            assert_eq!(
+                state.export(ExportFormat::Tsv, cx),
+                "ID\tName, Full\n1\t\"Row \"\"1\"\", tab\t\"\n2\t\"Row \"\"2\"\", tab\t\"\n"
+            );
+
+            assert_eq!(state.selection_text(cx), None);
+            state.set_selected_row(1, cx);
+            assert_eq!(
+                state.selection_text(cx).as_deref(),
+                Some("2\t\"Row \"\"2\"\", tab\t\"\n")
+            );
+            state.set_selected_col(0, cx);
+            assert_eq!(state.selection_text(cx).as_deref(), Some("ID\n1\n2\n"));
+
+            let svg = state.export_svg(ExportScope::All, cx);
+            assert!(svg.starts_with("<svg"));
+            assert!(svg.contains(">Name, Full</text>"));
+            assert!(svg.contains(">Row &quot;2&quot;, tab\t</text>"));
+            let png = state.export_png(ExportScope::All, 1., cx).unwrap();
+            assert_eq!(&png[1..4], b"PNG");
+        });
+    }
+
+    #[gpui::test]
+    fn test_footer(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let (state, cx) = cx.add_window_view(|window, cx| {
+            let delegate = TestDelegate {
+                columns: vec![Column::new("id", "ID"), Column::new("name", "Name")],
+                rows: (0..10).collect(),
+                ..Default::default()
+            };
+            TableState::new(delegate, window, cx)
+        });
+
+        let draw = |footer: bool, cx: &mut gpui::VisualTestContext| {
+            let state = state.clone();
+            cx.draw(
+                point(px(0.), px(0.)),
+                size(px(400.), px(600.)),
+                move |_, _| Table::new(&state).footer(footer).into_any_element(),
+            );
+        };
+
+        draw(false, cx);
+        state.update(cx, |state, _| {
+            assert_eq!(state.delegate().rendered_tfoot_tds.get(), 0);
+        });
+
+        draw(true, cx);
+        state.update(cx, |state, _| {
+            assert!(state.delegate().rendered_tfoot_tds.get() > 0);
+        });
+    }
+
+    #[gpui::test]
+    fn test_paginated(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let (state, cx) = cx.add_window_view(|window, cx| {
+            let delegate = TestDelegate {
+                columns: vec![Column::new("id", "ID")],
+                total_rows: 45,
+                ..Default::default()
+            };
+            TableState::new(delegate, window, cx)
+        });
+
+        let pages = Rc::new(std::cell::RefCell::new(vec![]));
+        let _subscription = cx.update({
+            let pages = pages.clone();
+            |_, cx| {
+                cx.subscribe(&state, move |_, event: &TableEvent, _| {
+                    if let TableEvent::PageChanged(page) = event {
+                        pages.borrow_mut().push(*page);
+                    }
+                })
+            }
+        });
+
+        // The first page is loaded on the first render.
+        cx.draw(point(px(0.), px(0.)), size(px(400.), px(600.)), |_, _| {
+            Table::new(&state).paginated(20).into_any_element()
+        });
+        cx.run_until_parked();
+        state.update_in(cx, |state, window, cx| {
+            assert_eq!(state.page(), 0);
+            assert_eq!(state.pages_count(cx), 3);
+            assert_eq!(state.delegate().rows, (0..20).collect::<Vec<_>>());
+
+            state.set_selected_row(1, cx);
+            state.set_page(2, window, cx);
+            assert_eq!(state.delegate().rows, (40..45).collect::<Vec<_>>());
+            assert_eq!(state.selected_row(), None);
+
+            // Clamped to the last page.
+            state.set_page(10, window, cx);
+            assert_eq!(state.page(), 2);
+        });
+
+        assert_eq!(pages.borrow().as_slice(), [0, 2]);
+    }
+
+    #[gpui::test]
+    fn test_load_more(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let (state, cx) = cx.add_window_view(|window, cx| {
+            let delegate = TestDelegate {
+                columns: vec![Column::new("id", "ID")],
+                rows: (0..10).collect(),
+                ..Default::default()
+            };
+            TableState::new(delegate, window, cx)
+        });
+
+        let load_more_count = Rc::new(Cell::new(0));
+        let _subscription = cx.update({
+            let load_more_count = load_more_count.clone();
+            |_, cx| {
+                cx.subscribe(&state, move |_, event: &TableEvent, _| {
+                    if let TableEvent::LoadMore = event {
+                        load_more_count.set(load_more_count.get() + 1);
+                    }
+                })
+            }
+        });
+
+        state.update_in(cx, |state, window, cx| {
+            // Emitted once until the rows are loaded.
+            state.delegate_mut().rows = (0..15).collect();
+            state.load_more_if_need(15, 1, window, cx);
+            state.load_more_if_need(15, 10, window, cx);
+            state.delegate_mut().rows = (0..40).collect();
+            // Not near the end.
+            state.load_more_if_need(40, 10, window, cx);
+            state.load_more_if_need(40, 25, window, cx);
+            // No more rows.
+            state.delegate_mut().eof = true;
+            state.load_more_if_need(40, 39, window, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(load_more_count.get(), 2);
+    }
+
+    #[gpui::test]
+    fn test_keyboard_navigation(cx: &mut gpui::TestAppContext) {
+        use super::Movement;
+
+        cx.update(crate::init);
+        let (state, cx) = cx.add_window_view(|window, cx| {
+            let delegate = TestDelegate {
+                columns: vec![
+                    Column::new("a", "A"),
+                    Column::new("b", "B"),
+                    Column::new("c", "C"),
+                ],
+                rows: (0..10).collect(),
+                ..Default::default()
+            };
+            TableState::new(delegate, window, cx)
+                .cell_selectable(true)
+                .loop_selection(false)
+        });
+
+        let events = Rc::new(std::cell::RefCell::new(vec![]));
+        let _subscription = cx.update({
+            let events = events.clone();
+            |_, cx| {
+                cx.subscribe(&state, move |_, event: &TableEvent, _| {
+                    if let TableEvent::ActiveCellChanged { .. } | TableEvent::ActivateRow(_) = event
+                    {
+                        events.borrow_mut().push(format!("{:?}", event))
+                    }
+                })
+            }
+        });
+
+        state.update_in(cx, |state, window, cx| {
+            assert_eq!(state.active_cell(), None);
+            // Start from the first cell.
+            state.move_selection(Movement::Down, cx);
+            assert_eq!(state.active_cell(), Some((0, 0)));
+            state.move_selection(Movement::Left, cx);
+            assert_eq!(state.active_cell(), Some((0, 0)));
+            state.move_selection(Movement::Right, cx);
+            state.move_selection(Movement::Down, cx);
+            assert_eq!(state.active_cell(), Some((1, 1)));
+            assert_eq!(state.selected_row(), Some(1));
+            state.move_selection(Movement::End, cx);
+            assert_eq!(state.active_cell(), Some((1, 2)));
+            state.move_selection(Movement::LastRow, cx);
+            assert_eq!(state.active_cell(), Some((9, 2)));
+            state.move_selection(Movement::Home, cx);
+            assert_eq!(state.active_cell(), Some((9, 0)));
+            state.action_confirm(&Confirm { secondary: false }, window, cx);
+
+            // The hidden columns are skipped.
+            state.col_groups[1].hidden = true;
+            state.move_selection(Movement::Right, cx);
+            assert_eq!(state.active_cell(), Some((9, 2)));
+
+            state.clear_selection(cx);
+            assert_eq!(state.active_cell(), None);
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            *events.borrow(),
+            vec![
+                "ActiveCellChanged { row: 0, col: 0 }",
+                "ActiveCellChanged { row: 0, col: 1 }",
+                "ActiveCellChanged { row: 1, col: 1 }",
+                "ActiveCellChanged { row: 1, col: 2 }",
+                "ActiveCellChanged { row: 9, col: 2 }",
+                "ActiveCellChanged { row: 9, col: 0 }",
+                "ActivateRow(9)",
+                "ActiveCellChanged { row: 9, col: 2 }",
+            ]
+        );
+
+        // Without the cell selection, the home and end keys move the selected row.
+        state.update(cx, |state, cx| {
+            state.cell_selectable = false;
+            // The first key selects the first row.
+            state.move_selection(Movement::End, cx);
+            assert_eq!(state.selected_row(), Some(0));
+            state.move_selection(Movement::End, cx);
+            assert_eq!(state.selected_row(), Some(9));
+            assert_eq!(state.active_cell(), None);
+            state.move_selection(Movement::PageUp, cx);
+            assert!(state.selected_row() < Some(9));
+            state.move_selection(Movement::Home, cx);
+            assert_eq!(state.selected_row(), Some(0));
+        });
+    }
+
+    #[gpui::test]
+    fn test_col_visible(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let (state, cx) = cx.add_window_view(|window, cx| {
+            let delegate = TestDelegate {
+                columns: vec![
+                    Column::new("a", "A"),
+                    Column::new("b", "B"),
+                    Column::new("c", "C"),
+                ],
+                rows: (0..10).collect(),
+                hidden_keys: vec!["c".into()],
+                ..Default::default()
+            };
+            TableState::new(delegate, window, cx)
+        });
+
+        state.update_in(cx, |state, window, cx| {
+            // The initial visibility is from the delegate.
+            assert!(state.col_visible(0));
+            assert!(!state.col_visible(2));
+            assert!(state.col_groups[2].hidden);
+
+            state.set_selected_col(1, cx);
+            state.set_col_visible(1, false, window, cx);
+            assert!(!state.col_visible(1));
+            assert!(state.col_groups[1].hidden);
+            assert_eq!(state.selected_col(), None);
+            // The columns hidden by the user are not in the "+N" columns.
+            assert_eq!(state.hidden_cols(), Vec::<usize>::new());
+
+            // The last visible column can't be hidden.
+            state.set_col_visible(0, false, window, cx);
+            assert!(state.col_visible(0));
+
+            // The visibility is kept on refresh by the delegate.
+            state.set_col_visible(2, true, window, cx);
+            state.refresh(cx);
+            assert_eq!(
+                (0..3).map(|ix| state.col_visible(ix)).collect::<Vec<_>>(),
+                vec![true, false, true]
+            );
+        });
+    }
+
+    #[gpui::test]
+    fn test_col_state(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let (state, cx) = cx.add_window_view(|window, cx| {
+            let delegate = TestDelegate {
+                columns: vec![
+                    Column::new("a", "A"),
+                    Column::new("b", "B").sortable(),
+                    Column::new("c", "C"),
+                ],
+                rows: (0..10).collect(),
+                ..Default::default()
+            };
+            TableState::new(delegate, window, cx)
+        });
+
+        let col_state: TableColState = serde_json::from_str(
+            r#"{"columns": [
+                {"key": "c", "width": 150},
+                {"key": "unknown", "width": 100},
+                {"key": "a", "width": 5, "visible": false},
+                {"key": "b", "width": 100, "sort": "descending", "sort_priority": 3}
+            ]}"#,
+        )
+        .unwrap();
+
+        state.update_in(cx, |state, window, cx| {
+            state.set_col_state(&col_state, window, cx);
+
+            let keys = state
+                .delegate()
+                .columns
+                .iter()
+                .map(|column| column.key.clone())
+                .collect::<Vec<_>>();
+            assert_eq!(keys, vec!["c", "a", "b"]);
+            assert!(!state.col_visible(1));
+            assert_eq!(state.sorts(), vec![(2, ColumnSort::Descending)]);
+
+            let col_state = state.col_state();
+            assert_eq!(
+                serde_json::to_string(&col_state).unwrap(),
+                [
+                    r#"{"columns":["#,
+                    r#"{"key":"c","width":150.0,"visible":true},"#,
+                    // The width is limited by the min width.
+                    r#"{"key":"a","width":10.0,"visible":false},"#,
+                    r#"{"key":"b","width":100.0,"visible":true,"sort":"descending","sort_priority":0}"#,
+                    "]}",
+                ]
+                .concat()
+            );
+        });
+    }
+
+    #[gpui::test]
+    fn test_move_row(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let (state, cx) = cx.add_window_view(|window, cx| {
+            let delegate = TestDelegate {
+                columns: vec![Column::new("id", "ID")],
+                rows: (0..5).collect(),
+                ..Default::default()
+            };
+            TableState::new(delegate, window, cx)
+        });
+
+        let events = Rc::new(std::cell::RefCell::new(vec![]));
+        let _subscription = cx.update({
+            let events = events.clone();
+            |_, cx| {
+                cx.subscribe(&state, move |_, event: &TableEvent, _| {
+                    events.borrow_mut().push(format!("{:?}", event))
+                })
+            }
+        });
+
+        state.update_in(cx, |state, window, cx| {
+            state.set_selected_row(1, cx);
+            state.set_row_expanded(2, true, cx);
+
+            state.move_row(1, 3, window, cx);
+            assert_eq!(state.delegate().rows, vec![0, 2, 3, 1, 4]);
+            assert_eq!(state.selected_row, Some(3));
+            assert!(state.is_row_expanded(1));
+            assert!(!state.is_row_expanded(2));
+
+            // The first row can't be moved.
+            state.move_row(0, 2, window, cx);
+            assert_eq!(state.delegate().rows, vec![0, 2, 3, 1, 4]);
+
+            // Drop the row after the last row.
+            let drag = state.drag_row(1, cx);
+            state.drop_row = Some(5);
+            state.on_drop_row(&drag, window, cx);
+            assert_eq!(state.delegate().rows, vec![0, 3, 1, 4, 2]);
+            assert_eq!(state.drop_row, None);
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            *events.borrow(),
+            vec![
+                "SelectRow(1)",
+                "RowExpanded { row: 2, expanded: true }",
+                "MoveRow(1, 3)",
+                "MoveRow(1, 4)"
+            ]
+        );
+    }
+
+    #[gpui::test]
+    fn test_tree(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let (state, cx) = cx.add_window_view(|window, cx| {
+            let delegate = TestDelegate {
+                columns: vec![Column::new("id", "ID")],
+                rows: (0..4).collect(),
+                depths: vec![0; 4],
+                ..Default::default()
+            };
+            TableState::new(delegate, window, cx).tree(true)
+        });
+
+        state.update_in(cx, |state, window, cx| {
+            state.set_selected_row(3, cx);
+
+            state.set_tree_expanded(1, true, window, cx);
+            assert_eq!(state.delegate().rows, vec![0, 1, 11, 12, 2, 3]);
+            assert_eq!(state.delegate().depths, vec![0, 0, 1, 1, 0, 0]);
+            assert!(state.is_tree_expanded(1, cx));
+            assert_eq!(state.selected_row, Some(5));
+
+            // The leaf rows can't be expanded.
+            state.set_tree_expanded(2, true, window, cx);
+            assert!(!state.is_tree_expanded(2, cx));
+
+            // Load the children lazily.
+            state.set_tree_expanded(4, true, window, cx);
+            assert!(state.is_tree_loading(4, cx));
+            assert_eq!(state.delegate().rows.len(), 6);
+        });
+        cx.run_until_parked();
+
+        state.update_in(cx, |state, window, cx| {
+            assert!(!state.is_tree_loading(4, cx));
+            assert_eq!(state.delegate().rows, vec![0, 1, 11, 12, 2, 21, 22, 3]);
+            assert_eq!(state.selected_row, Some(7));
+
+            // Collapse the row with the descendants.
+            state.toggle_tree_expanded(1, window, cx);
+            assert!(!state.is_tree_expanded(1, cx));
+            assert_eq!(state.delegate().rows, vec![0, 1, 2, 21, 22, 3]);
+            assert_eq!(state.selected_row, Some(5));
+            assert!(state.is_tree_expanded(2, cx));
+        });
+    }
+}
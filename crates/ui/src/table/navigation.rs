@@ -0,0 +1,248 @@
+use gpui::{Context, MouseDownEvent, ScrollStrategy, Window};
+
+use crate::{
+    actions::Confirm,
+    table::{
+        SelectEnd, SelectFirstRow, SelectHome, SelectLastRow, SelectPageDown, SelectPageUp,
+        SelectionState, TableDelegate, TableEvent, TableState,
+    },
+};
+
+/// The movement of the selection by the keyboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Movement {
+    Up,
+    Down,
+    Left,
+    Right,
+    /// The first column of the row, or the first row if the cells are not selectable.
+    Home,
+    /// The last column of the row, or the last row if the cells are not selectable.
+    End,
+    FirstRow,
+    LastRow,
+    PageUp,
+    PageDown,
+}
+
+impl<D> TableState<D>
+where
+    D: TableDelegate,
+{
+    /// Returns the `(row_ix, col_ix)` of the active cell, if the cells are selectable.
+    pub fn active_cell(&self) -> Option<(usize, usize)> {
+        if self.selection_state != SelectionState::Cell {
+            return None;
+        }
+
+        self.selected_row.zip(self.active_col)
+    }
+
+    /// Sets the active cell, the row of the cell is selected as well.
+    pub fn set_active_cell(&mut self, row_ix: usize, col_ix: usize, cx: &mut Context<Self>) {
+        if self.active_cell() == Some((row_ix, col_ix)) {
+            return;
+        }
+
+        let row_changed = self.selected_row != Some(row_ix);
+        let is_down = self
+            .selected_row
+            .map_or(true, |selected_row| row_ix > selected_row);
+
+        self.selection_state = SelectionState::Cell;
+        self.right_clicked_row = None;
+        self.selected_row = Some(row_ix);
+        self.selected_row_id = self.delegate.row_id(row_ix, cx);
+        self.active_col = Some(col_ix);
+        self.scroll_to_row_item(
+            row_ix,
+            if is_down {
+                ScrollStrategy::Bottom
+            } else {
+                ScrollStrategy::Top
+            },
+        );
+        let left_cols_count = self.fixed_left_cols_count();
+        if col_ix >= left_cols_count
+            && !self
+                .visible_range
+                .cols
+                .contains(&(col_ix - left_cols_count))
+        {
+            self.scroll_to_col(col_ix, cx);
+        }
+
+        if row_changed {
+            cx.emit(TableEvent::SelectRow(row_ix));
+        }
+        cx.emit(TableEvent::ActiveCellChanged {
+            row: row_ix,
+            col: col_ix,
+        });
+        self.notify(cx);
+    }
+
+    /// Move the active cell (or the selected row if the cells are not selectable).
+    pub(super) fn move_selection(&mut self, movement: Movement, cx: &mut Context<Self>) {
+        let rows_count = self.delegate.rows_count(cx);
+        let visible_cols = self.visible_col_ixs();
+        if rows_count == 0 || visible_cols.is_empty() {
+            return;
+        }
+
+        let active_cell = self.active_cell();
+        let (row_ix, col_ix) = match (self.selected_row, active_cell) {
+            (_, Some((row_ix, col_ix))) => {
+                self.move_cell(row_ix, col_ix, movement, rows_count, &visible_cols)
+            }
+            (Some(row_ix), None) if !self.cell_selectable => {
+                self.move_cell(row_ix, visible_cols[0], movement, rows_count, &visible_cols)
+            }
+            // Activate the first cell of the selected row.
+            (Some(row_ix), None) => (row_ix, visible_cols[0]),
+            (None, _) => (0, visible_cols[0]),
+        };
+
+        if self.cell_selectable {
+            self.set_active_cell(row_ix, col_ix, cx);
+        } else {
+            self.set_selected_row(row_ix, cx);
+        }
+    }
+
+    /// Returns the cell after the movement.
+    fn move_cell(
+        &self,
+        row_ix: usize,
+        col_ix: usize,
+        movement: Movement,
+        rows_count: usize,
+        visible_cols: &[usize],
+    ) -> (usize, usize) {
+        let last_row = rows_count - 1;
+        let page = self.visible_range.rows.len().saturating_sub(1).max(1);
+        let col_pos = visible_cols
+            .iter()
+            .position(|ix| *ix >= col_ix)
+            .unwrap_or(0);
+        let first_col = visible_cols[0];
+        let last_col = visible_cols[visible_cols.len() - 1];
+
+        match movement {
+            Movement::Up if row_ix > 0 => (row_ix - 1, col_ix),
+            Movement::Up if self.loop_selection => (last_row, col_ix),
+            Movement::Down if row_ix < last_row => (row_ix + 1, col_ix),
+            Movement::Down if self.loop_selection => (0, col_ix),
+            Movement::Left if col_pos > 0 => (row_ix, visible_cols[col_pos - 1]),
+            Movement::Left if self.loop_selection => (row_ix, last_col),
+            Movement::Right if col_pos + 1 < visible_cols.len() => {
+                (row_ix, visible_cols[col_pos + 1])
+            }
+            Movement::Right if self.loop_selection => (row_ix, first_col),
+            Movement::Home if self.cell_selectable => (row_ix, first_col),
+            Movement::End if self.cell_selectable => (row_ix, last_col),
+            Movement::Home | Movement::FirstRow => (0, col_ix),
+            Movement::End | Movement::LastRow => (last_row, col_ix),
+            Movement::PageUp => (row_ix.saturating_sub(page), col_ix),
+            Movement::PageDown => ((row_ix + page).min(last_row), col_ix),
+            _ => (row_ix, col_ix),
+        }
+    }
+
+    /// Returns the indices of the columns not hidden, in the display order.
+    fn visible_col_ixs(&self) -> Vec<usize> {
+        self.col_groups
+            .iter()
+            .enumerate()
+            .filter(|(_, col_group)| !col_group.hidden)
+            .map(|(ix, _)| ix)
+            .collect()
+    }
+
+    pub(super) fn on_cell_click(
+        &mut self,
+        ev: &MouseDownEvent,
+        row_ix: usize,
+        col_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self
+            .editing_cell
+            .is_some_and(|editing_cell| editing_cell != (row_ix, col_ix))
+        {
+            self.commit_editing(window, cx);
+        }
+
+        self.set_active_cell(row_ix, col_ix, cx);
+        if ev.click_count == 2 {
+            cx.emit(TableEvent::DoubleClickedRow(row_ix));
+        }
+        // The row is selected with the cell, skip the click of the row.
+        cx.stop_propagation();
+    }
+
+    pub(super) fn action_select_home(
+        &mut self,
+        _: &SelectHome,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.move_selection(Movement::Home, cx);
+    }
+
+    pub(super) fn action_select_end(
+        &mut self,
+        _: &SelectEnd,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.move_selection(Movement::End, cx);
+    }
+
+    pub(super) fn action_select_first_row(
+        &mut self,
+        _: &SelectFirstRow,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.move_selection(Movement::FirstRow, cx);
+    }
+
+    pub(super) fn action_select_last_row(
+        &mut self,
+        _: &SelectLastRow,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.move_selection(Movement::LastRow, cx);
+    }
+
+    pub(super) fn action_select_page_up(
+        &mut self,
+        _: &SelectPageUp,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.move_selection(Movement::PageUp, cx);
+    }
+
+    pub(super) fn action_select_page_down(
+        &mut self,
+        _: &SelectPageDown,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.move_selection(Movement::PageDown, cx);
+    }
+
+    /// Emit the [`TableEvent::ActivateRow`] of the selected row by the `enter` key.
+    pub(super) fn action_confirm(&mut self, _: &Confirm, _: &mut Window, cx: &mut Context<Self>) {
+        match self.selected_row {
+            Some(row_ix) if row_ix < self.delegate.rows_count(cx) => {
+                cx.emit(TableEvent::ActivateRow(row_ix));
+            }
+            _ => cx.propagate(),
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use std::future::Future;
+
+use gpui::{App, Context, Window};
+
+use crate::table::{RowId, TableDelegate, TableState};
+
+/// The handle of an optimistic edit of a row, see [`TableState::optimistic_update`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OptimisticEdit(usize);
+
+/// An optimistic edit that is not committed or rolled back yet.
+pub(super) struct PendingEdit<D> {
+    id: usize,
+    row_ix: usize,
+    row_id: Option<RowId>,
+    rollback: Box<dyn FnOnce(&mut D)>,
+}
+
+impl<D> TableState<D>
+where
+    D: TableDelegate,
+{
+    /// Apply the `update` of the row to the delegate immediately, before it is saved.
+    ///
+    /// The row is rendered as pending until the edit is committed by [`Self::commit_edit`],
+    /// or reverted by the `rollback` with [`Self::rollback_edit`], e.g. when the save is failed.
+    pub fn optimistic_update(
+        &mut self,
+        row_ix: usize,
+        update: impl FnOnce(&mut D),
+        rollback: impl FnOnce(&mut D) + 'static,
+        cx: &mut Context<Self>,
+    ) -> OptimisticEdit {
+        let id = self.next_edit_id;
+        self.next_edit_id += 1;
+
+        update(&mut self.delegate);
+        self.pending_edits.push(PendingEdit {
+            id,
+            row_ix,
+            row_id: self.delegate.row_id(row_ix, cx),
+            rollback: Box::new(rollback),
+        });
+        self.rows_changed(row_ix..row_ix + 1, 1, cx);
+        OptimisticEdit(id)
+    }
+
+    /// Commit the optimistic edit after it is saved, returns false if the edit is not pending.
+    pub fn commit_edit(&mut self, edit: OptimisticEdit, cx: &mut Context<Self>) -> bool {
+        let Some(ix) = self.pending_edits.iter().position(|e| e.id == edit.0) else {
+            return false;
+        };
+
+        self.pending_edits.remove(ix);
+        self.notify(cx);
+        true
+    }
+
+    /// Revert the optimistic edit by its rollback, returns false if the edit is not pending.
+    pub fn rollback_edit(&mut self, edit: OptimisticEdit, cx: &mut Context<Self>) -> bool {
+        let Some(ix) = self.pending_edits.iter().position(|e| e.id == edit.0) else {
+            return false;
+        };
+
+        let pending = self.pending_edits.remove(ix);
+        (pending.rollback)(&mut self.delegate);
+        // The row may be moved by the sorts, find it by the id.
+        let row_ix = pending
+            .row_id
+            .and_then(|row_id| self.row_ix(&row_id, cx))
+            .unwrap_or(pending.row_ix);
+        if row_ix < self.delegate.rows_count(cx) {
+            self.rows_changed(row_ix..row_ix + 1, 1, cx);
+        } else {
+            self.notify(cx);
+        }
+        true
+    }
+
+    /// Apply the `update` optimistically and run the `save`, the edit is committed if the save
+    /// is succeeded, or rolled back if failed.
+    ///
+    /// ```ignore
+    /// let save = api.save_user(user.clone());
+    /// table.optimistic_save(
+    ///     row_ix,
+    ///     |delegate| delegate.users[row_ix].name = "New".into(),
+    ///     move |delegate| delegate.users[row_ix].name = old_name,
+    ///     save,
+    ///     window,
+    ///     cx,
+    /// );
+    /// ```
+    pub fn optimistic_save(
+        &mut self,
+        row_ix: usize,
+        update: impl FnOnce(&mut D),
+        rollback: impl FnOnce(&mut D) + 'static,
+        save: impl Future<Output = anyhow::Result<()>> + 'static,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> OptimisticEdit {
+        let edit = self.optimistic_update(row_ix, update, rollback, cx);
+        cx.spawn_in(window, async move |this, window| {
+            let result = save.await;
+            _ = this.update_in(window, |this, _, cx| match result {
+                Ok(()) => this.commit_edit(edit, cx),
+                Err(_) => this.rollback_edit(edit, cx),
+            });
+        })
+        .detach();
+        edit
+    }
+
+    /// Returns true if the row has an optimistic edit not committed or rolled back yet.
+    pub fn is_row_pending(&self, row_ix: usize, cx: &App) -> bool {
+        if self.pending_edits.is_empty() {
+            return false;
+        }
+
+        let row_id = self.delegate.row_id(row_ix, cx);
+        self.pending_edits
+            .iter()
+            .any(|edit| match (&edit.row_id, &row_id) {
+                (Some(a), Some(b)) => a == b,
+                _ => edit.row_ix == row_ix,
+            })
+    }
+}
@@ -0,0 +1,127 @@
+use gpui::{App, Context, IntoElement, ParentElement as _, ScrollStrategy, Styled as _, Window};
+
+use crate::{
+    h_flex,
+    pagination::Pagination,
+    table::{TableDelegate, TableEvent, TableState},
+    ActiveTheme as _, Disableable as _, Sizable as _,
+};
+
+impl<D> TableState<D>
+where
+    D: TableDelegate,
+{
+    /// Returns the current page in the paged mode, 0-based, see [`Table::paginated`](super::Table::paginated).
+    pub fn page(&self) -> usize {
+        self.page
+    }
+
+    /// Returns the number of the rows per page, `None` if the table is not in the paged mode.
+    pub fn page_size(&self) -> Option<usize> {
+        self.options.page_size
+    }
+
+    /// Returns the number of the pages by the [`TableDelegate::total_rows_count`], at least 1.
+    pub fn pages_count(&self, cx: &App) -> usize {
+        let Some(page_size) = self.page_size() else {
+            return 1;
+        };
+
+        self.delegate
+            .total_rows_count(cx)
+            .div_ceil(page_size.max(1))
+            .max(1)
+    }
+
+    /// Go to the page in the paged mode, call the [`TableDelegate::load_page`] to load the rows
+    /// of the page, and emit the [`TableEvent::PageChanged`].
+    ///
+    /// The page is clamped to the pages count, and the selection is cleared.
+    pub fn set_page(&mut self, page: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(page_size) = self.page_size() else {
+            return;
+        };
+
+        let page = page.min(self.pages_count(cx) - 1);
+        if self.page == page && self.page_loaded {
+            return;
+        }
+
+        self.page = page;
+        self.page_loaded = true;
+        self.selected_row = None;
+        self.selected_row_id = None;
+        self.expanded_rows.clear();
+        self.expanded_row_ids.clear();
+        self.delegate_mut().load_page(page, page_size, window, cx);
+        self.scroll_to_row_item(0, ScrollStrategy::Top);
+
+        cx.emit(TableEvent::PageChanged(page));
+        cx.notify();
+    }
+
+    /// Go back to the first page after the rows are sorted or filtered in the paged mode.
+    pub(super) fn reset_page(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.page_size().is_none() {
+            return;
+        }
+
+        self.page_loaded = false;
+        self.set_page(0, window, cx);
+    }
+
+    /// Load the first page on the first render in the paged mode.
+    pub(super) fn load_page_if_need(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.page_size().is_none() || self.page_loaded {
+            return;
+        }
+
+        self.page_loaded = true;
+        self._load_page_task = cx.spawn_in(window, async move |view, window| {
+            _ = view.update_in(window, |view, window, cx| {
+                view.page_loaded = false;
+                let page = view.page;
+                view.set_page(page, window, cx);
+            });
+        });
+    }
+
+    /// Render the bar of the pagination below the table, with the range of the rows in the page.
+    pub(super) fn render_pagination(
+        &self,
+        page_size: usize,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let total_rows_count = self.delegate.total_rows_count(cx);
+        let start = (self.page * page_size).min(total_rows_count);
+        let end = (start + page_size).min(total_rows_count);
+
+        h_flex()
+            .w_full()
+            .flex_shrink_0()
+            .justify_between()
+            .gap_2()
+            .px_2()
+            .py_1()
+            .border_t_1()
+            .border_color(cx.theme().border)
+            .text_sm()
+            .text_color(cx.theme().muted_foreground)
+            .child(format!(
+                "{}-{} of {}",
+                if end > start { start + 1 } else { start },
+                end,
+                total_rows_count
+            ))
+            .child(
+                Pagination::new("table-pagination")
+                    .page(self.page)
+                    .total_pages(self.pages_count(cx))
+                    .small()
+                    .disabled(self.delegate.loading(cx))
+                    .on_click(cx.listener(|this, page, window, cx| {
+                        this.set_page(*page, window, cx);
+                    })),
+            )
+    }
+}
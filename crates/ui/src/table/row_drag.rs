@@ -0,0 +1,280 @@
+use gpui::{
+    div, prelude::FluentBuilder as _, px, AppContext as _, Context, DragMoveEvent, EntityId,
+    InteractiveElement as _, IntoElement, ParentElement as _, Pixels, Point, Render, SharedString,
+    StatefulInteractiveElement as _, Styled as _, Window,
+};
+
+use crate::{
+    table::{TableDelegate, TableEvent, TableState},
+    ActiveTheme as _, Icon, IconName, Sizable as _,
+};
+
+/// The row in dragging to move, see [`TableState::row_movable`].
+#[derive(Clone)]
+pub(crate) struct DragRow {
+    pub(crate) entity_id: EntityId,
+    pub(crate) row_ix: usize,
+    pub(crate) label: SharedString,
+}
+
+impl Render for DragRow {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .px_4()
+            .py_1()
+            .bg(cx.theme().table)
+            .text_color(cx.theme().foreground)
+            .opacity(0.9)
+            .border_1()
+            .border_color(cx.theme().border)
+            .shadow_md()
+            .min_w(px(100.))
+            .max_w(px(450.))
+            .overflow_hidden()
+            .whitespace_nowrap()
+            .text_ellipsis()
+            .child(self.label.clone())
+    }
+}
+
+/// Returns the new index of the row at `ix` after the row at `from` is moved to `to`.
+fn moved_row_ix(ix: usize, from: usize, to: usize) -> usize {
+    if ix == from {
+        to
+    } else if from < ix && ix <= to {
+        ix - 1
+    } else if to <= ix && ix < from {
+        ix + 1
+    } else {
+        ix
+    }
+}
+
+impl<D> TableState<D>
+where
+    D: TableDelegate,
+{
+    /// Move the row at the `row_ix` to the `to_ix` by the [`TableDelegate::move_row`], the
+    /// selected and expanded rows are moved with it.
+    ///
+    /// Do nothing if the [`TableDelegate::can_move_row`] returns false.
+    pub fn move_row(
+        &mut self,
+        row_ix: usize,
+        to_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let rows_count = self.delegate.rows_count(cx);
+        if row_ix == to_ix
+            || row_ix >= rows_count
+            || to_ix >= rows_count
+            || !self.delegate.can_move_row(row_ix, cx)
+        {
+            return;
+        }
+
+        if self.editing_cell.is_some() {
+            self.commit_editing(window, cx);
+        }
+
+        self.delegate.move_row(row_ix, to_ix, window, cx);
+        self.selected_row = self.selected_row.map(|ix| moved_row_ix(ix, row_ix, to_ix));
+        self.right_clicked_row = None;
+        self.expanded_rows = std::mem::take(&mut self.expanded_rows)
+            .into_iter()
+            .map(|(ix, size)| (moved_row_ix(ix, row_ix, to_ix), size))
+            .collect();
+
+        cx.emit(TableEvent::MoveRow(row_ix, to_ix));
+        self.notify(cx);
+    }
+
+    /// Returns true if the row can be dragged, see [`TableDelegate::can_move_row`].
+    pub(super) fn is_row_draggable(&self, row_ix: usize, cx: &Context<Self>) -> bool {
+        self.row_movable && self.delegate.can_move_row(row_ix, cx)
+    }
+
+    /// Returns the [`DragRow`] of the row, labeled by the text of the first visible cell.
+    pub(super) fn drag_row(&self, row_ix: usize, cx: &Context<Self>) -> DragRow {
+        let label = self
+            .col_groups
+            .iter()
+            .position(|col_group| !col_group.hidden)
+            .and_then(|col_ix| self.delegate.cell_text(row_ix, col_ix, cx))
+            .unwrap_or_else(|| format!("#{}", row_ix + 1).into());
+
+        DragRow {
+            entity_id: cx.entity_id(),
+            row_ix,
+            label,
+        }
+    }
+
+    /// Render the grip to drag the row in the first visible cell, if the
+    /// [`TableState::row_drag_handle`] is enabled.
+    pub(super) fn render_drag_handle(
+        &self,
+        row_ix: usize,
+        cx: &mut Context<Self>,
+    ) -> Option<impl IntoElement> {
+        if !self.row_drag_handle || !self.is_row_draggable(row_ix, cx) {
+            return None;
+        }
+
+        Some(
+            div()
+                .id(("drag-row", row_ix))
+                .flex_shrink_0()
+                .cursor_grab()
+                .child(
+                    Icon::new(IconName::EllipsisVertical)
+                        .xsmall()
+                        .text_color(cx.theme().muted_foreground),
+                )
+                .on_drag(self.drag_row(row_ix, cx), |drag, _, _, cx| {
+                    cx.stop_propagation();
+                    cx.new(|_| drag.clone())
+                }),
+        )
+    }
+
+    /// Render the line to indicate the position to drop the dragging row, before or after the row.
+    pub(super) fn render_drop_indicator(
+        &self,
+        row_ix: usize,
+        rows_count: usize,
+        cx: &mut Context<Self>,
+    ) -> Option<impl IntoElement> {
+        let drop_ix = self.drop_row?;
+        let is_last_row = row_ix + 1 == rows_count;
+        if drop_ix != row_ix && !(is_last_row && drop_ix == rows_count) {
+            return None;
+        }
+
+        Some(
+            div()
+                .absolute()
+                .left_0()
+                .right_0()
+                .h(px(2.))
+                .bg(cx.theme().drag_border)
+                .map(|this| {
+                    if drop_ix == row_ix {
+                        this.top(px(-1.))
+                    } else {
+                        this.bottom(px(-1.))
+                    }
+                }),
+        )
+    }
+
+    /// Update the position to drop the dragging row by the mouse position over the row.
+    pub(super) fn on_drag_row_over(
+        &mut self,
+        e: &DragMoveEvent<DragRow>,
+        row_ix: usize,
+        cx: &mut Context<Self>,
+    ) {
+        let drag = e.drag(cx);
+        if drag.entity_id != cx.entity_id() || !e.bounds.contains(&e.event.position) {
+            return;
+        }
+
+        let drop_ix = if e.event.position.y < e.bounds.center().y {
+            row_ix
+        } else {
+            row_ix + 1
+        };
+        // Dropping around the dragging row does nothing.
+        let drop_row = (drop_ix != drag.row_ix && drop_ix != drag.row_ix + 1).then_some(drop_ix);
+        if self.drop_row != drop_row {
+            self.drop_row = drop_row;
+            cx.notify();
+        }
+    }
+
+    /// Scroll the rows if the dragging row is near the top or bottom edge.
+    pub(super) fn on_drag_row_move(
+        &mut self,
+        e: &DragMoveEvent<DragRow>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if e.drag(cx).entity_id != cx.entity_id() {
+            return;
+        }
+
+        if self.scroll_by_row_dragging(e.event.position) {
+            cx.notify();
+        }
+    }
+
+    /// Returns true if the rows are scrolled, faster when closer to the edge.
+    fn scroll_by_row_dragging(&mut self, position: Point<Pixels>) -> bool {
+        let edge = self.row_height();
+        let scroll_handle = self.vertical_scroll_handle.0.borrow();
+        let bounds = scroll_handle.base_handle.bounds();
+        let delta = if position.y < bounds.top() + edge {
+            edge - (position.y - bounds.top()).max(px(0.))
+        } else if position.y > bounds.bottom() - edge {
+            -(edge - (bounds.bottom() - position.y).max(px(0.)))
+        } else {
+            return false;
+        };
+
+        let mut offset = scroll_handle.base_handle.offset();
+        let max_offset = scroll_handle.base_handle.max_offset();
+        let y = (offset.y + delta / 4.).min(px(0.)).max(-max_offset.height);
+        if y == offset.y {
+            return false;
+        }
+
+        offset.y = y;
+        scroll_handle.base_handle.set_offset(offset);
+        true
+    }
+
+    /// Move the dragging row to the position of the [`Self::render_drop_indicator`].
+    pub(super) fn on_drop_row(
+        &mut self,
+        drag: &DragRow,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(drop_ix) = self.drop_row.take() else {
+            return;
+        };
+        if drag.entity_id != cx.entity_id() {
+            return;
+        }
+
+        let to_ix = if drop_ix > drag.row_ix {
+            drop_ix - 1
+        } else {
+            drop_ix
+        };
+        self.move_row(drag.row_ix, to_ix, window, cx);
+        cx.notify();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::moved_row_ix;
+
+    #[test]
+    fn test_moved_row_ix() {
+        // Move down
+        assert_eq!(moved_row_ix(1, 1, 3), 3);
+        assert_eq!(moved_row_ix(2, 1, 3), 1);
+        assert_eq!(moved_row_ix(3, 1, 3), 2);
+        assert_eq!(moved_row_ix(0, 1, 3), 0);
+        assert_eq!(moved_row_ix(4, 1, 3), 4);
+        // Move up
+        assert_eq!(moved_row_ix(3, 3, 1), 1);
+        assert_eq!(moved_row_ix(1, 3, 1), 2);
+        assert_eq!(moved_row_ix(2, 3, 1), 3);
+        assert_eq!(moved_row_ix(0, 3, 1), 0);
+    }
+}
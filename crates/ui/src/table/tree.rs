@@ -0,0 +1,187 @@
+use std::ops::Range;
+
+use gpui::{
+    div, prelude::FluentBuilder as _, px, App, Context, IntoElement, ParentElement as _, Pixels,
+    Styled as _, Window,
+};
+
+use crate::{
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    indicator::Indicator,
+    table::{TableDelegate, TableEvent, TableState},
+    ActiveTheme as _, IconName, Sizable as _,
+};
+
+/// The width of the indent of each level, the same as the chevron.
+const INDENT: Pixels = px(20.);
+
+impl<D> TableState<D>
+where
+    D: TableDelegate,
+{
+    /// Returns true if the row is expanded to show the children in the tree mode.
+    pub fn is_tree_expanded(&self, row_ix: usize, cx: &App) -> bool {
+        self.delegate
+            .row_id(row_ix, cx)
+            .is_some_and(|row_id| self.tree_expanded.contains(&row_id))
+    }
+
+    /// Returns true if the children of the row are loading, see [`TableDelegate::row_children`].
+    pub fn is_tree_loading(&self, row_ix: usize, cx: &App) -> bool {
+        self.delegate
+            .row_id(row_ix, cx)
+            .is_some_and(|row_id| self.tree_loading.contains_key(&row_id))
+    }
+
+    /// Expand or collapse the children of the row in the tree mode, and emit the
+    /// [`TableEvent::TreeExpanded`].
+    ///
+    /// The children are inserted by the [`TableDelegate::row_children`] and removed by the
+    /// [`TableDelegate::remove_row_children`], the expanded descendants are collapsed with it.
+    pub fn set_tree_expanded(
+        &mut self,
+        row_ix: usize,
+        expanded: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if row_ix >= self.delegate.rows_count(cx) || !self.delegate.has_row_children(row_ix, cx) {
+            return;
+        }
+        let Some(row_id) = self.delegate.row_id(row_ix, cx) else {
+            return;
+        };
+        if expanded == self.tree_expanded.contains(&row_id) {
+            return;
+        }
+
+        if expanded {
+            self.tree_expanded.insert(row_id.clone());
+            match self.delegate.row_children(&row_id, window, cx) {
+                Some(task) => {
+                    let loading_row_id = row_id.clone();
+                    let task = cx.spawn(async move |this, cx| {
+                        task.await;
+                        _ = this.update(cx, |this, cx| {
+                            this.tree_loading.remove(&loading_row_id);
+                            if let Some(row_ix) = this.row_ix(&loading_row_id, cx) {
+                                this.tree_children_inserted(row_ix, cx);
+                            }
+                        });
+                    });
+                    self.tree_loading.insert(row_id, task);
+                }
+                None => self.tree_children_inserted(row_ix, cx),
+            }
+        } else {
+            let children = self.tree_children_range(row_ix, cx);
+            for ix in children.clone() {
+                if let Some(row_id) = self.delegate.row_id(ix, cx) {
+                    self.tree_expanded.remove(&row_id);
+                    self.tree_loading.remove(&row_id);
+                }
+            }
+            self.tree_expanded.remove(&row_id);
+            self.tree_loading.remove(&row_id);
+            self.delegate
+                .remove_row_children(&row_id, children.clone(), window, cx);
+            self.rows_changed(children, 0, cx);
+        }
+
+        cx.emit(TableEvent::TreeExpanded {
+            row: row_ix,
+            expanded,
+        });
+        self.notify(cx);
+    }
+
+    /// Toggle the row between expanded and collapsed, see [`Self::set_tree_expanded`].
+    pub fn toggle_tree_expanded(
+        &mut self,
+        row_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let expanded = !self.is_tree_expanded(row_ix, cx);
+        self.set_tree_expanded(row_ix, expanded, window, cx);
+    }
+
+    /// Returns the range of the descendants of the row, the rows after it with a greater depth.
+    fn tree_children_range(&self, row_ix: usize, cx: &App) -> Range<usize> {
+        let depth = self.delegate.row_depth(row_ix, cx);
+        let end = (row_ix + 1..self.delegate.rows_count(cx))
+            .find(|ix| self.delegate.row_depth(*ix, cx) <= depth)
+            .unwrap_or(self.delegate.rows_count(cx));
+        row_ix + 1..end
+    }
+
+    /// Update the states of the rows after the children are inserted after the row.
+    fn tree_children_inserted(&mut self, row_ix: usize, cx: &mut Context<Self>) {
+        let children = self.tree_children_range(row_ix, cx);
+        self.rows_changed(children.start..children.start, children.len(), cx);
+    }
+
+    /// Render the indent guides and the chevron of the row in the first visible cell,
+    /// if the [`TableState::tree`] is enabled.
+    pub(super) fn render_tree_toggle(
+        &self,
+        row_ix: usize,
+        cx: &mut Context<Self>,
+    ) -> Option<impl IntoElement> {
+        if !self.tree {
+            return None;
+        }
+
+        let depth = self.delegate.row_depth(row_ix, cx);
+        let has_children = self.delegate.has_row_children(row_ix, cx);
+        let expanded = self.is_tree_expanded(row_ix, cx);
+        let loading = self.is_tree_loading(row_ix, cx);
+
+        Some(
+            h_flex()
+                .flex_shrink_0()
+                .h_full()
+                .children((0..depth).map(|_| {
+                    div().flex_shrink_0().w(INDENT).h_full().child(
+                        div()
+                            .h_full()
+                            .ml(INDENT / 2.)
+                            .border_l_1()
+                            .border_color(cx.theme().border),
+                    )
+                }))
+                .map(|this| {
+                    if loading {
+                        this.child(
+                            h_flex()
+                                .flex_shrink_0()
+                                .size(INDENT)
+                                .justify_center()
+                                .child(
+                                    Indicator::new().xsmall().color(cx.theme().muted_foreground),
+                                ),
+                        )
+                    } else if has_children {
+                        this.child(
+                            Button::new(("tree-toggle", row_ix))
+                                .ghost()
+                                .xsmall()
+                                .flex_shrink_0()
+                                .icon(if expanded {
+                                    IconName::ChevronDown
+                                } else {
+                                    IconName::ChevronRight
+                                })
+                                .on_click(cx.listener(move |this, _, window, cx| {
+                                    cx.stop_propagation();
+                                    this.toggle_tree_expanded(row_ix, window, cx);
+                                })),
+                        )
+                    } else {
+                        this.child(div().flex_shrink_0().w(INDENT))
+                    }
+                }),
+        )
+    }
+}
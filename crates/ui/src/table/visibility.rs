@@ -0,0 +1,83 @@
+use gpui::{Context, Window};
+
+use crate::{
+    menu::{PopupMenu, PopupMenuItem},
+    table::{TableDelegate, TableEvent, TableState},
+};
+
+impl<D> TableState<D>
+where
+    D: TableDelegate,
+{
+    /// Returns true if the column is not hidden by the user, see [`Self::set_col_visible`].
+    ///
+    /// The column may still be hidden because the table is too narrow, see [`Self::hidden_cols`].
+    pub fn col_visible(&self, col_ix: usize) -> bool {
+        self.col_groups
+            .get(col_ix)
+            .is_some_and(|col_group| col_group.visible)
+    }
+
+    /// Show or hide the column, the [`TableDelegate::set_col_visible`] is called to keep
+    /// the visibility on refresh.
+    ///
+    /// The last visible column can't be hidden.
+    pub fn set_col_visible(
+        &mut self,
+        col_ix: usize,
+        visible: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let visible_count = self
+            .col_groups
+            .iter()
+            .filter(|col_group| col_group.visible)
+            .count();
+        let Some(col_group) = self.col_groups.get_mut(col_ix) else {
+            return;
+        };
+        if col_group.visible == visible || (!visible && visible_count <= 1) {
+            return;
+        }
+
+        col_group.visible = visible;
+        if !visible {
+            if self.selected_col == Some(col_ix) {
+                self.selected_col = None;
+            }
+            if self.active_col == Some(col_ix) {
+                self.active_col = None;
+            }
+        }
+        self.delegate.set_col_visible(col_ix, visible, window, cx);
+        self.update_hidden_cols();
+
+        cx.emit(TableEvent::ColumnVisibilityChanged {
+            col: col_ix,
+            visible,
+        });
+        self.notify(cx);
+    }
+
+    /// Returns the context menu of the header, with a check item for each column to show or hide it.
+    pub(super) fn col_visibility_menu(&self, menu: PopupMenu, cx: &mut Context<Self>) -> PopupMenu {
+        let view = cx.entity();
+        self.col_groups
+            .iter()
+            .enumerate()
+            .fold(menu, |menu, (col_ix, col_group)| {
+                let view = view.clone();
+                let visible = col_group.visible;
+                menu.item(
+                    PopupMenuItem::new(col_group.column.name.clone())
+                        .checked(visible)
+                        .on_click(move |_, window, cx| {
+                            view.update(cx, |table, cx| {
+                                table.set_col_visible(col_ix, !visible, window, cx);
+                            })
+                        }),
+                )
+            })
+    }
+}
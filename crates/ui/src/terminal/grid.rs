@@ -0,0 +1,545 @@
+use std::collections::VecDeque;
+
+use vte::{Params, Perform};
+
+use crate::log_view::AnsiStyle;
+
+/// A character cell in the terminal grid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Cell {
+    pub c: char,
+    pub style: AnsiStyle,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            c: ' ',
+            style: AnsiStyle::default(),
+        }
+    }
+}
+
+/// A point in the grid, the `line` includes the scrollback lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub(crate) struct GridPoint {
+    pub line: usize,
+    pub col: usize,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Cursor {
+    row: usize,
+    col: usize,
+    style: AnsiStyle,
+}
+
+/// The screen and scrollback of a terminal, implementing the VT100 / xterm control sequences.
+pub(crate) struct Grid {
+    cols: usize,
+    rows: usize,
+    lines: Vec<Vec<Cell>>,
+    scrollback: VecDeque<Vec<Cell>>,
+    max_scrollback: usize,
+    cursor: Cursor,
+    saved_cursor: Option<Cursor>,
+    /// The main screen lines while the alternate screen is active.
+    main_screen: Option<Vec<Vec<Cell>>>,
+    /// The scroll region as `top..bottom` rows.
+    scroll_top: usize,
+    scroll_bottom: usize,
+    /// The cursor is at the last column and the next char wraps to a new line.
+    wrap_pending: bool,
+    pub cursor_visible: bool,
+    pub title: Option<String>,
+    /// Bytes to write back to the PTY, e.g.: the device status report.
+    pub response: Vec<u8>,
+}
+
+impl Grid {
+    pub fn new(cols: usize, rows: usize, max_scrollback: usize) -> Self {
+        let cols = cols.max(1);
+        let rows = rows.max(1);
+        Self {
+            cols,
+            rows,
+            lines: vec![vec![Cell::default(); cols]; rows],
+            scrollback: VecDeque::new(),
+            max_scrollback,
+            cursor: Cursor::default(),
+            saved_cursor: None,
+            main_screen: None,
+            scroll_top: 0,
+            scroll_bottom: rows,
+            wrap_pending: false,
+            cursor_visible: true,
+            title: None,
+            response: vec![],
+        }
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn scrollback_len(&self) -> usize {
+        self.scrollback.len()
+    }
+
+    /// Returns the cursor position as `(row, col)` on the screen.
+    pub fn cursor(&self) -> (usize, usize) {
+        (self.cursor.row, self.cursor.col)
+    }
+
+    /// Returns the line by the index including the scrollback lines.
+    pub fn line(&self, line: usize) -> Option<&[Cell]> {
+        match line.checked_sub(self.scrollback.len()) {
+            Some(row) => self.lines.get(row).map(|l| l.as_slice()),
+            None => self.scrollback.get(line).map(|l| l.as_slice()),
+        }
+    }
+
+    /// Returns the text between two points (inclusive), trailing spaces of each line are trimmed.
+    pub fn text_between(&self, start: GridPoint, end: GridPoint) -> String {
+        let (start, end) = if start <= end {
+            (start, end)
+        } else {
+            (end, start)
+        };
+
+        let mut lines = vec![];
+        for ix in start.line..=end.line {
+            let Some(line) = self.line(ix) else {
+                break;
+            };
+            let from = if ix == start.line { start.col } else { 0 };
+            let to = if ix == end.line {
+                (end.col + 1).min(line.len())
+            } else {
+                line.len()
+            };
+            let text: String = line
+                .get(from..to)
+                .unwrap_or_default()
+                .iter()
+                .map(|cell| cell.c)
+                .collect();
+            lines.push(text.trim_end().to_string());
+        }
+        lines.join("\n")
+    }
+
+    pub fn resize(&mut self, cols: usize, rows: usize) {
+        let cols = cols.max(1);
+        let rows = rows.max(1);
+        if cols == self.cols && rows == self.rows {
+            return;
+        }
+
+        for line in self.lines.iter_mut() {
+            line.resize(cols, Cell::default());
+        }
+        while self.lines.len() > rows {
+            if self.cursor.row > 0 {
+                let line = self.lines.remove(0);
+                self.push_scrollback(line);
+                self.cursor.row -= 1;
+            } else {
+                self.lines.pop();
+            }
+        }
+        while self.lines.len() < rows {
+            self.lines.push(vec![Cell::default(); cols]);
+        }
+
+        self.cols = cols;
+        self.rows = rows;
+        self.scroll_top = 0;
+        self.scroll_bottom = rows;
+        self.cursor.row = self.cursor.row.min(rows - 1);
+        self.cursor.col = self.cursor.col.min(cols - 1);
+        self.wrap_pending = false;
+    }
+
+    fn push_scrollback(&mut self, line: Vec<Cell>) {
+        if self.main_screen.is_some() || self.max_scrollback == 0 {
+            return;
+        }
+        if self.scrollback.len() == self.max_scrollback {
+            self.scrollback.pop_front();
+        }
+        self.scrollback.push_back(line);
+    }
+
+    /// An erased cell keeps the current background color.
+    fn blank_cell(&self) -> Cell {
+        Cell {
+            c: ' ',
+            style: AnsiStyle {
+                bg: self.cursor.style.bg,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn blank_line(&self) -> Vec<Cell> {
+        vec![self.blank_cell(); self.cols]
+    }
+
+    fn scroll_up(&mut self, count: usize) {
+        for _ in 0..count.min(self.scroll_bottom - self.scroll_top) {
+            let line = self.lines.remove(self.scroll_top);
+            if self.scroll_top == 0 {
+                self.push_scrollback(line);
+            }
+            self.lines.insert(self.scroll_bottom - 1, self.blank_line());
+        }
+    }
+
+    fn scroll_down(&mut self, count: usize) {
+        for _ in 0..count.min(self.scroll_bottom - self.scroll_top) {
+            self.lines.remove(self.scroll_bottom - 1);
+            self.lines.insert(self.scroll_top, self.blank_line());
+        }
+    }
+
+    fn linefeed(&mut self) {
+        self.wrap_pending = false;
+        if self.cursor.row + 1 == self.scroll_bottom {
+            self.scroll_up(1);
+        } else if self.cursor.row + 1 < self.rows {
+            self.cursor.row += 1;
+        }
+    }
+
+    fn reverse_index(&mut self) {
+        self.wrap_pending = false;
+        if self.cursor.row == self.scroll_top {
+            self.scroll_down(1);
+        } else {
+            self.cursor.row = self.cursor.row.saturating_sub(1);
+        }
+    }
+
+    fn move_to(&mut self, row: usize, col: usize) {
+        self.cursor.row = row.min(self.rows - 1);
+        self.cursor.col = col.min(self.cols - 1);
+        self.wrap_pending = false;
+    }
+
+    fn erase_cells(&mut self, row: usize, cols: std::ops::Range<usize>) {
+        let blank = self.blank_cell();
+        let line = &mut self.lines[row];
+        let end = cols.end.min(line.len());
+        for cell in line[cols.start.min(end)..end].iter_mut() {
+            *cell = blank;
+        }
+    }
+
+    fn set_alternate_screen(&mut self, enabled: bool) {
+        if enabled && self.main_screen.is_none() {
+            let blank = vec![vec![Cell::default(); self.cols]; self.rows];
+            self.main_screen = Some(std::mem::replace(&mut self.lines, blank));
+            self.saved_cursor = Some(self.cursor);
+        } else if !enabled {
+            if let Some(mut lines) = self.main_screen.take() {
+                lines.resize(self.rows, vec![Cell::default(); self.cols]);
+                for line in lines.iter_mut() {
+                    line.resize(self.cols, Cell::default());
+                }
+                self.lines = lines;
+                if let Some(cursor) = self.saved_cursor.take() {
+                    self.cursor = cursor;
+                    self.move_to(cursor.row, cursor.col);
+                }
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.cols, self.rows, self.max_scrollback);
+    }
+}
+
+fn arg(params: &Params, ix: usize, default: usize) -> usize {
+    match params.iter().nth(ix).and_then(|p| p.first()) {
+        Some(0) | None => default,
+        Some(v) => *v as usize,
+    }
+}
+
+impl Perform for Grid {
+    fn print(&mut self, c: char) {
+        if self.wrap_pending {
+            self.cursor.col = 0;
+            self.linefeed();
+        }
+
+        let (row, col) = (self.cursor.row, self.cursor.col);
+        self.lines[row][col] = Cell {
+            c,
+            style: self.cursor.style,
+        };
+
+        if col + 1 == self.cols {
+            self.wrap_pending = true;
+        } else {
+            self.cursor.col += 1;
+        }
+    }
+
+    fn execute(&mut self, byte: u8) {
+        match byte {
+            // BS
+            0x08 => {
+                self.cursor.col = self.cursor.col.saturating_sub(1);
+                self.wrap_pending = false;
+            }
+            // HT
+            0x09 => {
+                let col = (self.cursor.col / 8 + 1) * 8;
+                self.cursor.col = col.min(self.cols - 1);
+            }
+            // LF, VT, FF
+            0x0a..=0x0c => self.linefeed(),
+            // CR
+            0x0d => {
+                self.cursor.col = 0;
+                self.wrap_pending = false;
+            }
+            _ => {}
+        }
+    }
+
+    fn csi_dispatch(&mut self, params: &Params, intermediates: &[u8], ignore: bool, action: char) {
+        if ignore {
+            return;
+        }
+
+        let private = intermediates.first() == Some(&b'?');
+        let (row, col) = (self.cursor.row, self.cursor.col);
+        match (action, private) {
+            ('A', false) => self.move_to(row.saturating_sub(arg(params, 0, 1)), col),
+            ('B', false) | ('e', false) => self.move_to(row + arg(params, 0, 1), col),
+            ('C', false) | ('a', false) => self.move_to(row, col + arg(params, 0, 1)),
+            ('D', false) => self.move_to(row, col.saturating_sub(arg(params, 0, 1))),
+            ('E', false) => self.move_to(row + arg(params, 0, 1), 0),
+            ('F', false) => self.move_to(row.saturating_sub(arg(params, 0, 1)), 0),
+            ('G', false) | ('`', false) => self.move_to(row, arg(params, 0, 1) - 1),
+            ('d', false) => self.move_to(arg(params, 0, 1) - 1, col),
+            ('H', false) | ('f', false) => {
+                self.move_to(arg(params, 0, 1) - 1, arg(params, 1, 1) - 1)
+            }
+            ('J', false) => match arg(params, 0, 0) {
+                0 => {
+                    self.erase_cells(row, col..self.cols);
+                    for row in row + 1..self.rows {
+                        self.erase_cells(row, 0..self.cols);
+                    }
+                }
+                1 => {
+                    for row in 0..row {
+                        self.erase_cells(row, 0..self.cols);
+                    }
+                    self.erase_cells(row, 0..col + 1);
+                }
+                2 => {
+                    for row in 0..self.rows {
+                        self.erase_cells(row, 0..self.cols);
+                    }
+                }
+                3 => self.scrollback.clear(),
+                _ => {}
+            },
+            ('K', false) => match arg(params, 0, 0) {
+                0 => self.erase_cells(row, col..self.cols),
+                1 => self.erase_cells(row, 0..col + 1),
+                2 => self.erase_cells(row, 0..self.cols),
+                _ => {}
+            },
+            ('X', false) => self.erase_cells(row, col..col + arg(params, 0, 1)),
+            ('P', false) => {
+                let count = arg(params, 0, 1).min(self.cols - col);
+                let blank = self.blank_cell();
+                let line = &mut self.lines[row];
+                line.drain(col..col + count);
+                line.extend(std::iter::repeat_n(blank, count));
+            }
+            ('@', false) => {
+                let count = arg(params, 0, 1).min(self.cols - col);
+                let blank = self.blank_cell();
+                let line = &mut self.lines[row];
+                line.truncate(self.cols - count);
+                for _ in 0..count {
+                    line.insert(col, blank);
+                }
+            }
+            ('L', false) | ('M', false) => {
+                if row < self.scroll_top || row >= self.scroll_bottom {
+                    return;
+                }
+                let top = self.scroll_top;
+                self.scroll_top = row;
+                if action == 'L' {
+                    self.scroll_down(arg(params, 0, 1));
+                } else {
+                    self.scroll_up_in_place(arg(params, 0, 1));
+                }
+                self.scroll_top = top;
+                self.cursor.col = 0;
+            }
+            ('S', false) => self.scroll_up(arg(params, 0, 1)),
+            ('T', false) => self.scroll_down(arg(params, 0, 1)),
+            ('m', false) => {
+                let params = params.iter().flat_map(|p| p.iter().copied());
+                self.cursor.style.apply_sgr_params(params);
+            }
+            ('r', false) => {
+                let top = arg(params, 0, 1) - 1;
+                let bottom = arg(params, 1, self.rows).min(self.rows);
+                if top < bottom {
+                    self.scroll_top = top;
+                    self.scroll_bottom = bottom;
+                    self.move_to(0, 0);
+                }
+            }
+            ('s', false) => self.saved_cursor = Some(self.cursor),
+            ('u', false) => {
+                if let Some(cursor) = self.saved_cursor {
+                    self.cursor.style = cursor.style;
+                    self.move_to(cursor.row, cursor.col);
+                }
+            }
+            ('n', false) => {
+                if arg(params, 0, 0) == 6 {
+                    let report = format!("\x1b[{};{}R", row + 1, col + 1);
+                    self.response.extend_from_slice(report.as_bytes());
+                }
+            }
+            ('h', true) | ('l', true) => {
+                let enabled = action == 'h';
+                for param in params.iter() {
+                    match param.first() {
+                        Some(25) => self.cursor_visible = enabled,
+                        Some(47) | Some(1047) | Some(1049) => self.set_alternate_screen(enabled),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn esc_dispatch(&mut self, intermediates: &[u8], ignore: bool, byte: u8) {
+        if ignore || !intermediates.is_empty() {
+            return;
+        }
+
+        match byte {
+            b'7' => self.saved_cursor = Some(self.cursor),
+            b'8' => {
+                if let Some(cursor) = self.saved_cursor {
+                    self.cursor.style = cursor.style;
+                    self.move_to(cursor.row, cursor.col);
+                }
+            }
+            b'D' => self.linefeed(),
+            b'E' => {
+                self.cursor.col = 0;
+                self.linefeed();
+            }
+            b'M' => self.reverse_index(),
+            b'c' => self.reset(),
+            _ => {}
+        }
+    }
+
+    fn osc_dispatch(&mut self, params: &[&[u8]], _: bool) {
+        if let [b"0" | b"2", title, ..] = params {
+            self.title = Some(String::from_utf8_lossy(title).into_owned());
+        }
+    }
+}
+
+impl Grid {
+    /// Delete lines from the top of the scroll region without pushing them to the scrollback.
+    fn scroll_up_in_place(&mut self, count: usize) {
+        for _ in 0..count.min(self.scroll_bottom - self.scroll_top) {
+            self.lines.remove(self.scroll_top);
+            self.lines.insert(self.scroll_bottom - 1, self.blank_line());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Grid, GridPoint};
+
+    fn feed(grid: &mut Grid, bytes: &str) {
+        let mut parser = vte::Parser::new();
+        parser.advance(grid, bytes.as_bytes());
+    }
+
+    fn screen(grid: &Grid) -> Vec<String> {
+        (0..grid.rows())
+            .map(|row| {
+                let line = grid.line(grid.scrollback_len() + row).unwrap();
+                line.iter()
+                    .map(|c| c.c)
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_print_and_wrap() {
+        let mut grid = Grid::new(5, 3, 100);
+        feed(&mut grid, "hello world\r\nok");
+        assert_eq!(grid.scrollback_len(), 1);
+        assert_eq!(screen(&grid), vec![" worl", "d", "ok"]);
+        feed(&mut grid, "\r\nnext");
+        assert_eq!(grid.scrollback_len(), 2);
+        assert_eq!(screen(&grid), vec!["d", "ok", "next"]);
+        assert_eq!(grid.cursor(), (2, 4));
+    }
+
+    #[test]
+    fn test_cursor_and_erase() {
+        let mut grid = Grid::new(10, 3, 100);
+        feed(&mut grid, "aaaaaaaaaa\r\nbbbbbbbbbb\r\ncccccccccc");
+        feed(&mut grid, "\x1b[2;3H\x1b[K");
+        assert_eq!(screen(&grid), vec!["aaaaaaaaaa", "bb", "cccccccccc"]);
+        feed(&mut grid, "\x1b[1;1H\x1b[2P");
+        assert_eq!(screen(&grid)[0], "aaaaaaaa");
+        feed(&mut grid, "\x1b[2J");
+        assert_eq!(screen(&grid), vec!["", "", ""]);
+    }
+
+    #[test]
+    fn test_sgr_and_alternate_screen() {
+        let mut grid = Grid::new(10, 2, 100);
+        feed(&mut grid, "\x1b[31mred\x1b[0m");
+        let line = grid.line(0).unwrap();
+        assert!(line[0].style.fg.is_some());
+        assert!(line[3].style.fg.is_none());
+
+        feed(&mut grid, "\x1b[?1049hvim\x1b[?1049l");
+        assert_eq!(screen(&grid), vec!["red", ""]);
+        assert_eq!(grid.cursor(), (0, 3));
+    }
+
+    #[test]
+    fn test_text_between_and_title() {
+        let mut grid = Grid::new(10, 2, 100);
+        feed(&mut grid, "\x1b]0;shell\x07foo bar\r\nbaz");
+        assert_eq!(grid.title.as_deref(), Some("shell"));
+        let text = grid.text_between(GridPoint { line: 1, col: 2 }, GridPoint { line: 0, col: 4 });
+        assert_eq!(text, "bar\nbaz");
+    }
+}
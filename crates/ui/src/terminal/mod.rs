@@ -0,0 +1,596 @@
+mod grid;
+
+use std::{
+    io::{Read as _, Write},
+    ops::Range,
+};
+
+use anyhow::Result;
+use gpui::{
+    canvas, combine_highlights, div, font, prelude::FluentBuilder as _, px, App, Bounds,
+    ClipboardItem, Context, ElementId, Entity, EventEmitter, FocusHandle, HighlightStyle, Hsla,
+    InteractiveElement as _, IntoElement, KeyBinding, KeyDownEvent, Keystroke, MouseButton,
+    MouseDownEvent, MouseMoveEvent, ParentElement, Pixels, Point, Render, RenderOnce,
+    ScrollWheelEvent, SharedString, Size, StyleRefinement, Styled, StyledText, Task, Window,
+};
+use portable_pty::{native_pty_system, Child, MasterPty, PtySize};
+
+use crate::{
+    input,
+    log_view::{AnsiColor, AnsiStyle},
+    ActiveTheme, StyledExt as _,
+};
+use grid::{Cell, Grid, GridPoint};
+
+pub use portable_pty::CommandBuilder;
+
+const CONTEXT: &str = "Terminal";
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-c", input::Copy, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-shift-c", input::Copy, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-v", input::Paste, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-shift-v", input::Paste, Some(CONTEXT)),
+    ]);
+}
+
+/// Emitted by the [`TerminalState`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum TerminalEvent {
+    /// The title was changed by the `OSC 0` or `OSC 2` sequence.
+    TitleChanged(SharedString),
+    /// The process running in the terminal has exited.
+    Exited,
+}
+
+struct Pty {
+    master: Box<dyn MasterPty + Send>,
+    writer: Box<dyn Write + Send>,
+    child: Box<dyn Child + Send + Sync>,
+}
+
+impl Drop for Pty {
+    fn drop(&mut self) {
+        _ = self.child.kill();
+    }
+}
+
+/// State of the [`Terminal`].
+///
+/// Without [`TerminalState::spawn`], it can be used to display the output of a process by [`TerminalState::feed`].
+pub struct TerminalState {
+    focus_handle: FocusHandle,
+    grid: Grid,
+    parser: vte::Parser,
+    pty: Option<Pty>,
+    /// The number of lines scrolled back from the bottom.
+    display_offset: usize,
+    selection: Option<(GridPoint, GridPoint)>,
+    selecting: bool,
+    bounds: Bounds<Pixels>,
+    cell_size: Size<Pixels>,
+    font_family: SharedString,
+    font_size: Pixels,
+    ansi_colors: Option<[Hsla; 16]>,
+    _read_task: Option<Task<()>>,
+}
+
+impl EventEmitter<TerminalEvent> for TerminalState {}
+
+impl TerminalState {
+    /// Create a new terminal state with 80x24 cells, it is resized to fit the element.
+    pub fn new(_: &mut Window, cx: &mut Context<Self>) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            grid: Grid::new(80, 24, 10_000),
+            parser: vte::Parser::new(),
+            pty: None,
+            display_offset: 0,
+            selection: None,
+            selecting: false,
+            bounds: Bounds::default(),
+            cell_size: Size::default(),
+            font_family: "Menlo, Monaco, Consolas, monospace".into(),
+            font_size: px(13.),
+            ansi_colors: None,
+            _read_task: None,
+        }
+    }
+
+    /// Set the maximum number of scrollback lines, default: 10,000
+    pub fn scrollback(mut self, lines: usize) -> Self {
+        self.grid = Grid::new(self.grid.cols(), self.grid.rows(), lines);
+        self
+    }
+
+    /// Set the monospace font family.
+    pub fn font_family(mut self, font_family: impl Into<SharedString>) -> Self {
+        self.font_family = font_family.into();
+        self
+    }
+
+    /// Set the font size, default: 13px
+    pub fn font_size(mut self, font_size: impl Into<Pixels>) -> Self {
+        self.font_size = font_size.into();
+        self
+    }
+
+    /// Set the 16 standard and bright ANSI colors, default is the xterm palette.
+    pub fn ansi_colors(mut self, colors: [Hsla; 16]) -> Self {
+        self.ansi_colors = Some(colors);
+        self
+    }
+
+    /// Spawn the command in a new PTY, the output is displayed in the terminal.
+    ///
+    /// ```ignore
+    /// state.spawn(CommandBuilder::new_default_prog(), cx)?;
+    /// ```
+    pub fn spawn(&mut self, command: CommandBuilder, cx: &mut Context<Self>) -> Result<()> {
+        let pair = native_pty_system().openpty(self.pty_size())?;
+        let child = pair.slave.spawn_command(command)?;
+        drop(pair.slave);
+
+        let mut reader = pair.master.try_clone_reader()?;
+        let writer = pair.master.take_writer()?;
+        let (tx, rx) = smol::channel::unbounded::<Vec<u8>>();
+        std::thread::spawn(move || {
+            let mut buf = [0u8; 8192];
+            while let Ok(n) = reader.read(&mut buf) {
+                if n == 0 || tx.send_blocking(buf[..n].to_vec()).is_err() {
+                    break;
+                }
+            }
+        });
+
+        self._read_task = Some(cx.spawn(async move |this, cx| {
+            while let Ok(mut bytes) = rx.recv().await {
+                while let Ok(more) = rx.try_recv() {
+                    bytes.extend(more);
+                }
+                if this.update(cx, |this, cx| this.feed(&bytes, cx)).is_err() {
+                    return;
+                }
+            }
+            _ = this.update(cx, |this, cx| {
+                this.pty = None;
+                cx.emit(TerminalEvent::Exited);
+            });
+        }));
+
+        self.pty = Some(Pty {
+            master: pair.master,
+            writer,
+            child,
+        });
+        Ok(())
+    }
+
+    /// Process the output bytes, which may contain the VT100 / ANSI control sequences.
+    pub fn feed(&mut self, bytes: &[u8], cx: &mut Context<Self>) {
+        let title = self.grid.title.clone();
+        let scrollback_len = self.grid.scrollback_len();
+        self.parser.advance(&mut self.grid, bytes);
+
+        if self.display_offset > 0 {
+            // Keep the scrolled back content in place.
+            let added = self.grid.scrollback_len().saturating_sub(scrollback_len);
+            self.display_offset = (self.display_offset + added).min(self.grid.scrollback_len());
+        }
+        if !self.grid.response.is_empty() {
+            let response = std::mem::take(&mut self.grid.response);
+            self.write(&response);
+        }
+        if self.grid.title != title {
+            if let Some(title) = self.grid.title.clone() {
+                cx.emit(TerminalEvent::TitleChanged(title.into()));
+            }
+        }
+        cx.notify();
+    }
+
+    /// Write the input bytes to the PTY.
+    pub fn write(&mut self, bytes: &[u8]) {
+        if let Some(pty) = self.pty.as_mut() {
+            _ = pty.writer.write_all(bytes);
+            _ = pty.writer.flush();
+        }
+    }
+
+    /// Returns true if a process is running in the terminal.
+    pub fn is_running(&self) -> bool {
+        self.pty.is_some()
+    }
+
+    /// Returns the title set by the process, if any.
+    pub fn title(&self) -> Option<&str> {
+        self.grid.title.as_deref()
+    }
+
+    /// Returns the size of the terminal as `(cols, rows)`.
+    pub fn size(&self) -> (usize, usize) {
+        (self.grid.cols(), self.grid.rows())
+    }
+
+    /// Resize the terminal, this is done automatically to fit the element.
+    pub fn resize(&mut self, cols: usize, rows: usize, cx: &mut Context<Self>) {
+        if (cols, rows) == self.size() {
+            return;
+        }
+
+        self.grid.resize(cols, rows);
+        self.display_offset = self.display_offset.min(self.grid.scrollback_len());
+        let size = self.pty_size();
+        if let Some(pty) = self.pty.as_ref() {
+            _ = pty.master.resize(size);
+        }
+        cx.notify();
+    }
+
+    fn pty_size(&self) -> PtySize {
+        PtySize {
+            rows: self.grid.rows() as u16,
+            cols: self.grid.cols() as u16,
+            pixel_width: 0,
+            pixel_height: 0,
+        }
+    }
+
+    /// Returns the selected text, if any.
+    pub fn selected_text(&self) -> Option<String> {
+        let (start, end) = self.selection?;
+        Some(self.grid.text_between(start, end))
+    }
+
+    fn first_visible_line(&self) -> usize {
+        self.grid.scrollback_len() - self.display_offset
+    }
+
+    fn point_for_position(&self, position: Point<Pixels>) -> GridPoint {
+        let pos = position - self.bounds.origin;
+        let col = (pos.x / self.cell_size.width).max(0.) as usize;
+        let row = (pos.y / self.cell_size.height).max(0.) as usize;
+        GridPoint {
+            line: self.first_visible_line() + row.min(self.grid.rows() - 1),
+            col: col.min(self.grid.cols() - 1),
+        }
+    }
+
+    fn layout(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut Context<Self>) {
+        let text_system = window.text_system();
+        let font_id = text_system.resolve_font(&font(self.font_family.clone()));
+        let cell_width = text_system
+            .advance(font_id, self.font_size, 'm')
+            .map(|size| size.width)
+            .unwrap_or(self.font_size * 0.6);
+        self.cell_size = Size::new(cell_width, (self.font_size * 1.4).round());
+        self.bounds = bounds;
+
+        let cols = (bounds.size.width / self.cell_size.width).floor() as usize;
+        let rows = (bounds.size.height / self.cell_size.height).floor() as usize;
+        self.resize(cols.max(1), rows.max(1), cx);
+    }
+
+    fn on_key_down(&mut self, event: &KeyDownEvent, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(bytes) = keystroke_to_bytes(&event.keystroke) else {
+            return;
+        };
+
+        self.display_offset = 0;
+        self.selection = None;
+        self.write(&bytes);
+        cx.stop_propagation();
+        cx.notify();
+    }
+
+    fn on_action_copy(&mut self, _: &input::Copy, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(text) = self.selected_text() {
+            cx.write_to_clipboard(ClipboardItem::new_string(text));
+        }
+    }
+
+    fn on_action_paste(&mut self, _: &input::Paste, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(text) = cx.read_from_clipboard().and_then(|item| item.text()) {
+            self.display_offset = 0;
+            self.write(text.replace("\r\n", "\r").replace('\n', "\r").as_bytes());
+            cx.notify();
+        }
+    }
+
+    fn on_mouse_down(
+        &mut self,
+        event: &MouseDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.focus_handle.focus(window);
+        let point = self.point_for_position(event.position);
+        self.selection = Some((point, point));
+        self.selecting = true;
+        cx.notify();
+    }
+
+    fn on_mouse_move(&mut self, event: &MouseMoveEvent, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.selecting || event.pressed_button != Some(MouseButton::Left) {
+            self.selecting = false;
+            return;
+        }
+
+        let point = self.point_for_position(event.position);
+        if let Some((_, head)) = self.selection.as_mut() {
+            *head = point;
+            cx.notify();
+        }
+    }
+
+    fn on_scroll_wheel(
+        &mut self,
+        event: &ScrollWheelEvent,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let delta = event.delta.pixel_delta(self.cell_size.height);
+        let lines = (delta.y / self.cell_size.height).round() as isize;
+        let offset = (self.display_offset as isize + lines).max(0) as usize;
+        self.display_offset = offset.min(self.grid.scrollback_len());
+        cx.notify();
+    }
+
+    fn cell_highlight(&self, style: &AnsiStyle, cx: &App) -> Option<HighlightStyle> {
+        let color = |color: AnsiColor| match (color, self.ansi_colors.as_ref()) {
+            (AnsiColor::Indexed(ix @ 0..=15), Some(colors)) => colors[ix as usize],
+            _ => color.to_hsla(),
+        };
+
+        let mut highlight = style.to_highlight()?;
+        let mut fg = style.fg.map(color);
+        let mut bg = style.bg.map(color);
+        if style.inverse {
+            let default_fg = cx.theme().foreground;
+            let default_bg = cx.theme().background;
+            (fg, bg) = (
+                Some(bg.unwrap_or(default_bg)),
+                Some(fg.unwrap_or(default_fg)),
+            );
+        }
+        highlight.color = fg;
+        highlight.background_color = bg;
+        Some(highlight)
+    }
+
+    fn render_line(&self, line: usize, cells: &[Cell], cx: &App) -> StyledText {
+        let mut text = String::with_capacity(cells.len());
+        let mut highlights: Vec<(Range<usize>, HighlightStyle)> = vec![];
+        let mut selection_range: Option<Range<usize>> = None;
+        let selection = self
+            .selection
+            .map(|(a, b)| if a <= b { (a, b) } else { (b, a) });
+
+        let mut run: Option<(usize, AnsiStyle)> = None;
+        for (col, cell) in cells.iter().enumerate() {
+            let start = text.len();
+            if run.map_or(true, |(_, style)| style != cell.style) {
+                if let Some((run_start, style)) = run.take() {
+                    highlights.extend(
+                        self.cell_highlight(&style, cx)
+                            .map(|h| (run_start..start, h)),
+                    );
+                }
+                run = Some((start, cell.style));
+            }
+            text.push(cell.c);
+
+            let point = GridPoint { line, col };
+            if let Some((from, to)) = selection {
+                if from <= point && point <= to {
+                    let range = selection_range.get_or_insert(start..start);
+                    range.end = text.len();
+                }
+            }
+        }
+        if let Some((run_start, style)) = run {
+            highlights.extend(
+                self.cell_highlight(&style, cx)
+                    .map(|h| (run_start..text.len(), h)),
+            );
+        }
+
+        let selection_highlight = selection_range.map(|range| {
+            (
+                range,
+                HighlightStyle {
+                    background_color: Some(cx.theme().selection),
+                    ..Default::default()
+                },
+            )
+        });
+        let highlights = combine_highlights(highlights, selection_highlight).collect::<Vec<_>>();
+
+        StyledText::new(text).with_highlights(highlights)
+    }
+}
+
+/// Convert the keystroke to the bytes to write to the PTY.
+fn keystroke_to_bytes(keystroke: &Keystroke) -> Option<Vec<u8>> {
+    let modifiers = &keystroke.modifiers;
+    if modifiers.platform {
+        return None;
+    }
+
+    let bytes: &[u8] = match keystroke.key.as_str() {
+        "enter" => b"\r",
+        "backspace" => b"\x7f",
+        "tab" if modifiers.shift => b"\x1b[Z",
+        "tab" => b"\t",
+        "escape" => b"\x1b",
+        "up" => b"\x1b[A",
+        "down" => b"\x1b[B",
+        "right" => b"\x1b[C",
+        "left" => b"\x1b[D",
+        "home" => b"\x1b[H",
+        "end" => b"\x1b[F",
+        "delete" => b"\x1b[3~",
+        "pageup" => b"\x1b[5~",
+        "pagedown" => b"\x1b[6~",
+        "space" if modifiers.control => b"\x00",
+        key if modifiers.control && key.len() == 1 => {
+            let c = key.as_bytes()[0].to_ascii_lowercase();
+            return match c {
+                b'a'..=b'z' | b'[' | b'\\' | b']' => Some(vec![c & 0x1f]),
+                _ => None,
+            };
+        }
+        _ => {
+            let text = match keystroke.key_char.as_ref() {
+                Some(text) => text,
+                None if keystroke.key.chars().count() == 1 => &keystroke.key,
+                None => return None,
+            };
+            let mut bytes = Vec::with_capacity(text.len() + 1);
+            if modifiers.alt {
+                bytes.push(0x1b);
+            }
+            bytes.extend_from_slice(text.as_bytes());
+            return Some(bytes);
+        }
+    };
+
+    Some(bytes.to_vec())
+}
+
+impl Render for TerminalState {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let view = cx.entity();
+        let first_line = self.first_visible_line();
+        let (cursor_row, cursor_col) = self.grid.cursor();
+        let cursor_row = cursor_row + self.display_offset;
+        let show_cursor = self.grid.cursor_visible && cursor_row < self.grid.rows();
+        let focused = self.focus_handle.is_focused(window);
+
+        div()
+            .relative()
+            .size_full()
+            .overflow_hidden()
+            .bg(cx.theme().background)
+            .text_color(cx.theme().foreground)
+            .font_family(self.font_family.clone())
+            .text_size(self.font_size)
+            .line_height(self.cell_size.height)
+            .on_key_down(cx.listener(Self::on_key_down))
+            .on_mouse_down(MouseButton::Left, cx.listener(Self::on_mouse_down))
+            .on_mouse_move(cx.listener(Self::on_mouse_move))
+            .on_scroll_wheel(cx.listener(Self::on_scroll_wheel))
+            .child(
+                canvas(
+                    move |bounds, window, cx| {
+                        view.update(cx, |this, cx| this.layout(bounds, window, cx))
+                    },
+                    |_, _, _, _| {},
+                )
+                .absolute()
+                .size_full(),
+            )
+            .children((0..self.grid.rows()).filter_map(|row| {
+                let line = first_line + row;
+                let cells = self.grid.line(line)?;
+                Some(
+                    div()
+                        .h(self.cell_size.height)
+                        .whitespace_nowrap()
+                        .child(self.render_line(line, cells, cx)),
+                )
+            }))
+            .when(show_cursor, |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .left(self.cell_size.width * cursor_col as f32)
+                        .top(self.cell_size.height * cursor_row as f32)
+                        .w(self.cell_size.width)
+                        .h(self.cell_size.height)
+                        .map(|this| {
+                            if focused {
+                                this.bg(cx.theme().foreground.opacity(0.5))
+                            } else {
+                                this.border_1().border_color(cx.theme().foreground)
+                            }
+                        }),
+                )
+            })
+    }
+}
+
+/// A terminal emulator element.
+///
+/// ```ignore
+/// let state = cx.new(|cx| {
+///     let mut state = TerminalState::new(window, cx);
+///     state.spawn(CommandBuilder::new_default_prog(), cx).ok();
+///     state
+/// });
+///
+/// Terminal::new(&state)
+/// ```
+#[derive(IntoElement)]
+pub struct Terminal {
+    id: ElementId,
+    state: Entity<TerminalState>,
+    style: StyleRefinement,
+}
+
+impl Terminal {
+    /// Create a new terminal element with the given state.
+    pub fn new(state: &Entity<TerminalState>) -> Self {
+        Self {
+            id: ElementId::Name(format!("terminal-{}", state.entity_id()).into()),
+            state: state.clone(),
+            style: StyleRefinement::default(),
+        }
+    }
+}
+
+impl Styled for Terminal {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for Terminal {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let focus_handle = self.state.read(cx).focus_handle.clone();
+
+        div()
+            .id(self.id)
+            .key_context(CONTEXT)
+            .track_focus(&focus_handle)
+            .on_action(window.listener_for(&self.state, TerminalState::on_action_copy))
+            .on_action(window.listener_for(&self.state, TerminalState::on_action_paste))
+            .size_full()
+            .child(self.state)
+            .refine_style(&self.style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::Keystroke;
+
+    use super::keystroke_to_bytes;
+
+    fn bytes(source: &str) -> Option<Vec<u8>> {
+        keystroke_to_bytes(&Keystroke::parse(source).unwrap())
+    }
+
+    #[test]
+    fn test_keystroke_to_bytes() {
+        assert_eq!(bytes("enter"), Some(b"\r".to_vec()));
+        assert_eq!(bytes("up"), Some(b"\x1b[A".to_vec()));
+        assert_eq!(bytes("ctrl-c"), Some(vec![0x03]));
+        assert_eq!(bytes("a"), Some(b"a".to_vec()));
+        assert_eq!(bytes("cmd-c"), None);
+    }
+}
@@ -143,7 +143,7 @@ impl Inline {
     }
 
     /// Paint the selection background.
-    fn paint_selection(
+    pub(super) fn paint_selection(
         selection: &Selection,
         text_layout: &TextLayout,
         bounds: &Bounds<Pixels>,
@@ -0,0 +1,343 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    hash::{Hash, Hasher},
+    ops::Range,
+    rc::Rc,
+};
+
+use gpui::{
+    px, App, AvailableSpace, Bounds, Element, ElementId, Global, GlobalElementId, HighlightStyle,
+    InspectorElementId, IntoElement, LayoutId, Pixels, SharedString, Size, TextRun, TextStyle,
+    WhiteSpace, Window, WrappedLine,
+};
+use smallvec::SmallVec;
+
+/// The default number of the shaped texts to keep in the [`TextLayoutCache`].
+const DEFAULT_CAPACITY: usize = 10_000;
+
+struct CacheEntry {
+    lines: SmallVec<[WrappedLine; 1]>,
+    size: Size<Pixels>,
+    last_used: u64,
+}
+
+/// A cache of the shaped text for the long read-only content, it keeps the layout
+/// across frames, so the text is only shaped again when it scrolls out of the cache.
+///
+/// The entries are keyed by the text, the style runs, the font size and the wrap width,
+/// so a theme change (the colors of the runs) or a width change will use a new entry,
+/// and the stale entries are evicted by the least recently used order.
+pub struct TextLayoutCache {
+    entries: HashMap<u64, CacheEntry>,
+    capacity: usize,
+    tick: u64,
+}
+
+impl Global for TextLayoutCache {}
+
+impl Default for TextLayoutCache {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::default(),
+            capacity: DEFAULT_CAPACITY,
+            tick: 0,
+        }
+    }
+}
+
+impl TextLayoutCache {
+    /// Set the max number of the shaped texts to keep, default: 10000
+    pub fn set_capacity(capacity: usize, cx: &mut App) {
+        let cache = cx.default_global::<Self>();
+        cache.capacity = capacity;
+        cache.evict();
+    }
+
+    /// Return the number of the cached texts.
+    pub fn len(cx: &App) -> usize {
+        cx.try_global::<Self>()
+            .map_or(0, |cache| cache.entries.len())
+    }
+
+    /// Clear all the cached texts.
+    pub fn clear(cx: &mut App) {
+        cx.default_global::<Self>().entries.clear();
+    }
+
+    fn get(&mut self, key: u64) -> Option<(SmallVec<[WrappedLine; 1]>, Size<Pixels>)> {
+        self.tick += 1;
+        let entry = self.entries.get_mut(&key)?;
+        entry.last_used = self.tick;
+        Some((entry.lines.clone(), entry.size))
+    }
+
+    fn insert(&mut self, key: u64, lines: SmallVec<[WrappedLine; 1]>, size: Size<Pixels>) {
+        self.tick += 1;
+        self.entries.insert(
+            key,
+            CacheEntry {
+                lines,
+                size,
+                last_used: self.tick,
+            },
+        );
+        self.evict();
+    }
+
+    /// Evict the least recently used entries to the capacity.
+    ///
+    /// Evicting in batch of 1/4 capacity to avoid sorting on every insert.
+    fn evict(&mut self) {
+        if self.entries.len() <= self.capacity {
+            return;
+        }
+
+        let target = self.capacity - self.capacity / 4;
+        let mut used = self
+            .entries
+            .iter()
+            .map(|(key, entry)| (entry.last_used, *key))
+            .collect::<Vec<_>>();
+        used.sort_unstable();
+        for (_, key) in used.into_iter().take(self.entries.len() - target) {
+            self.entries.remove(&key);
+        }
+    }
+}
+
+/// Returns the cache key of the text with the runs, font size and wrap width.
+fn layout_key(text: &str, runs: &[TextRun], font_size: Pixels, wrap_width: Option<Pixels>) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    text.hash(&mut hasher);
+    for run in runs {
+        run.len.hash(&mut hasher);
+        run.font.hash(&mut hasher);
+        run.color.hash(&mut hasher);
+        run.background_color.hash(&mut hasher);
+        run.underline.hash(&mut hasher);
+        run.strikethrough.hash(&mut hasher);
+    }
+    f32::from(font_size).to_bits().hash(&mut hasher);
+    wrap_width
+        .map(|width| f32::from(width).to_bits())
+        .hash(&mut hasher);
+    hasher.finish()
+}
+
+fn compute_runs(
+    text: &str,
+    style: &TextStyle,
+    highlights: &[(Range<usize>, HighlightStyle)],
+) -> Vec<TextRun> {
+    let mut runs = Vec::with_capacity(highlights.len() * 2 + 1);
+    let mut ix = 0;
+    for (range, highlight) in highlights {
+        if ix < range.start {
+            runs.push(style.to_run(range.start - ix));
+        }
+        runs.push(style.clone().highlight(*highlight).to_run(range.len()));
+        ix = range.end;
+    }
+    if ix < text.len() {
+        runs.push(style.to_run(text.len() - ix));
+    }
+    runs
+}
+
+#[derive(Default)]
+struct CachedTextLayout {
+    lines: SmallVec<[WrappedLine; 1]>,
+    line_height: Pixels,
+}
+
+/// A text element like the [`StyledText`](gpui::StyledText) but with the shaped layout
+/// cached in the [`TextLayoutCache`], for the long read-only content (e.g. the lines of
+/// the [`LogView`](crate::log_view::LogView)) that is rendered every frame when scrolling.
+///
+/// The text is wrapped by the width when the `white_space` is normal, the `line_clamp`
+/// and `text_overflow` are not supported.
+pub struct CachedText {
+    text: SharedString,
+    highlights: Vec<(Range<usize>, HighlightStyle)>,
+    layout: Rc<RefCell<CachedTextLayout>>,
+}
+
+impl CachedText {
+    /// Create a new cached text.
+    pub fn new(text: impl Into<SharedString>) -> Self {
+        Self {
+            text: text.into(),
+            highlights: vec![],
+            layout: Rc::default(),
+        }
+    }
+
+    /// Set the highlights of the text, the ranges must be sorted and not overlap.
+    pub fn with_highlights(
+        mut self,
+        highlights: impl IntoIterator<Item = (Range<usize>, HighlightStyle)>,
+    ) -> Self {
+        self.highlights = highlights.into_iter().collect();
+        self
+    }
+}
+
+impl IntoElement for CachedText {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Element for CachedText {
+    type RequestLayoutState = ();
+    type PrepaintState = ();
+
+    fn id(&self) -> Option<ElementId> {
+        None
+    }
+
+    fn source_location(&self) -> Option<&'static core::panic::Location<'static>> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        _: Option<&InspectorElementId>,
+        window: &mut Window,
+        _: &mut App,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let text_style = window.text_style();
+        let font_size = text_style.font_size.to_pixels(window.rem_size());
+        let line_height = text_style
+            .line_height
+            .to_pixels(font_size.into(), window.rem_size());
+        let runs = compute_runs(&self.text, &text_style, &self.highlights);
+        let text = self.text.clone();
+        let layout = self.layout.clone();
+
+        let layout_id = window.request_measured_layout(
+            Default::default(),
+            move |known_dimensions, available_space, window, cx| {
+                let wrap_width = if text_style.white_space == WhiteSpace::Normal {
+                    known_dimensions.width.or(match available_space.width {
+                        AvailableSpace::Definite(width) => Some(width),
+                        _ => None,
+                    })
+                } else {
+                    None
+                };
+
+                let key = layout_key(&text, &runs, font_size, wrap_width);
+                let cache = cx.default_global::<TextLayoutCache>();
+                let (lines, size) = match cache.get(key) {
+                    Some(cached) => cached,
+                    None => {
+                        let lines = window
+                            .text_system()
+                            .shape_text(text.clone(), font_size, &runs, wrap_width, None)
+                            .unwrap_or_default();
+                        let mut size = Size::<Pixels>::default();
+                        for line in &lines {
+                            let line_size = line.size(line_height);
+                            size.height += line_size.height;
+                            size.width = size.width.max(line_size.width).ceil();
+                        }
+                        cx.default_global::<TextLayoutCache>()
+                            .insert(key, lines.clone(), size);
+                        (lines, size)
+                    }
+                };
+
+                *layout.borrow_mut() = CachedTextLayout { lines, line_height };
+                size
+            },
+        );
+
+        (layout_id, ())
+    }
+
+    fn prepaint(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        _: Option<&InspectorElementId>,
+        _: Bounds<Pixels>,
+        _: &mut Self::RequestLayoutState,
+        _: &mut Window,
+        _: &mut App,
+    ) {
+    }
+
+    fn paint(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        _: Option<&InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        _: &mut Self::RequestLayoutState,
+        _: &mut Self::PrepaintState,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let layout = self.layout.borrow();
+        let text_align = window.text_style().text_align;
+        let mut origin = bounds.origin;
+        for line in &layout.lines {
+            _ = line.paint_background(
+                origin,
+                layout.line_height,
+                text_align,
+                Some(bounds),
+                window,
+                cx,
+            );
+            _ = line.paint(
+                origin,
+                layout.line_height,
+                text_align,
+                Some(bounds),
+                window,
+                cx,
+            );
+            origin.y += line.size(layout.line_height).height.max(px(0.));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{px, Size};
+    use smallvec::SmallVec;
+
+    use super::{layout_key, TextLayoutCache};
+
+    #[test]
+    fn test_layout_key() {
+        let key = layout_key("Hello", &[], px(14.), None);
+        assert_eq!(key, layout_key("Hello", &[], px(14.), None));
+        assert_ne!(key, layout_key("Hello", &[], px(16.), None));
+        assert_ne!(key, layout_key("Hello", &[], px(14.), Some(px(100.))));
+        assert_ne!(key, layout_key("World", &[], px(14.), None));
+    }
+
+    #[test]
+    fn test_evict_least_recently_used() {
+        let mut cache = TextLayoutCache {
+            capacity: 4,
+            ..Default::default()
+        };
+        for key in 0..4 {
+            cache.insert(key, SmallVec::new(), Size::default());
+        }
+        assert!(cache.get(0).is_some());
+        cache.insert(4, SmallVec::new(), Size::default());
+
+        assert_eq!(cache.entries.len(), 3);
+        assert!(cache.get(0).is_some());
+        assert!(cache.get(4).is_some());
+        assert!(cache.get(1).is_none());
+        assert!(cache.get(2).is_none());
+    }
+}
@@ -1,14 +1,19 @@
 mod format;
 mod inline;
+mod layout_cache;
 mod node;
+mod selectable_text;
 mod style;
 mod text_view;
 mod utils;
 
 use gpui::App;
+pub use layout_cache::{CachedText, TextLayoutCache};
+pub use selectable_text::*;
 pub use style::*;
 pub use text_view::*;
 
 pub(crate) fn init(cx: &mut App) {
+    selectable_text::init(cx);
     text_view::init(cx);
 }
@@ -0,0 +1,419 @@
+use std::ops::Range;
+
+use gpui::{
+    div, App, Bounds, ClipboardItem, CursorStyle, Element, ElementId, Entity, FocusHandle,
+    GlobalElementId, HighlightStyle, Hitbox, HitboxBehavior, InspectorElementId,
+    InteractiveElement as _, IntoElement, KeyBinding, LayoutId, MouseButton, MouseDownEvent,
+    MouseMoveEvent, MouseUpEvent, ParentElement as _, Pixels, RenderOnce, SharedString,
+    StyleRefinement, Styled, StyledText, TextLayout, Window,
+};
+
+use crate::{input, input::Selection, text::inline::Inline, StyledExt as _};
+
+const CONTEXT: &str = "SelectableText";
+
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys(vec![
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-c", input::Copy, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-c", input::Copy, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-a", input::SelectAll, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-a", input::SelectAll, Some(CONTEXT)),
+    ]);
+}
+
+struct SelectableTextState {
+    focus_handle: FocusHandle,
+    text: SharedString,
+    selection: Selection,
+    /// The range selected by the mouse down, the drag extends from it.
+    anchor: Range<usize>,
+    /// The click count of the mouse down, 1 to select by chars, 2 by words, 3 by paragraphs.
+    click_count: usize,
+    is_selecting: bool,
+}
+
+impl SelectableTextState {
+    fn new(text: SharedString, cx: &mut App) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            text,
+            selection: Selection::default(),
+            anchor: 0..0,
+            click_count: 1,
+            is_selecting: false,
+        }
+    }
+
+    fn range_for_click(&self, offset: usize, click_count: usize) -> Range<usize> {
+        match click_count {
+            1 => offset..offset,
+            2 => word_range(&self.text, offset),
+            _ => paragraph_range(&self.text, offset),
+        }
+    }
+
+    fn start_selection(&mut self, offset: usize, click_count: usize) {
+        let range = self.range_for_click(offset, click_count);
+        self.selection = range.clone().into();
+        self.anchor = range;
+        self.click_count = click_count;
+        self.is_selecting = true;
+    }
+
+    fn update_selection(&mut self, offset: usize) {
+        let range = self.range_for_click(offset, self.click_count);
+        self.selection =
+            (self.anchor.start.min(range.start)..self.anchor.end.max(range.end)).into();
+    }
+
+    fn selected_text(&self) -> Option<&str> {
+        if self.selection.is_empty() {
+            return None;
+        }
+
+        self.text.get(self.selection.start..self.selection.end)
+    }
+}
+
+/// A static text that can be selected by the mouse and copied, like the text on a web page.
+///
+/// - Drag to select the text.
+/// - Double click to select a word, triple click to select a paragraph (split by the `\n`).
+/// - Press `cmd-c` (`ctrl-c` on Linux and Windows) to copy, `cmd-a` to select all.
+///
+/// See also [`Label::selectable`](crate::label::Label::selectable).
+///
+/// ```ignore
+/// SelectableText::new("version", "Version 0.2.2 (b1d2c3)")
+/// ```
+#[derive(IntoElement)]
+pub struct SelectableText {
+    id: ElementId,
+    text: SharedString,
+    highlights: Vec<(Range<usize>, HighlightStyle)>,
+    style: StyleRefinement,
+}
+
+impl SelectableText {
+    /// Create a new selectable text, the `id` keeps the selection.
+    pub fn new(id: impl Into<ElementId>, text: impl Into<SharedString>) -> Self {
+        Self {
+            id: id.into(),
+            text: text.into(),
+            highlights: vec![],
+            style: StyleRefinement::default(),
+        }
+    }
+
+    /// Set the highlights of the text, the ranges are in bytes.
+    pub fn highlights(
+        mut self,
+        highlights: impl IntoIterator<Item = (Range<usize>, HighlightStyle)>,
+    ) -> Self {
+        self.highlights = highlights.into_iter().collect();
+        self
+    }
+}
+
+impl Styled for SelectableText {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for SelectableText {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let state = window.use_keyed_state(self.id.clone(), cx, |_, cx| {
+            SelectableTextState::new(self.text.clone(), cx)
+        });
+        state.update(cx, |state, _| {
+            if state.text != self.text {
+                state.text = self.text.clone();
+                state.selection = Selection::default();
+                state.is_selecting = false;
+            }
+        });
+        let focus_handle = state.read(cx).focus_handle.clone();
+
+        div()
+            .id(self.id)
+            .key_context(CONTEXT)
+            .track_focus(&focus_handle)
+            .on_action({
+                let state = state.clone();
+                move |_: &input::Copy, _, cx| {
+                    if let Some(text) = state.read(cx).selected_text() {
+                        cx.write_to_clipboard(ClipboardItem::new_string(text.to_string()));
+                    }
+                }
+            })
+            .on_action({
+                let state = state.clone();
+                move |_: &input::SelectAll, _, cx| {
+                    state.update(cx, |state, cx| {
+                        state.selection = (0..state.text.len()).into();
+                        cx.notify();
+                    });
+                }
+            })
+            .refine_style(&self.style)
+            .child(SelectableTextElement {
+                text: self.text,
+                highlights: self.highlights,
+                styled_text: StyledText::new(SharedString::default()),
+                state,
+            })
+    }
+}
+
+struct SelectableTextElement {
+    text: SharedString,
+    highlights: Vec<(Range<usize>, HighlightStyle)>,
+    styled_text: StyledText,
+    state: Entity<SelectableTextState>,
+}
+
+impl IntoElement for SelectableTextElement {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+/// Returns the byte offset of the char boundary in the text closest to the position.
+fn offset_for_position(layout: &TextLayout, text: &str, position: gpui::Point<Pixels>) -> usize {
+    let mut offset = layout
+        .index_for_position(position)
+        .unwrap_or_else(|ix| ix)
+        .min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+impl Element for SelectableTextElement {
+    type RequestLayoutState = ();
+    type PrepaintState = Hitbox;
+
+    fn id(&self) -> Option<ElementId> {
+        None
+    }
+
+    fn source_location(&self) -> Option<&'static std::panic::Location<'static>> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        global_element_id: Option<&GlobalElementId>,
+        inspector_id: Option<&InspectorElementId>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        self.styled_text = StyledText::new(self.text.clone())
+            .with_default_highlights(&window.text_style(), self.highlights.clone());
+        let (layout_id, _) =
+            self.styled_text
+                .request_layout(global_element_id, inspector_id, window, cx);
+
+        (layout_id, ())
+    }
+
+    fn prepaint(
+        &mut self,
+        id: Option<&GlobalElementId>,
+        inspector_id: Option<&InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        _: &mut Self::RequestLayoutState,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self::PrepaintState {
+        self.styled_text
+            .prepaint(id, inspector_id, bounds, &mut (), window, cx);
+
+        window.insert_hitbox(bounds, HitboxBehavior::Normal)
+    }
+
+    fn paint(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        _: Option<&InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        _: &mut Self::RequestLayoutState,
+        hitbox: &mut Self::PrepaintState,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let text_layout = self.styled_text.layout().clone();
+        let (selection, is_selecting, focus_handle) = {
+            let state = self.state.read(cx);
+            (
+                state.selection,
+                state.is_selecting,
+                state.focus_handle.clone(),
+            )
+        };
+
+        window.set_cursor_style(CursorStyle::IBeam, hitbox);
+        if !selection.is_empty() {
+            Inline::paint_selection(&selection, &text_layout, &bounds, window, cx);
+        }
+        self.styled_text
+            .paint(global_id, None, bounds, &mut (), &mut (), window, cx);
+
+        window.on_mouse_event({
+            let state = self.state.clone();
+            let hitbox = hitbox.clone();
+            let text_layout = text_layout.clone();
+            move |event: &MouseDownEvent, phase, window, cx| {
+                if !phase.bubble() || event.button != MouseButton::Left {
+                    return;
+                }
+
+                if !hitbox.is_hovered(window) {
+                    // Mouse down outside to clear the selection.
+                    state.update(cx, |state, cx| {
+                        if !state.selection.is_empty() {
+                            state.selection = Selection::default();
+                            cx.notify();
+                        }
+                    });
+                    return;
+                }
+
+                window.focus(&focus_handle);
+                state.update(cx, |state, cx| {
+                    let offset = offset_for_position(&text_layout, &state.text, event.position);
+                    state.start_selection(offset, event.click_count.max(1));
+                    cx.notify();
+                });
+            }
+        });
+
+        if is_selecting {
+            window.on_mouse_event({
+                let state = self.state.clone();
+                let text_layout = text_layout.clone();
+                move |event: &MouseMoveEvent, phase, _, cx| {
+                    if !phase.bubble() {
+                        return;
+                    }
+
+                    state.update(cx, |state, cx| {
+                        let offset = offset_for_position(&text_layout, &state.text, event.position);
+                        state.update_selection(offset);
+                        cx.notify();
+                    });
+                }
+            });
+
+            window.on_mouse_event({
+                let state = self.state.clone();
+                move |_: &MouseUpEvent, phase, _, cx| {
+                    if !phase.bubble() {
+                        return;
+                    }
+
+                    state.update(cx, |state, cx| {
+                        state.is_selecting = false;
+                        cx.notify();
+                    });
+                }
+            });
+        }
+    }
+}
+
+#[derive(PartialEq)]
+enum CharKind {
+    Word,
+    Whitespace,
+    Punctuation,
+}
+
+impl CharKind {
+    fn of(c: char) -> Self {
+        if c.is_alphanumeric() || c == '_' {
+            Self::Word
+        } else if c.is_whitespace() {
+            Self::Whitespace
+        } else {
+            Self::Punctuation
+        }
+    }
+}
+
+/// Returns the range of the word at the byte `offset`, a run of the whitespaces is a word,
+/// and a punctuation is a word by itself.
+fn word_range(text: &str, offset: usize) -> Range<usize> {
+    let offset = offset.min(text.len());
+    // Use the char before the end of the text.
+    let (start, c) = match text[offset..].chars().next() {
+        Some(c) => (offset, c),
+        None => match text.chars().next_back() {
+            Some(c) => (offset - c.len_utf8(), c),
+            None => return offset..offset,
+        },
+    };
+
+    let kind = CharKind::of(c);
+    if kind == CharKind::Punctuation {
+        return start..start + c.len_utf8();
+    }
+
+    let start = text[..start]
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| CharKind::of(*c) == kind)
+        .last()
+        .map_or(start, |(ix, _)| ix);
+    let end = text[start..]
+        .char_indices()
+        .find(|(_, c)| CharKind::of(*c) != kind)
+        .map_or(text.len(), |(ix, _)| start + ix);
+    start..end
+}
+
+/// Returns the range of the paragraph (the line split by the `\n`) at the byte `offset`.
+fn paragraph_range(text: &str, offset: usize) -> Range<usize> {
+    let offset = offset.min(text.len());
+    let start = text[..offset].rfind('\n').map_or(0, |ix| ix + 1);
+    let end = text[offset..]
+        .find('\n')
+        .map_or(text.len(), |ix| offset + ix);
+    start..end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{paragraph_range, word_range};
+
+    #[test]
+    fn test_word_range() {
+        let text = "Hello, world_1  中文!";
+        assert_eq!(word_range(text, 0), 0..5);
+        assert_eq!(word_range(text, 3), 0..5);
+        assert_eq!(word_range(text, 5), 5..6);
+        assert_eq!(word_range(text, 8), 7..14);
+        assert_eq!(word_range(text, 14), 14..16);
+        assert_eq!(word_range(text, 16), 16..22);
+        assert_eq!(word_range(text, text.len()), 22..23);
+        assert_eq!(word_range("", 0), 0..0);
+    }
+
+    #[test]
+    fn test_paragraph_range() {
+        let text = "First line\nSecond line\n\nLast";
+        assert_eq!(paragraph_range(text, 0), 0..10);
+        assert_eq!(paragraph_range(text, 10), 0..10);
+        assert_eq!(paragraph_range(text, 11), 11..22);
+        assert_eq!(paragraph_range(text, 23), 23..23);
+        assert_eq!(paragraph_range(text, text.len()), 24..28);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{highlighter::HighlightTheme, scroll::ScrollbarShow};
+use crate::{breakpoint::Breakpoints, highlighter::HighlightTheme, scroll::ScrollbarShow, Density};
 use gpui::{px, App, Global, Hsla, Pixels, SharedString, Window, WindowAppearance};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -51,7 +51,13 @@ pub struct Theme {
     pub radius: Pixels,
     /// Radius for the large elements, e.g.: Modal, Notification border radius.
     pub radius_lg: Pixels,
+    /// Radius of the window with the client side decorations, see [`WindowChrome`](crate::window_chrome::WindowChrome).
+    pub window_radius: Pixels,
     pub shadow: bool,
+    /// Use the translucent surfaces with the blurred window background, default: false
+    ///
+    /// See [`Material`](crate::backdrop::Material).
+    pub backdrop_blur: bool,
     pub transparent: Hsla,
     /// Show the scrollbar mode, default: Scrolling
     pub scrollbar_show: ScrollbarShow,
@@ -61,6 +67,10 @@ pub struct Theme {
     pub tile_shadow: bool,
     /// The border radius of the tile panel, default is 0px.
     pub tile_radius: Pixels,
+    /// The minimum widths of the responsive breakpoints.
+    pub breakpoints: Breakpoints,
+    /// The density of the tables, default: Normal
+    pub table_density: Density,
 }
 
 impl Default for Theme {
@@ -182,11 +192,15 @@ impl From<&ThemeColor> for Theme {
             },
             radius: px(6.),
             radius_lg: px(8.),
+            window_radius: px(10.),
             shadow: true,
+            backdrop_blur: false,
             scrollbar_show: ScrollbarShow::default(),
             tile_grid_size: px(8.),
             tile_shadow: true,
             tile_radius: px(0.),
+            breakpoints: Breakpoints::default(),
+            table_density: Density::default(),
             colors: *colors,
             light_theme: Rc::new(ThemeConfig::default()),
             dark_theme: Rc::new(ThemeConfig::default()),
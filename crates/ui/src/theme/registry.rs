@@ -1,7 +1,6 @@
 use crate::{highlighter::HighlightTheme, Theme, ThemeColor, ThemeConfig, ThemeMode, ThemeSet};
 use anyhow::Result;
 use gpui::{App, Global, SharedString};
-use notify::Watcher as _;
 use std::{
     collections::HashMap,
     fs,
@@ -168,7 +167,16 @@ impl ThemeRegistry {
             .collect();
     }
 
+    /// The file system is not available on the web, the themes are only loaded once.
+    #[cfg(target_family = "wasm")]
+    fn _watch_themes_dir(_: PathBuf, _: &mut App) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    #[cfg(not(target_family = "wasm"))]
     fn _watch_themes_dir(themes_dir: PathBuf, cx: &mut App) -> anyhow::Result<()> {
+        use notify::Watcher as _;
+
         if !themes_dir.exists() {
             fs::create_dir_all(&themes_dir)?;
         }
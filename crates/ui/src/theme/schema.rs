@@ -6,8 +6,9 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    breakpoint::Breakpoints,
     highlighter::{HighlightTheme, HighlightThemeStyle},
-    Colorize, Theme, ThemeColor, ThemeMode,
+    Colorize, Density, Theme, ThemeColor, ThemeMode,
 };
 
 /// Represents a theme configuration.
@@ -48,9 +49,32 @@ pub struct ThemeConfig {
     /// The border radius for large elements like Modals and Notifications, default is 8.
     #[serde(rename = "radius.lg")]
     pub radius_lg: Option<usize>,
+    /// The border radius of the window with the client side decorations on Linux, default is 10.
+    #[serde(rename = "window.radius")]
+    pub window_radius: Option<usize>,
     /// Set shadows in the theme, for example the Input and Button, default is true.
     #[serde(rename = "shadow")]
     pub shadow: Option<bool>,
+    /// Use the translucent surfaces with the blurred window background, default is false.
+    #[serde(rename = "backdrop.blur")]
+    pub backdrop_blur: Option<bool>,
+
+    /// The minimum width of the `sm` breakpoint, default is 640.
+    #[serde(rename = "breakpoint.sm")]
+    pub breakpoint_sm: Option<f32>,
+    /// The minimum width of the `md` breakpoint, default is 768.
+    #[serde(rename = "breakpoint.md")]
+    pub breakpoint_md: Option<f32>,
+    /// The minimum width of the `lg` breakpoint, default is 1024.
+    #[serde(rename = "breakpoint.lg")]
+    pub breakpoint_lg: Option<f32>,
+    /// The minimum width of the `xl` breakpoint, default is 1280.
+    #[serde(rename = "breakpoint.xl")]
+    pub breakpoint_xl: Option<f32>,
+
+    /// The density of the tables: `compact`, `normal` or `comfortable`, default is `normal`.
+    #[serde(rename = "table.density")]
+    pub table_density: Option<Density>,
 
     /// The colors of the theme.
     pub colors: ThemeConfigColors,
@@ -158,6 +182,9 @@ pub struct ThemeConfigColors {
     /// Hover link text color.
     #[serde(rename = "link.hover")]
     pub link_hover: Option<SharedString>,
+    /// Visited link text color.
+    #[serde(rename = "link.visited")]
+    pub link_visited: Option<SharedString>,
     /// Background color for List and ListItem.
     #[serde(rename = "list.background")]
     pub list: Option<SharedString>,
@@ -556,6 +583,10 @@ impl ThemeColor {
         apply_color!(link, fallback = self.primary);
         apply_color!(link_active, fallback = self.link);
         apply_color!(link_hover, fallback = self.link);
+        apply_color!(
+            link_visited,
+            fallback = self.link.blend(self.muted_foreground.opacity(0.4))
+        );
         apply_color!(list, fallback = self.background);
         apply_color!(
             list_active,
@@ -662,11 +693,23 @@ impl Theme {
         } else {
             self.radius_lg = default_theme.radius_lg;
         }
+        self.window_radius = config
+            .window_radius
+            .map_or(default_theme.window_radius, |radius| px(radius as f32));
         if let Some(shadow) = config.shadow {
             self.shadow = shadow;
         } else {
             self.shadow = default_theme.shadow;
         }
+        self.backdrop_blur = config.backdrop_blur.unwrap_or(default_theme.backdrop_blur);
+        let default_breakpoints = default_theme.breakpoints;
+        self.breakpoints = Breakpoints {
+            sm: config.breakpoint_sm.map_or(default_breakpoints.sm, px),
+            md: config.breakpoint_md.map_or(default_breakpoints.md, px),
+            lg: config.breakpoint_lg.map_or(default_breakpoints.lg, px),
+            xl: config.breakpoint_xl.map_or(default_breakpoints.xl, px),
+        };
+        self.table_density = config.table_density.unwrap_or(default_theme.table_density);
 
         self.colors.apply_config(&config, &default_theme.colors);
         self.mode = config.mode;
@@ -71,6 +71,8 @@ pub struct ThemeColor {
     pub link_active: Hsla,
     /// Hover link text color.
     pub link_hover: Hsla,
+    /// Visited link text color.
+    pub link_visited: Hsla,
     /// Background color for List and ListItem.
     pub list: Hsla,
     /// Background color for active ListItem.
@@ -12,6 +12,7 @@ use rust_i18n::t;
 use crate::{
     actions::{Cancel, Confirm},
     button::{Button, ButtonVariants as _},
+    elevation::Layer,
     h_flex,
     input::{clear_button, Delete},
     v_flex, ActiveTheme, Disableable, Icon, IconName, Sizable, Size, StyleSized as _,
@@ -441,7 +442,7 @@ impl RenderOnce for DatePicker {
                                 .p_3()
                                 .border_1()
                                 .border_color(cx.theme().border)
-                                .shadow_lg()
+                                .elevation(Layer::Dropdown.elevation(), cx)
                                 .rounded((cx.theme().radius * 2.).min(px(8.)))
                                 .bg(cx.theme().background)
                                 .on_mouse_up_out(
@@ -488,7 +489,7 @@ impl RenderOnce for DatePicker {
                                 ),
                         ),
                     )
-                    .with_priority(2),
+                    .with_priority(Layer::Dropdown.priority()),
                 )
             })
     }
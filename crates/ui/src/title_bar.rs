@@ -1,6 +1,9 @@
 use std::rc::Rc;
 
-use crate::{h_flex, ActiveTheme, Icon, IconName, InteractiveElementExt as _, Sizable as _};
+use crate::{
+    h_flex, window_chrome::WindowChrome, ActiveTheme, Icon, IconName, InteractiveElementExt as _,
+    Sizable as _,
+};
 use gpui::{
     div, prelude::FluentBuilder as _, px, relative, AnyElement, App, ClickEvent, Div, Element,
     Hsla, InteractiveElement, IntoElement, MouseButton, ParentElement, Pixels, RenderOnce,
@@ -248,6 +251,7 @@ impl RenderOnce for TitleBar {
         let is_linux = cfg!(target_os = "linux");
         let is_macos = cfg!(target_os = "macos");
 
+        let radii = WindowChrome::corner_radii(window, cx);
         let paddings = self.base.style().padding.clone();
         self.base.style().padding.left = None;
         let left_padding = paddings.left.unwrap_or(TITLE_BAR_LEFT_PADDING.into());
@@ -262,6 +266,8 @@ impl RenderOnce for TitleBar {
                 .border_b_1()
                 .border_color(cx.theme().title_bar_border)
                 .bg(cx.theme().title_bar)
+                .rounded_tl(radii.top_left)
+                .rounded_tr(radii.top_right)
                 .when(is_linux, |this| {
                     this.on_double_click(|_, window, _| window.zoom_window())
                 })
@@ -0,0 +1,380 @@
+use std::time::{Duration, Instant};
+
+use gpui::{
+    div, point, prelude::FluentBuilder as _, px, App, Context, ElementId, Entity, EventEmitter,
+    InteractiveElement as _, IntoElement, ParentElement, Pixels, Render, RenderOnce, ScrollHandle,
+    SharedString, StatefulInteractiveElement as _, StyleRefinement, Styled, Task, Window,
+};
+use markdown::{mdast, ParseOptions};
+
+use crate::{
+    animation::{cubic_bezier, MotionSettings},
+    v_flex, ActiveTheme, StyledExt as _,
+};
+
+/// The duration of the smooth scrolling to a section.
+const SCROLL_DURATION: Duration = Duration::from_millis(300);
+
+/// An item of the [`Toc`], links to a section in the content view.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TocItem {
+    pub title: SharedString,
+    /// The heading level, starts from 1.
+    pub level: u8,
+    /// The index of the section in the children of the content view.
+    pub target: usize,
+}
+
+impl TocItem {
+    pub fn new(title: impl Into<SharedString>, target: usize) -> Self {
+        Self {
+            title: title.into(),
+            level: 1,
+            target,
+        }
+    }
+
+    /// Set the heading level, default: 1
+    pub fn level(mut self, level: u8) -> Self {
+        self.level = level.max(1);
+        self
+    }
+}
+
+/// A section of the Markdown split by the headings, see [`markdown_sections`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkdownSection {
+    /// The heading of the section, None for the content before the first heading.
+    pub heading: Option<TocItem>,
+    /// The Markdown of the section, starts with the heading.
+    pub markdown: SharedString,
+}
+
+/// Split the Markdown into sections by the top-level headings, up to the `max_level`.
+///
+/// Render each section as a child of the content view, and the headings are the items of the [`Toc`].
+pub fn markdown_sections(source: &str, max_level: u8) -> Vec<MarkdownSection> {
+    let mut starts = vec![];
+    if let Ok(mdast::Node::Root(root)) = markdown::to_mdast(source, &ParseOptions::gfm()) {
+        for node in root.children.iter() {
+            if let mdast::Node::Heading(heading) = node {
+                if heading.depth > max_level {
+                    continue;
+                }
+                if let Some(position) = &heading.position {
+                    let title = node.to_string();
+                    starts.push((position.start.offset, title, heading.depth));
+                }
+            }
+        }
+    }
+
+    let mut sections = vec![];
+    let first = starts.first().map_or(source.len(), |(start, _, _)| *start);
+    if !source[..first].trim().is_empty() {
+        sections.push(MarkdownSection {
+            heading: None,
+            markdown: source[..first].to_string().into(),
+        });
+    }
+    for (ix, (start, title, level)) in starts.iter().enumerate() {
+        let end = starts.get(ix + 1).map_or(source.len(), |(end, _, _)| *end);
+        sections.push(MarkdownSection {
+            heading: Some(TocItem::new(title.clone(), sections.len()).level(*level)),
+            markdown: source[*start..end].to_string().into(),
+        });
+    }
+    sections
+}
+
+/// Returns the index of the current section, the last one scrolled above the `threshold`.
+///
+/// The `tops` are the positions of the sections in the content, and the last section
+/// is current if the content is scrolled to the bottom.
+fn current_section(
+    tops: &[Option<Pixels>],
+    scroll_top: Pixels,
+    max_scroll_top: Pixels,
+    threshold: Pixels,
+) -> Option<usize> {
+    if max_scroll_top > px(0.) && scroll_top >= max_scroll_top - px(1.) {
+        if let Some(ix) = tops.iter().rposition(Option::is_some) {
+            return Some(ix);
+        }
+    }
+
+    tops.iter()
+        .enumerate()
+        .filter_map(|(ix, top)| top.map(|top| (ix, top)))
+        .take_while(|(_, top)| *top <= scroll_top + threshold)
+        .last()
+        .map(|(ix, _)| ix)
+}
+
+/// Events emitted by the [`TocState`].
+#[derive(Debug, Clone)]
+pub enum TocEvent {
+    /// The item at the index was clicked.
+    Select(usize),
+}
+
+/// State of the [`Toc`].
+pub struct TocState {
+    scroll_handle: ScrollHandle,
+    items: Vec<TocItem>,
+    threshold: Pixels,
+    /// The clicked item and the scroll offset it was scrolled to, it is kept current until scrolled away,
+    /// for the sections that can't be scrolled to the top.
+    pinned: Option<(usize, Pixels)>,
+    scrolling: bool,
+    _scroll_task: Option<Task<()>>,
+}
+
+impl EventEmitter<TocEvent> for TocState {}
+
+impl TocState {
+    /// Create a new state to track the content view with the scroll handle.
+    ///
+    /// The sections must be the direct children of the content view.
+    pub fn new(scroll_handle: &ScrollHandle, _: &mut Window, _: &mut Context<Self>) -> Self {
+        Self {
+            scroll_handle: scroll_handle.clone(),
+            items: vec![],
+            threshold: px(24.),
+            pinned: None,
+            scrolling: false,
+            _scroll_task: None,
+        }
+    }
+
+    /// Set the items.
+    pub fn items(mut self, items: impl IntoIterator<Item = TocItem>) -> Self {
+        self.items = items.into_iter().collect();
+        self
+    }
+
+    /// Set the distance from the top of the content view, within which a section becomes current, default: 24px
+    pub fn threshold(mut self, threshold: impl Into<Pixels>) -> Self {
+        self.threshold = threshold.into();
+        self
+    }
+
+    /// Set the items.
+    pub fn set_items(&mut self, items: impl IntoIterator<Item = TocItem>, cx: &mut Context<Self>) {
+        self.items = items.into_iter().collect();
+        self.pinned = None;
+        cx.notify();
+    }
+
+    fn scroll_top(&self) -> Pixels {
+        -self.scroll_handle.offset().y
+    }
+
+    /// Returns the top of the item's section in the content.
+    fn section_top(&self, ix: usize) -> Option<Pixels> {
+        let item = self.items.get(ix)?;
+        let bounds = self.scroll_handle.bounds_for_item(item.target)?;
+        Some(bounds.top() - self.scroll_handle.bounds().top())
+    }
+
+    /// Returns the index of the current item by the scroll position of the content view.
+    pub fn current_ix(&self) -> Option<usize> {
+        let scroll_top = self.scroll_top();
+        if let Some((ix, offset)) = self.pinned {
+            if self.scrolling || (offset - scroll_top).abs() < px(1.) {
+                return Some(ix);
+            }
+        }
+
+        let tops = (0..self.items.len())
+            .map(|ix| self.section_top(ix))
+            .collect::<Vec<_>>();
+        current_section(
+            &tops,
+            scroll_top,
+            self.scroll_handle.max_offset().height,
+            self.threshold,
+        )
+    }
+
+    /// Scroll the content view to the section of the item with a smooth animation.
+    pub fn scroll_to(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(top) = self.section_top(ix) else {
+            return;
+        };
+
+        let from = self.scroll_top();
+        let to = top.min(self.scroll_handle.max_offset().height).max(px(0.));
+        self.pinned = Some((ix, to));
+        self.scrolling = true;
+
+        let scroll_handle = self.scroll_handle.clone();
+        let duration = MotionSettings::global(cx).motion_duration(SCROLL_DURATION);
+        let start = Instant::now();
+        let easing = cubic_bezier(0.25, 0.1, 0.25, 1.);
+        self._scroll_task = Some(cx.spawn_in(window, async move |this, cx| loop {
+            cx.background_executor()
+                .timer(Duration::from_millis(16))
+                .await;
+
+            let progress = if duration.is_zero() {
+                1.
+            } else {
+                (start.elapsed().as_secs_f32() / duration.as_secs_f32()).min(1.)
+            };
+            let y = from + (to - from) * easing(progress);
+            let offset = scroll_handle.offset();
+            scroll_handle.set_offset(point(offset.x, -y));
+
+            let Ok(true) = this.update_in(cx, |this, window, _| {
+                window.refresh();
+                this.scrolling = progress < 1.;
+                this.scrolling
+            }) else {
+                break;
+            };
+        }));
+        cx.notify();
+    }
+}
+
+impl Render for TocState {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let current_ix = self.current_ix();
+
+        v_flex()
+            .border_l_1()
+            .border_color(cx.theme().border)
+            .children(self.items.iter().enumerate().map(|(ix, item)| {
+                let is_current = current_ix == Some(ix);
+                div()
+                    .id(ix)
+                    .py_1()
+                    .pr_2()
+                    .pl(px(12.) * item.level as f32)
+                    .ml(px(-1.))
+                    .border_l_1()
+                    .text_sm()
+                    .truncate()
+                    .cursor_pointer()
+                    .map(|this| {
+                        if is_current {
+                            this.border_color(cx.theme().primary)
+                                .text_color(cx.theme().foreground)
+                                .font_medium()
+                        } else {
+                            this.border_color(gpui::transparent_black())
+                                .text_color(cx.theme().muted_foreground)
+                                .hover(|this| this.text_color(cx.theme().foreground))
+                        }
+                    })
+                    .child(item.title.clone())
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.scroll_to(ix, window, cx);
+                        cx.emit(TocEvent::Select(ix));
+                    }))
+            }))
+    }
+}
+
+/// A table of contents, highlights the current section of a content view, and scrolls to the section on click.
+///
+/// ```ignore
+/// let scroll_handle = ScrollHandle::new();
+/// let sections = markdown_sections(SOURCE, 3);
+/// let toc = cx.new(|cx| {
+///     TocState::new(&scroll_handle, window, cx)
+///         .items(sections.iter().filter_map(|s| s.heading.clone()))
+/// });
+///
+/// h_flex()
+///     .child(
+///         div()
+///             .id("content")
+///             .track_scroll(&scroll_handle)
+///             .overflow_y_scroll()
+///             .children(sections.iter().enumerate().map(|(ix, s)| {
+///                 TextView::markdown(ix, s.markdown.clone(), window, cx)
+///             })),
+///     )
+///     .child(Toc::new(&toc).w(px(200.)))
+/// ```
+#[derive(IntoElement)]
+pub struct Toc {
+    id: ElementId,
+    state: Entity<TocState>,
+    style: StyleRefinement,
+}
+
+impl Toc {
+    /// Create a new table of contents with the given state.
+    pub fn new(state: &Entity<TocState>) -> Self {
+        Self {
+            id: ElementId::Name(format!("toc-{}", state.entity_id()).into()),
+            state: state.clone(),
+            style: StyleRefinement::default(),
+        }
+    }
+}
+
+impl Styled for Toc {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for Toc {
+    fn render(self, _: &mut Window, _: &mut App) -> impl IntoElement {
+        div()
+            .id(self.id)
+            .overflow_y_scroll()
+            .child(self.state)
+            .refine_style(&self.style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::px;
+
+    use super::{current_section, markdown_sections};
+
+    #[test]
+    fn test_current_section() {
+        let tops = [Some(px(0.)), Some(px(100.)), None, Some(px(300.))];
+        assert_eq!(current_section(&tops, px(0.), px(500.), px(10.)), Some(0));
+        assert_eq!(current_section(&tops, px(95.), px(500.), px(10.)), Some(1));
+        assert_eq!(current_section(&tops, px(299.), px(500.), px(10.)), Some(3));
+        // The last section is current at the bottom.
+        assert_eq!(current_section(&tops, px(250.), px(250.), px(10.)), Some(3));
+        assert_eq!(
+            current_section(&[Some(px(50.))], px(0.), px(0.), px(10.)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_markdown_sections() {
+        let sections = markdown_sections(
+            "Intro\n\n# Title\n\nText\n\n## Usage `code`\n\n```\n# Not heading\n```\n\n#### Deep\n",
+            3,
+        );
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[0].heading, None);
+        assert_eq!(sections[0].markdown.as_ref(), "Intro\n\n");
+
+        let title = sections[1].heading.as_ref().unwrap();
+        assert_eq!(
+            (title.title.as_ref(), title.level, title.target),
+            ("Title", 1, 1)
+        );
+        assert_eq!(sections[1].markdown.as_ref(), "# Title\n\nText\n\n");
+
+        let usage = sections[2].heading.as_ref().unwrap();
+        assert_eq!(
+            (usage.title.as_ref(), usage.level, usage.target),
+            ("Usage code", 2, 2)
+        );
+        assert!(sections[2].markdown.ends_with("#### Deep\n"));
+    }
+}
@@ -3,7 +3,7 @@ use gpui::{
     IntoElement, ParentElement, Render, SharedString, StyleRefinement, Styled, Window,
 };
 
-use crate::{h_flex, kbd::Kbd, text::Text, ActiveTheme, StyledExt};
+use crate::{elevation::Layer, h_flex, kbd::Kbd, text::Text, ActiveTheme, StyledExt};
 
 enum TooltipContext {
     Text(Text),
@@ -96,7 +96,7 @@ impl Render for Tooltip {
                 .bg(cx.theme().popover)
                 .border_1()
                 .border_color(cx.theme().border)
-                .shadow_md()
+                .elevation(Layer::Tooltip.elevation(), cx)
                 .rounded(px(6.))
                 .justify_between()
                 .py_0p5()
@@ -1,9 +1,9 @@
 use std::{cell::RefCell, ops::Range, rc::Rc};
 
 use gpui::{
-    div, prelude::FluentBuilder as _, uniform_list, App, Context, ElementId, Entity, FocusHandle,
-    InteractiveElement as _, IntoElement, KeyBinding, ListSizingBehavior, MouseButton,
-    ParentElement, Render, RenderOnce, SharedString, StyleRefinement, Styled,
+    div, prelude::FluentBuilder as _, uniform_list, App, Context, ElementId, Entity, EventEmitter,
+    FocusHandle, Focusable, InteractiveElement as _, IntoElement, KeyBinding, ListSizingBehavior,
+    MouseButton, ParentElement, Render, RenderOnce, SharedString, StyleRefinement, Styled,
     UniformListScrollHandle, Window,
 };
 
@@ -55,6 +55,7 @@ where
 struct TreeItemState {
     expanded: bool,
     disabled: bool,
+    folder: bool,
 }
 
 /// A tree item with a label, children, and an expanded state.
@@ -128,6 +129,7 @@ impl TreeItem {
             state: Rc::new(RefCell::new(TreeItemState {
                 expanded: false,
                 disabled: false,
+                folder: false,
             })),
         }
     }
@@ -156,10 +158,18 @@ impl TreeItem {
         self
     }
 
-    /// Whether this item is a folder (has children).
+    /// Mark this item as a folder even if it has no children, default: false
+    ///
+    /// This is useful for the folders that load their children lazily.
+    pub fn folder(self, folder: bool) -> Self {
+        self.state.borrow_mut().folder = folder;
+        self
+    }
+
+    /// Whether this item is a folder (has children, or marked as folder).
     #[inline]
     pub fn is_folder(&self) -> bool {
-        self.children.len() > 0
+        self.children.len() > 0 || self.state.borrow().folder
     }
 
     /// Return true if the item is disabled.
@@ -174,6 +184,15 @@ impl TreeItem {
     }
 }
 
+/// Events emitted by the [`TreeState`].
+#[derive(Clone)]
+pub enum TreeEvent {
+    /// A folder item was expanded or collapsed by the user.
+    Toggle(TreeEntry),
+    /// An item was selected by the user.
+    Select(TreeEntry),
+}
+
 /// State for managing tree items.
 pub struct TreeState {
     focus_handle: FocusHandle,
@@ -189,7 +208,8 @@ impl TreeState {
     pub fn new(cx: &mut App) -> Self {
         Self {
             selected_ix: None,
-            focus_handle: cx.focus_handle(),
+            // A single tab stop, the arrow keys move the selection in the tree.
+            focus_handle: cx.focus_handle().tab_stop(true),
             scrollbar_state: ScrollbarState::default(),
             scroll_handle: UniformListScrollHandle::default(),
             entries: Vec::new(),
@@ -238,6 +258,11 @@ impl TreeState {
         self.selected_ix.and_then(|ix| self.entries.get(ix))
     }
 
+    /// Get the visible entries, the children of the collapsed items are not included.
+    pub fn entries(&self) -> &[TreeEntry] {
+        &self.entries
+    }
+
     fn add_entry(&mut self, item: TreeItem, depth: usize) {
         self.entries.push(TreeEntry {
             item: item.clone(),
@@ -262,6 +287,28 @@ impl TreeState {
         self.rebuild_entries();
     }
 
+    fn toggle_expand_and_emit(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if !self
+            .entries
+            .get(ix)
+            .map_or(false, |entry| entry.is_folder())
+        {
+            return;
+        }
+
+        self.toggle_expand(ix);
+        if let Some(entry) = self.entries.get(ix) {
+            cx.emit(TreeEvent::Toggle(entry.clone()));
+        }
+        cx.notify();
+    }
+
+    fn emit_select(&mut self, cx: &mut Context<Self>) {
+        if let Some(entry) = self.selected_entry() {
+            cx.emit(TreeEvent::Select(entry.clone()));
+        }
+    }
+
     fn rebuild_entries(&mut self) {
         let root_items: Vec<TreeItem> = self
             .entries
@@ -279,8 +326,7 @@ impl TreeState {
         if let Some(selected_ix) = self.selected_ix {
             if let Some(entry) = self.entries.get(selected_ix) {
                 if entry.is_folder() {
-                    self.toggle_expand(selected_ix);
-                    cx.notify();
+                    self.toggle_expand_and_emit(selected_ix, cx);
                 }
             }
         }
@@ -290,8 +336,7 @@ impl TreeState {
         if let Some(selected_ix) = self.selected_ix {
             if let Some(entry) = self.entries.get(selected_ix) {
                 if entry.is_folder() && entry.is_expanded() {
-                    self.toggle_expand(selected_ix);
-                    cx.notify();
+                    self.toggle_expand_and_emit(selected_ix, cx);
                 }
             }
         }
@@ -301,8 +346,7 @@ impl TreeState {
         if let Some(selected_ix) = self.selected_ix {
             if let Some(entry) = self.entries.get(selected_ix) {
                 if entry.is_folder() && !entry.is_expanded() {
-                    self.toggle_expand(selected_ix);
-                    cx.notify();
+                    self.toggle_expand_and_emit(selected_ix, cx);
                 }
             }
         }
@@ -320,6 +364,7 @@ impl TreeState {
         self.selected_ix = Some(selected_ix);
         self.scroll_handle
             .scroll_to_item(selected_ix, gpui::ScrollStrategy::Top);
+        self.emit_select(cx);
         cx.notify();
     }
 
@@ -334,16 +379,26 @@ impl TreeState {
         self.selected_ix = Some(selected_ix);
         self.scroll_handle
             .scroll_to_item(selected_ix, gpui::ScrollStrategy::Bottom);
+        self.emit_select(cx);
         cx.notify();
     }
 
     fn on_entry_click(&mut self, ix: usize, _: &mut Window, cx: &mut Context<Self>) {
         self.selected_ix = Some(ix);
-        self.toggle_expand(ix);
+        self.emit_select(cx);
+        self.toggle_expand_and_emit(ix, cx);
         cx.notify();
     }
 }
 
+impl EventEmitter<TreeEvent> for TreeState {}
+
+impl Focusable for TreeState {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
 impl Render for TreeState {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let render_item = self.render_item.clone();
@@ -546,4 +601,15 @@ mod tests {
             );
         })
     }
+
+    #[test]
+    fn test_folder_item() {
+        use super::TreeItem;
+
+        let item = TreeItem::new("src", "src");
+        assert_eq!(item.is_folder(), false);
+        let item = item.folder(true);
+        assert_eq!(item.is_folder(), true);
+        assert_eq!(item.children.len(), 0);
+    }
 }
@@ -0,0 +1,284 @@
+use gpui::{
+    canvas, div, prelude::FluentBuilder as _, px, App, ElementId, InteractiveElement as _,
+    IntoElement, ParentElement as _, Pixels, RenderOnce, SharedString,
+    StatefulInteractiveElement as _, StyleRefinement, Styled, Window,
+};
+
+use crate::{tooltip::Tooltip, StyledExt as _};
+
+const ELLIPSIS: &str = "…";
+
+/// The position of the ellipsis in the [`TruncatedText`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Ellipsis {
+    /// Keep the end of the text, e.g. `…/src/main.rs`.
+    Start,
+    /// Keep the start and the end of the text, e.g. `~/projects/…/main.rs`.
+    Middle,
+    /// Keep the start of the text, e.g. `~/projects/app/…`.
+    #[default]
+    End,
+}
+
+#[derive(Default)]
+struct TruncatedTextState {
+    text: SharedString,
+    width: Pixels,
+    display: Option<SharedString>,
+    truncated: bool,
+}
+
+/// A text truncated to fit the width with the ellipsis at the start, middle or end,
+/// or clamped to the max lines, and shows the full text in a tooltip only when it is truncated.
+///
+/// The width of the text must be limited by the parent, e.g. `w_full` or `flex_1` in a flex row.
+///
+/// ```ignore
+/// TruncatedText::new("path", "~/projects/gpui-component/crates/ui/src/lib.rs")
+///     .ellipsis(Ellipsis::Middle)
+///     .w_full()
+/// ```
+#[derive(IntoElement)]
+pub struct TruncatedText {
+    id: ElementId,
+    text: SharedString,
+    ellipsis: Ellipsis,
+    max_lines: Option<usize>,
+    tooltip: bool,
+    style: StyleRefinement,
+}
+
+impl TruncatedText {
+    /// Create a new truncated text, the `id` keeps the measured width.
+    pub fn new(id: impl Into<ElementId>, text: impl Into<SharedString>) -> Self {
+        Self {
+            id: id.into(),
+            text: text.into(),
+            ellipsis: Ellipsis::End,
+            max_lines: None,
+            tooltip: true,
+            style: StyleRefinement::default(),
+        }
+    }
+
+    /// Set the position of the ellipsis for the single line text, default: [`Ellipsis::End`]
+    pub fn ellipsis(mut self, ellipsis: Ellipsis) -> Self {
+        self.ellipsis = ellipsis;
+        self
+    }
+
+    /// Wrap the text and clamp to the `lines` with the ellipsis at the end.
+    pub fn max_lines(mut self, lines: usize) -> Self {
+        self.max_lines = Some(lines.max(1));
+        self
+    }
+
+    /// Set to show the full text in a tooltip when it is truncated, default: true
+    pub fn tooltip_on_truncate(mut self, tooltip: bool) -> Self {
+        self.tooltip = tooltip;
+        self
+    }
+}
+
+impl Styled for TruncatedText {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+/// Returns the byte index of the end of the prefix and the start of the suffix to keep,
+/// or `None` if the text fits the `width`.
+///
+/// The `x_for_index` is the x position of the byte index in the single line text.
+fn truncate_range(
+    text: &str,
+    x_for_index: impl Fn(usize) -> Pixels,
+    width: Pixels,
+    ellipsis_width: Pixels,
+    ellipsis: Ellipsis,
+) -> Option<(usize, usize)> {
+    let full_width = x_for_index(text.len());
+    if full_width <= width {
+        return None;
+    }
+
+    let available = (width - ellipsis_width).max(px(0.));
+    let boundaries = text
+        .char_indices()
+        .map(|(ix, _)| ix)
+        .chain([text.len()])
+        .collect::<Vec<_>>();
+    let prefix_end = |budget: Pixels| {
+        boundaries
+            .iter()
+            .copied()
+            .take_while(|ix| x_for_index(*ix) <= budget)
+            .last()
+            .unwrap_or(0)
+    };
+    let suffix_start = |from: usize, budget: Pixels| {
+        boundaries
+            .iter()
+            .copied()
+            .find(|ix| *ix >= from && full_width - x_for_index(*ix) <= budget)
+            .unwrap_or(text.len())
+    };
+
+    Some(match ellipsis {
+        Ellipsis::End => (prefix_end(available), text.len()),
+        Ellipsis::Start => (0, suffix_start(0, available)),
+        Ellipsis::Middle => {
+            let prefix_end = prefix_end(available / 2.);
+            let rest = available - x_for_index(prefix_end);
+            (prefix_end, suffix_start(prefix_end, rest))
+        }
+    })
+}
+
+impl RenderOnce for TruncatedText {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let state = window.use_keyed_state(self.id.clone(), cx, |_, _| TruncatedTextState {
+            text: self.text.clone(),
+            ..Default::default()
+        });
+        let (display, truncated) = {
+            let state = state.read(cx);
+            if state.text == self.text {
+                (state.display.clone(), state.truncated)
+            } else {
+                (None, false)
+            }
+        };
+
+        let text = self.text.clone();
+        let ellipsis = self.ellipsis;
+        let max_lines = self.max_lines;
+
+        div()
+            .id(self.id)
+            .relative()
+            .min_w_0()
+            .overflow_hidden()
+            .text_ellipsis()
+            .map(|this| match max_lines {
+                Some(lines) => this.line_clamp(lines),
+                None => this.whitespace_nowrap(),
+            })
+            .refine_style(&self.style)
+            .child(display.unwrap_or(self.text.clone()))
+            .when(self.tooltip && truncated, |this| {
+                let text = self.text.clone();
+                this.tooltip(move |window, cx| Tooltip::new(text.clone()).build(window, cx))
+            })
+            .child(
+                canvas(
+                    move |bounds, window, cx| {
+                        {
+                            let state = state.read(cx);
+                            if state.text == text && state.width == bounds.size.width {
+                                return;
+                            }
+                        }
+
+                        let style = window.text_style();
+                        let font_size = style.font_size.to_pixels(window.rem_size());
+                        let width = bounds.size.width;
+                        let text_system = window.text_system().clone();
+
+                        let (display, truncated) = if let Some(lines) = max_lines {
+                            let runs = [style.to_run(text.len())];
+                            let wrapped_lines = text_system
+                                .shape_text(text.clone(), font_size, &runs, Some(width), None)
+                                .map(|lines| {
+                                    lines
+                                        .iter()
+                                        .map(|line| line.wrap_boundaries().len() + 1)
+                                        .sum::<usize>()
+                                })
+                                .unwrap_or(0);
+                            (None, wrapped_lines > lines)
+                        } else {
+                            let line = text_system.shape_line(
+                                text.clone(),
+                                font_size,
+                                &[style.to_run(text.len())],
+                                None,
+                            );
+                            let ellipsis_width = text_system
+                                .shape_line(
+                                    ELLIPSIS.into(),
+                                    font_size,
+                                    &[style.to_run(ELLIPSIS.len())],
+                                    None,
+                                )
+                                .width;
+
+                            match truncate_range(
+                                &text,
+                                |ix| line.x_for_index(ix),
+                                width,
+                                ellipsis_width,
+                                ellipsis,
+                            ) {
+                                Some((prefix_end, suffix_start)) => (
+                                    Some(SharedString::from(format!(
+                                        "{}{}{}",
+                                        &text[..prefix_end],
+                                        ELLIPSIS,
+                                        &text[suffix_start..]
+                                    ))),
+                                    true,
+                                ),
+                                None => (None, false),
+                            }
+                        };
+
+                        let changed = state.update(cx, |state, _| {
+                            let changed = state.display != display || state.truncated != truncated;
+                            *state = TruncatedTextState {
+                                text: text.clone(),
+                                width,
+                                display,
+                                truncated,
+                            };
+                            changed
+                        });
+                        if changed {
+                            window.refresh();
+                        }
+                    },
+                    |_, _, _, _| {},
+                )
+                .absolute()
+                .size_full(),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::px;
+
+    use super::{truncate_range, Ellipsis};
+
+    #[test]
+    fn test_truncate_range() {
+        // A monospace font with 10px for each character.
+        let x_for_index = |ix: usize| px(ix as f32 * 10.);
+        let text = "abcdefghij";
+        let truncate = |width: f32, ellipsis| {
+            truncate_range(text, x_for_index, px(width), px(10.), ellipsis).map(
+                |(prefix_end, suffix_start)| {
+                    format!("{}…{}", &text[..prefix_end], &text[suffix_start..])
+                },
+            )
+        };
+
+        assert_eq!(truncate(100., Ellipsis::End), None);
+        assert_eq!(truncate(60., Ellipsis::End).as_deref(), Some("abcde…"));
+        assert_eq!(truncate(60., Ellipsis::Start).as_deref(), Some("…fghij"));
+        assert_eq!(truncate(60., Ellipsis::Middle).as_deref(), Some("ab…hij"));
+        assert_eq!(truncate(70., Ellipsis::Middle).as_deref(), Some("abc…hij"));
+        assert_eq!(truncate(5., Ellipsis::Middle).as_deref(), Some("…"));
+    }
+}
@@ -0,0 +1,562 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use gpui::{
+    actions, anchored, deferred, div, img, point, prelude::FluentBuilder as _, px, App,
+    AppContext as _, Context, ElementId, Entity, EventEmitter, FocusHandle, Focusable, ImageSource,
+    InteractiveElement as _, IntoElement, KeyBinding, ObjectFit, ParentElement, Render,
+    RenderImage, RenderOnce, SharedString, StatefulInteractiveElement as _, StyleRefinement,
+    Styled, StyledImage as _, Subscription, Task, Window,
+};
+
+use crate::{
+    actions::Cancel,
+    button::{Button, ButtonVariants as _},
+    elevation::Layer,
+    h_flex,
+    slider::{Slider, SliderEvent, SliderState},
+    ActiveTheme, IconName, Sizable as _, StyledExt as _,
+};
+
+actions!(
+    video_player,
+    [TogglePlay, SeekForward, SeekBackward, ToggleFullscreen]
+);
+
+const CONTEXT: &str = "VideoPlayer";
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("space", TogglePlay, Some(CONTEXT)),
+        KeyBinding::new("right", SeekForward, Some(CONTEXT)),
+        KeyBinding::new("left", SeekBackward, Some(CONTEXT)),
+        KeyBinding::new("f", ToggleFullscreen, Some(CONTEXT)),
+        KeyBinding::new("escape", Cancel, Some(CONTEXT)),
+    ]);
+}
+
+const SEEK_STEP: Duration = Duration::from_secs(5);
+/// The max frames to decode in a tick to catch up with the clock, the rest are skipped.
+const MAX_CATCH_UP_FRAMES: usize = 4;
+
+/// A decoded video frame.
+#[derive(Clone)]
+pub struct VideoFrame {
+    /// The image of the frame, uploaded to the GPU texture when it is painted.
+    pub image: Arc<RenderImage>,
+    /// The presentation timestamp of the frame.
+    pub timestamp: Duration,
+}
+
+/// The backend to decode the video frames, e.g.: FFmpeg or GStreamer.
+///
+/// The methods are called on the main thread, so the backend should decode the frames
+/// ahead in its own thread, and return the buffered ones.
+pub trait VideoBackend: 'static {
+    /// Returns the duration of the video, `None` for a live stream.
+    fn duration(&self) -> Option<Duration>;
+
+    /// Returns the frame rate, used to schedule the decoding, default: 30
+    fn frame_rate(&self) -> f32 {
+        30.
+    }
+
+    /// Seek to the position, the next frame should be at or after the position.
+    fn seek(&mut self, position: Duration) -> anyhow::Result<()>;
+
+    /// Returns the next frame, `Ok(None)` at the end of the video.
+    fn next_frame(&mut self) -> anyhow::Result<Option<VideoFrame>>;
+}
+
+/// Events emitted by the [`VideoPlayerState`].
+#[derive(Debug, Clone)]
+pub enum VideoPlayerEvent {
+    Play,
+    Pause,
+    /// A new frame was presented, with its timestamp.
+    Frame(Duration),
+    /// The playback reached the end of the video.
+    Ended,
+    /// The backend failed to decode, the playback is paused.
+    Error(SharedString),
+}
+
+/// Format the duration as `m:ss` or `h:mm:ss`.
+fn format_time(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+/// State of the [`VideoPlayer`].
+pub struct VideoPlayerState {
+    focus_handle: FocusHandle,
+    backend: Box<dyn VideoBackend>,
+    frame: Option<VideoFrame>,
+    /// The replaced frames, to be dropped from the GPU texture in the next tick.
+    stale_frames: Vec<Arc<RenderImage>>,
+    /// The playback position when the clock was started, and the time it was started.
+    clock: Option<(Duration, Instant)>,
+    position: Duration,
+    looping: bool,
+    fullscreen: bool,
+    error: Option<SharedString>,
+    progress: Entity<SliderState>,
+    _playback: Option<Task<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl VideoPlayerState {
+    /// Create a new video player state with the backend, the first frame is decoded for the poster.
+    pub fn new(backend: impl VideoBackend, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let duration = backend.duration().unwrap_or_default();
+        let progress = cx.new(|_| {
+            SliderState::new()
+                .max(duration.as_secs_f32().max(0.01))
+                .step(0.01)
+        });
+        let _subscriptions = vec![cx.subscribe_in(
+            &progress,
+            window,
+            |this, _, event: &SliderEvent, window, cx| {
+                let SliderEvent::Change(value) = event;
+                this.seek(Duration::from_secs_f32(value.end()), window, cx);
+            },
+        )];
+
+        let mut this = Self {
+            focus_handle: cx.focus_handle(),
+            backend: Box::new(backend),
+            frame: None,
+            stale_frames: vec![],
+            clock: None,
+            position: Duration::ZERO,
+            looping: false,
+            fullscreen: false,
+            error: None,
+            progress,
+            _playback: None,
+            _subscriptions,
+        };
+        this.decode_next(cx);
+        this
+    }
+
+    /// Set to restart the playback at the end, default: false
+    pub fn looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Returns the backend.
+    pub fn backend(&self) -> &dyn VideoBackend {
+        self.backend.as_ref()
+    }
+
+    /// Returns the current frame.
+    pub fn frame(&self) -> Option<&VideoFrame> {
+        self.frame.as_ref()
+    }
+
+    /// Returns the playback position.
+    pub fn position(&self) -> Duration {
+        self.position
+    }
+
+    /// Returns true if it is playing.
+    pub fn is_playing(&self) -> bool {
+        self.clock.is_some()
+    }
+
+    /// Returns true if it is shown in fullscreen.
+    pub fn is_fullscreen(&self) -> bool {
+        self.fullscreen
+    }
+
+    /// Start the playback.
+    pub fn play(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.is_playing() {
+            return;
+        }
+
+        // Restart from the beginning at the end.
+        if self
+            .backend
+            .duration()
+            .is_some_and(|duration| self.position >= duration)
+        {
+            self.seek(Duration::ZERO, window, cx);
+        }
+
+        self.error = None;
+        self.clock = Some((self.position, Instant::now()));
+        let interval = Duration::from_secs_f32(1. / self.backend.frame_rate().max(1.));
+        self._playback = Some(cx.spawn_in(window, async move |this, cx| loop {
+            cx.background_executor().timer(interval).await;
+            let Ok(true) = this.update_in(cx, |this, window, cx| this.tick(window, cx)) else {
+                break;
+            };
+        }));
+        cx.emit(VideoPlayerEvent::Play);
+        cx.notify();
+    }
+
+    /// Pause the playback.
+    pub fn pause(&mut self, cx: &mut Context<Self>) {
+        if self.clock.take().is_none() {
+            return;
+        }
+
+        self._playback = None;
+        cx.emit(VideoPlayerEvent::Pause);
+        cx.notify();
+    }
+
+    /// Toggle between play and pause.
+    pub fn toggle_play(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.is_playing() {
+            self.pause(cx);
+        } else {
+            self.play(window, cx);
+        }
+    }
+
+    /// Seek to the position and present the frame there.
+    pub fn seek(&mut self, position: Duration, window: &mut Window, cx: &mut Context<Self>) {
+        let position = match self.backend.duration() {
+            Some(duration) => position.min(duration),
+            None => position,
+        };
+
+        if let Err(err) = self.backend.seek(position) {
+            self.fail(err, cx);
+            return;
+        }
+
+        self.position = position;
+        if let Some(clock) = self.clock.as_mut() {
+            *clock = (position, Instant::now());
+        }
+        self.decode_next(cx);
+        self.sync_progress(window, cx);
+        cx.notify();
+    }
+
+    /// Toggle the fullscreen of the window and the player fills the window.
+    pub fn toggle_fullscreen(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.fullscreen = !self.fullscreen;
+        if window.is_fullscreen() != self.fullscreen {
+            window.toggle_fullscreen();
+        }
+        cx.notify();
+    }
+
+    /// Decode the next frame and present it, returns false at the end or on error.
+    fn decode_next(&mut self, cx: &mut Context<Self>) -> bool {
+        match self.backend.next_frame() {
+            Ok(Some(frame)) => {
+                cx.emit(VideoPlayerEvent::Frame(frame.timestamp));
+                if let Some(old) = self.frame.replace(frame) {
+                    self.stale_frames.push(old.image);
+                }
+                true
+            }
+            Ok(None) => false,
+            Err(err) => {
+                self.fail(err, cx);
+                false
+            }
+        }
+    }
+
+    fn fail(&mut self, err: anyhow::Error, cx: &mut Context<Self>) {
+        let message: SharedString = err.to_string().into();
+        self.pause(cx);
+        self.error = Some(message.clone());
+        cx.emit(VideoPlayerEvent::Error(message));
+        cx.notify();
+    }
+
+    /// Advance the playback by the clock, returns false to stop the playback.
+    fn tick(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        for image in self.stale_frames.drain(..) {
+            _ = window.drop_image(image);
+        }
+
+        let Some((start, started_at)) = self.clock else {
+            return false;
+        };
+
+        self.position = start + started_at.elapsed();
+        let mut ended = false;
+        for _ in 0..MAX_CATCH_UP_FRAMES {
+            if self
+                .frame
+                .as_ref()
+                .is_some_and(|frame| frame.timestamp >= self.position)
+            {
+                break;
+            }
+
+            if !self.decode_next(cx) {
+                ended = self.error.is_none();
+                break;
+            }
+        }
+
+        if let Some(duration) = self.backend.duration() {
+            self.position = self.position.min(duration);
+        }
+        self.sync_progress(window, cx);
+        cx.notify();
+
+        if ended {
+            cx.emit(VideoPlayerEvent::Ended);
+            if self.looping {
+                self.seek(Duration::ZERO, window, cx);
+                return true;
+            }
+
+            self.pause(cx);
+            return false;
+        }
+
+        self.is_playing()
+    }
+
+    fn sync_progress(&self, window: &mut Window, cx: &mut Context<Self>) {
+        let position = self.position.as_secs_f32();
+        self.progress.update(cx, |progress, cx| {
+            progress.set_value(position, window, cx);
+        });
+    }
+
+    fn on_action_toggle_play(
+        &mut self,
+        _: &TogglePlay,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.toggle_play(window, cx);
+    }
+
+    fn on_action_seek_forward(
+        &mut self,
+        _: &SeekForward,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.seek(self.position + SEEK_STEP, window, cx);
+    }
+
+    fn on_action_seek_backward(
+        &mut self,
+        _: &SeekBackward,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.seek(self.position.saturating_sub(SEEK_STEP), window, cx);
+    }
+
+    fn on_action_toggle_fullscreen(
+        &mut self,
+        _: &ToggleFullscreen,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.toggle_fullscreen(window, cx);
+    }
+
+    fn on_action_cancel(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        if self.fullscreen {
+            self.toggle_fullscreen(window, cx);
+        } else {
+            cx.propagate();
+        }
+    }
+
+    fn render_controls(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let time = match self.backend.duration() {
+            Some(duration) => format!("{} / {}", format_time(self.position), format_time(duration)),
+            None => format_time(self.position),
+        };
+
+        h_flex()
+            .absolute()
+            .left_0()
+            .right_0()
+            .bottom_0()
+            .gap_2()
+            .px_2()
+            .py_1()
+            .bg(gpui::black().opacity(0.5))
+            .text_color(gpui::white())
+            .text_xs()
+            .child(
+                Button::new("play")
+                    .icon(if self.is_playing() {
+                        IconName::Pause
+                    } else {
+                        IconName::Play
+                    })
+                    .ghost()
+                    .small()
+                    .text_color(gpui::white())
+                    .on_click(cx.listener(|this, _, window, cx| this.toggle_play(window, cx))),
+            )
+            .child(div().flex_shrink_0().child(time))
+            .child(
+                div()
+                    .flex_1()
+                    .when(self.backend.duration().is_some(), |this| {
+                        this.child(Slider::new(&self.progress))
+                    }),
+            )
+            .child(
+                Button::new("fullscreen")
+                    .icon(if self.fullscreen {
+                        IconName::Minimize
+                    } else {
+                        IconName::Maximize
+                    })
+                    .ghost()
+                    .small()
+                    .text_color(gpui::white())
+                    .on_click(
+                        cx.listener(|this, _, window, cx| this.toggle_fullscreen(window, cx)),
+                    ),
+            )
+    }
+}
+
+impl EventEmitter<VideoPlayerEvent> for VideoPlayerState {}
+
+impl Focusable for VideoPlayerState {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for VideoPlayerState {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let player = div()
+            .id("video")
+            .key_context(CONTEXT)
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::on_action_toggle_play))
+            .on_action(cx.listener(Self::on_action_seek_forward))
+            .on_action(cx.listener(Self::on_action_seek_backward))
+            .on_action(cx.listener(Self::on_action_toggle_fullscreen))
+            .on_action(cx.listener(Self::on_action_cancel))
+            .relative()
+            .size_full()
+            .overflow_hidden()
+            .bg(gpui::black())
+            .on_click(cx.listener(|this, _, window, cx| {
+                this.focus_handle.focus(window);
+                this.toggle_play(window, cx);
+            }))
+            .when_some(self.frame.as_ref(), |this, frame| {
+                this.child(
+                    img(ImageSource::Render(frame.image.clone()))
+                        .size_full()
+                        .object_fit(ObjectFit::Contain),
+                )
+            })
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .absolute()
+                        .top_2()
+                        .left_2()
+                        .text_sm()
+                        .text_color(cx.theme().danger)
+                        .child(error),
+                )
+            })
+            .child(
+                div()
+                    .on_mouse_down(gpui::MouseButton::Left, |_, _, cx| cx.stop_propagation())
+                    .child(self.render_controls(cx)),
+            );
+
+        if self.fullscreen {
+            let viewport = window.viewport_size();
+            div().size_full().child(
+                deferred(
+                    anchored()
+                        .position(point(px(0.), px(0.)))
+                        .child(div().w(viewport.width).h(viewport.height).child(player)),
+                )
+                .with_priority(Layer::Fullscreen.priority()),
+            )
+        } else {
+            div().size_full().child(player)
+        }
+    }
+}
+
+/// A video player with play/pause, seek and fullscreen controls.
+///
+/// The frames are decoded by the [`VideoBackend`] and presented as GPU textures,
+/// subscribe [`VideoPlayerEvent::Frame`] to handle the frames.
+///
+/// ```ignore
+/// let state = cx.new(|cx| VideoPlayerState::new(MyBackend::open("video.mp4")?, window, cx));
+///
+/// VideoPlayer::new(&state).h(px(360.))
+/// ```
+#[derive(IntoElement)]
+pub struct VideoPlayer {
+    id: ElementId,
+    state: Entity<VideoPlayerState>,
+    style: StyleRefinement,
+}
+
+impl VideoPlayer {
+    /// Create a new video player with the given state.
+    pub fn new(state: &Entity<VideoPlayerState>) -> Self {
+        Self {
+            id: ElementId::Name(format!("video-player-{}", state.entity_id()).into()),
+            state: state.clone(),
+            style: StyleRefinement::default(),
+        }
+    }
+}
+
+impl Styled for VideoPlayer {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for VideoPlayer {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        div()
+            .id(self.id)
+            .size_full()
+            .rounded(cx.theme().radius)
+            .overflow_hidden()
+            .child(self.state)
+            .refine_style(&self.style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::format_time;
+
+    #[test]
+    fn test_format_time() {
+        assert_eq!(format_time(Duration::ZERO), "0:00");
+        assert_eq!(format_time(Duration::from_millis(65_900)), "1:05");
+        assert_eq!(format_time(Duration::from_secs(3600 + 62)), "1:01:02");
+    }
+}
@@ -0,0 +1,487 @@
+use std::rc::Rc;
+
+use gpui::{
+    actions, canvas, div, point, px, AnyElement, App, Bounds, Context, ElementId, Entity,
+    EventEmitter, FocusHandle, Focusable, InteractiveElement as _, IntoElement, KeyBinding,
+    KeyDownEvent, KeyUpEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
+    ParentElement, Pixels, Point, Render, RenderOnce, ScrollWheelEvent, Size, StyleRefinement,
+    Styled, Window,
+};
+
+use crate::StyledExt as _;
+
+actions!(viewport, [ZoomIn, ZoomOut, ResetZoom, FitToContent]);
+
+const CONTEXT: &str = "Viewport";
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-=", ZoomIn, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-=", ZoomIn, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd--", ZoomOut, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl--", ZoomOut, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-0", ResetZoom, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-0", ResetZoom, Some(CONTEXT)),
+        KeyBinding::new("shift-1", FitToContent, Some(CONTEXT)),
+    ]);
+}
+
+const ZOOM_STEP: f32 = 1.2;
+
+/// Events emitted by the [`ViewportState`].
+#[derive(Debug, Clone, Copy)]
+pub enum ViewportEvent {
+    /// The zoom or the pan offset was changed.
+    Change(ViewportTransform),
+}
+
+/// The transform from the content coordinates to the viewport coordinates.
+///
+/// `viewport = offset + content * zoom`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewportTransform {
+    pub zoom: f32,
+    pub offset: Point<Pixels>,
+}
+
+impl Default for ViewportTransform {
+    fn default() -> Self {
+        Self {
+            zoom: 1.,
+            offset: Point::default(),
+        }
+    }
+}
+
+impl ViewportTransform {
+    /// Convert a point from the content coordinates to the viewport coordinates.
+    pub fn apply(&self, point: Point<Pixels>) -> Point<Pixels> {
+        self.offset + point * self.zoom
+    }
+
+    /// Convert a size from the content coordinates to the viewport coordinates.
+    pub fn apply_size(&self, size: Size<Pixels>) -> Size<Pixels> {
+        size.map(|v| v * self.zoom)
+    }
+
+    /// Convert bounds from the content coordinates to the viewport coordinates.
+    pub fn apply_bounds(&self, bounds: Bounds<Pixels>) -> Bounds<Pixels> {
+        Bounds::new(self.apply(bounds.origin), self.apply_size(bounds.size))
+    }
+
+    /// Convert a point from the viewport coordinates to the content coordinates.
+    pub fn invert(&self, point: Point<Pixels>) -> Point<Pixels> {
+        (point - self.offset) / self.zoom
+    }
+
+    /// Set the zoom, keeping the content under the `anchor` (in viewport coordinates) fixed.
+    fn zoom_at(&self, zoom: f32, anchor: Point<Pixels>) -> Self {
+        let content = self.invert(anchor);
+        Self {
+            zoom,
+            offset: anchor - content * zoom,
+        }
+    }
+
+    /// Returns the transform to fit the `content` bounds in the center of the viewport `size`.
+    fn fit(
+        content: Bounds<Pixels>,
+        size: Size<Pixels>,
+        padding: Pixels,
+        zoom_range: (f32, f32),
+    ) -> Self {
+        let available = Size::new(
+            (size.width - padding * 2.).max(px(1.)),
+            (size.height - padding * 2.).max(px(1.)),
+        );
+        let zoom = if content.size.width > px(0.) && content.size.height > px(0.) {
+            (available.width / content.size.width).min(available.height / content.size.height)
+        } else {
+            1.
+        }
+        .clamp(zoom_range.0, zoom_range.1);
+
+        let center = content.center() * zoom;
+        Self {
+            zoom,
+            offset: point(size.width / 2. - center.x, size.height / 2. - center.y),
+        }
+    }
+}
+
+/// State of the [`Viewport`].
+pub struct ViewportState {
+    focus_handle: FocusHandle,
+    transform: ViewportTransform,
+    min_zoom: f32,
+    max_zoom: f32,
+    /// The bounds of the content in the content coordinates, for fit to content.
+    content_bounds: Option<Bounds<Pixels>>,
+    /// The bounds of the viewport in the window.
+    bounds: Bounds<Pixels>,
+    space_pressed: bool,
+    /// The last mouse position when panning.
+    drag_position: Option<Point<Pixels>>,
+    render_content: Option<Rc<dyn Fn(ViewportTransform, &mut Window, &mut App) -> AnyElement>>,
+}
+
+impl ViewportState {
+    /// Create a new viewport state.
+    pub fn new(_: &mut Window, cx: &mut Context<Self>) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            transform: ViewportTransform::default(),
+            min_zoom: 0.1,
+            max_zoom: 8.,
+            content_bounds: None,
+            bounds: Bounds::default(),
+            space_pressed: false,
+            drag_position: None,
+            render_content: None,
+        }
+    }
+
+    /// Set the zoom range, default: 0.1 to 8.
+    pub fn zoom_range(mut self, min: f32, max: f32) -> Self {
+        self.min_zoom = min.min(max);
+        self.max_zoom = max.max(min);
+        self
+    }
+
+    /// Set the bounds of the content in the content coordinates, it is used to fit to content.
+    pub fn set_content_bounds(&mut self, bounds: Bounds<Pixels>, cx: &mut Context<Self>) {
+        self.content_bounds = Some(bounds);
+        cx.notify();
+    }
+
+    /// Returns the current transform.
+    pub fn transform(&self) -> ViewportTransform {
+        self.transform
+    }
+
+    /// Set the transform, the zoom will be clamped in the zoom range.
+    pub fn set_transform(&mut self, transform: ViewportTransform, cx: &mut Context<Self>) {
+        let transform = ViewportTransform {
+            zoom: transform.zoom.clamp(self.min_zoom, self.max_zoom),
+            ..transform
+        };
+        if transform == self.transform {
+            return;
+        }
+
+        self.transform = transform;
+        cx.emit(ViewportEvent::Change(transform));
+        cx.notify();
+    }
+
+    /// Returns the current zoom.
+    pub fn zoom(&self) -> f32 {
+        self.transform.zoom
+    }
+
+    /// Set the zoom, keeping the center of the viewport fixed.
+    pub fn set_zoom(&mut self, zoom: f32, cx: &mut Context<Self>) {
+        let center = point(self.bounds.size.width / 2., self.bounds.size.height / 2.);
+        self.zoom_at(zoom, center, cx);
+    }
+
+    /// Set the zoom, keeping the content under the `anchor` (in viewport coordinates) fixed.
+    pub fn zoom_at(&mut self, zoom: f32, anchor: Point<Pixels>, cx: &mut Context<Self>) {
+        let zoom = zoom.clamp(self.min_zoom, self.max_zoom);
+        self.set_transform(self.transform.zoom_at(zoom, anchor), cx);
+    }
+
+    /// Move the content by `delta`.
+    pub fn pan_by(&mut self, delta: Point<Pixels>, cx: &mut Context<Self>) {
+        self.set_transform(
+            ViewportTransform {
+                offset: self.transform.offset + delta,
+                ..self.transform
+            },
+            cx,
+        );
+    }
+
+    /// Zoom and pan to fit the content bounds in the viewport, with the `padding`.
+    pub fn fit_to_content(&mut self, padding: Pixels, cx: &mut Context<Self>) {
+        let Some(content_bounds) = self.content_bounds else {
+            return;
+        };
+
+        self.set_transform(
+            ViewportTransform::fit(
+                content_bounds,
+                self.bounds.size,
+                padding,
+                (self.min_zoom, self.max_zoom),
+            ),
+            cx,
+        );
+    }
+
+    /// Convert a position in the window to the content coordinates, e.g.: the mouse position.
+    pub fn content_position(&self, position: Point<Pixels>) -> Point<Pixels> {
+        self.transform.invert(position - self.bounds.origin)
+    }
+
+    /// Returns true if the viewport is panning.
+    pub fn is_panning(&self) -> bool {
+        self.drag_position.is_some()
+    }
+
+    /// Returns true if the `Space` is held to pan, the content should not handle the left button.
+    pub fn is_pan_key_pressed(&self) -> bool {
+        self.space_pressed
+    }
+
+    fn on_action_zoom_in(&mut self, _: &ZoomIn, _: &mut Window, cx: &mut Context<Self>) {
+        self.set_zoom(self.zoom() * ZOOM_STEP, cx);
+    }
+
+    fn on_action_zoom_out(&mut self, _: &ZoomOut, _: &mut Window, cx: &mut Context<Self>) {
+        self.set_zoom(self.zoom() / ZOOM_STEP, cx);
+    }
+
+    fn on_action_reset_zoom(&mut self, _: &ResetZoom, _: &mut Window, cx: &mut Context<Self>) {
+        self.set_zoom(1., cx);
+    }
+
+    fn on_action_fit(&mut self, _: &FitToContent, _: &mut Window, cx: &mut Context<Self>) {
+        self.fit_to_content(px(16.), cx);
+    }
+
+    fn on_key_down(&mut self, event: &KeyDownEvent, _: &mut Window, cx: &mut Context<Self>) {
+        if event.keystroke.key == "space" && !self.space_pressed {
+            self.space_pressed = true;
+            cx.notify();
+        }
+    }
+
+    fn on_key_up(&mut self, event: &KeyUpEvent, _: &mut Window, cx: &mut Context<Self>) {
+        if event.keystroke.key == "space" {
+            self.space_pressed = false;
+            cx.notify();
+        }
+    }
+
+    fn on_mouse_down(
+        &mut self,
+        event: &MouseDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.focus_handle.focus(window);
+        let pan = event.button == MouseButton::Middle
+            || (event.button == MouseButton::Left && self.space_pressed);
+        if pan {
+            self.drag_position = Some(event.position);
+            cx.stop_propagation();
+            cx.notify();
+        }
+    }
+
+    fn on_mouse_move(&mut self, event: &MouseMoveEvent, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(last_position) = self.drag_position else {
+            return;
+        };
+
+        self.drag_position = Some(event.position);
+        self.pan_by(event.position - last_position, cx);
+    }
+
+    fn on_mouse_up(&mut self, _: &MouseUpEvent, _: &mut Window, cx: &mut Context<Self>) {
+        if self.drag_position.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    fn on_scroll_wheel(
+        &mut self,
+        event: &ScrollWheelEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let delta = event.delta.pixel_delta(window.line_height());
+        if event.modifiers.secondary() {
+            // Zoom in when scrolling up, centered at the cursor.
+            let factor = (1. + f32::from(delta.y) / 200.).clamp(0.5, 2.);
+            self.zoom_at(
+                self.zoom() * factor,
+                event.position - self.bounds.origin,
+                cx,
+            );
+        } else if event.modifiers.shift && delta.x == px(0.) {
+            self.pan_by(point(delta.y, px(0.)), cx);
+        } else {
+            self.pan_by(delta, cx);
+        }
+        cx.stop_propagation();
+    }
+}
+
+impl EventEmitter<ViewportEvent> for ViewportState {}
+
+impl Focusable for ViewportState {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for ViewportState {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let state = cx.entity();
+        let content = self
+            .render_content
+            .clone()
+            .map(|render_content| render_content(self.transform, window, cx));
+
+        div()
+            .relative()
+            .size_full()
+            .overflow_hidden()
+            .on_key_down(cx.listener(Self::on_key_down))
+            .on_key_up(cx.listener(Self::on_key_up))
+            .on_mouse_down(MouseButton::Left, cx.listener(Self::on_mouse_down))
+            .on_mouse_down(MouseButton::Middle, cx.listener(Self::on_mouse_down))
+            .on_mouse_move(cx.listener(Self::on_mouse_move))
+            .on_mouse_up(MouseButton::Left, cx.listener(Self::on_mouse_up))
+            .on_mouse_up(MouseButton::Middle, cx.listener(Self::on_mouse_up))
+            .on_mouse_up_out(MouseButton::Left, cx.listener(Self::on_mouse_up))
+            .on_mouse_up_out(MouseButton::Middle, cx.listener(Self::on_mouse_up))
+            .on_scroll_wheel(cx.listener(Self::on_scroll_wheel))
+            .child(
+                canvas(
+                    move |bounds, _, cx| state.update(cx, |r, _| r.bounds = bounds),
+                    |_, _, _, _| {},
+                )
+                .absolute()
+                .size_full(),
+            )
+            .children(content)
+    }
+}
+
+/// A container to zoom and pan its content, the base for graph editors, whiteboards and image annotation.
+///
+/// - Scroll to pan, `Shift` + scroll to pan horizontally.
+/// - `Cmd/Ctrl` + scroll to zoom at the cursor.
+/// - Drag with the middle button, or hold `Space` and drag to pan.
+///
+/// The content is rendered with the current [`ViewportTransform`], use it to position the children
+/// from the content coordinates.
+///
+/// ```ignore
+/// let state = cx.new(|cx| ViewportState::new(window, cx));
+///
+/// Viewport::new(&state).content(move |transform, _, _| {
+///     let bounds = transform.apply_bounds(node.bounds);
+///     div()
+///         .absolute()
+///         .left(bounds.origin.x)
+///         .top(bounds.origin.y)
+///         .size(bounds.size)
+///         .child(node.title.clone())
+/// })
+/// ```
+#[derive(IntoElement)]
+pub struct Viewport {
+    id: ElementId,
+    state: Entity<ViewportState>,
+    style: StyleRefinement,
+    render_content: Option<Rc<dyn Fn(ViewportTransform, &mut Window, &mut App) -> AnyElement>>,
+}
+
+impl Viewport {
+    /// Create a new viewport with the given state.
+    pub fn new(state: &Entity<ViewportState>) -> Self {
+        Self {
+            id: ElementId::Name(format!("viewport-{}", state.entity_id()).into()),
+            state: state.clone(),
+            style: StyleRefinement::default(),
+            render_content: None,
+        }
+    }
+
+    /// Set the content to render with the current transform.
+    pub fn content<F, E>(mut self, f: F) -> Self
+    where
+        F: Fn(ViewportTransform, &mut Window, &mut App) -> E + 'static,
+        E: IntoElement,
+    {
+        self.render_content = Some(Rc::new(move |transform, window, cx| {
+            f(transform, window, cx).into_any_element()
+        }));
+        self
+    }
+}
+
+impl Styled for Viewport {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for Viewport {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let focus_handle = self.state.update(cx, |state, _| {
+            state.render_content = self.render_content;
+            state.focus_handle.clone()
+        });
+
+        div()
+            .id(self.id)
+            .key_context(CONTEXT)
+            .track_focus(&focus_handle)
+            .on_action(window.listener_for(&self.state, ViewportState::on_action_zoom_in))
+            .on_action(window.listener_for(&self.state, ViewportState::on_action_zoom_out))
+            .on_action(window.listener_for(&self.state, ViewportState::on_action_reset_zoom))
+            .on_action(window.listener_for(&self.state, ViewportState::on_action_fit))
+            .size_full()
+            .child(self.state)
+            .refine_style(&self.style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{point, px, size, Bounds};
+
+    use super::ViewportTransform;
+
+    #[test]
+    fn test_transform() {
+        let transform = ViewportTransform {
+            zoom: 2.,
+            offset: point(px(10.), px(20.)),
+        };
+        let p = point(px(5.), px(5.));
+        assert_eq!(transform.apply(p), point(px(20.), px(30.)));
+        assert_eq!(transform.invert(transform.apply(p)), p);
+
+        // The content under the anchor is kept.
+        let anchor = point(px(100.), px(50.));
+        let content = transform.invert(anchor);
+        let zoomed = transform.zoom_at(4., anchor);
+        assert_eq!(zoomed.zoom, 4.);
+        assert_eq!(zoomed.apply(content), anchor);
+    }
+
+    #[test]
+    fn test_fit() {
+        let content = Bounds::new(point(px(100.), px(100.)), size(px(200.), px(100.)));
+        let transform =
+            ViewportTransform::fit(content, size(px(420.), px(420.)), px(10.), (0.1, 8.));
+        assert_eq!(transform.zoom, 2.);
+        assert_eq!(transform.apply(content.center()), point(px(210.), px(210.)));
+
+        // Clamped by the zoom range.
+        let transform =
+            ViewportTransform::fit(content, size(px(4200.), px(4200.)), px(0.), (0.1, 8.));
+        assert_eq!(transform.zoom, 8.);
+    }
+}
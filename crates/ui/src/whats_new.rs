@@ -0,0 +1,279 @@
+use std::{io, rc::Rc};
+
+use gpui::{
+    div, prelude::FluentBuilder as _, px, App, IntoElement, ParentElement as _, Pixels, RenderOnce,
+    SharedString, Styled as _, Window,
+};
+use rust_i18n::t;
+
+use crate::{
+    dismissal::{dismissed, Dismissals},
+    h_flex,
+    modal::ModalButtonProps,
+    tag::Tag,
+    text::TextView,
+    v_flex, ActiveTheme as _, ContextModal as _, Sizable as _, StyledExt as _,
+};
+
+/// The release notes of a version in the [`WhatsNew`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Release {
+    version: SharedString,
+    date: Option<SharedString>,
+    notes: SharedString,
+}
+
+impl Release {
+    /// Create a release with the version and the notes in Markdown.
+    pub fn new(version: impl Into<SharedString>, notes: impl Into<SharedString>) -> Self {
+        Self {
+            version: version.into(),
+            date: None,
+            notes: notes.into(),
+        }
+    }
+
+    /// Set the release date to show next to the version, e.g.: "2025-06-01".
+    pub fn date(mut self, date: impl Into<SharedString>) -> Self {
+        self.date = Some(date.into());
+        self
+    }
+
+    /// Returns the version.
+    pub fn version(&self) -> &SharedString {
+        &self.version
+    }
+}
+
+/// The "What's New" release notes of the app, shown in a modal or embedded in a panel.
+///
+/// The releases are in the newest first order, the seen versions are remembered by
+/// the [`Dismissals`], so [`WhatsNew::show_once`] opens the modal once per version.
+///
+/// ```ignore
+/// WhatsNew::new()
+///     .release(Release::new("1.2.0", include_str!("../changelog/1.2.0.md")).date("2025-06-01"))
+///     .release(Release::new("1.1.0", include_str!("../changelog/1.1.0.md")))
+///     .show_once(window, cx);
+/// ```
+#[derive(IntoElement, Clone)]
+pub struct WhatsNew {
+    id: SharedString,
+    title: Option<SharedString>,
+    releases: Rc<[Release]>,
+    width: Pixels,
+}
+
+impl Default for WhatsNew {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WhatsNew {
+    /// Create an empty release notes.
+    pub fn new() -> Self {
+        Self {
+            id: "whats-new".into(),
+            title: None,
+            releases: Rc::new([]),
+            width: px(560.),
+        }
+    }
+
+    /// Set the id to remember the seen versions, default: `whats-new`.
+    pub fn id(mut self, id: impl Into<SharedString>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    /// Set the title of the modal, default: "What's New".
+    pub fn title(mut self, title: impl Into<SharedString>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Add a release, older than the added releases.
+    pub fn release(mut self, release: Release) -> Self {
+        self.releases = self.releases.iter().cloned().chain([release]).collect();
+        self
+    }
+
+    /// Add the releases in the newest first order.
+    pub fn releases(mut self, releases: impl IntoIterator<Item = Release>) -> Self {
+        self.releases = self.releases.iter().cloned().chain(releases).collect();
+        self
+    }
+
+    /// Set the width of the modal, default: 560px
+    pub fn width(mut self, width: impl Into<Pixels>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    /// Returns the latest version, `None` if no release.
+    pub fn latest_version(&self) -> Option<&SharedString> {
+        self.releases.first().map(|release| &release.version)
+    }
+
+    fn seen_key(&self, version: &str) -> String {
+        format!("{}:{}", self.id, version)
+    }
+
+    /// Returns the last seen version, marked by [`Self::mark_seen`].
+    pub fn last_seen_version(&self, cx: &App) -> Option<&SharedString> {
+        self.releases
+            .iter()
+            .find(|release| dismissed(&self.seen_key(&release.version), cx))
+            .map(|release| &release.version)
+    }
+
+    /// Returns the number of the releases newer than the last seen version.
+    pub fn unseen_count(&self, cx: &App) -> usize {
+        self.releases
+            .iter()
+            .position(|release| dismissed(&self.seen_key(&release.version), cx))
+            .unwrap_or(self.releases.len())
+    }
+
+    /// Mark the latest version as seen, saved by the [`Dismissals`].
+    pub fn mark_seen(&self, cx: &mut App) -> io::Result<()> {
+        match self.latest_version() {
+            Some(version) => Dismissals::dismiss(self.seen_key(version), cx),
+            None => Ok(()),
+        }
+    }
+
+    /// Open the release notes in a modal, the latest version is marked as seen on close.
+    pub fn open(self, window: &mut Window, cx: &mut App) {
+        let title = self
+            .title
+            .clone()
+            .unwrap_or_else(|| t!("WhatsNew.title").into());
+        let width = self.width;
+        // The "New" tags are kept while the modal is open.
+        let unseen_count = self.unseen_count(cx);
+
+        window.open_modal(cx, move |modal, _, _| {
+            let this = self.clone();
+            modal
+                .title(title.clone())
+                .width(width)
+                .alert()
+                .button_props(ModalButtonProps::default().ok_text(t!("WhatsNew.ok")))
+                .child(WhatsNewContent {
+                    whats_new: this.clone(),
+                    unseen_count: Some(unseen_count),
+                })
+                .on_close(move |_, _, cx| {
+                    _ = this.mark_seen(cx);
+                })
+        });
+    }
+
+    /// Open the modal if the latest version is not seen, returns true if opened.
+    pub fn show_once(self, window: &mut Window, cx: &mut App) -> bool {
+        let Some(version) = self.latest_version() else {
+            return false;
+        };
+        if dismissed(&self.seen_key(version), cx) {
+            return false;
+        }
+
+        self.open(window, cx);
+        true
+    }
+}
+
+/// The content of the [`WhatsNew`] with the unseen count kept while the modal is open.
+#[derive(IntoElement)]
+struct WhatsNewContent {
+    whats_new: WhatsNew,
+    unseen_count: Option<usize>,
+}
+
+impl RenderOnce for WhatsNewContent {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let whats_new = self.whats_new;
+        let unseen_count = self
+            .unseen_count
+            .unwrap_or_else(|| whats_new.unseen_count(cx));
+
+        v_flex()
+            .gap_6()
+            .children(whats_new.releases.iter().enumerate().map(|(ix, release)| {
+                v_flex()
+                    .gap_2()
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(
+                                div()
+                                    .text_lg()
+                                    .font_semibold()
+                                    .child(release.version.clone()),
+                            )
+                            .when(ix < unseen_count, |this| {
+                                this.child(
+                                    Tag::primary().small().child(t!("WhatsNew.new").to_string()),
+                                )
+                            })
+                            .when_some(release.date.clone(), |this, date| {
+                                this.child(
+                                    div()
+                                        .text_sm()
+                                        .text_color(cx.theme().muted_foreground)
+                                        .child(date),
+                                )
+                            }),
+                    )
+                    .child(TextView::markdown(
+                        SharedString::from(format!("{}/{}", whats_new.id, release.version)),
+                        release.notes.clone(),
+                        window,
+                        cx,
+                    ))
+            }))
+    }
+}
+
+impl RenderOnce for WhatsNew {
+    fn render(self, _: &mut Window, _: &mut App) -> impl IntoElement {
+        WhatsNewContent {
+            whats_new: self,
+            unseen_count: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Release, WhatsNew};
+
+    #[gpui::test]
+    fn test_whats_new_seen(cx: &mut gpui::TestAppContext) {
+        cx.update(|cx| {
+            crate::dismissal::init(cx);
+
+            let whats_new = WhatsNew::new()
+                .id("test-whats-new")
+                .release(Release::new("1.1.0", "- New"))
+                .release(Release::new("1.0.0", "- Initial"));
+            assert_eq!(whats_new.latest_version().unwrap(), "1.1.0");
+            assert_eq!(whats_new.last_seen_version(cx), None);
+            assert_eq!(whats_new.unseen_count(cx), 2);
+
+            whats_new.mark_seen(cx).unwrap();
+            assert_eq!(whats_new.last_seen_version(cx).unwrap(), "1.1.0");
+            assert_eq!(whats_new.unseen_count(cx), 0);
+
+            // Upgrade
+            let whats_new = WhatsNew::new()
+                .id("test-whats-new")
+                .release(Release::new("1.2.0", "- Newer"))
+                .releases(whats_new.releases.iter().cloned());
+            assert_eq!(whats_new.last_seen_version(cx).unwrap(), "1.1.0");
+            assert_eq!(whats_new.unseen_count(cx), 1);
+        });
+    }
+}
@@ -6,14 +6,13 @@ use gpui::{
     ParentElement, Pixels, Point, RenderOnce, ResizeEdge, Size, Styled as _, Window,
 };
 
-use crate::ActiveTheme;
+use crate::{window_chrome::WindowChrome, ActiveTheme, StyledExt as _};
 
 #[cfg(not(target_os = "linux"))]
 const SHADOW_SIZE: Pixels = px(0.0);
 #[cfg(target_os = "linux")]
 const SHADOW_SIZE: Pixels = px(12.0);
 const BORDER_SIZE: Pixels = px(1.0);
-pub(crate) const BORDER_RADIUS: Pixels = px(0.0);
 
 /// Create a new window border.
 pub fn window_border() -> WindowBorder {
@@ -66,6 +65,7 @@ impl ParentElement for WindowBorder {
 impl RenderOnce for WindowBorder {
     fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
         let decorations = window.window_decorations();
+        let radii = WindowChrome::corner_radii(window, cx);
         window.set_client_inset(SHADOW_SIZE);
 
         div()
@@ -114,12 +114,7 @@ impl RenderOnce for WindowBorder {
                         .size_full()
                         .absolute(),
                     )
-                    .when(!(tiling.top || tiling.right), |div| {
-                        div.rounded_tr(BORDER_RADIUS)
-                    })
-                    .when(!(tiling.top || tiling.left), |div| {
-                        div.rounded_tl(BORDER_RADIUS)
-                    })
+                    .corner_radii(radii)
                     .when(!tiling.top, |div| div.pt(SHADOW_SIZE))
                     .when(!tiling.bottom, |div| div.pb(SHADOW_SIZE))
                     .when(!tiling.left, |div| div.pl(SHADOW_SIZE))
@@ -140,12 +135,7 @@ impl RenderOnce for WindowBorder {
                     .map(|div| match decorations {
                         Decorations::Server => div,
                         Decorations::Client { tiling } => div
-                            .when(!(tiling.top || tiling.right), |div| {
-                                div.rounded_tr(BORDER_RADIUS)
-                            })
-                            .when(!(tiling.top || tiling.left), |div| {
-                                div.rounded_tl(BORDER_RADIUS)
-                            })
+                            .corner_radii(radii)
                             .border_color(cx.theme().window_border)
                             .when(!tiling.top, |div| div.border_t(BORDER_SIZE))
                             .when(!tiling.bottom, |div| div.border_b(BORDER_SIZE))
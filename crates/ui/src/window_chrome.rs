@@ -0,0 +1,97 @@
+use gpui::{
+    px, App, Corners, Decorations, Pixels, Tiling, Window, WindowBackgroundAppearance,
+    WindowDecorations, WindowOptions,
+};
+
+use crate::{backdrop::is_backdrop_blur_supported, ActiveTheme, TitleBar};
+
+/// The platform window chrome coordinated with the theme, to make the frameless app look native.
+///
+/// - On macOS, the transparent title bar with the traffic lights, and the vibrancy by the
+///   blurred window background when the [`Theme::backdrop_blur`](crate::Theme::backdrop_blur) is enabled.
+/// - On Windows, the Acrylic by the blurred window background, GPUI doesn't support the Mica yet.
+/// - On Linux, the client side decorations with the rounded corners of the
+///   [`Theme::window_radius`](crate::Theme::window_radius), and the border of the `window.border` color.
+///
+/// The window background is kept in sync with the theme by the [`Root`](crate::Root).
+///
+/// ```ignore
+/// cx.open_window(
+///     WindowOptions {
+///         window_bounds: Some(WindowBounds::Windowed(bounds)),
+///         ..WindowChrome::window_options(cx)
+///     },
+///     |window, cx| cx.new(|cx| Root::new(view.into(), window, cx)),
+/// )
+/// ```
+pub struct WindowChrome;
+
+impl WindowChrome {
+    /// Returns the [`WindowOptions`] of the window chrome for the current theme,
+    /// use it with the [`TitleBar`].
+    pub fn window_options(cx: &App) -> WindowOptions {
+        WindowOptions {
+            titlebar: Some(TitleBar::title_bar_options()),
+            window_background: Self::window_background(cx),
+            window_decorations: cfg!(target_os = "linux").then_some(WindowDecorations::Client),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the window background appearance for the current theme.
+    pub fn window_background(cx: &App) -> WindowBackgroundAppearance {
+        if cx.theme().backdrop_blur && is_backdrop_blur_supported() {
+            WindowBackgroundAppearance::Blurred
+        } else if cfg!(target_os = "linux") {
+            // Transparent for the shadow and rounded corners of the client decorations.
+            WindowBackgroundAppearance::Transparent
+        } else {
+            WindowBackgroundAppearance::Opaque
+        }
+    }
+
+    /// Returns the corner radii of the window, they are only rounded with the client decorations.
+    pub fn corner_radii(window: &Window, cx: &App) -> Corners<Pixels> {
+        match window.window_decorations() {
+            Decorations::Server => Corners::all(px(0.)),
+            Decorations::Client { tiling } => corner_radii(tiling, cx.theme().window_radius),
+        }
+    }
+}
+
+/// The corners next to the tiled edges are not rounded.
+fn corner_radii(tiling: Tiling, radius: Pixels) -> Corners<Pixels> {
+    let radius_of = |tiled: bool| if tiled { px(0.) } else { radius };
+    Corners {
+        top_left: radius_of(tiling.top || tiling.left),
+        top_right: radius_of(tiling.top || tiling.right),
+        bottom_left: radius_of(tiling.bottom || tiling.left),
+        bottom_right: radius_of(tiling.bottom || tiling.right),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{px, Tiling};
+
+    use super::corner_radii;
+
+    #[test]
+    fn test_corner_radii() {
+        let radii = corner_radii(Tiling::default(), px(10.));
+        assert_eq!(radii.top_left, px(10.));
+        assert_eq!(radii.bottom_right, px(10.));
+
+        let radii = corner_radii(
+            Tiling {
+                left: true,
+                ..Default::default()
+            },
+            px(10.),
+        );
+        assert_eq!(radii.top_left, px(0.));
+        assert_eq!(radii.bottom_left, px(0.));
+        assert_eq!(radii.top_right, px(10.));
+        assert_eq!(radii.bottom_right, px(10.));
+    }
+}